@@ -1,8 +1,20 @@
-use schemars::schema_for;
+use clap::Parser;
 
-use php_parser_rs::parser::ast::Program;
+#[derive(Parser, Default, Debug)]
+#[clap(about = "Print the shape of the serialized AST")]
+struct Arguments {
+    #[clap(short, long)]
+    /// Print TypeScript declarations instead of a JSON Schema
+    typescript: bool,
+}
 
 fn main() {
-    let schema = schema_for!(Program);
-    println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+    let args = Arguments::parse();
+
+    if args.typescript {
+        print!("{}", php_parser_rs::schema::typescript());
+    } else {
+        let schema = php_parser_rs::schema::json_schema();
+        println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+    }
 }
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Any panic is a bug, and so is a stack overflow in the optimized build of the fuzzer, as the
+// nesting depth is limited, see the crate documentation.
+fuzz_target!(|input: &[u8]| {
+    let options = php_parser_rs::ParserOptions::untrusted();
+
+    let _ = php_parser_rs::parse_untrusted(input);
+    let _ = php_parser_rs::parse_tolerant_with_options(input, &options);
+});
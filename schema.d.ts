@@ -0,0 +1,2344 @@
+// This file is generated, do not edit.
+
+/**
+ * A serialized program, along with the version of its shape.
+ */
+export type SerializedProgram = {
+  program: Statement[];
+  /**
+   * The version of the shape of the program.
+   */
+  schema_version: 3;
+};
+
+export type Program = Statement[];
+
+export type AbstractConstructor = {
+  ampersand?: Span | null;
+  attributes: AttributeGroup[];
+  comments: CommentGroup;
+  function: Span;
+  modifiers: MethodModifier[];
+  name: SimpleIdentifier;
+  parameters: FunctionParameterList;
+  semicolon: Span;
+};
+
+export type AbstractMethod = {
+  ampersand?: Span | null;
+  attributes: AttributeGroup[];
+  comments: CommentGroup;
+  function: Span;
+  modifiers: MethodModifier[];
+  name: SimpleIdentifier;
+  parameters: FunctionParameterList;
+  return_type?: ReturnType | null;
+  semicolon: Span;
+};
+
+export type AnonymousClass = {
+  attributes: AttributeGroup[];
+  body: AnonymousClassBody;
+  class: Span;
+  extends?: ClassExtends | null;
+  implements?: ClassImplements | null;
+  readonly?: Span | null;
+};
+
+export type AnonymousClassBody = {
+  left_brace: Span;
+  members: AnonymousClassMember[];
+  right_brace: Span;
+};
+
+export type AnonymousClassMember = {
+  type: "constant";
+  value: ClassishConstant;
+} | {
+  type: "trait_usage";
+  value: TraitUsage;
+} | {
+  type: "property";
+  value: Property;
+} | {
+  type: "variable_property";
+  value: VariableProperty;
+} | {
+  type: "concrete_method";
+  value: ConcreteMethod;
+} | {
+  type: "concrete_constructor";
+  value: ConcreteConstructor;
+};
+
+export type Argument = {
+  type: "positional";
+  value: {
+    comments: CommentGroup;
+    ellipsis?: Span | null;
+    end: Span;
+    start: Span;
+    value: Expression;
+  };
+} | {
+  type: "named";
+  value: {
+    colon: Span;
+    comments: CommentGroup;
+    ellipsis?: Span | null;
+    end: Span;
+    name: SimpleIdentifier;
+    start: Span;
+    value: Expression;
+  };
+};
+
+export type ArgumentList = {
+  arguments: Argument[];
+  comments: CommentGroup;
+  left_parenthesis: Span;
+  right_parenthesis: Span;
+};
+
+export type ArgumentPlaceholder = {
+  comments: CommentGroup;
+  ellipsis: Span;
+  left_parenthesis: Span;
+  right_parenthesis: Span;
+};
+
+export type ArithmeticOperation = {
+  type: "addition";
+  value: {
+    left: Expression;
+    plus: Span;
+    right: Expression;
+  };
+} | {
+  type: "subtraction";
+  value: {
+    left: Expression;
+    minus: Span;
+    right: Expression;
+  };
+} | {
+  type: "multiplication";
+  value: {
+    asterisk: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "division";
+  value: {
+    left: Expression;
+    right: Expression;
+    slash: Span;
+  };
+} | {
+  type: "modulo";
+  value: {
+    left: Expression;
+    percent: Span;
+    right: Expression;
+  };
+} | {
+  type: "exponentiation";
+  value: {
+    left: Expression;
+    pow: Span;
+    right: Expression;
+  };
+} | {
+  type: "negative";
+  value: {
+    minus: Span;
+    right: Expression;
+  };
+} | {
+  type: "positive";
+  value: {
+    plus: Span;
+    right: Expression;
+  };
+} | {
+  type: "pre_increment";
+  value: {
+    increment: Span;
+    right: Expression;
+  };
+} | {
+  type: "post_increment";
+  value: {
+    increment: Span;
+    left: Expression;
+  };
+} | {
+  type: "pre_decrement";
+  value: {
+    decrement: Span;
+    right: Expression;
+  };
+} | {
+  type: "post_decrement";
+  value: {
+    decrement: Span;
+    left: Expression;
+  };
+};
+
+/**
+ * `array(1, 2, 3)`
+ */
+export type ArrayExpression = {
+  array: Span;
+  end: Span;
+  items: CommaSeparated_for_ArrayItem;
+  start: Span;
+};
+
+export type ArrayItem = {
+  type: "skipped";
+} | {
+  type: "value";
+  value: {
+    value: Expression;
+  };
+} | {
+  type: "referenced_value";
+  value: {
+    ampersand: Span;
+    value: Expression;
+  };
+} | {
+  type: "spread_value";
+  value: {
+    ellipsis: Span;
+    value: Expression;
+  };
+} | {
+  type: "key_value";
+  value: {
+    double_arrow: Span;
+    key: Expression;
+    value: Expression;
+  };
+} | {
+  type: "referenced_key_value";
+  value: {
+    ampersand: Span;
+    double_arrow: Span;
+    key: Expression;
+    value: Expression;
+  };
+};
+
+export type ArrowFunction = {
+  ampersand?: Span | null;
+  attributes: AttributeGroup[];
+  body: Expression;
+  comments: CommentGroup;
+  double_arrow: Span;
+  fn: Span;
+  parameters: FunctionParameterList;
+  return_type?: ReturnType | null;
+  static?: Span | null;
+};
+
+export type AssignmentOperation = {
+  type: "assign";
+  value: {
+    equals: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "assign_by_reference";
+  value: {
+    ampersand: Span;
+    equals: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "addition";
+  value: {
+    left: Expression;
+    plus_equals: Span;
+    right: Expression;
+  };
+} | {
+  type: "subtraction";
+  value: {
+    left: Expression;
+    minus_equals: Span;
+    right: Expression;
+  };
+} | {
+  type: "multiplication";
+  value: {
+    asterisk_equals: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "division";
+  value: {
+    left: Expression;
+    right: Expression;
+    slash_equals: Span;
+  };
+} | {
+  type: "modulo";
+  value: {
+    left: Expression;
+    percent_equals: Span;
+    right: Expression;
+  };
+} | {
+  type: "exponentiation";
+  value: {
+    left: Expression;
+    pow_equals: Span;
+    right: Expression;
+  };
+} | {
+  type: "concat";
+  value: {
+    dot_equals: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "bitwise_and";
+  value: {
+    ampersand_equals: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "bitwise_or";
+  value: {
+    left: Expression;
+    pipe_equals: Span;
+    right: Expression;
+  };
+} | {
+  type: "bitwise_xor";
+  value: {
+    caret_equals: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "left_shift";
+  value: {
+    left: Expression;
+    left_shift_equals: Span;
+    right: Expression;
+  };
+} | {
+  type: "right_shift";
+  value: {
+    left: Expression;
+    right: Expression;
+    right_shift_equals: Span;
+  };
+} | {
+  type: "coalesce";
+  value: {
+    coalesce_equals: Span;
+    left: Expression;
+    right: Expression;
+  };
+};
+
+export type Attribute = {
+  arguments?: ArgumentList | null;
+  end: Span;
+  name: SimpleIdentifier;
+  start: Span;
+};
+
+export type AttributeGroup = {
+  end: Span;
+  members: Attribute[];
+  start: Span;
+};
+
+export type BackedEnumBody = {
+  left_brace: Span;
+  members: BackedEnumMember[];
+  right_brace: Span;
+};
+
+export type BackedEnumCase = {
+  attributes: AttributeGroup[];
+  case: Span;
+  comments: CommentGroup;
+  equals: Span;
+  name: SimpleIdentifier;
+  semicolon: Span;
+  value: Expression;
+};
+
+export type BackedEnumMember = {
+  type: "case";
+  value: BackedEnumCase;
+} | {
+  type: "method";
+  value: ConcreteMethod;
+} | {
+  type: "constant";
+  value: ClassishConstant;
+};
+
+export type BackedEnumStatement = {
+  attributes: AttributeGroup[];
+  backed_type: BackedEnumType;
+  body: BackedEnumBody;
+  comments: CommentGroup;
+  enum: Span;
+  implements: SimpleIdentifier[];
+  name: SimpleIdentifier;
+};
+
+export type BackedEnumType = {
+  type: "String";
+  value: [Span, Span];
+} | {
+  type: "Int";
+  value: [Span, Span];
+};
+
+export type BitwiseOperation = {
+  type: "and";
+  value: {
+    and: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "or";
+  value: {
+    left: Expression;
+    or: Span;
+    right: Expression;
+  };
+} | {
+  type: "xor";
+  value: {
+    left: Expression;
+    right: Expression;
+    xor: Span;
+  };
+} | {
+  type: "left_shift";
+  value: {
+    left: Expression;
+    left_shift: Span;
+    right: Expression;
+  };
+} | {
+  type: "right_shift";
+  value: {
+    left: Expression;
+    right: Expression;
+    right_shift: Span;
+  };
+} | {
+  type: "not";
+  value: {
+    not: Span;
+    right: Expression;
+  };
+};
+
+export type BlockStatement = {
+  left_brace: Span;
+  right_brace: Span;
+  statements: Statement[];
+};
+
+export type BracedNamespace = {
+  body: BracedNamespaceBody;
+  name?: SimpleIdentifier | null;
+  namespace: Span;
+};
+
+export type BracedNamespaceBody = {
+  end: Span;
+  start: Span;
+  statements: Statement[];
+};
+
+export type BracedVariableVariable = {
+  end: Span;
+  start: Span;
+  variable: Expression;
+};
+
+export type BreakStatement = {
+  break: Span;
+  ending: Ending;
+  level?: Level | null;
+};
+
+export type ByteString = string | number[];
+
+export type Case = {
+  body: Statement[];
+  condition?: Expression | null;
+};
+
+export type CastKind = {
+  type: "int";
+} | {
+  type: "bool";
+} | {
+  type: "float";
+} | {
+  type: "string";
+} | {
+  type: "array";
+} | {
+  type: "object";
+} | {
+  type: "unset";
+};
+
+export type CatchBlock = {
+  body: Statement[];
+  end: Span;
+  start: Span;
+  types: CatchType;
+  var?: SimpleVariable | null;
+};
+
+export type CatchType = {
+  type: "identifier";
+  value: SimpleIdentifier;
+} | {
+  type: "union";
+  value: SimpleIdentifier[];
+};
+
+export type ClassBody = {
+  left_brace: Span;
+  members: ClassMember[];
+  right_brace: Span;
+};
+
+export type ClassExtends = {
+  extends: Span;
+  parent: SimpleIdentifier;
+};
+
+export type ClassImplements = {
+  implements: Span;
+  interfaces: CommaSeparated_for_SimpleIdentifier;
+};
+
+export type ClassMember = {
+  type: "constant";
+  value: ClassishConstant;
+} | {
+  type: "trait_usage";
+  value: TraitUsage;
+} | {
+  type: "property";
+  value: Property;
+} | {
+  type: "variable_property";
+  value: VariableProperty;
+} | {
+  type: "abstract_method";
+  value: AbstractMethod;
+} | {
+  type: "abstract_constructor";
+  value: AbstractConstructor;
+} | {
+  type: "concrete_method";
+  value: ConcreteMethod;
+} | {
+  type: "concrete_constructor";
+  value: ConcreteConstructor;
+};
+
+export type ClassModifier = {
+  type: "final";
+  value: Span;
+} | {
+  type: "abstract";
+  value: Span;
+} | {
+  type: "readonly";
+  value: Span;
+};
+
+export type ClassStatement = {
+  attributes: AttributeGroup[];
+  body: ClassBody;
+  class: Span;
+  comments: CommentGroup;
+  extends?: ClassExtends | null;
+  implements?: ClassImplements | null;
+  modifiers: ClassModifier[];
+  name: SimpleIdentifier;
+};
+
+export type ClassishConstant = {
+  attributes: AttributeGroup[];
+  comments: CommentGroup;
+  const: Span;
+  data_type?: Type | null;
+  entries: ConstantEntry[];
+  modifiers: ConstantModifierGroup;
+  semicolon: Span;
+};
+
+export type Closure = {
+  ampersand?: Span | null;
+  attributes: AttributeGroup[];
+  body: FunctionBody;
+  comments: CommentGroup;
+  function: Span;
+  parameters: FunctionParameterList;
+  return_type?: ReturnType | null;
+  static?: Span | null;
+  uses?: ClosureUse | null;
+};
+
+export type ClosureUse = {
+  comments: CommentGroup;
+  left_parenthesis: Span;
+  right_parenthesis: Span;
+  use: Span;
+  variables: CommaSeparated_for_ClosureUseVariable;
+};
+
+export type ClosureUseVariable = {
+  ampersand?: Span | null;
+  comments: CommentGroup;
+  variable: SimpleVariable;
+};
+
+export type CommaSeparated_for_ArrayItem = {
+  commas: Span[];
+  inner: ArrayItem[];
+};
+
+export type CommaSeparated_for_ClosureUseVariable = {
+  commas: Span[];
+  inner: ClosureUseVariable[];
+};
+
+export type CommaSeparated_for_ConstructorParameter = {
+  commas: Span[];
+  inner: ConstructorParameter[];
+};
+
+export type CommaSeparated_for_Expression = {
+  commas: Span[];
+  inner: Expression[];
+};
+
+export type CommaSeparated_for_FunctionParameter = {
+  commas: Span[];
+  inner: FunctionParameter[];
+};
+
+export type CommaSeparated_for_SimpleIdentifier = {
+  commas: Span[];
+  inner: SimpleIdentifier[];
+};
+
+export type Comment = {
+  content: ByteString;
+  format: CommentFormat;
+  span: Span;
+};
+
+export type CommentFormat = {
+  type: "single_line";
+} | {
+  type: "multi_line";
+} | {
+  type: "hash_mark";
+} | {
+  type: "document";
+};
+
+export type CommentGroup = {
+  comments: Comment[];
+};
+
+export type ComparisonOperation = {
+  type: "equal";
+  value: {
+    double_equals: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "identical";
+  value: {
+    left: Expression;
+    right: Expression;
+    triple_equals: Span;
+  };
+} | {
+  type: "not_equal";
+  value: {
+    bang_equals: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "angled_not_equal";
+  value: {
+    angled_left_right: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "not_identical";
+  value: {
+    bang_double_equals: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "less_than";
+  value: {
+    left: Expression;
+    less_than: Span;
+    right: Expression;
+  };
+} | {
+  type: "greater_than";
+  value: {
+    greater_than: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "less_than_or_equal";
+  value: {
+    left: Expression;
+    less_than_equals: Span;
+    right: Expression;
+  };
+} | {
+  type: "greater_than_or_equal";
+  value: {
+    greater_than_equals: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "spaceship";
+  value: {
+    left: Expression;
+    right: Expression;
+    spaceship: Span;
+  };
+};
+
+export type ConcreteConstructor = {
+  ampersand?: Span | null;
+  attributes: AttributeGroup[];
+  body: MethodBody;
+  comments: CommentGroup;
+  function: Span;
+  modifiers: MethodModifier[];
+  name: SimpleIdentifier;
+  parameters: ConstructorParameterList;
+};
+
+export type ConcreteMethod = {
+  ampersand?: Span | null;
+  attributes: AttributeGroup[];
+  body: MethodBody;
+  comments: CommentGroup;
+  function: Span;
+  modifiers: MethodModifier[];
+  name: SimpleIdentifier;
+  parameters: FunctionParameterList;
+  return_type?: ReturnType | null;
+};
+
+export type ConstantEntry = {
+  equals: Span;
+  name: SimpleIdentifier;
+  value: Expression;
+};
+
+export type ConstantModifier = {
+  type: "final";
+  value: Span;
+} | {
+  type: "public";
+  value: Span;
+} | {
+  type: "protected";
+  value: Span;
+} | {
+  type: "private";
+  value: Span;
+};
+
+export type ConstantModifierGroup = {
+  modifiers: ConstantModifier[];
+};
+
+export type ConstantStatement = {
+  comments: CommentGroup;
+  const: Span;
+  entries: ConstantEntry[];
+  semicolon: Span;
+};
+
+export type ConstructorParameter = {
+  ampersand?: Span | null;
+  attributes: AttributeGroup[];
+  comments: CommentGroup;
+  data_type?: Type | null;
+  default?: Expression | null;
+  ellipsis?: Span | null;
+  modifiers: PromotedPropertyModifier[];
+  name: SimpleVariable;
+};
+
+export type ConstructorParameterList = {
+  comments: CommentGroup;
+  left_parenthesis: Span;
+  parameters: CommaSeparated_for_ConstructorParameter;
+  right_parenthesis: Span;
+};
+
+export type ContinueStatement = {
+  continue: Span;
+  ending: Ending;
+  level?: Level | null;
+};
+
+export type DeclareBody = {
+  noop: {
+    semicolon: Span;
+  };
+} | {
+  braced: {
+    left_brace: Span;
+    right_brace: Span;
+    statements: Statement[];
+  };
+} | {
+  expression: {
+    expression: Expression;
+    semicolon: Span;
+  };
+} | {
+  block: {
+    colon: Span;
+    end: [Span, Span];
+    statements: Statement[];
+  };
+};
+
+export type DeclareEntry = {
+  equals: Span;
+  key: SimpleIdentifier;
+  value: Literal;
+};
+
+export type DeclareEntryGroup = {
+  entries: DeclareEntry[];
+  left_parenthesis: Span;
+  right_parenthesis: Span;
+};
+
+export type DeclareStatement = {
+  body: DeclareBody;
+  declare: Span;
+  entries: DeclareEntryGroup;
+};
+
+export type DefaultMatchArm = {
+  body: Expression;
+  double_arrow: Span;
+  keyword: Span;
+};
+
+export type DoWhileStatement = {
+  body: Statement;
+  condition: Expression;
+  do: Span;
+  left_parenthesis: Span;
+  right_parenthesis: Span;
+  semicolon: Span;
+  while: Span;
+};
+
+export type DocStringIndentationKind = "space" | "tab" | "none" | "both";
+
+export type DocStringKind = "heredoc" | "nowdoc";
+
+export type DynamicIdentifier = {
+  end: Span;
+  expr: Expression;
+  start: Span;
+};
+
+export type EchoStatement = {
+  echo: Span;
+  ending: Ending;
+  values: Expression[];
+};
+
+export type Ending = {
+  type: "semicolon";
+  value: Span;
+} | {
+  type: "close_tag";
+  value: Span;
+};
+
+/**
+ * A statement which failed to parse, holding the tokens skipped while recovering from the error, see [`crate::parser::parse_tolerant`].
+ */
+export type ErrorStatement = {
+  span: Span;
+  tokens: Token[];
+};
+
+/**
+ * `exit` or `die`, which behave like a function taking an optional status since PHP 8.4.
+ */
+export type ExitExpression = {
+  arguments?: ArgumentList | null;
+  keyword: Span;
+  kind: ExitKind;
+};
+
+export type ExitKind = "exit" | "die";
+
+export type Expression = {
+  type: "eval";
+  value: {
+    argument: SingleArgument;
+    eval: Span;
+  };
+} | {
+  type: "empty";
+  value: {
+    argument: SingleArgument;
+    empty: Span;
+  };
+} | {
+  type: "exit";
+  value: ExitExpression;
+} | {
+  type: "isset";
+  value: {
+    arguments: ArgumentList;
+    isset: Span;
+  };
+} | {
+  type: "unset";
+  value: {
+    arguments: ArgumentList;
+    unset: Span;
+  };
+} | {
+  type: "print";
+  value: {
+    argument?: SingleArgument | null;
+    print: Span;
+    value?: Expression | null;
+  };
+} | {
+  type: "literal";
+  value: Literal;
+} | {
+  type: "arithmetic_operation";
+  value: ArithmeticOperation;
+} | {
+  type: "assignment_operation";
+  value: AssignmentOperation;
+} | {
+  type: "bitwise_operation";
+  value: BitwiseOperation;
+} | {
+  type: "comparison_operation";
+  value: ComparisonOperation;
+} | {
+  type: "logical_operation";
+  value: LogicalOperation;
+} | {
+  type: "concat";
+  value: {
+    dot: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "instanceof";
+  value: {
+    instanceof: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "pipe";
+  value: {
+    left: Expression;
+    pipe_greater_than: Span;
+    right: Expression;
+  };
+} | {
+  type: "parenthesized";
+  value: {
+    end: Span;
+    expr: Expression;
+    start: Span;
+  };
+} | {
+  type: "error_suppress";
+  value: {
+    at: Span;
+    expr: Expression;
+  };
+} | {
+  type: "identifier";
+  value: Identifier;
+} | {
+  type: "variable";
+  value: Variable;
+} | {
+  type: "include";
+  value: {
+    include: Span;
+    path: Expression;
+  };
+} | {
+  type: "include_once";
+  value: {
+    include_once: Span;
+    path: Expression;
+  };
+} | {
+  type: "require";
+  value: {
+    path: Expression;
+    require: Span;
+  };
+} | {
+  type: "require_once";
+  value: {
+    path: Expression;
+    require_once: Span;
+  };
+} | {
+  type: "function_call";
+  value: {
+    arguments: ArgumentList;
+    target: Expression;
+  };
+} | {
+  type: "function_closure_creation";
+  value: {
+    placeholder: ArgumentPlaceholder;
+    target: Expression;
+  };
+} | {
+  type: "method_call";
+  value: {
+    arguments: ArgumentList;
+    arrow: Span;
+    method: Expression;
+    target: Expression;
+  };
+} | {
+  type: "method_closure_creation";
+  value: {
+    arrow: Span;
+    method: Expression;
+    placeholder: ArgumentPlaceholder;
+    target: Expression;
+  };
+} | {
+  type: "nullsafe_method_call";
+  value: {
+    arguments: ArgumentList;
+    method: Expression;
+    question_arrow: Span;
+    target: Expression;
+  };
+} | {
+  type: "static_method_call";
+  value: {
+    arguments: ArgumentList;
+    double_colon: Span;
+    method: Identifier;
+    target: Expression;
+  };
+} | {
+  type: "static_variable_method_call";
+  value: {
+    arguments: ArgumentList;
+    double_colon: Span;
+    method: Variable;
+    target: Expression;
+  };
+} | {
+  type: "static_method_closure_creation";
+  value: {
+    double_colon: Span;
+    method: Identifier;
+    placeholder: ArgumentPlaceholder;
+    target: Expression;
+  };
+} | {
+  type: "static_variable_method_closure_creation";
+  value: {
+    double_colon: Span;
+    method: Variable;
+    placeholder: ArgumentPlaceholder;
+    target: Expression;
+  };
+} | {
+  type: "property_fetch";
+  value: {
+    arrow: Span;
+    property: Expression;
+    target: Expression;
+  };
+} | {
+  type: "nullsafe_property_fetch";
+  value: {
+    property: Expression;
+    question_arrow: Span;
+    target: Expression;
+  };
+} | {
+  type: "static_property_fetch";
+  value: {
+    double_colon: Span;
+    property: Variable;
+    target: Expression;
+  };
+} | {
+  type: "constant_fetch";
+  value: {
+    constant: Identifier;
+    double_colon: Span;
+    target: Expression;
+  };
+} | {
+  type: "static";
+  value: {
+    span: Span;
+  };
+} | {
+  type: "self_";
+  value: {
+    span: Span;
+  };
+} | {
+  type: "parent";
+  value: {
+    span: Span;
+  };
+} | {
+  type: "short_array";
+  value: {
+    end: Span;
+    items: CommaSeparated_for_ArrayItem;
+    start: Span;
+  };
+} | {
+  type: "array";
+  value: ArrayExpression;
+} | {
+  type: "list";
+  value: ListExpression;
+} | {
+  type: "closure";
+  value: Closure;
+} | {
+  type: "arrow_function";
+  value: ArrowFunction;
+} | {
+  type: "new";
+  value: {
+    arguments?: ArgumentList | null;
+    new: Span;
+    target: Expression;
+  };
+} | {
+  type: "interpolated_string";
+  value: {
+    end: Span;
+    parts: StringPart[];
+    start: Span;
+  };
+} | {
+  type: "heredoc";
+  value: {
+    end: Span;
+    parts: StringPart[];
+    start: Span;
+  };
+} | {
+  type: "nowdoc";
+  value: {
+    end: Span;
+    parts: StringPart[];
+    start: Span;
+  };
+} | {
+  type: "shell_exec";
+  value: {
+    end: Span;
+    parts: StringPart[];
+    start: Span;
+  };
+} | {
+  type: "anonymous_class";
+  value: AnonymousClass;
+} | {
+  type: "bool";
+  value: {
+    span: Span;
+    value: boolean;
+  };
+} | {
+  type: "array_index";
+  value: {
+    array: Expression;
+    index?: Expression | null;
+    left_bracket: Span;
+    right_bracket: Span;
+  };
+} | {
+  type: "null";
+  value: {
+    span: Span;
+  };
+} | {
+  type: "magic_constant";
+  value: MagicConstant;
+} | {
+  type: "short_ternary";
+  value: {
+    condition: Expression;
+    else: Expression;
+    question_colon: Span;
+  };
+} | {
+  type: "ternary";
+  value: {
+    colon: Span;
+    condition: Expression;
+    else: Expression;
+    question: Span;
+    then: Expression;
+  };
+} | {
+  type: "coalesce";
+  value: {
+    double_question: Span;
+    lhs: Expression;
+    rhs: Expression;
+  };
+} | {
+  type: "clone";
+  value: {
+    clone: Span;
+    target: Expression;
+  };
+} | {
+  type: "match";
+  value: MatchExpression;
+} | {
+  type: "throw";
+  value: {
+    throw: Span;
+    value: Expression;
+  };
+} | {
+  type: "yield";
+  value: {
+    key?: Expression | null;
+    value?: Expression | null;
+    yield: Span;
+  };
+} | {
+  type: "yield_from";
+  value: {
+    from: Span;
+    value: Expression;
+    yield: Span;
+  };
+} | {
+  type: "cast";
+  value: {
+    cast: Span;
+    kind: CastKind;
+    length: number;
+    value: Expression;
+  };
+} | {
+  type: "noop";
+  value: {
+    span: Span;
+  };
+} | {
+  type: "missing";
+  value: {
+    span: Span;
+  };
+};
+
+export type ExpressionStatement = {
+  ending: Ending;
+  expression: Expression;
+};
+
+export type ExpressionStringPart = {
+  end: Span;
+  expression: Expression;
+  start: Span;
+  syntax: InterpolationSyntax;
+};
+
+export type FinallyBlock = {
+  body: Statement[];
+  end: Span;
+  start: Span;
+};
+
+export type ForStatement = {
+  body: ForStatementBody;
+  for: Span;
+  iterator: ForStatementIterator;
+  left_parenthesis: Span;
+  right_parenthesis: Span;
+};
+
+export type ForStatementBody = {
+  type: "statement";
+  value: Statement;
+} | {
+  type: "block";
+  value: {
+    colon: Span;
+    endfor: Span;
+    ending: Ending;
+    statements: Statement[];
+  };
+};
+
+export type ForStatementIterator = {
+  conditions: CommaSeparated_for_Expression;
+  conditions_semicolon: Span;
+  initializations: CommaSeparated_for_Expression;
+  initializations_semicolon: Span;
+  loop: CommaSeparated_for_Expression;
+};
+
+export type ForeachStatement = {
+  body: ForeachStatementBody;
+  foreach: Span;
+  iterator: ForeachStatementIterator;
+  left_parenthesis: Span;
+  right_parenthesis: Span;
+};
+
+export type ForeachStatementBody = {
+  type: "statement";
+  value: Statement;
+} | {
+  type: "block";
+  value: {
+    colon: Span;
+    endforeach: Span;
+    ending: Ending;
+    statements: Statement[];
+  };
+};
+
+export type ForeachStatementIterator = {
+  value: {
+    ampersand?: Span | null;
+    as: Span;
+    expression: Expression;
+    value: Expression;
+  };
+} | {
+  key_and_value: {
+    ampersand?: Span | null;
+    as: Span;
+    double_arrow: Span;
+    expression: Expression;
+    key: Expression;
+    value: Expression;
+  };
+};
+
+/**
+ * A region of a template delimited by foreign markers, e.g. `{{ $name }}`, which is kept as is instead of being parsed.
+ */
+export type ForeignChunk = {
+  span: Span;
+  value: ByteString;
+};
+
+export type FunctionBody = {
+  comments: CommentGroup;
+  left_brace: Span;
+  right_brace: Span;
+  statements: Statement[];
+};
+
+export type FunctionParameter = {
+  ampersand?: Span | null;
+  attributes: AttributeGroup[];
+  comments: CommentGroup;
+  data_type?: Type | null;
+  default?: Expression | null;
+  ellipsis?: Span | null;
+  name: SimpleVariable;
+};
+
+export type FunctionParameterList = {
+  comments: CommentGroup;
+  left_parenthesis: Span;
+  parameters: CommaSeparated_for_FunctionParameter;
+  right_parenthesis: Span;
+};
+
+export type FunctionStatement = {
+  ampersand?: Span | null;
+  attributes: AttributeGroup[];
+  body: FunctionBody;
+  comments: CommentGroup;
+  function: Span;
+  name: SimpleIdentifier;
+  parameters: FunctionParameterList;
+  return_type?: ReturnType | null;
+};
+
+export type GlobalStatement = {
+  global: Span;
+  variables: Variable[];
+};
+
+export type GotoStatement = {
+  comments: CommentGroup;
+  keyword: Span;
+  label: SimpleIdentifier;
+  semicolon: Span;
+};
+
+export type GroupUseStatement = {
+  kind: UseKind;
+  prefix: SimpleIdentifier;
+  uses: Use[];
+};
+
+export type HaltCompiler = {
+  content?: ByteString | null;
+};
+
+export type Identifier = {
+  type: "simple_identifier";
+  value: SimpleIdentifier;
+} | {
+  type: "dynamic_identifier";
+  value: DynamicIdentifier;
+};
+
+export type IfStatement = {
+  body: IfStatementBody;
+  condition: Expression;
+  if: Span;
+  left_parenthesis: Span;
+  right_parenthesis: Span;
+};
+
+export type IfStatementBody = {
+  type: "statement";
+  value: {
+    else?: IfStatementElse | null;
+    elseifs: IfStatementElseIf[];
+    statement: Statement;
+  };
+} | {
+  type: "block";
+  value: {
+    colon: Span;
+    else?: IfStatementElseBlock | null;
+    elseifs: IfStatementElseIfBlock[];
+    endif: Span;
+    ending: Ending;
+    statements: Statement[];
+  };
+};
+
+export type IfStatementElse = {
+  else: Span;
+  statement: Statement;
+};
+
+export type IfStatementElseBlock = {
+  colon: Span;
+  else: Span;
+  statements: Statement[];
+};
+
+export type IfStatementElseIf = {
+  condition: Expression;
+  elseif: Span;
+  left_parenthesis: Span;
+  right_parenthesis: Span;
+  statement: Statement;
+};
+
+export type IfStatementElseIfBlock = {
+  colon: Span;
+  condition: Expression;
+  elseif: Span;
+  left_parenthesis: Span;
+  right_parenthesis: Span;
+  statements: Statement[];
+};
+
+export type InterfaceBody = {
+  left_brace: Span;
+  members: InterfaceMember[];
+  right_brace: Span;
+};
+
+export type InterfaceExtends = {
+  extends: Span;
+  parents: CommaSeparated_for_SimpleIdentifier;
+};
+
+export type InterfaceMember = {
+  type: "constant";
+  value: ClassishConstant;
+} | {
+  type: "constructor";
+  value: AbstractConstructor;
+} | {
+  type: "method";
+  value: AbstractMethod;
+} | {
+  type: "property";
+  value: Property;
+};
+
+export type InterfaceStatement = {
+  attributes: AttributeGroup[];
+  body: InterfaceBody;
+  comments: CommentGroup;
+  extends?: InterfaceExtends | null;
+  interface: Span;
+  name: SimpleIdentifier;
+};
+
+/**
+ * The syntax used to interpolate an expression into a string.
+ *
+ * Each syntax accepts a different subset of the expression grammar.
+ */
+export type InterpolationSyntax = "simple" | "dollar_brace" | "complex";
+
+export type LabelStatement = {
+  colon: Span;
+  comments: CommentGroup;
+  label: SimpleIdentifier;
+};
+
+export type Level = {
+  type: "literal";
+  value: LiteralInteger;
+} | {
+  type: "parenthesized";
+  value: {
+    left_parenthesis: Span;
+    level: Level;
+    right_parenthesis: Span;
+  };
+};
+
+export type ListEntry = {
+  type: "skipped";
+} | {
+  type: "value";
+  value: {
+    value: Expression;
+  };
+} | {
+  type: "key_value";
+  value: {
+    double_arrow: Span;
+    key: Expression;
+    value: Expression;
+  };
+};
+
+/**
+ * `list($a, $b)`
+ */
+export type ListExpression = {
+  end: Span;
+  items: ListEntry[];
+  list: Span;
+  start: Span;
+};
+
+export type Literal = {
+  type: "string";
+  value: LiteralString;
+} | {
+  type: "integer";
+  value: LiteralInteger;
+} | {
+  type: "float";
+  value: LiteralFloat;
+};
+
+export type LiteralFloat = {
+  span: Span;
+  value: ByteString;
+};
+
+export type LiteralInteger = {
+  span: Span;
+  value: ByteString;
+};
+
+export type LiteralString = {
+  /**
+   * The length of the literal in the source, when its value is left empty as the literal exceeds [`crate::ResourceLimits::max_literal_length`], see [`LiteralString::source`].
+   */
+  deferred?: number | null;
+  span: Span;
+  value: ByteString;
+};
+
+/**
+ * A literal segment of an interpolated string.
+ *
+ * Heredoc and nowdoc literals never span more than a single line, so that each line can be mapped back to its exact position.
+ */
+export type LiteralStringPart = {
+  span: Span;
+  value: ByteString;
+};
+
+export type LogicalOperation = {
+  type: "and";
+  value: {
+    double_ampersand: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "or";
+  value: {
+    double_pipe: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "not";
+  value: {
+    bang: Span;
+    right: Expression;
+  };
+} | {
+  type: "logical_and";
+  value: {
+    and: Span;
+    left: Expression;
+    right: Expression;
+  };
+} | {
+  type: "logical_or";
+  value: {
+    left: Expression;
+    or: Span;
+    right: Expression;
+  };
+} | {
+  type: "logical_xor";
+  value: {
+    left: Expression;
+    right: Expression;
+    xor: Span;
+  };
+};
+
+export type MagicConstant = {
+  type: "directory";
+  value: Span;
+} | {
+  type: "file";
+  value: Span;
+} | {
+  type: "line";
+  value: Span;
+} | {
+  type: "class";
+  value: Span;
+} | {
+  type: "function";
+  value: Span;
+} | {
+  type: "method";
+  value: Span;
+} | {
+  type: "namespace";
+  value: Span;
+} | {
+  type: "trait";
+  value: Span;
+} | {
+  type: "compiler_halt_offset";
+  value: Span;
+};
+
+export type MatchArm = {
+  arrow: Span;
+  body: Expression;
+  conditions: Expression[];
+};
+
+/**
+ * `match ($a) { 1 => 'one', default => 'other' }`
+ */
+export type MatchExpression = {
+  arms: MatchArm[];
+  condition: Expression;
+  default?: DefaultMatchArm | null;
+  keyword: Span;
+  left_brace: Span;
+  left_parenthesis: Span;
+  right_brace: Span;
+  right_parenthesis: Span;
+};
+
+export type MethodBody = {
+  comments: CommentGroup;
+  left_brace: Span;
+  right_brace: Span;
+  statements: Statement[];
+};
+
+export type MethodModifier = {
+  type: "final";
+  value: Span;
+} | {
+  type: "static";
+  value: Span;
+} | {
+  type: "abstract";
+  value: Span;
+} | {
+  type: "public";
+  value: Span;
+} | {
+  type: "protected";
+  value: Span;
+} | {
+  type: "private";
+  value: Span;
+};
+
+export type NamespaceStatement = {
+  type: "unbraced";
+  value: UnbracedNamespace;
+} | {
+  type: "braced";
+  value: BracedNamespace;
+};
+
+export type OpenTagKind = "full" | "short" | "echo";
+
+export type PromotedPropertyModifier = {
+  type: "public";
+  value: Span;
+} | {
+  type: "protected";
+  value: Span;
+} | {
+  type: "private";
+  value: Span;
+} | {
+  type: "readonly";
+  value: Span;
+} | {
+  type: "public_set";
+  value: Span;
+} | {
+  type: "protected_set";
+  value: Span;
+} | {
+  type: "private_set";
+  value: Span;
+};
+
+export type Property = {
+  attributes: AttributeGroup[];
+  comments: CommentGroup;
+  end: Span;
+  entries: PropertyEntry[];
+  hooks?: PropertyHookList | null;
+  modifiers: PropertyModifier[];
+  type?: Type | null;
+};
+
+export type PropertyEntry = {
+  type: "uninitialized";
+  value: {
+    variable: SimpleVariable;
+  };
+} | {
+  type: "initialized";
+  value: {
+    equals: Span;
+    value: Expression;
+    variable: SimpleVariable;
+  };
+};
+
+/**
+ * A `get` or `set` hook of a property.
+ */
+export type PropertyHook = {
+  ampersand?: Span | null;
+  attributes: AttributeGroup[];
+  body: PropertyHookBody;
+  comments: CommentGroup;
+  final?: Span | null;
+  name: SimpleIdentifier;
+  parameters?: FunctionParameterList | null;
+};
+
+export type PropertyHookBody = {
+  type: "abstract";
+  value: {
+    semicolon: Span;
+  };
+} | {
+  type: "expression";
+  value: {
+    double_arrow: Span;
+    expression: Expression;
+    semicolon: Span;
+  };
+} | {
+  type: "block";
+  value: FunctionBody;
+};
+
+export type PropertyHookList = {
+  hooks: PropertyHook[];
+  left_brace: Span;
+  right_brace: Span;
+};
+
+export type PropertyModifier = {
+  type: "public";
+  value: Span;
+} | {
+  type: "protected";
+  value: Span;
+} | {
+  type: "private";
+  value: Span;
+} | {
+  type: "static";
+  value: Span;
+} | {
+  type: "readonly";
+  value: Span;
+} | {
+  type: "abstract";
+  value: Span;
+} | {
+  type: "final";
+  value: Span;
+} | {
+  type: "public_set";
+  value: Span;
+} | {
+  type: "protected_set";
+  value: Span;
+} | {
+  type: "private_set";
+  value: Span;
+};
+
+export type ReturnStatement = {
+  ending: Ending;
+  return: Span;
+  value?: Expression | null;
+};
+
+export type ReturnType = {
+  colon: Span;
+  data_type: Type;
+};
+
+export type SimpleIdentifier = {
+  span: Span;
+  value: ByteString;
+};
+
+export type SimpleVariable = {
+  name: ByteString;
+  span: Span;
+};
+
+export type SingleArgument = {
+  argument: Argument;
+  comments: CommentGroup;
+  left_parenthesis: Span;
+  right_parenthesis: Span;
+};
+
+export type Span = {
+  column: number;
+  line: number;
+  position: number;
+  /**
+   * Whether the span belongs to a token invented by the parser, e.g. a semicolon inserted while recovering from an error, rather than written in the source.
+   */
+  synthetic?: boolean;
+};
+
+export type Statement = {
+  type: "full_opening_tag";
+  value: Span;
+} | {
+  type: "short_opening_tag";
+  value: Span;
+} | {
+  type: "echo_opening_tag";
+  value: Span;
+} | {
+  type: "closing_tag";
+  value: Span;
+} | {
+  type: "inline_html";
+  value: ByteString;
+} | {
+  type: "foreign_chunk";
+  value: ForeignChunk;
+} | {
+  type: "label";
+  value: LabelStatement;
+} | {
+  type: "goto";
+  value: GotoStatement;
+} | {
+  type: "halt_compiler";
+  value: HaltCompiler;
+} | {
+  type: "static";
+  value: StaticStatement;
+} | {
+  type: "do_while";
+  value: DoWhileStatement;
+} | {
+  type: "while";
+  value: WhileStatement;
+} | {
+  type: "for";
+  value: ForStatement;
+} | {
+  type: "foreach";
+  value: ForeachStatement;
+} | {
+  type: "break";
+  value: BreakStatement;
+} | {
+  type: "continue";
+  value: ContinueStatement;
+} | {
+  type: "constant";
+  value: ConstantStatement;
+} | {
+  type: "function";
+  value: FunctionStatement;
+} | {
+  type: "class";
+  value: ClassStatement;
+} | {
+  type: "trait";
+  value: TraitStatement;
+} | {
+  type: "interface";
+  value: InterfaceStatement;
+} | {
+  type: "if";
+  value: IfStatement;
+} | {
+  type: "switch";
+  value: SwitchStatement;
+} | {
+  type: "echo";
+  value: EchoStatement;
+} | {
+  type: "expression";
+  value: ExpressionStatement;
+} | {
+  type: "return";
+  value: ReturnStatement;
+} | {
+  type: "namespace";
+  value: NamespaceStatement;
+} | {
+  type: "use";
+  value: UseStatement;
+} | {
+  type: "group_use";
+  value: GroupUseStatement;
+} | {
+  type: "comment";
+  value: Comment;
+} | {
+  type: "try";
+  value: TryStatement;
+} | {
+  type: "unit_enum";
+  value: UnitEnumStatement;
+} | {
+  type: "backed_enum";
+  value: BackedEnumStatement;
+} | {
+  type: "block";
+  value: BlockStatement;
+} | {
+  type: "global";
+  value: GlobalStatement;
+} | {
+  type: "declare";
+  value: DeclareStatement;
+} | {
+  type: "noop";
+  value: Span;
+} | {
+  type: "error";
+  value: ErrorStatement;
+};
+
+export type StaticStatement = {
+  vars: StaticVar[];
+};
+
+export type StaticVar = {
+  default?: Expression | null;
+  var: Variable;
+};
+
+export type StringPart = {
+  type: "literal";
+  value: LiteralStringPart;
+} | {
+  type: "expression";
+  value: ExpressionStringPart;
+};
+
+export type SwitchStatement = {
+  cases: Case[];
+  condition: Expression;
+  left_parenthesis: Span;
+  right_parenthesis: Span;
+  switch: Span;
+};
+
+/**
+ * A token of the input, whose value and source text borrow from the input unless they differ from it, see [`Token::into_owned`] to keep a token past the input.
+ */
+export type Token = {
+  kind: TokenKind;
+  /**
+   * The source text of the token, when it differs from the value, see [`Token::raw`].
+   */
+  raw?: ByteString | null;
+  span: Span;
+  value: ByteString;
+};
+
+export type TokenKind = "die" | "self_" | "parent" | "backtick" | "from" | "print" | "dollar" | "halt_compiler" | "readonly" | "global" | "abstract" | "ampersand" | "ampersand_equals" | "and" | "and_equals" | "array" | "array_cast" | "arrow" | "question_arrow" | "at" | "as" | "asterisk" | "attribute" | "bang" | "bang_equals" | "angled_left_right" | "bang_double_equals" | "spaceship" | "bool_cast" | "boolean_cast" | "boolean_and" | "boolean_or" | "break" | "callable" | "caret" | "caret_equals" | "case" | "catch" | "class" | "class_constant" | "trait_constant" | "function_constant" | "method_constant" | "line_constant" | "file_constant" | "clone" | "minus_equals" | "close_tag" | "double_question" | "double_question_equals" | "asterisk_equals" | "colon" | "comma" | "single_line_comment" | "hash_mark_comment" | "multi_line_comment" | "document_comment" | "const" | "literal_string" | "continue" | "curly_open" | "declare" | "decrement" | "default" | "dir_constant" | "div_equals" | "do" | "dollar_left_brace" | "dot" | "dot_equals" | "double_arrow" | "double_cast" | "real_cast" | "float_cast" | "double_colon" | "double_equals" | "double_quote" | "echo" | "ellipsis" | "else" | "else_if" | "empty" | "end_declare" | "end_for" | "end_foreach" | "end_if" | "end_switch" | "end_while" | "enum" | "eof" | "equals" | "extends" | "false" | "final" | "finally" | "literal_float" | "fn" | "for" | "foreach" | "foreign_chunk" | "fully_qualified_identifier" | "function" | "goto" | "greater_than" | "greater_than_equals" | "identifier" | "if" | "implements" | "include" | "include_once" | "increment" | "inline_html" | "instanceof" | "insteadof" | "eval" | "exit" | "unset" | "isset" | "list" | "literal_integer" | "int_cast" | "integer_cast" | "interface" | "left_brace" | "left_bracket" | "left_paren" | "left_shift" | "left_shift_equals" | "right_shift" | "right_shift_equals" | "less_than" | "less_than_equals" | "match" | "minus" | "namespace" | "namespace_separator" | "namespace_constant" | "compiler_halt_offset_constant" | "new" | "null" | "object_cast" | "unset_cast" | "percent" | "percent_equals" | "pipe" | "pipe_equals" | "pipe_greater_than" | "plus" | "plus_equals" | "pow" | "pow_equals" | "private" | "protected" | "public" | "qualified_identifier" | "question" | "question_colon" | "require" | "require_once" | "return" | "right_brace" | "right_bracket" | "right_paren" | "semi_colon" | "slash" | "slash_equals" | "static" | "string_cast" | "binary_cast" | "string_part" | "switch" | "throw" | "trait" | "triple_equals" | "true" | "try" | "use" | "var" | "variable" | "yield" | "while" | "bitwise_not" | "logical_and" | "logical_or" | "logical_xor" | {
+  start_doc_string: DocStringKind;
+} | {
+  end_doc_string: [DocStringIndentationKind, number];
+} | {
+  open_tag: OpenTagKind;
+} | "private_set" | "protected_set" | "public_set";
+
+export type TraitBody = {
+  left_brace: Span;
+  members: TraitMember[];
+  right_brace: Span;
+};
+
+export type TraitMember = {
+  type: "constant";
+  value: ClassishConstant;
+} | {
+  type: "trait_usage";
+  value: TraitUsage;
+} | {
+  type: "property";
+  value: Property;
+} | {
+  type: "variable_property";
+  value: VariableProperty;
+} | {
+  type: "abstract_method";
+  value: AbstractMethod;
+} | {
+  type: "abstract_constructor";
+  value: AbstractConstructor;
+} | {
+  type: "concrete_method";
+  value: ConcreteMethod;
+} | {
+  type: "concrete_constructor";
+  value: ConcreteConstructor;
+};
+
+export type TraitStatement = {
+  attributes: AttributeGroup[];
+  body: TraitBody;
+  comments: CommentGroup;
+  name: SimpleIdentifier;
+  trait: Span;
+};
+
+export type TraitUsage = {
+  adaptations: TraitUsageAdaptation[];
+  traits: SimpleIdentifier[];
+  use: Span;
+};
+
+export type TraitUsageAdaptation = {
+  type: "alias";
+  value: {
+    alias: SimpleIdentifier;
+    method: SimpleIdentifier;
+    trait?: SimpleIdentifier | null;
+    visibility?: VisibilityModifier | null;
+  };
+} | {
+  type: "visibility";
+  value: {
+    method: SimpleIdentifier;
+    trait?: SimpleIdentifier | null;
+    visibility: VisibilityModifier;
+  };
+} | {
+  type: "precedence";
+  value: {
+    insteadof: SimpleIdentifier[];
+    method: SimpleIdentifier;
+    trait?: SimpleIdentifier | null;
+  };
+};
+
+export type TryStatement = {
+  body: Statement[];
+  catches: CatchBlock[];
+  end: Span;
+  finally?: FinallyBlock | null;
+  start: Span;
+};
+
+export type Type = {
+  type: "named";
+  value: [Span, ByteString];
+} | {
+  type: "nullable";
+  value: [Span, Type];
+} | {
+  type: "union";
+  value: Type[];
+} | {
+  type: "intersection";
+  value: Type[];
+} | {
+  type: "void";
+  value: Span;
+} | {
+  type: "null";
+  value: Span;
+} | {
+  type: "true";
+  value: Span;
+} | {
+  type: "false";
+  value: Span;
+} | {
+  type: "never";
+  value: Span;
+} | {
+  type: "float";
+  value: Span;
+} | {
+  type: "boolean";
+  value: Span;
+} | {
+  type: "integer";
+  value: Span;
+} | {
+  type: "string";
+  value: Span;
+} | {
+  type: "array";
+  value: Span;
+} | {
+  type: "object";
+  value: Span;
+} | {
+  type: "mixed";
+  value: Span;
+} | {
+  type: "callable";
+  value: Span;
+} | {
+  type: "iterable";
+  value: Span;
+} | {
+  type: "static_reference";
+  value: Span;
+} | {
+  type: "self_reference";
+  value: Span;
+} | {
+  type: "parent_reference";
+  value: Span;
+};
+
+export type UnbracedNamespace = {
+  end: Span;
+  name: SimpleIdentifier;
+  start: Span;
+  statements: Statement[];
+};
+
+export type UnitEnumBody = {
+  left_brace: Span;
+  members: UnitEnumMember[];
+  right_brace: Span;
+};
+
+export type UnitEnumCase = {
+  attributes: AttributeGroup[];
+  comments: CommentGroup;
+  end: Span;
+  name: SimpleIdentifier;
+  start: Span;
+};
+
+export type UnitEnumMember = {
+  type: "case";
+  value: UnitEnumCase;
+} | {
+  type: "method";
+  value: ConcreteMethod;
+} | {
+  type: "constant";
+  value: ClassishConstant;
+};
+
+export type UnitEnumStatement = {
+  attributes: AttributeGroup[];
+  body: UnitEnumBody;
+  comments: CommentGroup;
+  enum: Span;
+  implements: SimpleIdentifier[];
+  name: SimpleIdentifier;
+};
+
+export type Use = {
+  alias?: SimpleIdentifier | null;
+  kind?: UseKind | null;
+  name: SimpleIdentifier;
+};
+
+export type UseKind = {
+  type: "normal";
+} | {
+  type: "function";
+} | {
+  type: "const";
+};
+
+export type UseStatement = {
+  kind: UseKind;
+  uses: Use[];
+};
+
+export type Variable = {
+  type: "simple_variable";
+  value: SimpleVariable;
+} | {
+  type: "variable_variable";
+  value: VariableVariable;
+} | {
+  type: "braced_variable_variable";
+  value: BracedVariableVariable;
+};
+
+export type VariableProperty = {
+  attributes: AttributeGroup[];
+  comments: CommentGroup;
+  end: Span;
+  entries: PropertyEntry[];
+  type?: Type | null;
+};
+
+export type VariableVariable = {
+  span: Span;
+  variable: Variable;
+};
+
+export type VisibilityModifier = {
+  type: "public";
+  value: Span;
+} | {
+  type: "protected";
+  value: Span;
+} | {
+  type: "private";
+  value: Span;
+};
+
+export type WhileStatement = {
+  body: WhileStatementBody;
+  condition: Expression;
+  left_parenthesis: Span;
+  right_parenthesis: Span;
+  while: Span;
+};
+
+export type WhileStatementBody = {
+  type: "statement";
+  value: Statement;
+} | {
+  type: "block";
+  value: {
+    colon: Span;
+    ending: Ending;
+    endwhile: Span;
+    statements: Statement[];
+  };
+};
@@ -0,0 +1,702 @@
+use std::fmt::Display;
+
+use crate::downcast::downcast_mut;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::calls::CallShape;
+use crate::parser::ast::classes::AnonymousClass;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::NameKind;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::Expression;
+use crate::parser::ast::Statement;
+
+use super::hierarchy::Hierarchy;
+use super::resolver::NameResolver;
+
+/// The caller of calls made outside of any function or method.
+pub const MAIN: &[u8] = b"{main}";
+
+// The name methods of anonymous classes are attributed to.
+const ANONYMOUS_CLASS: &[u8] = b"class@anonymous";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Callee {
+    /// A function, e.g. `App\foo`.
+    Function(ByteString),
+    /// A method of a known class-like, e.g. `App\Foo::bar`.
+    Method {
+        class: ByteString,
+        method: ByteString,
+    },
+    /// A method called on a receiver of unknown type, e.g. `$foo->bar()`.
+    UnknownMethod(ByteString),
+    /// A callee which can not be determined statically, e.g. `$callback()`, or `$foo->$name()`.
+    Dynamic,
+}
+
+impl Callee {
+    /// The fully qualified name of the callee, e.g. `App\foo`, or `App\Foo::bar`.
+    pub fn name(&self) -> Option<ByteString> {
+        match self {
+            Callee::Function(name) => Some(name.clone()),
+            Callee::Method { class, method } => Some(method_name(class, method)),
+            Callee::UnknownMethod(_) | Callee::Dynamic => None,
+        }
+    }
+}
+
+impl Display for Callee {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Callee::Function(name) => write!(f, "{}", name),
+            Callee::Method { class, method } => write!(f, "{}::{}", class, method),
+            Callee::UnknownMethod(method) => write!(f, "?::{}", method),
+            Callee::Dynamic => write!(f, "?"),
+        }
+    }
+}
+
+/// A single call site.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Call {
+    /// The function or method containing the call, e.g. `App\Foo::bar`, or [`MAIN`].
+    ///
+    /// Calls made within closures and arrow functions are attributed to the enclosing
+    /// function or method.
+    pub caller: ByteString,
+    pub callee: Callee,
+    /// The name of the callee, or the `new` keyword, or the operator preceding a dynamic callee.
+    pub span: Span,
+    /// The form of the call, or `None` for a `new` expression.
+    pub shape: Option<CallShape>,
+    /// Whether the callee is declared in the project.
+    pub resolved: bool,
+}
+
+impl Call {
+    /// Whether the callee can not be determined statically.
+    pub fn is_dynamic(&self) -> bool {
+        matches!(self.callee, Callee::UnknownMethod(_) | Callee::Dynamic)
+    }
+}
+
+/// The caller to callee graph of the functions and methods of a project.
+///
+/// Method calls are resolved statically, against the class-like the method is called on:
+/// `$this->foo()` calls the `foo` method available on the enclosing class-like, not the
+/// methods overriding it in subclasses.
+#[derive(Debug, Clone)]
+pub struct CallGraph<'a> {
+    hierarchy: &'a Hierarchy<'a>,
+    calls: Vec<Call>,
+}
+
+impl<'a> CallGraph<'a> {
+    pub fn new(hierarchy: &'a Hierarchy<'a>) -> Self {
+        Self {
+            hierarchy,
+            calls: Vec::new(),
+        }
+    }
+
+    /// Add the calls made in a single file.
+    pub fn add(&mut self, program: &mut [Statement]) {
+        let mut collector = Collector {
+            hierarchy: self.hierarchy,
+            resolver: NameResolver::new(),
+            caller: MAIN.into(),
+            class: None,
+            parent: None,
+            calls: Vec::new(),
+        };
+        collector.statements(program);
+
+        self.calls.append(&mut collector.calls);
+    }
+
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    /// The calls made by the given function or method.
+    pub fn calls_from(&self, caller: &[u8]) -> Vec<&Call> {
+        self.calls
+            .iter()
+            .filter(|call| same_name(&call.caller, caller))
+            .collect()
+    }
+
+    /// The calls to the given function or method.
+    pub fn calls_to(&self, callee: &[u8]) -> Vec<&Call> {
+        self.calls
+            .iter()
+            .filter(|call| {
+                call.callee
+                    .name()
+                    .is_some_and(|name| same_name(&name, callee))
+            })
+            .collect()
+    }
+
+    /// The calls whose callee can not be determined statically.
+    pub fn dynamic_calls(&self) -> Vec<&Call> {
+        self.calls.iter().filter(|call| call.is_dynamic()).collect()
+    }
+
+    /// Every function and method calling the given function or method, directly or indirectly,
+    /// e.g. everything reaching `unserialize`.
+    ///
+    /// Dynamic calls are not followed, see [`CallGraph::dynamic_calls`].
+    pub fn reaching(&self, callee: &[u8]) -> Vec<ByteString> {
+        self.walk(callee, |current| {
+            self.calls_to(current)
+                .into_iter()
+                .map(|call| call.caller.clone())
+                .collect()
+        })
+    }
+
+    /// Every function and method called by the given function or method, directly or indirectly.
+    ///
+    /// Dynamic calls are not followed, see [`CallGraph::dynamic_calls`].
+    pub fn reachable_from(&self, caller: &[u8]) -> Vec<ByteString> {
+        self.walk(caller, |current| {
+            self.calls_from(current)
+                .into_iter()
+                .filter_map(|call| call.callee.name())
+                .collect()
+        })
+    }
+
+    fn walk(&self, start: &[u8], next: impl Fn(&[u8]) -> Vec<ByteString>) -> Vec<ByteString> {
+        let start = start.strip_prefix(b"\\").unwrap_or(start);
+        let mut found: Vec<ByteString> = Vec::new();
+        let mut queue = vec![ByteString::from(start)];
+
+        while let Some(current) = queue.pop() {
+            for name in next(&current) {
+                if same_name(&name, start) || found.iter().any(|entry| same_name(entry, &name)) {
+                    continue;
+                }
+
+                found.push(name.clone());
+                queue.push(name);
+            }
+        }
+
+        found
+    }
+}
+
+struct Collector<'a> {
+    hierarchy: &'a Hierarchy<'a>,
+    resolver: NameResolver,
+    caller: ByteString,
+    // The enclosing class-like, `None` outside of class-likes and within anonymous classes.
+    class: Option<ByteString>,
+    // The parent class of the enclosing class-like.
+    parent: Option<ByteString>,
+    calls: Vec<Call>,
+}
+
+impl<'a> Collector<'a> {
+    fn statements(&mut self, statements: &mut [Statement]) {
+        for statement in statements.iter_mut() {
+            match statement {
+                Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                    self.resolver.enter_namespace(Some(&namespace.name.value));
+                    self.statements(&mut namespace.statements);
+                }
+                Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                    self.resolver
+                        .enter_namespace(namespace.name.as_ref().map(|name| &name.value[..]));
+                    self.statements(&mut namespace.body.statements);
+                }
+                Statement::Use(statement) => self.resolver.import_statement(statement),
+                Statement::GroupUse(statement) => self.resolver.import_group_statement(statement),
+                _ => self.statement(statement),
+            }
+        }
+    }
+
+    fn node(&mut self, node: &mut dyn Node) {
+        if let Some(statement) = downcast_mut::<Statement>(node) {
+            return self.statement(statement);
+        }
+
+        if let Some(expression) = downcast_mut::<Expression>(node) {
+            return self.expression(expression);
+        }
+
+        if let Some(method) = downcast_mut::<ConcreteMethod>(node) {
+            let name = method.name.value.clone();
+
+            return self.scope(&name, method);
+        }
+
+        if let Some(constructor) = downcast_mut::<ConcreteConstructor>(node) {
+            let name = constructor.name.value.clone();
+
+            return self.scope(&name, constructor);
+        }
+
+        self.children(node);
+    }
+
+    fn children(&mut self, node: &mut dyn Node) {
+        for child in node.children() {
+            self.node(child);
+        }
+    }
+
+    fn scope(&mut self, name: &[u8], method: &mut dyn Node) {
+        let caller = match &self.class {
+            Some(class) => method_name(class, name),
+            None => method_name(ANONYMOUS_CLASS, name),
+        };
+        let previous = std::mem::replace(&mut self.caller, caller);
+        self.children(method);
+        self.caller = previous;
+    }
+
+    fn class(&mut self, class: Option<ByteString>, node: &mut dyn Node) {
+        let parent = match &class {
+            Some(class) => self
+                .hierarchy
+                .index()
+                .get(class)
+                .and_then(|class| class.parent.clone()),
+            None => None,
+        };
+
+        let previous = (
+            std::mem::replace(&mut self.class, class),
+            std::mem::replace(&mut self.parent, parent),
+        );
+        self.children(node);
+        (self.class, self.parent) = previous;
+    }
+
+    fn anonymous_class(&mut self, class: &mut AnonymousClass) {
+        let parent = class
+            .extends
+            .as_ref()
+            .map(|extends| self.resolver.resolve_class(&extends.parent.value));
+
+        let previous = (
+            self.class.take(),
+            std::mem::replace(&mut self.parent, parent),
+        );
+        self.children(class);
+        (self.class, self.parent) = previous;
+    }
+
+    fn statement(&mut self, statement: &mut Statement) {
+        match statement {
+            Statement::Function(function) => {
+                let caller = self.resolver.qualify(&function.name.value);
+                let previous = std::mem::replace(&mut self.caller, caller);
+                self.children(function.as_mut());
+                self.caller = previous;
+            }
+            Statement::Class(class) => {
+                let name = self.resolver.qualify(&class.name.value);
+                self.class(Some(name), class.as_mut());
+            }
+            Statement::Interface(interface) => {
+                let name = self.resolver.qualify(&interface.name.value);
+                self.class(Some(name), interface.as_mut());
+            }
+            Statement::Trait(r#trait) => {
+                let name = self.resolver.qualify(&r#trait.name.value);
+                self.class(Some(name), r#trait.as_mut());
+            }
+            Statement::UnitEnum(r#enum) => {
+                let name = self.resolver.qualify(&r#enum.name.value);
+                self.class(Some(name), r#enum.as_mut());
+            }
+            Statement::BackedEnum(r#enum) => {
+                let name = self.resolver.qualify(&r#enum.name.value);
+                self.class(Some(name), r#enum.as_mut());
+            }
+            _ => self.children(statement),
+        }
+    }
+
+    fn expression(&mut self, expression: &mut Expression) {
+        let shape = expression.call_shape();
+
+        match expression {
+            Expression::FunctionCall { target, arguments } => {
+                let span = match target.as_ref() {
+                    Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                        identifier.span
+                    }
+                    _ => arguments.left_parenthesis,
+                };
+                self.function_call(target, span, shape);
+            }
+            Expression::FunctionClosureCreation {
+                target,
+                placeholder,
+            } => {
+                let span = match target.as_ref() {
+                    Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                        identifier.span
+                    }
+                    _ => placeholder.left_parenthesis,
+                };
+                self.function_call(target, span, shape);
+            }
+            Expression::MethodCall {
+                target,
+                arrow: span,
+                method,
+                ..
+            }
+            | Expression::MethodClosureCreation {
+                target,
+                arrow: span,
+                method,
+                ..
+            }
+            | Expression::NullsafeMethodCall {
+                target,
+                question_arrow: span,
+                method,
+                ..
+            } => {
+                let (callee, span) = match method.as_ref() {
+                    Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                        (self.method_call(target, &identifier.value), identifier.span)
+                    }
+                    _ => (Callee::Dynamic, *span),
+                };
+                self.call(callee, span, shape);
+            }
+            Expression::StaticMethodCall {
+                target,
+                double_colon,
+                method,
+                ..
+            }
+            | Expression::StaticMethodClosureCreation {
+                target,
+                double_colon,
+                method,
+                ..
+            } => {
+                let (callee, span) = match method {
+                    Identifier::SimpleIdentifier(identifier) => (
+                        self.static_method_call(target, &identifier.value),
+                        identifier.span,
+                    ),
+                    Identifier::DynamicIdentifier(_) => (Callee::Dynamic, *double_colon),
+                };
+                self.call(callee, span, shape);
+            }
+            Expression::StaticVariableMethodCall { double_colon, .. }
+            | Expression::StaticVariableMethodClosureCreation { double_colon, .. } => {
+                self.call(Callee::Dynamic, *double_colon, shape);
+            }
+            Expression::New { new, target, .. } => {
+                if let Some(callee) = self.instantiation(target) {
+                    self.call(callee, *new, None);
+                }
+            }
+            Expression::AnonymousClass(class) => return self.anonymous_class(class),
+            _ => {}
+        }
+
+        self.children(expression);
+    }
+
+    fn call(&mut self, callee: Callee, span: Span, shape: Option<CallShape>) {
+        let resolved = match &callee {
+            Callee::Function(name) => self.hierarchy.index().get_function(name).is_some(),
+            Callee::Method { class, method } => self
+                .hierarchy
+                .index()
+                .get(class)
+                .is_some_and(|class| class.get_method(method).is_some()),
+            Callee::UnknownMethod(_) | Callee::Dynamic => false,
+        };
+
+        self.calls.push(Call {
+            caller: self.caller.clone(),
+            callee,
+            span,
+            shape,
+            resolved,
+        });
+    }
+
+    fn function_call(&mut self, target: &Expression, span: Span, shape: Option<CallShape>) {
+        let name = match target {
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => &identifier.value,
+            _ => return self.call(Callee::Dynamic, span, shape),
+        };
+
+        let resolved = self.resolver.resolve_function(name);
+
+        // Unqualified names fall back to the global function when the namespaced
+        // one does not exist.
+        let name = if NameKind::of(name) == NameKind::Unqualified
+            && self.resolver.namespace().is_some()
+            && resolved == self.resolver.qualify(name)
+            && self.hierarchy.index().get_function(&resolved).is_none()
+        {
+            name.clone()
+        } else {
+            resolved
+        };
+
+        self.call(Callee::Function(name), span, shape);
+    }
+
+    fn method_call(&self, target: &Expression, method: &[u8]) -> Callee {
+        match target {
+            Expression::Variable(Variable::SimpleVariable(variable))
+                if variable.name == b"$this" =>
+            {
+                match &self.class {
+                    Some(class) => self.method(class, method),
+                    None => Callee::UnknownMethod(method.into()),
+                }
+            }
+            _ => Callee::UnknownMethod(method.into()),
+        }
+    }
+
+    fn static_method_call(&self, target: &Expression, method: &[u8]) -> Callee {
+        match self.class_name(target) {
+            Some(class) => self.method(&class, method),
+            None => Callee::UnknownMethod(method.into()),
+        }
+    }
+
+    fn instantiation(&self, target: &Expression) -> Option<Callee> {
+        if let Expression::AnonymousClass(_) = target {
+            return None;
+        }
+
+        let class = match self.class_name(target) {
+            Some(class) => class,
+            None => return Some(Callee::Dynamic),
+        };
+
+        match self.lookup(&class, b"__construct") {
+            Some(callee) => Some(callee),
+            // Classes declared in the project without a constructor are instantiated
+            // without calling anything.
+            None if self.hierarchy.index().get(&class).is_some() => None,
+            None => Some(Callee::Method {
+                class,
+                method: b"__construct".into(),
+            }),
+        }
+    }
+
+    // The fully qualified name of the class referenced by the given expression,
+    // if it can be determined statically.
+    fn class_name(&self, target: &Expression) -> Option<ByteString> {
+        let name = match target {
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                &identifier.value[..]
+            }
+            Expression::Self_ { .. } => b"self",
+            Expression::Static { .. } => b"static",
+            Expression::Parent { .. } => b"parent",
+            _ => return None,
+        };
+
+        if name.eq_ignore_ascii_case(b"self") || name.eq_ignore_ascii_case(b"static") {
+            return self.class.clone();
+        }
+
+        if name.eq_ignore_ascii_case(b"parent") {
+            return self.parent.clone();
+        }
+
+        Some(self.resolver.resolve_class(name))
+    }
+
+    // The given method of the given class-like, or the class-like declaring
+    // it when it is available through a trait or a parent.
+    fn method(&self, class: &[u8], method: &[u8]) -> Callee {
+        self.lookup(class, method)
+            .unwrap_or_else(|| Callee::Method {
+                class: class.into(),
+                method: method.into(),
+            })
+    }
+
+    fn lookup(&self, class: &[u8], method: &[u8]) -> Option<Callee> {
+        self.hierarchy
+            .all_methods_including_traits(class)
+            .into_iter()
+            .find(|candidate| same_name(&candidate.name, method))
+            .map(|candidate| Callee::Method {
+                class: candidate.declaring_class.name.clone(),
+                method: candidate.method.name.value.clone(),
+            })
+    }
+}
+
+fn method_name(class: &[u8], method: &[u8]) -> ByteString {
+    let mut name = class.to_vec();
+    name.extend_from_slice(b"::");
+    name.extend_from_slice(method);
+
+    name.into()
+}
+
+fn same_name(a: &[u8], b: &[u8]) -> bool {
+    let a = a.strip_prefix(b"\\").unwrap_or(a);
+    let b = b.strip_prefix(b"\\").unwrap_or(b);
+
+    a.eq_ignore_ascii_case(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CallGraph;
+    use super::Callee;
+    use crate::analysis::hierarchy::Hierarchy;
+    use crate::analysis::index::SymbolIndex;
+    use crate::lexer::byte_string::ByteString;
+    use crate::parser::ast::calls::CallShape;
+
+    fn graph(files: &[&str], test: impl Fn(&CallGraph)) {
+        let mut programs = files
+            .iter()
+            .map(|file| crate::parse(file).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut index = SymbolIndex::new();
+        for program in programs.iter_mut() {
+            index.add(program);
+        }
+
+        let hierarchy = Hierarchy::new(&index);
+        let mut graph = CallGraph::new(&hierarchy);
+        for program in programs.iter_mut() {
+            graph.add(program);
+        }
+
+        test(&graph);
+    }
+
+    fn names(graph: &CallGraph, caller: &str) -> Vec<String> {
+        graph
+            .calls_from(caller.as_bytes())
+            .iter()
+            .map(|call| call.callee.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_function_calls_across_namespaces() {
+        graph(
+            &[
+                "<?php namespace App; function helper() { strlen('a'); } function run() { helper(); \\App\\helper(); }",
+                "<?php namespace Other; use function App\\helper; use App as A; helper(); A\\run(); missing();",
+            ],
+            |graph| {
+                assert_eq!(names(graph, "App\\helper"), ["strlen"]);
+                assert_eq!(names(graph, "app\\run"), ["App\\helper", "App\\helper"]);
+                assert_eq!(names(graph, "{main}"), ["App\\helper", "App\\run", "missing"]);
+
+                let calls = graph.calls_from(b"{main}");
+                assert!(calls[0].resolved && calls[1].resolved);
+                assert!(!calls[2].resolved);
+                assert_eq!(calls[2].span.line, 1);
+            },
+        );
+    }
+
+    #[test]
+    fn test_method_calls() {
+        graph(
+            &[
+                "<?php namespace App;
+                trait Logs { function log() {} }
+                class Base { function __construct() {} static function make() { return new static(); } }
+                class Service extends Base {
+                    use Logs;
+                    function handle($other) {
+                        $this->log();
+                        self::make();
+                        parent::__construct();
+                        Base::make(...);
+                        $other->handle();
+                        $other->$name();
+                        $callback();
+                        new Plain();
+                        new \\Exception();
+                        new $class();
+                    }
+                }
+                class Plain {}",
+            ],
+            |graph| {
+                assert_eq!(
+                    names(graph, "App\\Service::handle"),
+                    [
+                        "App\\Logs::log",
+                        "App\\Base::make",
+                        "App\\Base::__construct",
+                        "App\\Base::make",
+                        "?::handle",
+                        "?",
+                        "?",
+                        "Exception::__construct",
+                        "?",
+                    ]
+                );
+                assert_eq!(names(graph, "App\\Base::make"), ["App\\Base::__construct"]);
+
+                let dynamic = graph.dynamic_calls();
+                assert_eq!(dynamic.len(), 4);
+                assert_eq!(dynamic[0].callee, Callee::UnknownMethod("handle".into()));
+                assert_eq!(dynamic[0].shape, Some(CallShape::Method));
+                assert_eq!(dynamic[1].shape, Some(CallShape::VariableMethod));
+                assert_eq!(dynamic[3].shape, None);
+            },
+        );
+    }
+
+    #[test]
+    fn test_reachability() {
+        graph(
+            &["<?php
+                function load($data) { return unserialize($data); }
+                class Cache { function get() { return load(''); } function unused() {} }
+                function controller() { $cache = new Cache(); return $cache->get(); }
+                function cached() { return (new Cache())->get(); }
+                function entry() { return fn () => load(''); }"],
+            |graph| {
+                let mut reaching = graph
+                    .reaching(b"\\unserialize")
+                    .iter()
+                    .map(|name| name.to_string())
+                    .collect::<Vec<String>>();
+                reaching.sort();
+
+                // calls on receivers of unknown type are not followed.
+                assert_eq!(reaching, ["Cache::get", "entry", "load"]);
+
+                assert_eq!(
+                    graph.reachable_from(b"entry"),
+                    [&b"load"[..], b"unserialize"].map(ByteString::from)
+                );
+                assert!(graph.calls_to(b"Cache::unused").is_empty());
+            },
+        );
+    }
+}
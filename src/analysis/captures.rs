@@ -0,0 +1,570 @@
+use crate::downcast::downcast_mut;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::functions::ArrowFunction;
+use crate::parser::ast::functions::Closure;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::loops::ForeachStatementIterator;
+use crate::parser::ast::operators::ArithmeticOperation;
+use crate::parser::ast::operators::AssignmentOperation;
+use crate::parser::ast::try_block::CatchBlock;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::Expression;
+use crate::parser::ast::ListEntry;
+use crate::parser::ast::Statement;
+use crate::traverser::Visitor;
+
+const SUPERGLOBALS: [&[u8]; 9] = [
+    b"$GLOBALS",
+    b"$_SERVER",
+    b"$_GET",
+    b"$_POST",
+    b"$_FILES",
+    b"$_COOKIE",
+    b"$_SESSION",
+    b"$_REQUEST",
+    b"$_ENV",
+];
+
+const DYNAMIC_FUNCTIONS: [&[u8]; 3] = [b"compact", b"extract", b"get_defined_vars"];
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FunctionKind {
+    Closure,
+    ArrowFunction,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CaptureKind {
+    // `use ($foo)`
+    Use,
+    // `use (&$foo)`
+    UseByReference,
+    // `fn() => $foo`
+    Implicit,
+    // `$this`
+    This,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Capture {
+    pub variable: ByteString,
+    pub kind: CaptureKind,
+    pub read: bool,
+    pub written: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ClosureCaptures {
+    pub kind: FunctionKind,
+    pub span: Span,
+    pub captures: Vec<Capture>,
+    /// Whether the body accesses variables dynamically (e.g. `$$name`, or `compact()`),
+    /// in which case the captured variables can not be determined statically.
+    pub dynamic: bool,
+}
+
+impl ClosureCaptures {
+    pub fn get(&self, variable: &[u8]) -> Option<&Capture> {
+        self.captures
+            .iter()
+            .find(|capture| capture.variable.bytes == variable)
+    }
+
+    pub fn captures_this(&self) -> bool {
+        self.captures
+            .iter()
+            .any(|capture| capture.kind == CaptureKind::This)
+    }
+
+    /// Whether any captured variable is written to, and the write would be
+    /// lost when converting between a closure and an arrow function.
+    pub fn has_writes(&self) -> bool {
+        self.captures
+            .iter()
+            .any(|capture| capture.kind != CaptureKind::This && capture.written)
+    }
+}
+
+/// Compute the captured variables of every closure and arrow function within the given node.
+pub fn analyse(node: &mut dyn Node) -> Vec<ClosureCaptures> {
+    let mut finder = Finder::default();
+    let _ = finder.visit_node(node);
+
+    finder.captures
+}
+
+/// Compute the variables captured by a closure, through its `use` clause, and `$this`.
+pub fn closure(closure: &mut Closure) -> ClosureCaptures {
+    let mut usage = Usage::default();
+    for statement in closure.body.statements.iter_mut() {
+        usage.statement(statement);
+    }
+
+    let mut captures = Vec::new();
+    if let Some(uses) = &closure.uses {
+        for variable in uses.variables.inner.iter() {
+            let name = &variable.variable.name;
+
+            captures.push(Capture {
+                variable: name.clone(),
+                kind: if variable.ampersand.is_some() {
+                    CaptureKind::UseByReference
+                } else {
+                    CaptureKind::Use
+                },
+                read: usage.is_read(name),
+                written: usage.is_written(name),
+            });
+        }
+    }
+
+    if closure.r#static.is_none() && usage.this {
+        captures.push(Capture {
+            variable: b"$this".into(),
+            kind: CaptureKind::This,
+            read: true,
+            written: false,
+        });
+    }
+
+    ClosureCaptures {
+        kind: FunctionKind::Closure,
+        span: closure.function,
+        captures,
+        dynamic: usage.dynamic,
+    }
+}
+
+/// Compute the variables implicitly captured by value by an arrow function, and `$this`.
+pub fn arrow_function(function: &mut ArrowFunction) -> ClosureCaptures {
+    let mut usage = Usage::default();
+    usage.expression(&mut function.body);
+
+    let parameters = function
+        .parameters
+        .parameters
+        .inner
+        .iter()
+        .map(|parameter| parameter.name.name.clone())
+        .collect::<Vec<ByteString>>();
+
+    let mut captures = usage
+        .variables
+        .into_iter()
+        .filter(|(name, _, _)| {
+            !parameters.contains(name) && !SUPERGLOBALS.contains(&&name.bytes[..])
+        })
+        .map(|(variable, read, written)| Capture {
+            variable,
+            kind: CaptureKind::Implicit,
+            read,
+            written,
+        })
+        .collect::<Vec<Capture>>();
+
+    if function.r#static.is_none() && usage.this {
+        captures.push(Capture {
+            variable: b"$this".into(),
+            kind: CaptureKind::This,
+            read: true,
+            written: false,
+        });
+    }
+
+    ClosureCaptures {
+        kind: FunctionKind::ArrowFunction,
+        span: function.r#fn,
+        captures,
+        dynamic: usage.dynamic,
+    }
+}
+
+#[derive(Debug, Default)]
+struct Finder {
+    captures: Vec<ClosureCaptures>,
+}
+
+impl Visitor<()> for Finder {
+    fn visit(&mut self, node: &mut dyn Node) -> Result<(), ()> {
+        match downcast_mut::<Expression>(node) {
+            Some(Expression::Closure(function)) => self.captures.push(closure(function)),
+            Some(Expression::ArrowFunction(function)) => {
+                self.captures.push(arrow_function(function))
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Usage {
+    // (name, read, written)
+    variables: Vec<(ByteString, bool, bool)>,
+    this: bool,
+    dynamic: bool,
+}
+
+impl Usage {
+    fn is_read(&self, name: &ByteString) -> bool {
+        self.variables
+            .iter()
+            .any(|(variable, read, _)| variable == name && *read)
+    }
+
+    fn is_written(&self, name: &ByteString) -> bool {
+        self.variables
+            .iter()
+            .any(|(variable, _, written)| variable == name && *written)
+    }
+
+    fn record(&mut self, name: &ByteString, read: bool, written: bool) {
+        if name == b"$this" {
+            self.this = true;
+
+            return;
+        }
+
+        match self
+            .variables
+            .iter_mut()
+            .find(|(variable, _, _)| variable == name)
+        {
+            Some((_, r, w)) => {
+                *r |= read;
+                *w |= written;
+            }
+            None => self.variables.push((name.clone(), read, written)),
+        }
+    }
+
+    fn node(&mut self, node: &mut dyn Node) {
+        if let Some(expression) = downcast_mut::<Expression>(node) {
+            return self.expression(expression);
+        }
+
+        if let Some(statement) = downcast_mut::<Statement>(node) {
+            return self.statement(statement);
+        }
+
+        if let Some(catch) = downcast_mut::<CatchBlock>(node) {
+            if let Some(variable) = &catch.var {
+                self.record(&variable.name, false, true);
+            }
+
+            for statement in catch.body.iter_mut() {
+                self.statement(statement);
+            }
+
+            return;
+        }
+
+        for child in node.children() {
+            self.node(child);
+        }
+    }
+
+    fn statement(&mut self, statement: &mut Statement) {
+        match statement {
+            // functions and classes have their own scope.
+            Statement::Function(_)
+            | Statement::Class(_)
+            | Statement::Trait(_)
+            | Statement::Interface(_)
+            | Statement::UnitEnum(_)
+            | Statement::BackedEnum(_) => {}
+            Statement::Foreach(foreach) => {
+                match &mut foreach.iterator {
+                    ForeachStatementIterator::Value {
+                        expression, value, ..
+                    } => {
+                        self.expression(expression);
+                        self.target(value, false);
+                    }
+                    ForeachStatementIterator::KeyAndValue {
+                        expression,
+                        key,
+                        value,
+                        ..
+                    } => {
+                        self.expression(expression);
+                        self.target(key, false);
+                        self.target(value, false);
+                    }
+                }
+
+                self.node(&mut foreach.body);
+            }
+            Statement::Global(global) => {
+                for variable in global.variables.iter_mut() {
+                    self.variable(variable, false, true);
+                }
+            }
+            Statement::Static(r#static) => {
+                for var in r#static.vars.iter_mut() {
+                    self.variable(&mut var.var, false, true);
+
+                    if let Some(default) = &mut var.default {
+                        self.expression(default);
+                    }
+                }
+            }
+            _ => {
+                for child in statement.children() {
+                    self.node(child);
+                }
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &mut Expression) {
+        match expression {
+            Expression::Variable(variable) => self.variable(variable, true, false),
+            Expression::AssignmentOperation(AssignmentOperation::AssignByReference {
+                left,
+                right,
+                ..
+            }) => {
+                self.target(left, false);
+                self.target(right, true);
+            }
+            Expression::AssignmentOperation(operation) => {
+                let compound = !matches!(operation, AssignmentOperation::Assign { .. });
+
+                let mut children = operation.children();
+                let right = children.pop();
+                let left = children.pop();
+
+                if let Some(left) = left.and_then(downcast_mut::<Expression>) {
+                    self.target(left, compound);
+                }
+
+                if let Some(right) = right {
+                    self.node(right);
+                }
+            }
+            Expression::ArithmeticOperation(
+                ArithmeticOperation::PreIncrement { right: operand, .. }
+                | ArithmeticOperation::PreDecrement { right: operand, .. }
+                | ArithmeticOperation::PostIncrement { left: operand, .. }
+                | ArithmeticOperation::PostDecrement { left: operand, .. },
+            ) => self.target(operand, true),
+            Expression::Unset { arguments, .. } => {
+                for argument in arguments.children() {
+                    for value in argument.children() {
+                        match downcast_mut::<Expression>(value) {
+                            Some(value) => self.target(value, false),
+                            None => self.node(value),
+                        }
+                    }
+                }
+            }
+            Expression::FunctionCall { target, arguments } => {
+                if let Expression::Identifier(Identifier::SimpleIdentifier(identifier)) =
+                    target.as_ref()
+                {
+                    let name = identifier.value.to_ascii_lowercase();
+                    let name = name.strip_prefix(b"\\").unwrap_or(&name);
+
+                    if DYNAMIC_FUNCTIONS.contains(&name) {
+                        self.dynamic = true;
+                    }
+                }
+
+                self.expression(target);
+                self.node(arguments);
+            }
+            Expression::Closure(function) => {
+                if let Some(uses) = &function.uses {
+                    for variable in uses.variables.inner.iter() {
+                        self.record(&variable.variable.name, true, variable.ampersand.is_some());
+                    }
+                }
+
+                if function.r#static.is_none() && closure(function).captures_this() {
+                    self.this = true;
+                }
+            }
+            Expression::ArrowFunction(function) => {
+                let captures = arrow_function(function);
+                for capture in captures.captures {
+                    self.record(&capture.variable, true, false);
+                }
+
+                self.dynamic |= captures.dynamic;
+            }
+            // anonymous classes have their own scope.
+            Expression::AnonymousClass(_) => {}
+            _ => {
+                for child in expression.children() {
+                    self.node(child);
+                }
+            }
+        }
+    }
+
+    fn variable(&mut self, variable: &mut Variable, read: bool, written: bool) {
+        match variable {
+            Variable::SimpleVariable(variable) => self.record(&variable.name, read, written),
+            _ => {
+                self.dynamic = true;
+
+                for child in variable.children() {
+                    self.node(child);
+                }
+            }
+        }
+    }
+
+    // Record the variables of an expression that is being assigned to.
+    fn target(&mut self, expression: &mut Expression, read: bool) {
+        match expression {
+            Expression::Variable(variable) => self.variable(variable, read, true),
+            Expression::ArrayIndex { array, index, .. } => {
+                self.target(array, true);
+
+                if let Some(index) = index {
+                    self.expression(index);
+                }
+            }
+            Expression::List(list) => {
+                for item in list.items.iter_mut() {
+                    match item {
+                        ListEntry::Skipped => {}
+                        ListEntry::Value { value } => self.target(value, false),
+                        ListEntry::KeyValue { key, value, .. } => {
+                            self.expression(key);
+                            self.target(value, false);
+                        }
+                    }
+                }
+            }
+            Expression::ShortArray { items, .. } => {
+                for item in items.inner.iter_mut() {
+                    match item {
+                        ArrayItem::Skipped => {}
+                        ArrayItem::Value { value }
+                        | ArrayItem::ReferencedValue { value, .. }
+                        | ArrayItem::SpreadValue { value, .. } => self.target(value, false),
+                        ArrayItem::KeyValue { key, value, .. }
+                        | ArrayItem::ReferencedKeyValue { key, value, .. } => {
+                            self.expression(key);
+                            self.target(value, false);
+                        }
+                    }
+                }
+            }
+            _ => self.expression(expression),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn analyse_code(code: &str) -> Vec<ClosureCaptures> {
+        // `$this` in a static closure or arrow function is reported (`E053`) without stopping
+        // the parser, so the partial program is complete.
+        let mut program = parse(code).unwrap_or_else(|stack| stack.partial);
+
+        program
+            .iter_mut()
+            .flat_map(|statement| analyse(statement))
+            .collect()
+    }
+
+    #[test]
+    fn test_closure_use_reads_and_writes() {
+        let captures = analyse_code("<?php function () use ($a, &$b, $c) { $b = $a + 1; };");
+
+        assert_eq!(captures.len(), 1);
+        assert_eq!(captures[0].kind, FunctionKind::Closure);
+
+        let a = captures[0].get(b"$a").unwrap();
+        assert_eq!(a.kind, CaptureKind::Use);
+        assert!(a.read && !a.written);
+
+        let b = captures[0].get(b"$b").unwrap();
+        assert_eq!(b.kind, CaptureKind::UseByReference);
+        assert!(!b.read && b.written);
+
+        let c = captures[0].get(b"$c").unwrap();
+        assert!(!c.read && !c.written);
+
+        assert!(captures[0].has_writes());
+    }
+
+    #[test]
+    fn test_arrow_function_implicit_captures() {
+        let captures = analyse_code("<?php fn ($x) => $x + $y + $_GET['z'] + $this->w;");
+
+        assert_eq!(captures.len(), 1);
+        assert_eq!(captures[0].kind, FunctionKind::ArrowFunction);
+        assert!(captures[0].get(b"$x").is_none());
+        assert!(captures[0].get(b"$_GET").is_none());
+
+        let y = captures[0].get(b"$y").unwrap();
+        assert_eq!(y.kind, CaptureKind::Implicit);
+        assert!(y.read && !y.written);
+
+        assert!(captures[0].captures_this());
+        assert!(!captures[0].has_writes());
+    }
+
+    #[test]
+    fn test_static_functions_do_not_capture_this() {
+        let captures = analyse_code("<?php static fn () => $this; static function () { $this; };");
+
+        assert_eq!(captures.len(), 2);
+        assert!(!captures[0].captures_this());
+        assert!(!captures[1].captures_this());
+    }
+
+    #[test]
+    fn test_nested_functions() {
+        let captures = analyse_code(
+            "<?php function () use ($a) { return fn () => function () use ($a) { $this; }; };",
+        );
+
+        assert_eq!(captures.len(), 3);
+
+        let a = captures[0].get(b"$a").unwrap();
+        assert!(a.read && !a.written);
+        assert!(captures[0].captures_this());
+
+        let a = captures[1].get(b"$a").unwrap();
+        assert_eq!(a.kind, CaptureKind::Implicit);
+        assert!(captures[1].captures_this());
+    }
+
+    #[test]
+    fn test_writes_through_destructuring_and_increments() {
+        let captures = analyse_code(
+            "<?php function () use ($a, $b, $c, $d) { [$a, [$b]] = $x; $c[] = 1; $d++; };",
+        );
+
+        for name in [&b"$a"[..], b"$b", b"$c", b"$d"] {
+            assert!(captures[0].get(name).unwrap().written);
+        }
+
+        assert!(captures[0].get(b"$c").unwrap().read);
+        assert!(!captures[0].get(b"$a").unwrap().read);
+    }
+
+    #[test]
+    fn test_dynamic_variable_access() {
+        let captures = analyse_code("<?php function () use ($a) { return compact('a'); };");
+
+        assert!(captures[0].dynamic);
+
+        let captures = analyse_code("<?php fn () => $$name;");
+
+        assert!(captures[0].dynamic);
+    }
+}
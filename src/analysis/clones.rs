@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::analysis::structural::fingerprint;
+use crate::downcast::downcast_mut;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::Expression;
+use crate::parser::ast::Statement;
+use crate::traverser::Visitor;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CloneOptions {
+    /// The minimum size of a function body to be considered, counted in AST nodes,
+    /// so trivial functions such as getters are not reported.
+    pub min_size: usize,
+}
+
+impl Default for CloneOptions {
+    fn default() -> Self {
+        Self { min_size: 40 }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CloneKind {
+    /// The code is identical, except for formatting and comments.
+    Exact,
+    /// The code is identical, except for the names of variables and identifiers,
+    /// and the values of literals.
+    Parameterized,
+}
+
+/// A function, method, or closure.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CloneLocation {
+    pub file: PathBuf,
+    /// The name of the function or method, `None` for closures.
+    pub name: Option<ByteString>,
+    /// The span of the `function` keyword.
+    pub start: Span,
+    /// The span of the closing brace of the body.
+    pub end: Span,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CloneGroup {
+    pub kind: CloneKind,
+    /// The size of each clone, counted in AST nodes.
+    pub size: usize,
+    pub members: Vec<CloneLocation>,
+}
+
+#[derive(Debug, Clone)]
+struct Unit {
+    location: CloneLocation,
+    size: usize,
+    exact: u64,
+    parameterized: u64,
+}
+
+/// Finds functions, methods, and closures duplicated across a project.
+///
+/// The parameters, return type, and body of each function are compared, ignoring its name,
+/// attributes, and modifiers, using [`crate::analysis::structural`] hashes.
+#[derive(Debug, Default, Clone)]
+pub struct CloneDetector {
+    options: CloneOptions,
+    units: Vec<Unit>,
+}
+
+impl CloneDetector {
+    pub fn new(options: CloneOptions) -> Self {
+        Self {
+            options,
+            units: Vec::new(),
+        }
+    }
+
+    /// Collect the functions, methods, and closures of a single file.
+    pub fn add(&mut self, file: &Path, program: &mut [Statement]) {
+        let mut collector = Collector {
+            file: file.to_path_buf(),
+            min_size: self.options.min_size,
+            units: Vec::new(),
+        };
+
+        for statement in program.iter_mut() {
+            let _ = collector.visit_node(statement);
+        }
+
+        self.units.extend(collector.units);
+    }
+
+    /// Group the collected functions by exact clones, then by parameterized clones.
+    ///
+    /// A parameterized group is only reported when its members are not all exact
+    /// clones of each other. Groups are sorted by decreasing size.
+    pub fn groups(&self) -> Vec<CloneGroup> {
+        let mut groups = Vec::new();
+
+        for (kind, key) in [
+            (
+                CloneKind::Exact,
+                (|unit: &Unit| unit.exact) as fn(&Unit) -> u64,
+            ),
+            (CloneKind::Parameterized, |unit: &Unit| unit.parameterized),
+        ] {
+            let mut buckets: HashMap<u64, Vec<&Unit>> = HashMap::new();
+            let mut order = Vec::new();
+            for unit in &self.units {
+                let bucket = buckets.entry(key(unit)).or_default();
+                if bucket.is_empty() {
+                    order.push(key(unit));
+                }
+
+                bucket.push(unit);
+            }
+
+            for hash in order {
+                let members = &buckets[&hash];
+                if members.len() < 2 {
+                    continue;
+                }
+
+                if kind == CloneKind::Parameterized
+                    && members.iter().all(|unit| unit.exact == members[0].exact)
+                {
+                    continue;
+                }
+
+                groups.push(CloneGroup {
+                    kind,
+                    size: members[0].size,
+                    members: members.iter().map(|unit| unit.location.clone()).collect(),
+                });
+            }
+        }
+
+        groups.sort_by_key(|group| std::cmp::Reverse(group.size));
+
+        groups
+    }
+}
+
+struct Collector {
+    file: PathBuf,
+    min_size: usize,
+    units: Vec<Unit>,
+}
+
+impl Collector {
+    fn unit<T: Serialize>(&mut self, code: &T, name: Option<ByteString>, start: Span, end: Span) {
+        let exact = fingerprint(code, false);
+        if exact.size < self.min_size {
+            return;
+        }
+
+        self.units.push(Unit {
+            location: CloneLocation {
+                file: self.file.clone(),
+                name,
+                start,
+                end,
+            },
+            size: exact.size,
+            exact: exact.hash,
+            parameterized: fingerprint(code, true).hash,
+        });
+    }
+}
+
+impl Visitor<()> for Collector {
+    fn visit(&mut self, node: &mut dyn Node) -> Result<(), ()> {
+        if let Some(function) = downcast_mut::<FunctionStatement>(node) {
+            let code = (
+                &function.parameters,
+                &function.return_type,
+                &function.body.statements,
+            );
+
+            self.unit(
+                &code,
+                Some(function.name.value.clone()),
+                function.function,
+                function.body.right_brace,
+            );
+        } else if let Some(method) = downcast_mut::<ConcreteMethod>(node) {
+            let code = (
+                &method.parameters,
+                &method.return_type,
+                &method.body.statements,
+            );
+
+            self.unit(
+                &code,
+                Some(method.name.value.clone()),
+                method.function,
+                method.body.right_brace,
+            );
+        } else if let Some(Expression::Closure(closure)) = downcast_mut::<Expression>(node) {
+            let code = (
+                &closure.parameters,
+                &closure.return_type,
+                &closure.body.statements,
+            );
+
+            self.unit(&code, None, closure.function, closure.body.right_brace);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::CloneDetector;
+    use super::CloneKind;
+    use super::CloneOptions;
+
+    #[test]
+    fn test_clone_groups() {
+        let mut a = crate::parse(
+            r#"<?php
+            function total(array $items): int {
+                $sum = 0;
+                foreach ($items as $item) { $sum += $item->price * 2; }
+                return $sum;
+            }
+
+            class Cart {
+                // Same as `total()`, formatted differently.
+                public function total(array $items): int
+                {
+                    $sum = 0;
+                    foreach ($items as $item) {
+                        $sum += $item->price * 2;
+                    }
+
+                    return $sum;
+                }
+
+                public function name(): string { return $this->name; }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let mut b = crate::parse(
+            r#"<?php
+            $weight = function (array $parcels): int {
+                $kg = 0;
+                foreach ($parcels as $parcel) { $kg += $parcel->weight * 3; }
+                return $kg;
+            };
+            "#,
+        )
+        .unwrap();
+
+        let mut detector = CloneDetector::new(CloneOptions::default());
+        detector.add(Path::new("a.php"), &mut a);
+        detector.add(Path::new("b.php"), &mut b);
+
+        let groups = detector.groups();
+        assert_eq!(groups.len(), 2);
+
+        let exact = groups
+            .iter()
+            .find(|group| group.kind == CloneKind::Exact)
+            .unwrap();
+        let names = exact
+            .members
+            .iter()
+            .map(|member| (member.name.as_ref().unwrap().to_string(), member.start.line))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![("total".to_string(), 2), ("total".to_string(), 10)]
+        );
+        assert_eq!(exact.members[1].end.line, 18);
+
+        let parameterized = groups
+            .iter()
+            .find(|group| group.kind == CloneKind::Parameterized)
+            .unwrap();
+        assert_eq!(parameterized.members.len(), 3);
+        assert_eq!(parameterized.members[2].file, Path::new("b.php"));
+        assert_eq!(parameterized.members[2].name, None);
+    }
+}
@@ -0,0 +1,313 @@
+use std::fmt::Display;
+
+use crate::downcast::downcast_mut;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::operators::ComparisonOperation;
+use crate::parser::ast::Expression;
+use crate::parser::ast::Statement;
+use crate::traverser::Visitor;
+
+use super::const_eval::is_numeric;
+use super::const_eval::to_value;
+use super::const_eval::PhpValue;
+
+/// The operator of an equality comparison.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EqualityOperator {
+    Equal,          // `==`
+    NotEqual,       // `!=`
+    AngledNotEqual, // `<>`
+    Identical,      // `===`
+    NotIdentical,   // `!==`
+}
+
+impl EqualityOperator {
+    /// Whether the operator compares both the types and the values of its operands.
+    pub fn is_strict(&self) -> bool {
+        matches!(
+            self,
+            EqualityOperator::Identical | EqualityOperator::NotIdentical
+        )
+    }
+
+    pub fn is_negated(&self) -> bool {
+        matches!(
+            self,
+            EqualityOperator::NotEqual
+                | EqualityOperator::AngledNotEqual
+                | EqualityOperator::NotIdentical
+        )
+    }
+
+    /// The operator comparing strictly with the same negation, e.g. `!==` for `!=`.
+    pub fn strict(&self) -> Self {
+        if self.is_negated() {
+            EqualityOperator::NotIdentical
+        } else {
+            EqualityOperator::Identical
+        }
+    }
+}
+
+impl Display for EqualityOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EqualityOperator::Equal => write!(f, "=="),
+            EqualityOperator::NotEqual => write!(f, "!="),
+            EqualityOperator::AngledNotEqual => write!(f, "<>"),
+            EqualityOperator::Identical => write!(f, "==="),
+            EqualityOperator::NotIdentical => write!(f, "!=="),
+        }
+    }
+}
+
+/// The kind of a literal operand, as far as PHP's loose comparison rules are concerned.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LiteralKind {
+    Null,          // `null`
+    True,          // `true`
+    False,         // `false`
+    Zero,          // `0`, `0.0`
+    Integer,       // `1`
+    Float,         // `1.5`
+    EmptyString,   // `''`
+    NumericString, // `'0'`, `'1e3'`
+    String,        // `'foo'`
+    EmptyArray,    // `[]`
+    Array,         // `[1, 2]`
+}
+
+impl LiteralKind {
+    /// The kind of the given expression, if it is a literal.
+    pub fn of(expression: &Expression) -> Option<Self> {
+        Some(match to_value(expression)? {
+            PhpValue::Null => LiteralKind::Null,
+            PhpValue::Bool(true) => LiteralKind::True,
+            PhpValue::Bool(false) => LiteralKind::False,
+            PhpValue::Int(0) => LiteralKind::Zero,
+            PhpValue::Int(_) => LiteralKind::Integer,
+            PhpValue::Float(0.0) => LiteralKind::Zero,
+            PhpValue::Float(_) => LiteralKind::Float,
+            PhpValue::String(value) if value.is_empty() => LiteralKind::EmptyString,
+            PhpValue::String(value) if is_numeric(&value) => LiteralKind::NumericString,
+            PhpValue::String(_) => LiteralKind::String,
+            PhpValue::Array(array) if array.is_empty() => LiteralKind::EmptyArray,
+            PhpValue::Array(_) => LiteralKind::Array,
+        })
+    }
+
+    /// Whether loosely comparing with the literal also holds for values of other types, or
+    /// of other representations, e.g. `false`, `0`, `''` and `[]` for `null`, or `'1e3'` for
+    /// `'1000'`.
+    pub fn is_ambiguous(&self) -> bool {
+        !matches!(
+            self,
+            LiteralKind::Integer | LiteralKind::Float | LiteralKind::String | LiteralKind::Array
+        )
+    }
+}
+
+impl Display for LiteralKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LiteralKind::Null => write!(f, "null"),
+            LiteralKind::True => write!(f, "true"),
+            LiteralKind::False => write!(f, "false"),
+            LiteralKind::Zero => write!(f, "zero"),
+            LiteralKind::Integer => write!(f, "integer"),
+            LiteralKind::Float => write!(f, "float"),
+            LiteralKind::EmptyString => write!(f, "empty string"),
+            LiteralKind::NumericString => write!(f, "numeric string"),
+            LiteralKind::String => write!(f, "string"),
+            LiteralKind::EmptyArray => write!(f, "empty array"),
+            LiteralKind::Array => write!(f, "array"),
+        }
+    }
+}
+
+/// An operand of an equality comparison.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Operand {
+    pub start: Span,
+    pub end: Span,
+    /// The kind of the operand, or `None` when it is not a literal.
+    pub literal: Option<LiteralKind>,
+}
+
+impl Operand {
+    fn new(expression: &Expression) -> Self {
+        Self {
+            start: expression.start(),
+            end: expression.end(),
+            literal: LiteralKind::of(expression),
+        }
+    }
+}
+
+/// A single `==`, `!=`, `<>`, `===` or `!==` comparison.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Comparison {
+    pub operator: EqualityOperator,
+    pub span: Span, // the operator
+    pub left: Operand,
+    pub right: Operand,
+}
+
+impl Comparison {
+    pub fn is_strict(&self) -> bool {
+        self.operator.is_strict()
+    }
+
+    pub fn is_loose(&self) -> bool {
+        !self.operator.is_strict()
+    }
+
+    /// The kind of the literal the comparison is made against, i.e. of the right operand when
+    /// it is a literal, and of the left operand otherwise, e.g. for `null == $a`.
+    pub fn literal(&self) -> Option<LiteralKind> {
+        self.right.literal.or(self.left.literal)
+    }
+
+    /// Whether the comparison is loose, and made against an ambiguous literal, see
+    /// [`LiteralKind::is_ambiguous`].
+    pub fn is_ambiguous(&self) -> bool {
+        self.is_loose() && self.literal().is_some_and(|kind| kind.is_ambiguous())
+    }
+}
+
+/// Collect every equality comparison within the given program, in source order.
+pub fn comparisons(program: &mut [Statement]) -> Vec<Comparison> {
+    let mut finder = Finder::default();
+    for statement in program.iter_mut() {
+        let _ = finder.visit_node(statement);
+    }
+
+    finder.comparisons
+}
+
+/// Collect the loose comparisons within the given program which are made against an
+/// ambiguous literal, e.g. `$a == null` or `$a != ''`, in source order.
+pub fn ambiguous_comparisons(program: &mut [Statement]) -> Vec<Comparison> {
+    comparisons(program)
+        .into_iter()
+        .filter(|comparison| comparison.is_ambiguous())
+        .collect()
+}
+
+#[derive(Debug, Default)]
+struct Finder {
+    comparisons: Vec<Comparison>,
+}
+
+impl Visitor<()> for Finder {
+    fn visit(&mut self, node: &mut dyn Node) -> Result<(), ()> {
+        if let Some(operation) = downcast_mut::<ComparisonOperation>(node) {
+            let (operator, left, span, right) = match operation {
+                ComparisonOperation::Equal {
+                    left,
+                    double_equals,
+                    right,
+                } => (EqualityOperator::Equal, left, double_equals, right),
+                ComparisonOperation::NotEqual {
+                    left,
+                    bang_equals,
+                    right,
+                } => (EqualityOperator::NotEqual, left, bang_equals, right),
+                ComparisonOperation::AngledNotEqual {
+                    left,
+                    angled_left_right,
+                    right,
+                } => (
+                    EqualityOperator::AngledNotEqual,
+                    left,
+                    angled_left_right,
+                    right,
+                ),
+                ComparisonOperation::Identical {
+                    left,
+                    triple_equals,
+                    right,
+                } => (EqualityOperator::Identical, left, triple_equals, right),
+                ComparisonOperation::NotIdentical {
+                    left,
+                    bang_double_equals,
+                    right,
+                } => (
+                    EqualityOperator::NotIdentical,
+                    left,
+                    bang_double_equals,
+                    right,
+                ),
+                _ => return Ok(()),
+            };
+
+            self.comparisons.push(Comparison {
+                operator,
+                span: *span,
+                left: Operand::new(left),
+                right: Operand::new(right),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ambiguous_comparisons;
+    use super::comparisons;
+    use super::EqualityOperator;
+    use super::LiteralKind;
+
+    #[test]
+    fn test_comparisons() {
+        let mut program = crate::parse(
+            "<?php if ($a == null || '' !== $b) { foo($c <> [1] && $d != -0.0, $e === $f); } $g < 1;",
+        )
+        .unwrap();
+
+        let comparisons = comparisons(&mut program);
+
+        assert_eq!(
+            comparisons
+                .iter()
+                .map(|comparison| (comparison.operator, comparison.literal()))
+                .collect::<Vec<_>>(),
+            vec![
+                (EqualityOperator::Equal, Some(LiteralKind::Null)),
+                (
+                    EqualityOperator::NotIdentical,
+                    Some(LiteralKind::EmptyString)
+                ),
+                (EqualityOperator::AngledNotEqual, Some(LiteralKind::Array)),
+                (EqualityOperator::NotEqual, Some(LiteralKind::Zero)),
+                (EqualityOperator::Identical, None),
+            ]
+        );
+        assert_eq!(comparisons[0].span.column, 14);
+        assert_eq!(comparisons[1].left.literal, Some(LiteralKind::EmptyString));
+        assert_eq!(comparisons[1].right.literal, None);
+        assert_eq!(
+            comparisons[3].operator.strict(),
+            EqualityOperator::NotIdentical
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_comparisons() {
+        let mut program = crate::parse(
+            "<?php $a == 'foo'; $a == '1e3'; $a != false; null == $a; $a === null; $a == 1; $a == $b;",
+        )
+        .unwrap();
+
+        assert_eq!(
+            ambiguous_comparisons(&mut program)
+                .iter()
+                .map(|comparison| comparison.literal().unwrap().to_string())
+                .collect::<Vec<_>>(),
+            vec!["numeric string", "false", "null"]
+        );
+    }
+}
@@ -0,0 +1,1230 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use serde::ser::SerializeMap;
+use serde::ser::SerializeSeq;
+use serde::Serialize;
+
+use crate::lexer::byte_string::ByteString;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::operators::ArithmeticOperation;
+use crate::parser::ast::operators::BitwiseOperation;
+use crate::parser::ast::operators::ComparisonOperation;
+use crate::parser::ast::operators::LogicalOperation;
+use crate::parser::ast::utils::CommaSeparated;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::CastKind;
+use crate::parser::ast::Expression;
+use crate::parser::ast::MagicConstant;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StringPart;
+
+/// A PHP array key, string keys holding a decimal integer are stored as integers.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum ArrayKey {
+    Int(i64),
+    String(ByteString),
+}
+
+impl From<i64> for ArrayKey {
+    fn from(key: i64) -> Self {
+        ArrayKey::Int(key)
+    }
+}
+
+impl From<&str> for ArrayKey {
+    fn from(key: &str) -> Self {
+        ArrayKey::from(ByteString::from(key))
+    }
+}
+
+impl From<ByteString> for ArrayKey {
+    fn from(key: ByteString) -> Self {
+        let bytes = &key.bytes;
+        let canonical = match bytes.first() {
+            Some(b'-') => bytes.len() > 1 && bytes[1] != b'0',
+            Some(b'0') => bytes.len() == 1,
+            Some(_) => true,
+            None => false,
+        } && bytes
+            .iter()
+            .enumerate()
+            .all(|(i, b)| b.is_ascii_digit() || (i == 0 && *b == b'-'));
+
+        match canonical
+            .then(|| std::str::from_utf8(bytes).ok()?.parse::<i64>().ok())
+            .flatten()
+        {
+            Some(key) => ArrayKey::Int(key),
+            None => ArrayKey::String(key),
+        }
+    }
+}
+
+impl Display for ArrayKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArrayKey::Int(key) => write!(f, "{}", key),
+            ArrayKey::String(key) => write!(f, "{}", key),
+        }
+    }
+}
+
+/// An ordered PHP array.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct PhpArray {
+    entries: Vec<(ArrayKey, PhpValue)>,
+    positions: HashMap<ArrayKey, usize>,
+    next_index: i64,
+}
+
+impl PhpArray {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a value, replacing the value of an existing key in place.
+    pub fn insert(&mut self, key: ArrayKey, value: PhpValue) {
+        if let ArrayKey::Int(index) = key {
+            if index >= self.next_index {
+                self.next_index = index.saturating_add(1);
+            }
+        }
+
+        match self.positions.get(&key) {
+            Some(position) => self.entries[*position].1 = value,
+            None => {
+                self.positions.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+            }
+        }
+    }
+
+    /// Append a value using the next integer key.
+    pub fn push(&mut self, value: PhpValue) {
+        self.insert(ArrayKey::Int(self.next_index), value);
+    }
+
+    pub fn get(&self, key: &ArrayKey) -> Option<&PhpValue> {
+        self.positions
+            .get(key)
+            .map(|position| &self.entries[*position].1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether the keys are `0` to `len - 1`, in order.
+    pub fn is_list(&self) -> bool {
+        self.entries
+            .iter()
+            .enumerate()
+            .all(|(index, (key, _))| *key == ArrayKey::Int(index as i64))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&ArrayKey, &PhpValue)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum PhpValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(ByteString),
+    Array(PhpArray),
+}
+
+impl PhpValue {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            PhpValue::Null => "null",
+            PhpValue::Bool(_) => "bool",
+            PhpValue::Int(_) => "int",
+            PhpValue::Float(_) => "float",
+            PhpValue::String(_) => "string",
+            PhpValue::Array(_) => "array",
+        }
+    }
+
+    pub fn to_bool(&self) -> bool {
+        match self {
+            PhpValue::Null => false,
+            PhpValue::Bool(value) => *value,
+            PhpValue::Int(value) => *value != 0,
+            PhpValue::Float(value) => *value != 0.0,
+            PhpValue::String(value) => !(value.is_empty() || value.bytes == b"0"),
+            PhpValue::Array(array) => !array.is_empty(),
+        }
+    }
+
+    /// The string conversion of the value, or `None` for arrays.
+    pub fn to_php_string(&self) -> Option<ByteString> {
+        Some(match self {
+            PhpValue::Null | PhpValue::Bool(false) => ByteString::default(),
+            PhpValue::Bool(true) => b"1"[..].into(),
+            PhpValue::Int(value) => value.to_string().into(),
+            PhpValue::Float(value) => float_to_string(*value).into(),
+            PhpValue::String(value) => value.clone(),
+            PhpValue::Array(_) => return None,
+        })
+    }
+
+    fn to_number(&self) -> Option<PhpValue> {
+        match self {
+            PhpValue::Null | PhpValue::Bool(false) => Some(PhpValue::Int(0)),
+            PhpValue::Bool(true) => Some(PhpValue::Int(1)),
+            PhpValue::Int(_) | PhpValue::Float(_) => Some(self.clone()),
+            PhpValue::String(value) => numeric_prefix(value),
+            PhpValue::Array(_) => None,
+        }
+    }
+
+    fn to_int(&self) -> Option<i64> {
+        match self.to_number()? {
+            PhpValue::Int(value) => Some(value),
+            PhpValue::Float(value) if value.is_finite() => Some(value as i64),
+            _ => Some(0),
+        }
+    }
+
+    fn to_array_key(&self) -> Option<ArrayKey> {
+        Some(match self {
+            PhpValue::Null => ArrayKey::String(ByteString::default()),
+            PhpValue::Bool(value) => ArrayKey::Int(*value as i64),
+            PhpValue::Int(value) => ArrayKey::Int(*value),
+            PhpValue::Float(value) => ArrayKey::Int(*value as i64),
+            PhpValue::String(value) => ArrayKey::from(value.clone()),
+            PhpValue::Array(_) => return None,
+        })
+    }
+}
+
+/// Arrays are serialized as sequences when they are lists, and as maps otherwise.
+impl Serialize for PhpArray {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.is_list() {
+            let mut sequence = serializer.serialize_seq(Some(self.len()))?;
+            for (_, value) in self.iter() {
+                sequence.serialize_element(value)?;
+            }
+
+            sequence.end()
+        } else {
+            let mut map = serializer.serialize_map(Some(self.len()))?;
+            for (key, value) in self.iter() {
+                map.serialize_entry(&key.to_string(), value)?;
+            }
+
+            map.end()
+        }
+    }
+}
+
+impl Serialize for PhpValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            PhpValue::Null => serializer.serialize_unit(),
+            PhpValue::Bool(value) => serializer.serialize_bool(*value),
+            PhpValue::Int(value) => serializer.serialize_i64(*value),
+            PhpValue::Float(value) => serializer.serialize_f64(*value),
+            PhpValue::String(value) => serializer.serialize_str(&String::from_utf8_lossy(value)),
+            PhpValue::Array(array) => array.serialize(serializer),
+        }
+    }
+}
+
+/// Convert an expression consisting only of array and scalar literals, e.g. the contents of
+/// a `composer.json`-like configuration array, into a value.
+///
+/// Returns `None` for any other expression, including constants and operations other than
+/// the negation of a number; use an [`Evaluator`] to support those.
+pub fn to_value(expression: &Expression) -> Option<PhpValue> {
+    match expression {
+        Expression::Literal(literal) => Some(literal_value(literal)),
+        Expression::Bool { value, .. } => Some(PhpValue::Bool(*value)),
+        Expression::Null { .. } => Some(PhpValue::Null),
+        Expression::ArithmeticOperation(ArithmeticOperation::Negative { right, .. }) => {
+            match right.as_ref() {
+                Expression::Literal(literal @ (Literal::Integer(_) | Literal::Float(_))) => {
+                    match literal_value(literal) {
+                        PhpValue::Int(value) => Some(PhpValue::Int(-value)),
+                        PhpValue::Float(value) => Some(PhpValue::Float(-value)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }
+        }
+        Expression::ShortArray { items, .. } => to_array(items),
+        Expression::Array(array) => to_array(&array.items),
+        _ => None,
+    }
+}
+
+fn to_array(items: &CommaSeparated<ArrayItem>) -> Option<PhpValue> {
+    let mut array = PhpArray::new();
+    for item in items.inner.iter() {
+        match item {
+            ArrayItem::Value { value } => array.push(to_value(value)?),
+            ArrayItem::KeyValue { key, value, .. } => {
+                let key = match to_value(key)? {
+                    PhpValue::Array(_) => return None,
+                    key => key.to_array_key()?,
+                };
+
+                array.insert(key, to_value(value)?);
+            }
+            _ => return None,
+        }
+    }
+
+    Some(PhpValue::Array(array))
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EvalError {
+    /// The expression or statement can only be evaluated by running PHP,
+    /// e.g. a function call or a variable.
+    Unsupported,
+    UndefinedConstant(ByteString),
+    DivisionByZero,
+    UnsupportedOperandTypes {
+        operator: &'static str,
+        left: &'static str,
+        right: &'static str,
+    },
+}
+
+impl Display for EvalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvalError::Unsupported => write!(f, "expression cannot be evaluated statically"),
+            EvalError::UndefinedConstant(name) => write!(f, "undefined constant `{}`", name),
+            EvalError::DivisionByZero => write!(f, "division by zero"),
+            EvalError::UnsupportedOperandTypes {
+                operator,
+                left,
+                right,
+            } => write!(
+                f,
+                "unsupported operand types: {} {} {}",
+                left, operator, right
+            ),
+        }
+    }
+}
+
+pub type EvalResult = Result<PhpValue, EvalError>;
+
+/// Evaluates the constant-expression subset of PHP, e.g. the contents of a configuration file,
+/// without running PHP.
+///
+/// Literals, arrays, string and arithmetic operations, comparisons, casts, and constants are
+/// supported; anything depending on runtime state, such as variables and calls, is not.
+#[derive(Debug, Clone)]
+pub struct Evaluator {
+    constants: HashMap<Vec<u8>, PhpValue>,
+    file: Option<ByteString>,
+}
+
+impl Default for Evaluator {
+    fn default() -> Self {
+        let mut evaluator = Self {
+            constants: HashMap::new(),
+            file: None,
+        };
+
+        evaluator.define("PHP_EOL", PhpValue::String(b"\n"[..].into()));
+        evaluator.define("PHP_INT_MAX", PhpValue::Int(i64::MAX));
+        evaluator.define("PHP_INT_MIN", PhpValue::Int(i64::MIN));
+        evaluator.define("PHP_INT_SIZE", PhpValue::Int(8));
+        evaluator.define("PHP_FLOAT_EPSILON", PhpValue::Float(f64::EPSILON));
+        evaluator.define("PHP_FLOAT_MAX", PhpValue::Float(f64::MAX));
+        evaluator.define("PHP_FLOAT_MIN", PhpValue::Float(f64::MIN_POSITIVE));
+        evaluator.define("DIRECTORY_SEPARATOR", PhpValue::String(b"/"[..].into()));
+        evaluator.define("PATH_SEPARATOR", PhpValue::String(b":"[..].into()));
+        evaluator.define("NAN", PhpValue::Float(f64::NAN));
+        evaluator.define("INF", PhpValue::Float(f64::INFINITY));
+        evaluator.define("M_PI", PhpValue::Float(std::f64::consts::PI));
+        evaluator.define("E_ALL", PhpValue::Int(32767));
+
+        evaluator
+    }
+}
+
+impl Evaluator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define a global constant, replacing any existing definition.
+    pub fn define(&mut self, name: &str, value: PhpValue) {
+        self.constants.insert(name.as_bytes().to_vec(), value);
+    }
+
+    /// Set the path of the evaluated file, used for `__FILE__` and `__DIR__`.
+    pub fn with_file(mut self, file: &str) -> Self {
+        self.file = Some(file.into());
+
+        self
+    }
+
+    /// Evaluate the value returned by the given program, e.g. `<?php return ['debug' => true];`.
+    ///
+    /// A program without a top-level `return` statement evaluates to `null`.
+    pub fn evaluate_program(&self, program: &[Statement]) -> EvalResult {
+        for statement in program {
+            match statement {
+                Statement::FullOpeningTag(_)
+                | Statement::ShortOpeningTag(_)
+                | Statement::ClosingTag(_)
+                | Statement::Comment(_)
+                | Statement::Declare(_)
+                | Statement::Use(_)
+                | Statement::GroupUse(_)
+                | Statement::Noop(_) => {}
+                Statement::Return(statement) => {
+                    return match &statement.value {
+                        Some(value) => self.evaluate(value),
+                        None => Ok(PhpValue::Null),
+                    }
+                }
+                _ => return Err(EvalError::Unsupported),
+            }
+        }
+
+        Ok(PhpValue::Null)
+    }
+
+    pub fn evaluate(&self, expression: &Expression) -> EvalResult {
+        match expression {
+            Expression::Literal(literal) => Ok(literal_value(literal)),
+            Expression::Bool { value, .. } => Ok(PhpValue::Bool(*value)),
+            Expression::Null { .. } => Ok(PhpValue::Null),
+            Expression::Parenthesized { expr, .. } => self.evaluate(expr),
+            Expression::ShortArray { items, .. } => self.array(items),
+            Expression::Array(array) => self.array(&array.items),
+            Expression::InterpolatedString { parts, .. }
+            | Expression::Heredoc { parts, .. }
+            | Expression::Nowdoc { parts, .. } => {
+                let mut string = Vec::new();
+                for part in parts {
+                    match part {
+                        StringPart::Literal(literal) => string.extend_from_slice(&literal.value),
+                        StringPart::Expression(_) => return Err(EvalError::Unsupported),
+                    }
+                }
+
+                Ok(PhpValue::String(string.into()))
+            }
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                self.constant(&identifier.value)
+            }
+            Expression::MagicConstant(constant) => self.magic_constant(constant),
+            Expression::Concat { left, right, .. } => {
+                let (left, right) = (self.evaluate(left)?, self.evaluate(right)?);
+                match (left.to_php_string(), right.to_php_string()) {
+                    (Some(left), Some(right)) => {
+                        let mut bytes = left.bytes;
+                        bytes.extend_from_slice(&right);
+
+                        Ok(PhpValue::String(bytes.into()))
+                    }
+                    _ => Err(EvalError::UnsupportedOperandTypes {
+                        operator: ".",
+                        left: left.type_name(),
+                        right: right.type_name(),
+                    }),
+                }
+            }
+            Expression::ArithmeticOperation(operation) => self.arithmetic(operation),
+            Expression::BitwiseOperation(operation) => self.bitwise(operation),
+            Expression::ComparisonOperation(operation) => self.comparison(operation),
+            Expression::LogicalOperation(operation) => self.logical(operation),
+            Expression::Ternary {
+                condition,
+                then,
+                r#else,
+                ..
+            } => {
+                if self.evaluate(condition)?.to_bool() {
+                    self.evaluate(then)
+                } else {
+                    self.evaluate(r#else)
+                }
+            }
+            Expression::ShortTernary {
+                condition, r#else, ..
+            } => {
+                let condition = self.evaluate(condition)?;
+                if condition.to_bool() {
+                    Ok(condition)
+                } else {
+                    self.evaluate(r#else)
+                }
+            }
+            Expression::Coalesce { lhs, rhs, .. } => match self.lookup(lhs)? {
+                Some(PhpValue::Null) | None => self.evaluate(rhs),
+                Some(value) => Ok(value),
+            },
+            Expression::ArrayIndex { .. } => Ok(self.lookup(expression)?.unwrap_or(PhpValue::Null)),
+            Expression::Cast { kind, value, .. } => self.cast(kind, self.evaluate(value)?),
+            _ => Err(EvalError::Unsupported),
+        }
+    }
+
+    fn array(&self, items: &CommaSeparated<ArrayItem>) -> EvalResult {
+        let mut array = PhpArray::new();
+        for item in items.inner.iter() {
+            self.array_item(&mut array, item)?;
+        }
+
+        Ok(PhpValue::Array(array))
+    }
+
+    fn array_item(&self, array: &mut PhpArray, item: &ArrayItem) -> Result<(), EvalError> {
+        match item {
+            ArrayItem::Value { value } => array.push(self.evaluate(value)?),
+            ArrayItem::KeyValue { key, value, .. } => {
+                let key = self.evaluate(key)?;
+                let key = key
+                    .to_array_key()
+                    .ok_or(EvalError::UnsupportedOperandTypes {
+                        operator: "=>",
+                        left: key.type_name(),
+                        right: "mixed",
+                    })?;
+
+                array.insert(key, self.evaluate(value)?);
+            }
+            ArrayItem::SpreadValue { value, .. } => match self.evaluate(value)? {
+                PhpValue::Array(spread) => {
+                    for (key, value) in spread.iter() {
+                        match key {
+                            ArrayKey::Int(_) => array.push(value.clone()),
+                            ArrayKey::String(_) => array.insert(key.clone(), value.clone()),
+                        }
+                    }
+                }
+                value => {
+                    return Err(EvalError::UnsupportedOperandTypes {
+                        operator: "...",
+                        left: value.type_name(),
+                        right: "array",
+                    })
+                }
+            },
+            ArrayItem::Skipped
+            | ArrayItem::ReferencedValue { .. }
+            | ArrayItem::ReferencedKeyValue { .. } => return Err(EvalError::Unsupported),
+        }
+
+        Ok(())
+    }
+
+    /// Evaluate an expression which may read an undefined array offset, which is `None`.
+    fn lookup(&self, expression: &Expression) -> Result<Option<PhpValue>, EvalError> {
+        let (array, index) = match expression {
+            Expression::ArrayIndex {
+                array,
+                index: Some(index),
+                ..
+            } => (array, index),
+            Expression::ArrayIndex { index: None, .. } => return Err(EvalError::Unsupported),
+            _ => return self.evaluate(expression).map(Some),
+        };
+
+        let index = self.evaluate(index)?;
+        Ok(match self.lookup(array)? {
+            Some(PhpValue::Array(array)) => index
+                .to_array_key()
+                .and_then(|key| array.get(&key).cloned()),
+            Some(PhpValue::String(string)) => {
+                let offset = index.to_int().unwrap_or(0);
+                let offset = if offset < 0 {
+                    string.len() as i64 + offset
+                } else {
+                    offset
+                };
+
+                usize::try_from(offset)
+                    .ok()
+                    .and_then(|offset| string.get(offset))
+                    .map(|byte| PhpValue::String(vec![*byte].into()))
+            }
+            _ => None,
+        })
+    }
+
+    fn constant(&self, name: &[u8]) -> EvalResult {
+        let name = name.strip_prefix(b"\\").unwrap_or(name);
+
+        if let Some(value) = self.constants.get(name) {
+            return Ok(value.clone());
+        }
+
+        if name.eq_ignore_ascii_case(b"true") {
+            Ok(PhpValue::Bool(true))
+        } else if name.eq_ignore_ascii_case(b"false") {
+            Ok(PhpValue::Bool(false))
+        } else if name.eq_ignore_ascii_case(b"null") {
+            Ok(PhpValue::Null)
+        } else {
+            Err(EvalError::UndefinedConstant(name.into()))
+        }
+    }
+
+    fn magic_constant(&self, constant: &MagicConstant) -> EvalResult {
+        match constant {
+            MagicConstant::Line(span) => Ok(PhpValue::Int(span.line as i64)),
+            MagicConstant::File(_) => self
+                .file
+                .clone()
+                .map(PhpValue::String)
+                .ok_or(EvalError::Unsupported),
+            MagicConstant::Directory(_) => {
+                let file = self.file.as_ref().ok_or(EvalError::Unsupported)?;
+                let directory = match file.iter().rposition(|b| *b == b'/') {
+                    Some(0) => &file[..1],
+                    Some(position) => &file[..position],
+                    None => &b"."[..],
+                };
+
+                Ok(PhpValue::String(directory.into()))
+            }
+            _ => Err(EvalError::Unsupported),
+        }
+    }
+
+    fn arithmetic(&self, operation: &ArithmeticOperation) -> EvalResult {
+        let (operator, left, right) = match operation {
+            ArithmeticOperation::Addition { left, right, .. } => ("+", left, right),
+            ArithmeticOperation::Subtraction { left, right, .. } => ("-", left, right),
+            ArithmeticOperation::Multiplication { left, right, .. } => ("*", left, right),
+            ArithmeticOperation::Division { left, right, .. } => ("/", left, right),
+            ArithmeticOperation::Modulo { left, right, .. } => ("%", left, right),
+            ArithmeticOperation::Exponentiation { left, right, .. } => ("**", left, right),
+            ArithmeticOperation::Negative { right, .. } => {
+                return binary_arithmetic("*", PhpValue::Int(-1), self.evaluate(right)?)
+            }
+            ArithmeticOperation::Positive { right, .. } => {
+                return binary_arithmetic("*", PhpValue::Int(1), self.evaluate(right)?)
+            }
+            _ => return Err(EvalError::Unsupported),
+        };
+
+        binary_arithmetic(operator, self.evaluate(left)?, self.evaluate(right)?)
+    }
+
+    fn bitwise(&self, operation: &BitwiseOperation) -> EvalResult {
+        let (operator, left, right) = match operation {
+            BitwiseOperation::And { left, right, .. } => ("&", left, right),
+            BitwiseOperation::Or { left, right, .. } => ("|", left, right),
+            BitwiseOperation::Xor { left, right, .. } => ("^", left, right),
+            BitwiseOperation::LeftShift { left, right, .. } => ("<<", left, right),
+            BitwiseOperation::RightShift { left, right, .. } => (">>", left, right),
+            BitwiseOperation::Not { right, .. } => {
+                let value = self.evaluate(right)?;
+
+                return match value.to_int() {
+                    Some(value) => Ok(PhpValue::Int(!value)),
+                    None => Err(EvalError::UnsupportedOperandTypes {
+                        operator: "~",
+                        left: "",
+                        right: value.type_name(),
+                    }),
+                };
+            }
+        };
+
+        let (left, right) = (self.evaluate(left)?, self.evaluate(right)?);
+        let (a, b) = match (left.to_int(), right.to_int()) {
+            (Some(a), Some(b)) => (a, b),
+            _ => {
+                return Err(EvalError::UnsupportedOperandTypes {
+                    operator,
+                    left: left.type_name(),
+                    right: right.type_name(),
+                })
+            }
+        };
+
+        Ok(PhpValue::Int(match operator {
+            "&" => a & b,
+            "|" => a | b,
+            "^" => a ^ b,
+            "<<" => {
+                if b < 0 {
+                    return Err(EvalError::Unsupported);
+                }
+
+                if b >= 64 {
+                    0
+                } else {
+                    a << b
+                }
+            }
+            _ => {
+                if b < 0 {
+                    return Err(EvalError::Unsupported);
+                }
+
+                a >> b.min(63)
+            }
+        }))
+    }
+
+    fn comparison(&self, operation: &ComparisonOperation) -> EvalResult {
+        let (left, right) = match operation {
+            ComparisonOperation::Equal { left, right, .. }
+            | ComparisonOperation::Identical { left, right, .. }
+            | ComparisonOperation::NotEqual { left, right, .. }
+            | ComparisonOperation::AngledNotEqual { left, right, .. }
+            | ComparisonOperation::NotIdentical { left, right, .. }
+            | ComparisonOperation::LessThan { left, right, .. }
+            | ComparisonOperation::GreaterThan { left, right, .. }
+            | ComparisonOperation::LessThanOrEqual { left, right, .. }
+            | ComparisonOperation::GreaterThanOrEqual { left, right, .. }
+            | ComparisonOperation::Spaceship { left, right, .. } => {
+                (self.evaluate(left)?, self.evaluate(right)?)
+            }
+        };
+
+        let ordering = || compare(&left, &right);
+
+        Ok(match operation {
+            ComparisonOperation::Equal { .. } => PhpValue::Bool(loose_equals(&left, &right)),
+            ComparisonOperation::NotEqual { .. } | ComparisonOperation::AngledNotEqual { .. } => {
+                PhpValue::Bool(!loose_equals(&left, &right))
+            }
+            ComparisonOperation::Identical { .. } => PhpValue::Bool(identical(&left, &right)),
+            ComparisonOperation::NotIdentical { .. } => PhpValue::Bool(!identical(&left, &right)),
+            ComparisonOperation::LessThan { .. } => {
+                PhpValue::Bool(ordering() == Some(Ordering::Less))
+            }
+            ComparisonOperation::GreaterThan { .. } => {
+                PhpValue::Bool(ordering() == Some(Ordering::Greater))
+            }
+            ComparisonOperation::LessThanOrEqual { .. } => {
+                PhpValue::Bool(matches!(ordering(), Some(Ordering::Less | Ordering::Equal)))
+            }
+            ComparisonOperation::GreaterThanOrEqual { .. } => PhpValue::Bool(matches!(
+                ordering(),
+                Some(Ordering::Greater | Ordering::Equal)
+            )),
+            ComparisonOperation::Spaceship { .. } => PhpValue::Int(match ordering() {
+                Some(Ordering::Less) => -1,
+                Some(Ordering::Equal) => 0,
+                _ => 1,
+            }),
+        })
+    }
+
+    fn logical(&self, operation: &LogicalOperation) -> EvalResult {
+        Ok(PhpValue::Bool(match operation {
+            LogicalOperation::And { left, right, .. }
+            | LogicalOperation::LogicalAnd { left, right, .. } => {
+                self.evaluate(left)?.to_bool() && self.evaluate(right)?.to_bool()
+            }
+            LogicalOperation::Or { left, right, .. }
+            | LogicalOperation::LogicalOr { left, right, .. } => {
+                self.evaluate(left)?.to_bool() || self.evaluate(right)?.to_bool()
+            }
+            LogicalOperation::LogicalXor { left, right, .. } => {
+                self.evaluate(left)?.to_bool() ^ self.evaluate(right)?.to_bool()
+            }
+            LogicalOperation::Not { right, .. } => !self.evaluate(right)?.to_bool(),
+        }))
+    }
+
+    fn cast(&self, kind: &CastKind, value: PhpValue) -> EvalResult {
+        Ok(match kind {
+            CastKind::Bool => PhpValue::Bool(value.to_bool()),
+            CastKind::Int => match value {
+                PhpValue::Array(array) => PhpValue::Int(!array.is_empty() as i64),
+                value => PhpValue::Int(value.to_int().unwrap_or(0)),
+            },
+            CastKind::Float => match value.to_number() {
+                Some(PhpValue::Int(value)) => PhpValue::Float(value as f64),
+                Some(PhpValue::Float(value)) => PhpValue::Float(value),
+                _ => match value {
+                    PhpValue::Array(array) => PhpValue::Float(!array.is_empty() as i64 as f64),
+                    _ => PhpValue::Float(0.0),
+                },
+            },
+            CastKind::String => match value.to_php_string() {
+                Some(string) => PhpValue::String(string),
+                None => PhpValue::String(b"Array"[..].into()),
+            },
+            CastKind::Array => match value {
+                PhpValue::Array(array) => PhpValue::Array(array),
+                PhpValue::Null => PhpValue::Array(PhpArray::new()),
+                value => {
+                    let mut array = PhpArray::new();
+                    array.push(value);
+
+                    PhpValue::Array(array)
+                }
+            },
+            CastKind::Unset => PhpValue::Null,
+            CastKind::Object => return Err(EvalError::Unsupported),
+        })
+    }
+}
+
+fn literal_value(literal: &Literal) -> PhpValue {
+    match literal {
+        Literal::String(string) => {
+            let value = &string.value;
+            let value = match value.first() {
+                Some(b'\'' | b'"') if value.len() >= 2 => &value[1..value.len() - 1],
+                _ => &value[..],
+            };
+
+            PhpValue::String(value.into())
+        }
+        Literal::Integer(integer) => parse_integer(&integer.value),
+        Literal::Float(float) => {
+            let value = String::from_utf8_lossy(&float.value).replace('_', "");
+
+            PhpValue::Float(value.parse().unwrap_or(0.0))
+        }
+    }
+}
+
+fn parse_integer(value: &[u8]) -> PhpValue {
+    let value = String::from_utf8_lossy(value).replace('_', "");
+    let lowercase = value.to_ascii_lowercase();
+
+    let (digits, radix) = if let Some(digits) = lowercase.strip_prefix("0x") {
+        (digits, 16)
+    } else if let Some(digits) = lowercase.strip_prefix("0b") {
+        (digits, 2)
+    } else if let Some(digits) = lowercase.strip_prefix("0o") {
+        (digits, 8)
+    } else if lowercase.len() > 1 && lowercase.starts_with('0') {
+        (&lowercase[1..], 8)
+    } else {
+        (&lowercase[..], 10)
+    };
+
+    match i64::from_str_radix(digits, radix) {
+        Ok(value) => PhpValue::Int(value),
+        // Integers overflowing are interpreted as floats.
+        Err(_) => PhpValue::Float(digits.chars().fold(0.0, |total, digit| {
+            total * radix as f64 + digit.to_digit(radix).unwrap_or(0) as f64
+        })),
+    }
+}
+
+/// The number a string starts with, e.g. `5` for `"5 apples"`, or `None` when it does not start with a number.
+fn numeric_prefix(value: &[u8]) -> Option<PhpValue> {
+    numeric(value).map(|(number, _)| number)
+}
+
+/// Whether the whole string is numeric, allowing surrounding whitespace.
+pub(crate) fn is_numeric(value: &[u8]) -> bool {
+    let trimmed = value.trim_ascii_end();
+
+    numeric(trimmed).is_some_and(|(_, length)| length == trimmed.len())
+}
+
+/// The number a string starts with, and the length of the prefix including leading whitespace.
+fn numeric(value: &[u8]) -> Option<(PhpValue, usize)> {
+    let string = std::str::from_utf8(value).ok()?;
+    let trimmed = string.trim_start_matches([' ', '\t', '\n', '\r', '\x0b', '\x0c']);
+    let whitespace = string.len() - trimmed.len();
+
+    let mut end = 0;
+    let bytes = trimmed.as_bytes();
+    if matches!(bytes.first(), Some(b'+' | b'-')) {
+        end += 1;
+    }
+
+    let digits_start = end;
+    while bytes.get(end).is_some_and(|b| b.is_ascii_digit()) {
+        end += 1;
+    }
+
+    let mut float = false;
+    if bytes.get(end) == Some(&b'.') {
+        let mut fraction = end + 1;
+        while bytes.get(fraction).is_some_and(|b| b.is_ascii_digit()) {
+            fraction += 1;
+        }
+
+        if fraction > end + 1 || end > digits_start {
+            float = true;
+            end = fraction;
+        }
+    }
+
+    if end == digits_start {
+        return None;
+    }
+
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let mut exponent = end + 1;
+        if matches!(bytes.get(exponent), Some(b'+' | b'-')) {
+            exponent += 1;
+        }
+
+        if bytes.get(exponent).is_some_and(|b| b.is_ascii_digit()) {
+            while bytes.get(exponent).is_some_and(|b| b.is_ascii_digit()) {
+                exponent += 1;
+            }
+
+            float = true;
+            end = exponent;
+        }
+    }
+
+    let number = &trimmed[..end];
+    let value = match number.parse::<i64>() {
+        Ok(value) if !float => PhpValue::Int(value),
+        _ => PhpValue::Float(number.parse::<f64>().ok()?),
+    };
+
+    Some((value, whitespace + end))
+}
+
+fn float_to_string(value: f64) -> String {
+    if value.is_nan() {
+        "NAN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "INF" } else { "-INF" }.to_string()
+    } else if value == 0.0 || (1e-4..1e15).contains(&value.abs()) {
+        format!("{}", value)
+    } else {
+        // Formatted like `1.0E+25`.
+        let formatted = format!("{:e}", value);
+        let (mantissa, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+        let mantissa = if mantissa.contains('.') {
+            mantissa.to_string()
+        } else {
+            format!("{}.0", mantissa)
+        };
+
+        match exponent.strip_prefix('-') {
+            Some(exponent) => format!("{}E-{}", mantissa, exponent),
+            None => format!("{}E+{}", mantissa, exponent),
+        }
+    }
+}
+
+fn binary_arithmetic(operator: &'static str, left: PhpValue, right: PhpValue) -> EvalResult {
+    if let (PhpValue::Array(left), PhpValue::Array(right), "+") = (&left, &right, operator) {
+        let mut union = left.clone();
+        for (key, value) in right.iter() {
+            if union.get(key).is_none() {
+                union.insert(key.clone(), value.clone());
+            }
+        }
+
+        return Ok(PhpValue::Array(union));
+    }
+
+    let error = || EvalError::UnsupportedOperandTypes {
+        operator,
+        left: left.type_name(),
+        right: right.type_name(),
+    };
+
+    let (a, b) = match (left.to_number(), right.to_number()) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return Err(error()),
+    };
+
+    if operator == "%" {
+        let (a, b) = (a.to_int().unwrap_or(0), b.to_int().unwrap_or(0));
+        if b == 0 {
+            return Err(EvalError::DivisionByZero);
+        }
+
+        return Ok(PhpValue::Int(a.wrapping_rem(b)));
+    }
+
+    if let (PhpValue::Int(a), PhpValue::Int(b)) = (&a, &b) {
+        let (a, b) = (*a, *b);
+        let result = match operator {
+            "+" => a.checked_add(b),
+            "-" => a.checked_sub(b),
+            "*" => a.checked_mul(b),
+            "/" if b == 0 => return Err(EvalError::DivisionByZero),
+            "/" => (a.checked_rem(b) == Some(0))
+                .then(|| a.checked_div(b))
+                .flatten(),
+            _ => u32::try_from(b).ok().and_then(|b| a.checked_pow(b)),
+        };
+
+        if let Some(result) = result {
+            return Ok(PhpValue::Int(result));
+        }
+    }
+
+    let as_float = |value: &PhpValue| match value {
+        PhpValue::Int(value) => *value as f64,
+        PhpValue::Float(value) => *value,
+        _ => 0.0,
+    };
+    let (a, b) = (as_float(&a), as_float(&b));
+
+    Ok(PhpValue::Float(match operator {
+        "+" => a + b,
+        "-" => a - b,
+        "*" => a * b,
+        "/" if b == 0.0 => return Err(EvalError::DivisionByZero),
+        "/" => a / b,
+        _ => a.powf(b),
+    }))
+}
+
+fn identical(left: &PhpValue, right: &PhpValue) -> bool {
+    match (left, right) {
+        (PhpValue::Array(a), PhpValue::Array(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b.iter())
+                    .all(|((ak, av), (bk, bv))| ak == bk && identical(av, bv))
+        }
+        _ => left == right,
+    }
+}
+
+fn loose_equals(left: &PhpValue, right: &PhpValue) -> bool {
+    match (left, right) {
+        (PhpValue::Array(a), PhpValue::Array(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, value)| b.get(key).is_some_and(|other| loose_equals(value, other)))
+        }
+        _ => compare(left, right) == Some(Ordering::Equal),
+    }
+}
+
+/// Compare two values following PHP 8's comparison rules, `None` when they are not comparable.
+fn compare(left: &PhpValue, right: &PhpValue) -> Option<Ordering> {
+    match (left, right) {
+        (PhpValue::Bool(_), _) | (_, PhpValue::Bool(_)) | (PhpValue::Null, PhpValue::Null) => {
+            Some(left.to_bool().cmp(&right.to_bool()))
+        }
+        (PhpValue::Null, PhpValue::String(string)) => Some(b"".as_slice().cmp(&string[..])),
+        (PhpValue::String(string), PhpValue::Null) => Some(string[..].cmp(b"".as_slice())),
+        (PhpValue::Null, _) | (_, PhpValue::Null) => Some(left.to_bool().cmp(&right.to_bool())),
+        (PhpValue::String(a), PhpValue::String(b)) => {
+            if is_numeric(a) && is_numeric(b) {
+                compare_numbers(&numeric_prefix(a)?, &numeric_prefix(b)?)
+            } else {
+                Some(a[..].cmp(&b[..]))
+            }
+        }
+        (PhpValue::String(string), number @ (PhpValue::Int(_) | PhpValue::Float(_))) => {
+            if is_numeric(string) {
+                compare_numbers(&numeric_prefix(string)?, number)
+            } else {
+                Some(string[..].cmp(&number.to_php_string()?[..]))
+            }
+        }
+        (PhpValue::Int(_) | PhpValue::Float(_), PhpValue::String(_)) => {
+            compare(right, left).map(Ordering::reverse)
+        }
+        (PhpValue::Array(a), PhpValue::Array(b)) => match a.len().cmp(&b.len()) {
+            Ordering::Equal => {
+                for (key, value) in a.iter() {
+                    match compare(value, b.get(key)?)? {
+                        Ordering::Equal => continue,
+                        ordering => return Some(ordering),
+                    }
+                }
+
+                Some(Ordering::Equal)
+            }
+            ordering => Some(ordering),
+        },
+        (PhpValue::Array(_), _) => Some(Ordering::Greater),
+        (_, PhpValue::Array(_)) => Some(Ordering::Less),
+        _ => compare_numbers(left, right),
+    }
+}
+
+fn compare_numbers(left: &PhpValue, right: &PhpValue) -> Option<Ordering> {
+    match (left, right) {
+        (PhpValue::Int(a), PhpValue::Int(b)) => Some(a.cmp(b)),
+        (PhpValue::Int(a), PhpValue::Float(b)) => (*a as f64).partial_cmp(b),
+        (PhpValue::Float(a), PhpValue::Int(b)) => a.partial_cmp(&(*b as f64)),
+        (PhpValue::Float(a), PhpValue::Float(b)) => a.partial_cmp(b),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArrayKey;
+    use super::EvalError;
+    use super::Evaluator;
+    use super::PhpArray;
+    use super::PhpValue;
+    use crate::parser::ast::Statement;
+
+    fn evaluate(code: &str) -> Result<PhpValue, EvalError> {
+        let program = crate::parse(&format!("<?php return {};", code)).unwrap();
+
+        Evaluator::new()
+            .with_file("/app/config/app.php")
+            .evaluate_program(&program)
+    }
+
+    fn string(value: &str) -> PhpValue {
+        PhpValue::String(value.into())
+    }
+
+    #[test]
+    fn test_scalars_and_operators() {
+        assert_eq!(evaluate("'it\\'s'"), Ok(string("it's")));
+        assert_eq!(evaluate("\"a\\tb\""), Ok(string("a\tb")));
+        assert_eq!(
+            evaluate("0x1F + 0b11 + 017 + 1_000"),
+            Ok(PhpValue::Int(1049))
+        );
+        assert_eq!(evaluate("7 / 2"), Ok(PhpValue::Float(3.5)));
+        assert_eq!(evaluate("8 / 2"), Ok(PhpValue::Int(4)));
+        assert_eq!(evaluate("2 ** 3 - -1"), Ok(PhpValue::Int(9)));
+        assert_eq!(
+            evaluate("PHP_INT_MAX + 1"),
+            Ok(PhpValue::Float(9.223372036854776e18))
+        );
+        assert_eq!(evaluate("'v' . 1.5 . true . null"), Ok(string("v1.51")));
+        assert_eq!(
+            evaluate("1e25 . '|' . 2.0 . '|' . -0.0000015"),
+            Ok(string("1.0E+25|2|-1.5E-6"))
+        );
+        assert_eq!(
+            evaluate("__DIR__ . '/../storage'"),
+            Ok(string("/app/config/../storage"))
+        );
+        assert_eq!(evaluate("(1 | 4) << 2"), Ok(PhpValue::Int(20)));
+        assert_eq!(evaluate("'10' == '1e1'"), Ok(PhpValue::Bool(true)));
+        assert_eq!(evaluate("0 == 'a'"), Ok(PhpValue::Bool(false)));
+        assert_eq!(
+            evaluate("null == false && '1' !== 1"),
+            Ok(PhpValue::Bool(true))
+        );
+        assert_eq!(evaluate("'abc' <=> 'abd'"), Ok(PhpValue::Int(-1)));
+        assert_eq!(evaluate("false ?: 'default'"), Ok(string("default")));
+        assert_eq!(
+            evaluate("(int) '12abc' + (float) '0.5'"),
+            Ok(PhpValue::Float(12.5))
+        );
+    }
+
+    #[test]
+    fn test_arrays() {
+        let mut expected = PhpArray::new();
+        expected.insert(ArrayKey::from("name"), string("app"));
+        expected.insert(ArrayKey::Int(0), PhpValue::Int(1));
+        expected.insert(ArrayKey::Int(5), PhpValue::Int(2));
+        expected.insert(ArrayKey::Int(6), PhpValue::Int(3));
+        expected.insert(ArrayKey::Int(7), string("a"));
+        expected.insert(ArrayKey::from("debug"), PhpValue::Bool(false));
+
+        assert_eq!(
+            evaluate(
+                "['name' => 'app', 1, '5' => 2, 3, ...['a', 'debug' => true], 'debug' => false]"
+            ),
+            Ok(PhpValue::Array(expected))
+        );
+
+        assert_eq!(
+            evaluate("['a' => ['b' => 1]]['a']['b']"),
+            Ok(PhpValue::Int(1))
+        );
+        assert_eq!(
+            evaluate("['a' => 1]['missing'] ?? 'fallback'"),
+            Ok(string("fallback"))
+        );
+        assert_eq!(evaluate("'abc'[-1]"), Ok(string("c")));
+        assert_eq!(
+            evaluate("[1, 2] + [5, 6, 7] === [1, 2, 7]"),
+            Ok(PhpValue::Bool(true))
+        );
+    }
+
+    #[test]
+    fn test_unsupported_expressions() {
+        assert_eq!(evaluate("getenv('APP_ENV')"), Err(EvalError::Unsupported));
+        assert_eq!(evaluate("['key' => $value]"), Err(EvalError::Unsupported));
+        assert_eq!(evaluate("\"Hello $name\""), Err(EvalError::Unsupported));
+        assert_eq!(evaluate("1 % 0"), Err(EvalError::DivisionByZero));
+        assert_eq!(
+            evaluate("APP_NAME"),
+            Err(EvalError::UndefinedConstant("APP_NAME".into()))
+        );
+        assert_eq!(
+            evaluate("[] * 2"),
+            Err(EvalError::UnsupportedOperandTypes {
+                operator: "*",
+                left: "array",
+                right: "int"
+            })
+        );
+
+        let program = crate::parse("<?php echo 1; return 2;").unwrap();
+        assert_eq!(
+            Evaluator::new().evaluate_program(&program),
+            Err(EvalError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn test_to_value() {
+        let program = crate::parse(
+            r#"<?php return [
+                'name' => 'acme/app',
+                'require' => ['php' => '^8.1', 'ext-json' => '*'],
+                'keywords' => ['parser', "php"],
+                'version' => 1.5,
+                'priority' => -10,
+                'abandoned' => false,
+                'extra' => null,
+                'empty' => [],
+            ];"#,
+        )
+        .unwrap();
+        let expression = match &program[1] {
+            Statement::Return(statement) => statement.value.as_ref().unwrap(),
+            _ => unreachable!(),
+        };
+
+        let value = super::to_value(expression).unwrap();
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"name":"acme/app","require":{"php":"^8.1","ext-json":"*"},"keywords":["parser","php"],"version":1.5,"priority":-10,"abandoned":false,"extra":null,"empty":[]}"#
+        );
+
+        for code in ["['a' => FOO]", "[1 + 2]", "['a' . 'b' => 1]", "[...$a]"] {
+            let program = crate::parse(&format!("<?php {};", code)).unwrap();
+            let expression = match &program[1] {
+                Statement::Expression(statement) => &statement.expression,
+                _ => unreachable!(),
+            };
+
+            assert_eq!(super::to_value(expression), None, "{}", code);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::downcast::downcast_mut;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::declares::DeclareBody;
+use crate::parser::ast::declares::DeclareStatement;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::Statement;
+use crate::traverser::Visitor;
+
+/// A single directive of a `declare` statement, e.g. `strict_types=1`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Declare {
+    pub declare: Span,         // `declare`
+    pub key: SimpleIdentifier, // `strict_types`
+    pub equals: Span,          // `=`
+    pub value: Literal,        // `1`
+    /// Whether the `declare` statement is the very first statement of the file.
+    pub first_statement: bool,
+    /// Whether the `declare` statement applies to a block (`{ ... }` or `: ... enddeclare;`).
+    pub block_mode: bool,
+}
+
+impl Declare {
+    pub fn is_strict_types(&self) -> bool {
+        self.key.value.eq_ignore_ascii_case(b"strict_types")
+    }
+
+    pub fn is_ticks(&self) -> bool {
+        self.key.value.eq_ignore_ascii_case(b"ticks")
+    }
+
+    pub fn is_encoding(&self) -> bool {
+        self.key.value.eq_ignore_ascii_case(b"encoding")
+    }
+
+    /// The value of the directive, if it is an integer literal.
+    pub fn integer_value(&self) -> Option<i64> {
+        match &self.value {
+            Literal::Integer(integer) => std::str::from_utf8(&integer.value)
+                .ok()?
+                .replace('_', "")
+                .parse()
+                .ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Collect every `declare` directive within the given program, in source order,
+/// including the ones nested within other statements.
+pub fn declares(program: &mut [Statement]) -> Vec<Declare> {
+    let first = program.iter().position(|statement| {
+        !matches!(
+            statement,
+            Statement::FullOpeningTag(_) | Statement::ShortOpeningTag(_) | Statement::Comment(_)
+        )
+    });
+
+    let mut finder = Finder::default();
+    for (index, statement) in program.iter_mut().enumerate() {
+        finder.first_statement = Some(index) == first && matches!(statement, Statement::Declare(_));
+
+        let _ = finder.visit_node(statement);
+    }
+
+    finder.declares
+}
+
+/// Whether the given program enables `strict_types`.
+pub fn strict_types(program: &mut [Statement]) -> bool {
+    declares(program).iter().any(|declare| {
+        declare.is_strict_types()
+            && declare.first_statement
+            && !declare.block_mode
+            && declare.integer_value() == Some(1)
+    })
+}
+
+#[derive(Debug, Default)]
+struct Finder {
+    declares: Vec<Declare>,
+    first_statement: bool,
+}
+
+impl Visitor<()> for Finder {
+    fn visit(&mut self, node: &mut dyn Node) -> Result<(), ()> {
+        if let Some(statement) = downcast_mut::<DeclareStatement>(node) {
+            for entry in statement.entries.entries.iter() {
+                self.declares.push(Declare {
+                    declare: statement.declare,
+                    key: entry.key.clone(),
+                    equals: entry.equals,
+                    value: entry.value.clone(),
+                    first_statement: self.first_statement,
+                    block_mode: !matches!(statement.body, DeclareBody::Noop { .. }),
+                });
+            }
+
+            // Only the outermost `declare` statement can be the first statement.
+            self.first_statement = false;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::declares;
+    use super::strict_types;
+
+    #[test]
+    fn test_collects_nested_declares() {
+        let mut program = crate::parse(
+            "<?php declare(strict_types=1, ticks=5); function foo() { declare(ticks=1) { } }",
+        )
+        .unwrap();
+
+        let declares = declares(&mut program);
+
+        assert_eq!(declares.len(), 3);
+        assert!(declares[0].is_strict_types() && declares[0].first_statement);
+        assert!(declares[1].is_ticks() && declares[1].first_statement);
+        assert_eq!(declares[1].integer_value(), Some(5));
+        assert!(declares[2].is_ticks() && !declares[2].first_statement);
+        assert!(declares[2].block_mode);
+    }
+
+    #[test]
+    fn test_strict_types() {
+        let mut enabled = crate::parse("<?php\n// header\ndeclare(strict_types=1);").unwrap();
+        let mut disabled = crate::parse("<?php declare(strict_types=0);").unwrap();
+        let mut missing = crate::parse("<?php declare(ticks=1);").unwrap();
+        let mut nested = crate::parse("<?php namespace Foo { declare(strict_types=1); }")
+            .unwrap_or_else(|stack| stack.partial);
+
+        assert!(strict_types(&mut enabled));
+        assert!(!strict_types(&mut disabled));
+        assert!(!strict_types(&mut missing));
+        assert!(!strict_types(&mut nested));
+    }
+}
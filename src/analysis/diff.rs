@@ -0,0 +1,663 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::Statement;
+
+use super::resolver::NameResolver;
+use super::structural::structural_hash;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeKind::Added => write!(f, "added"),
+            ChangeKind::Removed => write!(f, "removed"),
+            ChangeKind::Changed => write!(f, "changed"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ItemKind {
+    Function,
+    Constant,
+    Class,
+    Interface,
+    Trait,
+    Enum,
+    Method,
+    Property,
+    ClassConstant,
+    EnumCase,
+    TraitUse,
+    // Any other top-level statement, such as `use` imports or expressions.
+    Statement,
+}
+
+impl Display for ItemKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItemKind::Function => write!(f, "function"),
+            ItemKind::Constant => write!(f, "constant"),
+            ItemKind::Class => write!(f, "class"),
+            ItemKind::Interface => write!(f, "interface"),
+            ItemKind::Trait => write!(f, "trait"),
+            ItemKind::Enum => write!(f, "enum"),
+            ItemKind::Method => write!(f, "method"),
+            ItemKind::Property => write!(f, "property"),
+            ItemKind::ClassConstant => write!(f, "class constant"),
+            ItemKind::EnumCase => write!(f, "enum case"),
+            ItemKind::TraitUse => write!(f, "trait use"),
+            ItemKind::Statement => write!(f, "statement"),
+        }
+    }
+}
+
+/// A declaration or a top-level statement which differs between two versions of a file.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Change {
+    pub kind: ChangeKind,
+    pub item: ItemKind,
+    /// The fully qualified name of the declaration, e.g. `App\Foo::bar`, or `App\Foo::$bar`.
+    ///
+    /// Statements have no name, and trait uses are named after the class-like using them.
+    pub name: Option<ByteString>,
+    /// The span of the item in the old version, unless it is added.
+    pub before: Option<Span>,
+    /// The span of the item in the new version, unless it is removed.
+    pub after: Option<Span>,
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:<8} {}", self.kind.to_string(), self.item)?;
+        if let Some(name) = &self.name {
+            write!(f, " {}", name)?;
+        }
+
+        match (self.before, self.after) {
+            (Some(before), Some(after)) => write!(f, " (line {} -> {})", before.line, after.line),
+            (Some(span), None) | (None, Some(span)) => write!(f, " (line {})", span.line),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Compare the declarations and top-level statements of two versions of a file, ignoring
+/// changes which only affect formatting or comments.
+///
+/// Declarations are matched by name, so moving a declaration is not a change. Other
+/// statements are matched in order. Class-likes are only changed when their header, such
+/// as their modifiers, parent, or interfaces, changes, their members are compared separately.
+pub fn diff(before: &[Statement], after: &[Statement]) -> Vec<Change> {
+    let before = Items::collect(before);
+    let after = Items::collect(after);
+
+    let mut changes = Vec::new();
+
+    let declarations = |items: &Items| -> HashMap<(ItemKind, Vec<u8>), usize> {
+        items
+            .declarations
+            .iter()
+            .enumerate()
+            .map(|(position, item)| ((item.kind, item.key.clone()), position))
+            .collect()
+    };
+    let (old, new) = (declarations(&before), declarations(&after));
+
+    for item in before.declarations.iter() {
+        match new.get(&(item.kind, item.key.clone())) {
+            Some(position) if after.declarations[*position].hash != item.hash => {
+                changes.push(item.change(ChangeKind::Changed, Some(&after.declarations[*position])))
+            }
+            Some(_) => {}
+            None => changes.push(item.change(ChangeKind::Removed, None)),
+        }
+    }
+
+    for item in after.declarations.iter() {
+        if !old.contains_key(&(item.kind, item.key.clone())) {
+            changes.push(item.change(ChangeKind::Added, None));
+        }
+    }
+
+    changes.extend(statements(&before.statements, &after.statements));
+
+    changes.sort_by_key(|change| {
+        change
+            .after
+            .or(change.before)
+            .map_or(0, |span| span.position)
+    });
+
+    changes
+}
+
+#[derive(Debug)]
+struct Item {
+    kind: ItemKind,
+    name: Option<ByteString>,
+    // The name used to match declarations, lowercased when case-insensitive.
+    key: Vec<u8>,
+    hash: u64,
+    span: Option<Span>,
+}
+
+impl Item {
+    fn change(&self, kind: ChangeKind, after: Option<&Item>) -> Change {
+        let (before, after) = match (kind, after) {
+            (ChangeKind::Added, _) => (None, self.span),
+            (_, Some(after)) => (self.span, after.span),
+            (_, None) => (self.span, None),
+        };
+
+        Change {
+            kind,
+            item: self.kind,
+            name: self.name.clone(),
+            before,
+            after,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Items {
+    resolver: NameResolver,
+    declarations: Vec<Item>,
+    statements: Vec<Item>,
+}
+
+impl Items {
+    fn collect(program: &[Statement]) -> Self {
+        let mut items = Self::default();
+        items.statements_of(program);
+
+        items
+    }
+
+    fn statements_of(&mut self, statements: &[Statement]) {
+        for statement in statements.iter() {
+            match statement {
+                Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                    self.resolver.enter_namespace(Some(&namespace.name.value));
+                    self.statements_of(&namespace.statements);
+                }
+                Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                    self.resolver
+                        .enter_namespace(namespace.name.as_ref().map(|name| &name.value[..]));
+                    self.statements_of(&namespace.body.statements);
+                }
+                Statement::Function(function) => {
+                    self.declaration(ItemKind::Function, &function.name, function);
+                }
+                Statement::Constant(constant) => {
+                    for entry in constant.entries.iter() {
+                        let name = self.resolver.qualify(&entry.name.value);
+                        self.declarations.push(Item {
+                            kind: ItemKind::Constant,
+                            key: name.to_vec(),
+                            name: Some(name),
+                            hash: structural_hash(entry),
+                            span: Some(entry.name.span),
+                        });
+                    }
+                }
+                Statement::Class(class) => {
+                    let mut header = class.clone();
+                    header.body.members.clear();
+                    let name = self.declaration(ItemKind::Class, &class.name, &header);
+
+                    for member in class.body.members.iter() {
+                        match member {
+                            ClassMember::Constant(constant) => self.constant(&name, constant),
+                            ClassMember::TraitUsage(usage) => self.trait_usage(&name, usage),
+                            ClassMember::Property(property) => {
+                                let header =
+                                    (&property.attributes, &property.modifiers, &property.hooks);
+                                self.properties(
+                                    &name,
+                                    &property.entries,
+                                    &header,
+                                    &property.r#type,
+                                );
+                            }
+                            ClassMember::VariableProperty(property) => {
+                                let header = &property.attributes;
+                                self.properties(&name, &property.entries, header, &property.r#type);
+                            }
+                            ClassMember::AbstractMethod(method) => {
+                                self.member(ItemKind::Method, &name, &method.name, method)
+                            }
+                            ClassMember::AbstractConstructor(method) => {
+                                self.member(ItemKind::Method, &name, &method.name, method)
+                            }
+                            ClassMember::ConcreteMethod(method) => {
+                                self.member(ItemKind::Method, &name, &method.name, method)
+                            }
+                            ClassMember::ConcreteConstructor(method) => {
+                                self.member(ItemKind::Method, &name, &method.name, method)
+                            }
+                        }
+                    }
+                }
+                Statement::Interface(interface) => {
+                    let mut header = interface.clone();
+                    header.body.members.clear();
+                    let name = self.declaration(ItemKind::Interface, &interface.name, &header);
+
+                    for member in interface.body.members.iter() {
+                        match member {
+                            InterfaceMember::Constant(constant) => self.constant(&name, constant),
+                            InterfaceMember::Constructor(method) => {
+                                self.member(ItemKind::Method, &name, &method.name, method)
+                            }
+                            InterfaceMember::Method(method) => {
+                                self.member(ItemKind::Method, &name, &method.name, method)
+                            }
+                            InterfaceMember::Property(property) => {
+                                let header =
+                                    (&property.attributes, &property.modifiers, &property.hooks);
+                                self.properties(
+                                    &name,
+                                    &property.entries,
+                                    &header,
+                                    &property.r#type,
+                                );
+                            }
+                        }
+                    }
+                }
+                Statement::Trait(r#trait) => {
+                    let mut header = r#trait.clone();
+                    header.body.members.clear();
+                    let name = self.declaration(ItemKind::Trait, &r#trait.name, &header);
+
+                    for member in r#trait.body.members.iter() {
+                        match member {
+                            TraitMember::Constant(constant) => self.constant(&name, constant),
+                            TraitMember::TraitUsage(usage) => self.trait_usage(&name, usage),
+                            TraitMember::Property(property) => {
+                                let header =
+                                    (&property.attributes, &property.modifiers, &property.hooks);
+                                self.properties(
+                                    &name,
+                                    &property.entries,
+                                    &header,
+                                    &property.r#type,
+                                );
+                            }
+                            TraitMember::VariableProperty(property) => {
+                                let header = &property.attributes;
+                                self.properties(&name, &property.entries, header, &property.r#type);
+                            }
+                            TraitMember::AbstractMethod(method) => {
+                                self.member(ItemKind::Method, &name, &method.name, method)
+                            }
+                            TraitMember::AbstractConstructor(method) => {
+                                self.member(ItemKind::Method, &name, &method.name, method)
+                            }
+                            TraitMember::ConcreteMethod(method) => {
+                                self.member(ItemKind::Method, &name, &method.name, method)
+                            }
+                            TraitMember::ConcreteConstructor(method) => {
+                                self.member(ItemKind::Method, &name, &method.name, method)
+                            }
+                        }
+                    }
+                }
+                Statement::UnitEnum(r#enum) => {
+                    let mut header = r#enum.clone();
+                    header.body.members.clear();
+                    let name = self.declaration(ItemKind::Enum, &r#enum.name, &header);
+
+                    for member in r#enum.body.members.iter() {
+                        match member {
+                            UnitEnumMember::Case(case) => {
+                                self.member(ItemKind::EnumCase, &name, &case.name, case)
+                            }
+                            UnitEnumMember::Method(method) => {
+                                self.member(ItemKind::Method, &name, &method.name, method)
+                            }
+                            UnitEnumMember::Constant(constant) => self.constant(&name, constant),
+                        }
+                    }
+                }
+                Statement::BackedEnum(r#enum) => {
+                    let mut header = r#enum.clone();
+                    header.body.members.clear();
+                    let name = self.declaration(ItemKind::Enum, &r#enum.name, &header);
+
+                    for member in r#enum.body.members.iter() {
+                        match member {
+                            BackedEnumMember::Case(case) => {
+                                self.member(ItemKind::EnumCase, &name, &case.name, case)
+                            }
+                            BackedEnumMember::Method(method) => {
+                                self.member(ItemKind::Method, &name, &method.name, method)
+                            }
+                            BackedEnumMember::Constant(constant) => self.constant(&name, constant),
+                        }
+                    }
+                }
+                // tags and comments only affect formatting.
+                Statement::FullOpeningTag(_)
+                | Statement::ShortOpeningTag(_)
+                | Statement::EchoOpeningTag(_)
+                | Statement::ClosingTag(_)
+                | Statement::Comment(_)
+                | Statement::Noop(_) => {}
+                Statement::Use(r#use) => {
+                    self.resolver.import_statement(r#use);
+                    self.statement(statement);
+                }
+                Statement::GroupUse(r#use) => {
+                    self.resolver.import_group_statement(r#use);
+                    self.statement(statement);
+                }
+                _ => self.statement(statement),
+            }
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        self.statements.push(Item {
+            kind: ItemKind::Statement,
+            name: None,
+            // statements are only matched within the same namespace.
+            key: self
+                .resolver
+                .namespace()
+                .map(|namespace| namespace.to_ascii_lowercase())
+                .unwrap_or_default(),
+            hash: structural_hash(statement),
+            span: first_span(statement),
+        });
+    }
+
+    // Record a function or a class-like, returning its fully qualified name.
+    fn declaration<T: Serialize>(
+        &mut self,
+        kind: ItemKind,
+        name: &SimpleIdentifier,
+        node: &T,
+    ) -> ByteString {
+        let qualified = self.resolver.qualify(&name.value);
+
+        self.declarations.push(Item {
+            kind,
+            key: qualified.to_ascii_lowercase(),
+            name: Some(qualified.clone()),
+            hash: structural_hash(node),
+            span: Some(name.span),
+        });
+
+        qualified
+    }
+
+    fn member<T: Serialize>(
+        &mut self,
+        kind: ItemKind,
+        class: &[u8],
+        name: &SimpleIdentifier,
+        node: &T,
+    ) {
+        let qualified = member_name(class, &name.value);
+        let key = match kind {
+            // method names are case-insensitive, enum case names are not.
+            ItemKind::Method => qualified.to_ascii_lowercase(),
+            _ => qualified.to_vec(),
+        };
+
+        self.declarations.push(Item {
+            kind,
+            key,
+            name: Some(qualified),
+            hash: structural_hash(node),
+            span: Some(name.span),
+        });
+    }
+
+    fn constant(&mut self, class: &[u8], constant: &ClassishConstant) {
+        for entry in constant.entries.iter() {
+            let name = member_name(class, &entry.name.value);
+
+            self.declarations.push(Item {
+                kind: ItemKind::ClassConstant,
+                key: name.to_vec(),
+                name: Some(name),
+                hash: structural_hash(&(
+                    &constant.attributes,
+                    &constant.modifiers,
+                    &constant.data_type,
+                    entry,
+                )),
+                span: Some(entry.name.span),
+            });
+        }
+    }
+
+    fn properties<H: Serialize, T: Serialize>(
+        &mut self,
+        class: &[u8],
+        entries: &[PropertyEntry],
+        header: &H,
+        r#type: &T,
+    ) {
+        for entry in entries.iter() {
+            let variable = match entry {
+                PropertyEntry::Uninitialized { variable } => variable,
+                PropertyEntry::Initialized { variable, .. } => variable,
+            };
+            let name = member_name(class, &variable.name);
+
+            self.declarations.push(Item {
+                kind: ItemKind::Property,
+                key: name.to_vec(),
+                name: Some(name),
+                hash: structural_hash(&(header, r#type, entry)),
+                span: Some(variable.span),
+            });
+        }
+    }
+
+    fn trait_usage(&mut self, class: &[u8], usage: &TraitUsage) {
+        let traits = usage
+            .traits
+            .iter()
+            .map(|name| {
+                self.resolver
+                    .resolve_class(&name.value)
+                    .to_ascii_lowercase()
+            })
+            .collect::<Vec<Vec<u8>>>()
+            .join(&b","[..]);
+
+        let mut key = class.to_ascii_lowercase();
+        key.push(b' ');
+        key.extend_from_slice(&traits);
+
+        self.declarations.push(Item {
+            kind: ItemKind::TraitUse,
+            key,
+            name: Some(class.into()),
+            hash: structural_hash(usage),
+            span: Some(usage.r#use),
+        });
+    }
+}
+
+// Match the statements of both versions in order, using their longest common subsequence.
+//
+// A run of removed statements directly followed by a run of added statements is reported as
+// changed statements, pairwise.
+fn statements(before: &[Item], after: &[Item]) -> Vec<Change> {
+    let same = |a: &Item, b: &Item| a.key == b.key && a.hash == b.hash;
+
+    // lengths[i][j] is the length of the longest common subsequence of before[i..] and after[j..].
+    let mut lengths = vec![vec![0usize; after.len() + 1]; before.len() + 1];
+    for i in (0..before.len()).rev() {
+        for j in (0..after.len()).rev() {
+            lengths[i][j] = if same(&before[i], &after[j]) {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let (mut removed, mut added) = (Vec::new(), Vec::new());
+    let (mut i, mut j) = (0, 0);
+
+    loop {
+        let matched = i < before.len() && j < after.len() && same(&before[i], &after[j]);
+        let end = i == before.len() && j == after.len();
+
+        if matched || end {
+            pair(&mut changes, &removed, &added);
+            removed.clear();
+            added.clear();
+
+            if end {
+                break;
+            }
+
+            i += 1;
+            j += 1;
+        } else if j == after.len() || (i < before.len() && lengths[i + 1][j] >= lengths[i][j + 1]) {
+            removed.push(&before[i]);
+            i += 1;
+        } else {
+            added.push(&after[j]);
+            j += 1;
+        }
+    }
+
+    changes
+}
+
+fn pair(changes: &mut Vec<Change>, removed: &[&Item], added: &[&Item]) {
+    for position in 0..removed.len().max(added.len()) {
+        changes.push(match (removed.get(position), added.get(position)) {
+            (Some(before), Some(after)) => before.change(ChangeKind::Changed, Some(after)),
+            (Some(before), None) => before.change(ChangeKind::Removed, None),
+            (None, Some(after)) => after.change(ChangeKind::Added, None),
+            (None, None) => unreachable!(),
+        });
+    }
+}
+
+fn member_name(class: &[u8], member: &[u8]) -> ByteString {
+    let mut name = class.to_vec();
+    name.extend_from_slice(b"::");
+    name.extend_from_slice(member);
+
+    name.into()
+}
+
+// The first span within a node, skipping the spans of tokens inserted by the parser.
+fn first_span<T: Serialize>(node: &T) -> Option<Span> {
+    fn spans(value: &Value, found: &mut Option<Span>) {
+        match value {
+            Value::Object(object) => {
+                if object.contains_key("position") && object.contains_key("line") {
+                    if let Ok(span) = serde_json::from_value::<Span>(value.clone()) {
+                        if !span.synthetic
+                            && found.is_none_or(|found| span.position < found.position)
+                        {
+                            *found = Some(span);
+                        }
+                    }
+
+                    return;
+                }
+
+                object.values().for_each(|value| spans(value, found));
+            }
+            Value::Array(values) => values.iter().for_each(|value| spans(value, found)),
+            _ => {}
+        }
+    }
+
+    let mut found = None;
+    spans(&serde_json::to_value(node).ok()?, &mut found);
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::diff;
+
+    fn changes(before: &str, after: &str) -> Vec<String> {
+        let before = crate::parse(before).unwrap();
+        let after = crate::parse(after).unwrap();
+
+        diff(&before, &after)
+            .iter()
+            .map(|change| change.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_formatting_changes() {
+        let before = "<?php namespace App; use Foo\\Bar; class A extends Bar { public function a($x) { return $x + 1; } } echo 1;";
+        let after = "<?php\n\nnamespace App;\n\nuse Foo\\Bar;\n\n/** A class. */\nclass A extends Bar\n{\n    // add one.\n    public function a( $x )\n    {\n        return $x + 1;\n    }\n}\n\necho 1 ;\n";
+
+        assert!(changes(before, after).is_empty());
+    }
+
+    #[test]
+    fn test_declaration_changes() {
+        let before = "<?php namespace App; class A { const X = 1; public $a; public function a() {} public function b() {} } function f() {}";
+        let after = "<?php namespace App; function f() { return 1; } abstract class A { const X = 2; public $a; public function a() {} public function c() {} }";
+
+        assert_eq!(
+            changes(before, after),
+            vec![
+                "changed  function App\\f (line 1 -> 1)",
+                "changed  class App\\A (line 1 -> 1)",
+                "changed  class constant App\\A::X (line 1 -> 1)",
+                "removed  method App\\A::b (line 1)",
+                "added    method App\\A::c (line 1)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_statement_changes() {
+        let before = "<?php\n$a = 1;\n$b = 2;\n$c = 3;\necho $a;\n";
+        let after = "<?php\n$a = 1;\n$b = 20;\necho $a;\necho $b;\n";
+
+        assert_eq!(
+            changes(before, after),
+            vec![
+                "changed  statement (line 3 -> 3)",
+                "removed  statement (line 4)",
+                "added    statement (line 5)",
+            ]
+        );
+    }
+}
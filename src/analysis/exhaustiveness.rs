@@ -0,0 +1,337 @@
+use crate::downcast::downcast_mut;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::Expression;
+use crate::parser::ast::MatchExpression;
+use crate::parser::ast::Statement;
+use crate::parser::ast::SwitchStatement;
+
+use super::index::ClassLikeKind;
+use super::index::SymbolIndex;
+use super::resolver::NameResolver;
+
+/// The construct branching over the cases of an enum.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Branching {
+    Match,  // `match ($suit) { Suit::Hearts => ... }`
+    Switch, // `switch ($suit) { case Suit::Hearts: ... }`
+}
+
+/// A `match` or `switch` over the cases of an enum, which handles some of them only, and has no
+/// default arm.
+///
+/// A `match` failing to handle its subject throws an `UnhandledMatchError`, while a `switch`
+/// silently does nothing.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NonExhaustive {
+    pub branching: Branching,
+    pub span: Span, // `match` or `switch`
+    /// The fully qualified name of the enum.
+    pub r#enum: ByteString,
+    /// The names of the cases which are not handled, in declaration order.
+    pub missing: Vec<ByteString>,
+}
+
+/// Collect the `match` expressions and `switch` statements of the given program which do not
+/// handle every case of an enum, in source order.
+///
+/// The enum is inferred from the conditions: every condition must be a case of the same enum
+/// declared in the given index, e.g. `Suit::Hearts` or `self::Hearts` within the enum, or
+/// `null`. Branching over anything else is never reported.
+pub fn non_exhaustive(program: &mut [Statement], index: &SymbolIndex) -> Vec<NonExhaustive> {
+    let mut collector = Collector {
+        index,
+        resolver: NameResolver::new(),
+        class: None,
+        found: vec![],
+    };
+    collector.statements(program);
+
+    collector.found
+}
+
+struct Collector<'a> {
+    index: &'a SymbolIndex,
+    resolver: NameResolver,
+    // The enclosing class-like, `None` outside of class-likes and within anonymous classes.
+    class: Option<ByteString>,
+    found: Vec<NonExhaustive>,
+}
+
+impl Collector<'_> {
+    fn statements(&mut self, statements: &mut [Statement]) {
+        for statement in statements.iter_mut() {
+            match statement {
+                Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                    self.resolver.enter_namespace(Some(&namespace.name.value));
+                    self.statements(&mut namespace.statements);
+                }
+                Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                    self.resolver
+                        .enter_namespace(namespace.name.as_ref().map(|name| &name.value[..]));
+                    self.statements(&mut namespace.body.statements);
+                }
+                Statement::Use(statement) => self.resolver.import_statement(statement),
+                Statement::GroupUse(statement) => self.resolver.import_group_statement(statement),
+                _ => self.node(statement),
+            }
+        }
+    }
+
+    fn node(&mut self, node: &mut dyn Node) {
+        if let Some(statement) = downcast_mut::<Statement>(node) {
+            let name = match statement {
+                Statement::Class(class) => Some(&class.name),
+                Statement::Interface(interface) => Some(&interface.name),
+                Statement::Trait(r#trait) => Some(&r#trait.name),
+                Statement::UnitEnum(r#enum) => Some(&r#enum.name),
+                Statement::BackedEnum(r#enum) => Some(&r#enum.name),
+                _ => None,
+            };
+
+            if let Some(name) = name {
+                let class = Some(self.resolver.qualify(&name.value));
+                let previous = std::mem::replace(&mut self.class, class);
+                self.children(statement);
+                self.class = previous;
+
+                return;
+            }
+
+            if let Statement::Switch(switch) = statement {
+                self.switch(switch);
+            }
+        } else if let Some(expression) = downcast_mut::<Expression>(node) {
+            match expression {
+                Expression::AnonymousClass(_) => {
+                    let previous = self.class.take();
+                    self.children(expression);
+                    self.class = previous;
+
+                    return;
+                }
+                Expression::Match(expression) => self.r#match(expression),
+                _ => {}
+            }
+        }
+
+        self.children(node);
+    }
+
+    fn children(&mut self, node: &mut dyn Node) {
+        for child in node.children() {
+            self.node(child);
+        }
+    }
+
+    fn r#match(&mut self, expression: &MatchExpression) {
+        if expression.default.is_some() {
+            return;
+        }
+
+        let conditions = expression.arms.iter().flat_map(|arm| arm.conditions.iter());
+        self.check(Branching::Match, expression.keyword, conditions);
+    }
+
+    fn switch(&mut self, statement: &SwitchStatement) {
+        let mut conditions = vec![];
+        for case in statement.cases.iter() {
+            match &case.condition {
+                Some(condition) => conditions.push(condition),
+                None => return,
+            }
+        }
+
+        self.check(Branching::Switch, statement.switch, conditions.into_iter());
+    }
+
+    fn check<'e>(
+        &mut self,
+        branching: Branching,
+        span: Span,
+        conditions: impl Iterator<Item = &'e Expression>,
+    ) {
+        let mut r#enum: Option<ByteString> = None;
+        let mut handled: Vec<&ByteString> = vec![];
+
+        for condition in conditions {
+            if let Expression::Null { .. } = condition {
+                continue;
+            }
+
+            let Some((name, case)) = self.case(condition) else {
+                return;
+            };
+
+            match &r#enum {
+                Some(r#enum) if !r#enum.eq_ignore_ascii_case(&name) => return,
+                Some(_) => {}
+                None => r#enum = Some(name),
+            }
+
+            handled.push(case);
+        }
+
+        let Some(r#enum) = r#enum.and_then(|name| self.index.get(&name)) else {
+            return;
+        };
+
+        if r#enum.kind != ClassLikeKind::Enum {
+            return;
+        }
+
+        let missing: Vec<ByteString> = r#enum
+            .cases
+            .iter()
+            .filter(|case| !handled.contains(&&case.value))
+            .map(|case| case.value.clone())
+            .collect();
+
+        if !missing.is_empty() {
+            self.found.push(NonExhaustive {
+                branching,
+                span,
+                r#enum: r#enum.name.clone(),
+                missing,
+            });
+        }
+    }
+
+    /// The class and the case referenced by the given condition, e.g. `Suit` and `Hearts` for
+    /// `Suit::Hearts`.
+    fn case<'e>(&self, condition: &'e Expression) -> Option<(ByteString, &'e ByteString)> {
+        let Expression::ConstantFetch {
+            target,
+            constant: Identifier::SimpleIdentifier(constant),
+            ..
+        } = condition
+        else {
+            return None;
+        };
+
+        let class = match target.as_ref() {
+            Expression::Identifier(Identifier::SimpleIdentifier(name)) => {
+                match self.resolver.resolve_class(&name.value) {
+                    name if &name[..] == b"self" || &name[..] == b"static" => self.class.clone()?,
+                    name => name,
+                }
+            }
+            Expression::Self_ { .. } | Expression::Static { .. } => self.class.clone()?,
+            _ => return None,
+        };
+
+        Some((class, &constant.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::non_exhaustive;
+    use super::Branching;
+    use crate::analysis::index::SymbolIndex;
+
+    fn missing(code: &str) -> Vec<(Branching, String, Vec<String>)> {
+        let mut program = crate::parse(code).unwrap();
+        let mut index = SymbolIndex::new();
+        index.add(&mut program);
+
+        non_exhaustive(&mut program, &index)
+            .into_iter()
+            .map(|found| {
+                (
+                    found.branching,
+                    found.r#enum.to_string(),
+                    found.missing.iter().map(|case| case.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_non_exhaustive() {
+        let code = r#"<?php
+namespace App;
+
+enum Suit: string {
+    case Hearts = 'H';
+    case Spades = 'S';
+    case Clubs = 'C';
+
+    public function color(): string {
+        return match ($this) {
+            self::Hearts => 'red',
+            static::Spades, self::Clubs => 'black',
+        };
+    }
+
+    public function label(): string {
+        return match ($this) {
+            Suit::Hearts => 'hearts',
+        };
+    }
+}
+
+function a(Suit $suit) {
+    match ($suit) { Suit::Hearts, \App\Suit::Spades => 1, default => 2 };
+    match ($suit) { Suit::Hearts, null => 1 };
+    match ($suit) { Suit::Hearts => 1, Other::Spades => 2 };
+    match ($suit) { Suit::Hearts => 1, foo() => 2 };
+    match (true) { $suit === Suit::Hearts => 1 };
+    match ($suit) { Suit::Diamonds => 1 };
+
+    switch ($suit) {
+        case Suit::Clubs:
+            break;
+    }
+
+    switch ($suit) {
+        case Suit::Clubs:
+            break;
+        default:
+            break;
+    }
+}
+"#;
+
+        assert_eq!(
+            missing(code),
+            vec![
+                (
+                    Branching::Match,
+                    "App\\Suit".to_string(),
+                    vec!["Spades".to_string(), "Clubs".to_string()]
+                ),
+                (
+                    Branching::Match,
+                    "App\\Suit".to_string(),
+                    vec!["Spades".to_string(), "Clubs".to_string()]
+                ),
+                (
+                    Branching::Match,
+                    "App\\Suit".to_string(),
+                    vec![
+                        "Hearts".to_string(),
+                        "Spades".to_string(),
+                        "Clubs".to_string()
+                    ]
+                ),
+                (
+                    Branching::Switch,
+                    "App\\Suit".to_string(),
+                    vec!["Hearts".to_string(), "Spades".to_string()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_not_an_enum() {
+        assert_eq!(
+            missing("<?php class A { const B = 1; const C = 2; } match ($a) { A::B => 1 };"),
+            vec![]
+        );
+    }
+}
@@ -0,0 +1,729 @@
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::parser::ast::comments::Comment;
+use crate::parser::ast::modifiers::Visibility;
+
+use super::index::ClassLike;
+use super::index::ClassLikeKind;
+use super::index::Method;
+use super::index::SymbolIndex;
+use super::index::TraitAdaptation;
+use super::index::TraitUse;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EdgeKind {
+    // `class Foo extends Bar`, or `interface Foo extends Bar`
+    Extends,
+    // `class Foo implements Bar`, or `enum Foo implements Bar`
+    Implements,
+    // `use Bar;` within a class-like body
+    Uses,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Edge {
+    pub from: ByteString,
+    pub to: ByteString,
+    pub kind: EdgeKind,
+}
+
+/// A method available on a class-like, either declared directly, imported from a trait,
+/// or inherited from a parent class or interface.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ResolvedMethod<'a> {
+    /// The name the method is available as, which differs from the declared
+    /// name when it is imported from a trait using an alias.
+    pub name: ByteString,
+    /// The class-like declaring the method.
+    pub declaring_class: &'a ClassLike,
+    pub method: &'a Method,
+    pub visibility: Visibility,
+}
+
+/// Two or more traits used by a class-like providing a method with the same name,
+/// which is neither declared in the class-like itself nor resolved by an `insteadof`
+/// precedence.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TraitConflict {
+    pub class: ByteString,
+    pub method: ByteString,
+    /// The traits providing the method, in the order they were used.
+    pub traits: Vec<ByteString>,
+    /// The `use` keyword of the statement importing the conflicting method.
+    pub span: Span,
+}
+
+/// The effective member set of a class-like.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Composition<'a> {
+    pub methods: Vec<ResolvedMethod<'a>>,
+    /// The unresolved conflicts between the traits used by the class-like.
+    pub conflicts: Vec<TraitConflict>,
+}
+
+/// The extends, implements, and uses graph of the class-likes of a project.
+#[derive(Debug, Clone)]
+pub struct Hierarchy<'a> {
+    index: &'a SymbolIndex,
+    edges: Vec<Edge>,
+    cycles: Vec<Vec<ByteString>>,
+}
+
+impl<'a> Hierarchy<'a> {
+    pub fn new(index: &'a SymbolIndex) -> Self {
+        let mut edges = Vec::new();
+        for class in index.classes() {
+            let edge = |to: &ByteString, kind| Edge {
+                from: class.name.clone(),
+                to: to.clone(),
+                kind,
+            };
+
+            if let Some(parent) = &class.parent {
+                edges.push(edge(parent, EdgeKind::Extends));
+            }
+
+            let kind = match class.kind {
+                ClassLikeKind::Interface => EdgeKind::Extends,
+                _ => EdgeKind::Implements,
+            };
+            for interface in class.interfaces.iter() {
+                edges.push(edge(interface, kind));
+            }
+
+            for usage in class.traits.iter() {
+                for r#trait in usage.traits.iter() {
+                    edges.push(edge(r#trait, EdgeKind::Uses));
+                }
+            }
+        }
+
+        let mut hierarchy = Self {
+            index,
+            edges,
+            cycles: Vec::new(),
+        };
+        hierarchy.cycles = hierarchy.find_cycles();
+
+        hierarchy
+    }
+
+    pub fn index(&self) -> &'a SymbolIndex {
+        self.index
+    }
+
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    /// Every cycle in the graph, e.g. `A extends B` and `B extends A`.
+    ///
+    /// Each cycle is listed once, starting with the class-like it was first found from.
+    pub fn cycles(&self) -> &[Vec<ByteString>] {
+        &self.cycles
+    }
+
+    /// The direct parent class, interfaces, and traits of the given class-like.
+    pub fn parents_of(&self, name: &[u8]) -> Vec<&Edge> {
+        self.edges
+            .iter()
+            .filter(|edge| same_name(&edge.from, name))
+            .collect()
+    }
+
+    /// Every class-like the given class-like extends, implements, or uses, directly or indirectly.
+    pub fn ancestors_of(&self, name: &[u8]) -> Vec<ByteString> {
+        let mut ancestors: Vec<ByteString> = Vec::new();
+        let mut queue = vec![ByteString::from(name)];
+
+        while let Some(current) = queue.pop() {
+            for edge in self.parents_of(&current) {
+                if same_name(&edge.to, name)
+                    || ancestors
+                        .iter()
+                        .any(|ancestor| same_name(ancestor, &edge.to))
+                {
+                    continue;
+                }
+
+                ancestors.push(edge.to.clone());
+                queue.push(edge.to.clone());
+            }
+        }
+
+        ancestors
+    }
+
+    /// Every indexed class-like extending or implementing the given class or interface,
+    /// directly or indirectly.
+    pub fn subclasses_of(&self, name: &[u8]) -> Vec<&'a ClassLike> {
+        let mut subclasses: Vec<&'a ClassLike> = Vec::new();
+        let mut queue = vec![ByteString::from(name)];
+
+        while let Some(current) = queue.pop() {
+            for edge in self.edges.iter() {
+                if edge.kind == EdgeKind::Uses || !same_name(&edge.to, &current) {
+                    continue;
+                }
+
+                if same_name(&edge.from, name)
+                    || subclasses
+                        .iter()
+                        .any(|subclass| same_name(&subclass.name, &edge.from))
+                {
+                    continue;
+                }
+
+                if let Some(subclass) = self.index.get(&edge.from) {
+                    subclasses.push(subclass);
+                    queue.push(subclass.name.clone());
+                }
+            }
+        }
+
+        subclasses
+    }
+
+    /// Whether the given class-like is, extends, or implements the given parent.
+    pub fn is_subtype_of(&self, name: &[u8], parent: &[u8]) -> bool {
+        same_name(name, parent)
+            || self.ancestors_of(name).iter().any(|ancestor| {
+                same_name(ancestor, parent)
+                    && self
+                        .index
+                        .get(ancestor)
+                        .is_none_or(|ancestor| ancestor.kind != ClassLikeKind::Trait)
+            })
+    }
+
+    /// Every method available on the given class-like, including the ones imported from traits,
+    /// with trait aliases and `insteadof` precedences applied, and the ones inherited from its
+    /// parent class and interfaces.
+    ///
+    /// Methods declared in the class-like itself take precedence over trait methods, which in turn
+    /// take precedence over inherited methods. Private methods of parent classes are not inherited.
+    pub fn all_methods_including_traits(&self, name: &[u8]) -> Vec<ResolvedMethod<'a>> {
+        self.compose(name).methods
+    }
+
+    /// The effective member set of the given class-like, along with the trait method
+    /// conflicts which are not resolved by an `insteadof` precedence.
+    ///
+    /// Conflicts of the traits used by the class-like are not included, see [`Hierarchy::trait_conflicts`].
+    pub fn compose(&self, name: &[u8]) -> Composition<'a> {
+        self.composition(name, &mut Vec::new())
+    }
+
+    /// Every unresolved trait method conflict of the indexed class-likes.
+    pub fn trait_conflicts(&self) -> Vec<TraitConflict> {
+        self.index
+            .classes()
+            .flat_map(|class| self.compose(&class.name).conflicts)
+            .collect()
+    }
+
+    /// The order in which class-likes are searched when looking up a method of the given
+    /// class-like: the class-like itself, the traits it uses, its parent class, and finally
+    /// its interfaces.
+    pub fn method_resolution_order(&self, name: &[u8]) -> Vec<ByteString> {
+        let mut order = Vec::new();
+        self.resolution_order(name, &mut order);
+
+        order
+    }
+
+    /// The effective docblock of the given method.
+    ///
+    /// When the method has no docblock, or its docblock contains an `@inheritDoc` tag,
+    /// the docblock of the first method it overrides or implements in the method
+    /// resolution order is used instead.
+    pub fn docblock(&self, class: &[u8], method: &[u8]) -> Option<&'a Comment> {
+        let resolved = self
+            .all_methods_including_traits(class)
+            .into_iter()
+            .find(|candidate| same_name(&candidate.name, method))?;
+
+        let own = resolved.method.docblock.as_ref();
+        if own.is_some_and(|docblock| !inherits_docblock(docblock)) {
+            return own;
+        }
+
+        self.method_resolution_order(class)
+            .iter()
+            .filter(|name| !same_name(name, &resolved.declaring_class.name))
+            .filter_map(|name| self.index.get(name)?.get_method(method)?.docblock.as_ref())
+            .find(|docblock| !inherits_docblock(docblock))
+            .or(own)
+    }
+
+    fn resolution_order(&self, name: &[u8], order: &mut Vec<ByteString>) {
+        if order.iter().any(|entry| same_name(entry, name)) {
+            return;
+        }
+
+        let class = match self.index.get(name) {
+            Some(class) => class,
+            None => {
+                order.push(name.into());
+
+                return;
+            }
+        };
+
+        order.push(class.name.clone());
+        for usage in class.traits.iter() {
+            for r#trait in usage.traits.iter() {
+                self.resolution_order(r#trait, order);
+            }
+        }
+
+        if let Some(parent) = &class.parent {
+            self.resolution_order(parent, order);
+        }
+
+        for interface in class.interfaces.iter() {
+            self.resolution_order(interface, order);
+        }
+    }
+
+    fn composition(&self, name: &[u8], path: &mut Vec<Vec<u8>>) -> Composition<'a> {
+        let mut composition = Composition {
+            methods: vec![],
+            conflicts: vec![],
+        };
+
+        let class = match self.index.get(name) {
+            Some(class) => class,
+            None => return composition,
+        };
+
+        let key = class.name.to_ascii_lowercase();
+        if path.contains(&key) {
+            return composition;
+        }
+
+        path.push(key);
+
+        composition.methods = class
+            .methods
+            .iter()
+            .map(|method| ResolvedMethod {
+                name: method.name.value.clone(),
+                declaring_class: class,
+                method,
+                visibility: method.visibility(),
+            })
+            .collect();
+
+        let own = composition.methods.len();
+        for usage in class.traits.iter() {
+            for method in self.trait_methods(usage, path) {
+                if composition.methods[..own]
+                    .iter()
+                    .any(|existing| same_name(&existing.name, &method.name))
+                {
+                    continue;
+                }
+
+                let existing = match composition.methods[own..]
+                    .iter()
+                    .position(|existing| same_name(&existing.name, &method.name))
+                {
+                    Some(position) => own + position,
+                    None => {
+                        composition.methods.push(method);
+                        continue;
+                    }
+                };
+
+                let existing_method = &composition.methods[existing];
+                if same_name(
+                    &existing_method.declaring_class.name,
+                    &method.declaring_class.name,
+                ) && same_name(
+                    &existing_method.method.name.value,
+                    &method.method.name.value,
+                ) {
+                    // The same method, imported through more than one trait.
+                    continue;
+                }
+
+                if method.method.r#abstract {
+                    continue;
+                }
+
+                if existing_method.method.r#abstract {
+                    composition.methods[existing] = method;
+                    continue;
+                }
+
+                let r#trait = method.declaring_class.name.clone();
+                match composition
+                    .conflicts
+                    .iter_mut()
+                    .find(|conflict| same_name(&conflict.method, &method.name))
+                {
+                    Some(conflict) => {
+                        if !conflict
+                            .traits
+                            .iter()
+                            .any(|other| same_name(other, &r#trait))
+                        {
+                            conflict.traits.push(r#trait);
+                        }
+                    }
+                    None => composition.conflicts.push(TraitConflict {
+                        class: class.name.clone(),
+                        method: method.name.clone(),
+                        traits: vec![existing_method.declaring_class.name.clone(), r#trait],
+                        span: usage.span,
+                    }),
+                }
+            }
+        }
+
+        if let Some(parent) = &class.parent {
+            for method in self.composition(parent, path).methods {
+                if method.visibility != Visibility::Private {
+                    merge(&mut composition.methods, method);
+                }
+            }
+        }
+
+        for interface in class.interfaces.iter() {
+            for method in self.composition(interface, path).methods {
+                merge(&mut composition.methods, method);
+            }
+        }
+
+        path.pop();
+
+        composition
+    }
+
+    fn trait_methods(&self, usage: &TraitUse, path: &mut Vec<Vec<u8>>) -> Vec<ResolvedMethod<'a>> {
+        let mut methods = Vec::new();
+
+        for r#trait in usage.traits.iter() {
+            let targets = |adaptation_trait: &Option<ByteString>,
+                           name: &ByteString,
+                           method: &ResolvedMethod| {
+                adaptation_trait
+                    .as_ref()
+                    .is_none_or(|adaptation_trait| same_name(adaptation_trait, r#trait))
+                    && same_name(name, &method.name)
+            };
+
+            for mut method in self.composition(r#trait, path).methods {
+                let mut excluded = false;
+                for adaptation in usage.adaptations.iter() {
+                    match adaptation {
+                        TraitAdaptation::Precedence {
+                            method: name,
+                            insteadof,
+                            ..
+                        } => {
+                            if same_name(name, &method.name)
+                                && insteadof.iter().any(|other| same_name(other, r#trait))
+                            {
+                                excluded = true;
+                            }
+                        }
+                        TraitAdaptation::Visibility {
+                            r#trait: adaptation_trait,
+                            method: name,
+                            visibility,
+                        } => {
+                            if targets(adaptation_trait, name, &method) {
+                                method.visibility = visibility.clone();
+                            }
+                        }
+                        TraitAdaptation::Alias {
+                            r#trait: adaptation_trait,
+                            method: name,
+                            alias,
+                            visibility,
+                        } => {
+                            if targets(adaptation_trait, name, &method) {
+                                methods.push(ResolvedMethod {
+                                    name: alias.clone(),
+                                    visibility: visibility
+                                        .clone()
+                                        .unwrap_or_else(|| method.visibility.clone()),
+                                    ..method.clone()
+                                });
+                            }
+                        }
+                    }
+                }
+
+                if !excluded {
+                    methods.push(method);
+                }
+            }
+        }
+
+        methods
+    }
+
+    fn find_cycles(&self) -> Vec<Vec<ByteString>> {
+        let mut cycles = Vec::new();
+        let mut finished: Vec<Vec<u8>> = Vec::new();
+
+        for class in self.index.classes() {
+            self.visit(&class.name, &mut Vec::new(), &mut finished, &mut cycles);
+        }
+
+        cycles
+    }
+
+    fn visit(
+        &self,
+        name: &ByteString,
+        path: &mut Vec<ByteString>,
+        finished: &mut Vec<Vec<u8>>,
+        cycles: &mut Vec<Vec<ByteString>>,
+    ) {
+        let key = name.to_ascii_lowercase();
+        if finished.contains(&key) {
+            return;
+        }
+
+        if let Some(position) = path.iter().position(|entry| same_name(entry, name)) {
+            cycles.push(path[position..].to_vec());
+
+            return;
+        }
+
+        path.push(name.clone());
+        for edge in self.parents_of(name) {
+            self.visit(&edge.to, path, finished, cycles);
+        }
+        path.pop();
+
+        finished.push(key);
+    }
+}
+
+pub(crate) fn same_name(a: &[u8], b: &[u8]) -> bool {
+    let a = a.strip_prefix(b"\\").unwrap_or(a);
+    let b = b.strip_prefix(b"\\").unwrap_or(b);
+
+    a.eq_ignore_ascii_case(b)
+}
+
+fn inherits_docblock(docblock: &Comment) -> bool {
+    docblock
+        .content
+        .windows(11)
+        .any(|window| window.eq_ignore_ascii_case(b"@inheritdoc"))
+}
+
+fn merge<'a>(methods: &mut Vec<ResolvedMethod<'a>>, method: ResolvedMethod<'a>) {
+    if !methods
+        .iter()
+        .any(|existing| same_name(&existing.name, &method.name))
+    {
+        methods.push(method);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EdgeKind;
+    use super::Hierarchy;
+    use crate::analysis::index::SymbolIndex;
+    use crate::lexer::byte_string::ByteString;
+    use crate::parser::ast::modifiers::Visibility;
+
+    fn index(files: &[&str]) -> SymbolIndex {
+        let mut index = SymbolIndex::new();
+        for file in files {
+            index.add(&mut crate::parse(file).unwrap());
+        }
+
+        index
+    }
+
+    fn names(hierarchy: &Hierarchy, class: &str) -> Vec<String> {
+        hierarchy
+            .all_methods_including_traits(class.as_bytes())
+            .iter()
+            .map(|method| method.name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_subclasses_across_files() {
+        let index = index(&[
+            "<?php namespace App; interface Shape {} abstract class Base implements Shape {}",
+            "<?php namespace App\\Shapes; use App\\Base; class Circle extends Base {}",
+            "<?php namespace App\\Shapes; final class Unit extends Circle {}",
+        ]);
+        let hierarchy = Hierarchy::new(&index);
+
+        let subclasses = hierarchy
+            .subclasses_of(b"App\\Shape")
+            .iter()
+            .map(|class| class.name.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            subclasses,
+            vec!["App\\Base", "App\\Shapes\\Circle", "App\\Shapes\\Unit"]
+        );
+        assert!(hierarchy.is_subtype_of(b"App\\Shapes\\Unit", b"\\app\\shape"));
+        assert!(!hierarchy.is_subtype_of(b"App\\Base", b"App\\Shapes\\Circle"));
+        assert!(hierarchy.cycles().is_empty());
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        let index = index(&[
+            "<?php class A extends B {} class B extends C {} class C extends A {} class D extends A {}",
+        ]);
+        let hierarchy = Hierarchy::new(&index);
+
+        assert_eq!(hierarchy.cycles().len(), 1);
+        assert_eq!(hierarchy.cycles()[0].len(), 3);
+        assert_eq!(hierarchy.subclasses_of(b"A").len(), 3);
+        assert_eq!(hierarchy.parents_of(b"D")[0].kind, EdgeKind::Extends);
+    }
+
+    #[test]
+    fn test_all_methods_including_traits() {
+        let index = index(&[r#"<?php
+            trait Hello { public function say() {} public function hello() {} }
+            trait World { public function say() {} private function world() {} }
+            class Base { public function base() {} private function secret() {} }
+            class Greeter extends Base {
+                use Hello, World {
+                    Hello::say insteadof World;
+                    World::say as protected sayWorld;
+                    world as public;
+                }
+
+                public function hello() {}
+            }
+        "#]);
+        let hierarchy = Hierarchy::new(&index);
+
+        assert_eq!(
+            names(&hierarchy, "Greeter"),
+            vec!["hello", "say", "sayWorld", "world", "base"]
+        );
+
+        let methods = hierarchy.all_methods_including_traits(b"greeter");
+        assert_eq!(methods[0].declaring_class.name, b"Greeter");
+        assert_eq!(methods[1].declaring_class.name, b"Hello");
+        assert_eq!(methods[2].declaring_class.name, b"World");
+        assert_eq!(methods[2].visibility, Visibility::Protected);
+        assert_eq!(methods[3].visibility, Visibility::Public);
+    }
+
+    #[test]
+    fn test_unresolved_trait_conflicts() {
+        let index = index(&[r#"<?php
+            trait A { public function foo() {} public function bar() {} }
+            trait B { public function foo() {} public function bar() {} }
+            trait C { public function foo() {} }
+            class Conflicting { use A, B, C { A::bar insteadof B; } }
+            class Overriding { use A, B; public function foo() {} public function bar() {} }
+        "#]);
+        let hierarchy = Hierarchy::new(&index);
+
+        let conflicts = hierarchy.trait_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].class, b"Conflicting");
+        assert_eq!(conflicts[0].method, b"foo");
+        assert_eq!(
+            conflicts[0].traits,
+            vec![
+                ByteString::from("A"),
+                ByteString::from("B"),
+                ByteString::from("C")
+            ]
+        );
+
+        let composition = hierarchy.compose(b"Conflicting");
+        assert_eq!(composition.methods.len(), 2);
+        assert_eq!(composition.methods[0].declaring_class.name, b"A");
+        assert_eq!(composition.methods[1].declaring_class.name, b"A");
+    }
+
+    #[test]
+    fn test_trait_compositions_without_conflicts() {
+        let index = index(&[r#"<?php
+            trait Base { public function shared() {} }
+            trait Left { use Base; abstract public function render(); }
+            trait Right { use Base; public function render() {} }
+            class Widget { use Left, Right; }
+        "#]);
+        let hierarchy = Hierarchy::new(&index);
+
+        let composition = hierarchy.compose(b"Widget");
+        assert!(composition.conflicts.is_empty());
+        assert_eq!(names(&hierarchy, "Widget"), vec!["render", "shared"]);
+        assert_eq!(composition.methods[0].declaring_class.name, b"Right");
+    }
+
+    #[test]
+    fn test_method_resolution_order() {
+        let index = index(&[r#"<?php
+            interface Shape {}
+            trait Named {}
+            trait Sized { use Named; }
+            class Base implements Shape { use Named; }
+            class Square extends Base implements Countable { use Sized; }
+        "#]);
+        let hierarchy = Hierarchy::new(&index);
+
+        let order = hierarchy
+            .method_resolution_order(b"Square")
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            order,
+            vec!["Square", "Sized", "Named", "Base", "Shape", "Countable"]
+        );
+    }
+
+    #[test]
+    fn test_docblock_inheritance() {
+        let index = index(&[r#"<?php
+            interface Shape {
+                /** @return float The area. */
+                public function area();
+            }
+            abstract class Base implements Shape {
+                /** {@inheritDoc} */
+                public function area() {}
+                public function name() {}
+            }
+            class Square extends Base {
+                public function area() {}
+                /** @inheritdoc */
+                public function name() {}
+            }
+        "#]);
+        let hierarchy = Hierarchy::new(&index);
+
+        let docblock = |class: &str, method: &str| {
+            hierarchy
+                .docblock(class.as_bytes(), method.as_bytes())
+                .map(|comment| comment.content.to_string())
+        };
+
+        let area = Some("/** @return float The area. */".to_string());
+        assert_eq!(docblock("Square", "area"), area);
+        assert_eq!(docblock("Base", "AREA"), area);
+        assert_eq!(
+            docblock("Square", "name"),
+            Some("/** @inheritdoc */".to_string())
+        );
+        assert_eq!(docblock("Base", "name"), None);
+        assert_eq!(docblock("Square", "missing"), None);
+    }
+}
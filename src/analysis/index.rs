@@ -0,0 +1,676 @@
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::downcast::downcast_mut;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::comments::Comment;
+use crate::parser::ast::comments::CommentGroup;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::functions::AbstractConstructor;
+use crate::parser::ast::functions::AbstractMethod;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::functions::ReturnType;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::interfaces::InterfaceStatement;
+use crate::parser::ast::modifiers::MethodModifierGroup;
+use crate::parser::ast::modifiers::Visibility;
+use crate::parser::ast::modifiers::VisibilityModifier;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitStatement;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::traits::TraitUsageAdaptation;
+use crate::parser::ast::Statement;
+use crate::traverser::Visitor;
+
+use super::references;
+use super::references::Reference;
+use super::references::SymbolKind;
+use super::resolver::NameResolver;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClassLikeKind {
+    Class,
+    Interface,
+    Trait,
+    Enum,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Method {
+    pub name: SimpleIdentifier,
+    pub modifiers: MethodModifierGroup,
+    /// Whether the method has no body, i.e. it is an abstract or an interface method.
+    pub r#abstract: bool,
+    pub parameters: Vec<Parameter>,
+    /// The return type, with all class names fully qualified.
+    pub return_type: Option<Type>,
+    /// The document comment directly preceding the method.
+    pub docblock: Option<Comment>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Parameter {
+    pub name: ByteString,
+    /// The parameter type, with all class names fully qualified.
+    pub data_type: Option<Type>,
+    pub optional: bool,
+    pub variadic: bool,
+    pub by_reference: bool,
+}
+
+impl Method {
+    pub fn visibility(&self) -> Visibility {
+        self.modifiers.visibility()
+    }
+
+    /// The number of parameters which must be passed when calling the method.
+    pub fn required_parameters(&self) -> usize {
+        self.parameters
+            .iter()
+            .filter(|parameter| !parameter.optional && !parameter.variadic)
+            .count()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TraitAdaptation {
+    // `Foo::bar as protected baz;`
+    Alias {
+        r#trait: Option<ByteString>,
+        method: ByteString,
+        alias: ByteString,
+        visibility: Option<Visibility>,
+    },
+    // `Foo::bar as protected;`
+    Visibility {
+        r#trait: Option<ByteString>,
+        method: ByteString,
+        visibility: Visibility,
+    },
+    // `Foo::bar insteadof Baz;`
+    Precedence {
+        r#trait: Option<ByteString>,
+        method: ByteString,
+        insteadof: Vec<ByteString>,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TraitUse {
+    pub span: Span,
+    pub traits: Vec<ByteString>,
+    pub adaptations: Vec<TraitAdaptation>,
+}
+
+/// A class, interface, trait, or enum declaration, with all names fully qualified.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ClassLike {
+    pub name: ByteString,
+    pub kind: ClassLikeKind,
+    pub span: Span,
+    /// The parent class of a class.
+    pub parent: Option<ByteString>,
+    /// The interfaces implemented by a class or enum, or extended by an interface.
+    pub interfaces: Vec<ByteString>,
+    pub traits: Vec<TraitUse>,
+    pub methods: Vec<Method>,
+    /// The cases of an enum, in declaration order.
+    pub cases: Vec<SimpleIdentifier>,
+    /// The document comment directly preceding the declaration.
+    pub docblock: Option<Comment>,
+}
+
+impl ClassLike {
+    pub fn get_method(&self, name: &[u8]) -> Option<&Method> {
+        self.methods
+            .iter()
+            .find(|method| method.name.value.eq_ignore_ascii_case(name))
+    }
+}
+
+/// A function declaration, with all names fully qualified.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Function {
+    pub name: ByteString,
+    pub span: Span,
+    pub parameters: Vec<Parameter>,
+    /// The return type, with all class names fully qualified.
+    pub return_type: Option<Type>,
+    /// The document comment directly preceding the function.
+    pub docblock: Option<Comment>,
+}
+
+/// A constant declared using the `const` statement, with its name fully qualified.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Constant {
+    pub name: ByteString,
+    pub span: Span,
+}
+
+/// An index of the class-like, function, and constant declarations of a project,
+/// and of the references to them.
+#[derive(Debug, Default, Clone)]
+pub struct SymbolIndex {
+    classes: Vec<ClassLike>,
+    // lowercased name -> position in `classes`
+    names: HashMap<Vec<u8>, usize>,
+    functions: Vec<Function>,
+    // lowercased name -> position in `functions`
+    function_names: HashMap<Vec<u8>, usize>,
+    constants: Vec<Constant>,
+    references: Vec<Reference>,
+}
+
+impl SymbolIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index all declarations of a single file, and the references they contain.
+    ///
+    /// When a name is declared more than once, the first declaration takes precedence.
+    pub fn add(&mut self, program: &mut [Statement]) {
+        let mut collector = Collector::default();
+        collector.statements(program);
+
+        for class in collector.classes {
+            let key = class.name.to_ascii_lowercase();
+            if !self.names.contains_key(&key) {
+                self.names.insert(key, self.classes.len());
+            }
+
+            self.classes.push(class);
+        }
+
+        for function in collector.functions {
+            let key = function.name.to_ascii_lowercase();
+            if !self.function_names.contains_key(&key) {
+                self.function_names.insert(key, self.functions.len());
+            }
+
+            self.functions.push(function);
+        }
+
+        self.constants.append(&mut collector.constants);
+        self.references.append(&mut references::collect(program));
+    }
+
+    pub fn get(&self, name: &[u8]) -> Option<&ClassLike> {
+        let name = name.strip_prefix(b"\\").unwrap_or(name);
+
+        self.names
+            .get(&name.to_ascii_lowercase())
+            .map(|position| &self.classes[*position])
+    }
+
+    pub fn classes(&self) -> impl Iterator<Item = &ClassLike> {
+        self.classes.iter()
+    }
+
+    pub fn get_function(&self, name: &[u8]) -> Option<&Function> {
+        let name = name.strip_prefix(b"\\").unwrap_or(name);
+
+        self.function_names
+            .get(&name.to_ascii_lowercase())
+            .map(|position| &self.functions[*position])
+    }
+
+    pub fn functions(&self) -> impl Iterator<Item = &Function> {
+        self.functions.iter()
+    }
+
+    pub fn get_constant(&self, name: &[u8]) -> Option<&Constant> {
+        self.constants
+            .iter()
+            .find(|constant| same_constant(&constant.name, name))
+    }
+
+    pub fn constants(&self) -> impl Iterator<Item = &Constant> {
+        self.constants.iter()
+    }
+
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+
+    /// Every reference to the given class-like, function, or constant, across all indexed files.
+    ///
+    /// Unqualified function and constant names within a namespace refer to the global symbol
+    /// when no namespaced symbol with that name is declared.
+    pub fn references_to(&self, symbol: SymbolKind, name: &[u8]) -> Vec<&Reference> {
+        let matches = |candidate: &[u8]| match symbol {
+            SymbolKind::Constant => same_constant(candidate, name),
+            _ => same_name(candidate, name),
+        };
+
+        self.references
+            .iter()
+            .filter(|reference| reference.symbol == symbol)
+            .filter(|reference| {
+                let declared = match symbol {
+                    SymbolKind::Class => true,
+                    SymbolKind::Function => self.get_function(&reference.name).is_some(),
+                    SymbolKind::Constant => self.get_constant(&reference.name).is_some(),
+                };
+
+                match &reference.fallback {
+                    Some(fallback) if !declared => matches(fallback),
+                    _ => matches(&reference.name),
+                }
+            })
+            .collect()
+    }
+}
+
+fn same_name(a: &[u8], b: &[u8]) -> bool {
+    let a = a.strip_prefix(b"\\").unwrap_or(a);
+    let b = b.strip_prefix(b"\\").unwrap_or(b);
+
+    a.eq_ignore_ascii_case(b)
+}
+
+// Namespaces are case-insensitive, but constant names are not.
+fn same_constant(a: &[u8], b: &[u8]) -> bool {
+    let a = a.strip_prefix(b"\\").unwrap_or(a);
+    let b = b.strip_prefix(b"\\").unwrap_or(b);
+
+    let split = |name: &[u8]| -> (usize, usize) {
+        match name.iter().rposition(|b| *b == b'\\') {
+            Some(position) => (position, position + 1),
+            None => (0, 0),
+        }
+    };
+    let ((a_end, a_start), (b_end, b_start)) = (split(a), split(b));
+
+    a[..a_end].eq_ignore_ascii_case(&b[..b_end]) && a[a_start..] == b[b_start..]
+}
+
+#[derive(Debug, Default)]
+struct Collector {
+    resolver: NameResolver,
+    classes: Vec<ClassLike>,
+    functions: Vec<Function>,
+    constants: Vec<Constant>,
+}
+
+impl Collector {
+    fn statements(&mut self, statements: &mut [Statement]) {
+        for statement in statements.iter_mut() {
+            match statement {
+                Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                    self.resolver.enter_namespace(Some(&namespace.name.value));
+                    self.statements(&mut namespace.statements);
+                }
+                Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                    self.resolver
+                        .enter_namespace(namespace.name.as_ref().map(|name| &name.value[..]));
+                    self.statements(&mut namespace.body.statements);
+                }
+                Statement::Use(statement) => self.resolver.import_statement(statement),
+                Statement::GroupUse(statement) => self.resolver.import_group_statement(statement),
+                _ => {
+                    let _ = self.visit_node(statement);
+                }
+            }
+        }
+    }
+
+    fn names(&self, names: &[SimpleIdentifier]) -> Vec<ByteString> {
+        names
+            .iter()
+            .map(|name| self.resolver.resolve_class(&name.value))
+            .collect()
+    }
+
+    fn trait_use(&self, usage: &TraitUsage) -> TraitUse {
+        let resolve = |name: &Option<SimpleIdentifier>| {
+            name.as_ref()
+                .map(|name| self.resolver.resolve_class(&name.value))
+        };
+
+        TraitUse {
+            span: usage.r#use,
+            traits: self.names(&usage.traits),
+            adaptations: usage
+                .adaptations
+                .iter()
+                .map(|adaptation| match adaptation {
+                    TraitUsageAdaptation::Alias {
+                        r#trait,
+                        method,
+                        alias,
+                        visibility,
+                    } => TraitAdaptation::Alias {
+                        r#trait: resolve(r#trait),
+                        method: method.value.clone(),
+                        alias: alias.value.clone(),
+                        visibility: visibility.as_ref().map(visibility_of),
+                    },
+                    TraitUsageAdaptation::Visibility {
+                        r#trait,
+                        method,
+                        visibility,
+                    } => TraitAdaptation::Visibility {
+                        r#trait: resolve(r#trait),
+                        method: method.value.clone(),
+                        visibility: visibility_of(visibility),
+                    },
+                    TraitUsageAdaptation::Precedence {
+                        r#trait,
+                        method,
+                        insteadof,
+                    } => TraitAdaptation::Precedence {
+                        r#trait: resolve(r#trait),
+                        method: method.value.clone(),
+                        insteadof: self.names(insteadof),
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    fn parameters(&self, parameters: &FunctionParameterList) -> Vec<Parameter> {
+        parameters
+            .parameters
+            .inner
+            .iter()
+            .map(|parameter| Parameter {
+                name: parameter.name.name.clone(),
+                data_type: parameter
+                    .data_type
+                    .as_ref()
+                    .map(|t| self.resolver.resolve_type(t)),
+                optional: parameter.default.is_some(),
+                variadic: parameter.ellipsis.is_some(),
+                by_reference: parameter.ampersand.is_some(),
+            })
+            .collect()
+    }
+
+    fn method(
+        &self,
+        comments: &CommentGroup,
+        name: &SimpleIdentifier,
+        modifiers: &MethodModifierGroup,
+        r#abstract: bool,
+        parameters: Vec<Parameter>,
+        return_type: Option<&ReturnType>,
+    ) -> Method {
+        Method {
+            name: name.clone(),
+            modifiers: modifiers.clone(),
+            r#abstract,
+            parameters,
+            return_type: return_type.map(|r| self.resolver.resolve_type(&r.data_type)),
+            docblock: comments.doc_comment().cloned(),
+        }
+    }
+
+    fn abstract_method(&self, method: &AbstractMethod) -> Method {
+        self.method(
+            &method.comments,
+            &method.name,
+            &method.modifiers,
+            true,
+            self.parameters(&method.parameters),
+            method.return_type.as_ref(),
+        )
+    }
+
+    fn concrete_method(&self, method: &ConcreteMethod) -> Method {
+        self.method(
+            &method.comments,
+            &method.name,
+            &method.modifiers,
+            false,
+            self.parameters(&method.parameters),
+            method.return_type.as_ref(),
+        )
+    }
+
+    fn abstract_constructor(&self, method: &AbstractConstructor) -> Method {
+        self.method(
+            &method.comments,
+            &method.name,
+            &method.modifiers,
+            true,
+            self.parameters(&method.parameters),
+            None,
+        )
+    }
+
+    fn concrete_constructor(&self, method: &ConcreteConstructor) -> Method {
+        let parameters = method
+            .parameters
+            .parameters
+            .inner
+            .iter()
+            .map(|parameter| Parameter {
+                name: parameter.name.name.clone(),
+                data_type: parameter
+                    .data_type
+                    .as_ref()
+                    .map(|t| self.resolver.resolve_type(t)),
+                optional: parameter.default.is_some(),
+                variadic: parameter.ellipsis.is_some(),
+                by_reference: parameter.ampersand.is_some(),
+            })
+            .collect();
+
+        self.method(
+            &method.comments,
+            &method.name,
+            &method.modifiers,
+            false,
+            parameters,
+            None,
+        )
+    }
+
+    fn function(&self, statement: &FunctionStatement) -> Function {
+        Function {
+            name: self.resolver.qualify(&statement.name.value),
+            span: statement.name.span,
+            parameters: self.parameters(&statement.parameters),
+            return_type: statement
+                .return_type
+                .as_ref()
+                .map(|r| self.resolver.resolve_type(&r.data_type)),
+            docblock: statement.comments.doc_comment().cloned(),
+        }
+    }
+
+    fn class_like(
+        &self,
+        comments: &CommentGroup,
+        name: &SimpleIdentifier,
+        kind: ClassLikeKind,
+    ) -> ClassLike {
+        ClassLike {
+            name: self.resolver.qualify(&name.value),
+            kind,
+            span: name.span,
+            parent: None,
+            interfaces: vec![],
+            traits: vec![],
+            methods: vec![],
+            cases: vec![],
+            docblock: comments.doc_comment().cloned(),
+        }
+    }
+
+    fn class(&self, statement: &ClassStatement) -> ClassLike {
+        let mut class = self.class_like(&statement.comments, &statement.name, ClassLikeKind::Class);
+        class.parent = statement
+            .extends
+            .as_ref()
+            .map(|extends| self.resolver.resolve_class(&extends.parent.value));
+        if let Some(implements) = &statement.implements {
+            class.interfaces = self.names(&implements.interfaces.inner);
+        }
+
+        for member in statement.body.members.iter() {
+            match member {
+                ClassMember::TraitUsage(usage) => class.traits.push(self.trait_use(usage)),
+                ClassMember::AbstractMethod(method) => {
+                    class.methods.push(self.abstract_method(method));
+                }
+                ClassMember::AbstractConstructor(method) => {
+                    class.methods.push(self.abstract_constructor(method));
+                }
+                ClassMember::ConcreteMethod(method) => {
+                    class.methods.push(self.concrete_method(method));
+                }
+                ClassMember::ConcreteConstructor(method) => {
+                    class.methods.push(self.concrete_constructor(method));
+                }
+                _ => {}
+            }
+        }
+
+        class
+    }
+
+    fn interface(&self, statement: &InterfaceStatement) -> ClassLike {
+        let mut interface = self.class_like(
+            &statement.comments,
+            &statement.name,
+            ClassLikeKind::Interface,
+        );
+        if let Some(extends) = &statement.extends {
+            interface.interfaces = self.names(&extends.parents.inner);
+        }
+
+        for member in statement.body.members.iter() {
+            match member {
+                InterfaceMember::Method(method) => {
+                    interface.methods.push(self.abstract_method(method))
+                }
+                InterfaceMember::Constructor(method) => {
+                    interface.methods.push(self.abstract_constructor(method))
+                }
+                InterfaceMember::Constant(_) | InterfaceMember::Property(_) => {}
+            }
+        }
+
+        interface
+    }
+
+    fn r#trait(&self, statement: &TraitStatement) -> ClassLike {
+        let mut r#trait =
+            self.class_like(&statement.comments, &statement.name, ClassLikeKind::Trait);
+
+        for member in statement.body.members.iter() {
+            match member {
+                TraitMember::TraitUsage(usage) => r#trait.traits.push(self.trait_use(usage)),
+                TraitMember::AbstractMethod(method) => {
+                    r#trait.methods.push(self.abstract_method(method))
+                }
+                TraitMember::AbstractConstructor(method) => {
+                    r#trait.methods.push(self.abstract_constructor(method))
+                }
+                TraitMember::ConcreteMethod(method) => {
+                    r#trait.methods.push(self.concrete_method(method))
+                }
+                TraitMember::ConcreteConstructor(method) => {
+                    r#trait.methods.push(self.concrete_constructor(method))
+                }
+                _ => {}
+            }
+        }
+
+        r#trait
+    }
+
+    fn unit_enum(&self, statement: &UnitEnumStatement) -> ClassLike {
+        let mut r#enum = self.class_like(&statement.comments, &statement.name, ClassLikeKind::Enum);
+        r#enum.interfaces = self.names(&statement.implements);
+
+        for member in statement.body.members.iter() {
+            match member {
+                UnitEnumMember::Case(case) => r#enum.cases.push(case.name.clone()),
+                UnitEnumMember::Method(method) => {
+                    r#enum.methods.push(self.concrete_method(method));
+                }
+                UnitEnumMember::Constant(_) => {}
+            }
+        }
+
+        r#enum
+    }
+
+    fn backed_enum(&self, statement: &BackedEnumStatement) -> ClassLike {
+        let mut r#enum = self.class_like(&statement.comments, &statement.name, ClassLikeKind::Enum);
+        r#enum.interfaces = self.names(&statement.implements);
+
+        for member in statement.body.members.iter() {
+            match member {
+                BackedEnumMember::Case(case) => r#enum.cases.push(case.name.clone()),
+                BackedEnumMember::Method(method) => {
+                    r#enum.methods.push(self.concrete_method(method));
+                }
+                BackedEnumMember::Constant(_) => {}
+            }
+        }
+
+        r#enum
+    }
+}
+
+impl Visitor<()> for Collector {
+    fn visit(&mut self, node: &mut dyn Node) -> Result<(), ()> {
+        let class = match downcast_mut::<Statement>(node) {
+            Some(Statement::Class(statement)) => self.class(statement),
+            Some(Statement::Interface(statement)) => self.interface(statement),
+            Some(Statement::Trait(statement)) => self.r#trait(statement),
+            Some(Statement::UnitEnum(statement)) => self.unit_enum(statement),
+            Some(Statement::BackedEnum(statement)) => self.backed_enum(statement),
+            Some(Statement::Constant(statement)) => {
+                for entry in statement.entries.iter() {
+                    self.constants.push(Constant {
+                        name: self.resolver.qualify(&entry.name.value),
+                        span: entry.name.span,
+                    });
+                }
+
+                return Ok(());
+            }
+            Some(Statement::Function(statement)) => {
+                let function = self.function(statement);
+                self.functions.push(function);
+
+                return Ok(());
+            }
+            _ => return Ok(()),
+        };
+
+        self.classes.push(class);
+
+        Ok(())
+    }
+}
+
+fn visibility_of(modifier: &VisibilityModifier) -> Visibility {
+    match modifier {
+        VisibilityModifier::Public(_) => Visibility::Public,
+        VisibilityModifier::Protected(_) => Visibility::Protected,
+        VisibilityModifier::Private(_) => Visibility::Private,
+    }
+}
@@ -0,0 +1,16 @@
+pub mod calls;
+pub mod captures;
+pub mod clones;
+pub mod comparisons;
+pub mod const_eval;
+pub mod declares;
+pub mod diff;
+pub mod exhaustiveness;
+pub mod hierarchy;
+pub mod index;
+pub mod overrides;
+pub mod pedantic;
+pub mod references;
+pub mod resolver;
+pub mod shapes;
+pub mod structural;
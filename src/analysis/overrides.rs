@@ -0,0 +1,528 @@
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::modifiers::Visibility;
+
+use super::hierarchy::same_name;
+use super::hierarchy::Hierarchy;
+use super::hierarchy::ResolvedMethod;
+use super::index::ClassLike;
+use super::index::ClassLikeKind;
+use super::index::Method;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ViolationKind {
+    // `final public function foo()` overridden by `public function foo()`
+    OverridesFinal,
+    // `public static function foo()` overridden by `public function foo()`, or vice versa
+    StaticMismatch {
+        expected_static: bool,
+    },
+    // `public function foo()` overridden by `protected function foo()`
+    ReducedVisibility {
+        expected: Visibility,
+        found: Visibility,
+    },
+    // `function foo($a)` overridden by `function foo()`
+    MissingParameter {
+        name: ByteString,
+    },
+    // `function foo()` overridden by `function foo($a)`
+    RequiredParameterAdded {
+        name: ByteString,
+    },
+    // `function foo($a = 1)` overridden by `function foo($a)`
+    ParameterMadeRequired {
+        name: ByteString,
+    },
+    // `function foo(&$a)` overridden by `function foo($a)`, or vice versa
+    ParameterReferenceMismatch {
+        name: ByteString,
+    },
+    // `function foo(int|string $a)` overridden by `function foo(int $a)`
+    ParameterTypeNarrowed {
+        name: ByteString,
+        expected: Option<Type>,
+        found: Type,
+    },
+    // `function foo(): int` overridden by `function foo(): int|string`
+    ReturnTypeWidened {
+        expected: Type,
+        found: Option<Type>,
+    },
+}
+
+/// A method which is not compatible with the method it overrides or implements.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OverrideViolation {
+    pub class: ByteString,
+    pub method: ByteString,
+    /// The class-like declaring the overridden method.
+    pub parent: ByteString,
+    /// The name of the overriding method, which is located in a trait
+    /// when the method is imported from one.
+    pub span: Span,
+    pub kind: ViolationKind,
+}
+
+impl OverrideViolation {
+    pub fn message(&self) -> String {
+        let declaration = format!(
+            "{}::{}() must be compatible with {}::{}()",
+            self.class, self.method, self.parent, self.method
+        );
+
+        match &self.kind {
+            ViolationKind::OverridesFinal => format!(
+                "Cannot override final method {}::{}()",
+                self.parent, self.method
+            ),
+            ViolationKind::StaticMismatch { expected_static } => format!(
+                "Cannot make {} method {}::{}() {} in class {}",
+                if *expected_static {
+                    "static"
+                } else {
+                    "non static"
+                },
+                self.parent,
+                self.method,
+                if *expected_static {
+                    "non static"
+                } else {
+                    "static"
+                },
+                self.class
+            ),
+            ViolationKind::ReducedVisibility { expected, found } => format!(
+                "Access level to {}::{}() must be {} (as in class {}), found {}",
+                self.class,
+                self.method,
+                visibility_name(expected),
+                self.parent,
+                visibility_name(found)
+            ),
+            ViolationKind::MissingParameter { name } => {
+                format!("{}: missing parameter {}", declaration, name)
+            }
+            ViolationKind::RequiredParameterAdded { name } => {
+                format!("{}: parameter {} must be optional", declaration, name)
+            }
+            ViolationKind::ParameterMadeRequired { name } => {
+                format!("{}: parameter {} must be optional", declaration, name)
+            }
+            ViolationKind::ParameterReferenceMismatch { name } => format!(
+                "{}: parameter {} must have the same by-reference semantics",
+                declaration, name
+            ),
+            ViolationKind::ParameterTypeNarrowed {
+                name,
+                expected,
+                found,
+            } => format!(
+                "{}: parameter {} must accept {}, found {}",
+                declaration,
+                name,
+                expected
+                    .as_ref()
+                    .map_or_else(|| "mixed".to_string(), |t| t.to_string()),
+                found
+            ),
+            ViolationKind::ReturnTypeWidened { expected, found } => format!(
+                "{}: return type must be {}, found {}",
+                declaration,
+                expected,
+                found
+                    .as_ref()
+                    .map_or_else(|| "none".to_string(), |t| t.to_string()),
+            ),
+        }
+    }
+}
+
+/// Compare every method of the indexed class-likes against the methods it overrides
+/// or implements, following PHP's inheritance rules: parameters are contravariant,
+/// return types are covariant, and visibility can only be widened.
+///
+/// Class names which are not indexed are assumed to be compatible.
+pub fn check_overrides(hierarchy: &Hierarchy) -> Vec<OverrideViolation> {
+    let mut violations = Vec::new();
+
+    for class in hierarchy.index().classes() {
+        if class.kind == ClassLikeKind::Trait {
+            continue;
+        }
+
+        let checker = Checker { hierarchy, class };
+        let methods = hierarchy.all_methods_including_traits(&class.name);
+
+        let mut parents: Vec<ByteString> = class.parent.iter().cloned().collect();
+        parents.extend(class.interfaces.iter().cloned());
+
+        for method in methods.iter().filter(|method| {
+            same_name(&method.declaring_class.name, &class.name)
+                || method.declaring_class.kind == ClassLikeKind::Trait
+        }) {
+            let mut checked: Vec<(&ByteString, &ByteString)> = Vec::new();
+
+            for parent in parents.iter() {
+                let overridden = hierarchy
+                    .all_methods_including_traits(parent)
+                    .into_iter()
+                    .find(|candidate| same_name(&candidate.name, &method.name));
+
+                let overridden = match overridden {
+                    Some(overridden) => overridden,
+                    None => continue,
+                };
+
+                if overridden.visibility == Visibility::Private
+                    || checked.iter().any(|(class, name)| {
+                        same_name(class, &overridden.declaring_class.name)
+                            && same_name(name, &overridden.method.name.value)
+                    })
+                {
+                    continue;
+                }
+
+                checked.push((
+                    &overridden.declaring_class.name,
+                    &overridden.method.name.value,
+                ));
+
+                for kind in checker.check(method, &overridden) {
+                    violations.push(OverrideViolation {
+                        class: class.name.clone(),
+                        method: method.name.clone(),
+                        parent: overridden.declaring_class.name.clone(),
+                        span: method.method.name.span,
+                        kind,
+                    });
+                }
+            }
+        }
+    }
+
+    violations
+}
+
+struct Checker<'a, 'b> {
+    hierarchy: &'b Hierarchy<'a>,
+    class: &'a ClassLike,
+}
+
+impl<'a, 'b> Checker<'a, 'b> {
+    fn check(&self, child: &ResolvedMethod<'a>, parent: &ResolvedMethod<'a>) -> Vec<ViolationKind> {
+        let mut violations = Vec::new();
+
+        if parent.method.modifiers.has_final() {
+            violations.push(ViolationKind::OverridesFinal);
+        }
+
+        let expected_static = parent.method.modifiers.has_static();
+        if expected_static != child.method.modifiers.has_static() {
+            violations.push(ViolationKind::StaticMismatch { expected_static });
+        }
+
+        if rank(&child.visibility) > rank(&parent.visibility) {
+            violations.push(ViolationKind::ReducedVisibility {
+                expected: parent.visibility.clone(),
+                found: child.visibility.clone(),
+            });
+        }
+
+        // The signature of a constructor is only enforced when it is abstract.
+        if child.name.eq_ignore_ascii_case(b"__construct") && !parent.method.r#abstract {
+            return violations;
+        }
+
+        self.parameters(child.method, parent, &mut violations);
+
+        if let Some(expected) = &parent.method.return_type {
+            match &child.method.return_type {
+                Some(found) if self.is_subtype(found, expected, parent.declaring_class) => {}
+                found => violations.push(ViolationKind::ReturnTypeWidened {
+                    expected: expected.clone(),
+                    found: found.clone(),
+                }),
+            }
+        }
+
+        violations
+    }
+
+    fn parameters(
+        &self,
+        child: &Method,
+        parent: &ResolvedMethod<'a>,
+        violations: &mut Vec<ViolationKind>,
+    ) {
+        let variadic = child
+            .parameters
+            .last()
+            .filter(|parameter| parameter.variadic);
+
+        for (position, expected) in parent.method.parameters.iter().enumerate() {
+            let found = match child.parameters.get(position).or(variadic) {
+                Some(found) => found,
+                None => {
+                    violations.push(ViolationKind::MissingParameter {
+                        name: expected.name.clone(),
+                    });
+
+                    continue;
+                }
+            };
+
+            if (expected.optional || expected.variadic) && !(found.optional || found.variadic) {
+                violations.push(ViolationKind::ParameterMadeRequired {
+                    name: found.name.clone(),
+                });
+            }
+
+            if expected.by_reference != found.by_reference {
+                violations.push(ViolationKind::ParameterReferenceMismatch {
+                    name: found.name.clone(),
+                });
+            }
+
+            if let Some(found_type) = &found.data_type {
+                let compatible = match &expected.data_type {
+                    Some(expected_type) => {
+                        // Parameter types are contravariant, the child must accept everything the parent does.
+                        self.is_subtype_in(
+                            expected_type,
+                            parent.declaring_class,
+                            found_type,
+                            self.class,
+                        )
+                    }
+                    None => matches!(found_type, Type::Mixed(_)),
+                };
+
+                if !compatible {
+                    violations.push(ViolationKind::ParameterTypeNarrowed {
+                        name: found.name.clone(),
+                        expected: expected.data_type.clone(),
+                        found: found_type.clone(),
+                    });
+                }
+            }
+        }
+
+        for parameter in child.parameters.iter().skip(parent.method.parameters.len()) {
+            if !parameter.optional && !parameter.variadic {
+                violations.push(ViolationKind::RequiredParameterAdded {
+                    name: parameter.name.clone(),
+                });
+            }
+        }
+    }
+
+    fn is_subtype(&self, child: &Type, parent: &Type, parent_scope: &'a ClassLike) -> bool {
+        self.is_subtype_in(child, self.class, parent, parent_scope)
+    }
+
+    fn is_subtype_in(
+        &self,
+        child: &Type,
+        child_scope: &ClassLike,
+        parent: &Type,
+        parent_scope: &ClassLike,
+    ) -> bool {
+        let is_subtype = |child: &Type, parent: &Type| {
+            self.is_subtype_in(child, child_scope, parent, parent_scope)
+        };
+
+        match (child, parent) {
+            (Type::Void(_), Type::Void(_)) => true,
+            (Type::Void(_), _) => false,
+            (_, Type::Mixed(_)) | (Type::Never(_), _) => true,
+            (Type::Union(types), _) => types.iter().all(|t| is_subtype(t, parent)),
+            (Type::Nullable(span, inner), _) => {
+                is_subtype(&Type::Null(*span), parent) && is_subtype(inner, parent)
+            }
+            (_, Type::Union(types)) => types.iter().any(|t| is_subtype(child, t)),
+            (_, Type::Nullable(_, inner)) => {
+                matches!(child, Type::Null(_)) || is_subtype(child, inner)
+            }
+            (_, Type::Intersection(types)) => types.iter().all(|t| is_subtype(child, t)),
+            (Type::Intersection(types), _) => types.iter().any(|t| is_subtype(t, parent)),
+            (_, Type::StaticReference(_)) => matches!(child, Type::StaticReference(_)),
+            _ => {
+                let child = match self.class_name(child, child_scope) {
+                    Ok(Some(name)) => name,
+                    Ok(None) => return self.is_scalar_subtype(child, parent),
+                    Err(()) => return true,
+                };
+
+                match parent {
+                    Type::Object(_) => true,
+                    Type::Iterable(_) => self.is_class_subtype(&child, b"Traversable"),
+                    Type::Callable(_) => self.is_class_subtype(&child, b"Closure"),
+                    _ => match self.class_name(parent, parent_scope) {
+                        Ok(Some(parent)) => self.is_class_subtype(&child, &parent),
+                        Ok(None) => false,
+                        Err(()) => true,
+                    },
+                }
+            }
+        }
+    }
+
+    fn is_scalar_subtype(&self, child: &Type, parent: &Type) -> bool {
+        std::mem::discriminant(child) == std::mem::discriminant(parent)
+            || matches!(
+                (child, parent),
+                (Type::True(_) | Type::False(_), Type::Boolean(_))
+                    | (Type::Array(_), Type::Iterable(_))
+            )
+    }
+
+    /// The class name referenced by the given type, `None` when the type is not a class,
+    /// or an error when the class cannot be determined.
+    fn class_name(&self, data_type: &Type, scope: &ClassLike) -> Result<Option<ByteString>, ()> {
+        Ok(Some(match data_type {
+            Type::Named(_, name) => name.clone(),
+            Type::SelfReference(_) | Type::StaticReference(_) => scope.name.clone(),
+            Type::ParentReference(_) => match &scope.parent {
+                Some(parent) => parent.clone(),
+                None => return Err(()),
+            },
+            _ => return Ok(None),
+        }))
+    }
+
+    fn is_class_subtype(&self, child: &[u8], parent: &[u8]) -> bool {
+        if self.hierarchy.is_subtype_of(child, parent) {
+            return true;
+        }
+
+        // When part of the hierarchy is not indexed, the relation cannot be ruled out.
+        let index = self.hierarchy.index();
+
+        index.get(child).is_none()
+            || self
+                .hierarchy
+                .ancestors_of(child)
+                .iter()
+                .any(|ancestor| index.get(ancestor).is_none())
+    }
+}
+
+fn rank(visibility: &Visibility) -> u8 {
+    match visibility {
+        Visibility::Public => 0,
+        Visibility::Protected => 1,
+        Visibility::Private => 2,
+    }
+}
+
+fn visibility_name(visibility: &Visibility) -> &'static str {
+    match visibility {
+        Visibility::Public => "public",
+        Visibility::Protected => "protected",
+        Visibility::Private => "private",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_overrides;
+    use crate::analysis::hierarchy::Hierarchy;
+    use crate::analysis::index::SymbolIndex;
+
+    fn messages(code: &str) -> Vec<String> {
+        let mut index = SymbolIndex::new();
+        index.add(&mut crate::parse(code).unwrap());
+
+        check_overrides(&Hierarchy::new(&index))
+            .iter()
+            .map(|violation| violation.message())
+            .collect()
+    }
+
+    #[test]
+    fn test_compatible_overrides() {
+        let messages = messages(
+            r#"<?php
+            interface Shape { public function area(): int|float; public function scale(int $by): static; }
+            class Base implements Shape {
+                public function __construct(int $a) {}
+                public function area(): int|float { return 0; }
+                public function scale(int $by): static { return $this; }
+                protected function copy(self $other): ?Base { return null; }
+                public function each(&$value, ...$rest) {}
+                private function secret(int $a) {}
+            }
+            class Square extends Base {
+                public function __construct(string $a, string $b) {}
+                public function area(): int { return 0; }
+                public function copy(Base|string $other, $extra = null): Square { return $this; }
+                public function each(&$value, ...$rest): void {}
+                private function secret() {}
+            }
+            "#,
+        );
+
+        assert!(messages.is_empty(), "{:?}", messages);
+    }
+
+    #[test]
+    fn test_signature_violations() {
+        let messages = messages(
+            r#"<?php
+            class Base {
+                public function area(): int { return 0; }
+                public function scale(int|float $by, $round = false) {}
+                final public function name() {}
+                public static function make() {}
+                public function each(&$value) {}
+            }
+            class Square extends Base {
+                protected function area(): int|float { return 0; }
+                public function scale(int $by, $round, $precision) {}
+                public function name() {}
+                public function make() {}
+                public function each($value) {}
+            }
+            "#,
+        );
+
+        assert_eq!(
+            messages,
+            vec![
+                "Access level to Square::area() must be public (as in class Base), found protected",
+                "Square::area() must be compatible with Base::area(): return type must be int, found int|float",
+                "Square::scale() must be compatible with Base::scale(): parameter $by must accept int|float, found int",
+                "Square::scale() must be compatible with Base::scale(): parameter $round must be optional",
+                "Square::scale() must be compatible with Base::scale(): parameter $precision must be optional",
+                "Cannot override final method Base::name()",
+                "Cannot make static method Base::make() non static in class Square",
+                "Square::each() must be compatible with Base::each(): parameter $value must have the same by-reference semantics",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interface_and_trait_methods() {
+        let messages = messages(
+            r#"<?php
+            namespace App;
+            use Countable;
+            interface Repository { public function find(int $id): ?Model; }
+            interface Model {}
+            class User implements Model {}
+            trait FindsNothing { public function find(int $id): string { return ''; } }
+            class Users implements Repository { use FindsNothing; }
+            class Posts implements Repository, Countable { public function find(int|string $id): User { return new User(); } }
+            "#,
+        );
+
+        assert_eq!(
+            messages,
+            vec![
+                "App\\Users::find() must be compatible with App\\Repository::find(): return type must be ?App\\Model, found string",
+            ]
+        );
+    }
+}
@@ -0,0 +1,362 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::downcast::downcast_mut;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::control_flow::IfStatement;
+use crate::parser::ast::control_flow::IfStatementElseIf;
+use crate::parser::ast::control_flow::IfStatementElseIfBlock;
+use crate::parser::ast::loops::DoWhileStatement;
+use crate::parser::ast::loops::ForStatementIterator;
+use crate::parser::ast::loops::WhileStatement;
+use crate::parser::ast::operators::BitwiseOperation;
+use crate::parser::ast::operators::ComparisonOperation;
+use crate::parser::ast::operators::LogicalOperation;
+use crate::parser::ast::Expression;
+use crate::parser::ast::Statement;
+use crate::traverser::Visitor;
+
+/// A rule of the pedantic diagnostic set, flagging code which is valid but does not parse the
+/// way it reads.
+///
+/// None of the rules are enabled by default, each one is enabled by its id, e.g.
+/// `assignment-in-condition`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum PedanticRule {
+    /// `if ($a = foo())`, silenced by doubling the parentheses: `if (($a = foo()))`.
+    AssignmentInCondition,
+    /// `$a & 1 == 1`, which is `$a & (1 == 1)`.
+    BitwiseComparison,
+    /// `!$a == $b`, which is `(!$a) == $b`.
+    NegatedComparison,
+    /// `$a = foo() and bar()`, which is `($a = foo()) and bar()`.
+    LowPrecedenceLogical,
+    /// `@include 'foo.php'`, which also hides the errors of the included file.
+    SuppressedInclude,
+    /// `foo()::bar()`, calling a static method on the result of an expression.
+    StaticCallOnExpression,
+}
+
+impl PedanticRule {
+    pub const ALL: [PedanticRule; 6] = [
+        PedanticRule::AssignmentInCondition,
+        PedanticRule::BitwiseComparison,
+        PedanticRule::NegatedComparison,
+        PedanticRule::LowPrecedenceLogical,
+        PedanticRule::SuppressedInclude,
+        PedanticRule::StaticCallOnExpression,
+    ];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            PedanticRule::AssignmentInCondition => "assignment-in-condition",
+            PedanticRule::BitwiseComparison => "bitwise-comparison",
+            PedanticRule::NegatedComparison => "negated-comparison",
+            PedanticRule::LowPrecedenceLogical => "low-precedence-logical",
+            PedanticRule::SuppressedInclude => "suppressed-include",
+            PedanticRule::StaticCallOnExpression => "static-call-on-expression",
+        }
+    }
+}
+
+impl Display for PedanticRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+impl FromStr for PedanticRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PedanticRule::ALL
+            .into_iter()
+            .find(|rule| rule.id() == s)
+            .ok_or_else(|| format!("unknown pedantic rule `{}`", s))
+    }
+}
+
+/// How a construct flagged by a pedantic rule is reported, a warning by default.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    /// Reported without failing the check, e.g. the exit code of a linter.
+    #[default]
+    Warning,
+    /// Reported as failing the check.
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "warning" => Ok(Severity::Warning),
+            "error" => Ok(Severity::Error),
+            _ => Err(format!(
+                "unknown severity `{}`, expecting `warning` or `error`",
+                s
+            )),
+        }
+    }
+}
+
+/// A construct flagged by a pedantic rule.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PedanticWarning {
+    pub rule: PedanticRule,
+    pub span: Span, // the operator, or the start of the expression
+    pub message: String,
+}
+
+impl Display for PedanticWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} on line {} column {} [{}]",
+            self.message, self.span.line, self.span.column, self.rule
+        )
+    }
+}
+
+/// Check the given program against the given pedantic rules, returning the warnings in
+/// source order.
+pub fn pedantic(program: &mut [Statement], rules: &[PedanticRule]) -> Vec<PedanticWarning> {
+    let mut checker = Checker {
+        rules,
+        warnings: vec![],
+    };
+    for statement in program.iter_mut() {
+        let _ = checker.visit_node(statement);
+    }
+
+    checker
+        .warnings
+        .sort_by_key(|warning| warning.span.position);
+    checker.warnings
+}
+
+struct Checker<'a> {
+    rules: &'a [PedanticRule],
+    warnings: Vec<PedanticWarning>,
+}
+
+impl Checker<'_> {
+    fn report(&mut self, rule: PedanticRule, span: Span, message: &str) {
+        if self.rules.contains(&rule) {
+            self.warnings.push(PedanticWarning {
+                rule,
+                span,
+                message: message.to_string(),
+            });
+        }
+    }
+
+    fn condition(&mut self, condition: &Expression) {
+        if let Expression::AssignmentOperation(_) = condition {
+            self.report(
+                PedanticRule::AssignmentInCondition,
+                condition.start(),
+                "assignment used as a condition, wrap it in parentheses if intended",
+            );
+        }
+    }
+
+    fn bitwise(&mut self, span: Span, left: &Expression, right: &Expression) {
+        if is_comparison(left) || is_comparison(right) {
+            self.report(
+                PedanticRule::BitwiseComparison,
+                span,
+                "comparison binds tighter than the bitwise operator",
+            );
+        }
+    }
+}
+
+impl Visitor<()> for Checker<'_> {
+    fn visit(&mut self, node: &mut dyn Node) -> Result<(), ()> {
+        if let Some(statement) = downcast_mut::<IfStatement>(node) {
+            self.condition(&statement.condition);
+        } else if let Some(statement) = downcast_mut::<IfStatementElseIf>(node) {
+            self.condition(&statement.condition);
+        } else if let Some(statement) = downcast_mut::<IfStatementElseIfBlock>(node) {
+            self.condition(&statement.condition);
+        } else if let Some(statement) = downcast_mut::<WhileStatement>(node) {
+            self.condition(&statement.condition);
+        } else if let Some(statement) = downcast_mut::<DoWhileStatement>(node) {
+            self.condition(&statement.condition);
+        } else if let Some(iterator) = downcast_mut::<ForStatementIterator>(node) {
+            for condition in iterator.conditions.inner.iter() {
+                self.condition(condition);
+            }
+        } else if let Some(operation) = downcast_mut::<BitwiseOperation>(node) {
+            match operation {
+                BitwiseOperation::And { left, and, right } => self.bitwise(*and, left, right),
+                BitwiseOperation::Or { left, or, right } => self.bitwise(*or, left, right),
+                BitwiseOperation::Xor { left, xor, right } => self.bitwise(*xor, left, right),
+                _ => {}
+            }
+        } else if let Some(operation) = downcast_mut::<ComparisonOperation>(node) {
+            if let Some(Expression::LogicalOperation(LogicalOperation::Not { bang, .. })) =
+                comparison_left(operation)
+            {
+                self.report(
+                    PedanticRule::NegatedComparison,
+                    *bang,
+                    "negation binds tighter than the comparison",
+                );
+            }
+        } else if let Some(operation) = downcast_mut::<LogicalOperation>(node) {
+            if let LogicalOperation::LogicalAnd {
+                left, and: span, ..
+            }
+            | LogicalOperation::LogicalOr { left, or: span, .. }
+            | LogicalOperation::LogicalXor {
+                left, xor: span, ..
+            } = operation
+            {
+                if let Expression::AssignmentOperation(_) = left.as_ref() {
+                    self.report(
+                        PedanticRule::LowPrecedenceLogical,
+                        *span,
+                        "assignment binds tighter than the logical operator",
+                    );
+                }
+            }
+        } else if let Some(expression) = downcast_mut::<Expression>(node) {
+            match expression {
+                Expression::ErrorSuppress { at, expr }
+                    if matches!(
+                        expr.as_ref(),
+                        Expression::Include { .. }
+                            | Expression::IncludeOnce { .. }
+                            | Expression::Require { .. }
+                            | Expression::RequireOnce { .. }
+                    ) =>
+                {
+                    self.report(
+                        PedanticRule::SuppressedInclude,
+                        *at,
+                        "error suppression also applies to the included file",
+                    );
+                }
+                Expression::StaticMethodCall {
+                    target,
+                    double_colon,
+                    ..
+                }
+                | Expression::StaticVariableMethodCall {
+                    target,
+                    double_colon,
+                    ..
+                } if !matches!(
+                    target.as_ref(),
+                    Expression::Identifier(_)
+                        | Expression::Variable(_)
+                        | Expression::Self_ { .. }
+                        | Expression::Static { .. }
+                        | Expression::Parent { .. }
+                ) =>
+                {
+                    self.report(
+                        PedanticRule::StaticCallOnExpression,
+                        *double_colon,
+                        "static method called on the result of an expression",
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_comparison(expression: &Expression) -> bool {
+    matches!(expression, Expression::ComparisonOperation(_))
+}
+
+fn comparison_left(operation: &ComparisonOperation) -> Option<&Expression> {
+    match operation {
+        ComparisonOperation::Equal { left, .. }
+        | ComparisonOperation::Identical { left, .. }
+        | ComparisonOperation::NotEqual { left, .. }
+        | ComparisonOperation::AngledNotEqual { left, .. }
+        | ComparisonOperation::NotIdentical { left, .. }
+        | ComparisonOperation::LessThan { left, .. }
+        | ComparisonOperation::GreaterThan { left, .. }
+        | ComparisonOperation::LessThanOrEqual { left, .. }
+        | ComparisonOperation::GreaterThanOrEqual { left, .. } => Some(left),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pedantic;
+    use super::PedanticRule;
+
+    fn check(code: &str, rules: &[PedanticRule]) -> Vec<(PedanticRule, usize)> {
+        let mut program = crate::parse(code).unwrap();
+
+        pedantic(&mut program, rules)
+            .into_iter()
+            .map(|warning| (warning.rule, warning.span.column))
+            .collect()
+    }
+
+    #[test]
+    fn test_pedantic() {
+        let code = "<?php if ($a = foo()) {} while (($b = bar())) {} $c & 1 == 1; ($c & 1) == 1; !$d == $e; !($d == $e); $f = g() or h(); @include 'i.php'; @j(); k()::l(); K::l(); $k::l();";
+
+        assert_eq!(
+            check(code, &PedanticRule::ALL),
+            vec![
+                (PedanticRule::AssignmentInCondition, 11),
+                (PedanticRule::BitwiseComparison, 53),
+                (PedanticRule::NegatedComparison, 78),
+                (PedanticRule::LowPrecedenceLogical, 111),
+                (PedanticRule::SuppressedInclude, 119),
+                (PedanticRule::StaticCallOnExpression, 146),
+            ]
+        );
+        assert_eq!(
+            check(code, &[PedanticRule::SuppressedInclude]),
+            vec![(PedanticRule::SuppressedInclude, 119)]
+        );
+        assert_eq!(check(code, &[]), vec![]);
+    }
+
+    #[test]
+    fn test_conditions() {
+        assert_eq!(
+            check(
+                "<?php if (1) {} elseif ($a = 1) {} do {} while ($b = 2); for (; $c = 3, $d = 4;) {}",
+                &[PedanticRule::AssignmentInCondition]
+            )
+            .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_rule_ids() {
+        for rule in PedanticRule::ALL {
+            assert_eq!(rule.to_string().parse(), Ok(rule));
+        }
+        assert_eq!(
+            "unused-variable".parse::<PedanticRule>(),
+            Err("unknown pedantic rule `unused-variable`".to_string())
+        );
+    }
+}
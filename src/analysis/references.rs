@@ -0,0 +1,840 @@
+use crate::downcast::downcast;
+use crate::downcast::downcast_mut;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::arguments::ArgumentList;
+use crate::parser::ast::arguments::SingleArgument;
+use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::classes::AnonymousClass;
+use crate::parser::ast::classes::ClassExtends;
+use crate::parser::ast::classes::ClassImplements;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::comments::CommentFormat;
+use crate::parser::ast::comments::CommentGroup;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::constant::ConstantStatement;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::enums::BackedEnumCase;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::UnitEnumCase;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::functions::AbstractConstructor;
+use crate::parser::ast::functions::AbstractMethod;
+use crate::parser::ast::functions::ArrowFunction;
+use crate::parser::ast::functions::Closure;
+use crate::parser::ast::functions::ClosureUse;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::ConstructorParameter;
+use crate::parser::ast::functions::FunctionBody;
+use crate::parser::ast::functions::FunctionParameter;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::functions::MethodBody;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::NameKind;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceExtends;
+use crate::parser::ast::interfaces::InterfaceStatement;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::properties::Property;
+use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::traits::TraitStatement;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::traits::TraitUsageAdaptation;
+use crate::parser::ast::try_block::CatchType;
+use crate::parser::ast::Expression;
+use crate::parser::ast::Statement;
+use crate::parser::ast::UseKind;
+
+use super::resolver::is_special_class_name;
+use super::resolver::NameResolver;
+
+// Docblock tags followed by a type, without their `phpstan-` or `psalm-` prefix.
+const TYPE_TAGS: [&[u8]; 15] = [
+    b"param",
+    b"return",
+    b"var",
+    b"throws",
+    b"property",
+    b"property-read",
+    b"property-write",
+    b"method",
+    b"mixin",
+    b"extends",
+    b"implements",
+    b"use",
+    b"template-extends",
+    b"template-implements",
+    b"template-use",
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SymbolKind {
+    Class,
+    Function,
+    Constant,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ReferenceKind {
+    // `use Foo\Bar;`
+    Import,
+    // `new Foo()`
+    New,
+    // `class Foo extends Bar`, or `interface Foo extends Bar`
+    Extends,
+    // `class Foo implements Bar`, or `enum Foo implements Bar`
+    Implements,
+    // `use Bar;` within a class-like body
+    TraitUse,
+    // `$foo instanceof Bar`
+    Instanceof,
+    // `Foo::bar()`
+    StaticCall,
+    // `Foo::BAR`, `Foo::$bar`, or `Foo::class`
+    StaticAccess,
+    // `function (Foo $foo): Bar`
+    TypeHint,
+    // `catch (Foo $e)`
+    Catch,
+    // `#[Foo]`
+    Attribute,
+    // `@param Foo $foo`
+    Docblock,
+    // `foo()`
+    Call,
+    // `FOO`
+    Fetch,
+}
+
+/// A reference to a class-like, function, or constant.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Reference {
+    /// The fully qualified name of the referenced symbol.
+    pub name: ByteString,
+    /// The global name an unqualified function or constant name falls back to at runtime,
+    /// when `name` is not declared.
+    pub fallback: Option<ByteString>,
+    pub symbol: SymbolKind,
+    pub kind: ReferenceKind,
+    pub span: Span,
+}
+
+/// Collect the references to class-likes, functions, and constants of a single file.
+///
+/// The special `self`, `static`, and `parent` class names are not included.
+pub fn collect(program: &mut [Statement]) -> Vec<Reference> {
+    let mut collector = Collector::default();
+    collector.statements(program);
+
+    collector.references
+}
+
+#[derive(Debug, Default)]
+struct Collector {
+    resolver: NameResolver,
+    // The `@template` names declared by the docblocks of the enclosing nodes.
+    templates: Vec<Vec<u8>>,
+    references: Vec<Reference>,
+}
+
+impl Collector {
+    fn statements(&mut self, statements: &mut [Statement]) {
+        for statement in statements.iter_mut() {
+            match statement {
+                Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                    self.resolver.enter_namespace(Some(&namespace.name.value));
+                    self.statements(&mut namespace.statements);
+                }
+                Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                    self.resolver
+                        .enter_namespace(namespace.name.as_ref().map(|name| &name.value[..]));
+                    self.statements(&mut namespace.body.statements);
+                }
+                _ => self.node(statement),
+            }
+        }
+    }
+
+    fn node(&mut self, node: &mut dyn Node) {
+        let templates = self.templates.len();
+        self.visit(node);
+        self.templates.truncate(templates);
+    }
+
+    fn visit(&mut self, node: &mut dyn Node) {
+        if let Some(group) = comments(node) {
+            self.docblocks(group);
+        }
+
+        if let Some(groups) = attributes(node) {
+            for attribute in groups.iter_mut().flat_map(|group| group.members.iter_mut()) {
+                self.class(&attribute.name, ReferenceKind::Attribute);
+
+                if let Some(arguments) = &mut attribute.arguments {
+                    self.node(arguments);
+                }
+            }
+        }
+
+        if let Some(expression) = downcast_mut::<Expression>(node) {
+            return self.expression(expression);
+        }
+
+        if let Some(Statement::Use(statement)) = downcast::<Statement>(node) {
+            for r#use in statement.uses.iter() {
+                let kind = r#use.kind.unwrap_or(statement.kind);
+                self.import(kind, r#use.name.value.to_vec(), r#use.name.span);
+            }
+
+            return self.resolver.import_statement(statement);
+        }
+
+        if let Some(Statement::GroupUse(statement)) = downcast::<Statement>(node) {
+            let prefix = statement.prefix.value.strip_prefix(b"\\");
+            let prefix = prefix.unwrap_or(&statement.prefix.value);
+
+            for r#use in statement.uses.iter() {
+                let mut name = prefix.to_vec();
+                if !name.ends_with(b"\\") {
+                    name.push(b'\\');
+                }
+                name.extend_from_slice(&r#use.name.value);
+
+                let kind = r#use.kind.unwrap_or(statement.kind);
+                self.import(kind, name, r#use.name.span);
+            }
+
+            return self.resolver.import_group_statement(statement);
+        }
+
+        if let Some(extends) = downcast::<ClassExtends>(node) {
+            self.class(&extends.parent, ReferenceKind::Extends);
+        } else if let Some(extends) = downcast::<InterfaceExtends>(node) {
+            for parent in extends.parents.inner.iter() {
+                self.class(parent, ReferenceKind::Extends);
+            }
+        } else if let Some(implements) = downcast::<ClassImplements>(node) {
+            for interface in implements.interfaces.inner.iter() {
+                self.class(interface, ReferenceKind::Implements);
+            }
+        } else if let Some(statement) = downcast::<UnitEnumStatement>(node) {
+            for interface in statement.implements.iter() {
+                self.class(interface, ReferenceKind::Implements);
+            }
+        } else if let Some(statement) = downcast::<BackedEnumStatement>(node) {
+            for interface in statement.implements.iter() {
+                self.class(interface, ReferenceKind::Implements);
+            }
+        } else if let Some(usage) = downcast::<TraitUsage>(node) {
+            self.trait_usage(usage);
+        } else if let Some(catch) = downcast::<CatchType>(node) {
+            match catch {
+                CatchType::Identifier(identifier) => self.class(identifier, ReferenceKind::Catch),
+                CatchType::Union(identifiers) => {
+                    for identifier in identifiers.iter() {
+                        self.class(identifier, ReferenceKind::Catch);
+                    }
+                }
+            }
+        } else if let Some(Type::Named(span, name)) = downcast::<Type>(node) {
+            // nested types are visited as children.
+            self.class_name(name, *span, ReferenceKind::TypeHint);
+        }
+
+        for child in node.children() {
+            self.node(child);
+        }
+    }
+
+    fn expression(&mut self, expression: &mut Expression) {
+        match expression {
+            Expression::FunctionCall { target, .. }
+            | Expression::FunctionClosureCreation { target, .. } => {
+                if let Expression::Identifier(Identifier::SimpleIdentifier(identifier)) =
+                    target.as_ref()
+                {
+                    let name = self.resolver.resolve_function(&identifier.value);
+                    let fallback = self.fallback(&identifier.value, &name);
+
+                    self.push(
+                        name,
+                        fallback,
+                        SymbolKind::Function,
+                        ReferenceKind::Call,
+                        identifier.span,
+                    );
+                }
+            }
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                let name = self.resolver.resolve_constant(&identifier.value);
+                let fallback = self.fallback(&identifier.value, &name);
+
+                self.push(
+                    name,
+                    fallback,
+                    SymbolKind::Constant,
+                    ReferenceKind::Fetch,
+                    identifier.span,
+                );
+            }
+            Expression::New { target, .. } => self.class_target(target, ReferenceKind::New),
+            Expression::Instanceof { right, .. } => {
+                self.class_target(right, ReferenceKind::Instanceof)
+            }
+            Expression::StaticMethodCall { target, .. }
+            | Expression::StaticVariableMethodCall { target, .. }
+            | Expression::StaticMethodClosureCreation { target, .. }
+            | Expression::StaticVariableMethodClosureCreation { target, .. } => {
+                self.class_target(target, ReferenceKind::StaticCall)
+            }
+            Expression::StaticPropertyFetch { target, .. }
+            | Expression::ConstantFetch { target, .. } => {
+                self.class_target(target, ReferenceKind::StaticAccess)
+            }
+            _ => {}
+        }
+
+        // the names of functions, classes, methods, and properties are not constants.
+        let names = matches!(
+            expression,
+            Expression::FunctionCall { .. }
+                | Expression::FunctionClosureCreation { .. }
+                | Expression::MethodCall { .. }
+                | Expression::MethodClosureCreation { .. }
+                | Expression::NullsafeMethodCall { .. }
+                | Expression::PropertyFetch { .. }
+                | Expression::NullsafePropertyFetch { .. }
+                | Expression::New { .. }
+                | Expression::StaticMethodCall { .. }
+                | Expression::StaticVariableMethodCall { .. }
+                | Expression::StaticMethodClosureCreation { .. }
+                | Expression::StaticVariableMethodClosureCreation { .. }
+                | Expression::StaticPropertyFetch { .. }
+                | Expression::ConstantFetch { .. }
+        );
+        let instanceof = matches!(expression, Expression::Instanceof { .. });
+
+        for (position, child) in expression.children().into_iter().enumerate() {
+            let name = matches!(
+                downcast::<Expression>(child),
+                Some(Expression::Identifier(Identifier::SimpleIdentifier(_)))
+            );
+
+            // `FOO instanceof Bar`
+            if name && (names || instanceof && position == 1) {
+                continue;
+            }
+
+            self.node(child);
+        }
+    }
+
+    fn import(&mut self, kind: UseKind, name: Vec<u8>, span: Span) {
+        let name = match name.strip_prefix(b"\\") {
+            Some(name) => name.to_vec(),
+            None => name,
+        };
+        let symbol = match kind {
+            UseKind::Normal => SymbolKind::Class,
+            UseKind::Function => SymbolKind::Function,
+            UseKind::Const => SymbolKind::Constant,
+        };
+
+        self.push(name.into(), None, symbol, ReferenceKind::Import, span);
+    }
+
+    fn trait_usage(&mut self, usage: &TraitUsage) {
+        for r#trait in usage.traits.iter() {
+            self.class(r#trait, ReferenceKind::TraitUse);
+        }
+
+        for adaptation in usage.adaptations.iter() {
+            let (r#trait, insteadof) = match adaptation {
+                TraitUsageAdaptation::Alias { r#trait, .. }
+                | TraitUsageAdaptation::Visibility { r#trait, .. } => (r#trait, &[][..]),
+                TraitUsageAdaptation::Precedence {
+                    r#trait, insteadof, ..
+                } => (r#trait, &insteadof[..]),
+            };
+
+            for name in r#trait.iter().chain(insteadof.iter()) {
+                self.class(name, ReferenceKind::TraitUse);
+            }
+        }
+    }
+
+    fn class_target(&mut self, target: &Expression, kind: ReferenceKind) {
+        if let Expression::Identifier(Identifier::SimpleIdentifier(identifier)) = target {
+            self.class(identifier, kind);
+        }
+    }
+
+    fn class(&mut self, identifier: &SimpleIdentifier, kind: ReferenceKind) {
+        self.class_name(&identifier.value, identifier.span, kind);
+    }
+
+    fn class_name(&mut self, name: &[u8], span: Span, kind: ReferenceKind) {
+        if is_special_class_name(name) {
+            return;
+        }
+
+        let name = self.resolver.resolve_class(name);
+        self.push(name, None, SymbolKind::Class, kind, span);
+    }
+
+    // The global name of an unqualified function or constant name within a namespace.
+    fn fallback(&self, name: &[u8], resolved: &ByteString) -> Option<ByteString> {
+        if NameKind::of(name) == NameKind::Unqualified
+            && self.resolver.namespace().is_some()
+            && *resolved == self.resolver.qualify(name)
+        {
+            Some(name.into())
+        } else {
+            None
+        }
+    }
+
+    fn push(
+        &mut self,
+        name: ByteString,
+        fallback: Option<ByteString>,
+        symbol: SymbolKind,
+        kind: ReferenceKind,
+        span: Span,
+    ) {
+        self.references.push(Reference {
+            name,
+            fallback,
+            symbol,
+            kind,
+            span,
+        });
+    }
+
+    // Record the class names within the types of the tags of the document comments.
+    fn docblocks(&mut self, group: &CommentGroup) {
+        for comment in group.comments.iter() {
+            if comment.format != CommentFormat::Document {
+                continue;
+            }
+
+            let content = &comment.content[..];
+            self.templates
+                .extend(templates(content).into_iter().map(|name| name.to_vec()));
+
+            for (offset, word) in docblock_types(content)
+                .into_iter()
+                .flat_map(|(start, end)| words(content, start, end))
+            {
+                if self.templates.iter().any(|template| template == word) {
+                    continue;
+                }
+
+                // pseudo-types, such as `list` or `non-empty-string`, are not named types.
+                let name = match crate::parse_docblock_type(word) {
+                    Ok(Type::Named(_, name)) => name,
+                    _ => continue,
+                };
+
+                let span = offset_span(comment.span, content, offset);
+                self.class_name(&name, span, ReferenceKind::Docblock);
+            }
+        }
+    }
+}
+
+// The comments of the given node, which are not visited as children.
+fn comments(node: &dyn Node) -> Option<&CommentGroup> {
+    macro_rules! comments_of {
+        ($($node:ty),+ $(,)?) => {
+            $(
+                if let Some(node) = downcast::<$node>(node) {
+                    return Some(&node.comments);
+                }
+            )+
+        };
+    }
+
+    comments_of!(
+        FunctionStatement,
+        FunctionParameter,
+        FunctionBody,
+        ConcreteMethod,
+        AbstractMethod,
+        ConcreteConstructor,
+        AbstractConstructor,
+        ConstructorParameter,
+        MethodBody,
+        Closure,
+        ClosureUse,
+        ArrowFunction,
+        ClassishConstant,
+        ConstantStatement,
+        ArgumentList,
+        SingleArgument,
+    );
+
+    None
+}
+
+// The attributes of the given node, which are not visited as children.
+fn attributes(node: &mut dyn Node) -> Option<&mut Vec<AttributeGroup>> {
+    macro_rules! attributes_of {
+        ($($node:ty),+ $(,)?) => {
+            $(
+                if downcast::<$node>(node).is_some() {
+                    return downcast_mut::<$node>(node).map(|node| &mut node.attributes);
+                }
+            )+
+        };
+    }
+
+    attributes_of!(
+        FunctionStatement,
+        FunctionParameter,
+        Closure,
+        ArrowFunction,
+        ConcreteMethod,
+        AbstractMethod,
+        ConcreteConstructor,
+        AbstractConstructor,
+        ConstructorParameter,
+        ClassStatement,
+        AnonymousClass,
+        InterfaceStatement,
+        TraitStatement,
+        UnitEnumStatement,
+        UnitEnumCase,
+        BackedEnumStatement,
+        BackedEnumCase,
+        ClassishConstant,
+        Property,
+        VariableProperty,
+    );
+
+    None
+}
+
+// The byte ranges of the types following the tags of a document comment.
+fn docblock_types(content: &[u8]) -> Vec<(usize, usize)> {
+    let mut types = Vec::new();
+    let mut position = 0;
+
+    while let Some(at) = content[position..].iter().position(|b| *b == b'@') {
+        let start = position + at + 1;
+        let end = skip(content, start, |b| b.is_ascii_alphanumeric() || b == b'-');
+        position = end;
+
+        let tag = &content[start..end];
+        let tag = tag
+            .strip_prefix(b"phpstan-")
+            .or_else(|| tag.strip_prefix(b"psalm-"))
+            .unwrap_or(tag);
+
+        let mut start = skip(content, end, |b| b == b' ' || b == b'\t');
+        if tag == b"template" || (tag.starts_with(b"template-") && !TYPE_TAGS.contains(&tag)) {
+            // `@template T of Foo`
+            let name = skip(content, start, |b| !b.is_ascii_whitespace());
+            let of = skip(content, name, |b| b == b' ' || b == b'\t');
+            if !content[of..].starts_with(b"of ") {
+                continue;
+            }
+
+            start = skip(content, of + 2, |b| b == b' ' || b == b'\t');
+        } else if !TYPE_TAGS.contains(&tag) {
+            continue;
+        }
+
+        if tag == b"method" && content[start..].starts_with(b"static ") {
+            start = skip(content, start + 6, |b| b == b' ' || b == b'\t');
+        }
+
+        let end = type_end(content, start);
+        // `@method foo()` has no return type.
+        if tag == b"method"
+            && content[start..end].ends_with(b")")
+            && !is_callable(&content[start..end])
+        {
+            continue;
+        }
+
+        types.push((start, end));
+        position = end;
+    }
+
+    types
+}
+
+// The end of a type, which ends at the first whitespace outside of brackets,
+// unless it continues with a union or an intersection.
+fn type_end(content: &[u8], start: usize) -> usize {
+    let mut depth = 0;
+    let mut position = start;
+
+    while position < content.len() {
+        match content[position] {
+            b'<' | b'(' | b'{' | b'[' => depth += 1,
+            b'>' | b')' | b'}' | b']' if depth > 0 => depth -= 1,
+            b'*' if content[position..].starts_with(b"*/") => break,
+            b' ' | b'\t' if depth == 0 => {
+                let next = skip(content, position, |b| b == b' ' || b == b'\t');
+                let previous = content[position - 1];
+                if matches!(previous, b'|' | b'&' | b':' | b',')
+                    || matches!(content.get(next), Some(b'|' | b'&'))
+                {
+                    position = next;
+
+                    continue;
+                }
+
+                break;
+            }
+            byte if byte.is_ascii_whitespace() && depth == 0 => break,
+            _ => {}
+        }
+
+        position += 1;
+    }
+
+    position
+}
+
+// The names within the given type, along with their offsets, skipping variables,
+// shape keys, and class constants.
+fn words(content: &[u8], start: usize, end: usize) -> Vec<(usize, &[u8])> {
+    let mut words = Vec::new();
+    let mut position = start;
+
+    while position < end {
+        let byte = content[position];
+        if !(byte.is_ascii_alphabetic() || byte == b'_' || byte == b'\\' || byte >= 0x80) {
+            position += 1;
+
+            continue;
+        }
+
+        let word_end = skip(content, position, |b| {
+            b.is_ascii_alphanumeric() || b == b'_' || b == b'\\' || b == b'-' || b >= 0x80
+        })
+        .min(end);
+
+        let variable = position > start && content[position - 1] == b'$';
+        let constant = position > start + 1 && content[position - 2..position] == *b"::";
+        let key =
+            matches!(content.get(word_end), Some(b':')) && !content[word_end..].starts_with(b"::");
+
+        if !variable && !constant && !key {
+            words.push((position, &content[position..word_end]));
+        }
+
+        position = word_end;
+    }
+
+    words
+}
+
+// The names declared by the `@template` tags of a document comment.
+fn templates(content: &[u8]) -> Vec<&[u8]> {
+    let mut templates = Vec::new();
+    let mut position = 0;
+
+    while let Some(at) = content[position..].iter().position(|b| *b == b'@') {
+        let start = position + at + 1;
+        let end = skip(content, start, |b| b.is_ascii_alphanumeric() || b == b'-');
+        position = end;
+
+        let tag = &content[start..end];
+        let tag = tag
+            .strip_prefix(b"phpstan-")
+            .or_else(|| tag.strip_prefix(b"psalm-"))
+            .unwrap_or(tag);
+
+        if tag == b"template" || tag == b"template-covariant" || tag == b"template-contravariant" {
+            let start = skip(content, end, |b| b == b' ' || b == b'\t');
+            let end = skip(content, start, |b| !b.is_ascii_whitespace());
+            templates.push(&content[start..end]);
+        }
+    }
+
+    templates
+}
+
+fn is_callable(r#type: &[u8]) -> bool {
+    let r#type = r#type.strip_prefix(b"\\").unwrap_or(r#type);
+
+    [
+        &b"callable("[..],
+        b"Closure(",
+        b"pure-callable(",
+        b"pure-Closure(",
+    ]
+    .iter()
+    .any(|prefix| {
+        r#type.len() > prefix.len() && r#type[..prefix.len()].eq_ignore_ascii_case(prefix)
+    })
+}
+
+fn skip(content: &[u8], start: usize, predicate: impl Fn(u8) -> bool) -> usize {
+    content[start..]
+        .iter()
+        .position(|b| !predicate(*b))
+        .map_or(content.len(), |position| start + position)
+}
+
+// The span of the given offset within a comment.
+fn offset_span(span: Span, content: &[u8], offset: usize) -> Span {
+    span.advance(&content[..offset])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReferenceKind;
+    use super::SymbolKind;
+    use crate::analysis::index::SymbolIndex;
+
+    fn index(files: &[&str]) -> SymbolIndex {
+        let mut index = SymbolIndex::new();
+        for file in files {
+            index.add(&mut crate::parse(file).unwrap());
+        }
+
+        index
+    }
+
+    fn kinds(index: &SymbolIndex, symbol: SymbolKind, name: &str) -> Vec<ReferenceKind> {
+        index
+            .references_to(symbol, name.as_bytes())
+            .iter()
+            .map(|reference| reference.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_class_references() {
+        let index = index(&[
+            "<?php namespace App; class User {}",
+            "<?php
+            namespace App\\Http;
+
+            use App\\User;
+
+            #[User]
+            class Controller extends User implements \\App\\User {
+                use User;
+
+                function show(?User $user): User|int {
+                    try {
+                        return new User() instanceof User ? User::find() : User::class;
+                    } catch (User $e) {
+                    }
+                }
+            }",
+        ]);
+
+        assert_eq!(
+            kinds(&index, SymbolKind::Class, "app\\user"),
+            [
+                ReferenceKind::Import,
+                ReferenceKind::Attribute,
+                ReferenceKind::Extends,
+                ReferenceKind::Implements,
+                ReferenceKind::TraitUse,
+                ReferenceKind::TypeHint,
+                ReferenceKind::TypeHint,
+                ReferenceKind::Instanceof,
+                ReferenceKind::New,
+                ReferenceKind::StaticCall,
+                ReferenceKind::StaticAccess,
+                ReferenceKind::Catch,
+            ]
+        );
+
+        let import = index.references_to(SymbolKind::Class, b"App\\User")[0];
+        assert_eq!((import.span.line, import.span.column), (4, 17));
+    }
+
+    #[test]
+    fn test_docblock_references() {
+        let index = index(&["<?php
+            namespace App;
+
+            use Models\\User;
+
+            /**
+             * @template T of Model
+             * @param array<int, User>|null $users The users.
+             * @param T $model
+             * @return list<Post::TYPE|array{user: Comment}>
+             * @throws \\RuntimeException
+             */
+            function handle($users, $model) {
+                /** @var T $copy */
+                $copy = copy($model);
+            }"]);
+
+        let names = index
+            .references()
+            .iter()
+            .filter(|reference| reference.kind == ReferenceKind::Docblock)
+            .map(|reference| reference.name.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            names,
+            [
+                "App\\Model",
+                "Models\\User",
+                "App\\Post",
+                "App\\Comment",
+                "RuntimeException"
+            ]
+        );
+
+        let user = index.references_to(SymbolKind::Class, b"Models\\User")[1];
+        assert_eq!((user.span.line, user.span.column), (8, 34));
+    }
+
+    #[test]
+    fn test_function_and_constant_references() {
+        let index = index(&[
+            "<?php namespace App; function strlen() {} const VERSION = 1;",
+            "<?php namespace App; strlen(VERSION); \\strlen(PHP_EOL); $a->strlen; $a->strlen(); $a::VERSION;",
+            "<?php namespace Other; use function App\\strlen; use const App\\VERSION; strlen(...); count(VERSION);",
+        ]);
+
+        assert_eq!(
+            kinds(&index, SymbolKind::Function, "App\\strlen"),
+            [
+                ReferenceKind::Call,
+                ReferenceKind::Import,
+                ReferenceKind::Call
+            ]
+        );
+        assert_eq!(
+            kinds(&index, SymbolKind::Function, "strlen"),
+            [ReferenceKind::Call]
+        );
+        assert_eq!(
+            kinds(&index, SymbolKind::Function, "count"),
+            [ReferenceKind::Call]
+        );
+
+        assert_eq!(
+            kinds(&index, SymbolKind::Constant, "app\\VERSION"),
+            [
+                ReferenceKind::Fetch,
+                ReferenceKind::Import,
+                ReferenceKind::Fetch
+            ]
+        );
+        assert!(index
+            .references_to(SymbolKind::Constant, b"App\\version")
+            .is_empty());
+        assert_eq!(
+            kinds(&index, SymbolKind::Constant, "PHP_EOL"),
+            [ReferenceKind::Fetch]
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use crate::lexer::byte_string::ByteString;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::identifiers::NameKind;
+use crate::parser::ast::GroupUseStatement;
+use crate::parser::ast::UseKind;
+use crate::parser::ast::UseStatement;
+
+/// Resolves names against the current namespace and `use` imports of a file.
+///
+/// Resolved names are fully qualified, without a leading `\`.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct NameResolver {
+    namespace: Option<ByteString>,
+    // (kind, lowercased alias, fully qualified name)
+    imports: Vec<(UseKind, Vec<u8>, ByteString)>,
+}
+
+impl NameResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enter a new namespace, discarding all imports of the previous one.
+    pub fn enter_namespace(&mut self, namespace: Option<&[u8]>) {
+        self.namespace = namespace
+            .map(|namespace| strip_leading_backslash(namespace).into())
+            .filter(|namespace: &ByteString| !namespace.is_empty());
+        self.imports.clear();
+    }
+
+    pub fn namespace(&self) -> Option<&ByteString> {
+        self.namespace.as_ref()
+    }
+
+    pub fn import(&mut self, kind: UseKind, name: &[u8], alias: Option<&[u8]>) {
+        let name = strip_leading_backslash(name);
+        let alias = alias.unwrap_or_else(|| last_segment(name));
+
+        self.imports
+            .push((kind, alias.to_ascii_lowercase(), name.into()));
+    }
+
+    pub fn import_statement(&mut self, statement: &UseStatement) {
+        for r#use in statement.uses.iter() {
+            self.import(
+                r#use.kind.unwrap_or(statement.kind),
+                &r#use.name.value,
+                r#use.alias.as_ref().map(|alias| &alias.value[..]),
+            );
+        }
+    }
+
+    pub fn import_group_statement(&mut self, statement: &GroupUseStatement) {
+        let prefix = strip_leading_backslash(&statement.prefix.value);
+
+        for r#use in statement.uses.iter() {
+            let mut name = prefix.to_vec();
+            if !name.ends_with(b"\\") {
+                name.push(b'\\');
+            }
+            name.extend_from_slice(&r#use.name.value);
+
+            self.import(
+                r#use.kind.unwrap_or(statement.kind),
+                &name,
+                r#use.alias.as_ref().map(|alias| &alias.value[..]),
+            );
+        }
+    }
+
+    /// Qualify the name of a declaration with the current namespace.
+    pub fn qualify(&self, name: &[u8]) -> ByteString {
+        match &self.namespace {
+            Some(namespace) => {
+                let mut qualified = namespace.to_vec();
+                qualified.push(b'\\');
+                qualified.extend_from_slice(name);
+                qualified.into()
+            }
+            None => name.into(),
+        }
+    }
+
+    /// Resolve a class, interface, trait, or enum name.
+    ///
+    /// The special `self`, `static`, and `parent` names are returned as is.
+    pub fn resolve_class(&self, name: &[u8]) -> ByteString {
+        if is_special_class_name(name) {
+            return name.into();
+        }
+
+        self.resolve(UseKind::Normal, name)
+    }
+
+    /// Resolve the class names within a parameter, property, or return type.
+    pub fn resolve_type(&self, data_type: &Type) -> Type {
+        match data_type {
+            Type::Named(span, name) => Type::Named(*span, self.resolve_class(name)),
+            Type::Nullable(span, inner) => {
+                Type::Nullable(*span, Box::new(self.resolve_type(inner)))
+            }
+            Type::Union(inner) => Type::Union(inner.iter().map(|t| self.resolve_type(t)).collect()),
+            Type::Intersection(inner) => {
+                Type::Intersection(inner.iter().map(|t| self.resolve_type(t)).collect())
+            }
+            _ => data_type.clone(),
+        }
+    }
+
+    /// Resolve a function name.
+    ///
+    /// Unqualified function names fall back to the global namespace at runtime,
+    /// therefore the namespaced name is only a candidate for those.
+    pub fn resolve_function(&self, name: &[u8]) -> ByteString {
+        self.resolve(UseKind::Function, name)
+    }
+
+    /// Resolve a constant name.
+    ///
+    /// Unqualified constant names fall back to the global namespace at runtime,
+    /// therefore the namespaced name is only a candidate for those.
+    pub fn resolve_constant(&self, name: &[u8]) -> ByteString {
+        self.resolve(UseKind::Const, name)
+    }
+
+    fn resolve(&self, kind: UseKind, name: &[u8]) -> ByteString {
+        let (first, rest) = match NameKind::of(name) {
+            NameKind::FullyQualified => return name[1..].into(),
+            NameKind::Relative => return self.qualify(&name[10..]),
+            NameKind::Qualified => {
+                let position = name.iter().position(|b| *b == b'\\').unwrap_or(name.len());
+
+                (&name[..position], Some(&name[position..]))
+            }
+            NameKind::Unqualified => (name, None),
+        };
+
+        // Qualified names are always resolved against class imports.
+        let kind = if rest.is_some() {
+            UseKind::Normal
+        } else {
+            kind
+        };
+        let first = first.to_ascii_lowercase();
+        let import = self
+            .imports
+            .iter()
+            .rev()
+            .find(|(import_kind, alias, _)| *import_kind == kind && *alias == first);
+
+        match (import, rest) {
+            (Some((_, _, imported)), Some(rest)) => {
+                let mut resolved = imported.to_vec();
+                resolved.extend_from_slice(rest);
+                resolved.into()
+            }
+            (Some((_, _, imported)), None) => imported.clone(),
+            (None, _) => self.qualify(name),
+        }
+    }
+}
+
+pub fn is_special_class_name(name: &[u8]) -> bool {
+    name.eq_ignore_ascii_case(b"self")
+        || name.eq_ignore_ascii_case(b"static")
+        || name.eq_ignore_ascii_case(b"parent")
+}
+
+fn strip_leading_backslash(name: &[u8]) -> &[u8] {
+    name.strip_prefix(b"\\").unwrap_or(name)
+}
+
+fn last_segment(name: &[u8]) -> &[u8] {
+    match name.iter().rposition(|b| *b == b'\\') {
+        Some(position) => &name[position + 1..],
+        None => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NameResolver;
+    use crate::parser::ast::UseKind;
+
+    #[test]
+    fn test_resolve_class_names() {
+        let mut resolver = NameResolver::new();
+        resolver.enter_namespace(Some(b"App\\Http"));
+        resolver.import(UseKind::Normal, b"\\Psr\\Log\\LoggerInterface", None);
+        resolver.import(UseKind::Normal, b"Symfony\\Component", Some(b"Sf"));
+
+        assert_eq!(resolver.resolve_class(b"Foo"), b"App\\Http\\Foo");
+        assert_eq!(resolver.resolve_class(b"\\Foo"), b"Foo");
+        assert_eq!(resolver.resolve_class(b"namespace\\Foo"), b"App\\Http\\Foo");
+        assert_eq!(
+            resolver.resolve_class(b"loggerinterface"),
+            b"Psr\\Log\\LoggerInterface"
+        );
+        assert_eq!(
+            resolver.resolve_class(b"Sf\\Console\\Application"),
+            b"Symfony\\Component\\Console\\Application"
+        );
+        assert_eq!(resolver.resolve_class(b"self"), b"self");
+        assert_eq!(resolver.resolve_class(b"Namespace\\Foo"), b"App\\Http\\Foo");
+    }
+
+    #[test]
+    fn test_resolve_function_and_constant_names() {
+        let mut resolver = NameResolver::new();
+        resolver.import(UseKind::Function, b"Foo\\bar", None);
+        resolver.import(UseKind::Const, b"Foo\\BAZ", None);
+
+        assert_eq!(resolver.resolve_function(b"bar"), b"Foo\\bar");
+        assert_eq!(resolver.resolve_constant(b"BAZ"), b"Foo\\BAZ");
+        assert_eq!(resolver.resolve_class(b"bar"), b"bar");
+        assert_eq!(resolver.resolve_function(b"strlen"), b"strlen");
+    }
+}
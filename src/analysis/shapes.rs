@@ -0,0 +1,649 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use smallvec::SmallVec;
+
+use crate::downcast::downcast_mut;
+use crate::node::Node;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::functions::AbstractConstructor;
+use crate::parser::ast::functions::AbstractMethod;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::functions::ReturnType;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::modifiers::MethodModifierGroup;
+use crate::parser::ast::modifiers::PropertyModifierGroup;
+use crate::parser::ast::modifiers::Visibility;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::properties::Property;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::Expression;
+use crate::parser::ast::Statement;
+use crate::printer::print_expression;
+use crate::printer::PrinterOptions;
+use crate::traverser::Visitor;
+
+use super::index::ClassLikeKind;
+use super::resolver::NameResolver;
+
+/// The shape of a class, interface, trait, or enum: the constants, properties, and methods
+/// it declares, without their bodies.
+///
+/// Types, default values, and signatures are rendered as strings, with all class names in
+/// types fully qualified, so the shape can be exchanged with other tools without the AST.
+/// Members are keyed by their name, without the `$` of properties.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ClassShape {
+    pub name: String,
+    pub kind: ClassLikeKind,
+    pub constants: BTreeMap<String, ConstantShape>,
+    pub properties: BTreeMap<String, PropertyShape>,
+    pub methods: BTreeMap<String, MethodShape>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ConstantShape {
+    pub visibility: Visibility,
+    pub r#final: bool,
+    pub r#type: Option<String>, // `int`
+    pub value: String,          // `1 << 2`
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PropertyShape {
+    pub r#type: Option<String>, // `?App\User`
+    pub visibility: Visibility,
+    /// The visibility of writing to the property, when it is asymmetric, e.g. `private(set)`.
+    pub set_visibility: Option<Visibility>,
+    pub r#static: bool,
+    /// Whether the property is readonly, either itself or as a property of a readonly class.
+    pub readonly: bool,
+    pub default: Option<String>, // `[]`
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MethodShape {
+    pub signature: String, // `find(int $id, ?string $name = null): ?App\User`
+    pub visibility: Visibility,
+    pub r#static: bool,
+    /// Whether the method has no body, i.e. it is an abstract or an interface method.
+    pub r#abstract: bool,
+    pub r#final: bool,
+}
+
+/// Collect the shape of every class, interface, trait, and enum declared within the given
+/// program, in source order.
+///
+/// Anonymous classes are not collected, as they have no name to refer to them.
+pub fn shapes(program: &mut [Statement]) -> Vec<ClassShape> {
+    let mut collector = Collector::default();
+    collector.statements(program);
+
+    collector.shapes
+}
+
+#[derive(Debug, Default)]
+struct Collector {
+    resolver: NameResolver,
+    shapes: Vec<ClassShape>,
+}
+
+impl Collector {
+    fn statements(&mut self, statements: &mut [Statement]) {
+        for statement in statements.iter_mut() {
+            match statement {
+                Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                    self.resolver.enter_namespace(Some(&namespace.name.value));
+                    self.statements(&mut namespace.statements);
+                }
+                Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                    self.resolver
+                        .enter_namespace(namespace.name.as_ref().map(|name| &name.value[..]));
+                    self.statements(&mut namespace.body.statements);
+                }
+                Statement::Use(statement) => self.resolver.import_statement(statement),
+                Statement::GroupUse(statement) => self.resolver.import_group_statement(statement),
+                _ => {
+                    let _ = self.visit_node(statement);
+                }
+            }
+        }
+    }
+
+    fn shape(&self, name: &SimpleIdentifier, kind: ClassLikeKind) -> ClassShape {
+        ClassShape {
+            name: self.resolver.qualify(&name.value).to_string(),
+            kind,
+            constants: BTreeMap::new(),
+            properties: BTreeMap::new(),
+            methods: BTreeMap::new(),
+        }
+    }
+
+    fn data_type(&self, data_type: &Type) -> String {
+        self.resolver.resolve_type(data_type).to_string()
+    }
+
+    fn constants(&self, shape: &mut ClassShape, constant: &ClassishConstant) {
+        for entry in constant.entries.iter() {
+            shape.constants.insert(
+                entry.name.value.to_string(),
+                ConstantShape {
+                    visibility: constant.modifiers.visibility(),
+                    r#final: constant.modifiers.has_final(),
+                    r#type: constant.data_type.as_ref().map(|t| self.data_type(t)),
+                    value: expression(&entry.value),
+                },
+            );
+        }
+    }
+
+    fn properties(&self, shape: &mut ClassShape, property: &Property, readonly: bool) {
+        for entry in property.entries.iter() {
+            shape.properties.insert(
+                property_name(entry.variable()),
+                self.property(
+                    &property.modifiers,
+                    property.r#type.as_ref(),
+                    entry,
+                    readonly,
+                ),
+            );
+        }
+    }
+
+    // Properties declared using `var` are public.
+    fn variable_properties(
+        &self,
+        shape: &mut ClassShape,
+        property: &VariableProperty,
+        readonly: bool,
+    ) {
+        let modifiers = PropertyModifierGroup {
+            modifiers: SmallVec::new(),
+        };
+        for entry in property.entries.iter() {
+            shape.properties.insert(
+                property_name(entry.variable()),
+                self.property(&modifiers, property.r#type.as_ref(), entry, readonly),
+            );
+        }
+    }
+
+    fn property(
+        &self,
+        modifiers: &PropertyModifierGroup,
+        r#type: Option<&Type>,
+        entry: &PropertyEntry,
+        readonly: bool,
+    ) -> PropertyShape {
+        PropertyShape {
+            r#type: r#type.map(|t| self.data_type(t)),
+            visibility: modifiers.visibility(),
+            set_visibility: modifiers
+                .get_set_visibility()
+                .map(|_| modifiers.set_visibility()),
+            r#static: modifiers.has_static(),
+            readonly: readonly || modifiers.has_readonly(),
+            default: match entry {
+                PropertyEntry::Initialized { value, .. } => Some(expression(value)),
+                PropertyEntry::Uninitialized { .. } => None,
+            },
+        }
+    }
+
+    fn signature(
+        &self,
+        ampersand: bool,
+        name: &SimpleIdentifier,
+        parameters: Vec<String>,
+        return_type: Option<&ReturnType>,
+    ) -> String {
+        let mut signature = format!(
+            "{}{}({})",
+            if ampersand { "&" } else { "" },
+            name,
+            parameters.join(", ")
+        );
+        if let Some(return_type) = return_type {
+            signature.push_str(": ");
+            signature.push_str(&self.data_type(&return_type.data_type));
+        }
+
+        signature
+    }
+
+    fn parameters(&self, parameters: &FunctionParameterList) -> Vec<String> {
+        parameters
+            .parameters
+            .inner
+            .iter()
+            .map(|parameter| {
+                self.parameter(
+                    parameter.data_type.as_ref(),
+                    parameter.ampersand.is_some(),
+                    parameter.ellipsis.is_some(),
+                    &parameter.name,
+                    parameter.default.as_ref(),
+                )
+            })
+            .collect()
+    }
+
+    fn parameter(
+        &self,
+        data_type: Option<&Type>,
+        ampersand: bool,
+        ellipsis: bool,
+        name: &SimpleVariable,
+        default: Option<&Expression>,
+    ) -> String {
+        let mut parameter = String::new();
+        if let Some(data_type) = data_type {
+            parameter.push_str(&self.data_type(data_type));
+            parameter.push(' ');
+        }
+        if ampersand {
+            parameter.push('&');
+        }
+        if ellipsis {
+            parameter.push_str("...");
+        }
+        parameter.push_str(&name.name.to_string());
+        if let Some(default) = default {
+            parameter.push_str(" = ");
+            parameter.push_str(&expression(default));
+        }
+
+        parameter
+    }
+
+    fn method(
+        &self,
+        shape: &mut ClassShape,
+        name: &SimpleIdentifier,
+        modifiers: &MethodModifierGroup,
+        r#abstract: bool,
+        signature: String,
+    ) {
+        shape.methods.insert(
+            name.value.to_string(),
+            MethodShape {
+                signature,
+                visibility: modifiers.visibility(),
+                r#static: modifiers.has_static(),
+                r#abstract,
+                r#final: modifiers.has_final(),
+            },
+        );
+    }
+
+    fn abstract_method(&self, shape: &mut ClassShape, method: &AbstractMethod) {
+        let signature = self.signature(
+            method.ampersand.is_some(),
+            &method.name,
+            self.parameters(&method.parameters),
+            method.return_type.as_ref(),
+        );
+
+        self.method(shape, &method.name, &method.modifiers, true, signature);
+    }
+
+    fn concrete_method(&self, shape: &mut ClassShape, method: &ConcreteMethod) {
+        let signature = self.signature(
+            method.ampersand.is_some(),
+            &method.name,
+            self.parameters(&method.parameters),
+            method.return_type.as_ref(),
+        );
+
+        self.method(shape, &method.name, &method.modifiers, false, signature);
+    }
+
+    fn abstract_constructor(&self, shape: &mut ClassShape, method: &AbstractConstructor) {
+        let signature = self.signature(
+            method.ampersand.is_some(),
+            &method.name,
+            self.parameters(&method.parameters),
+            None,
+        );
+
+        self.method(shape, &method.name, &method.modifiers, true, signature);
+    }
+
+    // Promoted constructor parameters also declare properties, which have no default value.
+    fn concrete_constructor(
+        &self,
+        shape: &mut ClassShape,
+        method: &ConcreteConstructor,
+        readonly: bool,
+    ) {
+        let mut parameters = vec![];
+        for parameter in method.parameters.parameters.inner.iter() {
+            parameters.push(self.parameter(
+                parameter.data_type.as_ref(),
+                parameter.ampersand.is_some(),
+                parameter.ellipsis.is_some(),
+                &parameter.name,
+                parameter.default.as_ref(),
+            ));
+
+            let modifiers = &parameter.modifiers;
+            if modifiers.is_empty() {
+                continue;
+            }
+
+            shape.properties.insert(
+                property_name(&parameter.name),
+                PropertyShape {
+                    r#type: parameter.data_type.as_ref().map(|t| self.data_type(t)),
+                    visibility: modifiers.visibility(),
+                    set_visibility: modifiers
+                        .get_set_visibility()
+                        .map(|_| modifiers.set_visibility()),
+                    r#static: false,
+                    readonly: readonly || modifiers.has_readonly(),
+                    default: None,
+                },
+            );
+        }
+
+        let signature = self.signature(method.ampersand.is_some(), &method.name, parameters, None);
+
+        self.method(shape, &method.name, &method.modifiers, false, signature);
+    }
+}
+
+impl Visitor<()> for Collector {
+    fn visit(&mut self, node: &mut dyn Node) -> Result<(), ()> {
+        let shape = match downcast_mut::<Statement>(node) {
+            Some(Statement::Class(statement)) => {
+                let mut shape = self.shape(&statement.name, ClassLikeKind::Class);
+                let readonly = statement.modifiers.has_readonly();
+
+                for member in statement.body.members.iter() {
+                    match member {
+                        ClassMember::Constant(constant) => self.constants(&mut shape, constant),
+                        ClassMember::Property(property) => {
+                            self.properties(&mut shape, property, readonly)
+                        }
+                        ClassMember::VariableProperty(property) => {
+                            self.variable_properties(&mut shape, property, readonly)
+                        }
+                        ClassMember::AbstractMethod(method) => {
+                            self.abstract_method(&mut shape, method)
+                        }
+                        ClassMember::AbstractConstructor(method) => {
+                            self.abstract_constructor(&mut shape, method)
+                        }
+                        ClassMember::ConcreteMethod(method) => {
+                            self.concrete_method(&mut shape, method)
+                        }
+                        ClassMember::ConcreteConstructor(method) => {
+                            self.concrete_constructor(&mut shape, method, readonly)
+                        }
+                        ClassMember::TraitUsage(_) => {}
+                    }
+                }
+
+                shape
+            }
+            Some(Statement::Interface(statement)) => {
+                let mut shape = self.shape(&statement.name, ClassLikeKind::Interface);
+
+                for member in statement.body.members.iter() {
+                    match member {
+                        InterfaceMember::Constant(constant) => self.constants(&mut shape, constant),
+                        InterfaceMember::Property(property) => {
+                            self.properties(&mut shape, property, false)
+                        }
+                        InterfaceMember::Method(method) => self.abstract_method(&mut shape, method),
+                        InterfaceMember::Constructor(method) => {
+                            self.abstract_constructor(&mut shape, method)
+                        }
+                    }
+                }
+
+                shape
+            }
+            Some(Statement::Trait(statement)) => {
+                let mut shape = self.shape(&statement.name, ClassLikeKind::Trait);
+
+                for member in statement.body.members.iter() {
+                    match member {
+                        TraitMember::Constant(constant) => self.constants(&mut shape, constant),
+                        TraitMember::Property(property) => {
+                            self.properties(&mut shape, property, false)
+                        }
+                        TraitMember::VariableProperty(property) => {
+                            self.variable_properties(&mut shape, property, false)
+                        }
+                        TraitMember::AbstractMethod(method) => {
+                            self.abstract_method(&mut shape, method)
+                        }
+                        TraitMember::AbstractConstructor(method) => {
+                            self.abstract_constructor(&mut shape, method)
+                        }
+                        TraitMember::ConcreteMethod(method) => {
+                            self.concrete_method(&mut shape, method)
+                        }
+                        TraitMember::ConcreteConstructor(method) => {
+                            self.concrete_constructor(&mut shape, method, false)
+                        }
+                        TraitMember::TraitUsage(_) => {}
+                    }
+                }
+
+                shape
+            }
+            Some(Statement::UnitEnum(statement)) => {
+                let mut shape = self.shape(&statement.name, ClassLikeKind::Enum);
+
+                for member in statement.body.members.iter() {
+                    match member {
+                        UnitEnumMember::Constant(constant) => self.constants(&mut shape, constant),
+                        UnitEnumMember::Method(method) => self.concrete_method(&mut shape, method),
+                        UnitEnumMember::Case(_) => {}
+                    }
+                }
+
+                shape
+            }
+            Some(Statement::BackedEnum(statement)) => {
+                let mut shape = self.shape(&statement.name, ClassLikeKind::Enum);
+
+                for member in statement.body.members.iter() {
+                    match member {
+                        BackedEnumMember::Constant(constant) => {
+                            self.constants(&mut shape, constant)
+                        }
+                        BackedEnumMember::Method(method) => {
+                            self.concrete_method(&mut shape, method)
+                        }
+                        BackedEnumMember::Case(_) => {}
+                    }
+                }
+
+                shape
+            }
+            _ => return Ok(()),
+        };
+
+        self.shapes.push(shape);
+
+        Ok(())
+    }
+}
+
+fn property_name(variable: &SimpleVariable) -> String {
+    let name = variable.name.to_string();
+
+    match name.strip_prefix('$') {
+        Some(name) => name.to_string(),
+        None => name,
+    }
+}
+
+fn expression(expression: &Expression) -> String {
+    print_expression(expression, &PrinterOptions::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::shapes;
+    use super::ClassShape;
+    use crate::analysis::index::ClassLikeKind;
+    use crate::parser::ast::modifiers::Visibility;
+
+    #[test]
+    fn test_class_shapes() {
+        let mut program = crate::parse(
+            r#"<?php
+namespace App;
+
+use Psr\Log\LoggerInterface;
+
+abstract class Service extends Base
+{
+    final public const int LIMIT = 10 * 2;
+    private const NAME = 'service';
+
+    protected static ?LoggerInterface $logger = null;
+    public private(set) array $items;
+    public readonly int $count;
+
+    public function __construct(private LoggerInterface $log, int $size = 1) {}
+
+    public static function &find(int $id, string ...$names): ?self {}
+
+    abstract protected function clear(array &$items = []): void;
+}
+
+interface Repository
+{
+    public function all(): iterable;
+}
+
+enum Status: string
+{
+    case Active = 'active';
+
+    const DEFAULT = self::Active;
+}
+"#,
+        )
+        .unwrap();
+
+        let shapes = shapes(&mut program);
+
+        assert_eq!(
+            shapes
+                .iter()
+                .map(|shape| (shape.name.as_str(), shape.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                ("App\\Service", ClassLikeKind::Class),
+                ("App\\Repository", ClassLikeKind::Interface),
+                ("App\\Status", ClassLikeKind::Enum),
+            ]
+        );
+
+        let service = &shapes[0];
+        assert_eq!(
+            service.constants.keys().collect::<Vec<_>>(),
+            vec!["LIMIT", "NAME"]
+        );
+        assert_eq!(service.constants["LIMIT"].value, "10 * 2");
+        assert_eq!(service.constants["LIMIT"].r#type.as_deref(), Some("int"));
+        assert!(service.constants["LIMIT"].r#final);
+        assert_eq!(service.constants["NAME"].visibility, Visibility::Private);
+
+        let logger = &service.properties["logger"];
+        assert_eq!(logger.r#type.as_deref(), Some("?Psr\\Log\\LoggerInterface"));
+        assert_eq!(logger.visibility, Visibility::Protected);
+        assert_eq!(logger.default.as_deref(), Some("null"));
+        assert!(logger.r#static);
+        assert!(!logger.readonly);
+        assert!(service.properties["count"].readonly);
+
+        let items = &service.properties["items"];
+        assert_eq!(items.set_visibility, Some(Visibility::Private));
+        assert_eq!(items.default, None);
+
+        let log = &service.properties["log"];
+        assert_eq!(log.visibility, Visibility::Private);
+        assert_eq!(log.r#type.as_deref(), Some("Psr\\Log\\LoggerInterface"));
+        assert!(!service.properties.contains_key("size"));
+
+        assert_eq!(
+            service
+                .methods
+                .values()
+                .map(|method| method.signature.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "__construct(Psr\\Log\\LoggerInterface $log, int $size = 1)",
+                "clear(array &$items = []): void",
+                "&find(int $id, string ...$names): ?self",
+            ]
+        );
+        assert!(service.methods["find"].r#static);
+        assert!(service.methods["clear"].r#abstract);
+        assert_eq!(service.methods["clear"].visibility, Visibility::Protected);
+
+        assert!(shapes[1].methods["all"].r#abstract);
+        assert_eq!(shapes[2].constants["DEFAULT"].value, "self::Active");
+        assert!(shapes[2].properties.is_empty());
+    }
+
+    #[test]
+    fn test_readonly_class_shapes() {
+        let mut program = crate::parse(
+            "<?php readonly class Point { public int $x; public function __construct(public int $y) {} }",
+        )
+        .unwrap();
+
+        let shapes = shapes(&mut program);
+
+        assert!(shapes[0].properties["x"].readonly);
+        assert!(shapes[0].properties["y"].readonly);
+    }
+
+    #[test]
+    fn test_serialized_shapes() {
+        let mut program =
+            crate::parse("<?php class Foo { var $bar = [1]; function baz(): int {} }").unwrap();
+
+        let shapes = shapes(&mut program);
+        let json = serde_json::to_string(&shapes[0]).unwrap();
+
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"name":"Foo","kind":"class","constants":{},"#,
+                r#""properties":{"bar":{"type":null,"visibility":{"type":"public"},"set_visibility":null,"static":false,"readonly":false,"default":"[1]"}},"#,
+                r#""methods":{"baz":{"signature":"baz(): int","visibility":{"type":"public"},"static":false,"abstract":false,"final":false}}}"#,
+            )
+        );
+        assert_eq!(
+            serde_json::from_str::<ClassShape>(&json).unwrap(),
+            shapes[0]
+        );
+    }
+}
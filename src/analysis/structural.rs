@@ -0,0 +1,437 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+
+use serde::ser;
+use serde::Serialize;
+
+/// Structs which are never hashed, as they don't affect the meaning of the code.
+const IGNORED: [&str; 3] = ["Span", "Comment", "CommentGroup"];
+
+/// Structs holding a name or a value, which are only hashed by kind in parameterized hashes.
+const PARAMETERS: [&str; 6] = [
+    "SimpleVariable",
+    "SimpleIdentifier",
+    "LiteralString",
+    "LiteralInteger",
+    "LiteralFloat",
+    "LiteralStringPart",
+];
+
+/// The hash of a node and the number of hashed structs and enum variants it contains.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Fingerprint {
+    pub hash: u64,
+    pub size: usize,
+}
+
+/// Hash the structure of a node, ignoring spans and comments, so two pieces of code
+/// differing only in formatting have the same hash.
+pub fn structural_hash<T: ?Sized + Serialize>(node: &T) -> u64 {
+    fingerprint(node, false).hash
+}
+
+/// Hash the structure of a node like [`structural_hash`], additionally ignoring the names of
+/// variables and identifiers and the values of literals, e.g. `$a + 1` and `$b + 2` have the
+/// same hash.
+pub fn parameterized_hash<T: ?Sized + Serialize>(node: &T) -> u64 {
+    fingerprint(node, true).hash
+}
+
+pub fn fingerprint<T: ?Sized + Serialize>(node: &T, parameterized: bool) -> Fingerprint {
+    let mut serializer = HashSerializer::new(parameterized);
+    // Hashing never fails.
+    let _ = node.serialize(&mut serializer);
+
+    Fingerprint {
+        hash: serializer.hasher.finish(),
+        size: serializer.size,
+    }
+}
+
+struct HashSerializer {
+    hasher: DefaultHasher,
+    parameterized: bool,
+    size: usize,
+    // The last serialized unit variant, used to recognize comment statements.
+    variant: Option<&'static str>,
+}
+
+impl HashSerializer {
+    fn new(parameterized: bool) -> Self {
+        Self {
+            hasher: DefaultHasher::new(),
+            parameterized,
+            size: 0,
+            variant: None,
+        }
+    }
+
+    fn compound(&mut self, name: &'static str) -> Compound<'_> {
+        let skip = IGNORED.contains(&name);
+        let opaque = self.parameterized && PARAMETERS.contains(&name);
+
+        let mut child = HashSerializer::new(self.parameterized);
+        child.hasher.write(name.as_bytes());
+
+        Compound {
+            parent: self,
+            child,
+            skip,
+            opaque,
+            name,
+        }
+    }
+}
+
+/// A sequence, map, or struct, hashed separately so it can be discarded once serialized.
+struct Compound<'a> {
+    parent: &'a mut HashSerializer,
+    child: HashSerializer,
+    skip: bool,
+    // Whether the contents are not hashed, only the name.
+    opaque: bool,
+    name: &'static str,
+}
+
+impl<'a> Compound<'a> {
+    fn element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), std::fmt::Error> {
+        if !self.skip && !self.opaque {
+            value.serialize(&mut self.child)?;
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), std::fmt::Error> {
+        if !self.skip {
+            self.parent.size += self.child.size + 1;
+            self.parent.hasher.write_u64(self.child.hasher.finish());
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut HashSerializer {
+    type Ok = ();
+    type Error = std::fmt::Error;
+
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Self::Error> {
+        self.hasher.write_u8(v as u8);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Self::Error> {
+        self.hasher.write_i64(v);
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Self::Error> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Self::Error> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Self::Error> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Self::Error> {
+        self.hasher.write_u64(v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Self::Error> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Self::Error> {
+        self.hasher.write_u64(v.to_bits());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Self::Error> {
+        self.hasher.write_u32(v as u32);
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Self::Error> {
+        self.serialize_bytes(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Self::Error> {
+        self.hasher.write(v);
+        self.hasher.write_u8(0xff);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), Self::Error> {
+        self.hasher.write_u8(0);
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), Self::Error> {
+        self.hasher.write_u8(1);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<(), Self::Error> {
+        self.hasher.write(name.as_bytes());
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Self::Error> {
+        self.size += 1;
+        self.variant = Some(variant);
+        self.hasher.write(variant.as_bytes());
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        let mut compound = self.compound(name);
+        compound.element(value)?;
+        compound.finish()
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        let mut compound = self.compound(variant);
+        compound.element(value)?;
+        compound.finish()
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(self.compound("[]"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(self.compound("()"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(self.compound(name))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Ok(self.compound(variant))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(self.compound("{}"))
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(self.compound(name))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Ok(self.compound(variant))
+    }
+}
+
+impl<'a> ser::SerializeSeq for Compound<'a> {
+    type Ok = ();
+    type Error = std::fmt::Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTuple for Compound<'a> {
+    type Ok = ();
+    type Error = std::fmt::Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for Compound<'a> {
+    type Ok = ();
+    type Error = std::fmt::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTupleVariant for Compound<'a> {
+    type Ok = ();
+    type Error = std::fmt::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeMap for Compound<'a> {
+    type Ok = ();
+    type Error = std::fmt::Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.element(key)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeStruct for Compound<'a> {
+    type Ok = ();
+    type Error = std::fmt::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        if self.skip || self.opaque {
+            return Ok(());
+        }
+
+        self.child.hasher.write(key.as_bytes());
+        self.child.variant = None;
+        self.element(value)?;
+
+        // Statements are tagged with their kind, e.g. `{"type": "comment", "value": ...}`.
+        if self.name == "Statement" && key == "type" && self.child.variant == Some("comment") {
+            self.skip = true;
+        }
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for Compound<'a> {
+    type Ok = ();
+    type Error = std::fmt::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        if !self.skip && !self.opaque {
+            self.child.hasher.write(key.as_bytes());
+        }
+
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parameterized_hash;
+    use super::structural_hash;
+
+    fn body(code: &str) -> Vec<crate::parser::ast::Statement> {
+        crate::parse(&format!("<?php {}", code))
+            .unwrap()
+            .into_iter()
+            .skip(1)
+            .collect()
+    }
+
+    #[test]
+    fn test_hashes() {
+        let a = body("$total = $price * 2; // double\nreturn $total;");
+        let b = body("$total  =  $price*2;\n\n/* the result */\nreturn $total;");
+        let c = body("$sum = $cost * 3; return $sum;");
+        let d = body("$sum = $cost + 3; return $sum;");
+
+        assert_eq!(structural_hash(&a), structural_hash(&b));
+        assert_ne!(structural_hash(&a), structural_hash(&c));
+        assert_eq!(parameterized_hash(&a), parameterized_hash(&c));
+        assert_ne!(parameterized_hash(&c), parameterized_hash(&d));
+    }
+}
@@ -2,16 +2,16 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
+use std::borrow::Cow;
 use std::ops::Deref;
 use std::ops::DerefMut;
-use std::str::from_utf8;
 
 /// A wrapper for Vec<u8> that provides a human-readable Debug impl and
 /// a few other conveniences.
 ///
 /// The Trunk lexer and parser work mainly with byte strings because
 /// valid PHP code is not required to be valid UTF-8.
-#[derive(PartialOrd, PartialEq, Eq, Clone)]
+#[derive(PartialOrd, PartialEq, Eq, Clone, Hash)]
 pub struct ByteString {
     pub bytes: Vec<u8>,
     pub length: usize,
@@ -70,12 +70,17 @@ impl std::fmt::Debug for ByteString {
     }
 }
 
+/// A byte string is serialized as a string when it is valid UTF-8, and as a sequence of bytes
+/// otherwise, e.g. a string literal containing binary data.
 impl Serialize for ByteString {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        match std::str::from_utf8(&self.bytes) {
+            Ok(string) => serializer.serialize_str(string),
+            Err(_) => self.bytes.serialize(serializer),
+        }
     }
 }
 
@@ -84,8 +89,37 @@ impl<'de> Deserialize<'de> for ByteString {
     where
         D: serde::Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        Ok(ByteString::new(s.into_bytes()))
+        struct ByteStringVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ByteStringVisitor {
+            type Value = ByteString;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a string or a sequence of bytes")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<ByteString, E> {
+                Ok(ByteString::from(value))
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, value: &[u8]) -> Result<ByteString, E> {
+                Ok(ByteString::from(value))
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut sequence: A,
+            ) -> Result<ByteString, A::Error> {
+                let mut bytes = Vec::with_capacity(sequence.size_hint().unwrap_or(0));
+                while let Some(byte) = sequence.next_element::<u8>()? {
+                    bytes.push(byte);
+                }
+
+                Ok(ByteString::new(bytes))
+            }
+        }
+
+        deserializer.deserialize_any(ByteStringVisitor)
     }
 }
 
@@ -94,10 +128,15 @@ impl JsonSchema for ByteString {
         "ByteString".to_string()
     }
 
-    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
         schemars::schema::SchemaObject {
-            instance_type: Some(schemars::schema::InstanceType::String.into()),
-            format: Some("byte-string".to_string()),
+            subschemas: Some(Box::new(schemars::schema::SubschemaValidation {
+                any_of: Some(vec![
+                    gen.subschema_for::<String>(),
+                    gen.subschema_for::<Vec<u8>>(),
+                ]),
+                ..Default::default()
+            })),
             ..Default::default()
         }
         .into()
@@ -154,7 +193,7 @@ impl From<String> for ByteString {
 
 impl From<ByteString> for String {
     fn from(bytes: ByteString) -> Self {
-        String::from(from_utf8(&bytes.bytes).unwrap())
+        String::from_utf8_lossy(&bytes.bytes).into_owned()
     }
 }
 
@@ -172,6 +211,134 @@ impl DerefMut for ByteString {
     }
 }
 
+/// A byte string borrowed from the input, or owned when it differs from the input, e.g. the
+/// value of a string literal with escape sequences, see [`crate::lexer::token::Token`].
+///
+/// It is displayed, compared, and serialized as a [`ByteString`].
+#[derive(PartialOrd, PartialEq, Eq, Clone, Hash, Default)]
+pub struct ByteStr<'a> {
+    bytes: Cow<'a, [u8]>,
+}
+
+impl<'a> ByteStr<'a> {
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        ByteStr {
+            bytes: Cow::Borrowed(bytes),
+        }
+    }
+
+    /// Whether the byte string borrows from the input.
+    pub const fn is_borrowed(&self) -> bool {
+        matches!(self.bytes, Cow::Borrowed(_))
+    }
+
+    pub fn into_owned(self) -> ByteStr<'static> {
+        ByteStr {
+            bytes: Cow::Owned(self.bytes.into_owned()),
+        }
+    }
+
+    /// Copy the bytes into a [`ByteString`], e.g. to store them in the AST.
+    pub fn to_byte_string(&self) -> ByteString {
+        ByteString::from(&self.bytes[..])
+    }
+}
+
+impl std::fmt::Display for ByteStr<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&ByteString::from(&self.bytes[..]), f)
+    }
+}
+
+impl std::fmt::Debug for ByteStr<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&ByteString::from(&self.bytes[..]), f)
+    }
+}
+
+impl Serialize for ByteStr<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match std::str::from_utf8(&self.bytes) {
+            Ok(string) => serializer.serialize_str(string),
+            Err(_) => self.bytes.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteStr<'_> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        ByteString::deserialize(deserializer).map(ByteStr::from)
+    }
+}
+
+impl JsonSchema for ByteStr<'_> {
+    fn schema_name() -> String {
+        ByteString::schema_name()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        ByteString::json_schema(gen)
+    }
+}
+
+impl<const N: usize> PartialEq<&[u8; N]> for ByteStr<'_> {
+    fn eq(&self, other: &&[u8; N]) -> bool {
+        self.bytes[..] == other[..]
+    }
+}
+
+impl<'a> From<&'a [u8]> for ByteStr<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        ByteStr::new(bytes)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for ByteStr<'a> {
+    fn from(bytes: &'a [u8; N]) -> Self {
+        ByteStr::new(bytes)
+    }
+}
+
+impl From<Vec<u8>> for ByteStr<'_> {
+    fn from(bytes: Vec<u8>) -> Self {
+        ByteStr {
+            bytes: Cow::Owned(bytes),
+        }
+    }
+}
+
+impl From<ByteString> for ByteStr<'_> {
+    fn from(string: ByteString) -> Self {
+        ByteStr::from(string.bytes)
+    }
+}
+
+impl From<&ByteStr<'_>> for ByteString {
+    fn from(string: &ByteStr<'_>) -> Self {
+        ByteString::from(&string.bytes[..])
+    }
+}
+
+impl From<ByteStr<'_>> for ByteString {
+    fn from(string: ByteStr<'_>) -> Self {
+        ByteString::new(string.bytes.into_owned())
+    }
+}
+
+impl Deref for ByteStr<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -188,4 +355,32 @@ mod tests {
             r#""\x01\x10\x7f\xff""#
         );
     }
+
+    #[test]
+    fn test_byte_str() {
+        let input = b"abc\xff".to_vec();
+        let borrowed = ByteStr::new(&input[..3]);
+        assert!(borrowed.is_borrowed());
+        assert_eq!(format!("{:?}", borrowed), r#""abc""#);
+
+        let owned = ByteStr::from(b"\xff\x00".to_vec());
+        assert!(!owned.is_borrowed());
+        assert_eq!(serde_json::to_string(&owned).unwrap(), "[255,0]");
+        assert_eq!(
+            ByteString::from(owned.into_owned()),
+            ByteString::from(b"\xff\x00")
+        );
+    }
+
+    #[test]
+    fn test_byte_string_serde() {
+        for (bytes, json) in [
+            (&b"a\\n\n\xc3\xa9"[..], r#""a\\n\né""#),
+            (&b"\xff\x00"[..], "[255,0]"),
+        ] {
+            let string = ByteString::from(bytes);
+            assert_eq!(serde_json::to_string(&string).unwrap(), json);
+            assert_eq!(serde_json::from_str::<ByteString>(json).unwrap(), string);
+        }
+    }
 }
@@ -17,6 +17,7 @@ pub enum SyntaxError {
     InvalidDocIndentation(Span),
     InvalidDocBodyIndentationLevel(usize, Span),
     UnrecognisedToken(u8, Span),
+    TooManyTokens(usize, Span),
 }
 
 impl SyntaxError {
@@ -33,6 +34,7 @@ impl SyntaxError {
             Self::InvalidDocIndentation(span) => *span,
             Self::InvalidDocBodyIndentationLevel(_, span) => *span,
             Self::UnrecognisedToken(_, span) => *span,
+            Self::TooManyTokens(_, span) => *span,
         }
     }
 }
@@ -97,7 +99,14 @@ impl Display for SyntaxError {
                 token,
                 span.line,
                 span.column
-            )
+            ),
+            Self::TooManyTokens(maximum, span) => write!(
+                f,
+                "Syntax Error: Exceeded the maximum of {} tokens on line {} column {}",
+                maximum,
+                span.line,
+                span.column
+            ),
         }
     }
 }
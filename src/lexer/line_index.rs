@@ -0,0 +1,110 @@
+use crate::lexer::token::Span;
+
+/// The start of every line of a source, to resolve byte positions to 1-based lines and
+/// columns without scanning the source again.
+///
+/// Columns are counted in bytes, like the columns of the spans produced by the lexer.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LineIndex {
+    // The position of the first byte of each line, the first line starting at 0.
+    starts: Vec<usize>,
+    length: usize,
+}
+
+impl LineIndex {
+    pub fn new<B: ?Sized + AsRef<[u8]>>(source: &B) -> Self {
+        let source = source.as_ref();
+
+        let mut starts = vec![0];
+        starts.extend(
+            source
+                .iter()
+                .enumerate()
+                .filter(|(_, byte)| **byte == b'\n')
+                .map(|(position, _)| position + 1),
+        );
+
+        Self {
+            starts,
+            length: source.len(),
+        }
+    }
+
+    /// The number of lines of the source, a trailing newline starting an empty last line.
+    pub fn lines(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// The 1-based line and column of the given position.
+    ///
+    /// Positions past the end of the source are clamped to its end.
+    pub fn line_col(&self, position: usize) -> (usize, usize) {
+        let position = position.min(self.length);
+        let line = self.starts.partition_point(|start| *start <= position);
+
+        (line, position - self.starts[line - 1] + 1)
+    }
+
+    /// The span of the given position.
+    pub fn span(&self, position: usize) -> Span {
+        let position = position.min(self.length);
+        let (line, column) = self.line_col(position);
+
+        Span::new(line, column, position)
+    }
+
+    /// The position of the given 1-based line and column, if it is within the source.
+    pub fn position(&self, line: usize, column: usize) -> Option<usize> {
+        let start = *self.starts.get(line.checked_sub(1)?)?;
+        let end = self.starts.get(line).copied().unwrap_or(self.length + 1);
+
+        let position = start + column.checked_sub(1)?;
+        if position < end {
+            Some(position)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LineIndex;
+    use crate::lexer::token::Span;
+
+    #[test]
+    fn test_line_col() {
+        let index = LineIndex::new("<?php\n\necho 1;\n");
+
+        assert_eq!(index.lines(), 4);
+        assert_eq!(index.line_col(0), (1, 1));
+        assert_eq!(index.line_col(5), (1, 6));
+        assert_eq!(index.line_col(6), (2, 1));
+        assert_eq!(index.line_col(7), (3, 1));
+        assert_eq!(index.line_col(12), (3, 6));
+        assert_eq!(index.line_col(15), (4, 1));
+        assert_eq!(index.line_col(100), (4, 1));
+
+        assert_eq!(index.position(3, 6), Some(12));
+        assert_eq!(index.position(1, 7), None);
+        assert_eq!(index.position(5, 1), None);
+        assert_eq!(index.position(0, 1), None);
+    }
+
+    #[test]
+    fn test_spans_match_lexer() {
+        let code = "<?php\n\nfunction foo() {\n    return \"a\nb\";\n}\n\r\necho foo();";
+        let index = LineIndex::new(code);
+
+        let tokens = crate::lexer::Lexer::new()
+            .tokenize(code.as_bytes())
+            .unwrap();
+        for token in tokens.iter() {
+            let span = token.span;
+            assert_eq!(index.span(span.position), span, "{:?}", token);
+            assert_eq!(index.position(span.line, span.column), Some(span.position));
+        }
+
+        assert_eq!(index.span(7), Span::new(3, 1, 7));
+    }
+}
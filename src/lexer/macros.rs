@@ -1,5 +1,4 @@
 // Reusable pattern for the first byte of an identifier.
-#[macro_export]
 macro_rules! ident_start {
     () => {
         b'a'..=b'z' | b'A'..=b'Z' | b'_' | b'\x80'..=b'\xff'
@@ -7,9 +6,11 @@ macro_rules! ident_start {
 }
 
 // Reusable pattern for identifier after the first byte.
-#[macro_export]
 macro_rules! ident {
     () => {
         b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' | b'_' | b'\x80'..=b'\xff'
     };
 }
+
+pub(crate) use ident;
+pub(crate) use ident_start;
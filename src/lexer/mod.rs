@@ -1,112 +1,300 @@
-use crate::ident;
-use crate::ident_start;
+use crate::lexer::byte_string::ByteStr;
 use crate::lexer::byte_string::ByteString;
 use crate::lexer::error::SyntaxError;
 use crate::lexer::error::SyntaxResult;
+use crate::lexer::macros::ident;
+use crate::lexer::macros::ident_start;
 use crate::lexer::state::source::Source;
 use crate::lexer::state::StackFrame;
 use crate::lexer::state::State;
 use crate::lexer::token::DocStringIndentationKind;
 use crate::lexer::token::DocStringKind;
 use crate::lexer::token::OpenTagKind;
+use crate::lexer::token::Span;
 use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
 
 pub mod byte_string;
 pub mod error;
+pub mod line_index;
 pub mod stream;
 pub mod token;
 
+mod sniff;
 mod state;
+mod tokens;
 
 mod macros;
 
+pub use sniff::sniff;
+pub use sniff::Confidence;
+pub use tokens::Tokens;
+
+/// How the lexer handles null bytes and other control characters found in code.
+///
+/// Control characters are the ASCII control characters, but for whitespace, along with
+/// `DEL`. Within inline HTML, strings, and comments they are always kept as is.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum ControlCharacters {
+    // Stop with an error, like PHP does.
+    #[default]
+    Error,
+    // Lex them as whitespace.
+    Replace,
+    // Lex them as whitespace, and report them, see `Lexer::tokenize_with_diagnostics`.
+    Report,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
-pub struct Lexer;
+pub struct Lexer {
+    // (opening marker, closing marker)
+    foreign_markers: Vec<(ByteString, ByteString)>,
+    scripting: bool,
+    max_tokens: Option<usize>,
+    control_characters: ControlCharacters,
+}
 
 impl Lexer {
     pub const fn new() -> Self {
-        Self {}
+        Self {
+            foreign_markers: Vec::new(),
+            scripting: false,
+            max_tokens: None,
+            control_characters: ControlCharacters::Error,
+        }
+    }
+
+    /// Handle null bytes and other control characters found in code as given, e.g. to lex
+    /// obfuscated files, which PHP would refuse to run, rather than stopping at the first one.
+    pub fn with_control_characters(mut self, handling: ControlCharacters) -> Self {
+        self.control_characters = handling;
+
+        self
+    }
+
+    /// Stop with an error as soon as more than the given number of tokens are produced,
+    /// the final end of file token excluded.
+    pub fn with_max_tokens(mut self, max: usize) -> Self {
+        self.max_tokens = Some(max);
+
+        self
     }
 
-    pub fn tokenize<B: ?Sized + AsRef<[u8]>>(&self, input: &B) -> SyntaxResult<Vec<Token>> {
-        let mut state = State::new(Source::new(input.as_ref()));
+    /// Lex the input as PHP code from the start, as if it was preceded by an open tag,
+    /// e.g. to lex a snippet such as `$a + 1`.
+    pub fn with_scripting(mut self) -> Self {
+        self.scripting = true;
+
+        self
+    }
+
+    /// Capture regions of inline HTML delimited by the given markers, e.g. `{{` and `}}`,
+    /// as opaque `ForeignChunk` tokens.
+    ///
+    /// The contents of a foreign chunk are never lexed, so an open tag within a chunk
+    /// does not start PHP code. A chunk without a closing marker extends to the end of the file.
+    pub fn with_foreign_markers<B: ?Sized + AsRef<[u8]>>(mut self, open: &B, close: &B) -> Self {
+        self.foreign_markers
+            .push((open.as_ref().into(), close.as_ref().into()));
+
+        self
+    }
+
+    pub fn tokenize<'a, B: ?Sized + AsRef<[u8]>>(
+        &self,
+        input: &'a B,
+    ) -> SyntaxResult<Vec<Token<'a>>> {
+        self.lex(input.as_ref(), &mut Vec::new())
+    }
+
+    /// Tokenize the input lazily, lexing each token as it is requested, e.g. to highlight
+    /// code without lexing it upfront.
+    ///
+    /// ```
+    /// use php_parser_rs::lexer::token::TokenKind;
+    /// use php_parser_rs::lexer::Lexer;
+    ///
+    /// let lexer = Lexer::new();
+    /// let kinds = lexer
+    ///     .tokens("<?php echo 1;")
+    ///     .map(|token| token.map(|token| token.kind))
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(kinds[1], TokenKind::Echo);
+    /// ```
+    pub fn tokens<'a, B: ?Sized + AsRef<[u8]>>(&'a self, input: &'a B) -> Tokens<'a> {
+        Tokens::new(self, input.as_ref())
+    }
+
+    /// Tokenize the input into the given buffer, clearing it first, e.g. to reuse its
+    /// capacity when tokenizing many inputs.
+    pub fn tokenize_into<'a, B: ?Sized + AsRef<[u8]>>(
+        &self,
+        input: &'a B,
+        tokens: &mut Vec<Token<'a>>,
+    ) -> SyntaxResult<()> {
+        self.lex_into(input.as_ref(), tokens, &mut Vec::new())
+    }
+
+    /// Tokenize the input, along with an `UnexpectedCharacter` error for every control
+    /// character lexed as whitespace when they are reported, see [`ControlCharacters`].
+    pub fn tokenize_with_diagnostics<'a, B: ?Sized + AsRef<[u8]>>(
+        &self,
+        input: &'a B,
+    ) -> SyntaxResult<(Vec<Token<'a>>, Vec<SyntaxError>)> {
+        let mut diagnostics = Vec::new();
+        let tokens = self.lex(input.as_ref(), &mut diagnostics)?;
+
+        Ok((tokens, diagnostics))
+    }
+
+    fn lex<'a>(
+        &self,
+        input: &'a [u8],
+        diagnostics: &mut Vec<SyntaxError>,
+    ) -> SyntaxResult<Vec<Token<'a>>> {
         let mut tokens = Vec::new();
+        self.lex_into(input, &mut tokens, diagnostics)?;
 
-        while !state.source.eof() {
-            match state.frame()? {
-                // The "Initial" state is used to parse inline HTML. It is essentially a catch-all
-                // state that will build up a single token buffer until it encounters an open tag
-                // of some description.
-                StackFrame::Initial => self.initial(&mut state, &mut tokens)?,
-                // The scripting state is entered when an open tag is encountered in the source code.
-                // This tells the lexer to start analysing characters at PHP tokens instead of inline HTML.
-                StackFrame::Scripting => {
-                    self.skip_whitespace(&mut state);
-
-                    // If we have consumed whitespace and then reached the end of the file, we should break.
-                    if state.source.eof() {
-                        break;
-                    }
+        Ok(tokens)
+    }
 
-                    tokens.push(self.scripting(&mut state)?);
+    fn lex_into<'a>(
+        &self,
+        input: &'a [u8],
+        tokens: &mut Vec<Token<'a>>,
+        diagnostics: &mut Vec<SyntaxError>,
+    ) -> SyntaxResult<()> {
+        let mut state = self.state(input);
+        tokens.clear();
+
+        while self.step(&mut state, tokens, diagnostics)? {
+            self.check_token_count(tokens)?;
+        }
+
+        self.check_token_count(tokens)?;
+
+        tokens.push(Self::eof(&state));
+
+        Ok(())
+    }
+
+    fn state<'a>(&self, input: &'a [u8]) -> State<'a> {
+        let mut state = State::new(Source::new(input));
+        if self.scripting {
+            state.replace(StackFrame::Scripting);
+        }
+
+        state
+    }
+
+    fn eof<'a>(state: &State<'a>) -> Token<'a> {
+        Token {
+            kind: TokenKind::Eof,
+            span: state.source.span(),
+            value: ByteStr::new(&[]),
+            raw: None,
+        }
+    }
+
+    /// Lex the next tokens of the input, if any, returning whether there may be more
+    /// tokens to lex, the end of file token excluded.
+    fn step<'a>(
+        &self,
+        state: &mut State<'a>,
+        tokens: &mut Vec<Token<'a>>,
+        diagnostics: &mut Vec<SyntaxError>,
+    ) -> SyntaxResult<bool> {
+        if state.source.eof() {
+            return Ok(false);
+        }
+
+        match state.frame()? {
+            // The "Initial" state is used to parse inline HTML. It is essentially a catch-all
+            // state that will build up a single token buffer until it encounters an open tag
+            // of some description.
+            StackFrame::Initial => self.initial(state, tokens)?,
+            // The scripting state is entered when an open tag is encountered in the source code.
+            // This tells the lexer to start analysing characters at PHP tokens instead of inline HTML.
+            StackFrame::Scripting => {
+                self.skip_whitespace(state);
+
+                // If we have consumed whitespace and then reached the end of the file, we should break.
+                if state.source.eof() {
+                    return Ok(false);
                 }
-                // The "Halted" state is entered when the `__halt_compiler` token is encountered.
-                // In this state, all the text that follows is no longer parsed as PHP as is collected
-                // into a single "InlineHtml" token (kind of cheating, oh well).
-                StackFrame::Halted => {
-                    tokens.push(Token {
-                        kind: TokenKind::InlineHtml,
-                        span: state.source.span(),
-                        value: state.source.read_remaining().into(),
-                    });
-                    break;
+
+                if self.skip_control_character(state, diagnostics) {
+                    return Ok(true);
                 }
-                // The double quote state is entered when inside a double-quoted string that
-                // contains variables.
-                StackFrame::DoubleQuote => self.double_quote(&mut state, &mut tokens)?,
-                // The shell exec state is entered when inside of a execution string (`).
-                StackFrame::ShellExec => self.shell_exec(&mut state, &mut tokens)?,
-                // The doc string state is entered when tokenizing heredocs and nowdocs.
-                StackFrame::DocString(kind, label, ..) => {
-                    let label = label.clone();
-
-                    match kind {
-                        DocStringKind::Heredoc => self.heredoc(&mut state, &mut tokens, label)?,
-                        DocStringKind::Nowdoc => self.nowdoc(&mut state, &mut tokens, label)?,
-                    }
+
+                tokens.push(self.scripting(state)?);
+            }
+            // The "Halted" state is entered when the `__halt_compiler` token is encountered.
+            // In this state, all the text that follows is no longer parsed as PHP as is collected
+            // into a single "InlineHtml" token (kind of cheating, oh well).
+            StackFrame::Halted => {
+                let span = state.source.span();
+                let value = state.source.read_remaining();
+                state.source.skip(value.len());
+
+                tokens.push(Token {
+                    kind: TokenKind::InlineHtml,
+                    span,
+                    value: value.into(),
+                    raw: None,
+                });
+
+                return Ok(false);
+            }
+            // The double quote state is entered when inside a double-quoted string that
+            // contains variables.
+            StackFrame::DoubleQuote => self.double_quote(state, tokens)?,
+            // The shell exec state is entered when inside of a execution string (`).
+            StackFrame::ShellExec => self.shell_exec(state, tokens)?,
+            // The doc string state is entered when tokenizing heredocs and nowdocs.
+            StackFrame::DocString(kind, label, ..) => {
+                let label = label.clone();
+
+                match kind {
+                    DocStringKind::Heredoc => self.heredoc(state, tokens, label)?,
+                    DocStringKind::Nowdoc => self.nowdoc(state, tokens, label)?,
                 }
-                // LookingForProperty is entered inside double quotes,
-                // backticks, or a heredoc, expecting a variable name.
-                // If one isn't found, it switches to scripting.
-                StackFrame::LookingForVarname => {
-                    if let Some(token) = self.looking_for_varname(&mut state)? {
-                        tokens.push(token);
-                    }
+            }
+            // LookingForProperty is entered inside double quotes,
+            // backticks, or a heredoc, expecting a variable name.
+            // If one isn't found, it switches to scripting.
+            StackFrame::LookingForVarname => {
+                if let Some(token) = self.looking_for_varname(state)? {
+                    tokens.push(token);
                 }
-                // LookingForProperty is entered inside double quotes,
-                // backticks, or a heredoc, expecting an arrow followed by a
-                // property name.
-                StackFrame::LookingForProperty => {
-                    tokens.push(self.looking_for_property(&mut state)?);
+            }
+            // LookingForProperty is entered inside double quotes,
+            // backticks, or a heredoc, expecting an arrow followed by a
+            // property name.
+            StackFrame::LookingForProperty => {
+                tokens.push(self.looking_for_property(state)?);
+            }
+            StackFrame::VarOffset => {
+                if state.source.eof() {
+                    return Ok(false);
                 }
-                StackFrame::VarOffset => {
-                    if state.source.eof() {
-                        break;
-                    }
 
-                    tokens.push(self.var_offset(&mut state)?);
-                }
+                tokens.push(self.var_offset(state)?);
             }
         }
 
-        tokens.push(Token {
-            kind: TokenKind::Eof,
-            span: state.source.span(),
-            value: ByteString::default(),
-        });
+        Ok(true)
+    }
 
-        Ok(tokens)
+    fn check_token_count(&self, tokens: &[Token]) -> SyntaxResult<()> {
+        match self.max_tokens {
+            Some(max) if tokens.len() > max => {
+                Err(SyntaxError::TooManyTokens(max, tokens[max].span))
+            }
+            _ => Ok(()),
+        }
     }
 
     fn skip_whitespace(&self, state: &mut State) {
@@ -115,30 +303,66 @@ impl Lexer {
         }
     }
 
-    fn read_and_skip_whitespace(&self, state: &mut State) -> Vec<u8> {
-        let mut buffer = Vec::new();
-        while let Some(true) = state.source.current().map(|u: &u8| u.is_ascii_whitespace()) {
-            buffer.push(*state.source.current().unwrap());
-            state.source.next();
+    /// Skip a control character as whitespace, unless they are errors.
+    fn skip_control_character(
+        &self,
+        state: &mut State,
+        diagnostics: &mut Vec<SyntaxError>,
+    ) -> bool {
+        let byte = match state.source.current() {
+            Some(byte) if is_control_character(*byte) => *byte,
+            _ => return false,
+        };
+
+        match self.control_characters {
+            ControlCharacters::Error => return false,
+            ControlCharacters::Replace => {}
+            ControlCharacters::Report => {
+                diagnostics.push(SyntaxError::UnexpectedCharacter(byte, state.source.span()));
+            }
         }
-        buffer
+
+        state.source.next();
+
+        true
     }
 
-    fn initial(&self, state: &mut State, tokens: &mut Vec<Token>) -> SyntaxResult<()> {
+    fn initial<'a>(&self, state: &mut State<'a>, tokens: &mut Vec<Token<'a>>) -> SyntaxResult<()> {
         let inline_span = state.source.span();
-        let mut buffer = Vec::new();
-        while let Some(char) = state.source.current() {
-            if state.source.at_case_insensitive(b"<?php", 5) {
+        while !state.source.eof() {
+            let html = state
+                .source
+                .slice(inline_span.position, state.source.span().position);
+
+            if let Some((open, close)) = self
+                .foreign_markers
+                .iter()
+                .find(|(open, _)| state.source.at(open, open.len()))
+            {
+                if !html.is_empty() {
+                    tokens.push(Token {
+                        kind: TokenKind::InlineHtml,
+                        span: inline_span,
+                        value: html.into(),
+                        raw: None,
+                    });
+                }
+
+                tokens.push(self.foreign_chunk(state, open, close));
+
+                return Ok(());
+            } else if state.source.at_case_insensitive(b"<?php", 5) {
                 let tag_span = state.source.span();
 
                 let tag = state.source.read_and_skip(5);
                 state.replace(StackFrame::Scripting);
 
-                if !buffer.is_empty() {
+                if !html.is_empty() {
                     tokens.push(Token {
                         kind: TokenKind::InlineHtml,
                         span: inline_span,
-                        value: buffer.into(),
+                        value: html.into(),
+                        raw: None,
                     });
                 }
 
@@ -146,6 +370,7 @@ impl Lexer {
                     kind: TokenKind::OpenTag(OpenTagKind::Full),
                     span: tag_span,
                     value: tag.into(),
+                    raw: None,
                 });
 
                 return Ok(());
@@ -155,11 +380,12 @@ impl Lexer {
                 state.source.skip(3);
                 state.replace(StackFrame::Scripting);
 
-                if !buffer.is_empty() {
+                if !html.is_empty() {
                     tokens.push(Token {
                         kind: TokenKind::InlineHtml,
                         span: inline_span,
-                        value: buffer.into(),
+                        value: html.into(),
+                        raw: None,
                     });
                 }
 
@@ -167,6 +393,7 @@ impl Lexer {
                     kind: TokenKind::OpenTag(OpenTagKind::Echo),
                     span: tag_span,
                     value: b"<?=".into(),
+                    raw: None,
                 });
 
                 return Ok(());
@@ -176,11 +403,12 @@ impl Lexer {
                 state.source.skip(2);
                 state.replace(StackFrame::Scripting);
 
-                if !buffer.is_empty() {
+                if !html.is_empty() {
                     tokens.push(Token {
                         kind: TokenKind::InlineHtml,
                         span: inline_span,
-                        value: buffer.into(),
+                        value: html.into(),
+                        raw: None,
                     });
                 }
 
@@ -188,27 +416,60 @@ impl Lexer {
                     kind: TokenKind::OpenTag(OpenTagKind::Short),
                     span: tag_span,
                     value: b"<?".into(),
+                    raw: None,
                 });
 
                 return Ok(());
             }
 
             state.source.next();
-            buffer.push(*char);
         }
 
         tokens.push(Token {
             kind: TokenKind::InlineHtml,
             span: inline_span,
-            value: buffer.into(),
+            value: state
+                .source
+                .slice(inline_span.position, state.source.span().position)
+                .into(),
+            raw: None,
         });
 
         Ok(())
     }
 
-    fn scripting(&self, state: &mut State) -> SyntaxResult<Token> {
+    fn foreign_chunk<'a>(&self, state: &mut State<'a>, open: &[u8], close: &[u8]) -> Token<'a> {
         let span = state.source.span();
-        let (kind, value): (TokenKind, ByteString) = match state.source.read(3) {
+        state.source.skip(open.len());
+
+        loop {
+            if state.source.at(close, close.len()) {
+                state.source.skip(close.len());
+
+                break;
+            }
+
+            if state.source.eof() {
+                break;
+            }
+
+            state.source.next();
+        }
+
+        Token {
+            kind: TokenKind::ForeignChunk,
+            span,
+            value: state
+                .source
+                .slice(span.position, state.source.span().position)
+                .into(),
+            raw: None,
+        }
+    }
+
+    fn scripting<'a>(&self, state: &mut State<'a>) -> SyntaxResult<Token<'a>> {
+        let span = state.source.span();
+        let (kind, value): (TokenKind, ByteStr<'a>) = match state.source.read(3) {
             [b'!', b'=', b'='] => {
                 state.source.skip(3);
 
@@ -293,12 +554,12 @@ impl Lexer {
             }
             // Single quoted string.
             [b'\'', ..] => {
-                let opening = state.source.read_and_skip(1);
-                self.tokenize_single_quote_string(state, opening)?
+                state.source.next();
+                self.tokenize_single_quote_string(state, span.position)?
             }
             [b'b' | b'B', b'\'', ..] => {
-                let opening = state.source.read_and_skip(2);
-                self.tokenize_single_quote_string(state, opening)?
+                state.source.skip(2);
+                self.tokenize_single_quote_string(state, span.position)?
             }
             [b'"', ..] => {
                 let opening = state.source.read_and_skip(1);
@@ -329,15 +590,15 @@ impl Lexer {
                 match self.scripting(state)? {
                     Token {
                         kind: TokenKind::Identifier | TokenKind::QualifiedIdentifier,
-                        value,
                         ..
-                    } => {
-                        let mut bytes = value;
-                        bytes.insert(0, b'\\');
-                        bytes.length += 1;
-
-                        (TokenKind::FullyQualifiedIdentifier, bytes)
-                    }
+                    } => (
+                        TokenKind::FullyQualifiedIdentifier,
+                        ByteStr::new(
+                            state
+                                .source
+                                .slice(span.position, state.source.span().position),
+                        ),
+                    ),
                     Token {
                         kind: TokenKind::True,
                         ..
@@ -350,7 +611,16 @@ impl Lexer {
                         kind: TokenKind::Null,
                         ..
                     } => (TokenKind::FullyQualifiedIdentifier, b"\\null".into()),
-                    s => unreachable!("{:?}", s),
+                    // Keywords are names once qualified, e.g. `\readonly`.
+                    Token { value, .. } if value.iter().all(|b| matches!(b, ident!())) => (
+                        TokenKind::FullyQualifiedIdentifier,
+                        ByteStr::new(
+                            state
+                                .source
+                                .slice(span.position, state.source.span().position),
+                        ),
+                    ),
+                    _ => return Err(SyntaxError::UnexpectedCharacter(b'\\', span)),
                 }
             }
             [b'\\', ..] => {
@@ -359,18 +629,15 @@ impl Lexer {
             }
             [b'/', b'*', ..] => {
                 state.source.next();
-                let mut buffer = vec![b'/'];
 
                 loop {
                     match state.source.read(2) {
                         [b'*', b'/'] => {
                             state.source.skip(2);
-                            buffer.extend_from_slice(b"*/");
                             break;
                         }
-                        &[t, ..] => {
+                        [_, ..] => {
                             state.source.next();
-                            buffer.push(t);
                         }
                         _ => {
                             break;
@@ -378,6 +645,10 @@ impl Lexer {
                     }
                 }
 
+                let buffer = state
+                    .source
+                    .slice(span.position, state.source.span().position);
+
                 if buffer.starts_with(b"/**") {
                     (TokenKind::DocumentComment, buffer.into())
                 } else {
@@ -389,14 +660,14 @@ impl Lexer {
                 (TokenKind::Attribute, b"#[".into())
             }
             [ch @ b'/', b'/', ..] | [ch @ b'#', ..] => {
-                let mut buffer = if *ch == b'/' {
+                if *ch == b'/' {
                     state.source.skip(2);
-                    b"//".to_vec()
                 } else {
                     state.source.next();
-                    b"#".to_vec()
-                };
+                }
 
+                // The line break ending the comment isn't part of it.
+                let mut end = state.source.span().position;
                 while let Some(c) = state.source.current() {
                     if *c == b'\n' {
                         state.source.next();
@@ -407,10 +678,12 @@ impl Lexer {
                         break;
                     }
 
-                    buffer.push(*c);
                     state.source.next();
+                    end = state.source.span().position;
                 }
 
+                let buffer = state.source.slice(span.position, end);
+
                 if buffer.starts_with(b"#") {
                     (TokenKind::HashMarkComment, buffer.into())
                 } else {
@@ -444,17 +717,14 @@ impl Lexer {
             }
             [b'<', b'<', b'<'] => {
                 state.source.skip(3);
-                let mut buffer = b"<<<".to_vec();
-                buffer.extend(self.read_and_skip_whitespace(state));
+                self.skip_whitespace(state);
 
                 let doc_string_kind = match state.source.read(1) {
                     [b'\''] => {
-                        buffer.push(b'\'');
                         state.source.next();
                         DocStringKind::Nowdoc
                     }
                     [b'"'] => {
-                        buffer.push(b'"');
                         state.source.next();
                         DocStringKind::Heredoc
                     }
@@ -466,50 +736,33 @@ impl Lexer {
 
                 let label: ByteString = match self.peek_identifier(state) {
                     Some(_) => self.consume_identifier(state).into(),
-                    None => {
-                        return match state.source.current() {
-                            Some(c) => {
-                                Err(SyntaxError::UnexpectedCharacter(*c, state.source.span()))
-                            }
-                            None => Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
-                        }
-                    }
+                    None => return Err(unexpected(state)),
                 };
 
-                buffer.extend_from_slice(&label);
-
                 if doc_string_kind == DocStringKind::Nowdoc {
                     match state.source.current() {
                         Some(b'\'') => {
-                            buffer.push(b'\'');
                             state.source.next();
                         }
-                        _ => {
-                            // TODO(azjezz) this is most likely a bug, what if current is none?
-                            return Err(SyntaxError::UnexpectedCharacter(
-                                *state.source.current().unwrap(),
-                                state.source.span(),
-                            ));
-                        }
+                        _ => return Err(unexpected(state)),
                     };
                 } else if let Some(b'"') = state.source.current() {
-                    buffer.push(b'"');
                     state.source.next();
                 }
 
+                // The line break following the label isn't part of the token.
+                let buffer = state
+                    .source
+                    .slice(span.position, state.source.span().position);
+
                 if !matches!(state.source.current(), Some(b'\n')) {
-                    return Err(SyntaxError::UnexpectedCharacter(
-                        *state.source.current().unwrap(),
-                        state.source.span(),
-                    ));
+                    return Err(unexpected(state));
                 }
 
                 state.source.next();
                 state.replace(StackFrame::DocString(
                     doc_string_kind.clone(),
                     label.clone(),
-                    DocStringIndentationKind::None,
-                    0,
                 ));
 
                 (TokenKind::StartDocString(doc_string_kind), buffer.into())
@@ -534,6 +787,10 @@ impl Lexer {
                 state.source.skip(2);
                 (TokenKind::PipeEquals, b"|=".into())
             }
+            [b'|', b'>', ..] => {
+                state.source.skip(2);
+                (TokenKind::PipeGreaterThan, b"|>".into())
+            }
             [b'|', ..] => {
                 state.source.next();
                 (TokenKind::Pipe, b"|".into())
@@ -558,134 +815,135 @@ impl Lexer {
             }
             [b'(', ..] => {
                 state.source.next();
-                let mut buffer = b"(".to_vec();
+                self.skip_whitespace(state);
 
-                // Inlined so we can add whitespace to the buffer.
-                while let Some(true) = state.source.current().map(|u: &u8| u.is_ascii_whitespace())
-                {
-                    buffer.push(*state.source.current().unwrap());
-                    state.source.next();
-                }
-
-                if state.source.at_case_insensitive(b"int", 3) {
+                let kind = if state.source.at_case_insensitive(b"int", 3) {
                     if state.source.at_case_insensitive(b"integer", 7)
                         && state.source.peek_ignoring_whitespace(7, 1) == [b')']
                     {
-                        buffer.extend(state.source.read_and_skip(7));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
+                        state.source.skip(7);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
 
-                        (TokenKind::IntegerCast, buffer.into())
+                        TokenKind::IntegerCast
                     } else if state.source.peek_ignoring_whitespace(3, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(3));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
+                        state.source.skip(3);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
 
-                        (TokenKind::IntCast, buffer.into())
+                        TokenKind::IntCast
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        TokenKind::LeftParen
                     }
                 } else if state.source.at_case_insensitive(b"bool", 4) {
                     if state.source.at_case_insensitive(b"boolean", 7)
                         && state.source.peek_ignoring_whitespace(7, 1) == [b')']
                     {
-                        buffer.extend(state.source.read_and_skip(7));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
+                        state.source.skip(7);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
 
-                        (TokenKind::BooleanCast, buffer.into())
+                        TokenKind::BooleanCast
                     } else if state.source.peek_ignoring_whitespace(4, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(4));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
+                        state.source.skip(4);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
 
-                        (TokenKind::BoolCast, buffer.into())
+                        TokenKind::BoolCast
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        TokenKind::LeftParen
                     }
                 } else if state.source.at_case_insensitive(b"float", 5) {
                     if state.source.peek_ignoring_whitespace(5, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(5));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
+                        state.source.skip(5);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
 
-                        (TokenKind::FloatCast, buffer.into())
+                        TokenKind::FloatCast
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        TokenKind::LeftParen
                     }
                 } else if state.source.at_case_insensitive(b"double", 6) {
                     if state.source.peek_ignoring_whitespace(6, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(6));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
+                        state.source.skip(6);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
 
-                        (TokenKind::DoubleCast, buffer.into())
+                        TokenKind::DoubleCast
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        TokenKind::LeftParen
                     }
                 } else if state.source.at_case_insensitive(b"real", 4) {
                     if state.source.peek_ignoring_whitespace(4, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(4));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
+                        state.source.skip(4);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
 
-                        (TokenKind::RealCast, buffer.into())
+                        TokenKind::RealCast
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        TokenKind::LeftParen
                     }
                 } else if state.source.at_case_insensitive(b"string", 6) {
                     if state.source.peek_ignoring_whitespace(6, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(6));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
+                        state.source.skip(6);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
 
-                        (TokenKind::StringCast, buffer.into())
+                        TokenKind::StringCast
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        TokenKind::LeftParen
                     }
                 } else if state.source.at_case_insensitive(b"binary", 6) {
                     if state.source.peek_ignoring_whitespace(6, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(6));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
+                        state.source.skip(6);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
 
-                        (TokenKind::BinaryCast, buffer.into())
+                        TokenKind::BinaryCast
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        TokenKind::LeftParen
                     }
                 } else if state.source.at_case_insensitive(b"array", 5) {
                     if state.source.peek_ignoring_whitespace(5, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(5));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
+                        state.source.skip(5);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
 
-                        (TokenKind::ArrayCast, buffer.into())
+                        TokenKind::ArrayCast
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        TokenKind::LeftParen
                     }
                 } else if state.source.at_case_insensitive(b"object", 6) {
                     if state.source.peek_ignoring_whitespace(6, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(6));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
+                        state.source.skip(6);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
 
-                        (TokenKind::ObjectCast, buffer.into())
+                        TokenKind::ObjectCast
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        TokenKind::LeftParen
                     }
                 } else if state.source.at_case_insensitive(b"unset", 5) {
                     if state.source.peek_ignoring_whitespace(5, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(5));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
+                        state.source.skip(5);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
 
-                        (TokenKind::UnsetCast, buffer.into())
+                        TokenKind::UnsetCast
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        TokenKind::LeftParen
                     }
                 } else {
-                    (TokenKind::LeftParen, buffer.into())
-                }
+                    TokenKind::LeftParen
+                };
+
+                (
+                    kind,
+                    state
+                        .source
+                        .slice(span.position, state.source.span().position)
+                        .into(),
+                )
             }
             [b')', ..] => {
                 state.source.next();
@@ -783,15 +1041,13 @@ impl Lexer {
                 state.source.next();
                 (TokenKind::BitwiseNot, b"~".into())
             }
-            [b @ ident_start!(), ..] => {
+            [ident_start!(), ..] => {
                 state.source.next();
                 let mut qualified = false;
                 let mut last_was_slash = false;
 
-                let mut buffer = vec![*b];
                 while let Some(next @ ident!() | next @ b'\\') = state.source.current() {
                     if matches!(next, ident!()) {
-                        buffer.push(*next);
                         state.source.next();
                         last_was_slash = false;
                         continue;
@@ -800,7 +1056,6 @@ impl Lexer {
                     if *next == b'\\' && !last_was_slash {
                         qualified = true;
                         last_was_slash = true;
-                        buffer.push(*next);
                         state.source.next();
                         continue;
                     }
@@ -808,10 +1063,14 @@ impl Lexer {
                     break;
                 }
 
+                let buffer = state
+                    .source
+                    .slice(span.position, state.source.span().position);
+
                 if qualified {
                     (TokenKind::QualifiedIdentifier, buffer.into())
                 } else {
-                    let kind = identifier_to_keyword(&buffer).unwrap_or(TokenKind::Identifier);
+                    let kind = TokenKind::keyword(buffer).unwrap_or(TokenKind::Identifier);
 
                     if kind == TokenKind::HaltCompiler {
                         match state.source.read(3) {
@@ -826,24 +1085,38 @@ impl Lexer {
                     (kind, buffer.into())
                 }
             }
-            [b, ..] => unimplemented!(
-                "<scripting> char: {}, line: {}, col: {}",
-                *b as char,
-                state.source.span().line,
-                state.source.span().column
-            ),
+            [b, ..] => return Err(SyntaxError::UnexpectedCharacter(*b, span)),
             // We should never reach this point since we have the empty checks surrounding
             // the call to this function, but it's better to be safe than sorry.
             [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
         };
 
-        Ok(Token { kind, span, value })
+        let raw = raw(
+            state
+                .source
+                .slice(span.position, state.source.span().position),
+            &value,
+        );
+
+        Ok(Token {
+            kind,
+            span,
+            value,
+            raw,
+        })
     }
 
-    fn double_quote(&self, state: &mut State, tokens: &mut Vec<Token>) -> SyntaxResult<()> {
+    fn double_quote<'a>(
+        &self,
+        state: &mut State<'a>,
+        tokens: &mut Vec<Token<'a>>,
+    ) -> SyntaxResult<()> {
         let span = state.source.span();
         let mut buffer = Vec::new();
-        let (kind, value) = loop {
+        let mut token_span;
+        let (kind, value): (TokenKind, ByteStr<'a>) = loop {
+            token_span = state.source.span();
+
             match state.source.read(3) {
                 [b'$', b'{', ..] => {
                     state.source.skip(2);
@@ -859,7 +1132,7 @@ impl Lexer {
                 [b'"', ..] => {
                     state.source.next();
                     state.replace(StackFrame::Scripting);
-                    break (TokenKind::DoubleQuote, b'"'.into());
+                    break (TokenKind::DoubleQuote, b"\"".into());
                 }
                 &[b'\\', b @ (b'"' | b'\\' | b'$'), ..] => {
                     state.source.skip(2);
@@ -953,8 +1226,7 @@ impl Lexer {
                     }
                 }
                 [b'$', ident_start!(), ..] => {
-                    let mut var = state.source.read_and_skip(1).to_vec();
-                    var.extend(self.consume_identifier(state));
+                    let (_, var) = self.tokenize_variable(state);
 
                     match state.source.read(4) {
                         [b'[', ..] => state.enter(StackFrame::VarOffset),
@@ -964,7 +1236,7 @@ impl Lexer {
                         _ => {}
                     }
 
-                    break (TokenKind::Variable, var.into());
+                    break (TokenKind::Variable, var);
                 }
                 &[b, ..] => {
                     state.source.next();
@@ -975,21 +1247,36 @@ impl Lexer {
         };
 
         if !buffer.is_empty() {
+            let source = state.source.slice(span.position, token_span.position);
+
             tokens.push(Token {
                 kind: TokenKind::StringPart,
                 span,
+                raw: raw(source, &buffer),
                 value: buffer.into(),
             })
         }
 
-        tokens.push(Token { kind, span, value });
+        tokens.push(Token {
+            kind,
+            span: token_span,
+            value,
+            raw: None,
+        });
         Ok(())
     }
 
-    fn shell_exec(&self, state: &mut State, tokens: &mut Vec<Token>) -> SyntaxResult<()> {
+    fn shell_exec<'a>(
+        &self,
+        state: &mut State<'a>,
+        tokens: &mut Vec<Token<'a>>,
+    ) -> SyntaxResult<()> {
         let span = state.source.span();
         let mut buffer = Vec::new();
-        let (kind, value) = loop {
+        let mut token_span;
+        let (kind, value): (TokenKind, ByteStr<'a>) = loop {
+            token_span = state.source.span();
+
             match state.source.read(2) {
                 [b'$', b'{'] => {
                     state.source.skip(2);
@@ -1008,8 +1295,7 @@ impl Lexer {
                     break (TokenKind::Backtick, b"`".into());
                 }
                 [b'$', ident_start!()] => {
-                    let mut var = state.source.read_and_skip(1).to_vec();
-                    var.extend(self.consume_identifier(state));
+                    let (_, var) = self.tokenize_variable(state);
 
                     match state.source.read(4) {
                         [b'[', ..] => state.enter(StackFrame::VarOffset),
@@ -1019,7 +1305,7 @@ impl Lexer {
                         _ => {}
                     }
 
-                    break (TokenKind::Variable, var.into());
+                    break (TokenKind::Variable, var);
                 }
                 &[b, ..] => {
                     state.source.next();
@@ -1030,28 +1316,51 @@ impl Lexer {
         };
 
         if !buffer.is_empty() {
+            let source = state.source.slice(span.position, token_span.position);
+
             tokens.push(Token {
                 kind: TokenKind::StringPart,
                 span,
+                raw: raw(source, &buffer),
                 value: buffer.into(),
             })
         }
 
-        tokens.push(Token { kind, span, value });
+        tokens.push(Token {
+            kind,
+            span: token_span,
+            value,
+            raw: None,
+        });
 
         Ok(())
     }
 
-    fn heredoc(
+    fn heredoc<'a>(
         &self,
-        state: &mut State,
-        tokens: &mut Vec<Token>,
+        state: &mut State<'a>,
+        tokens: &mut Vec<Token<'a>>,
         label: ByteString,
     ) -> SyntaxResult<()> {
-        let span = state.source.span();
+        let mut span = state.source.span();
         let mut buffer: Vec<u8> = Vec::new();
+        let mut line_start = self.at_line_start(state);
+
+        let mut token_span;
+        let (kind, value): (TokenKind, ByteStr<'a>) = loop {
+            if line_start {
+                line_start = false;
+
+                if let Some((end, kind)) =
+                    self.doc_string_line(state, tokens, &mut buffer, &mut span, &label)?
+                {
+                    token_span = end;
+                    break (kind, label.into());
+                }
+            }
+
+            token_span = state.source.span();
 
-        let (kind, value) = loop {
             match state.source.read(3) {
                 [b'$', b'{', ..] => {
                     state.source.skip(2);
@@ -1064,7 +1373,8 @@ impl Lexer {
                     state.enter(StackFrame::Scripting);
                     break (TokenKind::LeftBrace, b"{".into());
                 }
-                &[b'\\', b @ (b'"' | b'\\' | b'$'), ..] => {
+                // Quotes don't need escaping in a heredoc, so `\"` is kept as is, like in php-src.
+                &[b'\\', b @ (b'\\' | b'$'), ..] => {
                     state.source.skip(2);
                     buffer.push(b);
                 }
@@ -1156,8 +1466,7 @@ impl Lexer {
                     }
                 }
                 [b'$', ident_start!(), ..] => {
-                    let mut var = state.source.read_and_skip(1).to_vec();
-                    var.extend(self.consume_identifier(state));
+                    let (_, var) = self.tokenize_variable(state);
 
                     match state.source.read(4) {
                         [b'[', ..] => state.enter(StackFrame::VarOffset),
@@ -1167,86 +1476,14 @@ impl Lexer {
                         _ => {}
                     }
 
-                    break (TokenKind::Variable, var.into());
+                    break (TokenKind::Variable, var);
                 }
                 // If we find a new-line, we can start to check if we can see the EndHeredoc token.
                 [b'\n', ..] => {
                     buffer.push(b'\n');
                     state.source.next();
 
-                    // Check if we can see the closing label right here.
-                    if state.source.at(&label, label.len()) {
-                        state.source.skip(label.len());
-                        state.replace(StackFrame::Scripting);
-                        break (
-                            TokenKind::EndDocString(DocStringIndentationKind::None, 0),
-                            label,
-                        );
-                    }
-
-                    // Check if there's any whitespace first.
-                    let (whitespace_kind, whitespace_amount) = match state.source.read(1) {
-                        [b' '] => {
-                            let mut amount = 0;
-                            while state.source.read(1) == [b' '] {
-                                amount += 1;
-                                state.source.next();
-                            }
-                            (DocStringIndentationKind::Space, amount)
-                        }
-                        [b'\t'] => {
-                            let mut amount = 0;
-                            while state.source.read(1) == [b'\t'] {
-                                amount += 1;
-                                state.source.next();
-                            }
-                            (DocStringIndentationKind::Tab, amount)
-                        }
-                        _ => (DocStringIndentationKind::None, 0),
-                    };
-
-                    // We've figured out what type of whitespace was being used
-                    // at the start of the line.
-                    // We should now check for any extra whitespace, of any kind.
-                    let mut extra_whitespace_buffer = Vec::new();
-                    while let [b @ b' ' | b @ b'\t'] = state.source.read(1) {
-                        extra_whitespace_buffer.push(b);
-                        state.source.next();
-                    }
-
-                    // We've consumed all leading whitespace on this line now,
-                    // so let's try to read the label again.
-                    if state.source.at(&label, label.len()) {
-                        // We've found the label, finally! We need to do 1 last
-                        // check to make sure there wasn't a mixture of indentation types.
-                        if whitespace_kind != DocStringIndentationKind::None
-                            && !extra_whitespace_buffer.is_empty()
-                        {
-                            return Err(SyntaxError::InvalidDocIndentation(state.source.span()));
-                        }
-
-                        // If we get here, only 1 type of indentation was found. We can move
-                        // the process along by reading over the label and breaking out
-                        // with the EndHeredoc token, storing the kind and amount of whitespace.
-                        state.source.skip(label.len());
-                        state.replace(StackFrame::Scripting);
-                        break (
-                            TokenKind::EndDocString(whitespace_kind, whitespace_amount),
-                            label,
-                        );
-                    } else {
-                        // We didn't find the label. The buffer still needs to know about
-                        // the whitespace, so let's extend the buffer with the whitespace
-                        // and let the loop run again to handle the rest of the line.
-                        if whitespace_kind != DocStringIndentationKind::None {
-                            let whitespace_char: u8 = whitespace_kind.into();
-                            for _ in 0..whitespace_amount {
-                                buffer.push(whitespace_char);
-                            }
-                        }
-
-                        buffer.extend(extra_whitespace_buffer);
-                    }
+                    line_start = true;
                 }
                 &[b, ..] => {
                     state.source.next();
@@ -1256,113 +1493,67 @@ impl Lexer {
             }
         };
 
+        let mut source = state.source.slice(span.position, token_span.position);
+
         // Any trailing line breaks should be removed from the final heredoc.
         if buffer.last() == Some(&b'\n') {
             buffer.pop();
+
+            // The line break, and the indentation of the closing label, aren't part of the string.
+            if let Some(end) = source.iter().rposition(|b| *b == b'\n') {
+                source = &source[..end];
+            }
         }
 
         if !buffer.is_empty() {
             tokens.push(Token {
                 kind: TokenKind::StringPart,
                 span,
+                raw: raw(source, &buffer),
                 value: buffer.into(),
             })
         }
 
-        tokens.push(Token { kind, span, value });
+        tokens.push(Token {
+            kind,
+            span: token_span,
+            value,
+            raw: None,
+        });
 
         Ok(())
     }
 
-    fn nowdoc(
+    fn nowdoc<'a>(
         &self,
-        state: &mut State,
-        tokens: &mut Vec<Token>,
+        state: &mut State<'a>,
+        tokens: &mut Vec<Token<'a>>,
         label: ByteString,
     ) -> SyntaxResult<()> {
-        let span = state.source.span();
+        let mut span = state.source.span();
         let mut buffer: Vec<u8> = Vec::new();
+        let mut line_start = self.at_line_start(state);
+
+        let token_span;
+        let (kind, value): (TokenKind, ByteStr<'a>) = loop {
+            if line_start {
+                line_start = false;
+
+                if let Some((end, kind)) =
+                    self.doc_string_line(state, tokens, &mut buffer, &mut span, &label)?
+                {
+                    token_span = end;
+                    break (kind, label.into());
+                }
+            }
 
-        let (kind, value) = loop {
             match state.source.read(3) {
                 // If we find a new-line, we can start to check if we can see the EndHeredoc token.
                 [b'\n', ..] => {
                     buffer.push(b'\n');
                     state.source.next();
 
-                    // Check if we can see the closing label right here.
-                    if state.source.at(&label, label.len()) {
-                        state.source.skip(label.len());
-                        state.replace(StackFrame::Scripting);
-                        break (
-                            TokenKind::EndDocString(DocStringIndentationKind::None, 0),
-                            label,
-                        );
-                    }
-
-                    // Check if there's any whitespace first.
-                    let (whitespace_kind, whitespace_amount) = match state.source.read(1) {
-                        [b' '] => {
-                            let mut amount = 0;
-                            while state.source.read(1) == [b' '] {
-                                amount += 1;
-                                state.source.next();
-                            }
-                            (DocStringIndentationKind::Space, amount)
-                        }
-                        [b'\t'] => {
-                            let mut amount = 0;
-                            while state.source.read(1) == [b'\t'] {
-                                amount += 1;
-                                state.source.next();
-                            }
-                            (DocStringIndentationKind::Tab, amount)
-                        }
-                        _ => (DocStringIndentationKind::None, 0),
-                    };
-
-                    // We've figured out what type of whitespace was being used
-                    // at the start of the line.
-                    // We should now check for any extra whitespace, of any kind.
-                    let mut extra_whitespace_buffer = Vec::new();
-                    while let [b @ b' ' | b @ b'\t'] = state.source.read(1) {
-                        extra_whitespace_buffer.push(b);
-                        state.source.next();
-                    }
-
-                    // We've consumed all leading whitespace on this line now,
-                    // so let's try to read the label again.
-                    if state.source.at(&label, label.len()) {
-                        // We've found the label, finally! We need to do 1 last
-                        // check to make sure there wasn't a mixture of indentation types.
-                        if whitespace_kind != DocStringIndentationKind::None
-                            && !extra_whitespace_buffer.is_empty()
-                        {
-                            return Err(SyntaxError::InvalidDocIndentation(state.source.span()));
-                        }
-
-                        // If we get here, only 1 type of indentation was found. We can move
-                        // the process along by reading over the label and breaking out
-                        // with the EndHeredoc token, storing the kind and amount of whitespace.
-                        state.source.skip(label.len());
-                        state.replace(StackFrame::Scripting);
-                        break (
-                            TokenKind::EndDocString(whitespace_kind, whitespace_amount),
-                            label,
-                        );
-                    } else {
-                        // We didn't find the label. The buffer still needs to know about
-                        // the whitespace, so let's extend the buffer with the whitespace
-                        // and let the loop run again to handle the rest of the line.
-                        if whitespace_kind != DocStringIndentationKind::None {
-                            let whitespace_char: u8 = whitespace_kind.into();
-                            for _ in 0..whitespace_amount {
-                                buffer.push(whitespace_char);
-                            }
-                        }
-
-                        buffer.extend(extra_whitespace_buffer);
-                    }
+                    line_start = true;
                 }
                 &[b, ..] => {
                     state.source.next();
@@ -1372,30 +1563,151 @@ impl Lexer {
             }
         };
 
+        let mut source = state.source.slice(span.position, token_span.position);
+
         // Any trailing line breaks should be removed from the final heredoc.
         if buffer.last() == Some(&b'\n') {
             buffer.pop();
+
+            // The line break, and the indentation of the closing label, aren't part of the string.
+            if let Some(end) = source.iter().rposition(|b| *b == b'\n') {
+                source = &source[..end];
+            }
         }
 
         if !buffer.is_empty() {
             tokens.push(Token {
                 kind: TokenKind::StringPart,
                 span,
+                raw: raw(source, &buffer),
                 value: buffer.into(),
             })
         }
 
-        tokens.push(Token { kind, span, value });
+        tokens.push(Token {
+            kind,
+            span: token_span,
+            value,
+            raw: None,
+        });
 
         Ok(())
     }
 
-    fn looking_for_varname(&self, state: &mut State) -> SyntaxResult<Option<Token>> {
+    /// Whether the source is at the start of a line, e.g. at the start of the body of a doc string.
+    fn at_line_start(&self, state: &State) -> bool {
+        let position = state.source.span().position;
+
+        position > 0 && state.source.slice(position - 1, position) == b"\n"
+    }
+
+    /// Read the start of a line of a doc string: its indentation, followed by either the closing
+    /// label, which ends the doc string, or the content of the line.
+    ///
+    /// When the line isn't closing the doc string, the previous line is emitted as a string part,
+    /// and the indentation is kept in the buffer, as part of the content of the line.
+    fn doc_string_line<'a>(
+        &self,
+        state: &mut State<'a>,
+        tokens: &mut Vec<Token<'a>>,
+        buffer: &mut Vec<u8>,
+        span: &mut Span,
+        label: &ByteString,
+    ) -> SyntaxResult<Option<(Span, TokenKind)>> {
+        let line_span = state.source.span();
+
+        // Check if there's any whitespace first.
+        let (whitespace_kind, whitespace_amount) = match state.source.read(1) {
+            [b' '] => {
+                let mut amount = 0;
+                while state.source.read(1) == [b' '] {
+                    amount += 1;
+                    state.source.next();
+                }
+                (DocStringIndentationKind::Space, amount)
+            }
+            [b'\t'] => {
+                let mut amount = 0;
+                while state.source.read(1) == [b'\t'] {
+                    amount += 1;
+                    state.source.next();
+                }
+                (DocStringIndentationKind::Tab, amount)
+            }
+            _ => (DocStringIndentationKind::None, 0),
+        };
+
+        // We've figured out what type of whitespace was being used
+        // at the start of the line.
+        // We should now check for any extra whitespace, of any kind.
+        let mut extra_whitespace_buffer = Vec::new();
+        while let [b @ b' ' | b @ b'\t'] = state.source.read(1) {
+            extra_whitespace_buffer.push(b);
+            state.source.next();
+        }
+
+        // We've consumed all leading whitespace on this line now, so let's try to read the label.
+        // Like in php-src, the label only closes the doc string when it isn't followed by another
+        // label character, e.g. `EOT;` or `EOT)` closes `<<<EOT`, but `EOT_2` doesn't.
+        if state.source.at(label, label.len())
+            && !matches!(state.source.peek(label.len(), 1), [ident!()])
+        {
+            // We've found the label, finally! We need to do 1 last
+            // check to make sure there wasn't a mixture of indentation types.
+            if whitespace_kind != DocStringIndentationKind::None
+                && !extra_whitespace_buffer.is_empty()
+            {
+                return Err(SyntaxError::InvalidDocIndentation(state.source.span()));
+            }
+
+            // If we get here, only 1 type of indentation was found. We can move
+            // the process along by reading over the label and returning the
+            // EndHeredoc token, storing the kind and amount of whitespace.
+            let end = state.source.span();
+            state.source.skip(label.len());
+            state.replace(StackFrame::Scripting);
+
+            return Ok(Some((
+                end,
+                TokenKind::EndDocString(whitespace_kind, whitespace_amount),
+            )));
+        }
+
+        // We didn't find the label, so the previous line is complete.
+        // Each line is emitted as its own string part, so that every
+        // part carries the exact position it started at.
+        if !buffer.is_empty() {
+            let source = state.source.slice(span.position, line_span.position);
+
+            tokens.push(Token {
+                kind: TokenKind::StringPart,
+                span: *span,
+                raw: raw(source, buffer),
+                value: std::mem::take(buffer).into(),
+            });
+        }
+        *span = line_span;
+
+        // The buffer still needs to know about the whitespace, so let's
+        // extend the buffer with the whitespace and let the loop run
+        // again to handle the rest of the line.
+        if whitespace_kind != DocStringIndentationKind::None {
+            let whitespace_char: u8 = whitespace_kind.into();
+            for _ in 0..whitespace_amount {
+                buffer.push(whitespace_char);
+            }
+        }
+
+        buffer.extend(extra_whitespace_buffer);
+
+        Ok(None)
+    }
+
+    fn looking_for_varname<'a>(&self, state: &mut State<'a>) -> SyntaxResult<Option<Token<'a>>> {
         let identifier = self.peek_identifier(state);
 
         if let Some(ident) = identifier {
             if let [b'[' | b'}'] = state.source.peek(ident.len(), 1) {
-                let ident = ident.to_vec();
                 let span = state.source.span();
                 state.source.skip(ident.len());
                 state.replace(StackFrame::Scripting);
@@ -1403,6 +1715,7 @@ impl Lexer {
                     kind: TokenKind::Identifier,
                     span,
                     value: ident.into(),
+                    raw: None,
                 }));
             }
         }
@@ -1412,7 +1725,7 @@ impl Lexer {
         Ok(None)
     }
 
-    fn looking_for_property(&self, state: &mut State) -> SyntaxResult<Token> {
+    fn looking_for_property<'a>(&self, state: &mut State<'a>) -> SyntaxResult<Token<'a>> {
         let span = state.source.span();
         let (kind, value) = match state.source.read(3) {
             [b'?', b'-', b'>'] => {
@@ -1429,13 +1742,18 @@ impl Lexer {
                 (TokenKind::Identifier, buffer.into())
             }
             // Should be impossible as we already looked ahead this far inside double_quote.
-            _ => unreachable!(),
+            _ => return Err(unexpected(state)),
         };
 
-        Ok(Token { kind, span, value })
+        Ok(Token {
+            kind,
+            span,
+            value,
+            raw: None,
+        })
     }
 
-    fn var_offset(&self, state: &mut State) -> SyntaxResult<Token> {
+    fn var_offset<'a>(&self, state: &mut State<'a>) -> SyntaxResult<Token<'a>> {
         let span = state.source.span();
         let (kind, value) = match state.source.read(2) {
             [b'$', ident_start!()] => self.tokenize_variable(state),
@@ -1465,43 +1783,71 @@ impl Lexer {
             &[b, ..] => return Err(SyntaxError::UnrecognisedToken(b, state.source.span())),
             [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
         };
-        Ok(Token { kind, span, value })
+        let raw = raw(
+            state
+                .source
+                .slice(span.position, state.source.span().position),
+            &value,
+        );
+
+        Ok(Token {
+            kind,
+            span,
+            value,
+            raw,
+        })
     }
 
-    fn tokenize_single_quote_string(
+    fn tokenize_single_quote_string<'a>(
         &self,
-        state: &mut State,
-        opening: &[u8],
-    ) -> SyntaxResult<(TokenKind, ByteString)> {
-        let mut buffer = opening.to_vec();
+        state: &mut State<'a>,
+        start: usize,
+    ) -> SyntaxResult<(TokenKind, ByteStr<'a>)> {
+        // The string is borrowed from the input, unless it contains an escape sequence.
+        let mut buffer: Option<Vec<u8>> = None;
 
         loop {
             match state.source.read(2) {
                 [b'\'', ..] => {
-                    buffer.push(b'\'');
                     state.source.next();
                     break;
                 }
                 &[b'\\', b @ b'\'' | b @ b'\\'] => {
+                    let position = state.source.span().position;
+                    buffer
+                        .get_or_insert_with(|| state.source.slice(start, position).to_vec())
+                        .push(b);
                     state.source.skip(2);
-                    buffer.push(b);
                 }
                 &[b, ..] => {
                     state.source.next();
-                    buffer.push(b);
+                    if let Some(buffer) = &mut buffer {
+                        buffer.push(b);
+                    }
                 }
                 [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
             }
         }
 
-        Ok((TokenKind::LiteralString, buffer.into()))
+        let value = match buffer {
+            Some(mut buffer) => {
+                buffer.push(b'\'');
+                buffer.into()
+            }
+            None => state
+                .source
+                .slice(start, state.source.span().position)
+                .into(),
+        };
+
+        Ok((TokenKind::LiteralString, value))
     }
 
-    fn tokenize_double_quote_string(
+    fn tokenize_double_quote_string<'a>(
         &self,
-        state: &mut State,
+        state: &mut State<'a>,
         opening: &[u8],
-    ) -> SyntaxResult<(TokenKind, ByteString)> {
+    ) -> SyntaxResult<(TokenKind, ByteStr<'a>)> {
         let mut buffer = opening.to_vec();
 
         let constant = loop {
@@ -1622,7 +1968,7 @@ impl Lexer {
         })
     }
 
-    fn peek_identifier<'a>(&'a self, state: &'a State) -> Option<&'a [u8]> {
+    fn peek_identifier<'a>(&self, state: &State<'a>) -> Option<&'a [u8]> {
         let mut size = 0;
 
         if let [ident_start!()] = state.source.read(1) {
@@ -1637,20 +1983,28 @@ impl Lexer {
         }
     }
 
-    fn consume_identifier(&self, state: &mut State) -> Vec<u8> {
-        let ident = self.peek_identifier(state).unwrap().to_vec();
+    fn consume_identifier<'a>(&self, state: &mut State<'a>) -> &'a [u8] {
+        let ident = self.peek_identifier(state).unwrap_or_default();
         state.source.skip(ident.len());
 
         ident
     }
 
-    fn tokenize_variable(&self, state: &mut State) -> (TokenKind, ByteString) {
-        let mut var = state.source.read_and_skip(1).to_vec();
-        var.extend(self.consume_identifier(state));
-        (TokenKind::Variable, var.into())
+    fn tokenize_variable<'a>(&self, state: &mut State<'a>) -> (TokenKind, ByteStr<'a>) {
+        let start = state.source.span().position;
+        state.source.next();
+        self.consume_identifier(state);
+
+        (
+            TokenKind::Variable,
+            state
+                .source
+                .slice(start, state.source.span().position)
+                .into(),
+        )
     }
 
-    fn tokenize_number(&self, state: &mut State) -> SyntaxResult<(TokenKind, ByteString)> {
+    fn tokenize_number<'a>(&self, state: &mut State<'a>) -> SyntaxResult<(TokenKind, ByteStr<'a>)> {
         let mut buffer = Vec::new();
 
         let (base, kind) = match state.source.read(2) {
@@ -1680,7 +2034,7 @@ impl Lexer {
         if kind != NumberKind::Float {
             self.read_digits(state, &mut buffer, base);
             if kind == NumberKind::Int {
-                return parse_int(&buffer);
+                return parse_int(buffer);
             }
         }
 
@@ -1691,7 +2045,7 @@ impl Lexer {
         );
 
         if !is_float {
-            return parse_int(&buffer);
+            return parse_int(buffer);
         }
 
         if let Some(b'.') = state.source.current() {
@@ -1756,104 +2110,30 @@ impl Lexer {
     }
 }
 
+/// The error for the current character, or for the end of the input when there is none.
+fn unexpected(state: &State) -> SyntaxError {
+    match state.source.current() {
+        Some(c) => SyntaxError::UnexpectedCharacter(*c, state.source.span()),
+        None => SyntaxError::UnexpectedEndOfFile(state.source.span()),
+    }
+}
+
+// ASCII control characters but whitespace, along with `DEL`.
+fn is_control_character(byte: u8) -> bool {
+    (byte < 0x20 && !byte.is_ascii_whitespace()) || byte == 0x7f
+}
+
+/// The source text of a token, when it differs from the value of the token.
+fn raw<'a>(source: &'a [u8], value: &[u8]) -> Option<ByteStr<'a>> {
+    (source != value).then(|| ByteStr::new(source))
+}
+
 // Parses an integer literal in the given base and converts errors to SyntaxError.
 // It returns a float token instead on overflow.
-fn parse_int(buffer: &[u8]) -> SyntaxResult<(TokenKind, ByteString)> {
+fn parse_int<'a>(buffer: Vec<u8>) -> SyntaxResult<(TokenKind, ByteStr<'a>)> {
     Ok((TokenKind::LiteralInteger, buffer.into()))
 }
 
-#[inline(always)]
-fn identifier_to_keyword(ident: &[u8]) -> Option<TokenKind> {
-    Some(match ident.to_ascii_lowercase().as_slice() {
-        b"eval" => TokenKind::Eval,
-        b"die" => TokenKind::Die,
-        b"empty" => TokenKind::Empty,
-        b"isset" => TokenKind::Isset,
-        b"unset" => TokenKind::Unset,
-        b"exit" => TokenKind::Exit,
-        b"enddeclare" => TokenKind::EndDeclare,
-        b"endswitch" => TokenKind::EndSwitch,
-        b"endfor" => TokenKind::EndFor,
-        b"endwhile" => TokenKind::EndWhile,
-        b"endforeach" => TokenKind::EndForeach,
-        b"endif" => TokenKind::EndIf,
-        b"from" => TokenKind::From,
-        b"and" => TokenKind::LogicalAnd,
-        b"or" => TokenKind::LogicalOr,
-        b"xor" => TokenKind::LogicalXor,
-        b"print" => TokenKind::Print,
-        b"__halt_compiler" => TokenKind::HaltCompiler,
-        b"readonly" => TokenKind::Readonly,
-        b"global" => TokenKind::Global,
-        b"match" => TokenKind::Match,
-        b"abstract" => TokenKind::Abstract,
-        b"array" => TokenKind::Array,
-        b"as" => TokenKind::As,
-        b"break" => TokenKind::Break,
-        b"case" => TokenKind::Case,
-        b"catch" => TokenKind::Catch,
-        b"class" => TokenKind::Class,
-        b"clone" => TokenKind::Clone,
-        b"continue" => TokenKind::Continue,
-        b"const" => TokenKind::Const,
-        b"declare" => TokenKind::Declare,
-        b"default" => TokenKind::Default,
-        b"do" => TokenKind::Do,
-        b"echo" => TokenKind::Echo,
-        b"else" => TokenKind::Else,
-        b"elseif" => TokenKind::ElseIf,
-        b"enum" => TokenKind::Enum,
-        b"extends" => TokenKind::Extends,
-        b"false" => TokenKind::False,
-        b"final" => TokenKind::Final,
-        b"finally" => TokenKind::Finally,
-        b"fn" => TokenKind::Fn,
-        b"for" => TokenKind::For,
-        b"foreach" => TokenKind::Foreach,
-        b"function" => TokenKind::Function,
-        b"goto" => TokenKind::Goto,
-        b"if" => TokenKind::If,
-        b"include" => TokenKind::Include,
-        b"include_once" => TokenKind::IncludeOnce,
-        b"implements" => TokenKind::Implements,
-        b"interface" => TokenKind::Interface,
-        b"instanceof" => TokenKind::Instanceof,
-        b"namespace" => TokenKind::Namespace,
-        b"new" => TokenKind::New,
-        b"null" => TokenKind::Null,
-        b"private" => TokenKind::Private,
-        b"protected" => TokenKind::Protected,
-        b"public" => TokenKind::Public,
-        b"require" => TokenKind::Require,
-        b"require_once" => TokenKind::RequireOnce,
-        b"return" => TokenKind::Return,
-        b"static" => TokenKind::Static,
-        b"switch" => TokenKind::Switch,
-        b"throw" => TokenKind::Throw,
-        b"trait" => TokenKind::Trait,
-        b"true" => TokenKind::True,
-        b"try" => TokenKind::Try,
-        b"use" => TokenKind::Use,
-        b"var" => TokenKind::Var,
-        b"yield" => TokenKind::Yield,
-        b"__dir__" => TokenKind::DirConstant,
-        b"__file__" => TokenKind::FileConstant,
-        b"__line__" => TokenKind::LineConstant,
-        b"__function__" => TokenKind::FunctionConstant,
-        b"__class__" => TokenKind::ClassConstant,
-        b"__method__" => TokenKind::MethodConstant,
-        b"__trait__" => TokenKind::TraitConstant,
-        b"__namespace__" => TokenKind::NamespaceConstant,
-        b"__compiler_halt_offset__" => TokenKind::CompilerHaltOffsetConstant,
-        b"while" => TokenKind::While,
-        b"insteadof" => TokenKind::Insteadof,
-        b"list" => TokenKind::List,
-        b"self" => TokenKind::Self_,
-        b"parent" => TokenKind::Parent,
-        _ => return None,
-    })
-}
-
 #[derive(Debug, Eq, PartialEq)]
 enum NumberKind {
     Int,
@@ -1861,3 +2141,168 @@ enum NumberKind {
     IntOrFloat,
     OctalOrFloat,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ControlCharacters;
+    use super::Lexer;
+    use crate::lexer::error::SyntaxError;
+    use crate::lexer::token::OpenTagKind;
+    use crate::lexer::token::TokenKind;
+
+    /// Concatenate the source text of the tokens of the given input, and the whitespace between them.
+    fn reconstruct(input: &[u8]) -> Vec<u8> {
+        let tokens = Lexer::new().tokenize(input).unwrap();
+
+        let mut output = Vec::new();
+        for token in &tokens {
+            let whitespace = &input[output.len()..token.span.position];
+            assert!(
+                whitespace.iter().all(u8::is_ascii_whitespace),
+                "unexpected text {:?} before {:?}",
+                String::from_utf8_lossy(whitespace),
+                token
+            );
+
+            output.extend(whitespace);
+            output.extend(token.raw());
+        }
+
+        output
+    }
+
+    #[test]
+    fn test_raw_tokens_reproduce_input() {
+        let inputs = [
+            "<?php $a = 1_000 + 0x1_F + 0b1_0 + 0o1_7 + 01_7 + 1_0.5e1_0;",
+            "<?php ECHO TRUE; FuNcTiOn a() {} $a = (INT) $b; $c = ( float )$d;",
+            "<?php $a = 'it\\'s \\\\ \\n'; $b = \"\\t\\u{41}\\x41\\101\\$ $c {$d} ${e} $f->g $h[0x1_0]\";",
+            "<?php $a = <<<EOT\n    foo\\n $b\n      bar {$c}\n    EOT;\n$d = <<<'EOT'\n  x\\n\n  EOT;\n",
+            "<?php $a = `ls \\n $b`; $c = B\"y\\n\"; ?>\r\n<p><?= $x ?><?php\n#[A] # a\n/** b */",
+            "<?php\r\n$a = \"\\r\\n\";\r\n__halt_compiler(); \\ anything",
+        ];
+
+        for input in inputs {
+            assert_eq!(
+                String::from_utf8_lossy(&reconstruct(input.as_bytes())),
+                input
+            );
+        }
+
+        let fixtures = std::fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"));
+        for fixture in fixtures.unwrap() {
+            let input = std::fs::read(fixture.unwrap().path().join("code.php")).unwrap();
+            if Lexer::new().tokenize(&input).is_ok() {
+                assert_eq!(reconstruct(&input), input);
+            }
+        }
+
+        let tokens = Lexer::new().tokenize("<?php 1_000 'a\\'b';").unwrap();
+        let tokens = tokens
+            .iter()
+            .filter(|token| token.kind != TokenKind::OpenTag(OpenTagKind::Full))
+            .map(|token| (token.value.to_string(), token.raw.is_some()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            vec![
+                ("1000".to_string(), true),
+                ("'a'b'".to_string(), true),
+                (";".to_string(), false),
+                ("".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_borrowed_tokens() {
+        let input = "<p><?php // a\n$b = \\C\\d('e', 'f\\'g', 1_0); /* h */";
+        let tokens = Lexer::new().tokenize(input).unwrap();
+        let tokens = tokens
+            .iter()
+            .map(|token| (token.value.to_string(), token.value.is_borrowed()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens,
+            [
+                ("<p>", true),
+                ("<?php", true),
+                ("// a", true),
+                ("$b", true),
+                ("=", true),
+                ("\\C\\d", true),
+                ("(", true),
+                ("'e'", true),
+                (",", true),
+                ("'f'g'", false),
+                (",", true),
+                ("10", false),
+                (")", true),
+                (";", true),
+                ("/* h */", true),
+                ("", true),
+            ]
+            .map(|(value, borrowed)| (value.to_string(), borrowed))
+        );
+    }
+
+    #[test]
+    fn test_control_characters() {
+        let input = "a\0b<?php echo \"\0\"; // \0\n$a\x01 = 1;\0\x7f";
+
+        assert_eq!(
+            Lexer::new().tokenize(input),
+            Err(SyntaxError::UnexpectedCharacter(
+                0x01,
+                crate::lexer::token::Span::new(2, 3, 26)
+            ))
+        );
+
+        let kinds = |tokens: Vec<crate::lexer::token::Token>| {
+            tokens
+                .into_iter()
+                .map(|token| token.kind)
+                .collect::<Vec<TokenKind>>()
+        };
+
+        let replaced = Lexer::new()
+            .with_control_characters(ControlCharacters::Replace)
+            .tokenize_with_diagnostics(input)
+            .unwrap();
+        assert_eq!(
+            kinds(replaced.0.clone()),
+            vec![
+                TokenKind::InlineHtml,
+                TokenKind::OpenTag(OpenTagKind::Full),
+                TokenKind::Echo,
+                TokenKind::LiteralString,
+                TokenKind::SemiColon,
+                TokenKind::SingleLineComment,
+                TokenKind::Variable,
+                TokenKind::Equals,
+                TokenKind::LiteralInteger,
+                TokenKind::SemiColon,
+                TokenKind::Eof,
+            ]
+        );
+        assert_eq!(replaced.0[3].value, b"\"\0\"");
+        assert!(replaced.1.is_empty());
+
+        let (tokens, diagnostics) = Lexer::new()
+            .with_control_characters(ControlCharacters::Report)
+            .tokenize_with_diagnostics(input)
+            .unwrap();
+        assert_eq!(tokens, replaced.0);
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| match diagnostic {
+                    SyntaxError::UnexpectedCharacter(byte, span) => (*byte, span.position),
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>(),
+            vec![(0x01, 26), (0x00, 32), (0x7f, 33)]
+        );
+    }
+}
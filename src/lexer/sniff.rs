@@ -0,0 +1,157 @@
+use crate::lexer::error::SyntaxError;
+use crate::lexer::token::OpenTagKind;
+use crate::lexer::token::TokenKind;
+use crate::lexer::Lexer;
+
+/// The number of bytes inspected at the start of the input.
+const WINDOW: usize = 16 * 1024;
+
+/// How likely some bytes are to be PHP code, see [`sniff`].
+///
+/// Levels are ordered, so a scanner can keep the inputs above a given level.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Confidence {
+    // Binary content, or text without an open tag.
+    None,
+    // A short open tag, an open tag without code, or followed by code that doesn't lex.
+    Low,
+    // An open tag, followed by code that lexes.
+    High,
+}
+
+/// Cheaply check whether some bytes are probably PHP code, e.g. to skip files with a `.php`
+/// extension which are not, or to find PHP code hidden in files with another extension.
+///
+/// Only the first 16 KiB of the input are inspected: they must be text, contain a `<?php`
+/// or `<?=` open tag, and the code following the open tag must be made of valid tokens.
+///
+/// ```
+/// use php_parser_rs::lexer::sniff;
+/// use php_parser_rs::lexer::Confidence;
+///
+/// assert_eq!(sniff("<?php echo 'Hello';"), Confidence::High);
+/// assert_eq!(sniff("<p>Hello</p>"), Confidence::None);
+/// assert_eq!(sniff(b"\x7fELF\x02\x01\x01\x00"), Confidence::None);
+/// ```
+pub fn sniff<B: ?Sized + AsRef<[u8]>>(input: &B) -> Confidence {
+    let input = input.as_ref();
+    let truncated = input.len() > WINDOW;
+    let window = if truncated {
+        // cut after the last complete line, to not split a token in most cases.
+        let window = &input[..WINDOW];
+        match window.iter().rposition(|byte| *byte == b'\n') {
+            Some(position) => &window[..=position],
+            None => window,
+        }
+    } else {
+        input
+    };
+
+    if is_binary(window) {
+        return Confidence::None;
+    }
+
+    let tokens = match Lexer::new().tokenize(window) {
+        Ok(tokens) => tokens,
+        // a string, comment, or heredoc running past the window.
+        Err(SyntaxError::UnexpectedEndOfFile(_)) if truncated => {
+            return if has_open_tag(window) {
+                Confidence::High
+            } else {
+                Confidence::None
+            };
+        }
+        Err(_) => {
+            return if has_open_tag(window) {
+                Confidence::Low
+            } else {
+                Confidence::None
+            };
+        }
+    };
+
+    let mut confidence = Confidence::None;
+    for (index, token) in tokens.iter().enumerate() {
+        match token.kind {
+            TokenKind::OpenTag(OpenTagKind::Full | OpenTagKind::Echo) => {
+                let code = tokens[index + 1..].iter().any(|token| {
+                    !matches!(
+                        token.kind,
+                        TokenKind::CloseTag | TokenKind::InlineHtml | TokenKind::Eof
+                    )
+                });
+
+                if code {
+                    return Confidence::High;
+                }
+
+                confidence = Confidence::Low;
+            }
+            TokenKind::OpenTag(OpenTagKind::Short) => confidence = confidence.max(Confidence::Low),
+            _ => {}
+        }
+    }
+
+    confidence
+}
+
+/// Whether the given bytes contain a null byte, or are mostly made of control characters,
+/// like executables, images, and archives.
+fn is_binary(bytes: &[u8]) -> bool {
+    if bytes.contains(&0) {
+        return true;
+    }
+
+    let control = bytes
+        .iter()
+        .filter(|byte| byte.is_ascii_control() && !byte.is_ascii_whitespace())
+        .count();
+
+    control * 10 > bytes.len()
+}
+
+fn has_open_tag(bytes: &[u8]) -> bool {
+    bytes
+        .windows(5)
+        .any(|tag| tag.eq_ignore_ascii_case(b"<?php"))
+        || bytes.windows(3).any(|tag| tag == b"<?=")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sniff;
+    use super::Confidence;
+    use super::WINDOW;
+
+    #[test]
+    fn test_sniff() {
+        assert_eq!(sniff("<?php\n\nnamespace App;\n"), Confidence::High);
+        assert_eq!(sniff("#!/usr/bin/env php\n<?php echo 1;"), Confidence::High);
+        assert_eq!(sniff("<html><?= $title ?></html>"), Confidence::High);
+        assert_eq!(sniff("<?PHP\nexit;"), Confidence::High);
+
+        assert_eq!(sniff("<?xml version=\"1.0\"?><a/>"), Confidence::Low);
+        assert_eq!(sniff("<?php ?>"), Confidence::Low);
+        assert_eq!(sniff("<?php"), Confidence::Low);
+        assert_eq!(sniff("Start with `<?php` then \x01"), Confidence::Low);
+
+        assert_eq!(sniff(""), Confidence::None);
+        assert_eq!(sniff("<p>Hello</p>"), Confidence::None);
+        assert_eq!(sniff(b"<?php \x00\x01\x02"), Confidence::None);
+        assert_eq!(sniff(b"\x89PNG\r\n\x1a\n\x01\x02\x03"), Confidence::None);
+
+        assert!(Confidence::High > Confidence::Low);
+    }
+
+    #[test]
+    fn test_sniff_only_inspects_the_start() {
+        let mut input = String::from("<?php\n$a = '");
+        input.push_str(&"a\n".repeat(WINDOW));
+        input.push_str("';\n");
+        assert_eq!(sniff(&input), Confidence::High);
+
+        let mut input = "<p></p>\n".repeat(WINDOW);
+        input.push_str("<?php echo 1;");
+        assert_eq!(sniff(&input), Confidence::None);
+    }
+}
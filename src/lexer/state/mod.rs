@@ -4,8 +4,6 @@ use crate::lexer::byte_string::ByteString;
 use crate::lexer::error::SyntaxError;
 use crate::lexer::error::SyntaxResult;
 use crate::lexer::state::source::Source;
-use crate::lexer::token::DocStringIndentationAmount;
-use crate::lexer::token::DocStringIndentationKind;
 use crate::lexer::token::DocStringKind;
 
 pub mod source;
@@ -17,12 +15,7 @@ pub enum StackFrame {
     Halted,
     DoubleQuote,
     ShellExec,
-    DocString(
-        DocStringKind,
-        ByteString,
-        DocStringIndentationKind,
-        DocStringIndentationAmount,
-    ),
+    DocString(DocStringKind, ByteString),
     LookingForVarname,
     LookingForProperty,
     VarOffset,
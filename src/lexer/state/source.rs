@@ -9,7 +9,6 @@ pub struct Source<'a> {
 
 impl<'a> Source<'a> {
     pub fn new(input: &'a [u8]) -> Self {
-        let input = input;
         let length = input.len();
 
         Self {
@@ -78,6 +77,10 @@ impl<'a> Source<'a> {
         })..]
     }
 
+    pub fn slice(&self, from: usize, until: usize) -> &'a [u8] {
+        &self.input[from.min(self.length)..until.min(self.length)]
+    }
+
     pub fn at(&self, search: &[u8], len: usize) -> bool {
         self.read(len) == search
     }
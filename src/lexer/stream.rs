@@ -57,15 +57,15 @@ use crate::parser::ast::comments::CommentGroup;
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TokenStream<'a> {
-    tokens: &'a [Token],
+    tokens: &'a [Token<'a>],
     length: usize,
-    comments: Vec<&'a Token>,
+    comments: Vec<&'a Token<'a>>,
     cursor: usize,
 }
 
 /// Token stream.
 impl<'a> TokenStream<'a> {
-    pub fn new(tokens: &'a [Token]) -> TokenStream {
+    pub fn new(tokens: &'a [Token<'a>]) -> TokenStream<'a> {
         let length = tokens.len();
 
         let mut stream = TokenStream {
@@ -89,7 +89,7 @@ impl<'a> TokenStream<'a> {
     }
 
     /// Get current token.
-    pub const fn current(&self) -> &'a Token {
+    pub const fn current(&self) -> &'a Token<'a> {
         let position = if self.cursor >= self.length {
             self.length - 1
         } else {
@@ -100,7 +100,7 @@ impl<'a> TokenStream<'a> {
     }
 
     /// Get previous token.
-    pub const fn previous(&self) -> &'a Token {
+    pub const fn previous(&self) -> &'a Token<'a> {
         let position = if self.cursor == 0 { 0 } else { self.cursor - 1 };
 
         &self.tokens[position]
@@ -109,14 +109,14 @@ impl<'a> TokenStream<'a> {
     /// Peek next token.
     ///
     /// All comments are skipped.
-    pub const fn peek(&self) -> &'a Token {
+    pub const fn peek(&self) -> &'a Token<'a> {
         self.peek_nth(1)
     }
 
     /// Peek nth+1 token.
     ///
     /// All comments are skipped.
-    pub const fn lookahead(&self, n: usize) -> &'a Token {
+    pub const fn lookahead(&self, n: usize) -> &'a Token<'a> {
         self.peek_nth(n + 1)
     }
 
@@ -124,7 +124,7 @@ impl<'a> TokenStream<'a> {
     ///
     /// All comments are skipped.
     #[inline(always)]
-    const fn peek_nth(&self, n: usize) -> &'a Token {
+    const fn peek_nth(&self, n: usize) -> &'a Token<'a> {
         let mut cursor = self.cursor + 1;
         let mut target = 1;
         loop {
@@ -154,6 +154,23 @@ impl<'a> TokenStream<'a> {
         }
     }
 
+    /// Get the position of the cursor, see [`TokenStream::consumed_since`].
+    pub const fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Get all tokens of the stream, comments included.
+    pub fn tokens(&self) -> &'a [Token<'a>] {
+        self.tokens
+    }
+
+    /// Get the tokens consumed since the cursor was at the given position, comments included.
+    pub fn consumed_since(&self, cursor: usize) -> &'a [Token<'a>] {
+        let end = self.cursor.min(self.length);
+
+        &self.tokens[cursor.min(end)..end]
+    }
+
     /// Check if current token is EOF.
     pub fn is_eof(&self) -> bool {
         if self.cursor >= self.length {
@@ -178,37 +195,41 @@ impl<'a> TokenStream<'a> {
                         kind: TokenKind::SingleLineComment,
                         span,
                         value,
+                        ..
                     } => Comment {
                         span: *span,
                         format: CommentFormat::SingleLine,
-                        content: value.clone(),
+                        content: value.into(),
                     },
                     Token {
                         kind: TokenKind::MultiLineComment,
                         span,
                         value,
+                        ..
                     } => Comment {
                         span: *span,
                         format: CommentFormat::MultiLine,
-                        content: value.clone(),
+                        content: value.into(),
                     },
                     Token {
                         kind: TokenKind::HashMarkComment,
                         span,
                         value,
+                        ..
                     } => Comment {
                         span: *span,
                         format: CommentFormat::HashMark,
-                        content: value.clone(),
+                        content: value.into(),
                     },
                     Token {
                         kind: TokenKind::DocumentComment,
                         span,
                         value,
+                        ..
                     } => Comment {
                         span: *span,
                         format: CommentFormat::Document,
-                        content: value.clone(),
+                        content: value.into(),
                     },
                     _ => unreachable!(),
                 })
@@ -246,8 +267,8 @@ impl<'a> Default for TokenStream<'a> {
     }
 }
 
-impl<'a> From<&'a Vec<Token>> for TokenStream<'a> {
-    fn from(tokens: &'a Vec<Token>) -> Self {
+impl<'a> From<&'a Vec<Token<'a>>> for TokenStream<'a> {
+    fn from(tokens: &'a Vec<Token<'a>>) -> Self {
         Self::new(tokens.as_slice())
     }
 }
@@ -4,14 +4,19 @@ use serde::Serialize;
 
 use std::fmt::Display;
 
-use crate::lexer::byte_string::ByteString;
+use crate::lexer::byte_string::ByteStr;
+use crate::parser::options::PhpVersion;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+#[derive(PartialEq, Eq, Clone, Copy, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct Span {
     pub line: usize,
     pub column: usize,
     pub position: usize,
+    /// Whether the span belongs to a token invented by the parser, e.g. a semicolon inserted
+    /// while recovering from an error, rather than written in the source.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub synthetic: bool,
 }
 
 impl Span {
@@ -20,8 +25,56 @@ impl Span {
             line,
             column,
             position,
+            synthetic: false,
         }
     }
+
+    /// A zero-width span for a synthetic token, anchored at the position it is inserted at.
+    pub fn synthetic(line: usize, column: usize, position: usize) -> Self {
+        Self {
+            synthetic: true,
+            ..Self::new(line, column, position)
+        }
+    }
+
+    pub const fn is_synthetic(&self) -> bool {
+        self.synthetic
+    }
+
+    /// The span following the given text, when the text starts at this span.
+    pub fn advance(self, text: &[u8]) -> Self {
+        let (line, column) = match text.iter().rposition(|byte| *byte == b'\n') {
+            Some(newline) => (
+                self.line + text.iter().filter(|byte| **byte == b'\n').count(),
+                text.len() - newline,
+            ),
+            None => (self.line, self.column + text.len()),
+        };
+
+        Self {
+            line,
+            column,
+            position: self.position + text.len(),
+            ..self
+        }
+    }
+}
+
+impl std::fmt::Debug for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("Span");
+        debug
+            .field("line", &self.line)
+            .field("column", &self.column)
+            .field("position", &self.position);
+
+        // Only shown when set, to keep the output for parsed source unchanged.
+        if self.synthetic {
+            debug.field("synthetic", &self.synthetic);
+        }
+
+        debug.finish()
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -70,217 +123,366 @@ impl From<DocStringIndentationKind> for u8 {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
-#[serde(rename_all = "snake_case")]
-pub enum TokenKind {
-    Die,
+/// Define [`TokenKind`] from a table of its variants, along with their metadata.
+///
+/// Each row is a variant, its display name, the name of the equivalent token in PHP's
+/// tokenizer, if any, and whether it is a keyword, which is lexed from an identifier
+/// matching its display name case-insensitively. A `reserved(version)` keyword can't be
+/// used as a name as of the given version, whereas a `keyword` is contextual.
+macro_rules! token_kinds {
+    (
+        $(
+            $(#[$meta:meta])*
+            $variant:ident $( ( $($field:ty),+ ) )? = $display:literal
+                $( => $php:ident )?
+                $( , $keyword:ident $( ( $since:ident ) )? )?;
+        )*
+    ) => {
+        #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+        #[serde(rename_all = "snake_case")]
+        pub enum TokenKind {
+            $(
+                $(#[$meta])*
+                $variant $( ( $($field),+ ) )?,
+            )*
+        }
+
+        impl TokenKind {
+            /// The keyword for the given identifier, if any.
+            pub fn keyword(identifier: &[u8]) -> Option<TokenKind> {
+                $( token_kinds!(@lookup identifier, $variant, $display $(, $keyword)?); )*
+
+                None
+            }
+
+            /// Whether the token is a keyword, see [`TokenKind::keyword`].
+            pub fn is_keyword(&self) -> bool {
+                match self {
+                    $( Self::$variant { .. } => token_kinds!(@keyword $($keyword)?), )*
+                }
+            }
+
+            fn reserved_since(&self) -> Option<PhpVersion> {
+                match self {
+                    $( Self::$variant { .. } => token_kinds!(@since $($keyword $($since)?)?), )*
+                }
+            }
+
+            fn table_display(&self) -> &'static str {
+                match self {
+                    $( Self::$variant { .. } => $display, )*
+                }
+            }
+
+            fn table_php_name(&self) -> Option<&'static str> {
+                match self {
+                    $( Self::$variant { .. } => token_kinds!(@php $($php)?), )*
+                }
+            }
+        }
+    };
+    (@lookup $identifier:ident, $variant:ident, $display:literal) => {};
+    (@lookup $identifier:ident, $variant:ident, $display:literal, $keyword:ident) => {
+        if $identifier.eq_ignore_ascii_case($display.as_bytes()) {
+            return Some(Self::$variant);
+        }
+    };
+    (@keyword) => { false };
+    (@keyword keyword) => { true };
+    (@keyword reserved) => { true };
+    (@since) => { None };
+    (@since keyword) => { None };
+    (@since reserved $since:ident) => { Some(PhpVersion::$since) };
+    (@php) => { None };
+    (@php $php:ident) => { Some(stringify!($php)) };
+}
+
+token_kinds! {
+    Die = "die" => T_EXIT, reserved(Php70);
     // Can't use `Self` as a name here, so suffixing with an underscore.
-    Self_,
-    Parent,
-    Backtick,
-    StartDocString(DocStringKind),
-    EndDocString(DocStringIndentationKind, usize),
-    From,
-    Print,
-    Dollar,
-    HaltCompiler,
-    Readonly,
-    Global,
-    Abstract,
-    Ampersand,
-    AmpersandEquals,
-    And,
-    AndEquals,
-    Array,
-    ArrayCast,
-    Arrow,
-    QuestionArrow,
-    At,
-    As,
-    Asterisk,
-    Attribute,
-    Bang,
-    BangEquals,
-    AngledLeftRight,
-    BangDoubleEquals,
-    Spaceship,
-    BoolCast,
-    BooleanCast,
-    BooleanAnd,
-    BooleanOr,
-    Break,
-    Callable,
-    Caret,
-    CaretEquals,
-    Case,
-    Catch,
-    Class,
-    ClassConstant,
-    TraitConstant,
-    FunctionConstant,
-    MethodConstant,
-    LineConstant,
-    FileConstant,
-    Clone,
-    MinusEquals,
-    CloseTag,
-    DoubleQuestion,
-    DoubleQuestionEquals,
-    AsteriskEquals,
-    Colon,
-    Comma,
-    SingleLineComment,
-    HashMarkComment,
-    MultiLineComment,
-    DocumentComment,
-    Const,
-    LiteralString,
-    Continue,
-    CurlyOpen,
-    Declare,
-    Decrement,
-    Default,
-    DirConstant,
-    DivEquals,
-    Do,
-    DollarLeftBrace,
-    Dot,
-    DotEquals,
-    DoubleArrow,
-    DoubleCast,
-    RealCast,
-    FloatCast,
-    DoubleColon,
-    DoubleEquals,
-    DoubleQuote,
-    Echo,
-    Ellipsis,
-    Else,
-    ElseIf,
-    Empty,
-    EndDeclare,
-    EndFor,
-    EndForeach,
-    EndIf,
-    EndSwitch,
-    EndWhile,
-    Enum,
-    Eof,
-    Equals,
-    Extends,
-    False,
-    Final,
-    Finally,
-    LiteralFloat,
-    Fn,
-    For,
-    Foreach,
-    FullyQualifiedIdentifier,
-    Function,
-    Goto,
-    GreaterThan,
-    GreaterThanEquals,
-    Identifier,
-    If,
-    Implements,
-    Include,
-    IncludeOnce,
-    Increment,
-    InlineHtml,
-    Instanceof,
-    Insteadof,
-    Eval,
-    Exit,
-    Unset,
-    Isset,
-    List,
-    LiteralInteger,
-    IntCast,
-    IntegerCast,
-    Interface,
-    LeftBrace,
-    LeftBracket,
-    LeftParen,
-    LeftShift,
-    LeftShiftEquals,
-    RightShift,
-    RightShiftEquals,
-    LessThan,
-    LessThanEquals,
-    Match,
-    Minus,
-    Namespace,
-    NamespaceSeparator,
-    NamespaceConstant,
-    CompilerHaltOffsetConstant,
-    New,
-    Null,
-    ObjectCast,
-    UnsetCast,
-    OpenTag(OpenTagKind),
-    Percent,
-    PercentEquals,
-    Pipe,
-    PipeEquals,
-    Plus,
-    PlusEquals,
-    Pow,
-    PowEquals,
-    Private,
-    Protected,
-    Public,
-    QualifiedIdentifier,
-    Question,
-    QuestionColon,
-    Require,
-    RequireOnce,
-    Return,
-    RightBrace,
-    RightBracket,
-    RightParen,
-    SemiColon,
-    Slash,
-    SlashEquals,
-    Static,
-    StringCast,
-    BinaryCast,
-    StringPart,
-    Switch,
-    Throw,
-    Trait,
-    TripleEquals,
-    True,
-    Try,
-    Use,
-    Var,
-    Variable,
-    Yield,
-    While,
-    BitwiseNot,
-    LogicalAnd,
-    LogicalOr,
-    LogicalXor,
+    Self_ = "self" => T_STRING, keyword;
+    Parent = "parent" => T_STRING, keyword;
+    Backtick = "`";
+    StartDocString(DocStringKind) = "<<<" => T_START_HEREDOC;
+    EndDocString(DocStringIndentationKind, usize) = "EndDocString" => T_END_HEREDOC;
+    From = "from" => T_STRING, keyword;
+    Print = "print" => T_PRINT, reserved(Php70);
+    Dollar = "$";
+    HaltCompiler = "__halt_compiler" => T_HALT_COMPILER, reserved(Php70);
+    Readonly = "readonly" => T_READONLY, reserved(Php81);
+    Global = "global" => T_GLOBAL, reserved(Php70);
+    Abstract = "abstract" => T_ABSTRACT, reserved(Php70);
+    Ampersand = "&";
+    AmpersandEquals = "&=" => T_AND_EQUAL;
+    And = "&&" => T_BOOLEAN_AND;
+    AndEquals = "&=" => T_AND_EQUAL;
+    Array = "array" => T_ARRAY, reserved(Php70);
+    ArrayCast = "(array)" => T_ARRAY_CAST;
+    Arrow = "->" => T_OBJECT_OPERATOR;
+    QuestionArrow = "?->" => T_NULLSAFE_OBJECT_OPERATOR;
+    At = "@";
+    As = "as" => T_AS, reserved(Php70);
+    Asterisk = "*";
+    Attribute = "#[" => T_ATTRIBUTE;
+    Bang = "!";
+    BangEquals = "!=" => T_IS_NOT_EQUAL;
+    AngledLeftRight = "<>" => T_IS_NOT_EQUAL;
+    BangDoubleEquals = "!==" => T_IS_NOT_IDENTICAL;
+    Spaceship = "<=>" => T_SPACESHIP;
+    BoolCast = "(bool)" => T_BOOL_CAST;
+    BooleanCast = "(boolean)" => T_BOOL_CAST;
+    BooleanAnd = "&&" => T_BOOLEAN_AND;
+    BooleanOr = "||" => T_BOOLEAN_OR;
+    Break = "break" => T_BREAK, reserved(Php70);
+    Callable = "callable" => T_CALLABLE;
+    Caret = "^";
+    CaretEquals = "^=" => T_XOR_EQUAL;
+    Case = "case" => T_CASE, reserved(Php70);
+    Catch = "catch" => T_CATCH, reserved(Php70);
+    Class = "class" => T_CLASS, reserved(Php70);
+    ClassConstant = "__CLASS__" => T_CLASS_C, reserved(Php70);
+    TraitConstant = "__TRAIT__" => T_TRAIT_C, reserved(Php70);
+    FunctionConstant = "__FUNCTION__" => T_FUNC_C, reserved(Php70);
+    MethodConstant = "__METHOD__" => T_METHOD_C, reserved(Php70);
+    LineConstant = "__LINE__" => T_LINE, reserved(Php70);
+    FileConstant = "__FILE__" => T_FILE, reserved(Php70);
+    Clone = "clone" => T_CLONE, reserved(Php70);
+    MinusEquals = "-=" => T_MINUS_EQUAL;
+    CloseTag = "?>" => T_CLOSE_TAG;
+    DoubleQuestion = "??" => T_COALESCE;
+    DoubleQuestionEquals = "??=" => T_COALESCE_EQUAL;
+    AsteriskEquals = "*=" => T_MUL_EQUAL;
+    Colon = ":";
+    Comma = ",";
+    SingleLineComment = "SingleLineComment" => T_COMMENT;
+    HashMarkComment = "HashMarkComment" => T_COMMENT;
+    MultiLineComment = "MultiLineComment" => T_COMMENT;
+    DocumentComment = "DocumentComment" => T_DOC_COMMENT;
+    Const = "const" => T_CONST, reserved(Php70);
+    LiteralString = "LiteralString" => T_CONSTANT_ENCAPSED_STRING;
+    Continue = "continue" => T_CONTINUE, reserved(Php70);
+    CurlyOpen = "{$" => T_CURLY_OPEN;
+    Declare = "declare" => T_DECLARE, reserved(Php70);
+    Decrement = "--" => T_DEC;
+    Default = "default" => T_DEFAULT, reserved(Php70);
+    DirConstant = "__DIR__" => T_DIR, reserved(Php70);
+    DivEquals = "/=" => T_DIV_EQUAL;
+    Do = "do" => T_DO, reserved(Php70);
+    DollarLeftBrace = "${" => T_DOLLAR_OPEN_CURLY_BRACES;
+    Dot = ".";
+    DotEquals = ".=" => T_CONCAT_EQUAL;
+    DoubleArrow = "=>" => T_DOUBLE_ARROW;
+    DoubleCast = "(double)" => T_DOUBLE_CAST;
+    RealCast = "(real)" => T_DOUBLE_CAST;
+    FloatCast = "(float)" => T_DOUBLE_CAST;
+    DoubleColon = "::" => T_DOUBLE_COLON;
+    DoubleEquals = "==" => T_IS_EQUAL;
+    DoubleQuote = "\"";
+    Echo = "echo" => T_ECHO, reserved(Php70);
+    Ellipsis = "..." => T_ELLIPSIS;
+    Else = "else" => T_ELSE, reserved(Php70);
+    ElseIf = "elseif" => T_ELSEIF, reserved(Php70);
+    Empty = "empty" => T_EMPTY, reserved(Php70);
+    EndDeclare = "enddeclare" => T_ENDDECLARE, reserved(Php70);
+    EndFor = "endfor" => T_ENDFOR, reserved(Php70);
+    EndForeach = "endforeach" => T_ENDFOREACH, reserved(Php70);
+    EndIf = "endif" => T_ENDIF, reserved(Php70);
+    EndSwitch = "endswitch" => T_ENDSWITCH, reserved(Php70);
+    EndWhile = "endwhile" => T_ENDWHILE, reserved(Php70);
+    Enum = "enum" => T_ENUM, keyword;
+    Eof = "[end of file]";
+    Equals = "=";
+    Extends = "extends" => T_EXTENDS, reserved(Php70);
+    False = "false" => T_STRING, keyword;
+    Final = "final" => T_FINAL, reserved(Php70);
+    Finally = "finally" => T_FINALLY, reserved(Php70);
+    LiteralFloat = "float literal" => T_DNUMBER;
+    Fn = "fn" => T_FN, reserved(Php74);
+    For = "for" => T_FOR, reserved(Php70);
+    Foreach = "foreach" => T_FOREACH, reserved(Php70);
+    ForeignChunk = "ForeignChunk";
+    FullyQualifiedIdentifier = "FullyQualifiedIdentifier" => T_NAME_FULLY_QUALIFIED;
+    Function = "function" => T_FUNCTION, reserved(Php70);
+    Goto = "goto" => T_GOTO, reserved(Php70);
+    GreaterThan = ">";
+    GreaterThanEquals = ">=" => T_IS_GREATER_OR_EQUAL;
+    Identifier = "Identifier" => T_STRING;
+    If = "if" => T_IF, reserved(Php70);
+    Implements = "implements" => T_IMPLEMENTS, reserved(Php70);
+    Include = "include" => T_INCLUDE, reserved(Php70);
+    IncludeOnce = "include_once" => T_INCLUDE_ONCE, reserved(Php70);
+    Increment = "++" => T_INC;
+    InlineHtml = "InlineHtml" => T_INLINE_HTML;
+    Instanceof = "instanceof" => T_INSTANCEOF, reserved(Php70);
+    Insteadof = "insteadof" => T_INSTEADOF, reserved(Php70);
+    Eval = "eval" => T_EVAL, reserved(Php70);
+    Exit = "exit" => T_EXIT, reserved(Php70);
+    Unset = "unset" => T_UNSET, reserved(Php70);
+    Isset = "isset" => T_ISSET, reserved(Php70);
+    List = "list" => T_LIST, reserved(Php70);
+    LiteralInteger = "integer literal" => T_LNUMBER;
+    IntCast = "(int)" => T_INT_CAST;
+    IntegerCast = "(integer)" => T_INT_CAST;
+    Interface = "interface" => T_INTERFACE, reserved(Php70);
+    LeftBrace = "{";
+    LeftBracket = "[";
+    LeftParen = "(";
+    LeftShift = "<<" => T_SL;
+    LeftShiftEquals = "<<=" => T_SL_EQUAL;
+    RightShift = ">>" => T_SR;
+    RightShiftEquals = ">>=" => T_SR_EQUAL;
+    LessThan = "<";
+    LessThanEquals = "<=" => T_IS_SMALLER_OR_EQUAL;
+    Match = "match" => T_MATCH, reserved(Php80);
+    Minus = "-";
+    Namespace = "namespace" => T_NAMESPACE, reserved(Php70);
+    NamespaceSeparator = "\\" => T_NS_SEPARATOR;
+    NamespaceConstant = "__NAMESPACE__" => T_NS_C, reserved(Php70);
+    CompilerHaltOffsetConstant = "__COMPILER_HALT_OFFSET__" => T_STRING, keyword;
+    New = "new" => T_NEW, reserved(Php70);
+    Null = "null" => T_STRING, keyword;
+    ObjectCast = "(object)" => T_OBJECT_CAST;
+    UnsetCast = "(unset)" => T_UNSET_CAST;
+    OpenTag(OpenTagKind) = "<?php" => T_OPEN_TAG;
+    Percent = "%";
+    PercentEquals = "%=" => T_MOD_EQUAL;
+    Pipe = "|";
+    PipeEquals = "|=" => T_OR_EQUAL;
+    PipeGreaterThan = "|>" => T_PIPE;
+    Plus = "+";
+    PlusEquals = "+=" => T_PLUS_EQUAL;
+    Pow = "**" => T_POW;
+    PowEquals = "**=" => T_POW_EQUAL;
+    Private = "private" => T_PRIVATE, reserved(Php70);
+    /// `private(set)`, made of several tokens combined when collecting modifiers.
+    PrivateSet = "private(set)" => T_PRIVATE_SET;
+    Protected = "protected" => T_PROTECTED, reserved(Php70);
+    /// `protected(set)`, see [`TokenKind::PrivateSet`].
+    ProtectedSet = "protected(set)" => T_PROTECTED_SET;
+    Public = "public" => T_PUBLIC, reserved(Php70);
+    /// `public(set)`, see [`TokenKind::PrivateSet`].
+    PublicSet = "public(set)" => T_PUBLIC_SET;
+    QualifiedIdentifier = "QualifiedIdentifier" => T_NAME_QUALIFIED;
+    Question = "?";
+    QuestionColon = "?:";
+    Require = "require" => T_REQUIRE, reserved(Php70);
+    RequireOnce = "require_once" => T_REQUIRE_ONCE, reserved(Php70);
+    Return = "return" => T_RETURN, reserved(Php70);
+    RightBrace = "}";
+    RightBracket = "]";
+    RightParen = ")";
+    SemiColon = ";";
+    Slash = "/";
+    SlashEquals = "/=" => T_DIV_EQUAL;
+    Static = "static" => T_STATIC, reserved(Php70);
+    StringCast = "(string)" => T_STRING_CAST;
+    BinaryCast = "(binary)" => T_STRING_CAST;
+    StringPart = "StringPart" => T_ENCAPSED_AND_WHITESPACE;
+    Switch = "switch" => T_SWITCH, reserved(Php70);
+    Throw = "throw" => T_THROW, reserved(Php70);
+    Trait = "trait" => T_TRAIT, reserved(Php70);
+    TripleEquals = "===" => T_IS_IDENTICAL;
+    True = "true" => T_STRING, keyword;
+    Try = "try" => T_TRY, reserved(Php70);
+    Use = "use" => T_USE, reserved(Php70);
+    Var = "var" => T_VAR, reserved(Php70);
+    Variable = "Variable" => T_VARIABLE;
+    Yield = "yield" => T_YIELD, reserved(Php70);
+    While = "while" => T_WHILE, reserved(Php70);
+    BitwiseNot = "~";
+    LogicalAnd = "and" => T_LOGICAL_AND, reserved(Php70);
+    LogicalOr = "or" => T_LOGICAL_OR, reserved(Php70);
+    LogicalXor = "xor" => T_LOGICAL_XOR, reserved(Php70);
+}
+
+impl TokenKind {
+    /// The name of the token in messages, e.g. `=>` or `function`, or the name of the
+    /// variant for tokens which don't have a fixed text, e.g. `Variable`.
+    pub fn display(&self) -> &'static str {
+        match self {
+            Self::OpenTag(OpenTagKind::Short) => "<?",
+            Self::OpenTag(OpenTagKind::Echo) => "<?=",
+            _ => self.table_display(),
+        }
+    }
+
+    /// The name of the equivalent token in PHP's tokenizer, e.g. `T_DOUBLE_ARROW`, as
+    /// returned by `token_name()`. Tokens which PHP's tokenizer returns as single characters,
+    /// such as `;`, don't have one.
+    pub fn php_name(&self) -> Option<&'static str> {
+        match self {
+            Self::OpenTag(OpenTagKind::Echo) => Some("T_OPEN_TAG_WITH_ECHO"),
+            _ => self.table_php_name(),
+        }
+    }
+
+    /// Whether the token is a keyword which can't be used as a class, function, or constant
+    /// name in the given version of PHP. Contextual keywords, such as `enum`, are not reserved.
+    pub fn is_reserved_in(&self, version: PhpVersion) -> bool {
+        self.reserved_since().is_some_and(|since| version >= since)
+    }
 }
 
+/// A token of the input, whose value and source text borrow from the input unless they
+/// differ from it, see [`Token::into_owned`] to keep a token past the input.
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
-pub struct Token {
+pub struct Token<'a> {
     pub kind: TokenKind,
     pub span: Span,
-    pub value: ByteString,
+    pub value: ByteStr<'a>,
+    /// The source text of the token, when it differs from the value, see [`Token::raw`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<ByteStr<'a>>,
 }
 
-impl Default for Token {
+impl Default for Token<'_> {
     fn default() -> Self {
         Self {
             kind: TokenKind::Eof,
             span: Span::new(0, 0, 0),
-            value: ByteString::default(),
+            value: ByteStr::default(),
+            raw: None,
         }
     }
 }
 
-impl Display for Token {
+impl<'a> Token<'a> {
+    /// A token invented by the parser, which doesn't appear in the source: it has no value,
+    /// and a zero-width span anchored at the given span.
+    pub fn synthetic(kind: TokenKind, at: Span) -> Self {
+        Self {
+            kind,
+            span: Span::synthetic(at.line, at.column, at.position),
+            value: ByteStr::default(),
+            raw: None,
+        }
+    }
+
+    pub const fn is_synthetic(&self) -> bool {
+        self.span.is_synthetic()
+    }
+
+    /// The exact source text of the token, which may differ from its value, e.g. a string with
+    /// escape sequences, or a number with separators. Synthetic tokens have no source text.
+    ///
+    /// The source text of all tokens, and the whitespace between them, reproduces the input.
+    pub fn raw(&self) -> &[u8] {
+        self.raw.as_deref().unwrap_or(&self.value)
+    }
+
+    /// The token, copying its value and source text when they borrow from the input.
+    pub fn into_owned(self) -> Token<'static> {
+        Token {
+            kind: self.kind,
+            span: self.span,
+            value: self.value.into_owned(),
+            raw: self.raw.map(ByteStr::into_owned),
+        }
+    }
+}
+
+impl Display for Token<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.value)
     }
@@ -288,201 +490,55 @@ impl Display for Token {
 
 impl Display for TokenKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let s = match self {
-            Self::CompilerHaltOffsetConstant => "__COMPILER_HALT_OFFSET__",
-            Self::Die => "die",
-            Self::Self_ => "self",
-            Self::Parent => "parent",
-            Self::Backtick => "`",
-            Self::StartDocString(_) => todo!(),
-            Self::EndDocString(..) => todo!(),
-            Self::BangEquals => "!=",
-            Self::From => "from",
-            Self::Print => "print",
-            Self::BitwiseNot => "~",
-            Self::Dollar => "$",
-            Self::HaltCompiler => "__halt_compiler",
-            Self::Readonly => "readonly",
-            Self::AsteriskEquals => "*=",
-            Self::ObjectCast => "(object)",
-            Self::UnsetCast => "(unset)",
-            Self::Abstract => "abstract",
-            Self::Ampersand => "&",
-            Self::And => "&&",
-            Self::AndEquals => "&=",
-            Self::Arrow => "->",
-            Self::QuestionArrow => "?->",
-            Self::Array => "array",
-            Self::ArrayCast => "(array)",
-            Self::As => "as",
-            Self::Asterisk => "*",
-            Self::Attribute => "#[",
-            Self::Bang => "!",
-            Self::BoolCast => "(bool)",
-            Self::BooleanCast => "(boolean)",
-            Self::BooleanAnd => "&&",
-            Self::BooleanOr => "||",
-            Self::Break => "break",
-            Self::Callable => "callable",
-            Self::Caret => "^",
-            Self::CaretEquals => "^=",
-            Self::Case => "case",
-            Self::Catch => "catch",
-            Self::Class => "class",
-            Self::ClassConstant => "__CLASS__",
-            Self::Clone => "clone",
-            Self::CloseTag => "?>",
-            Self::DoubleQuestion => "??",
-            Self::DoubleQuestionEquals => "??=",
-            Self::Colon => ":",
-            Self::Comma => ",",
-            Self::Const => "const",
-            Self::Continue => "continue",
-            Self::IntCast => "(int)",
-            Self::IntegerCast => "(integer)",
-            Self::CurlyOpen => "{$",
-            Self::Declare => "declare",
-            Self::Decrement => "--",
-            Self::Default => "default",
-            Self::DirConstant => "__DIR__",
-            Self::DivEquals => "/=",
-            Self::Do => "do",
-            Self::Dot => ".",
-            Self::DotEquals => ".=",
-            Self::DoubleArrow => "=>",
-            Self::DoubleCast => "(double)",
-            Self::RealCast => "(real)",
-            Self::FloatCast => "(float)",
-            Self::DoubleColon => "::",
-            Self::DoubleEquals => "==",
-            Self::Echo => "echo",
-            Self::Ellipsis => "...",
-            Self::Else => "else",
-            Self::ElseIf => "elseif",
-            Self::Empty => "empty",
-            Self::EndDeclare => "enddeclare",
-            Self::EndFor => "endfor",
-            Self::EndForeach => "endforeach",
-            Self::EndIf => "endif",
-            Self::EndSwitch => "endswitch",
-            Self::EndWhile => "endwhile",
-            Self::Enum => "enum",
-            Self::Eof => "[end of file]",
-            Self::Equals => "=",
-            Self::Extends => "extends",
-            Self::False => "false",
-            Self::Final => "final",
-            Self::Finally => "finally",
-            Self::LiteralFloat => return write!(f, "float literal"),
-            Self::Fn => "fn",
-            Self::For => "for",
-            Self::Function => "function",
-            Self::Goto => "goto",
-            Self::GreaterThan => ">",
-            Self::GreaterThanEquals => ">=",
-            Self::If => "if",
-            Self::Implements => "implements",
-            Self::Increment => "++",
-            Self::InlineHtml => "InlineHtml",
-            Self::LiteralInteger => return write!(f, "integer literal"),
-            Self::LeftBrace => "{",
-            Self::LeftBracket => "[",
-            Self::LeftParen => "(",
-            Self::LeftShift => "<<",
-            Self::LeftShiftEquals => "<<=",
-            Self::RightShift => ">>",
-            Self::RightShiftEquals => ">>=",
-            Self::LessThan => "<",
-            Self::LessThanEquals => "<=",
-            Self::Match => "match",
-            Self::Minus => "-",
-            Self::MinusEquals => "-=",
-            Self::Namespace => "namespace",
-            Self::NamespaceSeparator => "\\",
-            Self::New => "new",
-            Self::Null => "null",
-            Self::OpenTag(kind) => match kind {
-                OpenTagKind::Full => "<?php",
-                OpenTagKind::Short => "<?",
-                OpenTagKind::Echo => "<?=",
-            },
-            Self::Percent => "%",
-            Self::PercentEquals => "%=",
-            Self::Pipe => "|",
-            Self::PipeEquals => "|=",
-            Self::Plus => "+",
-            Self::PlusEquals => "+=",
-            Self::Pow => "**",
-            Self::Private => "private",
-            Self::Protected => "protected",
-            Self::Public => "public",
-            Self::Question => "?",
-            Self::QuestionColon => "?:",
-            Self::Require => "require",
-            Self::RequireOnce => "require_once",
-            Self::Return => "return",
-            Self::RightBrace => "}",
-            Self::RightBracket => "]",
-            Self::RightParen => ")",
-            Self::SemiColon => ";",
-            Self::Slash => "/",
-            Self::SlashEquals => "/=",
-            Self::Static => "static",
-            Self::StringCast => "(string)",
-            Self::BinaryCast => "(binary)",
-            Self::Switch => "switch",
-            Self::Throw => "throw",
-            Self::Trait => "trait",
-            Self::TripleEquals => "===",
-            Self::True => "true",
-            Self::Try => "try",
-            Self::Use => "use",
-            Self::Var => "var",
-            Self::Yield => "yield",
-            Self::While => "while",
-            Self::Global => "global",
-            Self::AngledLeftRight => "<>",
-            Self::Spaceship => "<=>",
-            Self::LogicalAnd => "and",
-            Self::LogicalOr => "or",
-            Self::LogicalXor => "xor",
-            Self::Foreach => "foreach",
-            Self::AmpersandEquals => "&=",
-            Self::At => "at",
-            Self::BangDoubleEquals => "!==",
-            Self::TraitConstant => "__TRAIT__",
-            Self::FunctionConstant => "__FUNCTION__",
-            Self::MethodConstant => "__METHOD__",
-            Self::LineConstant => "__LINE__",
-            Self::FileConstant => "__FILE__",
-            Self::DollarLeftBrace => "${",
-            Self::DoubleQuote => "\"",
-            Self::Include => "include",
-            Self::IncludeOnce => "include_once",
-            Self::Instanceof => "instanceof",
-            Self::Insteadof => "insteadof",
-            Self::Eval => "eval",
-            Self::Exit => "exit",
-            Self::Unset => "unset",
-            Self::Isset => "isset",
-            Self::List => "list",
-            Self::Interface => "interface",
-            Self::NamespaceConstant => "__NAMESPACE__",
-            Self::PowEquals => "**=",
-            Self::StringPart
-            | Self::Variable
-            | Self::QualifiedIdentifier
-            | Self::Identifier
-            | Self::FullyQualifiedIdentifier
-            | Self::LiteralString
-            | Self::SingleLineComment
-            | Self::MultiLineComment
-            | Self::HashMarkComment
-            | Self::DocumentComment => {
-                return write!(f, "{:?}", self);
-            }
-        };
+        write!(f, "{}", self.display())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OpenTagKind;
+    use super::TokenKind;
+    use crate::parser::options::PhpVersion;
+
+    #[test]
+    fn test_keywords() {
+        assert_eq!(TokenKind::keyword(b"FuNcTiOn"), Some(TokenKind::Function));
+        assert_eq!(
+            TokenKind::keyword(b"__class__"),
+            Some(TokenKind::ClassConstant)
+        );
+        assert_eq!(TokenKind::keyword(b"callable"), None);
+        assert_eq!(TokenKind::keyword(b"foo"), None);
+
+        assert!(TokenKind::Enum.is_keyword());
+        assert!(!TokenKind::Identifier.is_keyword());
+        assert!(!TokenKind::PrivateSet.is_keyword());
+    }
+
+    #[test]
+    fn test_reserved_keywords() {
+        assert!(TokenKind::Class.is_reserved_in(PhpVersion::Php70));
+        assert!(!TokenKind::Match.is_reserved_in(PhpVersion::Php74));
+        assert!(TokenKind::Match.is_reserved_in(PhpVersion::Php80));
+        assert!(!TokenKind::Enum.is_reserved_in(PhpVersion::Php84));
+        assert!(!TokenKind::Arrow.is_reserved_in(PhpVersion::Php84));
+    }
+
+    #[test]
+    fn test_display_and_php_names() {
+        assert_eq!(TokenKind::DoubleArrow.to_string(), "=>");
+        assert_eq!(TokenKind::Variable.to_string(), "Variable");
+        assert_eq!(TokenKind::OpenTag(OpenTagKind::Echo).to_string(), "<?=");
 
-        write!(f, "{}", s)
+        assert_eq!(TokenKind::DoubleArrow.php_name(), Some("T_DOUBLE_ARROW"));
+        assert_eq!(
+            TokenKind::OpenTag(OpenTagKind::Full).php_name(),
+            Some("T_OPEN_TAG")
+        );
+        assert_eq!(
+            TokenKind::OpenTag(OpenTagKind::Echo).php_name(),
+            Some("T_OPEN_TAG_WITH_ECHO")
+        );
+        assert_eq!(TokenKind::SemiColon.php_name(), None);
     }
 }
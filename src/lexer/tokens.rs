@@ -0,0 +1,150 @@
+use std::collections::VecDeque;
+
+use crate::lexer::error::SyntaxError;
+use crate::lexer::state::State;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+use crate::lexer::Lexer;
+
+/// An iterator over the tokens of an input, lexed as they are requested, see [`Lexer::tokens`].
+///
+/// The tokens are the ones returned by [`Lexer::tokenize`], ending with the end of file
+/// token. Reported control characters are yielded as errors in the order they are found,
+/// and lexing continues after them, see [`crate::lexer::ControlCharacters`]. Lexing stops
+/// after any other error, which is the last item yielded.
+pub struct Tokens<'a> {
+    lexer: &'a Lexer,
+    state: State<'a>,
+    // Tokens and errors lexed but not yielded yet.
+    pending: VecDeque<Result<Token<'a>, SyntaxError>>,
+    // Buffers for the tokens and diagnostics of a single step.
+    tokens: Vec<Token<'a>>,
+    diagnostics: Vec<SyntaxError>,
+    // The number of tokens yielded so far.
+    count: usize,
+    done: bool,
+}
+
+impl<'a> Tokens<'a> {
+    pub(super) fn new(lexer: &'a Lexer, input: &'a [u8]) -> Self {
+        Self {
+            lexer,
+            state: lexer.state(input),
+            pending: VecDeque::new(),
+            tokens: Vec::new(),
+            diagnostics: Vec::new(),
+            count: 0,
+            done: false,
+        }
+    }
+
+    fn exceeds_max_tokens(&self, token: &Token<'a>) -> Option<usize> {
+        if token.kind == TokenKind::Eof {
+            return None;
+        }
+
+        self.lexer.max_tokens.filter(|max| self.count >= *max)
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Result<Token<'a>, SyntaxError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.pending.pop_front() {
+                Some(Ok(token)) => {
+                    if let Some(max) = self.exceeds_max_tokens(&token) {
+                        self.pending.clear();
+                        self.done = true;
+
+                        return Some(Err(SyntaxError::TooManyTokens(max, token.span)));
+                    }
+
+                    self.count += 1;
+
+                    return Some(Ok(token));
+                }
+                Some(Err(error)) => return Some(Err(error)),
+                None if self.done => return None,
+                None => {}
+            }
+
+            let result = self
+                .lexer
+                .step(&mut self.state, &mut self.tokens, &mut self.diagnostics);
+
+            self.pending.extend(self.diagnostics.drain(..).map(Err));
+            self.pending.extend(self.tokens.drain(..).map(Ok));
+
+            match result {
+                Ok(true) => {}
+                Ok(false) => {
+                    self.pending.push_back(Ok(Lexer::eof(&self.state)));
+                    self.done = true;
+                }
+                Err(error) => {
+                    self.pending.push_back(Err(error));
+                    self.done = true;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::error::SyntaxError;
+    use crate::lexer::token::TokenKind;
+    use crate::lexer::ControlCharacters;
+    use crate::lexer::Lexer;
+
+    #[test]
+    fn test_tokens_match_tokenize() {
+        let inputs = [
+            "<?php $a = \"$b {$c->d} ${e}\"; echo <<<EOT\n  $f[0]\n  EOT;",
+            "<p><?= $x ?></p><?php __halt_compiler(); anything",
+            "",
+            "<?php ",
+        ];
+
+        for input in inputs {
+            let lexer = Lexer::new();
+            let tokens = lexer.tokens(input).collect::<Result<Vec<_>, _>>();
+
+            assert_eq!(tokens, lexer.tokenize(input), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_tokens_stop_at_errors() {
+        let lexer = Lexer::new();
+        let tokens = lexer.tokens("<?php $a = 'b").collect::<Vec<_>>();
+
+        assert_eq!(tokens.len(), 4);
+        assert_eq!(tokens[2].as_ref().unwrap().kind, TokenKind::Equals);
+        assert!(matches!(
+            tokens[3],
+            Err(SyntaxError::UnexpectedEndOfFile(_))
+        ));
+
+        let lexer = Lexer::new().with_max_tokens(2);
+        let tokens = lexer.tokens("<?php echo 1;").collect::<Vec<_>>();
+
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[2], Err(lexer.tokenize("<?php echo 1;").unwrap_err()));
+    }
+
+    #[test]
+    fn test_tokens_report_control_characters() {
+        let lexer = Lexer::new().with_control_characters(ControlCharacters::Report);
+        let tokens = lexer.tokens("<?php $a\0;").collect::<Vec<_>>();
+
+        assert!(matches!(
+            tokens[2],
+            Err(SyntaxError::UnexpectedCharacter(0, _))
+        ));
+        assert_eq!(tokens[3].as_ref().unwrap().kind, TokenKind::SemiColon);
+        assert_eq!(tokens[4].as_ref().unwrap().kind, TokenKind::Eof);
+    }
+}
@@ -1,9 +1,63 @@
+//! A PHP parser.
+//!
+//! Most users only need the [`prelude`], which re-exports the parse functions, the lexer,
+//! the AST, the visitor traits and the diagnostics.
+//!
+//! # Stability
+//!
+//! The crate follows semantic versioning for everything reachable from the [`prelude`] and
+//! the public modules, with the following hooks to allow growing the language support in
+//! minor releases:
+//!
+//! - All AST enums are `#[non_exhaustive]`, new statements, expressions and other node
+//!   variants are added without a major release. Matches over them need a wildcard arm.
+//! - New fields may be added to AST nodes, build nodes by parsing and read them through
+//!   their fields and accessors rather than struct literals or exhaustive destructuring.
+//! - Options are configured through builders, such as [`ParserOptions`], which holds the
+//!   [`ResourceLimits`] and the [`CancellationToken`] of a parse, and [`lexer::Lexer`], which
+//!   gain methods instead of changing signatures.
+//! - Error codes are stable, their English messages are not, see [`parser::catalog`].
+//! - The shape of the serialized AST follows [`schema::SCHEMA_VERSION`] rather than the
+//!   crate version, consumers of the json can check it with [`schema::from_json`].
+//!
+//! The parser internals, such as the parser state and the grammar functions, are private.
+//!
+//! # Panics
+//!
+//! The lexer and the parse functions never panic, whatever the input bytes: invalid input is
+//! reported as errors, and a panic on any input is a bug. Inputs from untrusted sources should
+//! go through [`parse_untrusted`], which also bounds the resources spent on them.
+//!
+//! The parser recurses into nested constructs, so deeply nested input can still overflow the
+//! stack, which aborts the process rather than panicking. Only parses with a
+//! [`ResourceLimits::max_depth`], such as [`parse_untrusted`], are guarded against it: the depth of
+//! [`ResourceLimits::untrusted`] fits a 2 MiB stack, the default of a spawned thread, in release
+//! builds. Debug builds use far more stack per level, and need a 16 MiB stack at that depth, a
+//! lower maximum depth, or the `stacker` feature.
+
+#![allow(clippy::result_large_err)]
+
+pub mod analysis;
 pub mod downcast;
 pub mod lexer;
 pub mod node;
 pub mod parser;
+pub mod prelude;
 pub mod printer;
+pub mod project;
+pub mod schema;
+pub mod transforms;
 pub mod traverser;
 
 pub use lexer::stream::TokenStream;
-pub use parser::{construct, parse};
+pub use parser::cancellation::CancellationToken;
+pub use parser::error::Parse;
+pub use parser::limits::ResourceLimits;
+pub use parser::options::ParserOptions;
+pub use parser::options::PhpVersion;
+pub use parser::reusable::Parser;
+pub use parser::{
+    construct, construct_with_options, parse, parse_all, parse_all_with_options,
+    parse_class_member, parse_docblock_type, parse_method_body, parse_template, parse_tolerant,
+    parse_tolerant_with_options, parse_type, parse_untrusted, parse_with_options,
+};
@@ -1,27 +1,177 @@
 use clap::Parser;
+use clap::Subcommand;
+use php_parser_rs::analysis::diff::diff;
+use php_parser_rs::parser::ast::Program;
+use php_parser_rs::parser::error::ParseErrorStack;
+use php_parser_rs::parser::suppressions::SuppressionMode;
+use php_parser_rs::printer::format;
+use php_parser_rs::printer::print_stubs;
+use php_parser_rs::printer::unified_diff;
+use php_parser_rs::printer::BraceStyle;
+use php_parser_rs::printer::Indentation;
+use php_parser_rs::printer::PrinterOptions;
+use php_parser_rs::project::scan;
+use php_parser_rs::project::scan_file;
+use php_parser_rs::project::FileOutcome;
+use php_parser_rs::project::ProfileReport;
+use php_parser_rs::project::ProjectConfig;
+use php_parser_rs::project::ScanOptions;
+use php_parser_rs::schema::to_json_pretty;
+use php_parser_rs::ParserOptions;
+use php_parser_rs::PhpVersion;
 use std::io::Result;
+use std::path::Path;
 
 #[derive(Parser, Default, Debug)]
-#[clap(version, about = "A PHP Parser")]
+#[clap(
+    version,
+    about = "A PHP Parser",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 struct Arguments {
-    file: String,
+    #[clap(required = true)]
+    file: Option<String>,
     #[clap(short, long)]
     /// Don't print anything
     silent: bool,
     #[clap(short, long)]
-    /// Print as json
+    /// Print as json, along with the version of the schema of the AST
     json: bool,
+    #[clap(short, long)]
+    /// Print the parse time, token count, and node count of each file,
+    /// parsing every PHP file within the given path when it is a directory
+    profile: bool,
+    #[clap(long)]
+    /// Reject syntax which is not supported by the given version of PHP, e.g. `7.4`
+    php_version: Option<PhpVersion>,
+    #[clap(long)]
+    /// Accept experimental syntax, which is not part of a released version of PHP yet
+    experimental: bool,
+    #[clap(long)]
+    /// Apply `@parser-ignore` comments (`on`), also reporting the unused ones (`report-unused`)
+    suppressions: Option<SuppressionMode>,
+    #[clap(long)]
+    /// The configuration file to read, rather than the `php-parser.toml` file of the
+    /// current directory or its closest ancestor containing one
+    config: Option<String>,
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the declarations and statements which differ between two versions of a file,
+    /// ignoring changes which only affect formatting or comments
+    ///
+    /// Exits with 1 when the files differ, and 2 when either of them fails to parse.
+    Diff { before: String, after: String },
+    /// Format the given files in place with the pretty printer
+    ///
+    /// Files whose comments would be lost are left untouched. Exits with 2 when a file can
+    /// not be formatted.
+    Format {
+        #[clap(required = true)]
+        /// The files to format
+        files: Vec<String>,
+        #[clap(long)]
+        /// Don't write the files, and exit with 1 when any of them is not formatted
+        check: bool,
+        #[clap(long)]
+        /// Don't write the files, and print the changes formatting would make
+        diff: bool,
+        #[clap(long)]
+        /// The number of spaces per indentation level, 4 by default
+        indent: Option<usize>,
+        #[clap(long)]
+        /// Indent with tabs rather than spaces
+        tabs: bool,
+        #[clap(long)]
+        /// The placement of opening braces: `psr12` (the default), `same-line` or `next-line`
+        braces: Option<BraceStyle>,
+    },
+    /// Write signature-only stubs of every PHP file within a directory to another directory
+    ///
+    /// Function and method bodies, private members, and statements other than declarations
+    /// are left out, and files without declarations are skipped. Exits with 2 when a file
+    /// fails to parse.
+    Stubs {
+        /// The directory to generate stubs for
+        input: String,
+        /// The directory to write the stubs to, mirroring the layout of the input directory
+        output: String,
+    },
 }
 
 fn main() -> Result<()> {
     let args = Arguments::parse();
+    let config = match &args.config {
+        Some(path) => ProjectConfig::load(Path::new(path))?,
+        None => match ProjectConfig::find(&std::env::current_dir()?) {
+            Some(path) => ProjectConfig::load(&path)?,
+            None => ProjectConfig::default(),
+        },
+    };
+
+    let parser_options = match (
+        args.php_version.or(config.php_version),
+        args.experimental || config.experimental,
+        args.suppressions.or(config.suppressions),
+    ) {
+        (None, false, None) => None,
+        (php_version, experimental, suppressions) => Some(ParserOptions {
+            php_version,
+            ..ParserOptions::new()
+                .with_experimental(experimental)
+                .with_suppressions(suppressions.unwrap_or_default())
+        }),
+    };
+    let scan_options = ScanOptions {
+        parser: parser_options.clone(),
+        ..config.scan_options()
+    };
+
+    match &args.command {
+        Some(Command::Diff { before, after }) => {
+            return changes(before, after, parser_options.as_ref())
+        }
+        Some(Command::Format {
+            files,
+            check,
+            diff,
+            indent,
+            tabs,
+            braces,
+        }) => {
+            let mut options = config.printer_options();
+            if *tabs {
+                options.indentation = Indentation::Tabs;
+            } else if let Some(indent) = indent {
+                options.indentation = Indentation::Spaces(*indent);
+            }
+
+            if let Some(braces) = braces {
+                options.brace_style = *braces;
+            }
+
+            return format_files(files, &options, *check, *diff);
+        }
+        Some(Command::Stubs { input, output }) => return write_stubs(input, output, scan_options),
+        None => {}
+    }
+
+    let file = args.file.unwrap_or_default();
+    if args.profile {
+        return profile(&file, scan_options);
+    }
 
-    let file = args.file;
     let contents = std::fs::read_to_string(&file)?;
     let silent = args.silent;
     let print_json = args.json;
 
-    match php_parser_rs::parse(&contents) {
+    let result = parse(&contents, parser_options.as_ref());
+
+    match result {
         Ok(ast) => {
             // if --silent is passed, don't print anything
             if silent {
@@ -30,7 +180,7 @@ fn main() -> Result<()> {
 
             // if --json is passed, print as json
             if print_json {
-                match serde_json::to_string_pretty(&ast) {
+                match to_json_pretty(&ast) {
                     Ok(json) => println!("{}", json),
                     Err(error) => {
                         eprintln!("Failed to convert ast to json: {}", error);
@@ -52,3 +202,143 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+fn parse(
+    contents: &str,
+    options: Option<&ParserOptions>,
+) -> std::result::Result<Program, ParseErrorStack> {
+    match options {
+        Some(options) => php_parser_rs::parse_with_options(contents, options),
+        None => php_parser_rs::parse(contents),
+    }
+}
+
+fn changes(before: &str, after: &str, options: Option<&ParserOptions>) -> Result<()> {
+    let read = |file: &str| -> Result<_> {
+        let contents = std::fs::read_to_string(file)?;
+
+        match parse(&contents, options) {
+            Ok(ast) => Ok(ast),
+            Err(error) => {
+                println!("{}", error.report(&contents, Some(file), true, false)?);
+
+                std::process::exit(2);
+            }
+        }
+    };
+
+    let changes = diff(&read(before)?, &read(after)?);
+    for change in changes.iter() {
+        println!("{}", change);
+    }
+
+    if !changes.is_empty() {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn format_files(files: &[String], options: &PrinterOptions, check: bool, diff: bool) -> Result<()> {
+    let (mut unformatted, mut failed) = (false, false);
+
+    for file in files.iter() {
+        let contents = std::fs::read_to_string(file)?;
+
+        let formatted = match format(&contents, options) {
+            Ok(formatted) => formatted,
+            Err(error) => {
+                eprintln!("{}: {}", file, error);
+                failed = true;
+
+                continue;
+            }
+        };
+
+        if formatted == contents {
+            continue;
+        }
+
+        unformatted = true;
+        if diff {
+            print!("{}", unified_diff(file, &contents, &formatted));
+        } else if check {
+            println!("{} is not formatted", file);
+        }
+
+        if !check && !diff {
+            std::fs::write(file, formatted)?;
+        }
+    }
+
+    if failed {
+        std::process::exit(2);
+    }
+
+    if check && unformatted {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn write_stubs(input: &str, output: &str, options: ScanOptions) -> Result<()> {
+    let options = ScanOptions {
+        skip_generated: false,
+        ..options
+    };
+
+    let mut failed = false;
+    for result in scan(Path::new(input), &options).map_err(std::io::Error::other)? {
+        let program = match result.outcome {
+            FileOutcome::Parsed(program) => program,
+            FileOutcome::Failed(error) => {
+                eprintln!("{}: {}", result.path.display(), error);
+                failed = true;
+
+                continue;
+            }
+            FileOutcome::Skipped(_) => continue,
+        };
+
+        let stubs = print_stubs(&program, &PrinterOptions::new());
+        if stubs.is_empty() {
+            continue;
+        }
+
+        let path = Path::new(output).join(&result.path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(path, stubs)?;
+    }
+
+    if failed {
+        std::process::exit(2);
+    }
+
+    Ok(())
+}
+
+fn profile(path: &str, options: ScanOptions) -> Result<()> {
+    let path = Path::new(path);
+    let options = ScanOptions {
+        profile: true,
+        skip_generated: false,
+        ..options
+    };
+
+    let results = if path.is_dir() {
+        scan(path, &options).map_err(std::io::Error::other)?
+    } else {
+        let directory = path.parent().unwrap_or(Path::new("."));
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+        scan_file(directory, &name, &options).map_err(std::io::Error::other)?
+    };
+
+    print!("{}", ProfileReport::new(&results));
+
+    Ok(())
+}
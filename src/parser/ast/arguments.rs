@@ -10,21 +10,72 @@ use crate::parser::ast::Expression;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum Argument {
     Positional {
         comments: CommentGroup,
+        start: Span,            // first token of the argument
         ellipsis: Option<Span>, // `...`
         value: Expression,      // `$var`
+        end: Span,              // last token of the argument
     },
     Named {
         comments: CommentGroup,
+        start: Span,            // first token of the argument
         name: SimpleIdentifier, // `foo`
         colon: Span,            // `:`
         ellipsis: Option<Span>, // `...`
         value: Expression,      // `$var`
+        end: Span,              // last token of the argument
     },
 }
 
+impl Argument {
+    pub fn name(&self) -> Option<&SimpleIdentifier> {
+        match self {
+            Argument::Positional { .. } => None,
+            Argument::Named { name, .. } => Some(name),
+        }
+    }
+
+    pub fn value(&self) -> &Expression {
+        match self {
+            Argument::Positional { value, .. } => value,
+            Argument::Named { value, .. } => value,
+        }
+    }
+
+    pub fn ellipsis(&self) -> Option<Span> {
+        match self {
+            Argument::Positional { ellipsis, .. } => *ellipsis,
+            Argument::Named { ellipsis, .. } => *ellipsis,
+        }
+    }
+
+    pub fn start(&self) -> Span {
+        match self {
+            Argument::Positional { start, .. } => *start,
+            Argument::Named { start, .. } => *start,
+        }
+    }
+
+    pub fn end(&self) -> Span {
+        match self {
+            Argument::Positional { end, .. } => *end,
+            Argument::Named { end, .. } => *end,
+        }
+    }
+
+    pub fn is_named(&self) -> bool {
+        matches!(self, Argument::Named { .. })
+    }
+
+    /// Whether this argument is unpacked using `...`.
+    pub fn is_spread(&self) -> bool {
+        self.ellipsis().is_some()
+    }
+}
+
 impl Node for Argument {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         match self {
@@ -43,6 +94,47 @@ pub struct ArgumentList {
     pub right_parenthesis: Span,  // `)`
 }
 
+impl ArgumentList {
+    pub fn is_empty(&self) -> bool {
+        self.arguments.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.arguments.len()
+    }
+
+    pub fn has_spread(&self) -> bool {
+        self.arguments.iter().any(|argument| argument.is_spread())
+    }
+
+    /// Get the argument passed to the parameter at the given position.
+    ///
+    /// Returns `None` if the argument is not passed positionally, or if an
+    /// unpacked argument makes the position unknown until runtime.
+    pub fn get_positional(&self, position: usize) -> Option<&Argument> {
+        for (index, argument) in self.arguments.iter().enumerate() {
+            if argument.is_named() || argument.is_spread() {
+                return None;
+            }
+
+            if index == position {
+                return Some(argument);
+            }
+        }
+
+        None
+    }
+
+    /// Get the argument passed to the parameter with the given name, without the leading `$`.
+    pub fn get_named(&self, name: &[u8]) -> Option<&Argument> {
+        self.arguments.iter().find(|argument| {
+            argument
+                .name()
+                .is_some_and(|identifier| identifier.value[..] == *name)
+        })
+    }
+}
+
 impl Node for ArgumentList {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.arguments
@@ -1,6 +1,7 @@
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use smallvec::SmallVec;
 
 use crate::lexer::token::Span;
 use crate::parser::ast::arguments::ArgumentList;
@@ -9,16 +10,16 @@ use crate::parser::ast::identifiers::SimpleIdentifier;
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct Attribute {
-    pub start: Span,
-    pub end: Span,
-    pub name: SimpleIdentifier,
-    pub arguments: Option<ArgumentList>,
+    pub start: Span,                     // the name
+    pub end: Span,                       // the `,` or `]` following the attribute
+    pub name: SimpleIdentifier,          // `Foo`
+    pub arguments: Option<ArgumentList>, // `(1, 2)`
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct AttributeGroup {
-    pub start: Span,
-    pub end: Span,
-    pub members: Vec<Attribute>,
+    pub start: Span,                       // `#[`
+    pub end: Span,                         // `]`
+    pub members: SmallVec<[Attribute; 1]>, // `Foo, Bar(1)`
 }
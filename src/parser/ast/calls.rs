@@ -0,0 +1,150 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::Expression;
+
+/// The form of the callee of a function or method call.
+///
+/// Callable resolution depends on exactly which form was used, so the
+/// shape of a call can be determined from the AST without re-inspecting
+/// its target.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "type")]
+#[non_exhaustive]
+pub enum CallShape {
+    // `foo()`
+    Named,
+    // `'foo'()`
+    String,
+    // `$foo()`
+    Variable,
+    // `['Foo', 'bar']()` or `array($foo, 'bar')()`
+    Array,
+    // `('foo' . $bar)()` or `foo()()`
+    Expression,
+    // `$foo->bar()`
+    Method,
+    // `$foo->$bar()`
+    VariableMethod,
+    // `$foo->{$bar}()`
+    DynamicMethod,
+    // `Foo::bar()`
+    StaticMethod,
+    // `Foo::$bar()`
+    StaticVariableMethod,
+    // `Foo::{$bar}()`
+    StaticDynamicMethod,
+}
+
+impl CallShape {
+    /// Whether the callee can only be resolved at runtime.
+    pub fn is_dynamic(&self) -> bool {
+        !matches!(
+            self,
+            CallShape::Named | CallShape::Method | CallShape::StaticMethod
+        )
+    }
+}
+
+impl Expression {
+    /// The shape of this call, or `None` if this expression is not a call.
+    ///
+    /// First-class callable creation (`foo(...)`) has the same shape as the
+    /// equivalent call.
+    pub fn call_shape(&self) -> Option<CallShape> {
+        Some(match self {
+            Expression::FunctionCall { target, .. }
+            | Expression::FunctionClosureCreation { target, .. } => function_shape(target),
+            Expression::MethodCall { method, .. }
+            | Expression::MethodClosureCreation { method, .. }
+            | Expression::NullsafeMethodCall { method, .. } => match method.as_ref() {
+                Expression::Identifier(Identifier::SimpleIdentifier(_)) => CallShape::Method,
+                Expression::Variable(_) => CallShape::VariableMethod,
+                _ => CallShape::DynamicMethod,
+            },
+            Expression::StaticMethodCall { method, .. }
+            | Expression::StaticMethodClosureCreation { method, .. } => match method {
+                Identifier::SimpleIdentifier(_) => CallShape::StaticMethod,
+                Identifier::DynamicIdentifier(_) => CallShape::StaticDynamicMethod,
+            },
+            Expression::StaticVariableMethodCall { .. }
+            | Expression::StaticVariableMethodClosureCreation { .. } => {
+                CallShape::StaticVariableMethod
+            }
+            _ => return None,
+        })
+    }
+}
+
+fn function_shape(target: &Expression) -> CallShape {
+    match target {
+        Expression::Parenthesized { expr, .. } => function_shape(expr),
+        Expression::Identifier(Identifier::SimpleIdentifier(_)) => CallShape::Named,
+        Expression::Literal(Literal::String(_)) => CallShape::String,
+        Expression::Variable(_) => CallShape::Variable,
+        Expression::ShortArray { .. } | Expression::Array { .. } => CallShape::Array,
+        _ => CallShape::Expression,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CallShape;
+    use crate::parser::ast::Statement;
+
+    fn shape(code: &str) -> Option<CallShape> {
+        let program = crate::parse(&format!("<?php {};", code)).unwrap();
+
+        match &program[1] {
+            Statement::Expression(statement) => statement.expression.call_shape(),
+            statement => panic!("expected an expression statement, found {:?}", statement),
+        }
+    }
+
+    #[test]
+    fn test_function_call_shapes() {
+        assert_eq!(shape("foo()"), Some(CallShape::Named));
+        assert_eq!(shape("\\Foo\\bar(...)"), Some(CallShape::Named));
+        assert_eq!(shape("'foo'()"), Some(CallShape::String));
+        assert_eq!(shape("$foo()"), Some(CallShape::Variable));
+        assert_eq!(shape("($foo)()"), Some(CallShape::Variable));
+        assert_eq!(shape("$$foo()"), Some(CallShape::Variable));
+        assert_eq!(shape("['Foo', 'bar']()"), Some(CallShape::Array));
+        assert_eq!(shape("array($foo, 'bar')()"), Some(CallShape::Array));
+        assert_eq!(shape("('foo' . $bar)()"), Some(CallShape::Expression));
+        assert_eq!(shape("foo()()"), Some(CallShape::Expression));
+    }
+
+    #[test]
+    fn test_method_call_shapes() {
+        assert_eq!(shape("$foo->bar()"), Some(CallShape::Method));
+        assert_eq!(shape("$foo?->bar()"), Some(CallShape::Method));
+        assert_eq!(shape("$foo->$bar()"), Some(CallShape::VariableMethod));
+        assert_eq!(shape("$foo->{$bar}()"), Some(CallShape::DynamicMethod));
+        assert_eq!(shape("Foo::bar()"), Some(CallShape::StaticMethod));
+        assert_eq!(shape("Foo::$bar()"), Some(CallShape::StaticVariableMethod));
+        assert_eq!(shape("Foo::{$bar}()"), Some(CallShape::StaticDynamicMethod));
+        assert_eq!(
+            shape("Foo::$bar(...)"),
+            Some(CallShape::StaticVariableMethod)
+        );
+    }
+
+    #[test]
+    fn test_non_call_expressions_have_no_shape() {
+        assert_eq!(shape("$foo"), None);
+        assert_eq!(shape("$foo->bar"), None);
+        assert_eq!(shape("new Foo()"), None);
+    }
+
+    #[test]
+    fn test_dynamic_shapes() {
+        assert!(!CallShape::Named.is_dynamic());
+        assert!(!CallShape::StaticMethod.is_dynamic());
+        assert!(CallShape::Array.is_dynamic());
+        assert!(CallShape::VariableMethod.is_dynamic());
+    }
+}
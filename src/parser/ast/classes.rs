@@ -5,14 +5,19 @@ use serde::Serialize;
 use crate::lexer::token::Span;
 use crate::node::Node;
 use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::comments::CommentGroup;
 use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::data_type::Type;
 use crate::parser::ast::functions::AbstractConstructor;
 use crate::parser::ast::functions::AbstractMethod;
 use crate::parser::ast::functions::ConcreteConstructor;
 use crate::parser::ast::functions::ConcreteMethod;
 use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::modifiers::ClassModifierGroup;
+use crate::parser::ast::modifiers::MethodModifierGroup;
+use crate::parser::ast::modifiers::PropertyModifierGroup;
 use crate::parser::ast::properties::Property;
+use crate::parser::ast::properties::PropertyEntry;
 use crate::parser::ast::properties::VariableProperty;
 use crate::parser::ast::traits::TraitUsage;
 use crate::parser::ast::utils::CommaSeparated;
@@ -37,6 +42,7 @@ impl Node for ClassBody {
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct ClassStatement {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>, // `#[Qux]`
     #[serde(flatten)]
     pub modifiers: ClassModifierGroup, // `abstract`, `final`
@@ -47,6 +53,179 @@ pub struct ClassStatement {
     pub body: ClassBody,                 // `{ ... }`
 }
 
+impl ClassStatement {
+    pub fn is_abstract(&self) -> bool {
+        self.modifiers.has_abstract()
+    }
+
+    pub fn is_final(&self) -> bool {
+        self.modifiers.has_final()
+    }
+
+    pub fn is_readonly(&self) -> bool {
+        self.modifiers.has_readonly()
+    }
+
+    pub fn parent(&self) -> Option<&SimpleIdentifier> {
+        self.extends.as_ref().map(|extends| &extends.parent)
+    }
+
+    pub fn interfaces(&self) -> impl Iterator<Item = &SimpleIdentifier> {
+        self.implements
+            .iter()
+            .flat_map(|implements| implements.interfaces.inner.iter())
+    }
+
+    pub fn constants(&self) -> impl Iterator<Item = &ClassishConstant> {
+        self.body.members.iter().filter_map(|member| match member {
+            ClassMember::Constant(constant) => Some(constant),
+            _ => None,
+        })
+    }
+
+    pub fn trait_uses(&self) -> impl Iterator<Item = &TraitUsage> {
+        self.body.members.iter().filter_map(|member| match member {
+            ClassMember::TraitUsage(usage) => Some(usage),
+            _ => None,
+        })
+    }
+
+    /// The properties declared in the class body, excluding promoted constructor properties.
+    pub fn properties(&self) -> impl Iterator<Item = ClassProperty<'_>> {
+        self.body.members.iter().filter_map(|member| match member {
+            ClassMember::Property(property) => Some(ClassProperty::Property(property)),
+            ClassMember::VariableProperty(property) => {
+                Some(ClassProperty::VariableProperty(property))
+            }
+            _ => None,
+        })
+    }
+
+    /// The methods of the class, excluding the constructor, see [`ClassStatement::constructor`].
+    pub fn methods(&self) -> impl Iterator<Item = ClassMethod<'_>> {
+        self.body.members.iter().filter_map(|member| match member {
+            ClassMember::AbstractMethod(method) => Some(ClassMethod::Abstract(method)),
+            ClassMember::ConcreteMethod(method) => Some(ClassMethod::Concrete(method)),
+            _ => None,
+        })
+    }
+
+    /// The method with the given name, compared case-insensitively as PHP does.
+    pub fn method(&self, name: &str) -> Option<ClassMethod<'_>> {
+        self.methods()
+            .find(|method| method.name().value.eq_ignore_ascii_case(name.as_bytes()))
+    }
+
+    pub fn constructor(&self) -> Option<ClassConstructor<'_>> {
+        self.body.members.iter().find_map(|member| match member {
+            ClassMember::AbstractConstructor(constructor) => {
+                Some(ClassConstructor::Abstract(constructor))
+            }
+            ClassMember::ConcreteConstructor(constructor) => {
+                Some(ClassConstructor::Concrete(constructor))
+            }
+            _ => None,
+        })
+    }
+}
+
+/// A method of a class, see [`ClassStatement::methods`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum ClassMethod<'a> {
+    Abstract(&'a AbstractMethod),
+    Concrete(&'a ConcreteMethod),
+}
+
+impl<'a> ClassMethod<'a> {
+    pub fn name(&self) -> &'a SimpleIdentifier {
+        match self {
+            ClassMethod::Abstract(method) => &method.name,
+            ClassMethod::Concrete(method) => &method.name,
+        }
+    }
+
+    pub fn modifiers(&self) -> &'a MethodModifierGroup {
+        match self {
+            ClassMethod::Abstract(method) => &method.modifiers,
+            ClassMethod::Concrete(method) => &method.modifiers,
+        }
+    }
+
+    pub fn is_abstract(&self) -> bool {
+        matches!(self, ClassMethod::Abstract(_))
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.modifiers().has_static()
+    }
+}
+
+/// The constructor of a class, see [`ClassStatement::constructor`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum ClassConstructor<'a> {
+    Abstract(&'a AbstractConstructor),
+    Concrete(&'a ConcreteConstructor),
+}
+
+impl<'a> ClassConstructor<'a> {
+    pub fn modifiers(&self) -> &'a MethodModifierGroup {
+        match self {
+            ClassConstructor::Abstract(constructor) => &constructor.modifiers,
+            ClassConstructor::Concrete(constructor) => &constructor.modifiers,
+        }
+    }
+
+    pub fn is_abstract(&self) -> bool {
+        matches!(self, ClassConstructor::Abstract(_))
+    }
+}
+
+/// A property declaration of a class, see [`ClassStatement::properties`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum ClassProperty<'a> {
+    Property(&'a Property),
+    /// A property declared with `var`.
+    VariableProperty(&'a VariableProperty),
+}
+
+impl<'a> ClassProperty<'a> {
+    /// `None` for properties declared with `var`.
+    pub fn modifiers(&self) -> Option<&'a PropertyModifierGroup> {
+        match self {
+            ClassProperty::Property(property) => Some(&property.modifiers),
+            ClassProperty::VariableProperty(_) => None,
+        }
+    }
+
+    pub fn r#type(&self) -> Option<&'a Type> {
+        match self {
+            ClassProperty::Property(property) => property.r#type.as_ref(),
+            ClassProperty::VariableProperty(property) => property.r#type.as_ref(),
+        }
+    }
+
+    /// The properties of the declaration, e.g. both `$a` and `$b` in `public $a, $b = 1;`.
+    pub fn entries(&self) -> &'a [PropertyEntry] {
+        match self {
+            ClassProperty::Property(property) => &property.entries,
+            ClassProperty::VariableProperty(property) => &property.entries,
+        }
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.modifiers()
+            .is_some_and(|modifiers| modifiers.has_static())
+    }
+
+    pub fn is_readonly(&self) -> bool {
+        self.modifiers()
+            .is_some_and(|modifiers| modifiers.has_readonly())
+    }
+}
+
 impl Node for ClassStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         let mut children: Vec<&mut dyn Node> = vec![&mut self.name];
@@ -82,12 +261,19 @@ impl Node for AnonymousClassBody {
 #[serde(rename_all = "snake_case")]
 pub struct AnonymousClass {
     pub attributes: Vec<AttributeGroup>,     // `#[Qux]`
+    pub readonly: Option<Span>,              // `readonly`
     pub class: Span,                         // `class`
     pub extends: Option<ClassExtends>,       // `extends Foo`
     pub implements: Option<ClassImplements>, // `implements Baz, Baz`
     pub body: AnonymousClassBody,            // `{ ... }`
 }
 
+impl AnonymousClass {
+    pub fn is_readonly(&self) -> bool {
+        self.readonly.is_some()
+    }
+}
+
 impl Node for AnonymousClass {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         let mut children: Vec<&mut dyn Node> = vec![];
@@ -130,6 +316,7 @@ impl Node for ClassImplements {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum ClassMember {
     Constant(ClassishConstant),
     TraitUsage(TraitUsage),
@@ -158,6 +345,7 @@ impl Node for ClassMember {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum AnonymousClassMember {
     Constant(ClassishConstant),
     TraitUsage(TraitUsage),
@@ -179,3 +367,99 @@ impl Node for AnonymousClassMember {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ClassConstructor;
+    use crate::parser::ast::properties::PropertyEntry;
+    use crate::parser::ast::Statement;
+
+    #[test]
+    fn test_class_accessors() {
+        let program = crate::parse(
+            "<?php
+abstract class User extends Model implements JsonSerializable, Countable {
+    use HasName, HasEmail;
+
+    const TABLE = 'users';
+
+    public static $count = 0;
+    var $legacy, $other;
+    protected readonly int $id;
+
+    public function __construct(private string $name) {}
+
+    abstract public function save(): void;
+
+    public function jsonSerialize(): mixed { return []; }
+}
+",
+        )
+        .unwrap();
+
+        let class = match &program[1] {
+            Statement::Class(class) => class,
+            statement => panic!("expected a class, found {:?}", statement),
+        };
+
+        assert!(class.is_abstract());
+        assert!(!class.is_final());
+        assert!(!class.is_readonly());
+        assert_eq!(class.parent().unwrap().to_string(), "Model");
+        assert_eq!(
+            class
+                .interfaces()
+                .map(|interface| interface.to_string())
+                .collect::<Vec<_>>(),
+            vec!["JsonSerializable", "Countable"]
+        );
+        assert_eq!(class.constants().count(), 1);
+        assert_eq!(class.trait_uses().next().unwrap().traits.len(), 2);
+
+        let properties = class
+            .properties()
+            .flat_map(|property| {
+                property.entries().iter().map(move |entry| {
+                    let variable = match entry {
+                        PropertyEntry::Uninitialized { variable }
+                        | PropertyEntry::Initialized { variable, .. } => variable,
+                    };
+
+                    (
+                        variable.name.to_string(),
+                        property.is_static(),
+                        property.is_readonly(),
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            properties,
+            vec![
+                ("$count".to_string(), true, false),
+                ("$legacy".to_string(), false, false),
+                ("$other".to_string(), false, false),
+                ("$id".to_string(), false, true),
+            ]
+        );
+
+        let methods = class
+            .methods()
+            .map(|method| (method.name().to_string(), method.is_abstract()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            methods,
+            vec![
+                ("save".to_string(), true),
+                ("jsonSerialize".to_string(), false)
+            ]
+        );
+        assert!(class.method("JSONSERIALIZE").is_some());
+        assert!(class.method("__construct").is_none());
+
+        assert!(matches!(
+            class.constructor(),
+            Some(ClassConstructor::Concrete(_))
+        ));
+    }
+}
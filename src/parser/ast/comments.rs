@@ -8,6 +8,7 @@ use crate::node::Node;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type")]
+#[non_exhaustive]
 pub enum CommentFormat {
     SingleLine,
     MultiLine,
@@ -30,3 +31,24 @@ impl Node for Comment {}
 pub struct CommentGroup {
     pub comments: Vec<Comment>,
 }
+
+impl CommentGroup {
+    /// The document comment of the node the comments are attached to, i.e. the last
+    /// `/** ... */` comment preceding it.
+    ///
+    /// ```
+    /// use php_parser_rs::parser::ast::Statement;
+    ///
+    /// let program = php_parser_rs::parse("<?php\n/** A user. */\n// @internal\nclass User {}").unwrap();
+    ///
+    /// if let Statement::Class(class) = &program[1] {
+    ///     assert_eq!(class.comments.doc_comment().unwrap().content, b"/** A user. */");
+    /// }
+    /// ```
+    pub fn doc_comment(&self) -> Option<&Comment> {
+        self.comments
+            .iter()
+            .rev()
+            .find(|comment| comment.format == CommentFormat::Document)
+    }
+}
@@ -6,6 +6,7 @@ use crate::lexer::token::Span;
 use crate::node::Node;
 use crate::parser::ast::attributes::AttributeGroup;
 use crate::parser::ast::comments::CommentGroup;
+use crate::parser::ast::data_type::Type;
 use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::modifiers::ConstantModifierGroup;
 use crate::parser::ast::Expression;
@@ -49,16 +50,18 @@ pub struct ClassishConstant {
     pub attributes: Vec<AttributeGroup>,  // `#[Foo]`
     pub modifiers: ConstantModifierGroup, // `public`
     pub r#const: Span,                    // `const`
-    #[serde(flatten)]
-    pub entries: Vec<ConstantEntry>, // `FOO = 123`
+    pub data_type: Option<Type>,          // `int`
+    pub entries: Vec<ConstantEntry>,      // `FOO = 123`
     pub semicolon: Span,                  // `;`
 }
 
 impl Node for ClassishConstant {
     fn children(&mut self) -> Vec<&mut dyn Node> {
-        self.entries
-            .iter_mut()
-            .map(|e| e as &mut dyn Node)
-            .collect()
+        let mut children: Vec<&mut dyn Node> = vec![];
+        if let Some(data_type) = &mut self.data_type {
+            children.push(data_type);
+        }
+        children.extend(self.entries.iter_mut().map(|e| e as &mut dyn Node));
+        children
     }
 }
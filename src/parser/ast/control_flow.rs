@@ -26,6 +26,7 @@ impl Node for IfStatement {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum IfStatementBody {
     Statement {
         statement: Box<Statement>,       // `*statement*`
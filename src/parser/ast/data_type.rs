@@ -10,6 +10,7 @@ use crate::node::Node;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum Type {
     Named(Span, ByteString),
     Nullable(Span, Box<Type>),
@@ -107,7 +108,10 @@ impl Display for Type {
                 "{}",
                 inner
                     .iter()
-                    .map(|t| t.to_string())
+                    .map(|t| match t {
+                        Type::Intersection(_) => format!("({})", t),
+                        _ => t.to_string(),
+                    })
                     .collect::<Vec<String>>()
                     .join("|")
             ),
@@ -116,7 +120,10 @@ impl Display for Type {
                 "{}",
                 inner
                     .iter()
-                    .map(|t| t.to_string())
+                    .map(|t| match t {
+                        Type::Union(_) => format!("({})", t),
+                        _ => t.to_string(),
+                    })
                     .collect::<Vec<String>>()
                     .join("&")
             ),
@@ -0,0 +1,381 @@
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::operators::ArithmeticOperation;
+use crate::parser::ast::operators::AssignmentOperation;
+use crate::parser::ast::operators::BitwiseOperation;
+use crate::parser::ast::operators::ComparisonOperation;
+use crate::parser::ast::operators::LogicalOperation;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::visitor::walk_expression;
+use crate::parser::ast::visitor::walk_identifier;
+use crate::parser::ast::visitor::walk_variable;
+use crate::parser::ast::visitor::Visitor;
+use crate::parser::ast::CastKind;
+use crate::parser::ast::ExitKind;
+use crate::parser::ast::Expression;
+use crate::parser::ast::MagicConstant;
+
+impl Expression {
+    /// An indented tree of this expression, with one line per sub-expression.
+    ///
+    /// Operators are shown as their symbol and their operands are nested below them,
+    /// which makes the precedence and associativity the expression was parsed with visible.
+    /// Parentheses from the source are kept as a `()` node.
+    ///
+    /// ```
+    /// use php_parser_rs::prelude::*;
+    ///
+    /// let program = parse("<?php $a = $b + $c * -$d;").unwrap();
+    /// let expression = match &program[1] {
+    ///     Statement::Expression(statement) => &statement.expression,
+    ///     _ => unreachable!(),
+    /// };
+    ///
+    /// assert_eq!(
+    ///     expression.debug_tree(),
+    ///     "=
+    /// ├── $a
+    /// └── +
+    ///     ├── $b
+    ///     └── *
+    ///         ├── $c
+    ///         └── - (unary)
+    ///             └── $d
+    /// "
+    /// );
+    /// ```
+    ///
+    /// The format is meant for humans, and may change between releases.
+    pub fn debug_tree(&self) -> String {
+        let mut output = String::new();
+
+        Tree::new(self).write(&mut output, "", "");
+
+        output
+    }
+}
+
+struct Tree {
+    label: String,
+    children: Vec<Tree>,
+}
+
+impl Tree {
+    fn new(expression: &Expression) -> Self {
+        let mut children = Children::default();
+
+        match expression {
+            Expression::Variable(variable) => return Self::variable(variable),
+            Expression::Identifier(identifier) => return Self::identifier(identifier),
+            // The bodies of closures and anonymous classes are statements, which would only
+            // clutter the tree.
+            Expression::Closure(_) | Expression::AnonymousClass(_) => {}
+            Expression::ArrowFunction(function) => children.visit_expression(&function.body),
+            _ => walk_expression(&mut children, expression),
+        }
+
+        Self {
+            label: label(expression),
+            children: children.trees,
+        }
+    }
+
+    fn variable(variable: &Variable) -> Self {
+        let mut children = Children::default();
+        if let Variable::BracedVariableVariable(_) = variable {
+            walk_variable(&mut children, variable);
+        }
+
+        Self {
+            label: variable_label(variable),
+            children: children.trees,
+        }
+    }
+
+    fn identifier(identifier: &Identifier) -> Self {
+        let mut children = Children::default();
+        walk_identifier(&mut children, identifier);
+
+        Self {
+            label: match identifier {
+                Identifier::SimpleIdentifier(identifier) => identifier.value.to_string(),
+                Identifier::DynamicIdentifier(_) => "{}".to_string(),
+            },
+            children: children.trees,
+        }
+    }
+
+    fn write(&self, output: &mut String, first: &str, rest: &str) {
+        output.push_str(first);
+        output.push_str(&self.label);
+        output.push('\n');
+
+        for (i, child) in self.children.iter().enumerate() {
+            if i + 1 == self.children.len() {
+                child.write(output, &format!("{}└── ", rest), &format!("{}    ", rest));
+            } else {
+                child.write(output, &format!("{}├── ", rest), &format!("{}│   ", rest));
+            }
+        }
+    }
+}
+
+/// Collects the closest sub-expressions of an expression, e.g. the arguments of a call,
+/// along with the variables and identifiers that are not expressions, e.g. in `A::$b`.
+#[derive(Default)]
+struct Children {
+    trees: Vec<Tree>,
+}
+
+impl Visitor for Children {
+    fn visit_expression(&mut self, expression: &Expression) {
+        self.trees.push(Tree::new(expression));
+    }
+
+    fn visit_variable(&mut self, variable: &Variable) {
+        self.trees.push(Tree::variable(variable));
+    }
+
+    fn visit_identifier(&mut self, identifier: &Identifier) {
+        self.trees.push(Tree::identifier(identifier));
+    }
+}
+
+fn label(expression: &Expression) -> String {
+    let label = match expression {
+        Expression::Literal(Literal::String(literal)) => return literal.value.to_string(),
+        Expression::Literal(Literal::Integer(literal)) => return literal.value.to_string(),
+        Expression::Literal(Literal::Float(literal)) => return literal.value.to_string(),
+        Expression::Variable(_) | Expression::Identifier(_) => unreachable!(),
+        Expression::ArithmeticOperation(operation) => match operation {
+            ArithmeticOperation::Addition { .. } => "+",
+            ArithmeticOperation::Subtraction { .. } => "-",
+            ArithmeticOperation::Multiplication { .. } => "*",
+            ArithmeticOperation::Division { .. } => "/",
+            ArithmeticOperation::Modulo { .. } => "%",
+            ArithmeticOperation::Exponentiation { .. } => "**",
+            ArithmeticOperation::Negative { .. } => "- (unary)",
+            ArithmeticOperation::Positive { .. } => "+ (unary)",
+            ArithmeticOperation::PreIncrement { .. } => "++ (prefix)",
+            ArithmeticOperation::PostIncrement { .. } => "++ (postfix)",
+            ArithmeticOperation::PreDecrement { .. } => "-- (prefix)",
+            ArithmeticOperation::PostDecrement { .. } => "-- (postfix)",
+        },
+        Expression::AssignmentOperation(operation) => match operation {
+            AssignmentOperation::Assign { .. } => "=",
+            AssignmentOperation::AssignByReference { .. } => "= &",
+            AssignmentOperation::Addition { .. } => "+=",
+            AssignmentOperation::Subtraction { .. } => "-=",
+            AssignmentOperation::Multiplication { .. } => "*=",
+            AssignmentOperation::Division { .. } => "/=",
+            AssignmentOperation::Modulo { .. } => "%=",
+            AssignmentOperation::Exponentiation { .. } => "**=",
+            AssignmentOperation::Concat { .. } => ".=",
+            AssignmentOperation::BitwiseAnd { .. } => "&=",
+            AssignmentOperation::BitwiseOr { .. } => "|=",
+            AssignmentOperation::BitwiseXor { .. } => "^=",
+            AssignmentOperation::LeftShift { .. } => "<<=",
+            AssignmentOperation::RightShift { .. } => ">>=",
+            AssignmentOperation::Coalesce { .. } => "??=",
+        },
+        Expression::BitwiseOperation(operation) => match operation {
+            BitwiseOperation::And { .. } => "&",
+            BitwiseOperation::Or { .. } => "|",
+            BitwiseOperation::Xor { .. } => "^",
+            BitwiseOperation::LeftShift { .. } => "<<",
+            BitwiseOperation::RightShift { .. } => ">>",
+            BitwiseOperation::Not { .. } => "~",
+        },
+        Expression::ComparisonOperation(operation) => match operation {
+            ComparisonOperation::Equal { .. } => "==",
+            ComparisonOperation::Identical { .. } => "===",
+            ComparisonOperation::NotEqual { .. } => "!=",
+            ComparisonOperation::AngledNotEqual { .. } => "<>",
+            ComparisonOperation::NotIdentical { .. } => "!==",
+            ComparisonOperation::LessThan { .. } => "<",
+            ComparisonOperation::GreaterThan { .. } => ">",
+            ComparisonOperation::LessThanOrEqual { .. } => "<=",
+            ComparisonOperation::GreaterThanOrEqual { .. } => ">=",
+            ComparisonOperation::Spaceship { .. } => "<=>",
+        },
+        Expression::LogicalOperation(operation) => match operation {
+            LogicalOperation::And { .. } => "&&",
+            LogicalOperation::Or { .. } => "||",
+            LogicalOperation::Not { .. } => "!",
+            LogicalOperation::LogicalAnd { .. } => "and",
+            LogicalOperation::LogicalOr { .. } => "or",
+            LogicalOperation::LogicalXor { .. } => "xor",
+        },
+        Expression::Concat { .. } => ".",
+        Expression::Instanceof { .. } => "instanceof",
+        Expression::Pipe { .. } => "|>",
+        Expression::Parenthesized { .. } => "()",
+        Expression::ErrorSuppress { .. } => "@",
+        Expression::ShortTernary { .. } => "?:",
+        Expression::Ternary { .. } => "? :",
+        Expression::Coalesce { .. } => "??",
+        Expression::Cast { kind, .. } => match kind {
+            CastKind::Int => "(int)",
+            CastKind::Bool => "(bool)",
+            CastKind::Float => "(float)",
+            CastKind::String => "(string)",
+            CastKind::Array => "(array)",
+            CastKind::Object => "(object)",
+            CastKind::Unset => "(unset)",
+        },
+        Expression::Eval { .. } => "eval",
+        Expression::Empty { .. } => "empty",
+        Expression::Exit(exit) => match exit.kind {
+            ExitKind::Exit => "exit",
+            ExitKind::Die => "die",
+        },
+        Expression::Isset { .. } => "isset",
+        Expression::Unset { .. } => "unset",
+        Expression::Print { .. } => "print",
+        Expression::Include { .. } => "include",
+        Expression::IncludeOnce { .. } => "include_once",
+        Expression::Require { .. } => "require",
+        Expression::RequireOnce { .. } => "require_once",
+        Expression::FunctionCall { .. } => "call",
+        Expression::FunctionClosureCreation { .. } => "call (...)",
+        Expression::MethodCall { .. } => "->()",
+        Expression::MethodClosureCreation { .. } => "->(...)",
+        Expression::NullsafeMethodCall { .. } => "?->()",
+        Expression::StaticMethodCall { .. } | Expression::StaticVariableMethodCall { .. } => "::()",
+        Expression::StaticMethodClosureCreation { .. }
+        | Expression::StaticVariableMethodClosureCreation { .. } => "::(...)",
+        Expression::PropertyFetch { .. } => "->",
+        Expression::NullsafePropertyFetch { .. } => "?->",
+        Expression::StaticPropertyFetch { .. } | Expression::ConstantFetch { .. } => "::",
+        Expression::ArrayIndex { .. } => "[]",
+        Expression::Static { .. } => "static",
+        Expression::Self_ { .. } => "self",
+        Expression::Parent { .. } => "parent",
+        Expression::ShortArray { .. } => "[...]",
+        Expression::Array { .. } => "array(...)",
+        Expression::List { .. } => "list(...)",
+        Expression::Closure(_) => "function",
+        Expression::ArrowFunction(_) => "fn",
+        Expression::New { .. } => "new",
+        Expression::AnonymousClass(_) => "new class",
+        Expression::InterpolatedString { .. } => "\"...\"",
+        Expression::Heredoc { .. } => "<<<",
+        Expression::Nowdoc { .. } => "<<<''",
+        Expression::ShellExec { .. } => "`...`",
+        Expression::Bool { value: true, .. } => "true",
+        Expression::Bool { value: false, .. } => "false",
+        Expression::Null { .. } => "null",
+        Expression::MagicConstant(constant) => match constant {
+            MagicConstant::Directory(_) => "__DIR__",
+            MagicConstant::File(_) => "__FILE__",
+            MagicConstant::Line(_) => "__LINE__",
+            MagicConstant::Class(_) => "__CLASS__",
+            MagicConstant::Function(_) => "__FUNCTION__",
+            MagicConstant::Method(_) => "__METHOD__",
+            MagicConstant::Namespace(_) => "__NAMESPACE__",
+            MagicConstant::Trait(_) => "__TRAIT__",
+            MagicConstant::CompilerHaltOffset(_) => "__COMPILER_HALT_OFFSET__",
+        },
+        Expression::Clone { .. } => "clone",
+        Expression::Match { .. } => "match",
+        Expression::Throw { .. } => "throw",
+        Expression::Yield { .. } => "yield",
+        Expression::YieldFrom { .. } => "yield from",
+        Expression::Noop { .. } => "noop",
+        Expression::Missing { .. } => "<missing>",
+    };
+
+    label.to_string()
+}
+
+fn variable_label(variable: &Variable) -> String {
+    match variable {
+        Variable::SimpleVariable(variable) => variable.name.to_string(),
+        Variable::VariableVariable(variable) => format!("${}", variable_label(&variable.variable)),
+        Variable::BracedVariableVariable(_) => "${}".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::token::Span;
+    use crate::parser::ast::Expression;
+    use crate::parser::ast::Statement;
+
+    fn tree(code: &str) -> String {
+        let program = crate::parse(&format!("<?php {};", code)).unwrap();
+
+        match &program[1] {
+            Statement::Expression(statement) => statement.expression.debug_tree(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_precedence_grouping() {
+        assert_eq!(
+            tree("!$a instanceof B && $c ?? $d"),
+            "\
+??
+├── &&
+│   ├── !
+│   │   └── instanceof
+│   │       ├── $a
+│   │       └── B
+│   └── $c
+└── $d
+"
+        );
+
+        assert_eq!(
+            tree("$a ? $b : ($c ? $d : $e)"),
+            "\
+? :
+├── $a
+├── $b
+└── ()
+    └── ? :
+        ├── $c
+        ├── $d
+        └── $e
+"
+        );
+    }
+
+    #[test]
+    fn test_calls_and_fetches() {
+        assert_eq!(
+            tree("$a->b($c . 'd', ...$e)[0]::$f"),
+            "\
+::
+├── []
+│   ├── ->()
+│   │   ├── $a
+│   │   ├── b
+│   │   ├── .
+│   │   │   ├── $c
+│   │   │   └── 'd'
+│   │   └── $e
+│   └── 0
+└── $f
+"
+        );
+
+        assert_eq!(
+            tree("fn() => $$a + (int) foo(...)"),
+            "\
+fn
+└── +
+    ├── $$a
+    └── (int)
+        └── call (...)
+            └── foo
+"
+        );
+
+        let noop = Expression::Noop {
+            span: Span::new(1, 1, 0),
+        };
+        assert_eq!(noop.debug_tree(), "noop\n");
+    }
+}
@@ -42,6 +42,7 @@ impl Node for DeclareEntryGroup {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum DeclareBody {
     // declaration is terminated with `;`
     Noop {
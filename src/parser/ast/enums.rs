@@ -2,9 +2,12 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::analysis::const_eval::EvalResult;
+use crate::analysis::const_eval::Evaluator;
 use crate::lexer::token::Span;
 use crate::node::Node;
 use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::comments::CommentGroup;
 use crate::parser::ast::constant::ClassishConstant;
 use crate::parser::ast::functions::ConcreteMethod;
 use crate::parser::ast::identifiers::SimpleIdentifier;
@@ -13,6 +16,7 @@ use crate::parser::ast::Expression;
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct UnitEnumCase {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>, // `#[Foo]`
     pub start: Span,                     // `case`
     pub name: SimpleIdentifier,          // `Bar`
@@ -27,17 +31,18 @@ impl Node for UnitEnumCase {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum UnitEnumMember {
-    Case(UnitEnumCase),         // `case Bar;`
-    Method(ConcreteMethod),     // `public function foo(): void { ... }`
-    Constant(ClassishConstant), // `public const FOO = 123;`
+    Case(UnitEnumCase),          // `case Bar;`
+    Method(Box<ConcreteMethod>), // `public function foo(): void { ... }`
+    Constant(ClassishConstant),  // `public const FOO = 123;`
 }
 
 impl Node for UnitEnumMember {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         match self {
             UnitEnumMember::Case(case) => vec![case],
-            UnitEnumMember::Method(method) => vec![method],
+            UnitEnumMember::Method(method) => vec![method.as_mut()],
             UnitEnumMember::Constant(constant) => vec![constant],
         }
     }
@@ -63,6 +68,7 @@ impl Node for UnitEnumBody {
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct UnitEnumStatement {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>,   // `#[Foo]`
     pub r#enum: Span,                      // `enum`
     pub name: SimpleIdentifier,            // `Foo`
@@ -70,6 +76,34 @@ pub struct UnitEnumStatement {
     pub body: UnitEnumBody,                // `{ ... }`
 }
 
+impl UnitEnumStatement {
+    pub fn cases(&self) -> impl Iterator<Item = &UnitEnumCase> {
+        self.body.members.iter().filter_map(|member| match member {
+            UnitEnumMember::Case(case) => Some(case),
+            _ => None,
+        })
+    }
+
+    pub fn methods(&self) -> impl Iterator<Item = &ConcreteMethod> {
+        self.body.members.iter().filter_map(|member| match member {
+            UnitEnumMember::Method(method) => Some(method.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub fn constants(&self) -> impl Iterator<Item = &ClassishConstant> {
+        self.body.members.iter().filter_map(|member| match member {
+            UnitEnumMember::Constant(constant) => Some(constant),
+            _ => None,
+        })
+    }
+
+    /// Always `None`, for symmetry with [`BackedEnumStatement::backed_type`].
+    pub fn backed_type(&self) -> Option<&BackedEnumType> {
+        None
+    }
+}
+
 impl Node for UnitEnumStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         let mut children: Vec<&mut dyn Node> = vec![&mut self.name];
@@ -83,6 +117,7 @@ impl Node for UnitEnumStatement {
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum BackedEnumType {
     String(Span, Span), // `:` + `string`
     Int(Span, Span),    // `:` + `int`
@@ -95,6 +130,7 @@ impl Node for BackedEnumType {
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct BackedEnumCase {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>, // `#[Foo]`
     pub case: Span,                      // `case`
     pub name: SimpleIdentifier,          // `Bar`
@@ -111,9 +147,10 @@ impl Node for BackedEnumCase {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum BackedEnumMember {
     Case(BackedEnumCase),
-    Method(ConcreteMethod),
+    Method(Box<ConcreteMethod>),
     Constant(ClassishConstant),
 }
 
@@ -121,7 +158,7 @@ impl Node for BackedEnumMember {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         match self {
             BackedEnumMember::Case(case) => vec![case],
-            BackedEnumMember::Method(method) => vec![method],
+            BackedEnumMember::Method(method) => vec![method.as_mut()],
             BackedEnumMember::Constant(constant) => vec![constant],
         }
     }
@@ -147,6 +184,7 @@ impl Node for BackedEnumBody {
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct BackedEnumStatement {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>,   // `#[Foo]`
     pub r#enum: Span,                      // `enum`
     pub name: SimpleIdentifier,            // `Foo`
@@ -155,6 +193,44 @@ pub struct BackedEnumStatement {
     pub body: BackedEnumBody,              // `{ ... }`
 }
 
+impl BackedEnumStatement {
+    pub fn cases(&self) -> impl Iterator<Item = &BackedEnumCase> {
+        self.body.members.iter().filter_map(|member| match member {
+            BackedEnumMember::Case(case) => Some(case),
+            _ => None,
+        })
+    }
+
+    pub fn methods(&self) -> impl Iterator<Item = &ConcreteMethod> {
+        self.body.members.iter().filter_map(|member| match member {
+            BackedEnumMember::Method(method) => Some(method.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub fn constants(&self) -> impl Iterator<Item = &ClassishConstant> {
+        self.body.members.iter().filter_map(|member| match member {
+            BackedEnumMember::Constant(constant) => Some(constant),
+            _ => None,
+        })
+    }
+
+    pub fn backed_type(&self) -> Option<&BackedEnumType> {
+        Some(&self.backed_type)
+    }
+
+    /// The value of each case, evaluated statically, see [`Evaluator`].
+    ///
+    /// Values referring to constants other than the builtin ones, including constants of the
+    /// enum itself, cannot be evaluated.
+    pub fn case_values(&self) -> impl Iterator<Item = (&BackedEnumCase, EvalResult)> {
+        let evaluator = Evaluator::new();
+
+        self.cases()
+            .map(move |case| (case, evaluator.evaluate(&case.value)))
+    }
+}
+
 impl Node for BackedEnumStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         let mut children: Vec<&mut dyn Node> = vec![&mut self.name, &mut self.backed_type];
@@ -165,3 +241,69 @@ impl Node for BackedEnumStatement {
         children
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::const_eval::EvalError;
+    use crate::analysis::const_eval::PhpValue;
+    use crate::parser::ast::Statement;
+
+    #[test]
+    fn test_enum_accessors() {
+        let program = crate::parse(
+            "<?php
+enum Suit {
+    case Hearts;
+    const Wild = self::Hearts;
+    case Spades;
+    public function color(): string { return 'red'; }
+}
+
+enum Status: string {
+    case Active = 'active';
+    case Archived = 'arch' . 'ived';
+    case Unknown = self::DEFAULT;
+    const DEFAULT = 'unknown';
+}
+",
+        )
+        .unwrap();
+
+        let unit = match &program[1] {
+            Statement::UnitEnum(unit) => unit,
+            statement => panic!("expected a unit enum, found {:?}", statement),
+        };
+        let cases = unit
+            .cases()
+            .map(|case| case.name.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(cases, vec!["Hearts", "Spades"]);
+        assert_eq!(unit.methods().count(), 1);
+        assert_eq!(unit.constants().count(), 1);
+        assert!(unit.backed_type().is_none());
+
+        let backed = match &program[2] {
+            Statement::BackedEnum(backed) => backed,
+            statement => panic!("expected a backed enum, found {:?}", statement),
+        };
+        assert!(backed.backed_type().is_some());
+        assert_eq!(backed.methods().count(), 0);
+        assert_eq!(backed.constants().count(), 1);
+
+        let values = backed
+            .case_values()
+            .map(|(case, value)| (case.name.to_string(), value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                ("Active".to_string(), Ok(PhpValue::String("active".into()))),
+                (
+                    "Archived".to_string(),
+                    Ok(PhpValue::String("archived".into()))
+                ),
+                ("Unknown".to_string(), Err(EvalError::Unsupported)),
+            ]
+        );
+    }
+}
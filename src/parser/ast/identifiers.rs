@@ -11,6 +11,7 @@ use crate::parser::ast::Expression;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum Identifier {
     SimpleIdentifier(SimpleIdentifier),
     DynamicIdentifier(DynamicIdentifier),
@@ -32,6 +33,13 @@ pub struct SimpleIdentifier {
     pub value: ByteString,
 }
 
+impl SimpleIdentifier {
+    /// The kind of the identifier, when used as the name of a class, function, or constant.
+    pub fn kind(&self) -> NameKind {
+        NameKind::of(&self.value)
+    }
+}
+
 impl Node for SimpleIdentifier {
     //
 }
@@ -42,6 +50,30 @@ impl Display for SimpleIdentifier {
     }
 }
 
+/// The kind of a name, which determines how it is resolved against the current namespace
+/// and `use` imports.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NameKind {
+    Unqualified,    // `Bar`
+    Qualified,      // `Foo\Bar`
+    FullyQualified, // `\Foo\Bar`
+    Relative,       // `namespace\Bar`
+}
+
+impl NameKind {
+    pub fn of(name: &[u8]) -> Self {
+        if name.starts_with(b"\\") {
+            NameKind::FullyQualified
+        } else if name.len() > 10 && name[..10].eq_ignore_ascii_case(b"namespace\\") {
+            NameKind::Relative
+        } else if name.contains(&b'\\') {
+            NameKind::Qualified
+        } else {
+            NameKind::Unqualified
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct DynamicIdentifier {
@@ -55,3 +87,19 @@ impl Node for DynamicIdentifier {
         vec![self.expr.as_mut()]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::NameKind;
+
+    #[test]
+    fn test_name_kind() {
+        assert_eq!(NameKind::of(b"Bar"), NameKind::Unqualified);
+        assert_eq!(NameKind::of(b"Foo\\Bar"), NameKind::Qualified);
+        assert_eq!(NameKind::of(b"\\Foo\\Bar"), NameKind::FullyQualified);
+        assert_eq!(NameKind::of(b"\\Bar"), NameKind::FullyQualified);
+        assert_eq!(NameKind::of(b"namespace\\Bar"), NameKind::Relative);
+        assert_eq!(NameKind::of(b"NAMESPACE\\Foo\\Bar"), NameKind::Relative);
+        assert_eq!(NameKind::of(b"Namespaces\\Bar"), NameKind::Qualified);
+    }
+}
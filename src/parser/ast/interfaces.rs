@@ -5,18 +5,22 @@ use serde::Serialize;
 use crate::lexer::token::Span;
 use crate::node::Node;
 use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::comments::CommentGroup;
 use crate::parser::ast::constant::ClassishConstant;
 use crate::parser::ast::functions::AbstractConstructor;
 use crate::parser::ast::functions::AbstractMethod;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::properties::Property;
 use crate::parser::ast::utils::CommaSeparated;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum InterfaceMember {
     Constant(ClassishConstant),       // `public const FOO = 123;`
     Constructor(AbstractConstructor), // `public function __construct(): void;`
     Method(AbstractMethod),           // `public function foo(): void;`
+    Property(Property),               // `public string $foo { get; }`
 }
 
 impl Node for InterfaceMember {
@@ -25,6 +29,7 @@ impl Node for InterfaceMember {
             InterfaceMember::Constant(constant) => vec![constant],
             InterfaceMember::Constructor(constructor) => vec![constructor],
             InterfaceMember::Method(method) => vec![method],
+            InterfaceMember::Property(property) => vec![property],
         }
     }
 }
@@ -62,6 +67,7 @@ impl Node for InterfaceBody {
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct InterfaceStatement {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>,   // `#[Foo]`
     pub interface: Span,                   // `interface`
     pub name: SimpleIdentifier,            // `Foo`
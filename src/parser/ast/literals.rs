@@ -8,12 +8,31 @@ use crate::node::Node;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum Literal {
     String(LiteralString),
     Integer(LiteralInteger),
     Float(LiteralFloat),
 }
 
+impl Literal {
+    pub fn span(&self) -> Span {
+        match self {
+            Literal::String(literal) => literal.span,
+            Literal::Integer(literal) => literal.span,
+            Literal::Float(literal) => literal.span,
+        }
+    }
+
+    pub fn value(&self) -> &ByteString {
+        match self {
+            Literal::String(literal) => &literal.value,
+            Literal::Integer(literal) => &literal.value,
+            Literal::Float(literal) => &literal.value,
+        }
+    }
+}
+
 impl Node for Literal {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         match self {
@@ -29,6 +48,25 @@ impl Node for Literal {
 pub struct LiteralString {
     pub value: ByteString,
     pub span: Span,
+    /// The length of the literal in the source, when its value is left empty as the literal
+    /// exceeds [`crate::ResourceLimits::max_literal_length`], see [`LiteralString::source`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deferred: Option<usize>,
+}
+
+impl LiteralString {
+    /// Whether the value of this literal was left empty, as the literal exceeds
+    /// [`crate::ResourceLimits::max_literal_length`], see [`LiteralString::deferred`].
+    pub fn is_deferred(&self) -> bool {
+        self.deferred.is_some()
+    }
+
+    /// The source of a deferred literal, quotes and escape sequences included, within the
+    /// given input the literal was parsed from.
+    pub fn source<'a>(&self, input: &'a [u8]) -> Option<&'a [u8]> {
+        self.deferred
+            .and_then(|length| input.get(self.span.position..self.span.position + length))
+    }
 }
 
 impl Node for LiteralString {
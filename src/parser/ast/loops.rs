@@ -28,6 +28,7 @@ impl Node for ForeachStatement {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum ForeachStatementIterator {
     // `*expression* as &$var`
     Value {
@@ -67,6 +68,7 @@ impl Node for ForeachStatementIterator {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum ForeachStatementBody {
     Statement(Box<Statement>),
     Block {
@@ -131,6 +133,7 @@ impl Node for ForStatementIterator {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum ForStatementBody {
     Statement(Box<Statement>),
     Block {
@@ -188,6 +191,7 @@ impl Node for WhileStatement {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum WhileStatementBody {
     Statement(Box<Statement>),
     Block {
@@ -211,6 +215,7 @@ impl Node for WhileStatementBody {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum Level {
     Literal(LiteralInteger),
     Parenthesized {
@@ -220,6 +225,38 @@ pub enum Level {
     },
 }
 
+impl Level {
+    /// Return the number of levels, or `None` if the literal overflows.
+    pub fn value(&self) -> Option<usize> {
+        match self {
+            Level::Literal(literal) => {
+                let value = literal
+                    .value
+                    .iter()
+                    .filter(|byte| **byte != b'_')
+                    .map(|byte| *byte as char)
+                    .collect::<String>()
+                    .to_ascii_lowercase();
+
+                let (digits, radix) = if let Some(digits) = value.strip_prefix("0x") {
+                    (digits, 16)
+                } else if let Some(digits) = value.strip_prefix("0b") {
+                    (digits, 2)
+                } else if let Some(digits) = value.strip_prefix("0o") {
+                    (digits, 8)
+                } else if value.len() > 1 && value.starts_with('0') {
+                    (&value[1..], 8)
+                } else {
+                    (value.as_str(), 10)
+                };
+
+                usize::from_str_radix(digits, radix).ok()
+            }
+            Level::Parenthesized { level, .. } => level.value(),
+        }
+    }
+}
+
 impl Node for Level {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         match self {
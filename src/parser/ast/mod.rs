@@ -4,8 +4,10 @@ use serde::Serialize;
 
 use crate::lexer::byte_string::ByteString;
 use crate::lexer::token::Span;
+use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
 use crate::node::Node;
+use crate::parser::ast::arguments::Argument;
 use crate::parser::ast::arguments::ArgumentPlaceholder;
 use crate::parser::ast::arguments::{ArgumentList, SingleArgument};
 use crate::parser::ast::classes::AnonymousClass;
@@ -44,11 +46,13 @@ use crate::parser::ast::variables::Variable;
 
 pub mod arguments;
 pub mod attributes;
+pub mod calls;
 pub mod classes;
 pub mod comments;
 pub mod constant;
 pub mod control_flow;
 pub mod data_type;
+mod debug_tree;
 pub mod declares;
 pub mod enums;
 pub mod functions;
@@ -61,10 +65,14 @@ pub mod modifiers;
 pub mod namespaces;
 pub mod operators;
 pub mod properties;
+pub mod receivers;
+mod spans;
 pub mod traits;
 pub mod try_block;
 pub mod utils;
 pub mod variables;
+pub mod visitor;
+pub mod visitor_mut;
 
 pub type Block = Vec<Statement>;
 
@@ -76,8 +84,9 @@ impl Node for Block {
 
 pub type Program = Block;
 
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type")]
+#[non_exhaustive]
 pub enum UseKind {
     Normal,
     Function,
@@ -103,11 +112,21 @@ impl Node for StaticVar {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum Ending {
     Semicolon(Span),
     CloseTag(Span),
 }
 
+impl Ending {
+    pub fn span(&self) -> Span {
+        match self {
+            Ending::Semicolon(span) => *span,
+            Ending::CloseTag(span) => *span,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub struct HaltCompiler {
@@ -116,6 +135,29 @@ pub struct HaltCompiler {
 
 impl Node for HaltCompiler {}
 
+/// A region of a template delimited by foreign markers, e.g. `{{ $name }}`,
+/// which is kept as is instead of being parsed.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ForeignChunk {
+    pub span: Span,
+    // including the opening and closing markers
+    pub value: ByteString,
+}
+
+impl Node for ForeignChunk {}
+
+/// A statement which failed to parse, holding the tokens skipped while recovering from the
+/// error, see [`crate::parser::parse_tolerant`].
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ErrorStatement {
+    pub span: Span,
+    pub tokens: Vec<Token<'static>>,
+}
+
+impl Node for ErrorStatement {}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub struct StaticStatement {
@@ -209,78 +251,91 @@ impl Node for GroupUseStatement {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum Statement {
     FullOpeningTag(Span),
     ShortOpeningTag(Span),
     EchoOpeningTag(Span),
     ClosingTag(Span),
     InlineHtml(ByteString),
+    ForeignChunk(ForeignChunk),
     Label(LabelStatement),
     Goto(GotoStatement),
     HaltCompiler(HaltCompiler),
     Static(StaticStatement),
-    DoWhile(DoWhileStatement),
-    While(WhileStatement),
-    For(ForStatement),
-    Foreach(ForeachStatement),
+    DoWhile(Box<DoWhileStatement>),
+    While(Box<WhileStatement>),
+    For(Box<ForStatement>),
+    Foreach(Box<ForeachStatement>),
     Break(BreakStatement),
     Continue(ContinueStatement),
     Constant(ConstantStatement),
-    Function(FunctionStatement),
-    Class(ClassStatement),
-    Trait(TraitStatement),
-    Interface(InterfaceStatement),
-    If(IfStatement),
-    Switch(SwitchStatement),
+    Function(Box<FunctionStatement>),
+    Class(Box<ClassStatement>),
+    Trait(Box<TraitStatement>),
+    Interface(Box<InterfaceStatement>),
+    If(Box<IfStatement>),
+    Switch(Box<SwitchStatement>),
     Echo(EchoStatement),
     Expression(ExpressionStatement),
-    Return(ReturnStatement),
+    Return(Box<ReturnStatement>),
     Namespace(NamespaceStatement),
     Use(UseStatement),
     GroupUse(GroupUseStatement),
     Comment(Comment),
-    Try(TryStatement),
-    UnitEnum(UnitEnumStatement),
-    BackedEnum(BackedEnumStatement),
+    Try(Box<TryStatement>),
+    UnitEnum(Box<UnitEnumStatement>),
+    BackedEnum(Box<BackedEnumStatement>),
     Block(BlockStatement),
     Global(GlobalStatement),
-    Declare(DeclareStatement),
+    Declare(Box<DeclareStatement>),
     Noop(Span),
+    Error(ErrorStatement),
 }
 
+// Every statement of a block and every operand takes as much memory as the largest variant
+// of its enum, so the large and less common variants are boxed.
+#[cfg(target_pointer_width = "64")]
+const _: () = {
+    assert!(std::mem::size_of::<Statement>() <= 184);
+    assert!(std::mem::size_of::<Expression>() <= 128);
+};
+
 impl Node for Statement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         match self {
             Statement::Label(statement) => vec![statement],
             Statement::Goto(statement) => vec![statement],
             Statement::HaltCompiler(statement) => vec![statement],
+            Statement::ForeignChunk(statement) => vec![statement],
             Statement::Static(statement) => vec![statement],
-            Statement::DoWhile(statement) => vec![statement],
-            Statement::While(statement) => vec![statement],
-            Statement::For(statement) => vec![statement],
-            Statement::Foreach(statement) => vec![statement],
+            Statement::DoWhile(statement) => vec![statement.as_mut()],
+            Statement::While(statement) => vec![statement.as_mut()],
+            Statement::For(statement) => vec![statement.as_mut()],
+            Statement::Foreach(statement) => vec![statement.as_mut()],
             Statement::Break(statement) => vec![statement],
             Statement::Continue(statement) => vec![statement],
             Statement::Constant(statement) => vec![statement],
-            Statement::Function(statement) => vec![statement],
-            Statement::Class(statement) => vec![statement],
-            Statement::Trait(statement) => vec![statement],
-            Statement::Interface(statement) => vec![statement],
-            Statement::If(statement) => vec![statement],
-            Statement::Switch(statement) => vec![statement],
+            Statement::Function(statement) => vec![statement.as_mut()],
+            Statement::Class(statement) => vec![statement.as_mut()],
+            Statement::Trait(statement) => vec![statement.as_mut()],
+            Statement::Interface(statement) => vec![statement.as_mut()],
+            Statement::If(statement) => vec![statement.as_mut()],
+            Statement::Switch(statement) => vec![statement.as_mut()],
             Statement::Echo(statement) => vec![statement],
             Statement::Expression(statement) => vec![statement],
-            Statement::Return(statement) => vec![statement],
+            Statement::Return(statement) => vec![statement.as_mut()],
             Statement::Namespace(statement) => vec![statement],
             Statement::Use(statement) => vec![statement],
             Statement::GroupUse(statement) => vec![statement],
             Statement::Comment(statement) => vec![statement],
-            Statement::Try(statement) => vec![statement],
-            Statement::UnitEnum(statement) => vec![statement],
-            Statement::BackedEnum(statement) => vec![statement],
+            Statement::Try(statement) => vec![statement.as_mut()],
+            Statement::UnitEnum(statement) => vec![statement.as_mut()],
+            Statement::BackedEnum(statement) => vec![statement.as_mut()],
             Statement::Block(statement) => vec![statement],
             Statement::Global(statement) => vec![statement],
-            Statement::Declare(statement) => vec![statement],
+            Statement::Declare(statement) => vec![statement.as_mut()],
+            Statement::Error(statement) => vec![statement],
             _ => vec![],
         }
     }
@@ -335,6 +390,7 @@ impl Node for BlockStatement {
 // See https://www.php.net/manual/en/language.types.type-juggling.php#language.types.typecasting for more info.
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type")]
+#[non_exhaustive]
 pub enum CastKind {
     Int,
     Bool,
@@ -409,6 +465,7 @@ impl Node for Use {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum Expression {
     // eval("$a = 1")
     Eval {
@@ -420,25 +477,17 @@ pub enum Expression {
         empty: Span,                   // empty
         argument: Box<SingleArgument>, // ($a)
     },
-    // die, die(1)
-    Die {
-        die: Span,                             // die
-        argument: Option<Box<SingleArgument>>, // (1)
-    },
-    // exit, exit(1)
-    Exit {
-        exit: Span,                            // exit
-        argument: Option<Box<SingleArgument>>, // (1)
-    },
+    // exit, exit(), exit(1), die('message')
+    Exit(Box<ExitExpression>),
     // isset($a), isset($a, ...)
     Isset {
-        isset: Span,             // isset
-        arguments: ArgumentList, // `($a, ...)`
+        isset: Span,                  // isset
+        arguments: Box<ArgumentList>, // `($a, ...)`
     },
     // unset($a), isset($a, ...)
     Unset {
-        unset: Span,             // unset
-        arguments: ArgumentList, // `($a, ...)`
+        unset: Span,                  // unset
+        arguments: Box<ArgumentList>, // `($a, ...)`
     },
     // print(1), print 1;
     Print {
@@ -464,9 +513,10 @@ pub enum Expression {
         instanceof: Span,
         right: Box<Self>,
     },
-    // &$foo
-    Reference {
-        ampersand: Span,
+    // $a |> foo(...)
+    Pipe {
+        left: Box<Self>,
+        pipe_greater_than: Span,
         right: Box<Self>,
     },
     // ($a && $b)
@@ -509,57 +559,57 @@ pub enum Expression {
     },
     // `foo(...)`
     FunctionClosureCreation {
-        target: Box<Self>,                // `foo`
-        placeholder: ArgumentPlaceholder, // `(...)`
+        target: Box<Self>,                     // `foo`
+        placeholder: Box<ArgumentPlaceholder>, // `(...)`
     },
     // `$foo->bar(1, 2, 3)`
     MethodCall {
-        target: Box<Self>,       // `$foo`
-        arrow: Span,             // `->`
-        method: Box<Self>,       // `bar`
-        arguments: ArgumentList, // `(1, 2, 3)`
+        target: Box<Self>,            // `$foo`
+        arrow: Span,                  // `->`
+        method: Box<Self>,            // `bar`
+        arguments: Box<ArgumentList>, // `(1, 2, 3)`
     },
     // `$foo->bar(...)`
     MethodClosureCreation {
-        target: Box<Self>,                // `$foo`
-        arrow: Span,                      // `->`
-        method: Box<Self>,                // `bar`
-        placeholder: ArgumentPlaceholder, // `(...)`
+        target: Box<Self>,                     // `$foo`
+        arrow: Span,                           // `->`
+        method: Box<Self>,                     // `bar`
+        placeholder: Box<ArgumentPlaceholder>, // `(...)`
     },
     // `$foo?->bar(1, 2, 3)`
     NullsafeMethodCall {
-        target: Box<Self>,       // `$foo`
-        question_arrow: Span,    // `?->`
-        method: Box<Self>,       // `bar`
-        arguments: ArgumentList, // `(1, 2, 3)`
+        target: Box<Self>,            // `$foo`
+        question_arrow: Span,         // `?->`
+        method: Box<Self>,            // `bar`
+        arguments: Box<ArgumentList>, // `(1, 2, 3)`
     },
     // `Foo::bar(1, 2, 3)`
     StaticMethodCall {
-        target: Box<Self>,       // `Foo`
-        double_colon: Span,      // `::`
-        method: Identifier,      // `bar`
-        arguments: ArgumentList, // `(1, 2, 3)`
+        target: Box<Self>,            // `Foo`
+        double_colon: Span,           // `::`
+        method: Identifier,           // `bar`
+        arguments: Box<ArgumentList>, // `(1, 2, 3)`
     },
     // `Foo::$bar(1, 2, 3)`
     StaticVariableMethodCall {
-        target: Box<Self>,       // `Foo`
-        double_colon: Span,      // `::`
-        method: Variable,        // `$bar`
-        arguments: ArgumentList, // `(1, 2, 3)`
+        target: Box<Self>,            // `Foo`
+        double_colon: Span,           // `::`
+        method: Variable,             // `$bar`
+        arguments: Box<ArgumentList>, // `(1, 2, 3)`
     },
     // `Foo::bar(...)`
     StaticMethodClosureCreation {
-        target: Box<Self>,                // `Foo`
-        double_colon: Span,               // `::`
-        method: Identifier,               // `bar`
-        placeholder: ArgumentPlaceholder, // `(...)`
+        target: Box<Self>,                     // `Foo`
+        double_colon: Span,                    // `::`
+        method: Identifier,                    // `bar`
+        placeholder: Box<ArgumentPlaceholder>, // `(...)`
     },
     // `Foo::$bar(...)`
     StaticVariableMethodClosureCreation {
-        target: Box<Self>,                // `Foo`
-        double_colon: Span,               // `::`
-        method: Variable,                 // `$bar`
-        placeholder: ArgumentPlaceholder, // `(...)`
+        target: Box<Self>,                     // `Foo`
+        double_colon: Span,                    // `::`
+        method: Variable,                      // `$bar`
+        placeholder: Box<ArgumentPlaceholder>, // `(...)`
     },
     // `foo()->bar`
     PropertyFetch {
@@ -586,60 +636,66 @@ pub enum Expression {
         constant: Identifier, // `bar`
     },
     // `static`
-    Static,
+    Static {
+        span: Span,
+    },
     // `self`
-    Self_,
+    Self_ {
+        span: Span,
+    },
     // `parent`
-    Parent,
+    Parent {
+        span: Span,
+    },
     // `[1, 2, 3]`
     ShortArray {
-        start: Span,                      // `[`
-        items: CommaSeparated<ArrayItem>, // `1, 2, 3`
-        end: Span,                        // `]`
+        start: Span,                           // `[`
+        items: Box<CommaSeparated<ArrayItem>>, // `1, 2, 3`
+        end: Span,                             // `]`
     },
     // `array(1, 2, 3)`
-    Array {
-        array: Span,                      // `array`
-        start: Span,                      // `(`
-        items: CommaSeparated<ArrayItem>, // `1, 2, 3`
-        end: Span,                        // `)`
-    },
+    Array(Box<ArrayExpression>),
     // list($a, $b)
-    List {
-        list: Span,            // `list`
-        start: Span,           // `(`
-        items: Vec<ListEntry>, // `$a, $b`
-        end: Span,             // `)`
-    },
+    List(Box<ListExpression>),
     // `function() {}`
-    Closure(Closure),
+    Closure(Box<Closure>),
     // `fn() => $foo`
-    ArrowFunction(ArrowFunction),
+    ArrowFunction(Box<ArrowFunction>),
     // `new Foo(1, 2, 3)`
     New {
-        new: Span,                       // `new`
-        target: Box<Self>,               // `Foo`
-        arguments: Option<ArgumentList>, // `(1, 2, 3)`
+        new: Span,                            // `new`
+        target: Box<Self>,                    // `Foo`
+        arguments: Option<Box<ArgumentList>>, // `(1, 2, 3)`
     },
     // `"foo $bar foo"`
     InterpolatedString {
+        start: Span, // `"`
         parts: Vec<StringPart>,
+        end: Span, // `"`
     },
     // `<<<"EOT"` / `<<<EOT`
     Heredoc {
+        start: Span, // `<<<"EOT"`
         parts: Vec<StringPart>,
+        end: Span, // `EOT`
     },
     // `<<<'EOT'`
     Nowdoc {
-        value: ByteString,
+        start: Span, // `<<<'EOT'`
+        parts: Vec<StringPart>,
+        end: Span, // `EOT`
     },
     // ``foo``
     ShellExec {
+        start: Span, // `
         parts: Vec<StringPart>,
+        end: Span, // `
     },
-    AnonymousClass(AnonymousClass),
+    AnonymousClass(Box<AnonymousClass>),
+    // `true`, `false`
     Bool {
         value: bool,
+        span: Span,
     },
     ArrayIndex {
         array: Box<Self>,
@@ -647,7 +703,10 @@ pub enum Expression {
         index: Option<Box<Self>>,
         right_bracket: Span,
     },
-    Null,
+    // `null`
+    Null {
+        span: Span,
+    },
     MagicConstant(MagicConstant),
     // `foo() ?: bar()`
     ShortTernary {
@@ -669,36 +728,78 @@ pub enum Expression {
         double_question: Span,
         rhs: Box<Self>,
     },
+    // `clone $foo`
     Clone {
-        target: Box<Self>,
-    },
-
-    Match {
-        keyword: Span,
-        left_parenthesis: Span,
-        condition: Box<Self>,
-        right_parenthesis: Span,
-        left_brace: Span,
-        default: Option<Box<DefaultMatchArm>>,
-        arms: Vec<MatchArm>,
-        right_brace: Span,
+        clone: Span,       // `clone`
+        target: Box<Self>, // `$foo`
     },
+    // `match ($a) { 1 => 'one', default => 'other' }`
+    Match(Box<MatchExpression>),
+    // `throw $e`
     Throw {
-        value: Box<Self>,
+        throw: Span,      // `throw`
+        value: Box<Self>, // `$e`
     },
+    // `yield`, `yield $value`, `yield $key => $value`
     Yield {
-        key: Option<Box<Self>>,
-        value: Option<Box<Self>>,
+        r#yield: Span,            // `yield`
+        key: Option<Box<Self>>,   // `$key`
+        value: Option<Box<Self>>, // `$value`
     },
+    // `yield from foo()`
     YieldFrom {
-        value: Box<Self>,
+        r#yield: Span,    // `yield`
+        from: Span,       // `from`
+        value: Box<Self>, // `foo()`
     },
+    // `(int) foo()`
     Cast {
-        cast: Span,
-        kind: CastKind,
-        value: Box<Self>,
+        cast: Span,       // `(int)`
+        length: usize,    // the length of the cast as written, e.g. 7 for `( int )`
+        kind: CastKind,   // `int`
+        value: Box<Self>, // `foo()`
+    },
+    // the omitted `then` of `foo() ? : bar()`, with a zero-width span following the `?`
+    Noop {
+        span: Span,
+    },
+    // an expression which is missing when parsing tolerantly, e.g. in `$a = ;`,
+    // with a zero-width span where it was expected
+    Missing {
+        span: Span,
     },
-    Noop,
+}
+
+impl Expression {
+    /// Whether this expression is wrapped in parentheses in the source, e.g. `(1 + 2)`.
+    pub fn is_parenthesized(&self) -> bool {
+        matches!(self, Expression::Parenthesized { .. })
+    }
+
+    /// This expression without the parentheses wrapping it, e.g. `$a` for `(($a))`.
+    ///
+    /// ```
+    /// use php_parser_rs::parser::ast::Expression;
+    /// use php_parser_rs::parser::ast::Statement;
+    ///
+    /// let program = php_parser_rs::parse("<?php ((1 + 2));").unwrap();
+    ///
+    /// if let Statement::Expression(statement) = &program[1] {
+    ///     assert!(statement.expression.is_parenthesized());
+    ///     assert!(matches!(
+    ///         statement.expression.unparenthesized(),
+    ///         Expression::ArithmeticOperation(_)
+    ///     ));
+    /// }
+    /// ```
+    pub fn unparenthesized(&self) -> &Expression {
+        let mut expression = self;
+        while let Expression::Parenthesized { expr, .. } = expression {
+            expression = expr;
+        }
+
+        expression
+    }
 }
 
 impl Node for Expression {
@@ -706,28 +807,15 @@ impl Node for Expression {
         match self {
             Expression::Eval { eval: _, argument } => vec![argument.as_mut()],
             Expression::Empty { empty: _, argument } => vec![argument.as_mut()],
-            Expression::Die { die: _, argument } => {
-                if let Some(argument) = argument {
-                    vec![argument.as_mut()]
-                } else {
-                    vec![]
-                }
-            }
-            Expression::Exit { exit: _, argument } => {
-                if let Some(argument) = argument {
-                    vec![argument.as_mut()]
-                } else {
-                    vec![]
-                }
-            }
+            Expression::Exit(exit) => exit.children(),
             Expression::Isset {
                 isset: _,
                 arguments,
-            } => vec![arguments],
+            } => vec![arguments.as_mut()],
             Expression::Unset {
                 unset: _,
                 arguments,
-            } => vec![arguments],
+            } => vec![arguments.as_mut()],
             Expression::Print {
                 print: _,
                 value,
@@ -757,10 +845,11 @@ impl Node for Expression {
                 instanceof: _,
                 right,
             } => vec![left.as_mut(), right.as_mut()],
-            Expression::Reference {
-                ampersand: _,
+            Expression::Pipe {
+                left,
+                pipe_greater_than: _,
                 right,
-            } => vec![right.as_mut()],
+            } => vec![left.as_mut(), right.as_mut()],
             Expression::Parenthesized {
                 start: _,
                 expr,
@@ -789,7 +878,7 @@ impl Node for Expression {
                 arrow: _,
                 method,
                 arguments,
-            } => vec![target.as_mut(), method.as_mut(), arguments],
+            } => vec![target.as_mut(), method.as_mut(), arguments.as_mut()],
             Expression::MethodClosureCreation {
                 target,
                 arrow: _,
@@ -801,19 +890,19 @@ impl Node for Expression {
                 question_arrow: _,
                 method,
                 arguments,
-            } => vec![target.as_mut(), method.as_mut(), arguments],
+            } => vec![target.as_mut(), method.as_mut(), arguments.as_mut()],
             Expression::StaticMethodCall {
                 target,
                 double_colon: _,
                 method,
                 arguments,
-            } => vec![target.as_mut(), method, arguments],
+            } => vec![target.as_mut(), method, arguments.as_mut()],
             Expression::StaticVariableMethodCall {
                 target,
                 double_colon: _,
                 method,
                 arguments,
-            } => vec![target.as_mut(), method, arguments],
+            } => vec![target.as_mut(), method, arguments.as_mut()],
             Expression::StaticMethodClosureCreation {
                 target,
                 double_colon: _,
@@ -846,26 +935,16 @@ impl Node for Expression {
                 double_colon: _,
                 constant,
             } => vec![target.as_mut(), constant],
-            Expression::Static => vec![],
-            Expression::Self_ => vec![],
-            Expression::Parent => vec![],
+            Expression::Static { span: _ } => vec![],
+            Expression::Self_ { span: _ } => vec![],
+            Expression::Parent { span: _ } => vec![],
             Expression::ShortArray {
                 start: _,
                 items,
                 end: _,
-            } => vec![items],
-            Expression::Array {
-                array: _,
-                start: _,
-                items,
-                end: _,
-            } => vec![items],
-            Expression::List {
-                list: _,
-                start: _,
-                items,
-                end: _,
-            } => items.iter_mut().map(|item| item as &mut dyn Node).collect(),
+            } => vec![items.as_mut()],
+            Expression::Array(array) => array.children(),
+            Expression::List(list) => list.children(),
             Expression::Closure(closure) => closure.children(),
             Expression::ArrowFunction(function) => function.children(),
             Expression::New {
@@ -875,22 +954,32 @@ impl Node for Expression {
             } => {
                 let mut children: Vec<&mut dyn Node> = vec![target.as_mut()];
                 if let Some(arguments) = arguments {
-                    children.push(arguments);
+                    children.push(arguments.as_mut());
                 }
                 children
             }
-            Expression::InterpolatedString { parts } => {
-                parts.iter_mut().map(|part| part as &mut dyn Node).collect()
-            }
-            Expression::Heredoc { parts } => {
-                parts.iter_mut().map(|part| part as &mut dyn Node).collect()
-            }
-            Expression::Nowdoc { value: _ } => vec![],
-            Expression::ShellExec { parts } => {
-                parts.iter_mut().map(|part| part as &mut dyn Node).collect()
-            }
+            Expression::InterpolatedString {
+                start: _,
+                parts,
+                end: _,
+            } => parts.iter_mut().map(|part| part as &mut dyn Node).collect(),
+            Expression::Heredoc {
+                start: _,
+                parts,
+                end: _,
+            } => parts.iter_mut().map(|part| part as &mut dyn Node).collect(),
+            Expression::Nowdoc {
+                start: _,
+                parts,
+                end: _,
+            } => parts.iter_mut().map(|part| part as &mut dyn Node).collect(),
+            Expression::ShellExec {
+                start: _,
+                parts,
+                end: _,
+            } => parts.iter_mut().map(|part| part as &mut dyn Node).collect(),
             Expression::AnonymousClass(class) => class.children(),
-            Expression::Bool { value: _ } => vec![],
+            Expression::Bool { value: _, span: _ } => vec![],
             Expression::ArrayIndex {
                 array: _,
                 left_bracket: _,
@@ -903,7 +992,7 @@ impl Node for Expression {
                 }
                 children
             }
-            Expression::Null => vec![],
+            Expression::Null { span: _ } => vec![],
             Expression::MagicConstant(constant) => constant.children(),
             Expression::ShortTernary {
                 condition,
@@ -922,30 +1011,14 @@ impl Node for Expression {
                 double_question: _,
                 rhs,
             } => vec![lhs.as_mut(), rhs.as_mut()],
-            Expression::Clone { target } => vec![target.as_mut()],
-            Expression::Match {
-                keyword: _,
-                left_parenthesis: _,
-                condition,
-                right_parenthesis: _,
-                left_brace: _,
-                default,
-                arms,
-                right_brace: _,
+            Expression::Clone { clone: _, target } => vec![target.as_mut()],
+            Expression::Match(expression) => expression.children(),
+            Expression::Throw { throw: _, value } => vec![value.as_mut()],
+            Expression::Yield {
+                r#yield: _,
+                key,
+                value,
             } => {
-                let mut children: Vec<&mut dyn Node> = vec![condition.as_mut()];
-                if let Some(default) = default {
-                    children.push(default.as_mut());
-                }
-                children.extend(
-                    arms.iter_mut()
-                        .map(|arm| arm as &mut dyn Node)
-                        .collect::<Vec<&mut dyn Node>>(),
-                );
-                children
-            }
-            Expression::Throw { value } => vec![value.as_mut()],
-            Expression::Yield { key, value } => {
                 let mut children: Vec<&mut dyn Node> = vec![];
                 if let Some(key) = key {
                     children.push(key.as_mut());
@@ -955,17 +1028,117 @@ impl Node for Expression {
                 }
                 children
             }
-            Expression::YieldFrom { value } => vec![value.as_mut()],
+            Expression::YieldFrom {
+                r#yield: _,
+                from: _,
+                value,
+            } => vec![value.as_mut()],
             Expression::Cast {
                 cast: _,
+                length: _,
                 kind: _,
                 value,
             } => vec![value.as_mut()],
-            Expression::Noop => vec![],
+            Expression::Noop { span: _ } => vec![],
+            Expression::Missing { span: _ } => vec![],
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitKind {
+    Exit, // `exit`
+    Die,  // `die`
+}
+
+/// `exit` or `die`, which behave like a function taking an optional status since PHP 8.4.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ExitExpression {
+    pub kind: ExitKind,
+    pub keyword: Span,                   // `exit`
+    pub arguments: Option<ArgumentList>, // `(1)`, or `()`
+}
+
+impl ExitExpression {
+    /// The status or message, e.g. `1` for `exit(1)`.
+    pub fn argument(&self) -> Option<&Argument> {
+        self.arguments
+            .as_ref()
+            .and_then(|arguments| arguments.arguments.first())
+    }
+}
+
+impl Node for ExitExpression {
+    fn children(&mut self) -> Vec<&mut dyn Node> {
+        match &mut self.arguments {
+            Some(arguments) => vec![arguments],
+            None => vec![],
         }
     }
 }
 
+/// `array(1, 2, 3)`
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ArrayExpression {
+    pub array: Span,                      // `array`
+    pub start: Span,                      // `(`
+    pub items: CommaSeparated<ArrayItem>, // `1, 2, 3`
+    pub end: Span,                        // `)`
+}
+
+impl Node for ArrayExpression {
+    fn children(&mut self) -> Vec<&mut dyn Node> {
+        vec![&mut self.items]
+    }
+}
+
+/// `list($a, $b)`
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ListExpression {
+    pub list: Span,            // `list`
+    pub start: Span,           // `(`
+    pub items: Vec<ListEntry>, // `$a, $b`
+    pub end: Span,             // `)`
+}
+
+impl Node for ListExpression {
+    fn children(&mut self) -> Vec<&mut dyn Node> {
+        self.items
+            .iter_mut()
+            .map(|item| item as &mut dyn Node)
+            .collect()
+    }
+}
+
+/// `match ($a) { 1 => 'one', default => 'other' }`
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchExpression {
+    pub keyword: Span,
+    pub left_parenthesis: Span,
+    pub condition: Expression,
+    pub right_parenthesis: Span,
+    pub left_brace: Span,
+    pub default: Option<Box<DefaultMatchArm>>,
+    pub arms: Vec<MatchArm>,
+    pub right_brace: Span,
+}
+
+impl Node for MatchExpression {
+    fn children(&mut self) -> Vec<&mut dyn Node> {
+        let mut children: Vec<&mut dyn Node> = vec![&mut self.condition];
+        if let Some(default) = &mut self.default {
+            children.push(default.as_mut());
+        }
+        children.extend(self.arms.iter_mut().map(|arm| arm as &mut dyn Node));
+        children
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct DefaultMatchArm {
@@ -1002,6 +1175,7 @@ impl Node for MatchArm {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum MagicConstant {
     Directory(Span),
     File(Span),
@@ -1020,22 +1194,85 @@ impl Node for MagicConstant {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum StringPart {
-    Literal(ByteString),
-    Expression(Box<Expression>),
+    Literal(LiteralStringPart),
+    Expression(ExpressionStringPart),
+}
+
+impl StringPart {
+    /// The position of the first byte of this part within the file.
+    ///
+    /// For heredoc and nowdoc literals, this is the position after
+    /// the closing label indentation has been stripped.
+    pub fn span(&self) -> Span {
+        match self {
+            StringPart::Literal(literal) => literal.span,
+            StringPart::Expression(expression) => expression.start,
+        }
+    }
 }
 
 impl Node for StringPart {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         match self {
-            StringPart::Literal(_) => vec![],
-            StringPart::Expression(expression) => vec![expression.as_mut()],
+            StringPart::Literal(literal) => vec![literal],
+            StringPart::Expression(expression) => vec![expression],
         }
     }
 }
 
+/// A literal segment of an interpolated string.
+///
+/// Heredoc and nowdoc literals never span more than a single line,
+/// so that each line can be mapped back to its exact position.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct LiteralStringPart {
+    pub span: Span,
+    pub value: ByteString,
+}
+
+impl Node for LiteralStringPart {
+    //
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ExpressionStringPart {
+    pub syntax: InterpolationSyntax,
+    pub start: Span, // `{`, `${` or the first token of `$foo->bar`
+    pub expression: Box<Expression>,
+    pub end: Span, // `}` or the last token of `$foo->bar`
+}
+
+/// The syntax used to interpolate an expression into a string.
+///
+/// Each syntax accepts a different subset of the expression grammar.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum InterpolationSyntax {
+    /// `"$foo"`, `"$foo[0]"`, `"$foo[bar]"`, `"$foo[$bar]"`, `"$foo->bar"` or `"$foo?->bar"`.
+    ///
+    /// Only a single array index or property fetch is allowed, with no method calls.
+    Simple,
+    /// `"${foo}"`, `"${foo[0]}"` or `"${expr}"`.
+    DollarBrace,
+    /// `"{$foo->bar()[0]}"`, any variable, array index, property fetch or call starting with a
+    /// variable.
+    Complex,
+}
+
+impl Node for ExpressionStringPart {
+    fn children(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.expression.as_mut()]
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum ArrayItem {
     Skipped,
     Value {
@@ -1089,6 +1326,7 @@ impl Node for ArrayItem {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum ListEntry {
     Skipped,
     Value {
@@ -1,19 +1,111 @@
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use smallvec::SmallVec;
 
 use crate::lexer::token::Span;
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type")]
+#[non_exhaustive]
 pub enum Visibility {
     Public,
     Protected,
     Private,
 }
 
+/// The keyword of a modifier, regardless of the declaration it modifies.
+///
+/// Kinds are ordered as recommended by PSR-12: `abstract` or `final` first, then the
+/// visibility, then `static`, and `readonly` last.
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ModifierKind {
+    Abstract,
+    Final,
+    Public,
+    Protected,
+    Private,
+    PublicSet,
+    ProtectedSet,
+    PrivateSet,
+    Static,
+    Readonly,
+}
+
+impl ModifierKind {
+    /// The rank of the modifier in the order recommended by PSR-12, `abstract` and `final`
+    /// sharing the first one.
+    pub fn rank(&self) -> usize {
+        match self {
+            ModifierKind::Abstract | ModifierKind::Final => 0,
+            ModifierKind::Public
+            | ModifierKind::Protected
+            | ModifierKind::Private
+            | ModifierKind::PublicSet
+            | ModifierKind::ProtectedSet
+            | ModifierKind::PrivateSet => 1,
+            ModifierKind::Static => 2,
+            ModifierKind::Readonly => 3,
+        }
+    }
+
+    pub fn is_visibility(&self) -> bool {
+        self.rank() == 1
+    }
+}
+
+impl std::fmt::Display for ModifierKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModifierKind::Abstract => write!(f, "abstract"),
+            ModifierKind::Final => write!(f, "final"),
+            ModifierKind::Public => write!(f, "public"),
+            ModifierKind::Protected => write!(f, "protected"),
+            ModifierKind::Private => write!(f, "private"),
+            ModifierKind::PublicSet => write!(f, "public(set)"),
+            ModifierKind::ProtectedSet => write!(f, "protected(set)"),
+            ModifierKind::PrivateSet => write!(f, "private(set)"),
+            ModifierKind::Static => write!(f, "static"),
+            ModifierKind::Readonly => write!(f, "readonly"),
+        }
+    }
+}
+
+/// The source order of a group of modifiers, and the order recommended by PSR-12.
+macro_rules! ordered_group {
+    ($group:ident, $modifier:ident) => {
+        impl $group {
+            /// The kinds of the modifiers, in source order.
+            pub fn kinds(&self) -> Vec<ModifierKind> {
+                self.modifiers.iter().map($modifier::kind).collect()
+            }
+
+            /// Whether the modifiers are in the order recommended by PSR-12, see [`ModifierKind`].
+            pub fn is_ordered(&self) -> bool {
+                self.modifiers
+                    .windows(2)
+                    .all(|pair| pair[0].kind().rank() <= pair[1].kind().rank())
+            }
+
+            /// The modifiers in the order recommended by PSR-12, keeping the source order of
+            /// modifiers of the same rank.
+            pub fn ordered(&self) -> Vec<&$modifier> {
+                let mut modifiers = self.modifiers.iter().collect::<Vec<&$modifier>>();
+                modifiers.sort_by_key(|modifier| modifier.kind().rank());
+
+                modifiers
+            }
+        }
+    };
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum VisibilityModifier {
     Public(Span),
     Protected(Span),
@@ -22,11 +114,15 @@ pub enum VisibilityModifier {
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum PromotedPropertyModifier {
     Public(Span),
     Protected(Span),
     Private(Span),
     Readonly(Span),
+    PublicSet(Span),    // `public(set)`
+    ProtectedSet(Span), // `protected(set)`
+    PrivateSet(Span),   // `private(set)`
 }
 
 impl PromotedPropertyModifier {
@@ -36,6 +132,21 @@ impl PromotedPropertyModifier {
             PromotedPropertyModifier::Protected(span) => *span,
             PromotedPropertyModifier::Private(span) => *span,
             PromotedPropertyModifier::Readonly(span) => *span,
+            PromotedPropertyModifier::PublicSet(span) => *span,
+            PromotedPropertyModifier::ProtectedSet(span) => *span,
+            PromotedPropertyModifier::PrivateSet(span) => *span,
+        }
+    }
+
+    pub fn kind(&self) -> ModifierKind {
+        match self {
+            PromotedPropertyModifier::Public(_) => ModifierKind::Public,
+            PromotedPropertyModifier::Protected(_) => ModifierKind::Protected,
+            PromotedPropertyModifier::Private(_) => ModifierKind::Private,
+            PromotedPropertyModifier::Readonly(_) => ModifierKind::Readonly,
+            PromotedPropertyModifier::PublicSet(_) => ModifierKind::PublicSet,
+            PromotedPropertyModifier::ProtectedSet(_) => ModifierKind::ProtectedSet,
+            PromotedPropertyModifier::PrivateSet(_) => ModifierKind::PrivateSet,
         }
     }
 }
@@ -47,6 +158,9 @@ impl std::fmt::Display for PromotedPropertyModifier {
             PromotedPropertyModifier::Protected(_) => write!(f, "protected"),
             PromotedPropertyModifier::Private(_) => write!(f, "private"),
             PromotedPropertyModifier::Readonly(_) => write!(f, "readonly"),
+            PromotedPropertyModifier::PublicSet(_) => write!(f, "public(set)"),
+            PromotedPropertyModifier::ProtectedSet(_) => write!(f, "protected(set)"),
+            PromotedPropertyModifier::PrivateSet(_) => write!(f, "private(set)"),
         }
     }
 }
@@ -55,7 +169,7 @@ impl std::fmt::Display for PromotedPropertyModifier {
 #[serde(rename_all = "snake_case")]
 #[repr(transparent)]
 pub struct PromotedPropertyModifierGroup {
-    pub modifiers: Vec<PromotedPropertyModifier>,
+    pub modifiers: SmallVec<[PromotedPropertyModifier; 2]>,
 }
 
 impl PromotedPropertyModifierGroup {
@@ -86,16 +200,44 @@ impl PromotedPropertyModifierGroup {
             })
             .unwrap_or(Visibility::Public)
     }
+
+    /// The `public(set)`, `protected(set)` or `private(set)` modifier.
+    pub fn get_set_visibility(&self) -> Option<&PromotedPropertyModifier> {
+        self.modifiers.iter().find(|modifier| {
+            matches!(
+                modifier,
+                PromotedPropertyModifier::PublicSet { .. }
+                    | PromotedPropertyModifier::ProtectedSet { .. }
+                    | PromotedPropertyModifier::PrivateSet { .. }
+            )
+        })
+    }
+
+    /// The visibility of the property when written, which defaults to its visibility.
+    pub fn set_visibility(&self) -> Visibility {
+        match self.get_set_visibility() {
+            Some(PromotedPropertyModifier::ProtectedSet { .. }) => Visibility::Protected,
+            Some(PromotedPropertyModifier::PrivateSet { .. }) => Visibility::Private,
+            Some(_) => Visibility::Public,
+            None => self.visibility(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum PropertyModifier {
     Public(Span),
     Protected(Span),
     Private(Span),
     Static(Span),
     Readonly(Span),
+    Abstract(Span),
+    Final(Span),
+    PublicSet(Span),    // `public(set)`
+    ProtectedSet(Span), // `protected(set)`
+    PrivateSet(Span),   // `private(set)`
 }
 
 impl PropertyModifier {
@@ -106,6 +248,26 @@ impl PropertyModifier {
             PropertyModifier::Private(span) => *span,
             PropertyModifier::Static(span) => *span,
             PropertyModifier::Readonly(span) => *span,
+            PropertyModifier::Abstract(span) => *span,
+            PropertyModifier::Final(span) => *span,
+            PropertyModifier::PublicSet(span) => *span,
+            PropertyModifier::ProtectedSet(span) => *span,
+            PropertyModifier::PrivateSet(span) => *span,
+        }
+    }
+
+    pub fn kind(&self) -> ModifierKind {
+        match self {
+            PropertyModifier::Public(_) => ModifierKind::Public,
+            PropertyModifier::Protected(_) => ModifierKind::Protected,
+            PropertyModifier::Private(_) => ModifierKind::Private,
+            PropertyModifier::Static(_) => ModifierKind::Static,
+            PropertyModifier::Readonly(_) => ModifierKind::Readonly,
+            PropertyModifier::Abstract(_) => ModifierKind::Abstract,
+            PropertyModifier::Final(_) => ModifierKind::Final,
+            PropertyModifier::PublicSet(_) => ModifierKind::PublicSet,
+            PropertyModifier::ProtectedSet(_) => ModifierKind::ProtectedSet,
+            PropertyModifier::PrivateSet(_) => ModifierKind::PrivateSet,
         }
     }
 }
@@ -114,7 +276,7 @@ impl PropertyModifier {
 #[serde(rename_all = "snake_case")]
 #[repr(transparent)]
 pub struct PropertyModifierGroup {
-    pub modifiers: Vec<PropertyModifier>,
+    pub modifiers: SmallVec<[PropertyModifier; 2]>,
 }
 
 impl PropertyModifierGroup {
@@ -134,6 +296,12 @@ impl PropertyModifierGroup {
             .find(|modifier| matches!(modifier, PropertyModifier::Static { .. }))
     }
 
+    pub fn get_abstract(&self) -> Option<&PropertyModifier> {
+        self.modifiers
+            .iter()
+            .find(|modifier| matches!(modifier, PropertyModifier::Abstract { .. }))
+    }
+
     pub fn has_readonly(&self) -> bool {
         self.modifiers
             .iter()
@@ -157,10 +325,33 @@ impl PropertyModifierGroup {
             })
             .unwrap_or(Visibility::Public)
     }
+
+    /// The `public(set)`, `protected(set)` or `private(set)` modifier.
+    pub fn get_set_visibility(&self) -> Option<&PropertyModifier> {
+        self.modifiers.iter().find(|modifier| {
+            matches!(
+                modifier,
+                PropertyModifier::PublicSet { .. }
+                    | PropertyModifier::ProtectedSet { .. }
+                    | PropertyModifier::PrivateSet { .. }
+            )
+        })
+    }
+
+    /// The visibility of the property when written, which defaults to its visibility.
+    pub fn set_visibility(&self) -> Visibility {
+        match self.get_set_visibility() {
+            Some(PropertyModifier::ProtectedSet { .. }) => Visibility::Protected,
+            Some(PropertyModifier::PrivateSet { .. }) => Visibility::Private,
+            Some(_) => Visibility::Public,
+            None => self.visibility(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum MethodModifier {
     Final(Span),
     Static(Span),
@@ -181,13 +372,24 @@ impl MethodModifier {
             MethodModifier::Private(span) => *span,
         }
     }
+
+    pub fn kind(&self) -> ModifierKind {
+        match self {
+            MethodModifier::Final(_) => ModifierKind::Final,
+            MethodModifier::Static(_) => ModifierKind::Static,
+            MethodModifier::Abstract(_) => ModifierKind::Abstract,
+            MethodModifier::Public(_) => ModifierKind::Public,
+            MethodModifier::Protected(_) => ModifierKind::Protected,
+            MethodModifier::Private(_) => ModifierKind::Private,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[repr(transparent)]
 pub struct MethodModifierGroup {
-    pub modifiers: Vec<MethodModifier>,
+    pub modifiers: SmallVec<[MethodModifier; 2]>,
 }
 
 impl MethodModifierGroup {
@@ -219,6 +421,12 @@ impl MethodModifierGroup {
             .find(|modifier| matches!(modifier, MethodModifier::Abstract { .. }))
     }
 
+    pub fn get_static(&self) -> Option<&MethodModifier> {
+        self.modifiers
+            .iter()
+            .find(|modifier| matches!(modifier, MethodModifier::Static { .. }))
+    }
+
     pub fn visibility(&self) -> Visibility {
         self.modifiers
             .iter()
@@ -234,17 +442,36 @@ impl MethodModifierGroup {
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum ClassModifier {
     Final(Span),
     Abstract(Span),
     Readonly(Span),
 }
 
+impl ClassModifier {
+    pub fn span(&self) -> Span {
+        match self {
+            ClassModifier::Final(span) => *span,
+            ClassModifier::Abstract(span) => *span,
+            ClassModifier::Readonly(span) => *span,
+        }
+    }
+
+    pub fn kind(&self) -> ModifierKind {
+        match self {
+            ClassModifier::Final(_) => ModifierKind::Final,
+            ClassModifier::Abstract(_) => ModifierKind::Abstract,
+            ClassModifier::Readonly(_) => ModifierKind::Readonly,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[repr(transparent)]
 pub struct ClassModifierGroup {
-    pub modifiers: Vec<ClassModifier>,
+    pub modifiers: SmallVec<[ClassModifier; 2]>,
 }
 
 impl ClassModifierGroup {
@@ -258,6 +485,12 @@ impl ClassModifierGroup {
             .any(|modifier| matches!(modifier, ClassModifier::Final { .. }))
     }
 
+    pub fn get_readonly(&self) -> Option<&ClassModifier> {
+        self.modifiers
+            .iter()
+            .find(|modifier| matches!(modifier, ClassModifier::Readonly { .. }))
+    }
+
     pub fn has_readonly(&self) -> bool {
         self.modifiers
             .iter()
@@ -273,6 +506,7 @@ impl ClassModifierGroup {
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum ConstantModifier {
     Final(Span),
     Public(Span),
@@ -280,11 +514,31 @@ pub enum ConstantModifier {
     Private(Span),
 }
 
+impl ConstantModifier {
+    pub fn span(&self) -> Span {
+        match self {
+            ConstantModifier::Final(span) => *span,
+            ConstantModifier::Public(span) => *span,
+            ConstantModifier::Protected(span) => *span,
+            ConstantModifier::Private(span) => *span,
+        }
+    }
+
+    pub fn kind(&self) -> ModifierKind {
+        match self {
+            ConstantModifier::Final(_) => ModifierKind::Final,
+            ConstantModifier::Public(_) => ModifierKind::Public,
+            ConstantModifier::Protected(_) => ModifierKind::Protected,
+            ConstantModifier::Private(_) => ModifierKind::Private,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[repr(transparent)]
 pub struct ConstantModifierGroup {
-    pub modifiers: Vec<ConstantModifier>,
+    pub modifiers: SmallVec<[ConstantModifier; 2]>,
 }
 
 impl ConstantModifierGroup {
@@ -292,6 +546,12 @@ impl ConstantModifierGroup {
         self.modifiers.is_empty()
     }
 
+    pub fn get_final(&self) -> Option<&ConstantModifier> {
+        self.modifiers
+            .iter()
+            .find(|modifier| matches!(modifier, ConstantModifier::Final { .. }))
+    }
+
     pub fn has_final(&self) -> bool {
         self.modifiers
             .iter()
@@ -310,3 +570,96 @@ impl ConstantModifierGroup {
             .unwrap_or(Visibility::Public)
     }
 }
+
+ordered_group!(PromotedPropertyModifierGroup, PromotedPropertyModifier);
+ordered_group!(PropertyModifierGroup, PropertyModifier);
+ordered_group!(MethodModifierGroup, MethodModifier);
+ordered_group!(ClassModifierGroup, ClassModifier);
+ordered_group!(ConstantModifierGroup, ConstantModifier);
+
+#[cfg(test)]
+mod tests {
+    use super::ModifierKind;
+    use super::PropertyModifier;
+    use crate::parser::ast::classes::ClassMember;
+    use crate::parser::ast::Statement;
+
+    #[test]
+    fn test_modifier_order() {
+        let program = crate::parse(
+            "<?php
+final class A {
+    static public $a;
+    public static $b;
+    static public function foo() {}
+    public function __construct(readonly public int $c) {}
+    final public const D = 1;
+}
+",
+        )
+        .unwrap();
+
+        let class = match &program[1] {
+            Statement::Class(class) => class,
+            _ => unreachable!(),
+        };
+        assert!(class.modifiers.is_ordered());
+        assert_eq!(class.modifiers.kinds(), vec![ModifierKind::Final]);
+
+        let members = &class.body.members;
+        let (a, b) = match (&members[0], &members[1]) {
+            (ClassMember::Property(a), ClassMember::Property(b)) => (a, b),
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            a.modifiers.kinds(),
+            vec![ModifierKind::Static, ModifierKind::Public]
+        );
+        assert!(!a.modifiers.is_ordered());
+        assert!(b.modifiers.is_ordered());
+        assert!(matches!(
+            a.modifiers.ordered()[..],
+            [PropertyModifier::Public(_), PropertyModifier::Static(_)]
+        ));
+        assert_eq!(a.modifiers.visibility(), b.modifiers.visibility());
+
+        match &members[2] {
+            ClassMember::ConcreteMethod(method) => assert!(!method.modifiers.is_ordered()),
+            _ => unreachable!(),
+        }
+        match &members[3] {
+            ClassMember::ConcreteConstructor(constructor) => {
+                let modifiers = &constructor.parameters.parameters.inner[0].modifiers;
+                assert!(!modifiers.is_ordered());
+                assert_eq!(
+                    modifiers
+                        .ordered()
+                        .iter()
+                        .map(|modifier| modifier.to_string())
+                        .collect::<Vec<String>>(),
+                    vec!["public", "readonly"]
+                );
+            }
+            _ => unreachable!(),
+        }
+        match &members[4] {
+            ClassMember::Constant(constant) => assert!(constant.modifiers.is_ordered()),
+            _ => unreachable!(),
+        }
+
+        let mut kinds = [
+            ModifierKind::Readonly,
+            ModifierKind::Static,
+            ModifierKind::Private,
+            ModifierKind::Abstract,
+        ];
+        kinds.sort();
+        assert_eq!(
+            kinds
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>(),
+            vec!["abstract", "private", "static", "readonly"]
+        );
+    }
+}
@@ -67,6 +67,7 @@ impl Node for BracedNamespaceBody {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum NamespaceStatement {
     Unbraced(UnbracedNamespace), // `namespace Foo; *statements*`
     Braced(BracedNamespace),     // `namespace Foo { *statements* }`
@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -6,8 +8,42 @@ use crate::lexer::token::Span;
 use crate::node::Node;
 use crate::parser::ast::Expression;
 
+/// The operator token of an operation, see [`Expression::operator`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Operator {
+    pub span: Span,
+    /// The operator as written, but for its case, e.g. `and` for `AND`.
+    ///
+    /// A cast is written in its canonical form instead, e.g. `(int)` for `( integer )`.
+    pub symbol: &'static str,
+    // The length of the operator as written.
+    length: usize,
+}
+
+impl Operator {
+    pub(crate) fn new(span: Span, symbol: &'static str) -> Self {
+        Self {
+            span,
+            symbol,
+            length: symbol.len(),
+        }
+    }
+
+    pub(crate) fn with_length(mut self, length: usize) -> Self {
+        self.length = length;
+
+        self
+    }
+
+    /// The byte range of the operator in the source, e.g. to replace it with another one.
+    pub fn range(&self) -> Range<usize> {
+        self.span.position..self.span.position + self.length
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum ArithmeticOperation {
     Addition {
         left: Box<Expression>,
@@ -96,12 +132,20 @@ impl Node for ArithmeticOperation {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum AssignmentOperation {
     Assign {
         left: Box<Expression>,
         equals: Span,
         right: Box<Expression>,
     },
+    // `$a = &$b`
+    AssignByReference {
+        left: Box<Expression>,
+        equals: Span,
+        ampersand: Span,
+        right: Box<Expression>,
+    },
     Addition {
         left: Box<Expression>,
         plus_equals: Span,
@@ -173,6 +217,9 @@ impl Node for AssignmentOperation {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         match self {
             AssignmentOperation::Assign { left, right, .. } => vec![left.as_mut(), right.as_mut()],
+            AssignmentOperation::AssignByReference { left, right, .. } => {
+                vec![left.as_mut(), right.as_mut()]
+            }
             AssignmentOperation::Addition { left, right, .. } => {
                 vec![left.as_mut(), right.as_mut()]
             }
@@ -214,6 +261,7 @@ impl Node for AssignmentOperation {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum BitwiseOperation {
     And {
         left: Box<Expression>,
@@ -261,6 +309,7 @@ impl Node for BitwiseOperation {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum ComparisonOperation {
     Equal {
         left: Box<Expression>,
@@ -351,6 +400,7 @@ impl Node for ComparisonOperation {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum LogicalOperation {
     And {
         left: Box<Expression>,
@@ -5,7 +5,11 @@ use serde::Serialize;
 use crate::lexer::token::Span;
 use crate::node::Node;
 use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::comments::CommentGroup;
 use crate::parser::ast::data_type::Type;
+use crate::parser::ast::functions::FunctionBody;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::modifiers::PropertyModifierGroup;
 use crate::parser::ast::variables::SimpleVariable;
 use crate::parser::ast::Expression;
@@ -13,12 +17,14 @@ use crate::parser::ast::Expression;
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct Property {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>,
     #[serde(flatten)]
     pub modifiers: PropertyModifierGroup,
     pub r#type: Option<Type>,
     pub entries: Vec<PropertyEntry>,
-    pub end: Span,
+    pub hooks: Option<PropertyHookList>, // `{ get => $this->foo; }`
+    pub end: Span,                       // `;`, or `}` of the hooks
 }
 
 impl Node for Property {
@@ -33,13 +39,104 @@ impl Node for Property {
                 .map(|e| e as &mut dyn Node)
                 .collect::<Vec<&mut dyn Node>>(),
         );
+        if let Some(hooks) = &mut self.hooks {
+            children.push(hooks);
+        }
         children
     }
 }
 
+impl Property {
+    /// Whether the property has hooks, introduced in PHP 8.4.
+    pub fn is_hooked(&self) -> bool {
+        self.hooks.is_some()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PropertyHookList {
+    pub left_brace: Span,
+    pub hooks: Vec<PropertyHook>,
+    pub right_brace: Span,
+}
+
+impl Node for PropertyHookList {
+    fn children(&mut self) -> Vec<&mut dyn Node> {
+        self.hooks.iter_mut().map(|h| h as &mut dyn Node).collect()
+    }
+}
+
+impl PropertyHookList {
+    pub fn get(&self, name: &str) -> Option<&PropertyHook> {
+        self.hooks
+            .iter()
+            .find(|hook| hook.name.value.bytes == name.as_bytes())
+    }
+}
+
+/// A `get` or `set` hook of a property.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PropertyHook {
+    pub comments: CommentGroup,
+    pub attributes: Vec<AttributeGroup>,
+    pub r#final: Option<Span>,                     // `final`
+    pub ampersand: Option<Span>,                   // `&`
+    pub name: SimpleIdentifier,                    // `get` or `set`
+    pub parameters: Option<FunctionParameterList>, // `(string $value)`
+    pub body: PropertyHookBody,
+}
+
+impl Node for PropertyHook {
+    fn children(&mut self) -> Vec<&mut dyn Node> {
+        let mut children: Vec<&mut dyn Node> = vec![&mut self.name];
+        if let Some(parameters) = &mut self.parameters {
+            children.push(parameters);
+        }
+        children.push(&mut self.body);
+        children
+    }
+}
+
+impl PropertyHook {
+    pub fn is_abstract(&self) -> bool {
+        matches!(self.body, PropertyHookBody::Abstract { .. })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
+pub enum PropertyHookBody {
+    // `get;`
+    Abstract {
+        semicolon: Span,
+    },
+    // `get => $this->foo;`
+    Expression {
+        double_arrow: Span,
+        expression: Expression,
+        semicolon: Span,
+    },
+    // `get { return $this->foo; }`
+    Block(FunctionBody),
+}
+
+impl Node for PropertyHookBody {
+    fn children(&mut self) -> Vec<&mut dyn Node> {
+        match self {
+            PropertyHookBody::Abstract { .. } => vec![],
+            PropertyHookBody::Expression { expression, .. } => vec![expression],
+            PropertyHookBody::Block(body) => vec![body],
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct VariableProperty {
+    pub comments: CommentGroup,
     pub attributes: Vec<AttributeGroup>,
     pub r#type: Option<Type>,
     pub entries: Vec<PropertyEntry>,
@@ -64,6 +161,7 @@ impl Node for VariableProperty {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum PropertyEntry {
     Uninitialized {
         variable: SimpleVariable,
@@ -75,6 +173,15 @@ pub enum PropertyEntry {
     },
 }
 
+impl PropertyEntry {
+    pub fn variable(&self) -> &SimpleVariable {
+        match self {
+            PropertyEntry::Uninitialized { variable } => variable,
+            PropertyEntry::Initialized { variable, .. } => variable,
+        }
+    }
+}
+
 impl Node for PropertyEntry {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         match self {
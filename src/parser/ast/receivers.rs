@@ -0,0 +1,153 @@
+use crate::lexer::token::Span;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::Expression;
+
+/// The receiver of a static method call, static property fetch, or class constant fetch,
+/// i.e. what precedes the `::`.
+///
+/// `self` and `parent` receivers are parsed as identifiers, whereas a `static` receiver is
+/// parsed as its own expression, so the receiver normalizes the three keywords regardless of
+/// how they are represented in the AST.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StaticReceiver<'a> {
+    SelfKeyword(Span),          // `self::$instance`
+    ParentKeyword(Span),        // `parent::__construct()`
+    StaticKeyword(Span),        // `static::create()`
+    Name(&'a SimpleIdentifier), // `Foo::create()`
+    Expression(&'a Expression), // `$foo::create()` or `foo()::$bar`
+}
+
+impl<'a> StaticReceiver<'a> {
+    /// The receiver for the given target of a `::`.
+    pub fn of(target: &'a Expression) -> Self {
+        match target {
+            Expression::Self_ { span } => StaticReceiver::SelfKeyword(*span),
+            Expression::Parent { span } => StaticReceiver::ParentKeyword(*span),
+            Expression::Static { span } => StaticReceiver::StaticKeyword(*span),
+            Expression::Identifier(Identifier::SimpleIdentifier(name)) => {
+                if name.value.eq_ignore_ascii_case(b"self") {
+                    StaticReceiver::SelfKeyword(name.span)
+                } else if name.value.eq_ignore_ascii_case(b"parent") {
+                    StaticReceiver::ParentKeyword(name.span)
+                } else if name.value.eq_ignore_ascii_case(b"static") {
+                    StaticReceiver::StaticKeyword(name.span)
+                } else {
+                    StaticReceiver::Name(name)
+                }
+            }
+            expression => StaticReceiver::Expression(expression),
+        }
+    }
+
+    /// The keyword of the receiver, if it is `self`, `parent`, or `static`.
+    pub fn keyword(&self) -> Option<&'static str> {
+        match self {
+            StaticReceiver::SelfKeyword(_) => Some("self"),
+            StaticReceiver::ParentKeyword(_) => Some("parent"),
+            StaticReceiver::StaticKeyword(_) => Some("static"),
+            _ => None,
+        }
+    }
+
+    /// Whether the receiver can only be resolved against the class scope it is used in.
+    pub fn is_class_scoped(&self) -> bool {
+        self.keyword().is_some()
+    }
+
+    pub fn start(&self) -> Span {
+        match self {
+            StaticReceiver::SelfKeyword(span)
+            | StaticReceiver::ParentKeyword(span)
+            | StaticReceiver::StaticKeyword(span) => *span,
+            StaticReceiver::Name(name) => name.span,
+            StaticReceiver::Expression(expression) => expression.start(),
+        }
+    }
+
+    pub fn end(&self) -> Span {
+        match self {
+            StaticReceiver::Expression(expression) => expression.end(),
+            _ => self.start(),
+        }
+    }
+}
+
+impl Expression {
+    /// The receiver of this static method call, static property fetch, or class constant
+    /// fetch, or `None` if this expression is not one.
+    ///
+    /// First-class callable creation (`Foo::bar(...)`) has the same receiver as the
+    /// equivalent call.
+    pub fn static_receiver(&self) -> Option<StaticReceiver<'_>> {
+        match self {
+            Expression::StaticMethodCall { target, .. }
+            | Expression::StaticVariableMethodCall { target, .. }
+            | Expression::StaticMethodClosureCreation { target, .. }
+            | Expression::StaticVariableMethodClosureCreation { target, .. }
+            | Expression::StaticPropertyFetch { target, .. }
+            | Expression::ConstantFetch { target, .. } => Some(StaticReceiver::of(target)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StaticReceiver;
+    use crate::parser::ast::Statement;
+
+    fn receiver(code: &str) -> Option<String> {
+        let program = crate::parse_method_body(&format!("{};", code)).unwrap();
+
+        match &program[0] {
+            Statement::Expression(statement) => {
+                statement
+                    .expression
+                    .static_receiver()
+                    .map(|receiver| match receiver {
+                        StaticReceiver::SelfKeyword(span) => format!("self@{}", span.column),
+                        StaticReceiver::ParentKeyword(span) => format!("parent@{}", span.column),
+                        StaticReceiver::StaticKeyword(span) => format!("static@{}", span.column),
+                        StaticReceiver::Name(name) => format!("name {}", name),
+                        StaticReceiver::Expression(_) => "expression".to_string(),
+                    })
+            }
+            statement => panic!("expected an expression statement, found {:?}", statement),
+        }
+    }
+
+    #[test]
+    fn test_class_scoped_receivers() {
+        assert_eq!(receiver("parent::__construct()"), Some("parent@1".into()));
+        assert_eq!(receiver("self::$instance"), Some("self@1".into()));
+        assert_eq!(receiver("static::create()"), Some("static@1".into()));
+        assert_eq!(receiver("Self::BAR"), Some("self@1".into()));
+        assert_eq!(receiver("static::$create(...)"), Some("static@1".into()));
+    }
+
+    #[test]
+    fn test_other_receivers() {
+        assert_eq!(
+            receiver("\\Foo\\Bar::create()"),
+            Some("name \\Foo\\Bar".into())
+        );
+        assert_eq!(receiver("$foo::create(...)"), Some("expression".into()));
+        assert_eq!(receiver("foo()::$bar"), Some("expression".into()));
+        assert_eq!(receiver("$foo->bar()"), None);
+    }
+
+    #[test]
+    fn test_receiver_spans() {
+        let program = crate::parse_method_body("$a = 1; $foo->bar::BAZ;").unwrap();
+
+        let Statement::Expression(statement) = &program[1] else {
+            panic!("expected an expression statement, found {:?}", program[1]);
+        };
+
+        let receiver = statement.expression.static_receiver().unwrap();
+        assert!(!receiver.is_class_scoped());
+        assert_eq!(receiver.keyword(), None);
+        assert_eq!((receiver.start().column, receiver.end().column), (9, 15));
+    }
+}
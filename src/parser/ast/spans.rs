@@ -0,0 +1,906 @@
+use std::ops::Range;
+
+use crate::lexer::token::Span;
+use crate::parser::ast::attributes::Attribute;
+use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::classes::AnonymousClass;
+use crate::parser::ast::functions::ArrowFunction;
+use crate::parser::ast::functions::Closure;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::operators::ArithmeticOperation;
+use crate::parser::ast::operators::AssignmentOperation;
+use crate::parser::ast::operators::BitwiseOperation;
+use crate::parser::ast::operators::ComparisonOperation;
+use crate::parser::ast::operators::LogicalOperation;
+use crate::parser::ast::operators::Operator;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::CastKind;
+use crate::parser::ast::Expression;
+use crate::parser::ast::MagicConstant;
+
+impl Expression {
+    /// The span of the first token of this expression.
+    ///
+    /// ```
+    /// use php_parser_rs::parser::ast::Statement;
+    ///
+    /// let program = php_parser_rs::parse("<?php\n$a = foo($b) ?? null;").unwrap();
+    ///
+    /// if let Statement::Expression(statement) = &program[1] {
+    ///     assert_eq!(statement.expression.start().column, 1);
+    ///     assert_eq!(statement.expression.end().column, 17);
+    /// }
+    /// ```
+    pub fn start(&self) -> Span {
+        match self {
+            Expression::Eval { eval: span, .. }
+            | Expression::Empty { empty: span, .. }
+            | Expression::Isset { isset: span, .. }
+            | Expression::Unset { unset: span, .. }
+            | Expression::Print { print: span, .. }
+            | Expression::Parenthesized { start: span, .. }
+            | Expression::ErrorSuppress { at: span, .. }
+            | Expression::Include { include: span, .. }
+            | Expression::IncludeOnce {
+                include_once: span, ..
+            }
+            | Expression::Require { require: span, .. }
+            | Expression::RequireOnce {
+                require_once: span, ..
+            }
+            | Expression::Static { span }
+            | Expression::Self_ { span }
+            | Expression::Parent { span }
+            | Expression::ShortArray { start: span, .. }
+            | Expression::New { new: span, .. }
+            | Expression::InterpolatedString { start: span, .. }
+            | Expression::Heredoc { start: span, .. }
+            | Expression::Nowdoc { start: span, .. }
+            | Expression::ShellExec { start: span, .. }
+            | Expression::Bool { span, .. }
+            | Expression::Null { span }
+            | Expression::Clone { clone: span, .. }
+            | Expression::Throw { throw: span, .. }
+            | Expression::Yield { r#yield: span, .. }
+            | Expression::YieldFrom { r#yield: span, .. }
+            | Expression::Cast { cast: span, .. }
+            | Expression::Noop { span }
+            | Expression::Missing { span } => *span,
+            Expression::Exit(exit) => exit.keyword,
+            Expression::Array(array) => array.array,
+            Expression::List(list) => list.list,
+            Expression::Match(expression) => expression.keyword,
+            Expression::Concat { left, .. }
+            | Expression::Instanceof { left, .. }
+            | Expression::Pipe { left, .. }
+            | Expression::FunctionCall { target: left, .. }
+            | Expression::FunctionClosureCreation { target: left, .. }
+            | Expression::MethodCall { target: left, .. }
+            | Expression::MethodClosureCreation { target: left, .. }
+            | Expression::NullsafeMethodCall { target: left, .. }
+            | Expression::StaticMethodCall { target: left, .. }
+            | Expression::StaticVariableMethodCall { target: left, .. }
+            | Expression::StaticMethodClosureCreation { target: left, .. }
+            | Expression::StaticVariableMethodClosureCreation { target: left, .. }
+            | Expression::PropertyFetch { target: left, .. }
+            | Expression::NullsafePropertyFetch { target: left, .. }
+            | Expression::StaticPropertyFetch { target: left, .. }
+            | Expression::ConstantFetch { target: left, .. }
+            | Expression::ArrayIndex { array: left, .. }
+            | Expression::ShortTernary {
+                condition: left, ..
+            }
+            | Expression::Ternary {
+                condition: left, ..
+            }
+            | Expression::Coalesce { lhs: left, .. } => left.start(),
+            Expression::Literal(literal) => literal.span(),
+            Expression::ArithmeticOperation(operation) => operation.start(),
+            Expression::AssignmentOperation(operation) => operation.start(),
+            Expression::BitwiseOperation(operation) => operation.start(),
+            Expression::ComparisonOperation(operation) => operation.start(),
+            Expression::LogicalOperation(operation) => operation.start(),
+            Expression::Identifier(identifier) => identifier.start(),
+            Expression::Variable(variable) => variable.start(),
+            Expression::Closure(closure) => closure.start(),
+            Expression::ArrowFunction(function) => function.start(),
+            Expression::AnonymousClass(class) => class.start(),
+            Expression::MagicConstant(constant) => constant.span(),
+        }
+    }
+
+    /// The span of the last token of this expression.
+    pub fn end(&self) -> Span {
+        match self {
+            Expression::Eval { argument, .. } | Expression::Empty { argument, .. } => {
+                argument.right_parenthesis
+            }
+            Expression::Exit(exit) => exit
+                .arguments
+                .as_ref()
+                .map_or(exit.keyword, |arguments| arguments.right_parenthesis),
+            Expression::Isset { arguments, .. }
+            | Expression::Unset { arguments, .. }
+            | Expression::MethodCall { arguments, .. }
+            | Expression::NullsafeMethodCall { arguments, .. }
+            | Expression::StaticMethodCall { arguments, .. }
+            | Expression::StaticVariableMethodCall { arguments, .. } => arguments.right_parenthesis,
+            Expression::FunctionCall { arguments, .. } => arguments.right_parenthesis,
+            Expression::FunctionClosureCreation { placeholder, .. }
+            | Expression::MethodClosureCreation { placeholder, .. }
+            | Expression::StaticMethodClosureCreation { placeholder, .. }
+            | Expression::StaticVariableMethodClosureCreation { placeholder, .. } => {
+                placeholder.right_parenthesis
+            }
+            Expression::Print {
+                print,
+                value,
+                argument,
+            } => match (argument, value) {
+                (Some(argument), _) => argument.right_parenthesis,
+                (None, Some(value)) => value.end(),
+                (None, None) => *print,
+            },
+            Expression::New {
+                target, arguments, ..
+            } => match (target.as_ref(), arguments) {
+                // the arguments of an anonymous class precede its body.
+                (Expression::AnonymousClass(_), _) | (_, None) => target.end(),
+                (_, Some(arguments)) => arguments.right_parenthesis,
+            },
+            Expression::Yield { r#yield, value, .. } => {
+                value.as_ref().map_or(*r#yield, |value| value.end())
+            }
+            Expression::Parenthesized { end: span, .. }
+            | Expression::Static { span }
+            | Expression::Self_ { span }
+            | Expression::Parent { span }
+            | Expression::ShortArray { end: span, .. }
+            | Expression::InterpolatedString { end: span, .. }
+            | Expression::Heredoc { end: span, .. }
+            | Expression::Nowdoc { end: span, .. }
+            | Expression::ShellExec { end: span, .. }
+            | Expression::Bool { span, .. }
+            | Expression::Null { span }
+            | Expression::ArrayIndex {
+                right_bracket: span,
+                ..
+            }
+            | Expression::Noop { span }
+            | Expression::Missing { span } => *span,
+            Expression::Array(array) => array.end,
+            Expression::List(list) => list.end,
+            Expression::Match(expression) => expression.right_brace,
+            Expression::Concat { right, .. }
+            | Expression::Instanceof { right, .. }
+            | Expression::Pipe { right, .. }
+            | Expression::ErrorSuppress { expr: right, .. }
+            | Expression::Include { path: right, .. }
+            | Expression::IncludeOnce { path: right, .. }
+            | Expression::Require { path: right, .. }
+            | Expression::RequireOnce { path: right, .. }
+            | Expression::PropertyFetch {
+                property: right, ..
+            }
+            | Expression::NullsafePropertyFetch {
+                property: right, ..
+            }
+            | Expression::ShortTernary { r#else: right, .. }
+            | Expression::Ternary { r#else: right, .. }
+            | Expression::Coalesce { rhs: right, .. }
+            | Expression::Clone { target: right, .. }
+            | Expression::Throw { value: right, .. }
+            | Expression::YieldFrom { value: right, .. }
+            | Expression::Cast { value: right, .. } => right.end(),
+            Expression::StaticPropertyFetch { property, .. } => property.end(),
+            Expression::ConstantFetch { constant, .. } => constant.end(),
+            Expression::Literal(literal) => literal.span(),
+            Expression::ArithmeticOperation(operation) => operation.end(),
+            Expression::AssignmentOperation(operation) => operation.end(),
+            Expression::BitwiseOperation(operation) => operation.end(),
+            Expression::ComparisonOperation(operation) => operation.end(),
+            Expression::LogicalOperation(operation) => operation.end(),
+            Expression::Identifier(identifier) => identifier.end(),
+            Expression::Variable(variable) => variable.end(),
+            Expression::Closure(closure) => closure.body.right_brace,
+            Expression::ArrowFunction(function) => function.body.end(),
+            Expression::AnonymousClass(class) => class.body.right_brace,
+            Expression::MagicConstant(constant) => constant.span(),
+        }
+    }
+}
+
+impl ArithmeticOperation {
+    /// The span of the first token of this operation.
+    pub fn start(&self) -> Span {
+        match self {
+            ArithmeticOperation::Addition { left, .. }
+            | ArithmeticOperation::Subtraction { left, .. }
+            | ArithmeticOperation::Multiplication { left, .. }
+            | ArithmeticOperation::Division { left, .. }
+            | ArithmeticOperation::Modulo { left, .. }
+            | ArithmeticOperation::Exponentiation { left, .. }
+            | ArithmeticOperation::PostIncrement { left, .. }
+            | ArithmeticOperation::PostDecrement { left, .. } => left.start(),
+            ArithmeticOperation::Negative { minus: span, .. }
+            | ArithmeticOperation::Positive { plus: span, .. }
+            | ArithmeticOperation::PreIncrement {
+                increment: span, ..
+            }
+            | ArithmeticOperation::PreDecrement {
+                decrement: span, ..
+            } => *span,
+        }
+    }
+
+    /// The span of the last token of this operation.
+    pub fn end(&self) -> Span {
+        match self {
+            ArithmeticOperation::Addition { right, .. }
+            | ArithmeticOperation::Subtraction { right, .. }
+            | ArithmeticOperation::Multiplication { right, .. }
+            | ArithmeticOperation::Division { right, .. }
+            | ArithmeticOperation::Modulo { right, .. }
+            | ArithmeticOperation::Exponentiation { right, .. }
+            | ArithmeticOperation::Negative { right, .. }
+            | ArithmeticOperation::Positive { right, .. }
+            | ArithmeticOperation::PreIncrement { right, .. }
+            | ArithmeticOperation::PreDecrement { right, .. } => right.end(),
+            ArithmeticOperation::PostIncrement {
+                increment: span, ..
+            }
+            | ArithmeticOperation::PostDecrement {
+                decrement: span, ..
+            } => *span,
+        }
+    }
+}
+
+impl AssignmentOperation {
+    /// The span of the first token of this operation.
+    pub fn start(&self) -> Span {
+        match self {
+            AssignmentOperation::Assign { left, .. }
+            | AssignmentOperation::AssignByReference { left, .. }
+            | AssignmentOperation::Addition { left, .. }
+            | AssignmentOperation::Subtraction { left, .. }
+            | AssignmentOperation::Multiplication { left, .. }
+            | AssignmentOperation::Division { left, .. }
+            | AssignmentOperation::Modulo { left, .. }
+            | AssignmentOperation::Exponentiation { left, .. }
+            | AssignmentOperation::Concat { left, .. }
+            | AssignmentOperation::BitwiseAnd { left, .. }
+            | AssignmentOperation::BitwiseOr { left, .. }
+            | AssignmentOperation::BitwiseXor { left, .. }
+            | AssignmentOperation::LeftShift { left, .. }
+            | AssignmentOperation::RightShift { left, .. }
+            | AssignmentOperation::Coalesce { left, .. } => left.start(),
+        }
+    }
+
+    /// The span of the last token of this operation.
+    pub fn end(&self) -> Span {
+        match self {
+            AssignmentOperation::Assign { right, .. }
+            | AssignmentOperation::AssignByReference { right, .. }
+            | AssignmentOperation::Addition { right, .. }
+            | AssignmentOperation::Subtraction { right, .. }
+            | AssignmentOperation::Multiplication { right, .. }
+            | AssignmentOperation::Division { right, .. }
+            | AssignmentOperation::Modulo { right, .. }
+            | AssignmentOperation::Exponentiation { right, .. }
+            | AssignmentOperation::Concat { right, .. }
+            | AssignmentOperation::BitwiseAnd { right, .. }
+            | AssignmentOperation::BitwiseOr { right, .. }
+            | AssignmentOperation::BitwiseXor { right, .. }
+            | AssignmentOperation::LeftShift { right, .. }
+            | AssignmentOperation::RightShift { right, .. }
+            | AssignmentOperation::Coalesce { right, .. } => right.end(),
+        }
+    }
+}
+
+impl BitwiseOperation {
+    /// The span of the first token of this operation.
+    pub fn start(&self) -> Span {
+        match self {
+            BitwiseOperation::And { left, .. }
+            | BitwiseOperation::Or { left, .. }
+            | BitwiseOperation::Xor { left, .. }
+            | BitwiseOperation::LeftShift { left, .. }
+            | BitwiseOperation::RightShift { left, .. } => left.start(),
+            BitwiseOperation::Not { not, .. } => *not,
+        }
+    }
+
+    /// The span of the last token of this operation.
+    pub fn end(&self) -> Span {
+        match self {
+            BitwiseOperation::And { right, .. }
+            | BitwiseOperation::Or { right, .. }
+            | BitwiseOperation::Xor { right, .. }
+            | BitwiseOperation::LeftShift { right, .. }
+            | BitwiseOperation::RightShift { right, .. }
+            | BitwiseOperation::Not { right, .. } => right.end(),
+        }
+    }
+}
+
+impl ComparisonOperation {
+    /// The span of the first token of this operation.
+    pub fn start(&self) -> Span {
+        match self {
+            ComparisonOperation::Equal { left, .. }
+            | ComparisonOperation::Identical { left, .. }
+            | ComparisonOperation::NotEqual { left, .. }
+            | ComparisonOperation::AngledNotEqual { left, .. }
+            | ComparisonOperation::NotIdentical { left, .. }
+            | ComparisonOperation::LessThan { left, .. }
+            | ComparisonOperation::GreaterThan { left, .. }
+            | ComparisonOperation::LessThanOrEqual { left, .. }
+            | ComparisonOperation::GreaterThanOrEqual { left, .. }
+            | ComparisonOperation::Spaceship { left, .. } => left.start(),
+        }
+    }
+
+    /// The span of the last token of this operation.
+    pub fn end(&self) -> Span {
+        match self {
+            ComparisonOperation::Equal { right, .. }
+            | ComparisonOperation::Identical { right, .. }
+            | ComparisonOperation::NotEqual { right, .. }
+            | ComparisonOperation::AngledNotEqual { right, .. }
+            | ComparisonOperation::NotIdentical { right, .. }
+            | ComparisonOperation::LessThan { right, .. }
+            | ComparisonOperation::GreaterThan { right, .. }
+            | ComparisonOperation::LessThanOrEqual { right, .. }
+            | ComparisonOperation::GreaterThanOrEqual { right, .. }
+            | ComparisonOperation::Spaceship { right, .. } => right.end(),
+        }
+    }
+}
+
+impl LogicalOperation {
+    /// The span of the first token of this operation.
+    pub fn start(&self) -> Span {
+        match self {
+            LogicalOperation::And { left, .. }
+            | LogicalOperation::Or { left, .. }
+            | LogicalOperation::LogicalAnd { left, .. }
+            | LogicalOperation::LogicalOr { left, .. }
+            | LogicalOperation::LogicalXor { left, .. } => left.start(),
+            LogicalOperation::Not { bang, .. } => *bang,
+        }
+    }
+
+    /// The span of the last token of this operation.
+    pub fn end(&self) -> Span {
+        match self {
+            LogicalOperation::And { right, .. }
+            | LogicalOperation::Or { right, .. }
+            | LogicalOperation::LogicalAnd { right, .. }
+            | LogicalOperation::LogicalOr { right, .. }
+            | LogicalOperation::LogicalXor { right, .. }
+            | LogicalOperation::Not { right, .. } => right.end(),
+        }
+    }
+}
+
+impl Identifier {
+    /// The span of the first token of this identifier.
+    pub fn start(&self) -> Span {
+        match self {
+            Identifier::SimpleIdentifier(identifier) => identifier.span,
+            Identifier::DynamicIdentifier(identifier) => identifier.start,
+        }
+    }
+
+    /// The span of the last token of this identifier.
+    pub fn end(&self) -> Span {
+        match self {
+            Identifier::SimpleIdentifier(identifier) => identifier.span,
+            Identifier::DynamicIdentifier(identifier) => identifier.end,
+        }
+    }
+}
+
+impl Variable {
+    /// The span of the first token of this variable.
+    pub fn start(&self) -> Span {
+        match self {
+            Variable::SimpleVariable(variable) => variable.span,
+            Variable::VariableVariable(variable) => variable.span,
+            Variable::BracedVariableVariable(variable) => variable.start,
+        }
+    }
+
+    /// The span of the last token of this variable.
+    pub fn end(&self) -> Span {
+        match self {
+            Variable::SimpleVariable(variable) => variable.span,
+            Variable::VariableVariable(variable) => variable.variable.end(),
+            Variable::BracedVariableVariable(variable) => variable.end,
+        }
+    }
+}
+
+impl Closure {
+    /// The span of the first token of this closure, including its attributes.
+    pub fn start(&self) -> Span {
+        self.attributes
+            .first()
+            .map(|attributes| attributes.start)
+            .or(self.r#static)
+            .unwrap_or(self.function)
+    }
+}
+
+impl ArrowFunction {
+    /// The span of the first token of this arrow function, including its attributes.
+    pub fn start(&self) -> Span {
+        self.attributes
+            .first()
+            .map(|attributes| attributes.start)
+            .or(self.r#static)
+            .unwrap_or(self.r#fn)
+    }
+}
+
+impl AnonymousClass {
+    /// The span of the first token of this class, including its attributes but not the
+    /// `new` keyword.
+    pub fn start(&self) -> Span {
+        self.attributes
+            .first()
+            .map(|attributes| attributes.start)
+            .or(self.readonly)
+            .unwrap_or(self.class)
+    }
+}
+
+impl Attribute {
+    /// The byte range of this attribute, from its name to its arguments included.
+    pub fn range(&self) -> Range<usize> {
+        let end = match &self.arguments {
+            Some(arguments) => arguments.right_parenthesis.position + 1,
+            None => self.name.span.position + self.name.value.len(),
+        };
+
+        self.name.span.position..end
+    }
+
+    /// The span of the comma following this attribute, if any.
+    pub fn comma(&self, group: &AttributeGroup) -> Option<Span> {
+        if self.end.position == group.end.position {
+            None
+        } else {
+            Some(self.end)
+        }
+    }
+}
+
+impl AttributeGroup {
+    /// The byte range of this group, from `#[` to `]` included.
+    pub fn range(&self) -> Range<usize> {
+        self.start.position..self.end.position + 1
+    }
+
+    /// The byte range to remove from the source to remove the attribute at the given index,
+    /// along with its comma, or the whole group when the attribute is the only one.
+    ///
+    /// ```
+    /// use php_parser_rs::parser::ast::Statement;
+    ///
+    /// let code = "<?php #[A, B(1), C] function foo() {}";
+    /// let program = php_parser_rs::parse(code).unwrap();
+    ///
+    /// if let Statement::Function(function) = &program[1] {
+    ///     let group = &function.attributes[0];
+    ///
+    ///     assert_eq!(&code[group.removal(1)], "B(1), ");
+    ///     assert_eq!(&code[group.removal(2)], ", C");
+    /// }
+    /// ```
+    pub fn removal(&self, index: usize) -> Range<usize> {
+        if self.members.len() == 1 {
+            return self.range();
+        }
+
+        let attribute = &self.members[index];
+        match self.members.get(index + 1) {
+            Some(next) => attribute.range().start..next.range().start,
+            None => self.members[index - 1].end.position..attribute.range().end,
+        }
+    }
+}
+
+impl Expression {
+    /// The operator token of this expression, when it is a unary, binary, assignment,
+    /// ternary or cast operation, e.g. `??` in `$a ?? $b`, and `?` in `$a ? $b : $c`.
+    ///
+    /// See [`Expression::operators`] for the `:` of a ternary operation.
+    ///
+    /// ```
+    /// use php_parser_rs::parser::ast::Statement;
+    ///
+    /// let code = "<?php $a = $b ?? 'c';";
+    /// let program = php_parser_rs::parse(code).unwrap();
+    ///
+    /// if let Statement::Expression(statement) = &program[1] {
+    ///     assert_eq!(&code[statement.expression.operator().unwrap().range()], "=");
+    /// }
+    /// ```
+    pub fn operator(&self) -> Option<Operator> {
+        let (span, symbol) = match self {
+            Expression::ArithmeticOperation(operation) => return Some(operation.operator()),
+            Expression::AssignmentOperation(operation) => return Some(operation.operator()),
+            Expression::BitwiseOperation(operation) => return Some(operation.operator()),
+            Expression::ComparisonOperation(operation) => return Some(operation.operator()),
+            Expression::LogicalOperation(operation) => return Some(operation.operator()),
+            Expression::Concat { dot, .. } => (dot, "."),
+            Expression::Instanceof { instanceof, .. } => (instanceof, "instanceof"),
+            Expression::Pipe {
+                pipe_greater_than, ..
+            } => (pipe_greater_than, "|>"),
+            Expression::ErrorSuppress { at, .. } => (at, "@"),
+            Expression::ShortTernary { question_colon, .. } => (question_colon, "?:"),
+            Expression::Ternary { question, .. } => (question, "?"),
+            Expression::Coalesce {
+                double_question, ..
+            } => (double_question, "??"),
+            Expression::Cast {
+                cast, length, kind, ..
+            } => {
+                let symbol = match kind {
+                    CastKind::Int => "(int)",
+                    CastKind::Bool => "(bool)",
+                    CastKind::Float => "(float)",
+                    CastKind::String => "(string)",
+                    CastKind::Array => "(array)",
+                    CastKind::Object => "(object)",
+                    CastKind::Unset => "(unset)",
+                };
+
+                return Some(Operator::new(*cast, symbol).with_length(*length));
+            }
+            _ => return None,
+        };
+
+        Some(Operator::new(*span, symbol))
+    }
+
+    /// The operator tokens of this expression in source order, which are the `?` and `:` of
+    /// a ternary operation, and the token of [`Expression::operator`] otherwise.
+    ///
+    /// ```
+    /// use php_parser_rs::parser::ast::Statement;
+    ///
+    /// let code = "<?php $a ? $b : $c;";
+    /// let program = php_parser_rs::parse(code).unwrap();
+    ///
+    /// if let Statement::Expression(statement) = &program[1] {
+    ///     let operators = statement.expression.operators();
+    ///     assert_eq!(&code[operators[1].range()], ":");
+    /// }
+    /// ```
+    pub fn operators(&self) -> Vec<Operator> {
+        match self {
+            Expression::Ternary {
+                question, colon, ..
+            } => vec![Operator::new(*question, "?"), Operator::new(*colon, ":")],
+            _ => self.operator().into_iter().collect(),
+        }
+    }
+}
+
+impl ArithmeticOperation {
+    /// The operator token of this operation.
+    pub fn operator(&self) -> Operator {
+        let (span, symbol) = match self {
+            ArithmeticOperation::Addition { plus, .. } => (plus, "+"),
+            ArithmeticOperation::Subtraction { minus, .. } => (minus, "-"),
+            ArithmeticOperation::Multiplication { asterisk, .. } => (asterisk, "*"),
+            ArithmeticOperation::Division { slash, .. } => (slash, "/"),
+            ArithmeticOperation::Modulo { percent, .. } => (percent, "%"),
+            ArithmeticOperation::Exponentiation { pow, .. } => (pow, "**"),
+            ArithmeticOperation::Negative { minus, .. } => (minus, "-"),
+            ArithmeticOperation::Positive { plus, .. } => (plus, "+"),
+            ArithmeticOperation::PreIncrement { increment, .. } => (increment, "++"),
+            ArithmeticOperation::PostIncrement { increment, .. } => (increment, "++"),
+            ArithmeticOperation::PreDecrement { decrement, .. } => (decrement, "--"),
+            ArithmeticOperation::PostDecrement { decrement, .. } => (decrement, "--"),
+        };
+
+        Operator::new(*span, symbol)
+    }
+}
+
+impl AssignmentOperation {
+    /// The operator token of this operation.
+    pub fn operator(&self) -> Operator {
+        let (span, symbol) = match self {
+            AssignmentOperation::Assign { equals, .. } => (equals, "="),
+            AssignmentOperation::AssignByReference { equals, .. } => (equals, "="),
+            AssignmentOperation::Addition { plus_equals, .. } => (plus_equals, "+="),
+            AssignmentOperation::Subtraction { minus_equals, .. } => (minus_equals, "-="),
+            AssignmentOperation::Multiplication {
+                asterisk_equals, ..
+            } => (asterisk_equals, "*="),
+            AssignmentOperation::Division { slash_equals, .. } => (slash_equals, "/="),
+            AssignmentOperation::Modulo { percent_equals, .. } => (percent_equals, "%="),
+            AssignmentOperation::Exponentiation { pow_equals, .. } => (pow_equals, "**="),
+            AssignmentOperation::Concat { dot_equals, .. } => (dot_equals, ".="),
+            AssignmentOperation::BitwiseAnd {
+                ampersand_equals, ..
+            } => (ampersand_equals, "&="),
+            AssignmentOperation::BitwiseOr { pipe_equals, .. } => (pipe_equals, "|="),
+            AssignmentOperation::BitwiseXor { caret_equals, .. } => (caret_equals, "^="),
+            AssignmentOperation::LeftShift {
+                left_shift_equals, ..
+            } => (left_shift_equals, "<<="),
+            AssignmentOperation::RightShift {
+                right_shift_equals, ..
+            } => (right_shift_equals, ">>="),
+            AssignmentOperation::Coalesce {
+                coalesce_equals, ..
+            } => (coalesce_equals, "??="),
+        };
+
+        Operator::new(*span, symbol)
+    }
+}
+
+impl BitwiseOperation {
+    /// The operator token of this operation.
+    pub fn operator(&self) -> Operator {
+        let (span, symbol) = match self {
+            BitwiseOperation::And { and, .. } => (and, "&"),
+            BitwiseOperation::Or { or, .. } => (or, "|"),
+            BitwiseOperation::Xor { xor, .. } => (xor, "^"),
+            BitwiseOperation::LeftShift { left_shift, .. } => (left_shift, "<<"),
+            BitwiseOperation::RightShift { right_shift, .. } => (right_shift, ">>"),
+            BitwiseOperation::Not { not, .. } => (not, "~"),
+        };
+
+        Operator::new(*span, symbol)
+    }
+}
+
+impl ComparisonOperation {
+    /// The operator token of this operation.
+    pub fn operator(&self) -> Operator {
+        let (span, symbol) = match self {
+            ComparisonOperation::Equal { double_equals, .. } => (double_equals, "=="),
+            ComparisonOperation::Identical { triple_equals, .. } => (triple_equals, "==="),
+            ComparisonOperation::NotEqual { bang_equals, .. } => (bang_equals, "!="),
+            ComparisonOperation::AngledNotEqual {
+                angled_left_right, ..
+            } => (angled_left_right, "<>"),
+            ComparisonOperation::NotIdentical {
+                bang_double_equals, ..
+            } => (bang_double_equals, "!=="),
+            ComparisonOperation::LessThan { less_than, .. } => (less_than, "<"),
+            ComparisonOperation::GreaterThan { greater_than, .. } => (greater_than, ">"),
+            ComparisonOperation::LessThanOrEqual {
+                less_than_equals, ..
+            } => (less_than_equals, "<="),
+            ComparisonOperation::GreaterThanOrEqual {
+                greater_than_equals,
+                ..
+            } => (greater_than_equals, ">="),
+            ComparisonOperation::Spaceship { spaceship, .. } => (spaceship, "<=>"),
+        };
+
+        Operator::new(*span, symbol)
+    }
+}
+
+impl LogicalOperation {
+    /// The operator token of this operation.
+    pub fn operator(&self) -> Operator {
+        let (span, symbol) = match self {
+            LogicalOperation::And {
+                double_ampersand, ..
+            } => (double_ampersand, "&&"),
+            LogicalOperation::Or { double_pipe, .. } => (double_pipe, "||"),
+            LogicalOperation::Not { bang, .. } => (bang, "!"),
+            LogicalOperation::LogicalAnd { and, .. } => (and, "and"),
+            LogicalOperation::LogicalOr { or, .. } => (or, "or"),
+            LogicalOperation::LogicalXor { xor, .. } => (xor, "xor"),
+        };
+
+        Operator::new(*span, symbol)
+    }
+}
+
+impl MagicConstant {
+    pub fn span(&self) -> Span {
+        match self {
+            MagicConstant::Directory(span)
+            | MagicConstant::File(span)
+            | MagicConstant::Line(span)
+            | MagicConstant::Class(span)
+            | MagicConstant::Function(span)
+            | MagicConstant::Method(span)
+            | MagicConstant::Namespace(span)
+            | MagicConstant::Trait(span)
+            | MagicConstant::CompilerHaltOffset(span) => *span,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::ast::Expression;
+    use crate::parser::ast::Statement;
+
+    // The source text between the start of the first token, and the end of the last token.
+    fn text(code: &str) -> String {
+        let code = format!("<?php {};", code);
+        let program = crate::parse(&code).unwrap();
+
+        let expression = match &program[1] {
+            Statement::Expression(statement) => &statement.expression,
+            statement => panic!("not an expression: {:?}", statement),
+        };
+
+        let start = expression.start().position;
+        let end = expression.end().position;
+        let last = crate::lexer::Lexer::new()
+            .tokenize(code.as_bytes())
+            .unwrap()
+            .into_iter()
+            .find(|token| token.span.position == end)
+            .unwrap();
+
+        code[start..end + last.value.len()].to_string()
+    }
+
+    #[test]
+    fn test_expression_spans() {
+        for code in [
+            "null",
+            "true",
+            "$a instanceof B",
+            "new B",
+            "clone $a->b",
+            "throw new Exception('a')",
+            "yield",
+            "yield $a => $b",
+            "yield from foo()",
+            "\"a $b c\"",
+            "`ls $a`",
+            "-$a++",
+            "$a = $b ??= [1, 2]",
+            "!$a || ~$b",
+            "$$a[0]",
+            "${'a'}->{$b}",
+            "print $a",
+            "exit",
+            "new class(1) { }",
+            "#[A] static fn() => $a",
+            "foo(...)",
+            "A::B",
+            "(int) __LINE__",
+        ] {
+            assert_eq!(text(code), code);
+        }
+    }
+
+    #[test]
+    fn test_attribute_spans() {
+        let code = "<?php\n#[A]\n#[B, \\C\\D(1, 2), E,]\nclass F {}";
+        let program = crate::parse(code).unwrap();
+
+        let groups = match &program[1] {
+            Statement::Class(class) => &class.attributes,
+            statement => panic!("not a class: {:?}", statement),
+        };
+
+        assert_eq!(&code[groups[0].range()], "#[A]");
+        assert_eq!(&code[groups[0].removal(0)], "#[A]");
+        assert_eq!(groups[0].members[0].comma(&groups[0]), None);
+
+        let group = &groups[1];
+        assert_eq!(&code[group.range()], "#[B, \\C\\D(1, 2), E,]");
+        assert_eq!(&code[group.members[1].range()], "\\C\\D(1, 2)");
+        assert_eq!(&code[group.removal(0)], "B, ");
+        assert_eq!(&code[group.removal(1)], "\\C\\D(1, 2), ");
+        assert_eq!(&code[group.removal(2)], ", E");
+        assert!(group.members[2].comma(group).is_some());
+    }
+
+    #[test]
+    fn test_heredoc_spans() {
+        let code = "<?php <<<EOT\n  a $b\n  EOT;";
+        let program = crate::parse(code).unwrap();
+
+        match &program[1] {
+            Statement::Expression(statement) => {
+                assert!(matches!(statement.expression, Expression::Heredoc { .. }));
+                assert_eq!(statement.expression.start().position, 6);
+                assert_eq!(statement.expression.end().position, 22);
+            }
+            statement => panic!("not an expression: {:?}", statement),
+        }
+    }
+
+    #[test]
+    fn test_operator_spans() {
+        let cases = [
+            ("$a + $b", "+"),
+            ("-$a", "-"),
+            ("$a++", "++"),
+            ("--$a", "--"),
+            ("$a ** $b", "**"),
+            ("$a = &$b", "="),
+            ("$a ??= $b", "??="),
+            ("$a <<= $b", "<<="),
+            ("~$a", "~"),
+            ("$a >> $b", ">>"),
+            ("$a <> $b", "<>"),
+            ("$a !== $b", "!=="),
+            ("$a <=> $b", "<=>"),
+            ("!$a", "!"),
+            ("$a AND $b", "AND"),
+            ("$a || $b", "||"),
+            ("$a . $b . $c", "."),
+            ("$a INSTANCEOF B", "INSTANCEOF"),
+            ("@$a", "@"),
+            ("$a ?: $b", "?:"),
+            ("$a ? : $b", "?"),
+            ("$a ? $b : $c", "?"),
+            ("$a ?? $b", "??"),
+            ("(int) $a", "(int)"),
+        ];
+
+        for (code, expected) in cases {
+            let code = format!("<?php {};", code);
+            let program = crate::parse(&code).unwrap();
+
+            match &program[1] {
+                Statement::Expression(statement) => {
+                    let operator = statement.expression.operator().unwrap();
+                    assert_eq!(&code[operator.range()], expected, "{}", code);
+                    assert!(operator.symbol.eq_ignore_ascii_case(expected));
+                }
+                statement => panic!("not an expression: {:?}", statement),
+            }
+        }
+
+        // Casts are named in their canonical form, and span the cast as written.
+        for (code, expected, symbol) in [
+            ("(BOOLEAN)$a", "(BOOLEAN)", "(bool)"),
+            ("( double ) $a", "( double )", "(float)"),
+        ] {
+            let code = format!("<?php {};", code);
+            let program = crate::parse(&code).unwrap();
+
+            match &program[1] {
+                Statement::Expression(statement) => {
+                    let operator = statement.expression.operator().unwrap();
+                    assert_eq!(&code[operator.range()], expected);
+                    assert_eq!(operator.symbol, symbol);
+                }
+                statement => panic!("not an expression: {:?}", statement),
+            }
+        }
+
+        let code = "<?php $a ? $b : $c;";
+        let program = crate::parse(code).unwrap();
+        match &program[1] {
+            Statement::Expression(statement) => {
+                let operators = statement.expression.operators();
+                let operators = operators.iter().map(|operator| &code[operator.range()]);
+                assert_eq!(operators.collect::<Vec<_>>(), vec!["?", ":"]);
+            }
+            statement => panic!("not an expression: {:?}", statement),
+        }
+
+        let program = crate::parse("<?php foo();").unwrap();
+        match &program[1] {
+            Statement::Expression(statement) => assert!(statement.expression.operator().is_none()),
+            statement => panic!("not an expression: {:?}", statement),
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use smallvec::SmallVec;
 
 use crate::lexer::token::Span;
 use crate::node::Node;
 use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::comments::CommentGroup;
 use crate::parser::ast::constant::ClassishConstant;
 use crate::parser::ast::functions::AbstractConstructor;
 use crate::parser::ast::functions::AbstractMethod;
@@ -17,6 +19,7 @@ use crate::parser::ast::properties::VariableProperty;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum TraitMember {
     Constant(ClassishConstant),
     TraitUsage(TraitUsage),
@@ -63,6 +66,7 @@ impl Node for TraitBody {
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct TraitStatement {
+    pub comments: CommentGroup,
     pub r#trait: Span,
     pub name: SimpleIdentifier,
     pub attributes: Vec<AttributeGroup>,
@@ -79,7 +83,7 @@ impl Node for TraitStatement {
 #[serde(rename_all = "snake_case")]
 pub struct TraitUsage {
     pub r#use: Span,
-    pub traits: Vec<SimpleIdentifier>,
+    pub traits: SmallVec<[SimpleIdentifier; 1]>,
     pub adaptations: Vec<TraitUsageAdaptation>,
 }
 
@@ -91,6 +95,7 @@ impl Node for TraitUsage {
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum TraitUsageAdaptation {
     Alias {
         r#trait: Option<SimpleIdentifier>,
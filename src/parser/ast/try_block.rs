@@ -11,6 +11,7 @@ use super::variables::SimpleVariable;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+#[non_exhaustive]
 pub enum CatchType {
     Identifier(SimpleIdentifier),
     Union(Vec<SimpleIdentifier>),
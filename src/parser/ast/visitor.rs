@@ -0,0 +1,2585 @@
+//! A typed visitor over the AST.
+//!
+//! The [`Visitor`] trait has a `visit_*` method for every node type. Each method defaults to
+//! calling the matching `walk_*` function, which visits the children of the node, so an
+//! implementation only overrides the methods for the nodes it is interested in, and calls the
+//! `walk_*` function from an override to keep descending.
+//!
+//! ```
+//! use php_parser_rs::parser::ast::functions::FunctionStatement;
+//! use php_parser_rs::parser::ast::visitor::{walk_function_statement, Visitor};
+//!
+//! #[derive(Default)]
+//! struct FunctionNames(Vec<String>);
+//!
+//! impl Visitor for FunctionNames {
+//!     fn visit_function_statement(&mut self, function: &FunctionStatement) {
+//!         self.0.push(function.name.value.to_string());
+//!
+//!         walk_function_statement(self, function);
+//!     }
+//! }
+//!
+//! let program = php_parser_rs::parse("<?php function a() { function b() {} }").unwrap();
+//!
+//! let mut names = FunctionNames::default();
+//! names.visit_program(&program);
+//!
+//! assert_eq!(names.0, vec!["a", "b"]);
+//! ```
+//!
+//! Spans, tokens, and plain values such as the name of a [`Type::Named`] are not visited, they
+//! are read from the node that holds them.
+
+use crate::parser::ast::arguments::Argument;
+use crate::parser::ast::arguments::ArgumentList;
+use crate::parser::ast::arguments::ArgumentPlaceholder;
+use crate::parser::ast::arguments::SingleArgument;
+use crate::parser::ast::attributes::Attribute;
+use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::classes::AnonymousClass;
+use crate::parser::ast::classes::AnonymousClassBody;
+use crate::parser::ast::classes::AnonymousClassMember;
+use crate::parser::ast::classes::ClassBody;
+use crate::parser::ast::classes::ClassExtends;
+use crate::parser::ast::classes::ClassImplements;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::comments::Comment;
+use crate::parser::ast::comments::CommentGroup;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::constant::ConstantEntry;
+use crate::parser::ast::constant::ConstantStatement;
+use crate::parser::ast::control_flow::IfStatement;
+use crate::parser::ast::control_flow::IfStatementBody;
+use crate::parser::ast::control_flow::IfStatementElse;
+use crate::parser::ast::control_flow::IfStatementElseBlock;
+use crate::parser::ast::control_flow::IfStatementElseIf;
+use crate::parser::ast::control_flow::IfStatementElseIfBlock;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::declares::DeclareBody;
+use crate::parser::ast::declares::DeclareEntry;
+use crate::parser::ast::declares::DeclareEntryGroup;
+use crate::parser::ast::declares::DeclareStatement;
+use crate::parser::ast::enums::BackedEnumBody;
+use crate::parser::ast::enums::BackedEnumCase;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::BackedEnumType;
+use crate::parser::ast::enums::UnitEnumBody;
+use crate::parser::ast::enums::UnitEnumCase;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::functions::AbstractConstructor;
+use crate::parser::ast::functions::AbstractMethod;
+use crate::parser::ast::functions::ArrowFunction;
+use crate::parser::ast::functions::Closure;
+use crate::parser::ast::functions::ClosureUse;
+use crate::parser::ast::functions::ClosureUseVariable;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::ConstructorParameter;
+use crate::parser::ast::functions::ConstructorParameterList;
+use crate::parser::ast::functions::FunctionBody;
+use crate::parser::ast::functions::FunctionParameter;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::functions::MethodBody;
+use crate::parser::ast::functions::ReturnType;
+use crate::parser::ast::goto::GotoStatement;
+use crate::parser::ast::goto::LabelStatement;
+use crate::parser::ast::identifiers::DynamicIdentifier;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceBody;
+use crate::parser::ast::interfaces::InterfaceExtends;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::interfaces::InterfaceStatement;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::literals::LiteralFloat;
+use crate::parser::ast::literals::LiteralInteger;
+use crate::parser::ast::literals::LiteralString;
+use crate::parser::ast::loops::BreakStatement;
+use crate::parser::ast::loops::ContinueStatement;
+use crate::parser::ast::loops::DoWhileStatement;
+use crate::parser::ast::loops::ForStatement;
+use crate::parser::ast::loops::ForStatementBody;
+use crate::parser::ast::loops::ForStatementIterator;
+use crate::parser::ast::loops::ForeachStatement;
+use crate::parser::ast::loops::ForeachStatementBody;
+use crate::parser::ast::loops::ForeachStatementIterator;
+use crate::parser::ast::loops::Level;
+use crate::parser::ast::loops::WhileStatement;
+use crate::parser::ast::loops::WhileStatementBody;
+use crate::parser::ast::modifiers::ClassModifier;
+use crate::parser::ast::modifiers::ClassModifierGroup;
+use crate::parser::ast::modifiers::ConstantModifier;
+use crate::parser::ast::modifiers::ConstantModifierGroup;
+use crate::parser::ast::modifiers::MethodModifier;
+use crate::parser::ast::modifiers::MethodModifierGroup;
+use crate::parser::ast::modifiers::PromotedPropertyModifier;
+use crate::parser::ast::modifiers::PromotedPropertyModifierGroup;
+use crate::parser::ast::modifiers::PropertyModifier;
+use crate::parser::ast::modifiers::PropertyModifierGroup;
+use crate::parser::ast::modifiers::VisibilityModifier;
+use crate::parser::ast::namespaces::BracedNamespace;
+use crate::parser::ast::namespaces::BracedNamespaceBody;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::namespaces::UnbracedNamespace;
+use crate::parser::ast::operators::ArithmeticOperation;
+use crate::parser::ast::operators::AssignmentOperation;
+use crate::parser::ast::operators::BitwiseOperation;
+use crate::parser::ast::operators::ComparisonOperation;
+use crate::parser::ast::operators::LogicalOperation;
+use crate::parser::ast::properties::Property;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::properties::PropertyHook;
+use crate::parser::ast::properties::PropertyHookBody;
+use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::traits::TraitBody;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitStatement;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::traits::TraitUsageAdaptation;
+use crate::parser::ast::try_block::CatchBlock;
+use crate::parser::ast::try_block::CatchType;
+use crate::parser::ast::try_block::FinallyBlock;
+use crate::parser::ast::try_block::TryStatement;
+use crate::parser::ast::variables::BracedVariableVariable;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::variables::VariableVariable;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::BlockStatement;
+use crate::parser::ast::Case;
+use crate::parser::ast::DefaultMatchArm;
+use crate::parser::ast::EchoStatement;
+use crate::parser::ast::ErrorStatement;
+use crate::parser::ast::ExitExpression;
+use crate::parser::ast::Expression;
+use crate::parser::ast::ExpressionStatement;
+use crate::parser::ast::ExpressionStringPart;
+use crate::parser::ast::ForeignChunk;
+use crate::parser::ast::GlobalStatement;
+use crate::parser::ast::GroupUseStatement;
+use crate::parser::ast::HaltCompiler;
+use crate::parser::ast::ListEntry;
+use crate::parser::ast::LiteralStringPart;
+use crate::parser::ast::MagicConstant;
+use crate::parser::ast::MatchArm;
+use crate::parser::ast::ReturnStatement;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StaticStatement;
+use crate::parser::ast::StaticVar;
+use crate::parser::ast::StringPart;
+use crate::parser::ast::SwitchStatement;
+use crate::parser::ast::Use;
+use crate::parser::ast::UseStatement;
+
+/// Visit the nodes of the AST, see the [module documentation](self).
+pub trait Visitor {
+    fn visit_program(&mut self, program: &[Statement]) {
+        walk_program(self, program);
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression);
+    }
+
+    fn visit_argument(&mut self, argument: &Argument) {
+        walk_argument(self, argument);
+    }
+
+    fn visit_argument_list(&mut self, argument_list: &ArgumentList) {
+        walk_argument_list(self, argument_list);
+    }
+
+    fn visit_single_argument(&mut self, single_argument: &SingleArgument) {
+        walk_single_argument(self, single_argument);
+    }
+
+    fn visit_argument_placeholder(&mut self, argument_placeholder: &ArgumentPlaceholder) {
+        walk_argument_placeholder(self, argument_placeholder);
+    }
+
+    fn visit_attribute(&mut self, attribute: &Attribute) {
+        walk_attribute(self, attribute);
+    }
+
+    fn visit_attribute_group(&mut self, attribute_group: &AttributeGroup) {
+        walk_attribute_group(self, attribute_group);
+    }
+
+    fn visit_class_body(&mut self, class_body: &ClassBody) {
+        walk_class_body(self, class_body);
+    }
+
+    fn visit_class_statement(&mut self, class_statement: &ClassStatement) {
+        walk_class_statement(self, class_statement);
+    }
+
+    fn visit_anonymous_class_body(&mut self, anonymous_class_body: &AnonymousClassBody) {
+        walk_anonymous_class_body(self, anonymous_class_body);
+    }
+
+    fn visit_anonymous_class(&mut self, anonymous_class: &AnonymousClass) {
+        walk_anonymous_class(self, anonymous_class);
+    }
+
+    fn visit_class_extends(&mut self, class_extends: &ClassExtends) {
+        walk_class_extends(self, class_extends);
+    }
+
+    fn visit_class_implements(&mut self, class_implements: &ClassImplements) {
+        walk_class_implements(self, class_implements);
+    }
+
+    fn visit_class_member(&mut self, class_member: &ClassMember) {
+        walk_class_member(self, class_member);
+    }
+
+    fn visit_anonymous_class_member(&mut self, anonymous_class_member: &AnonymousClassMember) {
+        walk_anonymous_class_member(self, anonymous_class_member);
+    }
+
+    fn visit_comment(&mut self, _comment: &Comment) {}
+
+    fn visit_comment_group(&mut self, comment_group: &CommentGroup) {
+        walk_comment_group(self, comment_group);
+    }
+
+    fn visit_constant_entry(&mut self, constant_entry: &ConstantEntry) {
+        walk_constant_entry(self, constant_entry);
+    }
+
+    fn visit_constant_statement(&mut self, constant_statement: &ConstantStatement) {
+        walk_constant_statement(self, constant_statement);
+    }
+
+    fn visit_classish_constant(&mut self, classish_constant: &ClassishConstant) {
+        walk_classish_constant(self, classish_constant);
+    }
+
+    fn visit_if_statement(&mut self, if_statement: &IfStatement) {
+        walk_if_statement(self, if_statement);
+    }
+
+    fn visit_if_statement_body(&mut self, if_statement_body: &IfStatementBody) {
+        walk_if_statement_body(self, if_statement_body);
+    }
+
+    fn visit_if_statement_else_if(&mut self, if_statement_else_if: &IfStatementElseIf) {
+        walk_if_statement_else_if(self, if_statement_else_if);
+    }
+
+    fn visit_if_statement_else(&mut self, if_statement_else: &IfStatementElse) {
+        walk_if_statement_else(self, if_statement_else);
+    }
+
+    fn visit_if_statement_else_if_block(
+        &mut self,
+        if_statement_else_if_block: &IfStatementElseIfBlock,
+    ) {
+        walk_if_statement_else_if_block(self, if_statement_else_if_block);
+    }
+
+    fn visit_if_statement_else_block(&mut self, if_statement_else_block: &IfStatementElseBlock) {
+        walk_if_statement_else_block(self, if_statement_else_block);
+    }
+
+    fn visit_type(&mut self, ty: &Type) {
+        walk_type(self, ty);
+    }
+
+    fn visit_declare_entry(&mut self, declare_entry: &DeclareEntry) {
+        walk_declare_entry(self, declare_entry);
+    }
+
+    fn visit_declare_entry_group(&mut self, declare_entry_group: &DeclareEntryGroup) {
+        walk_declare_entry_group(self, declare_entry_group);
+    }
+
+    fn visit_declare_body(&mut self, declare_body: &DeclareBody) {
+        walk_declare_body(self, declare_body);
+    }
+
+    fn visit_declare_statement(&mut self, declare_statement: &DeclareStatement) {
+        walk_declare_statement(self, declare_statement);
+    }
+
+    fn visit_unit_enum_case(&mut self, unit_enum_case: &UnitEnumCase) {
+        walk_unit_enum_case(self, unit_enum_case);
+    }
+
+    fn visit_unit_enum_member(&mut self, unit_enum_member: &UnitEnumMember) {
+        walk_unit_enum_member(self, unit_enum_member);
+    }
+
+    fn visit_unit_enum_body(&mut self, unit_enum_body: &UnitEnumBody) {
+        walk_unit_enum_body(self, unit_enum_body);
+    }
+
+    fn visit_unit_enum_statement(&mut self, unit_enum_statement: &UnitEnumStatement) {
+        walk_unit_enum_statement(self, unit_enum_statement);
+    }
+
+    fn visit_backed_enum_type(&mut self, _backed_enum_type: &BackedEnumType) {}
+
+    fn visit_backed_enum_case(&mut self, backed_enum_case: &BackedEnumCase) {
+        walk_backed_enum_case(self, backed_enum_case);
+    }
+
+    fn visit_backed_enum_member(&mut self, backed_enum_member: &BackedEnumMember) {
+        walk_backed_enum_member(self, backed_enum_member);
+    }
+
+    fn visit_backed_enum_body(&mut self, backed_enum_body: &BackedEnumBody) {
+        walk_backed_enum_body(self, backed_enum_body);
+    }
+
+    fn visit_backed_enum_statement(&mut self, backed_enum_statement: &BackedEnumStatement) {
+        walk_backed_enum_statement(self, backed_enum_statement);
+    }
+
+    fn visit_return_type(&mut self, return_type: &ReturnType) {
+        walk_return_type(self, return_type);
+    }
+
+    fn visit_function_parameter(&mut self, function_parameter: &FunctionParameter) {
+        walk_function_parameter(self, function_parameter);
+    }
+
+    fn visit_function_parameter_list(&mut self, function_parameter_list: &FunctionParameterList) {
+        walk_function_parameter_list(self, function_parameter_list);
+    }
+
+    fn visit_function_body(&mut self, function_body: &FunctionBody) {
+        walk_function_body(self, function_body);
+    }
+
+    fn visit_function_statement(&mut self, function_statement: &FunctionStatement) {
+        walk_function_statement(self, function_statement);
+    }
+
+    fn visit_closure_use_variable(&mut self, closure_use_variable: &ClosureUseVariable) {
+        walk_closure_use_variable(self, closure_use_variable);
+    }
+
+    fn visit_closure_use(&mut self, closure_use: &ClosureUse) {
+        walk_closure_use(self, closure_use);
+    }
+
+    fn visit_exit_expression(&mut self, exit_expression: &ExitExpression) {
+        walk_exit_expression(self, exit_expression);
+    }
+
+    fn visit_closure(&mut self, closure: &Closure) {
+        walk_closure(self, closure);
+    }
+
+    fn visit_arrow_function(&mut self, arrow_function: &ArrowFunction) {
+        walk_arrow_function(self, arrow_function);
+    }
+
+    fn visit_constructor_parameter(&mut self, constructor_parameter: &ConstructorParameter) {
+        walk_constructor_parameter(self, constructor_parameter);
+    }
+
+    fn visit_constructor_parameter_list(
+        &mut self,
+        constructor_parameter_list: &ConstructorParameterList,
+    ) {
+        walk_constructor_parameter_list(self, constructor_parameter_list);
+    }
+
+    fn visit_abstract_constructor(&mut self, abstract_constructor: &AbstractConstructor) {
+        walk_abstract_constructor(self, abstract_constructor);
+    }
+
+    fn visit_concrete_constructor(&mut self, concrete_constructor: &ConcreteConstructor) {
+        walk_concrete_constructor(self, concrete_constructor);
+    }
+
+    fn visit_abstract_method(&mut self, abstract_method: &AbstractMethod) {
+        walk_abstract_method(self, abstract_method);
+    }
+
+    fn visit_concrete_method(&mut self, concrete_method: &ConcreteMethod) {
+        walk_concrete_method(self, concrete_method);
+    }
+
+    fn visit_method_body(&mut self, method_body: &MethodBody) {
+        walk_method_body(self, method_body);
+    }
+
+    fn visit_label_statement(&mut self, label_statement: &LabelStatement) {
+        walk_label_statement(self, label_statement);
+    }
+
+    fn visit_goto_statement(&mut self, goto_statement: &GotoStatement) {
+        walk_goto_statement(self, goto_statement);
+    }
+
+    fn visit_identifier(&mut self, identifier: &Identifier) {
+        walk_identifier(self, identifier);
+    }
+
+    fn visit_simple_identifier(&mut self, _simple_identifier: &SimpleIdentifier) {}
+
+    fn visit_dynamic_identifier(&mut self, dynamic_identifier: &DynamicIdentifier) {
+        walk_dynamic_identifier(self, dynamic_identifier);
+    }
+
+    fn visit_interface_member(&mut self, interface_member: &InterfaceMember) {
+        walk_interface_member(self, interface_member);
+    }
+
+    fn visit_interface_extends(&mut self, interface_extends: &InterfaceExtends) {
+        walk_interface_extends(self, interface_extends);
+    }
+
+    fn visit_interface_body(&mut self, interface_body: &InterfaceBody) {
+        walk_interface_body(self, interface_body);
+    }
+
+    fn visit_interface_statement(&mut self, interface_statement: &InterfaceStatement) {
+        walk_interface_statement(self, interface_statement);
+    }
+
+    fn visit_literal(&mut self, literal: &Literal) {
+        walk_literal(self, literal);
+    }
+
+    fn visit_literal_string(&mut self, _literal_string: &LiteralString) {}
+
+    fn visit_literal_integer(&mut self, _literal_integer: &LiteralInteger) {}
+
+    fn visit_literal_float(&mut self, _literal_float: &LiteralFloat) {}
+
+    fn visit_foreach_statement(&mut self, foreach_statement: &ForeachStatement) {
+        walk_foreach_statement(self, foreach_statement);
+    }
+
+    fn visit_foreach_statement_iterator(
+        &mut self,
+        foreach_statement_iterator: &ForeachStatementIterator,
+    ) {
+        walk_foreach_statement_iterator(self, foreach_statement_iterator);
+    }
+
+    fn visit_foreach_statement_body(&mut self, foreach_statement_body: &ForeachStatementBody) {
+        walk_foreach_statement_body(self, foreach_statement_body);
+    }
+
+    fn visit_for_statement(&mut self, for_statement: &ForStatement) {
+        walk_for_statement(self, for_statement);
+    }
+
+    fn visit_for_statement_iterator(&mut self, for_statement_iterator: &ForStatementIterator) {
+        walk_for_statement_iterator(self, for_statement_iterator);
+    }
+
+    fn visit_for_statement_body(&mut self, for_statement_body: &ForStatementBody) {
+        walk_for_statement_body(self, for_statement_body);
+    }
+
+    fn visit_do_while_statement(&mut self, do_while_statement: &DoWhileStatement) {
+        walk_do_while_statement(self, do_while_statement);
+    }
+
+    fn visit_while_statement(&mut self, while_statement: &WhileStatement) {
+        walk_while_statement(self, while_statement);
+    }
+
+    fn visit_while_statement_body(&mut self, while_statement_body: &WhileStatementBody) {
+        walk_while_statement_body(self, while_statement_body);
+    }
+
+    fn visit_level(&mut self, level: &Level) {
+        walk_level(self, level);
+    }
+
+    fn visit_break_statement(&mut self, break_statement: &BreakStatement) {
+        walk_break_statement(self, break_statement);
+    }
+
+    fn visit_continue_statement(&mut self, continue_statement: &ContinueStatement) {
+        walk_continue_statement(self, continue_statement);
+    }
+
+    fn visit_static_var(&mut self, static_var: &StaticVar) {
+        walk_static_var(self, static_var);
+    }
+
+    fn visit_halt_compiler(&mut self, _halt_compiler: &HaltCompiler) {}
+
+    fn visit_foreign_chunk(&mut self, _foreign_chunk: &ForeignChunk) {}
+
+    fn visit_error_statement(&mut self, _error_statement: &ErrorStatement) {}
+
+    fn visit_static_statement(&mut self, static_statement: &StaticStatement) {
+        walk_static_statement(self, static_statement);
+    }
+
+    fn visit_switch_statement(&mut self, switch_statement: &SwitchStatement) {
+        walk_switch_statement(self, switch_statement);
+    }
+
+    fn visit_echo_statement(&mut self, echo_statement: &EchoStatement) {
+        walk_echo_statement(self, echo_statement);
+    }
+
+    fn visit_return_statement(&mut self, return_statement: &ReturnStatement) {
+        walk_return_statement(self, return_statement);
+    }
+
+    fn visit_use_statement(&mut self, use_statement: &UseStatement) {
+        walk_use_statement(self, use_statement);
+    }
+
+    fn visit_group_use_statement(&mut self, group_use_statement: &GroupUseStatement) {
+        walk_group_use_statement(self, group_use_statement);
+    }
+
+    fn visit_expression_statement(&mut self, expression_statement: &ExpressionStatement) {
+        walk_expression_statement(self, expression_statement);
+    }
+
+    fn visit_global_statement(&mut self, global_statement: &GlobalStatement) {
+        walk_global_statement(self, global_statement);
+    }
+
+    fn visit_block_statement(&mut self, block_statement: &BlockStatement) {
+        walk_block_statement(self, block_statement);
+    }
+
+    fn visit_case(&mut self, case: &Case) {
+        walk_case(self, case);
+    }
+
+    fn visit_use(&mut self, r#use: &Use) {
+        walk_use(self, r#use);
+    }
+
+    fn visit_default_match_arm(&mut self, default_match_arm: &DefaultMatchArm) {
+        walk_default_match_arm(self, default_match_arm);
+    }
+
+    fn visit_match_arm(&mut self, match_arm: &MatchArm) {
+        walk_match_arm(self, match_arm);
+    }
+
+    fn visit_magic_constant(&mut self, _magic_constant: &MagicConstant) {}
+
+    fn visit_string_part(&mut self, string_part: &StringPart) {
+        walk_string_part(self, string_part);
+    }
+
+    fn visit_literal_string_part(&mut self, _literal_string_part: &LiteralStringPart) {}
+
+    fn visit_expression_string_part(&mut self, expression_string_part: &ExpressionStringPart) {
+        walk_expression_string_part(self, expression_string_part);
+    }
+
+    fn visit_array_item(&mut self, array_item: &ArrayItem) {
+        walk_array_item(self, array_item);
+    }
+
+    fn visit_list_entry(&mut self, list_entry: &ListEntry) {
+        walk_list_entry(self, list_entry);
+    }
+
+    fn visit_visibility_modifier(&mut self, _visibility_modifier: &VisibilityModifier) {}
+
+    fn visit_promoted_property_modifier(
+        &mut self,
+        _promoted_property_modifier: &PromotedPropertyModifier,
+    ) {
+    }
+
+    fn visit_promoted_property_modifier_group(
+        &mut self,
+        promoted_property_modifier_group: &PromotedPropertyModifierGroup,
+    ) {
+        walk_promoted_property_modifier_group(self, promoted_property_modifier_group);
+    }
+
+    fn visit_property_modifier(&mut self, _property_modifier: &PropertyModifier) {}
+
+    fn visit_property_modifier_group(&mut self, property_modifier_group: &PropertyModifierGroup) {
+        walk_property_modifier_group(self, property_modifier_group);
+    }
+
+    fn visit_method_modifier(&mut self, _method_modifier: &MethodModifier) {}
+
+    fn visit_method_modifier_group(&mut self, method_modifier_group: &MethodModifierGroup) {
+        walk_method_modifier_group(self, method_modifier_group);
+    }
+
+    fn visit_class_modifier(&mut self, _class_modifier: &ClassModifier) {}
+
+    fn visit_class_modifier_group(&mut self, class_modifier_group: &ClassModifierGroup) {
+        walk_class_modifier_group(self, class_modifier_group);
+    }
+
+    fn visit_constant_modifier(&mut self, _constant_modifier: &ConstantModifier) {}
+
+    fn visit_constant_modifier_group(&mut self, constant_modifier_group: &ConstantModifierGroup) {
+        walk_constant_modifier_group(self, constant_modifier_group);
+    }
+
+    fn visit_unbraced_namespace(&mut self, unbraced_namespace: &UnbracedNamespace) {
+        walk_unbraced_namespace(self, unbraced_namespace);
+    }
+
+    fn visit_braced_namespace(&mut self, braced_namespace: &BracedNamespace) {
+        walk_braced_namespace(self, braced_namespace);
+    }
+
+    fn visit_braced_namespace_body(&mut self, braced_namespace_body: &BracedNamespaceBody) {
+        walk_braced_namespace_body(self, braced_namespace_body);
+    }
+
+    fn visit_namespace_statement(&mut self, namespace_statement: &NamespaceStatement) {
+        walk_namespace_statement(self, namespace_statement);
+    }
+
+    fn visit_arithmetic_operation(&mut self, arithmetic_operation: &ArithmeticOperation) {
+        walk_arithmetic_operation(self, arithmetic_operation);
+    }
+
+    fn visit_assignment_operation(&mut self, assignment_operation: &AssignmentOperation) {
+        walk_assignment_operation(self, assignment_operation);
+    }
+
+    fn visit_bitwise_operation(&mut self, bitwise_operation: &BitwiseOperation) {
+        walk_bitwise_operation(self, bitwise_operation);
+    }
+
+    fn visit_comparison_operation(&mut self, comparison_operation: &ComparisonOperation) {
+        walk_comparison_operation(self, comparison_operation);
+    }
+
+    fn visit_logical_operation(&mut self, logical_operation: &LogicalOperation) {
+        walk_logical_operation(self, logical_operation);
+    }
+
+    fn visit_property(&mut self, property: &Property) {
+        walk_property(self, property);
+    }
+
+    fn visit_variable_property(&mut self, variable_property: &VariableProperty) {
+        walk_variable_property(self, variable_property);
+    }
+
+    fn visit_property_entry(&mut self, property_entry: &PropertyEntry) {
+        walk_property_entry(self, property_entry);
+    }
+
+    fn visit_property_hook(&mut self, property_hook: &PropertyHook) {
+        walk_property_hook(self, property_hook);
+    }
+
+    fn visit_trait_member(&mut self, trait_member: &TraitMember) {
+        walk_trait_member(self, trait_member);
+    }
+
+    fn visit_trait_body(&mut self, trait_body: &TraitBody) {
+        walk_trait_body(self, trait_body);
+    }
+
+    fn visit_trait_statement(&mut self, trait_statement: &TraitStatement) {
+        walk_trait_statement(self, trait_statement);
+    }
+
+    fn visit_trait_usage(&mut self, trait_usage: &TraitUsage) {
+        walk_trait_usage(self, trait_usage);
+    }
+
+    fn visit_trait_usage_adaptation(&mut self, trait_usage_adaptation: &TraitUsageAdaptation) {
+        walk_trait_usage_adaptation(self, trait_usage_adaptation);
+    }
+
+    fn visit_catch_type(&mut self, catch_type: &CatchType) {
+        walk_catch_type(self, catch_type);
+    }
+
+    fn visit_try_statement(&mut self, try_statement: &TryStatement) {
+        walk_try_statement(self, try_statement);
+    }
+
+    fn visit_catch_block(&mut self, catch_block: &CatchBlock) {
+        walk_catch_block(self, catch_block);
+    }
+
+    fn visit_finally_block(&mut self, finally_block: &FinallyBlock) {
+        walk_finally_block(self, finally_block);
+    }
+
+    fn visit_variable(&mut self, variable: &Variable) {
+        walk_variable(self, variable);
+    }
+
+    fn visit_simple_variable(&mut self, _simple_variable: &SimpleVariable) {}
+
+    fn visit_variable_variable(&mut self, variable_variable: &VariableVariable) {
+        walk_variable_variable(self, variable_variable);
+    }
+
+    fn visit_braced_variable_variable(
+        &mut self,
+        braced_variable_variable: &BracedVariableVariable,
+    ) {
+        walk_braced_variable_variable(self, braced_variable_variable);
+    }
+}
+
+pub fn walk_program<V: Visitor + ?Sized>(visitor: &mut V, program: &[Statement]) {
+    for statement in program {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &Statement) {
+    match statement {
+        Statement::ForeignChunk(foreign_chunk) => visitor.visit_foreign_chunk(foreign_chunk),
+        Statement::Label(label_statement) => visitor.visit_label_statement(label_statement),
+        Statement::Goto(goto_statement) => visitor.visit_goto_statement(goto_statement),
+        Statement::HaltCompiler(halt_compiler) => visitor.visit_halt_compiler(halt_compiler),
+        Statement::Static(static_statement) => visitor.visit_static_statement(static_statement),
+        Statement::DoWhile(do_while_statement) => {
+            visitor.visit_do_while_statement(do_while_statement)
+        }
+        Statement::While(while_statement) => visitor.visit_while_statement(while_statement),
+        Statement::For(for_statement) => visitor.visit_for_statement(for_statement),
+        Statement::Foreach(foreach_statement) => visitor.visit_foreach_statement(foreach_statement),
+        Statement::Break(break_statement) => visitor.visit_break_statement(break_statement),
+        Statement::Continue(continue_statement) => {
+            visitor.visit_continue_statement(continue_statement)
+        }
+        Statement::Constant(constant_statement) => {
+            visitor.visit_constant_statement(constant_statement)
+        }
+        Statement::Function(function_statement) => {
+            visitor.visit_function_statement(function_statement)
+        }
+        Statement::Class(class_statement) => visitor.visit_class_statement(class_statement),
+        Statement::Trait(trait_statement) => visitor.visit_trait_statement(trait_statement),
+        Statement::Interface(interface_statement) => {
+            visitor.visit_interface_statement(interface_statement)
+        }
+        Statement::If(if_statement) => visitor.visit_if_statement(if_statement),
+        Statement::Switch(switch_statement) => visitor.visit_switch_statement(switch_statement),
+        Statement::Echo(echo_statement) => visitor.visit_echo_statement(echo_statement),
+        Statement::Expression(expression_statement) => {
+            visitor.visit_expression_statement(expression_statement)
+        }
+        Statement::Return(return_statement) => visitor.visit_return_statement(return_statement),
+        Statement::Namespace(namespace_statement) => {
+            visitor.visit_namespace_statement(namespace_statement)
+        }
+        Statement::Use(use_statement) => visitor.visit_use_statement(use_statement),
+        Statement::GroupUse(group_use_statement) => {
+            visitor.visit_group_use_statement(group_use_statement)
+        }
+        Statement::Comment(comment) => visitor.visit_comment(comment),
+        Statement::Try(try_statement) => visitor.visit_try_statement(try_statement),
+        Statement::UnitEnum(unit_enum_statement) => {
+            visitor.visit_unit_enum_statement(unit_enum_statement)
+        }
+        Statement::BackedEnum(backed_enum_statement) => {
+            visitor.visit_backed_enum_statement(backed_enum_statement)
+        }
+        Statement::Block(block_statement) => visitor.visit_block_statement(block_statement),
+        Statement::Global(global_statement) => visitor.visit_global_statement(global_statement),
+        Statement::Declare(declare_statement) => visitor.visit_declare_statement(declare_statement),
+        Statement::Error(error_statement) => visitor.visit_error_statement(error_statement),
+        Statement::FullOpeningTag(_)
+        | Statement::ShortOpeningTag(_)
+        | Statement::EchoOpeningTag(_)
+        | Statement::ClosingTag(_)
+        | Statement::InlineHtml(_)
+        | Statement::Noop(_) => {}
+    }
+}
+
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &Expression) {
+    match expression {
+        Expression::Eval { argument, .. } | Expression::Empty { argument, .. } => {
+            visitor.visit_single_argument(argument)
+        }
+        Expression::Exit(exit) => visitor.visit_exit_expression(exit),
+        Expression::Isset { arguments, .. } | Expression::Unset { arguments, .. } => {
+            visitor.visit_argument_list(arguments)
+        }
+        Expression::Print {
+            value, argument, ..
+        } => {
+            if let Some(value) = value {
+                visitor.visit_expression(value);
+            }
+            if let Some(argument) = argument {
+                visitor.visit_single_argument(argument);
+            }
+        }
+        Expression::Literal(literal) => visitor.visit_literal(literal),
+        Expression::ArithmeticOperation(arithmetic_operation) => {
+            visitor.visit_arithmetic_operation(arithmetic_operation)
+        }
+        Expression::AssignmentOperation(assignment_operation) => {
+            visitor.visit_assignment_operation(assignment_operation)
+        }
+        Expression::BitwiseOperation(bitwise_operation) => {
+            visitor.visit_bitwise_operation(bitwise_operation)
+        }
+        Expression::ComparisonOperation(comparison_operation) => {
+            visitor.visit_comparison_operation(comparison_operation)
+        }
+        Expression::LogicalOperation(logical_operation) => {
+            visitor.visit_logical_operation(logical_operation)
+        }
+        Expression::Concat { left, right, .. }
+        | Expression::Instanceof { left, right, .. }
+        | Expression::Pipe { left, right, .. } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+        Expression::Parenthesized { expr, .. } | Expression::ErrorSuppress { expr, .. } => {
+            visitor.visit_expression(expr)
+        }
+        Expression::Identifier(identifier) => visitor.visit_identifier(identifier),
+        Expression::Variable(variable) => visitor.visit_variable(variable),
+        Expression::Include { path, .. }
+        | Expression::IncludeOnce { path, .. }
+        | Expression::Require { path, .. }
+        | Expression::RequireOnce { path, .. } => visitor.visit_expression(path),
+        Expression::FunctionCall { target, arguments } => {
+            visitor.visit_expression(target);
+            visitor.visit_argument_list(arguments);
+        }
+        Expression::FunctionClosureCreation {
+            target,
+            placeholder,
+        } => {
+            visitor.visit_expression(target);
+            visitor.visit_argument_placeholder(placeholder);
+        }
+        Expression::MethodCall {
+            target,
+            method,
+            arguments,
+            ..
+        }
+        | Expression::NullsafeMethodCall {
+            target,
+            method,
+            arguments,
+            ..
+        } => {
+            visitor.visit_expression(target);
+            visitor.visit_expression(method);
+            visitor.visit_argument_list(arguments);
+        }
+        Expression::MethodClosureCreation {
+            target,
+            method,
+            placeholder,
+            ..
+        } => {
+            visitor.visit_expression(target);
+            visitor.visit_expression(method);
+            visitor.visit_argument_placeholder(placeholder);
+        }
+        Expression::StaticMethodCall {
+            target,
+            method,
+            arguments,
+            ..
+        } => {
+            visitor.visit_expression(target);
+            visitor.visit_identifier(method);
+            visitor.visit_argument_list(arguments);
+        }
+        Expression::StaticVariableMethodCall {
+            target,
+            method,
+            arguments,
+            ..
+        } => {
+            visitor.visit_expression(target);
+            visitor.visit_variable(method);
+            visitor.visit_argument_list(arguments);
+        }
+        Expression::StaticMethodClosureCreation {
+            target,
+            method,
+            placeholder,
+            ..
+        } => {
+            visitor.visit_expression(target);
+            visitor.visit_identifier(method);
+            visitor.visit_argument_placeholder(placeholder);
+        }
+        Expression::StaticVariableMethodClosureCreation {
+            target,
+            method,
+            placeholder,
+            ..
+        } => {
+            visitor.visit_expression(target);
+            visitor.visit_variable(method);
+            visitor.visit_argument_placeholder(placeholder);
+        }
+        Expression::PropertyFetch {
+            target, property, ..
+        }
+        | Expression::NullsafePropertyFetch {
+            target, property, ..
+        } => {
+            visitor.visit_expression(target);
+            visitor.visit_expression(property);
+        }
+        Expression::StaticPropertyFetch {
+            target, property, ..
+        } => {
+            visitor.visit_expression(target);
+            visitor.visit_variable(property);
+        }
+        Expression::ConstantFetch {
+            target, constant, ..
+        } => {
+            visitor.visit_expression(target);
+            visitor.visit_identifier(constant);
+        }
+        Expression::ShortArray { items, .. } => {
+            for array_item in &items.inner {
+                visitor.visit_array_item(array_item);
+            }
+        }
+        Expression::Array(array) => {
+            for array_item in &array.items.inner {
+                visitor.visit_array_item(array_item);
+            }
+        }
+        Expression::List(list) => {
+            for list_entry in &list.items {
+                visitor.visit_list_entry(list_entry);
+            }
+        }
+        Expression::Closure(closure) => visitor.visit_closure(closure),
+        Expression::ArrowFunction(arrow_function) => visitor.visit_arrow_function(arrow_function),
+        Expression::New {
+            target, arguments, ..
+        } => {
+            visitor.visit_expression(target);
+            if let Some(arguments) = arguments {
+                visitor.visit_argument_list(arguments);
+            }
+        }
+        Expression::InterpolatedString { parts, .. }
+        | Expression::Heredoc { parts, .. }
+        | Expression::Nowdoc { parts, .. }
+        | Expression::ShellExec { parts, .. } => {
+            for string_part in parts {
+                visitor.visit_string_part(string_part);
+            }
+        }
+        Expression::AnonymousClass(anonymous_class) => {
+            visitor.visit_anonymous_class(anonymous_class)
+        }
+        Expression::ArrayIndex { array, index, .. } => {
+            visitor.visit_expression(array);
+            if let Some(index) = index {
+                visitor.visit_expression(index);
+            }
+        }
+        Expression::MagicConstant(magic_constant) => visitor.visit_magic_constant(magic_constant),
+        Expression::ShortTernary {
+            condition, r#else, ..
+        } => {
+            visitor.visit_expression(condition);
+            visitor.visit_expression(r#else);
+        }
+        Expression::Ternary {
+            condition,
+            then,
+            r#else,
+            ..
+        } => {
+            visitor.visit_expression(condition);
+            visitor.visit_expression(then);
+            visitor.visit_expression(r#else);
+        }
+        Expression::Coalesce { lhs, rhs, .. } => {
+            visitor.visit_expression(lhs);
+            visitor.visit_expression(rhs);
+        }
+        Expression::Clone { target, .. } => visitor.visit_expression(target),
+        Expression::Match(expression) => {
+            visitor.visit_expression(&expression.condition);
+            for match_arm in &expression.arms {
+                visitor.visit_match_arm(match_arm);
+            }
+            if let Some(default) = &expression.default {
+                visitor.visit_default_match_arm(default);
+            }
+        }
+        Expression::Throw { value, .. } | Expression::YieldFrom { value, .. } => {
+            visitor.visit_expression(value)
+        }
+        Expression::Yield { key, value, .. } => {
+            if let Some(key) = key {
+                visitor.visit_expression(key);
+            }
+            if let Some(value) = value {
+                visitor.visit_expression(value);
+            }
+        }
+        Expression::Cast { value, .. } => visitor.visit_expression(value),
+        Expression::Static { .. }
+        | Expression::Self_ { .. }
+        | Expression::Parent { .. }
+        | Expression::Bool { .. }
+        | Expression::Null { .. }
+        | Expression::Noop { .. }
+        | Expression::Missing { .. } => {}
+    }
+}
+
+pub fn walk_argument<V: Visitor + ?Sized>(visitor: &mut V, argument: &Argument) {
+    match argument {
+        Argument::Positional {
+            comments, value, ..
+        } => {
+            visitor.visit_comment_group(comments);
+            visitor.visit_expression(value);
+        }
+        Argument::Named {
+            comments,
+            name,
+            value,
+            ..
+        } => {
+            visitor.visit_comment_group(comments);
+            visitor.visit_simple_identifier(name);
+            visitor.visit_expression(value);
+        }
+    }
+}
+
+pub fn walk_argument_list<V: Visitor + ?Sized>(visitor: &mut V, argument_list: &ArgumentList) {
+    visitor.visit_comment_group(&argument_list.comments);
+    for argument in &argument_list.arguments {
+        visitor.visit_argument(argument);
+    }
+}
+
+pub fn walk_single_argument<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    single_argument: &SingleArgument,
+) {
+    visitor.visit_comment_group(&single_argument.comments);
+    visitor.visit_argument(&single_argument.argument);
+}
+
+pub fn walk_argument_placeholder<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    argument_placeholder: &ArgumentPlaceholder,
+) {
+    visitor.visit_comment_group(&argument_placeholder.comments);
+}
+
+pub fn walk_attribute<V: Visitor + ?Sized>(visitor: &mut V, attribute: &Attribute) {
+    visitor.visit_simple_identifier(&attribute.name);
+    if let Some(arguments) = &attribute.arguments {
+        visitor.visit_argument_list(arguments);
+    }
+}
+
+pub fn walk_attribute_group<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    attribute_group: &AttributeGroup,
+) {
+    for attribute in &attribute_group.members {
+        visitor.visit_attribute(attribute);
+    }
+}
+
+pub fn walk_class_body<V: Visitor + ?Sized>(visitor: &mut V, class_body: &ClassBody) {
+    for class_member in &class_body.members {
+        visitor.visit_class_member(class_member);
+    }
+}
+
+pub fn walk_class_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    class_statement: &ClassStatement,
+) {
+    visitor.visit_comment_group(&class_statement.comments);
+    for attribute_group in &class_statement.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_class_modifier_group(&class_statement.modifiers);
+    visitor.visit_simple_identifier(&class_statement.name);
+    if let Some(extends) = &class_statement.extends {
+        visitor.visit_class_extends(extends);
+    }
+    if let Some(implements) = &class_statement.implements {
+        visitor.visit_class_implements(implements);
+    }
+    visitor.visit_class_body(&class_statement.body);
+}
+
+pub fn walk_anonymous_class_body<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    anonymous_class_body: &AnonymousClassBody,
+) {
+    for anonymous_class_member in &anonymous_class_body.members {
+        visitor.visit_anonymous_class_member(anonymous_class_member);
+    }
+}
+
+pub fn walk_anonymous_class<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    anonymous_class: &AnonymousClass,
+) {
+    for attribute_group in &anonymous_class.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    if let Some(extends) = &anonymous_class.extends {
+        visitor.visit_class_extends(extends);
+    }
+    if let Some(implements) = &anonymous_class.implements {
+        visitor.visit_class_implements(implements);
+    }
+    visitor.visit_anonymous_class_body(&anonymous_class.body);
+}
+
+pub fn walk_class_extends<V: Visitor + ?Sized>(visitor: &mut V, class_extends: &ClassExtends) {
+    visitor.visit_simple_identifier(&class_extends.parent);
+}
+
+pub fn walk_class_implements<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    class_implements: &ClassImplements,
+) {
+    for simple_identifier in &class_implements.interfaces.inner {
+        visitor.visit_simple_identifier(simple_identifier);
+    }
+}
+
+pub fn walk_class_member<V: Visitor + ?Sized>(visitor: &mut V, class_member: &ClassMember) {
+    match class_member {
+        ClassMember::Constant(classish_constant) => {
+            visitor.visit_classish_constant(classish_constant)
+        }
+        ClassMember::TraitUsage(trait_usage) => visitor.visit_trait_usage(trait_usage),
+        ClassMember::Property(property) => visitor.visit_property(property),
+        ClassMember::VariableProperty(variable_property) => {
+            visitor.visit_variable_property(variable_property)
+        }
+        ClassMember::AbstractMethod(abstract_method) => {
+            visitor.visit_abstract_method(abstract_method)
+        }
+        ClassMember::AbstractConstructor(abstract_constructor) => {
+            visitor.visit_abstract_constructor(abstract_constructor)
+        }
+        ClassMember::ConcreteMethod(concrete_method) => {
+            visitor.visit_concrete_method(concrete_method)
+        }
+        ClassMember::ConcreteConstructor(concrete_constructor) => {
+            visitor.visit_concrete_constructor(concrete_constructor)
+        }
+    }
+}
+
+pub fn walk_anonymous_class_member<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    anonymous_class_member: &AnonymousClassMember,
+) {
+    match anonymous_class_member {
+        AnonymousClassMember::Constant(classish_constant) => {
+            visitor.visit_classish_constant(classish_constant)
+        }
+        AnonymousClassMember::TraitUsage(trait_usage) => visitor.visit_trait_usage(trait_usage),
+        AnonymousClassMember::Property(property) => visitor.visit_property(property),
+        AnonymousClassMember::VariableProperty(variable_property) => {
+            visitor.visit_variable_property(variable_property)
+        }
+        AnonymousClassMember::ConcreteMethod(concrete_method) => {
+            visitor.visit_concrete_method(concrete_method)
+        }
+        AnonymousClassMember::ConcreteConstructor(concrete_constructor) => {
+            visitor.visit_concrete_constructor(concrete_constructor)
+        }
+    }
+}
+
+pub fn walk_comment_group<V: Visitor + ?Sized>(visitor: &mut V, comment_group: &CommentGroup) {
+    for comment in &comment_group.comments {
+        visitor.visit_comment(comment);
+    }
+}
+
+pub fn walk_constant_entry<V: Visitor + ?Sized>(visitor: &mut V, constant_entry: &ConstantEntry) {
+    visitor.visit_simple_identifier(&constant_entry.name);
+    visitor.visit_expression(&constant_entry.value);
+}
+
+pub fn walk_constant_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    constant_statement: &ConstantStatement,
+) {
+    visitor.visit_comment_group(&constant_statement.comments);
+    for constant_entry in &constant_statement.entries {
+        visitor.visit_constant_entry(constant_entry);
+    }
+}
+
+pub fn walk_classish_constant<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    classish_constant: &ClassishConstant,
+) {
+    visitor.visit_comment_group(&classish_constant.comments);
+    for attribute_group in &classish_constant.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_constant_modifier_group(&classish_constant.modifiers);
+    if let Some(ty) = &classish_constant.data_type {
+        visitor.visit_type(ty);
+    }
+    for constant_entry in &classish_constant.entries {
+        visitor.visit_constant_entry(constant_entry);
+    }
+}
+
+pub fn walk_if_statement<V: Visitor + ?Sized>(visitor: &mut V, if_statement: &IfStatement) {
+    visitor.visit_expression(&if_statement.condition);
+    visitor.visit_if_statement_body(&if_statement.body);
+}
+
+pub fn walk_if_statement_body<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    if_statement_body: &IfStatementBody,
+) {
+    match if_statement_body {
+        IfStatementBody::Statement {
+            statement,
+            elseifs,
+            r#else,
+        } => {
+            visitor.visit_statement(statement);
+            for if_statement_else_if in elseifs {
+                visitor.visit_if_statement_else_if(if_statement_else_if);
+            }
+            if let Some(r#else) = r#else {
+                visitor.visit_if_statement_else(r#else);
+            }
+        }
+        IfStatementBody::Block {
+            statements,
+            elseifs,
+            r#else,
+            ..
+        } => {
+            for statement in statements {
+                visitor.visit_statement(statement);
+            }
+            for if_statement_else_if_block in elseifs {
+                visitor.visit_if_statement_else_if_block(if_statement_else_if_block);
+            }
+            if let Some(r#else) = r#else {
+                visitor.visit_if_statement_else_block(r#else);
+            }
+        }
+    }
+}
+
+pub fn walk_if_statement_else_if<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    if_statement_else_if: &IfStatementElseIf,
+) {
+    visitor.visit_expression(&if_statement_else_if.condition);
+    visitor.visit_statement(&if_statement_else_if.statement);
+}
+
+pub fn walk_if_statement_else<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    if_statement_else: &IfStatementElse,
+) {
+    visitor.visit_statement(&if_statement_else.statement);
+}
+
+pub fn walk_if_statement_else_if_block<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    if_statement_else_if_block: &IfStatementElseIfBlock,
+) {
+    visitor.visit_expression(&if_statement_else_if_block.condition);
+    for statement in &if_statement_else_if_block.statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_if_statement_else_block<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    if_statement_else_block: &IfStatementElseBlock,
+) {
+    for statement in &if_statement_else_block.statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_type<V: Visitor + ?Sized>(visitor: &mut V, ty: &Type) {
+    match ty {
+        Type::Nullable(_, inner) => visitor.visit_type(inner),
+        Type::Union(inner) | Type::Intersection(inner) => {
+            for ty in inner {
+                visitor.visit_type(ty);
+            }
+        }
+        Type::Named(..)
+        | Type::Void(_)
+        | Type::Null(_)
+        | Type::True(_)
+        | Type::False(_)
+        | Type::Never(_)
+        | Type::Float(_)
+        | Type::Boolean(_)
+        | Type::Integer(_)
+        | Type::String(_)
+        | Type::Array(_)
+        | Type::Object(_)
+        | Type::Mixed(_)
+        | Type::Callable(_)
+        | Type::Iterable(_)
+        | Type::StaticReference(_)
+        | Type::SelfReference(_)
+        | Type::ParentReference(_) => {}
+    }
+}
+
+pub fn walk_declare_entry<V: Visitor + ?Sized>(visitor: &mut V, declare_entry: &DeclareEntry) {
+    visitor.visit_simple_identifier(&declare_entry.key);
+    visitor.visit_literal(&declare_entry.value);
+}
+
+pub fn walk_declare_entry_group<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    declare_entry_group: &DeclareEntryGroup,
+) {
+    for declare_entry in &declare_entry_group.entries {
+        visitor.visit_declare_entry(declare_entry);
+    }
+}
+
+pub fn walk_declare_body<V: Visitor + ?Sized>(visitor: &mut V, declare_body: &DeclareBody) {
+    match declare_body {
+        DeclareBody::Braced { statements, .. } | DeclareBody::Block { statements, .. } => {
+            for statement in statements {
+                visitor.visit_statement(statement);
+            }
+        }
+        DeclareBody::Expression { expression, .. } => visitor.visit_expression(expression),
+        DeclareBody::Noop { .. } => {}
+    }
+}
+
+pub fn walk_declare_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    declare_statement: &DeclareStatement,
+) {
+    visitor.visit_declare_entry_group(&declare_statement.entries);
+    visitor.visit_declare_body(&declare_statement.body);
+}
+
+pub fn walk_unit_enum_case<V: Visitor + ?Sized>(visitor: &mut V, unit_enum_case: &UnitEnumCase) {
+    visitor.visit_comment_group(&unit_enum_case.comments);
+    for attribute_group in &unit_enum_case.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_simple_identifier(&unit_enum_case.name);
+}
+
+pub fn walk_unit_enum_member<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    unit_enum_member: &UnitEnumMember,
+) {
+    match unit_enum_member {
+        UnitEnumMember::Case(unit_enum_case) => visitor.visit_unit_enum_case(unit_enum_case),
+        UnitEnumMember::Method(concrete_method) => visitor.visit_concrete_method(concrete_method),
+        UnitEnumMember::Constant(classish_constant) => {
+            visitor.visit_classish_constant(classish_constant)
+        }
+    }
+}
+
+pub fn walk_unit_enum_body<V: Visitor + ?Sized>(visitor: &mut V, unit_enum_body: &UnitEnumBody) {
+    for unit_enum_member in &unit_enum_body.members {
+        visitor.visit_unit_enum_member(unit_enum_member);
+    }
+}
+
+pub fn walk_unit_enum_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    unit_enum_statement: &UnitEnumStatement,
+) {
+    visitor.visit_comment_group(&unit_enum_statement.comments);
+    for attribute_group in &unit_enum_statement.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_simple_identifier(&unit_enum_statement.name);
+    for simple_identifier in &unit_enum_statement.implements {
+        visitor.visit_simple_identifier(simple_identifier);
+    }
+    visitor.visit_unit_enum_body(&unit_enum_statement.body);
+}
+
+pub fn walk_backed_enum_case<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    backed_enum_case: &BackedEnumCase,
+) {
+    visitor.visit_comment_group(&backed_enum_case.comments);
+    for attribute_group in &backed_enum_case.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_simple_identifier(&backed_enum_case.name);
+    visitor.visit_expression(&backed_enum_case.value);
+}
+
+pub fn walk_backed_enum_member<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    backed_enum_member: &BackedEnumMember,
+) {
+    match backed_enum_member {
+        BackedEnumMember::Case(backed_enum_case) => {
+            visitor.visit_backed_enum_case(backed_enum_case)
+        }
+        BackedEnumMember::Method(concrete_method) => visitor.visit_concrete_method(concrete_method),
+        BackedEnumMember::Constant(classish_constant) => {
+            visitor.visit_classish_constant(classish_constant)
+        }
+    }
+}
+
+pub fn walk_backed_enum_body<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    backed_enum_body: &BackedEnumBody,
+) {
+    for backed_enum_member in &backed_enum_body.members {
+        visitor.visit_backed_enum_member(backed_enum_member);
+    }
+}
+
+pub fn walk_backed_enum_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    backed_enum_statement: &BackedEnumStatement,
+) {
+    visitor.visit_comment_group(&backed_enum_statement.comments);
+    for attribute_group in &backed_enum_statement.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_simple_identifier(&backed_enum_statement.name);
+    visitor.visit_backed_enum_type(&backed_enum_statement.backed_type);
+    for simple_identifier in &backed_enum_statement.implements {
+        visitor.visit_simple_identifier(simple_identifier);
+    }
+    visitor.visit_backed_enum_body(&backed_enum_statement.body);
+}
+
+pub fn walk_return_type<V: Visitor + ?Sized>(visitor: &mut V, return_type: &ReturnType) {
+    visitor.visit_type(&return_type.data_type);
+}
+
+pub fn walk_function_parameter<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    function_parameter: &FunctionParameter,
+) {
+    visitor.visit_comment_group(&function_parameter.comments);
+    for attribute_group in &function_parameter.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    if let Some(data_type) = &function_parameter.data_type {
+        visitor.visit_type(data_type);
+    }
+    visitor.visit_simple_variable(&function_parameter.name);
+    if let Some(default) = &function_parameter.default {
+        visitor.visit_expression(default);
+    }
+}
+
+pub fn walk_function_parameter_list<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    function_parameter_list: &FunctionParameterList,
+) {
+    visitor.visit_comment_group(&function_parameter_list.comments);
+    for function_parameter in &function_parameter_list.parameters.inner {
+        visitor.visit_function_parameter(function_parameter);
+    }
+}
+
+pub fn walk_function_body<V: Visitor + ?Sized>(visitor: &mut V, function_body: &FunctionBody) {
+    visitor.visit_comment_group(&function_body.comments);
+    for statement in &function_body.statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_function_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    function_statement: &FunctionStatement,
+) {
+    visitor.visit_comment_group(&function_statement.comments);
+    for attribute_group in &function_statement.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_simple_identifier(&function_statement.name);
+    visitor.visit_function_parameter_list(&function_statement.parameters);
+    if let Some(return_type) = &function_statement.return_type {
+        visitor.visit_return_type(return_type);
+    }
+    visitor.visit_function_body(&function_statement.body);
+}
+
+pub fn walk_closure_use_variable<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    closure_use_variable: &ClosureUseVariable,
+) {
+    visitor.visit_comment_group(&closure_use_variable.comments);
+    visitor.visit_simple_variable(&closure_use_variable.variable);
+}
+
+pub fn walk_closure_use<V: Visitor + ?Sized>(visitor: &mut V, closure_use: &ClosureUse) {
+    visitor.visit_comment_group(&closure_use.comments);
+    for closure_use_variable in &closure_use.variables.inner {
+        visitor.visit_closure_use_variable(closure_use_variable);
+    }
+}
+
+pub fn walk_exit_expression<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    exit_expression: &ExitExpression,
+) {
+    if let Some(arguments) = &exit_expression.arguments {
+        visitor.visit_argument_list(arguments);
+    }
+}
+
+pub fn walk_closure<V: Visitor + ?Sized>(visitor: &mut V, closure: &Closure) {
+    visitor.visit_comment_group(&closure.comments);
+    for attribute_group in &closure.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_function_parameter_list(&closure.parameters);
+    if let Some(uses) = &closure.uses {
+        visitor.visit_closure_use(uses);
+    }
+    if let Some(return_type) = &closure.return_type {
+        visitor.visit_return_type(return_type);
+    }
+    visitor.visit_function_body(&closure.body);
+}
+
+pub fn walk_arrow_function<V: Visitor + ?Sized>(visitor: &mut V, arrow_function: &ArrowFunction) {
+    visitor.visit_comment_group(&arrow_function.comments);
+    for attribute_group in &arrow_function.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_function_parameter_list(&arrow_function.parameters);
+    if let Some(return_type) = &arrow_function.return_type {
+        visitor.visit_return_type(return_type);
+    }
+    visitor.visit_expression(&arrow_function.body);
+}
+
+pub fn walk_constructor_parameter<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    constructor_parameter: &ConstructorParameter,
+) {
+    visitor.visit_comment_group(&constructor_parameter.comments);
+    for attribute_group in &constructor_parameter.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_promoted_property_modifier_group(&constructor_parameter.modifiers);
+    if let Some(data_type) = &constructor_parameter.data_type {
+        visitor.visit_type(data_type);
+    }
+    visitor.visit_simple_variable(&constructor_parameter.name);
+    if let Some(default) = &constructor_parameter.default {
+        visitor.visit_expression(default);
+    }
+}
+
+pub fn walk_constructor_parameter_list<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    constructor_parameter_list: &ConstructorParameterList,
+) {
+    visitor.visit_comment_group(&constructor_parameter_list.comments);
+    for constructor_parameter in &constructor_parameter_list.parameters.inner {
+        visitor.visit_constructor_parameter(constructor_parameter);
+    }
+}
+
+pub fn walk_abstract_constructor<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    abstract_constructor: &AbstractConstructor,
+) {
+    visitor.visit_comment_group(&abstract_constructor.comments);
+    for attribute_group in &abstract_constructor.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_method_modifier_group(&abstract_constructor.modifiers);
+    visitor.visit_simple_identifier(&abstract_constructor.name);
+    visitor.visit_function_parameter_list(&abstract_constructor.parameters);
+}
+
+pub fn walk_concrete_constructor<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    concrete_constructor: &ConcreteConstructor,
+) {
+    visitor.visit_comment_group(&concrete_constructor.comments);
+    for attribute_group in &concrete_constructor.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_method_modifier_group(&concrete_constructor.modifiers);
+    visitor.visit_simple_identifier(&concrete_constructor.name);
+    visitor.visit_constructor_parameter_list(&concrete_constructor.parameters);
+    visitor.visit_method_body(&concrete_constructor.body);
+}
+
+pub fn walk_abstract_method<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    abstract_method: &AbstractMethod,
+) {
+    visitor.visit_comment_group(&abstract_method.comments);
+    for attribute_group in &abstract_method.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_method_modifier_group(&abstract_method.modifiers);
+    visitor.visit_simple_identifier(&abstract_method.name);
+    visitor.visit_function_parameter_list(&abstract_method.parameters);
+    if let Some(return_type) = &abstract_method.return_type {
+        visitor.visit_return_type(return_type);
+    }
+}
+
+pub fn walk_concrete_method<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    concrete_method: &ConcreteMethod,
+) {
+    visitor.visit_comment_group(&concrete_method.comments);
+    for attribute_group in &concrete_method.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_method_modifier_group(&concrete_method.modifiers);
+    visitor.visit_simple_identifier(&concrete_method.name);
+    visitor.visit_function_parameter_list(&concrete_method.parameters);
+    if let Some(return_type) = &concrete_method.return_type {
+        visitor.visit_return_type(return_type);
+    }
+    visitor.visit_method_body(&concrete_method.body);
+}
+
+pub fn walk_method_body<V: Visitor + ?Sized>(visitor: &mut V, method_body: &MethodBody) {
+    visitor.visit_comment_group(&method_body.comments);
+    for statement in &method_body.statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_label_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    label_statement: &LabelStatement,
+) {
+    visitor.visit_comment_group(&label_statement.comments);
+    visitor.visit_simple_identifier(&label_statement.label);
+}
+
+pub fn walk_goto_statement<V: Visitor + ?Sized>(visitor: &mut V, goto_statement: &GotoStatement) {
+    visitor.visit_comment_group(&goto_statement.comments);
+    visitor.visit_simple_identifier(&goto_statement.label);
+}
+
+pub fn walk_identifier<V: Visitor + ?Sized>(visitor: &mut V, identifier: &Identifier) {
+    match identifier {
+        Identifier::SimpleIdentifier(simple_identifier) => {
+            visitor.visit_simple_identifier(simple_identifier)
+        }
+        Identifier::DynamicIdentifier(dynamic_identifier) => {
+            visitor.visit_dynamic_identifier(dynamic_identifier)
+        }
+    }
+}
+
+pub fn walk_dynamic_identifier<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    dynamic_identifier: &DynamicIdentifier,
+) {
+    visitor.visit_expression(&dynamic_identifier.expr);
+}
+
+pub fn walk_interface_member<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    interface_member: &InterfaceMember,
+) {
+    match interface_member {
+        InterfaceMember::Constant(classish_constant) => {
+            visitor.visit_classish_constant(classish_constant)
+        }
+        InterfaceMember::Constructor(abstract_constructor) => {
+            visitor.visit_abstract_constructor(abstract_constructor)
+        }
+        InterfaceMember::Method(abstract_method) => visitor.visit_abstract_method(abstract_method),
+        InterfaceMember::Property(property) => visitor.visit_property(property),
+    }
+}
+
+pub fn walk_interface_extends<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    interface_extends: &InterfaceExtends,
+) {
+    for simple_identifier in &interface_extends.parents.inner {
+        visitor.visit_simple_identifier(simple_identifier);
+    }
+}
+
+pub fn walk_interface_body<V: Visitor + ?Sized>(visitor: &mut V, interface_body: &InterfaceBody) {
+    for interface_member in &interface_body.members {
+        visitor.visit_interface_member(interface_member);
+    }
+}
+
+pub fn walk_interface_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    interface_statement: &InterfaceStatement,
+) {
+    visitor.visit_comment_group(&interface_statement.comments);
+    for attribute_group in &interface_statement.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_simple_identifier(&interface_statement.name);
+    if let Some(extends) = &interface_statement.extends {
+        visitor.visit_interface_extends(extends);
+    }
+    visitor.visit_interface_body(&interface_statement.body);
+}
+
+pub fn walk_literal<V: Visitor + ?Sized>(visitor: &mut V, literal: &Literal) {
+    match literal {
+        Literal::String(literal_string) => visitor.visit_literal_string(literal_string),
+        Literal::Integer(literal_integer) => visitor.visit_literal_integer(literal_integer),
+        Literal::Float(literal_float) => visitor.visit_literal_float(literal_float),
+    }
+}
+
+pub fn walk_foreach_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    foreach_statement: &ForeachStatement,
+) {
+    visitor.visit_foreach_statement_iterator(&foreach_statement.iterator);
+    visitor.visit_foreach_statement_body(&foreach_statement.body);
+}
+
+pub fn walk_foreach_statement_iterator<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    foreach_statement_iterator: &ForeachStatementIterator,
+) {
+    match foreach_statement_iterator {
+        ForeachStatementIterator::Value {
+            expression, value, ..
+        } => {
+            visitor.visit_expression(expression);
+            visitor.visit_expression(value);
+        }
+        ForeachStatementIterator::KeyAndValue {
+            expression,
+            key,
+            value,
+            ..
+        } => {
+            visitor.visit_expression(expression);
+            visitor.visit_expression(key);
+            visitor.visit_expression(value);
+        }
+    }
+}
+
+pub fn walk_foreach_statement_body<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    foreach_statement_body: &ForeachStatementBody,
+) {
+    match foreach_statement_body {
+        ForeachStatementBody::Statement(statement) => visitor.visit_statement(statement),
+        ForeachStatementBody::Block { statements, .. } => {
+            for statement in statements {
+                visitor.visit_statement(statement);
+            }
+        }
+    }
+}
+
+pub fn walk_for_statement<V: Visitor + ?Sized>(visitor: &mut V, for_statement: &ForStatement) {
+    visitor.visit_for_statement_iterator(&for_statement.iterator);
+    visitor.visit_for_statement_body(&for_statement.body);
+}
+
+pub fn walk_for_statement_iterator<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    for_statement_iterator: &ForStatementIterator,
+) {
+    for expression in &for_statement_iterator.initializations.inner {
+        visitor.visit_expression(expression);
+    }
+    for expression in &for_statement_iterator.conditions.inner {
+        visitor.visit_expression(expression);
+    }
+    for expression in &for_statement_iterator.r#loop.inner {
+        visitor.visit_expression(expression);
+    }
+}
+
+pub fn walk_for_statement_body<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    for_statement_body: &ForStatementBody,
+) {
+    match for_statement_body {
+        ForStatementBody::Statement(statement) => visitor.visit_statement(statement),
+        ForStatementBody::Block { statements, .. } => {
+            for statement in statements {
+                visitor.visit_statement(statement);
+            }
+        }
+    }
+}
+
+pub fn walk_do_while_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    do_while_statement: &DoWhileStatement,
+) {
+    visitor.visit_statement(&do_while_statement.body);
+    visitor.visit_expression(&do_while_statement.condition);
+}
+
+pub fn walk_while_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    while_statement: &WhileStatement,
+) {
+    visitor.visit_expression(&while_statement.condition);
+    visitor.visit_while_statement_body(&while_statement.body);
+}
+
+pub fn walk_while_statement_body<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    while_statement_body: &WhileStatementBody,
+) {
+    match while_statement_body {
+        WhileStatementBody::Statement(statement) => visitor.visit_statement(statement),
+        WhileStatementBody::Block { statements, .. } => {
+            for statement in statements {
+                visitor.visit_statement(statement);
+            }
+        }
+    }
+}
+
+pub fn walk_level<V: Visitor + ?Sized>(visitor: &mut V, level: &Level) {
+    match level {
+        Level::Literal(literal_integer) => visitor.visit_literal_integer(literal_integer),
+        Level::Parenthesized { level, .. } => visitor.visit_level(level),
+    }
+}
+
+pub fn walk_break_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    break_statement: &BreakStatement,
+) {
+    if let Some(level) = &break_statement.level {
+        visitor.visit_level(level);
+    }
+}
+
+pub fn walk_continue_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    continue_statement: &ContinueStatement,
+) {
+    if let Some(level) = &continue_statement.level {
+        visitor.visit_level(level);
+    }
+}
+
+pub fn walk_static_var<V: Visitor + ?Sized>(visitor: &mut V, static_var: &StaticVar) {
+    visitor.visit_variable(&static_var.var);
+    if let Some(default) = &static_var.default {
+        visitor.visit_expression(default);
+    }
+}
+
+pub fn walk_static_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    static_statement: &StaticStatement,
+) {
+    for static_var in &static_statement.vars {
+        visitor.visit_static_var(static_var);
+    }
+}
+
+pub fn walk_switch_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    switch_statement: &SwitchStatement,
+) {
+    visitor.visit_expression(&switch_statement.condition);
+    for case in &switch_statement.cases {
+        visitor.visit_case(case);
+    }
+}
+
+pub fn walk_echo_statement<V: Visitor + ?Sized>(visitor: &mut V, echo_statement: &EchoStatement) {
+    for expression in &echo_statement.values {
+        visitor.visit_expression(expression);
+    }
+}
+
+pub fn walk_return_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    return_statement: &ReturnStatement,
+) {
+    if let Some(value) = &return_statement.value {
+        visitor.visit_expression(value);
+    }
+}
+
+pub fn walk_use_statement<V: Visitor + ?Sized>(visitor: &mut V, use_statement: &UseStatement) {
+    for r#use in &use_statement.uses {
+        visitor.visit_use(r#use);
+    }
+}
+
+pub fn walk_group_use_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    group_use_statement: &GroupUseStatement,
+) {
+    visitor.visit_simple_identifier(&group_use_statement.prefix);
+    for r#use in &group_use_statement.uses {
+        visitor.visit_use(r#use);
+    }
+}
+
+pub fn walk_expression_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    expression_statement: &ExpressionStatement,
+) {
+    visitor.visit_expression(&expression_statement.expression);
+}
+
+pub fn walk_global_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    global_statement: &GlobalStatement,
+) {
+    for variable in &global_statement.variables {
+        visitor.visit_variable(variable);
+    }
+}
+
+pub fn walk_block_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    block_statement: &BlockStatement,
+) {
+    for statement in &block_statement.statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_case<V: Visitor + ?Sized>(visitor: &mut V, case: &Case) {
+    if let Some(condition) = &case.condition {
+        visitor.visit_expression(condition);
+    }
+    for statement in &case.body {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_use<V: Visitor + ?Sized>(visitor: &mut V, r#use: &Use) {
+    visitor.visit_simple_identifier(&r#use.name);
+    if let Some(alias) = &r#use.alias {
+        visitor.visit_simple_identifier(alias);
+    }
+}
+
+pub fn walk_default_match_arm<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    default_match_arm: &DefaultMatchArm,
+) {
+    visitor.visit_expression(&default_match_arm.body);
+}
+
+pub fn walk_match_arm<V: Visitor + ?Sized>(visitor: &mut V, match_arm: &MatchArm) {
+    for expression in &match_arm.conditions {
+        visitor.visit_expression(expression);
+    }
+    visitor.visit_expression(&match_arm.body);
+}
+
+pub fn walk_string_part<V: Visitor + ?Sized>(visitor: &mut V, string_part: &StringPart) {
+    match string_part {
+        StringPart::Literal(literal_string_part) => {
+            visitor.visit_literal_string_part(literal_string_part)
+        }
+        StringPart::Expression(expression_string_part) => {
+            visitor.visit_expression_string_part(expression_string_part)
+        }
+    }
+}
+
+pub fn walk_expression_string_part<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    expression_string_part: &ExpressionStringPart,
+) {
+    visitor.visit_expression(&expression_string_part.expression);
+}
+
+pub fn walk_array_item<V: Visitor + ?Sized>(visitor: &mut V, array_item: &ArrayItem) {
+    match array_item {
+        ArrayItem::Value { value }
+        | ArrayItem::ReferencedValue { value, .. }
+        | ArrayItem::SpreadValue { value, .. } => visitor.visit_expression(value),
+        ArrayItem::KeyValue { key, value, .. }
+        | ArrayItem::ReferencedKeyValue { key, value, .. } => {
+            visitor.visit_expression(key);
+            visitor.visit_expression(value);
+        }
+        ArrayItem::Skipped => {}
+    }
+}
+
+pub fn walk_list_entry<V: Visitor + ?Sized>(visitor: &mut V, list_entry: &ListEntry) {
+    match list_entry {
+        ListEntry::Value { value } => visitor.visit_expression(value),
+        ListEntry::KeyValue { key, value, .. } => {
+            visitor.visit_expression(key);
+            visitor.visit_expression(value);
+        }
+        ListEntry::Skipped => {}
+    }
+}
+
+pub fn walk_promoted_property_modifier_group<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    promoted_property_modifier_group: &PromotedPropertyModifierGroup,
+) {
+    for promoted_property_modifier in &promoted_property_modifier_group.modifiers {
+        visitor.visit_promoted_property_modifier(promoted_property_modifier);
+    }
+}
+
+pub fn walk_property_modifier_group<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    property_modifier_group: &PropertyModifierGroup,
+) {
+    for property_modifier in &property_modifier_group.modifiers {
+        visitor.visit_property_modifier(property_modifier);
+    }
+}
+
+pub fn walk_method_modifier_group<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    method_modifier_group: &MethodModifierGroup,
+) {
+    for method_modifier in &method_modifier_group.modifiers {
+        visitor.visit_method_modifier(method_modifier);
+    }
+}
+
+pub fn walk_class_modifier_group<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    class_modifier_group: &ClassModifierGroup,
+) {
+    for class_modifier in &class_modifier_group.modifiers {
+        visitor.visit_class_modifier(class_modifier);
+    }
+}
+
+pub fn walk_constant_modifier_group<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    constant_modifier_group: &ConstantModifierGroup,
+) {
+    for constant_modifier in &constant_modifier_group.modifiers {
+        visitor.visit_constant_modifier(constant_modifier);
+    }
+}
+
+pub fn walk_unbraced_namespace<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    unbraced_namespace: &UnbracedNamespace,
+) {
+    visitor.visit_simple_identifier(&unbraced_namespace.name);
+    for statement in &unbraced_namespace.statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_braced_namespace<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    braced_namespace: &BracedNamespace,
+) {
+    if let Some(name) = &braced_namespace.name {
+        visitor.visit_simple_identifier(name);
+    }
+    visitor.visit_braced_namespace_body(&braced_namespace.body);
+}
+
+pub fn walk_braced_namespace_body<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    braced_namespace_body: &BracedNamespaceBody,
+) {
+    for statement in &braced_namespace_body.statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_namespace_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    namespace_statement: &NamespaceStatement,
+) {
+    match namespace_statement {
+        NamespaceStatement::Unbraced(unbraced_namespace) => {
+            visitor.visit_unbraced_namespace(unbraced_namespace)
+        }
+        NamespaceStatement::Braced(braced_namespace) => {
+            visitor.visit_braced_namespace(braced_namespace)
+        }
+    }
+}
+
+pub fn walk_arithmetic_operation<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    arithmetic_operation: &ArithmeticOperation,
+) {
+    match arithmetic_operation {
+        ArithmeticOperation::Addition { left, right, .. }
+        | ArithmeticOperation::Subtraction { left, right, .. }
+        | ArithmeticOperation::Multiplication { left, right, .. }
+        | ArithmeticOperation::Division { left, right, .. }
+        | ArithmeticOperation::Modulo { left, right, .. }
+        | ArithmeticOperation::Exponentiation { left, right, .. } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+        ArithmeticOperation::Negative { right, .. }
+        | ArithmeticOperation::Positive { right, .. }
+        | ArithmeticOperation::PreIncrement { right, .. }
+        | ArithmeticOperation::PreDecrement { right, .. } => visitor.visit_expression(right),
+        ArithmeticOperation::PostIncrement { left, .. }
+        | ArithmeticOperation::PostDecrement { left, .. } => visitor.visit_expression(left),
+    }
+}
+
+pub fn walk_assignment_operation<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    assignment_operation: &AssignmentOperation,
+) {
+    match assignment_operation {
+        AssignmentOperation::Assign { left, right, .. }
+        | AssignmentOperation::AssignByReference { left, right, .. }
+        | AssignmentOperation::Addition { left, right, .. }
+        | AssignmentOperation::Subtraction { left, right, .. }
+        | AssignmentOperation::Multiplication { left, right, .. }
+        | AssignmentOperation::Division { left, right, .. }
+        | AssignmentOperation::Modulo { left, right, .. }
+        | AssignmentOperation::Exponentiation { left, right, .. }
+        | AssignmentOperation::Concat { left, right, .. }
+        | AssignmentOperation::BitwiseAnd { left, right, .. }
+        | AssignmentOperation::BitwiseOr { left, right, .. }
+        | AssignmentOperation::BitwiseXor { left, right, .. }
+        | AssignmentOperation::LeftShift { left, right, .. }
+        | AssignmentOperation::RightShift { left, right, .. }
+        | AssignmentOperation::Coalesce { left, right, .. } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+    }
+}
+
+pub fn walk_bitwise_operation<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    bitwise_operation: &BitwiseOperation,
+) {
+    match bitwise_operation {
+        BitwiseOperation::And { left, right, .. }
+        | BitwiseOperation::Or { left, right, .. }
+        | BitwiseOperation::Xor { left, right, .. }
+        | BitwiseOperation::LeftShift { left, right, .. }
+        | BitwiseOperation::RightShift { left, right, .. } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+        BitwiseOperation::Not { right, .. } => visitor.visit_expression(right),
+    }
+}
+
+pub fn walk_comparison_operation<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    comparison_operation: &ComparisonOperation,
+) {
+    match comparison_operation {
+        ComparisonOperation::Equal { left, right, .. }
+        | ComparisonOperation::Identical { left, right, .. }
+        | ComparisonOperation::NotEqual { left, right, .. }
+        | ComparisonOperation::AngledNotEqual { left, right, .. }
+        | ComparisonOperation::NotIdentical { left, right, .. }
+        | ComparisonOperation::LessThan { left, right, .. }
+        | ComparisonOperation::GreaterThan { left, right, .. }
+        | ComparisonOperation::LessThanOrEqual { left, right, .. }
+        | ComparisonOperation::GreaterThanOrEqual { left, right, .. }
+        | ComparisonOperation::Spaceship { left, right, .. } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+    }
+}
+
+pub fn walk_logical_operation<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    logical_operation: &LogicalOperation,
+) {
+    match logical_operation {
+        LogicalOperation::And { left, right, .. }
+        | LogicalOperation::Or { left, right, .. }
+        | LogicalOperation::LogicalAnd { left, right, .. }
+        | LogicalOperation::LogicalOr { left, right, .. }
+        | LogicalOperation::LogicalXor { left, right, .. } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+        LogicalOperation::Not { right, .. } => visitor.visit_expression(right),
+    }
+}
+
+pub fn walk_property<V: Visitor + ?Sized>(visitor: &mut V, property: &Property) {
+    visitor.visit_comment_group(&property.comments);
+    for attribute_group in &property.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_property_modifier_group(&property.modifiers);
+    if let Some(ty) = &property.r#type {
+        visitor.visit_type(ty);
+    }
+    for property_entry in &property.entries {
+        visitor.visit_property_entry(property_entry);
+    }
+    if let Some(hooks) = &property.hooks {
+        for property_hook in &hooks.hooks {
+            visitor.visit_property_hook(property_hook);
+        }
+    }
+}
+
+pub fn walk_variable_property<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    variable_property: &VariableProperty,
+) {
+    visitor.visit_comment_group(&variable_property.comments);
+    for attribute_group in &variable_property.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    if let Some(ty) = &variable_property.r#type {
+        visitor.visit_type(ty);
+    }
+    for property_entry in &variable_property.entries {
+        visitor.visit_property_entry(property_entry);
+    }
+}
+
+pub fn walk_property_entry<V: Visitor + ?Sized>(visitor: &mut V, property_entry: &PropertyEntry) {
+    match property_entry {
+        PropertyEntry::Uninitialized { variable } => visitor.visit_simple_variable(variable),
+        PropertyEntry::Initialized {
+            variable, value, ..
+        } => {
+            visitor.visit_simple_variable(variable);
+            visitor.visit_expression(value);
+        }
+    }
+}
+
+pub fn walk_property_hook<V: Visitor + ?Sized>(visitor: &mut V, property_hook: &PropertyHook) {
+    visitor.visit_comment_group(&property_hook.comments);
+    for attribute_group in &property_hook.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_simple_identifier(&property_hook.name);
+    if let Some(parameters) = &property_hook.parameters {
+        visitor.visit_function_parameter_list(parameters);
+    }
+    match &property_hook.body {
+        PropertyHookBody::Abstract { .. } => {}
+        PropertyHookBody::Expression { expression, .. } => visitor.visit_expression(expression),
+        PropertyHookBody::Block(body) => visitor.visit_function_body(body),
+    }
+}
+
+pub fn walk_trait_member<V: Visitor + ?Sized>(visitor: &mut V, trait_member: &TraitMember) {
+    match trait_member {
+        TraitMember::Constant(classish_constant) => {
+            visitor.visit_classish_constant(classish_constant)
+        }
+        TraitMember::TraitUsage(trait_usage) => visitor.visit_trait_usage(trait_usage),
+        TraitMember::Property(property) => visitor.visit_property(property),
+        TraitMember::VariableProperty(variable_property) => {
+            visitor.visit_variable_property(variable_property)
+        }
+        TraitMember::AbstractMethod(abstract_method) => {
+            visitor.visit_abstract_method(abstract_method)
+        }
+        TraitMember::AbstractConstructor(abstract_constructor) => {
+            visitor.visit_abstract_constructor(abstract_constructor)
+        }
+        TraitMember::ConcreteMethod(concrete_method) => {
+            visitor.visit_concrete_method(concrete_method)
+        }
+        TraitMember::ConcreteConstructor(concrete_constructor) => {
+            visitor.visit_concrete_constructor(concrete_constructor)
+        }
+    }
+}
+
+pub fn walk_trait_body<V: Visitor + ?Sized>(visitor: &mut V, trait_body: &TraitBody) {
+    for trait_member in &trait_body.members {
+        visitor.visit_trait_member(trait_member);
+    }
+}
+
+pub fn walk_trait_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    trait_statement: &TraitStatement,
+) {
+    visitor.visit_comment_group(&trait_statement.comments);
+    for attribute_group in &trait_statement.attributes {
+        visitor.visit_attribute_group(attribute_group);
+    }
+    visitor.visit_simple_identifier(&trait_statement.name);
+    visitor.visit_trait_body(&trait_statement.body);
+}
+
+pub fn walk_trait_usage<V: Visitor + ?Sized>(visitor: &mut V, trait_usage: &TraitUsage) {
+    for simple_identifier in &trait_usage.traits {
+        visitor.visit_simple_identifier(simple_identifier);
+    }
+    for trait_usage_adaptation in &trait_usage.adaptations {
+        visitor.visit_trait_usage_adaptation(trait_usage_adaptation);
+    }
+}
+
+pub fn walk_trait_usage_adaptation<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    trait_usage_adaptation: &TraitUsageAdaptation,
+) {
+    match trait_usage_adaptation {
+        TraitUsageAdaptation::Alias {
+            r#trait,
+            method,
+            alias,
+            visibility,
+        } => {
+            if let Some(r#trait) = r#trait {
+                visitor.visit_simple_identifier(r#trait);
+            }
+            visitor.visit_simple_identifier(method);
+            visitor.visit_simple_identifier(alias);
+            if let Some(visibility) = visibility {
+                visitor.visit_visibility_modifier(visibility);
+            }
+        }
+        TraitUsageAdaptation::Visibility {
+            r#trait,
+            method,
+            visibility,
+        } => {
+            if let Some(r#trait) = r#trait {
+                visitor.visit_simple_identifier(r#trait);
+            }
+            visitor.visit_simple_identifier(method);
+            visitor.visit_visibility_modifier(visibility);
+        }
+        TraitUsageAdaptation::Precedence {
+            r#trait,
+            method,
+            insteadof,
+        } => {
+            if let Some(r#trait) = r#trait {
+                visitor.visit_simple_identifier(r#trait);
+            }
+            visitor.visit_simple_identifier(method);
+            for simple_identifier in insteadof {
+                visitor.visit_simple_identifier(simple_identifier);
+            }
+        }
+    }
+}
+
+pub fn walk_catch_type<V: Visitor + ?Sized>(visitor: &mut V, catch_type: &CatchType) {
+    match catch_type {
+        CatchType::Identifier(simple_identifier) => {
+            visitor.visit_simple_identifier(simple_identifier)
+        }
+        CatchType::Union(inner) => {
+            for simple_identifier in inner {
+                visitor.visit_simple_identifier(simple_identifier);
+            }
+        }
+    }
+}
+
+pub fn walk_try_statement<V: Visitor + ?Sized>(visitor: &mut V, try_statement: &TryStatement) {
+    for statement in &try_statement.body {
+        visitor.visit_statement(statement);
+    }
+    for catch_block in &try_statement.catches {
+        visitor.visit_catch_block(catch_block);
+    }
+    if let Some(finally) = &try_statement.finally {
+        visitor.visit_finally_block(finally);
+    }
+}
+
+pub fn walk_catch_block<V: Visitor + ?Sized>(visitor: &mut V, catch_block: &CatchBlock) {
+    visitor.visit_catch_type(&catch_block.types);
+    if let Some(var) = &catch_block.var {
+        visitor.visit_simple_variable(var);
+    }
+    for statement in &catch_block.body {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_finally_block<V: Visitor + ?Sized>(visitor: &mut V, finally_block: &FinallyBlock) {
+    for statement in &finally_block.body {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_variable<V: Visitor + ?Sized>(visitor: &mut V, variable: &Variable) {
+    match variable {
+        Variable::SimpleVariable(simple_variable) => visitor.visit_simple_variable(simple_variable),
+        Variable::VariableVariable(variable_variable) => {
+            visitor.visit_variable_variable(variable_variable)
+        }
+        Variable::BracedVariableVariable(braced_variable_variable) => {
+            visitor.visit_braced_variable_variable(braced_variable_variable)
+        }
+    }
+}
+
+pub fn walk_variable_variable<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    variable_variable: &VariableVariable,
+) {
+    visitor.visit_variable(&variable_variable.variable);
+}
+
+pub fn walk_braced_variable_variable<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    braced_variable_variable: &BracedVariableVariable,
+) {
+    visitor.visit_expression(&braced_variable_variable.variable);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::walk_expression;
+    use super::Visitor;
+    use crate::parser::ast::identifiers::SimpleIdentifier;
+    use crate::parser::ast::variables::SimpleVariable;
+    use crate::parser::ast::Expression;
+
+    #[derive(Default)]
+    struct Names(Vec<String>);
+
+    impl Visitor for Names {
+        fn visit_simple_identifier(&mut self, identifier: &SimpleIdentifier) {
+            self.0.push(identifier.value.to_string());
+        }
+
+        fn visit_simple_variable(&mut self, variable: &SimpleVariable) {
+            self.0.push(variable.name.to_string());
+        }
+    }
+
+    #[test]
+    fn test_visit_in_source_order() {
+        let program = crate::parse(
+            r#"<?php
+#[A(b: $c)]
+final class D extends E implements F {
+    use G { h as protected i; }
+
+    public function __construct(#[J] private ?K $l = M) {}
+
+    public function n(O $p): Q {
+        return fn($r) => match ($s) { T => "{$u->v}", default => [...$w] };
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        let mut names = Names::default();
+        names.visit_program(&program);
+
+        assert_eq!(
+            names.0,
+            vec![
+                "A",
+                "b",
+                "$c",
+                "D",
+                "E",
+                "F",
+                "G",
+                "h",
+                "i",
+                "__construct",
+                "J",
+                "$l",
+                "M",
+                "n",
+                "$p",
+                "$r",
+                "$s",
+                "T",
+                "$u",
+                "v",
+                "$w"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_skip_children() {
+        #[derive(Default)]
+        struct Calls(usize, Names);
+
+        impl Visitor for Calls {
+            fn visit_expression(&mut self, expression: &Expression) {
+                match expression {
+                    Expression::FunctionCall { .. } => self.0 += 1,
+                    _ => walk_expression(self, expression),
+                }
+            }
+
+            fn visit_simple_variable(&mut self, variable: &SimpleVariable) {
+                self.1.visit_simple_variable(variable);
+            }
+        }
+
+        let program = crate::parse("<?php $a = b($c) + d(e($f)) . $g;").unwrap();
+
+        let mut calls = Calls::default();
+        calls.visit_program(&program);
+
+        assert_eq!(calls.0, 2);
+        assert_eq!(calls.1 .0, vec!["$a", "$g"]);
+    }
+}
@@ -0,0 +1,2663 @@
+//! A visitor that rewrites the AST in place.
+//!
+//! [`VisitorMut`] mirrors the read-only [`Visitor`](super::visitor::Visitor), with a
+//! `visit_*_mut` method and a `walk_*_mut` function for every node type, all taking the node
+//! by mutable reference. A node is replaced by assigning to it, and statements are removed or
+//! inserted by overriding [`VisitorMut::visit_block_mut`].
+//!
+//! ```
+//! use php_parser_rs::parser::ast::identifiers::Identifier;
+//! use php_parser_rs::parser::ast::visitor_mut::{walk_block_mut, walk_expression_mut, VisitorMut};
+//! use php_parser_rs::parser::ast::{Block, Expression, Statement};
+//!
+//! struct Codemod;
+//!
+//! impl VisitorMut for Codemod {
+//!     fn visit_expression_mut(&mut self, expression: &mut Expression) {
+//!         walk_expression_mut(self, expression);
+//!
+//!         if let Expression::MethodCall { method, .. } = expression {
+//!             if let Expression::Identifier(Identifier::SimpleIdentifier(name)) = method.as_mut() {
+//!                 if name.value.to_string() == "foo" {
+//!                     name.value = "bar".into();
+//!                 }
+//!             }
+//!         }
+//!     }
+//!
+//!     fn visit_block_mut(&mut self, block: &mut Block) {
+//!         block.retain(|statement| !matches!(statement, Statement::Echo(_)));
+//!
+//!         walk_block_mut(self, block);
+//!     }
+//! }
+//!
+//! let mut program = php_parser_rs::parse("<?php echo 1; $a->foo();").unwrap();
+//! Codemod.visit_program_mut(&mut program);
+//!
+//! assert_eq!(program.len(), 2);
+//! assert!(format!("{:?}", program[1]).contains(r#"value: "bar""#));
+//! ```
+
+use crate::parser::ast::arguments::Argument;
+use crate::parser::ast::arguments::ArgumentList;
+use crate::parser::ast::arguments::ArgumentPlaceholder;
+use crate::parser::ast::arguments::SingleArgument;
+use crate::parser::ast::attributes::Attribute;
+use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::classes::AnonymousClass;
+use crate::parser::ast::classes::AnonymousClassBody;
+use crate::parser::ast::classes::AnonymousClassMember;
+use crate::parser::ast::classes::ClassBody;
+use crate::parser::ast::classes::ClassExtends;
+use crate::parser::ast::classes::ClassImplements;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::comments::Comment;
+use crate::parser::ast::comments::CommentGroup;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::constant::ConstantEntry;
+use crate::parser::ast::constant::ConstantStatement;
+use crate::parser::ast::control_flow::IfStatement;
+use crate::parser::ast::control_flow::IfStatementBody;
+use crate::parser::ast::control_flow::IfStatementElse;
+use crate::parser::ast::control_flow::IfStatementElseBlock;
+use crate::parser::ast::control_flow::IfStatementElseIf;
+use crate::parser::ast::control_flow::IfStatementElseIfBlock;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::declares::DeclareBody;
+use crate::parser::ast::declares::DeclareEntry;
+use crate::parser::ast::declares::DeclareEntryGroup;
+use crate::parser::ast::declares::DeclareStatement;
+use crate::parser::ast::enums::BackedEnumBody;
+use crate::parser::ast::enums::BackedEnumCase;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::BackedEnumType;
+use crate::parser::ast::enums::UnitEnumBody;
+use crate::parser::ast::enums::UnitEnumCase;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::functions::AbstractConstructor;
+use crate::parser::ast::functions::AbstractMethod;
+use crate::parser::ast::functions::ArrowFunction;
+use crate::parser::ast::functions::Closure;
+use crate::parser::ast::functions::ClosureUse;
+use crate::parser::ast::functions::ClosureUseVariable;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::ConstructorParameter;
+use crate::parser::ast::functions::ConstructorParameterList;
+use crate::parser::ast::functions::FunctionBody;
+use crate::parser::ast::functions::FunctionParameter;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::functions::MethodBody;
+use crate::parser::ast::functions::ReturnType;
+use crate::parser::ast::goto::GotoStatement;
+use crate::parser::ast::goto::LabelStatement;
+use crate::parser::ast::identifiers::DynamicIdentifier;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceBody;
+use crate::parser::ast::interfaces::InterfaceExtends;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::interfaces::InterfaceStatement;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::literals::LiteralFloat;
+use crate::parser::ast::literals::LiteralInteger;
+use crate::parser::ast::literals::LiteralString;
+use crate::parser::ast::loops::BreakStatement;
+use crate::parser::ast::loops::ContinueStatement;
+use crate::parser::ast::loops::DoWhileStatement;
+use crate::parser::ast::loops::ForStatement;
+use crate::parser::ast::loops::ForStatementBody;
+use crate::parser::ast::loops::ForStatementIterator;
+use crate::parser::ast::loops::ForeachStatement;
+use crate::parser::ast::loops::ForeachStatementBody;
+use crate::parser::ast::loops::ForeachStatementIterator;
+use crate::parser::ast::loops::Level;
+use crate::parser::ast::loops::WhileStatement;
+use crate::parser::ast::loops::WhileStatementBody;
+use crate::parser::ast::modifiers::ClassModifier;
+use crate::parser::ast::modifiers::ClassModifierGroup;
+use crate::parser::ast::modifiers::ConstantModifier;
+use crate::parser::ast::modifiers::ConstantModifierGroup;
+use crate::parser::ast::modifiers::MethodModifier;
+use crate::parser::ast::modifiers::MethodModifierGroup;
+use crate::parser::ast::modifiers::PromotedPropertyModifier;
+use crate::parser::ast::modifiers::PromotedPropertyModifierGroup;
+use crate::parser::ast::modifiers::PropertyModifier;
+use crate::parser::ast::modifiers::PropertyModifierGroup;
+use crate::parser::ast::modifiers::VisibilityModifier;
+use crate::parser::ast::namespaces::BracedNamespace;
+use crate::parser::ast::namespaces::BracedNamespaceBody;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::namespaces::UnbracedNamespace;
+use crate::parser::ast::operators::ArithmeticOperation;
+use crate::parser::ast::operators::AssignmentOperation;
+use crate::parser::ast::operators::BitwiseOperation;
+use crate::parser::ast::operators::ComparisonOperation;
+use crate::parser::ast::operators::LogicalOperation;
+use crate::parser::ast::properties::Property;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::properties::PropertyHook;
+use crate::parser::ast::properties::PropertyHookBody;
+use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::traits::TraitBody;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitStatement;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::traits::TraitUsageAdaptation;
+use crate::parser::ast::try_block::CatchBlock;
+use crate::parser::ast::try_block::CatchType;
+use crate::parser::ast::try_block::FinallyBlock;
+use crate::parser::ast::try_block::TryStatement;
+use crate::parser::ast::variables::BracedVariableVariable;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::variables::VariableVariable;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::Block;
+use crate::parser::ast::BlockStatement;
+use crate::parser::ast::Case;
+use crate::parser::ast::DefaultMatchArm;
+use crate::parser::ast::EchoStatement;
+use crate::parser::ast::ErrorStatement;
+use crate::parser::ast::ExitExpression;
+use crate::parser::ast::Expression;
+use crate::parser::ast::ExpressionStatement;
+use crate::parser::ast::ExpressionStringPart;
+use crate::parser::ast::ForeignChunk;
+use crate::parser::ast::GlobalStatement;
+use crate::parser::ast::GroupUseStatement;
+use crate::parser::ast::HaltCompiler;
+use crate::parser::ast::ListEntry;
+use crate::parser::ast::LiteralStringPart;
+use crate::parser::ast::MagicConstant;
+use crate::parser::ast::MatchArm;
+use crate::parser::ast::Program;
+use crate::parser::ast::ReturnStatement;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StaticStatement;
+use crate::parser::ast::StaticVar;
+use crate::parser::ast::StringPart;
+use crate::parser::ast::SwitchStatement;
+use crate::parser::ast::Use;
+use crate::parser::ast::UseStatement;
+
+/// Visit and rewrite the nodes of the AST in place, see the [module documentation](self).
+pub trait VisitorMut {
+    fn visit_program_mut(&mut self, program: &mut Program) {
+        walk_program_mut(self, program);
+    }
+
+    /// Visit a list of statements, such as the body of a function or a block.
+    ///
+    /// Override this method to remove or insert statements.
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block);
+    }
+
+    fn visit_statement_mut(&mut self, statement: &mut Statement) {
+        walk_statement_mut(self, statement);
+    }
+
+    fn visit_expression_mut(&mut self, expression: &mut Expression) {
+        walk_expression_mut(self, expression);
+    }
+
+    fn visit_argument_mut(&mut self, argument: &mut Argument) {
+        walk_argument_mut(self, argument);
+    }
+
+    fn visit_argument_list_mut(&mut self, argument_list: &mut ArgumentList) {
+        walk_argument_list_mut(self, argument_list);
+    }
+
+    fn visit_single_argument_mut(&mut self, single_argument: &mut SingleArgument) {
+        walk_single_argument_mut(self, single_argument);
+    }
+
+    fn visit_argument_placeholder_mut(&mut self, argument_placeholder: &mut ArgumentPlaceholder) {
+        walk_argument_placeholder_mut(self, argument_placeholder);
+    }
+
+    fn visit_attribute_mut(&mut self, attribute: &mut Attribute) {
+        walk_attribute_mut(self, attribute);
+    }
+
+    fn visit_attribute_group_mut(&mut self, attribute_group: &mut AttributeGroup) {
+        walk_attribute_group_mut(self, attribute_group);
+    }
+
+    fn visit_class_body_mut(&mut self, class_body: &mut ClassBody) {
+        walk_class_body_mut(self, class_body);
+    }
+
+    fn visit_class_statement_mut(&mut self, class_statement: &mut ClassStatement) {
+        walk_class_statement_mut(self, class_statement);
+    }
+
+    fn visit_anonymous_class_body_mut(&mut self, anonymous_class_body: &mut AnonymousClassBody) {
+        walk_anonymous_class_body_mut(self, anonymous_class_body);
+    }
+
+    fn visit_anonymous_class_mut(&mut self, anonymous_class: &mut AnonymousClass) {
+        walk_anonymous_class_mut(self, anonymous_class);
+    }
+
+    fn visit_class_extends_mut(&mut self, class_extends: &mut ClassExtends) {
+        walk_class_extends_mut(self, class_extends);
+    }
+
+    fn visit_class_implements_mut(&mut self, class_implements: &mut ClassImplements) {
+        walk_class_implements_mut(self, class_implements);
+    }
+
+    fn visit_class_member_mut(&mut self, class_member: &mut ClassMember) {
+        walk_class_member_mut(self, class_member);
+    }
+
+    fn visit_anonymous_class_member_mut(
+        &mut self,
+        anonymous_class_member: &mut AnonymousClassMember,
+    ) {
+        walk_anonymous_class_member_mut(self, anonymous_class_member);
+    }
+
+    fn visit_comment_mut(&mut self, _comment: &mut Comment) {}
+
+    fn visit_comment_group_mut(&mut self, comment_group: &mut CommentGroup) {
+        walk_comment_group_mut(self, comment_group);
+    }
+
+    fn visit_constant_entry_mut(&mut self, constant_entry: &mut ConstantEntry) {
+        walk_constant_entry_mut(self, constant_entry);
+    }
+
+    fn visit_constant_statement_mut(&mut self, constant_statement: &mut ConstantStatement) {
+        walk_constant_statement_mut(self, constant_statement);
+    }
+
+    fn visit_classish_constant_mut(&mut self, classish_constant: &mut ClassishConstant) {
+        walk_classish_constant_mut(self, classish_constant);
+    }
+
+    fn visit_if_statement_mut(&mut self, if_statement: &mut IfStatement) {
+        walk_if_statement_mut(self, if_statement);
+    }
+
+    fn visit_if_statement_body_mut(&mut self, if_statement_body: &mut IfStatementBody) {
+        walk_if_statement_body_mut(self, if_statement_body);
+    }
+
+    fn visit_if_statement_else_if_mut(&mut self, if_statement_else_if: &mut IfStatementElseIf) {
+        walk_if_statement_else_if_mut(self, if_statement_else_if);
+    }
+
+    fn visit_if_statement_else_mut(&mut self, if_statement_else: &mut IfStatementElse) {
+        walk_if_statement_else_mut(self, if_statement_else);
+    }
+
+    fn visit_if_statement_else_if_block_mut(
+        &mut self,
+        if_statement_else_if_block: &mut IfStatementElseIfBlock,
+    ) {
+        walk_if_statement_else_if_block_mut(self, if_statement_else_if_block);
+    }
+
+    fn visit_if_statement_else_block_mut(
+        &mut self,
+        if_statement_else_block: &mut IfStatementElseBlock,
+    ) {
+        walk_if_statement_else_block_mut(self, if_statement_else_block);
+    }
+
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        walk_type_mut(self, ty);
+    }
+
+    fn visit_declare_entry_mut(&mut self, declare_entry: &mut DeclareEntry) {
+        walk_declare_entry_mut(self, declare_entry);
+    }
+
+    fn visit_declare_entry_group_mut(&mut self, declare_entry_group: &mut DeclareEntryGroup) {
+        walk_declare_entry_group_mut(self, declare_entry_group);
+    }
+
+    fn visit_declare_body_mut(&mut self, declare_body: &mut DeclareBody) {
+        walk_declare_body_mut(self, declare_body);
+    }
+
+    fn visit_declare_statement_mut(&mut self, declare_statement: &mut DeclareStatement) {
+        walk_declare_statement_mut(self, declare_statement);
+    }
+
+    fn visit_unit_enum_case_mut(&mut self, unit_enum_case: &mut UnitEnumCase) {
+        walk_unit_enum_case_mut(self, unit_enum_case);
+    }
+
+    fn visit_unit_enum_member_mut(&mut self, unit_enum_member: &mut UnitEnumMember) {
+        walk_unit_enum_member_mut(self, unit_enum_member);
+    }
+
+    fn visit_unit_enum_body_mut(&mut self, unit_enum_body: &mut UnitEnumBody) {
+        walk_unit_enum_body_mut(self, unit_enum_body);
+    }
+
+    fn visit_unit_enum_statement_mut(&mut self, unit_enum_statement: &mut UnitEnumStatement) {
+        walk_unit_enum_statement_mut(self, unit_enum_statement);
+    }
+
+    fn visit_backed_enum_type_mut(&mut self, _backed_enum_type: &mut BackedEnumType) {}
+
+    fn visit_backed_enum_case_mut(&mut self, backed_enum_case: &mut BackedEnumCase) {
+        walk_backed_enum_case_mut(self, backed_enum_case);
+    }
+
+    fn visit_backed_enum_member_mut(&mut self, backed_enum_member: &mut BackedEnumMember) {
+        walk_backed_enum_member_mut(self, backed_enum_member);
+    }
+
+    fn visit_backed_enum_body_mut(&mut self, backed_enum_body: &mut BackedEnumBody) {
+        walk_backed_enum_body_mut(self, backed_enum_body);
+    }
+
+    fn visit_backed_enum_statement_mut(&mut self, backed_enum_statement: &mut BackedEnumStatement) {
+        walk_backed_enum_statement_mut(self, backed_enum_statement);
+    }
+
+    fn visit_return_type_mut(&mut self, return_type: &mut ReturnType) {
+        walk_return_type_mut(self, return_type);
+    }
+
+    fn visit_function_parameter_mut(&mut self, function_parameter: &mut FunctionParameter) {
+        walk_function_parameter_mut(self, function_parameter);
+    }
+
+    fn visit_function_parameter_list_mut(
+        &mut self,
+        function_parameter_list: &mut FunctionParameterList,
+    ) {
+        walk_function_parameter_list_mut(self, function_parameter_list);
+    }
+
+    fn visit_function_body_mut(&mut self, function_body: &mut FunctionBody) {
+        walk_function_body_mut(self, function_body);
+    }
+
+    fn visit_function_statement_mut(&mut self, function_statement: &mut FunctionStatement) {
+        walk_function_statement_mut(self, function_statement);
+    }
+
+    fn visit_closure_use_variable_mut(&mut self, closure_use_variable: &mut ClosureUseVariable) {
+        walk_closure_use_variable_mut(self, closure_use_variable);
+    }
+
+    fn visit_closure_use_mut(&mut self, closure_use: &mut ClosureUse) {
+        walk_closure_use_mut(self, closure_use);
+    }
+
+    fn visit_exit_expression_mut(&mut self, exit_expression: &mut ExitExpression) {
+        walk_exit_expression_mut(self, exit_expression);
+    }
+
+    fn visit_closure_mut(&mut self, closure: &mut Closure) {
+        walk_closure_mut(self, closure);
+    }
+
+    fn visit_arrow_function_mut(&mut self, arrow_function: &mut ArrowFunction) {
+        walk_arrow_function_mut(self, arrow_function);
+    }
+
+    fn visit_constructor_parameter_mut(
+        &mut self,
+        constructor_parameter: &mut ConstructorParameter,
+    ) {
+        walk_constructor_parameter_mut(self, constructor_parameter);
+    }
+
+    fn visit_constructor_parameter_list_mut(
+        &mut self,
+        constructor_parameter_list: &mut ConstructorParameterList,
+    ) {
+        walk_constructor_parameter_list_mut(self, constructor_parameter_list);
+    }
+
+    fn visit_abstract_constructor_mut(&mut self, abstract_constructor: &mut AbstractConstructor) {
+        walk_abstract_constructor_mut(self, abstract_constructor);
+    }
+
+    fn visit_concrete_constructor_mut(&mut self, concrete_constructor: &mut ConcreteConstructor) {
+        walk_concrete_constructor_mut(self, concrete_constructor);
+    }
+
+    fn visit_abstract_method_mut(&mut self, abstract_method: &mut AbstractMethod) {
+        walk_abstract_method_mut(self, abstract_method);
+    }
+
+    fn visit_concrete_method_mut(&mut self, concrete_method: &mut ConcreteMethod) {
+        walk_concrete_method_mut(self, concrete_method);
+    }
+
+    fn visit_method_body_mut(&mut self, method_body: &mut MethodBody) {
+        walk_method_body_mut(self, method_body);
+    }
+
+    fn visit_label_statement_mut(&mut self, label_statement: &mut LabelStatement) {
+        walk_label_statement_mut(self, label_statement);
+    }
+
+    fn visit_goto_statement_mut(&mut self, goto_statement: &mut GotoStatement) {
+        walk_goto_statement_mut(self, goto_statement);
+    }
+
+    fn visit_identifier_mut(&mut self, identifier: &mut Identifier) {
+        walk_identifier_mut(self, identifier);
+    }
+
+    fn visit_simple_identifier_mut(&mut self, _simple_identifier: &mut SimpleIdentifier) {}
+
+    fn visit_dynamic_identifier_mut(&mut self, dynamic_identifier: &mut DynamicIdentifier) {
+        walk_dynamic_identifier_mut(self, dynamic_identifier);
+    }
+
+    fn visit_interface_member_mut(&mut self, interface_member: &mut InterfaceMember) {
+        walk_interface_member_mut(self, interface_member);
+    }
+
+    fn visit_interface_extends_mut(&mut self, interface_extends: &mut InterfaceExtends) {
+        walk_interface_extends_mut(self, interface_extends);
+    }
+
+    fn visit_interface_body_mut(&mut self, interface_body: &mut InterfaceBody) {
+        walk_interface_body_mut(self, interface_body);
+    }
+
+    fn visit_interface_statement_mut(&mut self, interface_statement: &mut InterfaceStatement) {
+        walk_interface_statement_mut(self, interface_statement);
+    }
+
+    fn visit_literal_mut(&mut self, literal: &mut Literal) {
+        walk_literal_mut(self, literal);
+    }
+
+    fn visit_literal_string_mut(&mut self, _literal_string: &mut LiteralString) {}
+
+    fn visit_literal_integer_mut(&mut self, _literal_integer: &mut LiteralInteger) {}
+
+    fn visit_literal_float_mut(&mut self, _literal_float: &mut LiteralFloat) {}
+
+    fn visit_foreach_statement_mut(&mut self, foreach_statement: &mut ForeachStatement) {
+        walk_foreach_statement_mut(self, foreach_statement);
+    }
+
+    fn visit_foreach_statement_iterator_mut(
+        &mut self,
+        foreach_statement_iterator: &mut ForeachStatementIterator,
+    ) {
+        walk_foreach_statement_iterator_mut(self, foreach_statement_iterator);
+    }
+
+    fn visit_foreach_statement_body_mut(
+        &mut self,
+        foreach_statement_body: &mut ForeachStatementBody,
+    ) {
+        walk_foreach_statement_body_mut(self, foreach_statement_body);
+    }
+
+    fn visit_for_statement_mut(&mut self, for_statement: &mut ForStatement) {
+        walk_for_statement_mut(self, for_statement);
+    }
+
+    fn visit_for_statement_iterator_mut(
+        &mut self,
+        for_statement_iterator: &mut ForStatementIterator,
+    ) {
+        walk_for_statement_iterator_mut(self, for_statement_iterator);
+    }
+
+    fn visit_for_statement_body_mut(&mut self, for_statement_body: &mut ForStatementBody) {
+        walk_for_statement_body_mut(self, for_statement_body);
+    }
+
+    fn visit_do_while_statement_mut(&mut self, do_while_statement: &mut DoWhileStatement) {
+        walk_do_while_statement_mut(self, do_while_statement);
+    }
+
+    fn visit_while_statement_mut(&mut self, while_statement: &mut WhileStatement) {
+        walk_while_statement_mut(self, while_statement);
+    }
+
+    fn visit_while_statement_body_mut(&mut self, while_statement_body: &mut WhileStatementBody) {
+        walk_while_statement_body_mut(self, while_statement_body);
+    }
+
+    fn visit_level_mut(&mut self, level: &mut Level) {
+        walk_level_mut(self, level);
+    }
+
+    fn visit_break_statement_mut(&mut self, break_statement: &mut BreakStatement) {
+        walk_break_statement_mut(self, break_statement);
+    }
+
+    fn visit_continue_statement_mut(&mut self, continue_statement: &mut ContinueStatement) {
+        walk_continue_statement_mut(self, continue_statement);
+    }
+
+    fn visit_static_var_mut(&mut self, static_var: &mut StaticVar) {
+        walk_static_var_mut(self, static_var);
+    }
+
+    fn visit_halt_compiler_mut(&mut self, _halt_compiler: &mut HaltCompiler) {}
+
+    fn visit_foreign_chunk_mut(&mut self, _foreign_chunk: &mut ForeignChunk) {}
+
+    fn visit_error_statement_mut(&mut self, _error_statement: &mut ErrorStatement) {}
+
+    fn visit_static_statement_mut(&mut self, static_statement: &mut StaticStatement) {
+        walk_static_statement_mut(self, static_statement);
+    }
+
+    fn visit_switch_statement_mut(&mut self, switch_statement: &mut SwitchStatement) {
+        walk_switch_statement_mut(self, switch_statement);
+    }
+
+    fn visit_echo_statement_mut(&mut self, echo_statement: &mut EchoStatement) {
+        walk_echo_statement_mut(self, echo_statement);
+    }
+
+    fn visit_return_statement_mut(&mut self, return_statement: &mut ReturnStatement) {
+        walk_return_statement_mut(self, return_statement);
+    }
+
+    fn visit_use_statement_mut(&mut self, use_statement: &mut UseStatement) {
+        walk_use_statement_mut(self, use_statement);
+    }
+
+    fn visit_group_use_statement_mut(&mut self, group_use_statement: &mut GroupUseStatement) {
+        walk_group_use_statement_mut(self, group_use_statement);
+    }
+
+    fn visit_expression_statement_mut(&mut self, expression_statement: &mut ExpressionStatement) {
+        walk_expression_statement_mut(self, expression_statement);
+    }
+
+    fn visit_global_statement_mut(&mut self, global_statement: &mut GlobalStatement) {
+        walk_global_statement_mut(self, global_statement);
+    }
+
+    fn visit_block_statement_mut(&mut self, block_statement: &mut BlockStatement) {
+        walk_block_statement_mut(self, block_statement);
+    }
+
+    fn visit_case_mut(&mut self, case: &mut Case) {
+        walk_case_mut(self, case);
+    }
+
+    fn visit_use_mut(&mut self, r#use: &mut Use) {
+        walk_use_mut(self, r#use);
+    }
+
+    fn visit_default_match_arm_mut(&mut self, default_match_arm: &mut DefaultMatchArm) {
+        walk_default_match_arm_mut(self, default_match_arm);
+    }
+
+    fn visit_match_arm_mut(&mut self, match_arm: &mut MatchArm) {
+        walk_match_arm_mut(self, match_arm);
+    }
+
+    fn visit_magic_constant_mut(&mut self, _magic_constant: &mut MagicConstant) {}
+
+    fn visit_string_part_mut(&mut self, string_part: &mut StringPart) {
+        walk_string_part_mut(self, string_part);
+    }
+
+    fn visit_literal_string_part_mut(&mut self, _literal_string_part: &mut LiteralStringPart) {}
+
+    fn visit_expression_string_part_mut(
+        &mut self,
+        expression_string_part: &mut ExpressionStringPart,
+    ) {
+        walk_expression_string_part_mut(self, expression_string_part);
+    }
+
+    fn visit_array_item_mut(&mut self, array_item: &mut ArrayItem) {
+        walk_array_item_mut(self, array_item);
+    }
+
+    fn visit_list_entry_mut(&mut self, list_entry: &mut ListEntry) {
+        walk_list_entry_mut(self, list_entry);
+    }
+
+    fn visit_visibility_modifier_mut(&mut self, _visibility_modifier: &mut VisibilityModifier) {}
+
+    fn visit_promoted_property_modifier_mut(
+        &mut self,
+        _promoted_property_modifier: &mut PromotedPropertyModifier,
+    ) {
+    }
+
+    fn visit_promoted_property_modifier_group_mut(
+        &mut self,
+        promoted_property_modifier_group: &mut PromotedPropertyModifierGroup,
+    ) {
+        walk_promoted_property_modifier_group_mut(self, promoted_property_modifier_group);
+    }
+
+    fn visit_property_modifier_mut(&mut self, _property_modifier: &mut PropertyModifier) {}
+
+    fn visit_property_modifier_group_mut(
+        &mut self,
+        property_modifier_group: &mut PropertyModifierGroup,
+    ) {
+        walk_property_modifier_group_mut(self, property_modifier_group);
+    }
+
+    fn visit_method_modifier_mut(&mut self, _method_modifier: &mut MethodModifier) {}
+
+    fn visit_method_modifier_group_mut(&mut self, method_modifier_group: &mut MethodModifierGroup) {
+        walk_method_modifier_group_mut(self, method_modifier_group);
+    }
+
+    fn visit_class_modifier_mut(&mut self, _class_modifier: &mut ClassModifier) {}
+
+    fn visit_class_modifier_group_mut(&mut self, class_modifier_group: &mut ClassModifierGroup) {
+        walk_class_modifier_group_mut(self, class_modifier_group);
+    }
+
+    fn visit_constant_modifier_mut(&mut self, _constant_modifier: &mut ConstantModifier) {}
+
+    fn visit_constant_modifier_group_mut(
+        &mut self,
+        constant_modifier_group: &mut ConstantModifierGroup,
+    ) {
+        walk_constant_modifier_group_mut(self, constant_modifier_group);
+    }
+
+    fn visit_unbraced_namespace_mut(&mut self, unbraced_namespace: &mut UnbracedNamespace) {
+        walk_unbraced_namespace_mut(self, unbraced_namespace);
+    }
+
+    fn visit_braced_namespace_mut(&mut self, braced_namespace: &mut BracedNamespace) {
+        walk_braced_namespace_mut(self, braced_namespace);
+    }
+
+    fn visit_braced_namespace_body_mut(&mut self, braced_namespace_body: &mut BracedNamespaceBody) {
+        walk_braced_namespace_body_mut(self, braced_namespace_body);
+    }
+
+    fn visit_namespace_statement_mut(&mut self, namespace_statement: &mut NamespaceStatement) {
+        walk_namespace_statement_mut(self, namespace_statement);
+    }
+
+    fn visit_arithmetic_operation_mut(&mut self, arithmetic_operation: &mut ArithmeticOperation) {
+        walk_arithmetic_operation_mut(self, arithmetic_operation);
+    }
+
+    fn visit_assignment_operation_mut(&mut self, assignment_operation: &mut AssignmentOperation) {
+        walk_assignment_operation_mut(self, assignment_operation);
+    }
+
+    fn visit_bitwise_operation_mut(&mut self, bitwise_operation: &mut BitwiseOperation) {
+        walk_bitwise_operation_mut(self, bitwise_operation);
+    }
+
+    fn visit_comparison_operation_mut(&mut self, comparison_operation: &mut ComparisonOperation) {
+        walk_comparison_operation_mut(self, comparison_operation);
+    }
+
+    fn visit_logical_operation_mut(&mut self, logical_operation: &mut LogicalOperation) {
+        walk_logical_operation_mut(self, logical_operation);
+    }
+
+    fn visit_property_mut(&mut self, property: &mut Property) {
+        walk_property_mut(self, property);
+    }
+
+    fn visit_variable_property_mut(&mut self, variable_property: &mut VariableProperty) {
+        walk_variable_property_mut(self, variable_property);
+    }
+
+    fn visit_property_entry_mut(&mut self, property_entry: &mut PropertyEntry) {
+        walk_property_entry_mut(self, property_entry);
+    }
+
+    fn visit_property_hook_mut(&mut self, property_hook: &mut PropertyHook) {
+        walk_property_hook_mut(self, property_hook);
+    }
+
+    fn visit_trait_member_mut(&mut self, trait_member: &mut TraitMember) {
+        walk_trait_member_mut(self, trait_member);
+    }
+
+    fn visit_trait_body_mut(&mut self, trait_body: &mut TraitBody) {
+        walk_trait_body_mut(self, trait_body);
+    }
+
+    fn visit_trait_statement_mut(&mut self, trait_statement: &mut TraitStatement) {
+        walk_trait_statement_mut(self, trait_statement);
+    }
+
+    fn visit_trait_usage_mut(&mut self, trait_usage: &mut TraitUsage) {
+        walk_trait_usage_mut(self, trait_usage);
+    }
+
+    fn visit_trait_usage_adaptation_mut(
+        &mut self,
+        trait_usage_adaptation: &mut TraitUsageAdaptation,
+    ) {
+        walk_trait_usage_adaptation_mut(self, trait_usage_adaptation);
+    }
+
+    fn visit_catch_type_mut(&mut self, catch_type: &mut CatchType) {
+        walk_catch_type_mut(self, catch_type);
+    }
+
+    fn visit_try_statement_mut(&mut self, try_statement: &mut TryStatement) {
+        walk_try_statement_mut(self, try_statement);
+    }
+
+    fn visit_catch_block_mut(&mut self, catch_block: &mut CatchBlock) {
+        walk_catch_block_mut(self, catch_block);
+    }
+
+    fn visit_finally_block_mut(&mut self, finally_block: &mut FinallyBlock) {
+        walk_finally_block_mut(self, finally_block);
+    }
+
+    fn visit_variable_mut(&mut self, variable: &mut Variable) {
+        walk_variable_mut(self, variable);
+    }
+
+    fn visit_simple_variable_mut(&mut self, _simple_variable: &mut SimpleVariable) {}
+
+    fn visit_variable_variable_mut(&mut self, variable_variable: &mut VariableVariable) {
+        walk_variable_variable_mut(self, variable_variable);
+    }
+
+    fn visit_braced_variable_variable_mut(
+        &mut self,
+        braced_variable_variable: &mut BracedVariableVariable,
+    ) {
+        walk_braced_variable_variable_mut(self, braced_variable_variable);
+    }
+}
+
+pub fn walk_program_mut<V: VisitorMut + ?Sized>(visitor: &mut V, program: &mut Program) {
+    visitor.visit_block_mut(program);
+}
+
+pub fn walk_block_mut<V: VisitorMut + ?Sized>(visitor: &mut V, block: &mut Block) {
+    for statement in block {
+        visitor.visit_statement_mut(statement);
+    }
+}
+
+pub fn walk_statement_mut<V: VisitorMut + ?Sized>(visitor: &mut V, statement: &mut Statement) {
+    match statement {
+        Statement::ForeignChunk(foreign_chunk) => visitor.visit_foreign_chunk_mut(foreign_chunk),
+        Statement::Label(label_statement) => visitor.visit_label_statement_mut(label_statement),
+        Statement::Goto(goto_statement) => visitor.visit_goto_statement_mut(goto_statement),
+        Statement::HaltCompiler(halt_compiler) => visitor.visit_halt_compiler_mut(halt_compiler),
+        Statement::Static(static_statement) => visitor.visit_static_statement_mut(static_statement),
+        Statement::DoWhile(do_while_statement) => {
+            visitor.visit_do_while_statement_mut(do_while_statement)
+        }
+        Statement::While(while_statement) => visitor.visit_while_statement_mut(while_statement),
+        Statement::For(for_statement) => visitor.visit_for_statement_mut(for_statement),
+        Statement::Foreach(foreach_statement) => {
+            visitor.visit_foreach_statement_mut(foreach_statement)
+        }
+        Statement::Break(break_statement) => visitor.visit_break_statement_mut(break_statement),
+        Statement::Continue(continue_statement) => {
+            visitor.visit_continue_statement_mut(continue_statement)
+        }
+        Statement::Constant(constant_statement) => {
+            visitor.visit_constant_statement_mut(constant_statement)
+        }
+        Statement::Function(function_statement) => {
+            visitor.visit_function_statement_mut(function_statement)
+        }
+        Statement::Class(class_statement) => visitor.visit_class_statement_mut(class_statement),
+        Statement::Trait(trait_statement) => visitor.visit_trait_statement_mut(trait_statement),
+        Statement::Interface(interface_statement) => {
+            visitor.visit_interface_statement_mut(interface_statement)
+        }
+        Statement::If(if_statement) => visitor.visit_if_statement_mut(if_statement),
+        Statement::Switch(switch_statement) => visitor.visit_switch_statement_mut(switch_statement),
+        Statement::Echo(echo_statement) => visitor.visit_echo_statement_mut(echo_statement),
+        Statement::Expression(expression_statement) => {
+            visitor.visit_expression_statement_mut(expression_statement)
+        }
+        Statement::Return(return_statement) => visitor.visit_return_statement_mut(return_statement),
+        Statement::Namespace(namespace_statement) => {
+            visitor.visit_namespace_statement_mut(namespace_statement)
+        }
+        Statement::Use(use_statement) => visitor.visit_use_statement_mut(use_statement),
+        Statement::GroupUse(group_use_statement) => {
+            visitor.visit_group_use_statement_mut(group_use_statement)
+        }
+        Statement::Comment(comment) => visitor.visit_comment_mut(comment),
+        Statement::Try(try_statement) => visitor.visit_try_statement_mut(try_statement),
+        Statement::UnitEnum(unit_enum_statement) => {
+            visitor.visit_unit_enum_statement_mut(unit_enum_statement)
+        }
+        Statement::BackedEnum(backed_enum_statement) => {
+            visitor.visit_backed_enum_statement_mut(backed_enum_statement)
+        }
+        Statement::Block(block_statement) => visitor.visit_block_statement_mut(block_statement),
+        Statement::Global(global_statement) => visitor.visit_global_statement_mut(global_statement),
+        Statement::Declare(declare_statement) => {
+            visitor.visit_declare_statement_mut(declare_statement)
+        }
+        Statement::Error(error_statement) => visitor.visit_error_statement_mut(error_statement),
+        Statement::FullOpeningTag(_)
+        | Statement::ShortOpeningTag(_)
+        | Statement::EchoOpeningTag(_)
+        | Statement::ClosingTag(_)
+        | Statement::InlineHtml(_)
+        | Statement::Noop(_) => {}
+    }
+}
+
+pub fn walk_expression_mut<V: VisitorMut + ?Sized>(visitor: &mut V, expression: &mut Expression) {
+    match expression {
+        Expression::Eval { argument, .. } | Expression::Empty { argument, .. } => {
+            visitor.visit_single_argument_mut(argument)
+        }
+        Expression::Exit(exit) => visitor.visit_exit_expression_mut(exit),
+        Expression::Isset { arguments, .. } | Expression::Unset { arguments, .. } => {
+            visitor.visit_argument_list_mut(arguments)
+        }
+        Expression::Print {
+            value, argument, ..
+        } => {
+            if let Some(value) = value {
+                visitor.visit_expression_mut(value);
+            }
+            if let Some(argument) = argument {
+                visitor.visit_single_argument_mut(argument);
+            }
+        }
+        Expression::Literal(literal) => visitor.visit_literal_mut(literal),
+        Expression::ArithmeticOperation(arithmetic_operation) => {
+            visitor.visit_arithmetic_operation_mut(arithmetic_operation)
+        }
+        Expression::AssignmentOperation(assignment_operation) => {
+            visitor.visit_assignment_operation_mut(assignment_operation)
+        }
+        Expression::BitwiseOperation(bitwise_operation) => {
+            visitor.visit_bitwise_operation_mut(bitwise_operation)
+        }
+        Expression::ComparisonOperation(comparison_operation) => {
+            visitor.visit_comparison_operation_mut(comparison_operation)
+        }
+        Expression::LogicalOperation(logical_operation) => {
+            visitor.visit_logical_operation_mut(logical_operation)
+        }
+        Expression::Concat { left, right, .. }
+        | Expression::Instanceof { left, right, .. }
+        | Expression::Pipe { left, right, .. } => {
+            visitor.visit_expression_mut(left);
+            visitor.visit_expression_mut(right);
+        }
+        Expression::Parenthesized { expr, .. } | Expression::ErrorSuppress { expr, .. } => {
+            visitor.visit_expression_mut(expr)
+        }
+        Expression::Identifier(identifier) => visitor.visit_identifier_mut(identifier),
+        Expression::Variable(variable) => visitor.visit_variable_mut(variable),
+        Expression::Include { path, .. }
+        | Expression::IncludeOnce { path, .. }
+        | Expression::Require { path, .. }
+        | Expression::RequireOnce { path, .. } => visitor.visit_expression_mut(path),
+        Expression::FunctionCall { target, arguments } => {
+            visitor.visit_expression_mut(target);
+            visitor.visit_argument_list_mut(arguments);
+        }
+        Expression::FunctionClosureCreation {
+            target,
+            placeholder,
+        } => {
+            visitor.visit_expression_mut(target);
+            visitor.visit_argument_placeholder_mut(placeholder);
+        }
+        Expression::MethodCall {
+            target,
+            method,
+            arguments,
+            ..
+        }
+        | Expression::NullsafeMethodCall {
+            target,
+            method,
+            arguments,
+            ..
+        } => {
+            visitor.visit_expression_mut(target);
+            visitor.visit_expression_mut(method);
+            visitor.visit_argument_list_mut(arguments);
+        }
+        Expression::MethodClosureCreation {
+            target,
+            method,
+            placeholder,
+            ..
+        } => {
+            visitor.visit_expression_mut(target);
+            visitor.visit_expression_mut(method);
+            visitor.visit_argument_placeholder_mut(placeholder);
+        }
+        Expression::StaticMethodCall {
+            target,
+            method,
+            arguments,
+            ..
+        } => {
+            visitor.visit_expression_mut(target);
+            visitor.visit_identifier_mut(method);
+            visitor.visit_argument_list_mut(arguments);
+        }
+        Expression::StaticVariableMethodCall {
+            target,
+            method,
+            arguments,
+            ..
+        } => {
+            visitor.visit_expression_mut(target);
+            visitor.visit_variable_mut(method);
+            visitor.visit_argument_list_mut(arguments);
+        }
+        Expression::StaticMethodClosureCreation {
+            target,
+            method,
+            placeholder,
+            ..
+        } => {
+            visitor.visit_expression_mut(target);
+            visitor.visit_identifier_mut(method);
+            visitor.visit_argument_placeholder_mut(placeholder);
+        }
+        Expression::StaticVariableMethodClosureCreation {
+            target,
+            method,
+            placeholder,
+            ..
+        } => {
+            visitor.visit_expression_mut(target);
+            visitor.visit_variable_mut(method);
+            visitor.visit_argument_placeholder_mut(placeholder);
+        }
+        Expression::PropertyFetch {
+            target, property, ..
+        }
+        | Expression::NullsafePropertyFetch {
+            target, property, ..
+        } => {
+            visitor.visit_expression_mut(target);
+            visitor.visit_expression_mut(property);
+        }
+        Expression::StaticPropertyFetch {
+            target, property, ..
+        } => {
+            visitor.visit_expression_mut(target);
+            visitor.visit_variable_mut(property);
+        }
+        Expression::ConstantFetch {
+            target, constant, ..
+        } => {
+            visitor.visit_expression_mut(target);
+            visitor.visit_identifier_mut(constant);
+        }
+        Expression::ShortArray { items, .. } => {
+            for array_item in &mut items.inner {
+                visitor.visit_array_item_mut(array_item);
+            }
+        }
+        Expression::Array(array) => {
+            for array_item in &mut array.items.inner {
+                visitor.visit_array_item_mut(array_item);
+            }
+        }
+        Expression::List(list) => {
+            for list_entry in &mut list.items {
+                visitor.visit_list_entry_mut(list_entry);
+            }
+        }
+        Expression::Closure(closure) => visitor.visit_closure_mut(closure),
+        Expression::ArrowFunction(arrow_function) => {
+            visitor.visit_arrow_function_mut(arrow_function)
+        }
+        Expression::New {
+            target, arguments, ..
+        } => {
+            visitor.visit_expression_mut(target);
+            if let Some(arguments) = arguments {
+                visitor.visit_argument_list_mut(arguments);
+            }
+        }
+        Expression::InterpolatedString { parts, .. }
+        | Expression::Heredoc { parts, .. }
+        | Expression::Nowdoc { parts, .. }
+        | Expression::ShellExec { parts, .. } => {
+            for string_part in parts {
+                visitor.visit_string_part_mut(string_part);
+            }
+        }
+        Expression::AnonymousClass(anonymous_class) => {
+            visitor.visit_anonymous_class_mut(anonymous_class)
+        }
+        Expression::ArrayIndex { array, index, .. } => {
+            visitor.visit_expression_mut(array);
+            if let Some(index) = index {
+                visitor.visit_expression_mut(index);
+            }
+        }
+        Expression::MagicConstant(magic_constant) => {
+            visitor.visit_magic_constant_mut(magic_constant)
+        }
+        Expression::ShortTernary {
+            condition, r#else, ..
+        } => {
+            visitor.visit_expression_mut(condition);
+            visitor.visit_expression_mut(r#else);
+        }
+        Expression::Ternary {
+            condition,
+            then,
+            r#else,
+            ..
+        } => {
+            visitor.visit_expression_mut(condition);
+            visitor.visit_expression_mut(then);
+            visitor.visit_expression_mut(r#else);
+        }
+        Expression::Coalesce { lhs, rhs, .. } => {
+            visitor.visit_expression_mut(lhs);
+            visitor.visit_expression_mut(rhs);
+        }
+        Expression::Clone { target, .. } => visitor.visit_expression_mut(target),
+        Expression::Match(expression) => {
+            visitor.visit_expression_mut(&mut expression.condition);
+            for match_arm in &mut expression.arms {
+                visitor.visit_match_arm_mut(match_arm);
+            }
+            if let Some(default) = &mut expression.default {
+                visitor.visit_default_match_arm_mut(default);
+            }
+        }
+        Expression::Throw { value, .. } | Expression::YieldFrom { value, .. } => {
+            visitor.visit_expression_mut(value)
+        }
+        Expression::Yield { key, value, .. } => {
+            if let Some(key) = key {
+                visitor.visit_expression_mut(key);
+            }
+            if let Some(value) = value {
+                visitor.visit_expression_mut(value);
+            }
+        }
+        Expression::Cast { value, .. } => visitor.visit_expression_mut(value),
+        Expression::Static { .. }
+        | Expression::Self_ { .. }
+        | Expression::Parent { .. }
+        | Expression::Bool { .. }
+        | Expression::Null { .. }
+        | Expression::Noop { .. }
+        | Expression::Missing { .. } => {}
+    }
+}
+
+pub fn walk_argument_mut<V: VisitorMut + ?Sized>(visitor: &mut V, argument: &mut Argument) {
+    match argument {
+        Argument::Positional {
+            comments, value, ..
+        } => {
+            visitor.visit_comment_group_mut(comments);
+            visitor.visit_expression_mut(value);
+        }
+        Argument::Named {
+            comments,
+            name,
+            value,
+            ..
+        } => {
+            visitor.visit_comment_group_mut(comments);
+            visitor.visit_simple_identifier_mut(name);
+            visitor.visit_expression_mut(value);
+        }
+    }
+}
+
+pub fn walk_argument_list_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    argument_list: &mut ArgumentList,
+) {
+    visitor.visit_comment_group_mut(&mut argument_list.comments);
+    for argument in &mut argument_list.arguments {
+        visitor.visit_argument_mut(argument);
+    }
+}
+
+pub fn walk_single_argument_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    single_argument: &mut SingleArgument,
+) {
+    visitor.visit_comment_group_mut(&mut single_argument.comments);
+    visitor.visit_argument_mut(&mut single_argument.argument);
+}
+
+pub fn walk_argument_placeholder_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    argument_placeholder: &mut ArgumentPlaceholder,
+) {
+    visitor.visit_comment_group_mut(&mut argument_placeholder.comments);
+}
+
+pub fn walk_attribute_mut<V: VisitorMut + ?Sized>(visitor: &mut V, attribute: &mut Attribute) {
+    visitor.visit_simple_identifier_mut(&mut attribute.name);
+    if let Some(arguments) = &mut attribute.arguments {
+        visitor.visit_argument_list_mut(arguments);
+    }
+}
+
+pub fn walk_attribute_group_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    attribute_group: &mut AttributeGroup,
+) {
+    for attribute in &mut attribute_group.members {
+        visitor.visit_attribute_mut(attribute);
+    }
+}
+
+pub fn walk_class_body_mut<V: VisitorMut + ?Sized>(visitor: &mut V, class_body: &mut ClassBody) {
+    for class_member in &mut class_body.members {
+        visitor.visit_class_member_mut(class_member);
+    }
+}
+
+pub fn walk_class_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    class_statement: &mut ClassStatement,
+) {
+    visitor.visit_comment_group_mut(&mut class_statement.comments);
+    for attribute_group in &mut class_statement.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_class_modifier_group_mut(&mut class_statement.modifiers);
+    visitor.visit_simple_identifier_mut(&mut class_statement.name);
+    if let Some(extends) = &mut class_statement.extends {
+        visitor.visit_class_extends_mut(extends);
+    }
+    if let Some(implements) = &mut class_statement.implements {
+        visitor.visit_class_implements_mut(implements);
+    }
+    visitor.visit_class_body_mut(&mut class_statement.body);
+}
+
+pub fn walk_anonymous_class_body_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    anonymous_class_body: &mut AnonymousClassBody,
+) {
+    for anonymous_class_member in &mut anonymous_class_body.members {
+        visitor.visit_anonymous_class_member_mut(anonymous_class_member);
+    }
+}
+
+pub fn walk_anonymous_class_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    anonymous_class: &mut AnonymousClass,
+) {
+    for attribute_group in &mut anonymous_class.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    if let Some(extends) = &mut anonymous_class.extends {
+        visitor.visit_class_extends_mut(extends);
+    }
+    if let Some(implements) = &mut anonymous_class.implements {
+        visitor.visit_class_implements_mut(implements);
+    }
+    visitor.visit_anonymous_class_body_mut(&mut anonymous_class.body);
+}
+
+pub fn walk_class_extends_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    class_extends: &mut ClassExtends,
+) {
+    visitor.visit_simple_identifier_mut(&mut class_extends.parent);
+}
+
+pub fn walk_class_implements_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    class_implements: &mut ClassImplements,
+) {
+    for simple_identifier in &mut class_implements.interfaces.inner {
+        visitor.visit_simple_identifier_mut(simple_identifier);
+    }
+}
+
+pub fn walk_class_member_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    class_member: &mut ClassMember,
+) {
+    match class_member {
+        ClassMember::Constant(classish_constant) => {
+            visitor.visit_classish_constant_mut(classish_constant)
+        }
+        ClassMember::TraitUsage(trait_usage) => visitor.visit_trait_usage_mut(trait_usage),
+        ClassMember::Property(property) => visitor.visit_property_mut(property),
+        ClassMember::VariableProperty(variable_property) => {
+            visitor.visit_variable_property_mut(variable_property)
+        }
+        ClassMember::AbstractMethod(abstract_method) => {
+            visitor.visit_abstract_method_mut(abstract_method)
+        }
+        ClassMember::AbstractConstructor(abstract_constructor) => {
+            visitor.visit_abstract_constructor_mut(abstract_constructor)
+        }
+        ClassMember::ConcreteMethod(concrete_method) => {
+            visitor.visit_concrete_method_mut(concrete_method)
+        }
+        ClassMember::ConcreteConstructor(concrete_constructor) => {
+            visitor.visit_concrete_constructor_mut(concrete_constructor)
+        }
+    }
+}
+
+pub fn walk_anonymous_class_member_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    anonymous_class_member: &mut AnonymousClassMember,
+) {
+    match anonymous_class_member {
+        AnonymousClassMember::Constant(classish_constant) => {
+            visitor.visit_classish_constant_mut(classish_constant)
+        }
+        AnonymousClassMember::TraitUsage(trait_usage) => visitor.visit_trait_usage_mut(trait_usage),
+        AnonymousClassMember::Property(property) => visitor.visit_property_mut(property),
+        AnonymousClassMember::VariableProperty(variable_property) => {
+            visitor.visit_variable_property_mut(variable_property)
+        }
+        AnonymousClassMember::ConcreteMethod(concrete_method) => {
+            visitor.visit_concrete_method_mut(concrete_method)
+        }
+        AnonymousClassMember::ConcreteConstructor(concrete_constructor) => {
+            visitor.visit_concrete_constructor_mut(concrete_constructor)
+        }
+    }
+}
+
+pub fn walk_comment_group_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    comment_group: &mut CommentGroup,
+) {
+    for comment in &mut comment_group.comments {
+        visitor.visit_comment_mut(comment);
+    }
+}
+
+pub fn walk_constant_entry_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    constant_entry: &mut ConstantEntry,
+) {
+    visitor.visit_simple_identifier_mut(&mut constant_entry.name);
+    visitor.visit_expression_mut(&mut constant_entry.value);
+}
+
+pub fn walk_constant_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    constant_statement: &mut ConstantStatement,
+) {
+    visitor.visit_comment_group_mut(&mut constant_statement.comments);
+    for constant_entry in &mut constant_statement.entries {
+        visitor.visit_constant_entry_mut(constant_entry);
+    }
+}
+
+pub fn walk_classish_constant_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    classish_constant: &mut ClassishConstant,
+) {
+    visitor.visit_comment_group_mut(&mut classish_constant.comments);
+    for attribute_group in &mut classish_constant.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_constant_modifier_group_mut(&mut classish_constant.modifiers);
+    if let Some(ty) = &mut classish_constant.data_type {
+        visitor.visit_type_mut(ty);
+    }
+    for constant_entry in &mut classish_constant.entries {
+        visitor.visit_constant_entry_mut(constant_entry);
+    }
+}
+
+pub fn walk_if_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    if_statement: &mut IfStatement,
+) {
+    visitor.visit_expression_mut(&mut if_statement.condition);
+    visitor.visit_if_statement_body_mut(&mut if_statement.body);
+}
+
+pub fn walk_if_statement_body_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    if_statement_body: &mut IfStatementBody,
+) {
+    match if_statement_body {
+        IfStatementBody::Statement {
+            statement,
+            elseifs,
+            r#else,
+        } => {
+            visitor.visit_statement_mut(statement);
+            for if_statement_else_if in elseifs {
+                visitor.visit_if_statement_else_if_mut(if_statement_else_if);
+            }
+            if let Some(r#else) = r#else {
+                visitor.visit_if_statement_else_mut(r#else);
+            }
+        }
+        IfStatementBody::Block {
+            statements,
+            elseifs,
+            r#else,
+            ..
+        } => {
+            visitor.visit_block_mut(statements);
+            for if_statement_else_if_block in elseifs {
+                visitor.visit_if_statement_else_if_block_mut(if_statement_else_if_block);
+            }
+            if let Some(r#else) = r#else {
+                visitor.visit_if_statement_else_block_mut(r#else);
+            }
+        }
+    }
+}
+
+pub fn walk_if_statement_else_if_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    if_statement_else_if: &mut IfStatementElseIf,
+) {
+    visitor.visit_expression_mut(&mut if_statement_else_if.condition);
+    visitor.visit_statement_mut(&mut if_statement_else_if.statement);
+}
+
+pub fn walk_if_statement_else_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    if_statement_else: &mut IfStatementElse,
+) {
+    visitor.visit_statement_mut(&mut if_statement_else.statement);
+}
+
+pub fn walk_if_statement_else_if_block_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    if_statement_else_if_block: &mut IfStatementElseIfBlock,
+) {
+    visitor.visit_expression_mut(&mut if_statement_else_if_block.condition);
+    visitor.visit_block_mut(&mut if_statement_else_if_block.statements);
+}
+
+pub fn walk_if_statement_else_block_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    if_statement_else_block: &mut IfStatementElseBlock,
+) {
+    visitor.visit_block_mut(&mut if_statement_else_block.statements);
+}
+
+pub fn walk_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, ty: &mut Type) {
+    match ty {
+        Type::Nullable(_, inner) => visitor.visit_type_mut(inner),
+        Type::Union(inner) | Type::Intersection(inner) => {
+            for ty in inner {
+                visitor.visit_type_mut(ty);
+            }
+        }
+        Type::Named(..)
+        | Type::Void(_)
+        | Type::Null(_)
+        | Type::True(_)
+        | Type::False(_)
+        | Type::Never(_)
+        | Type::Float(_)
+        | Type::Boolean(_)
+        | Type::Integer(_)
+        | Type::String(_)
+        | Type::Array(_)
+        | Type::Object(_)
+        | Type::Mixed(_)
+        | Type::Callable(_)
+        | Type::Iterable(_)
+        | Type::StaticReference(_)
+        | Type::SelfReference(_)
+        | Type::ParentReference(_) => {}
+    }
+}
+
+pub fn walk_declare_entry_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    declare_entry: &mut DeclareEntry,
+) {
+    visitor.visit_simple_identifier_mut(&mut declare_entry.key);
+    visitor.visit_literal_mut(&mut declare_entry.value);
+}
+
+pub fn walk_declare_entry_group_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    declare_entry_group: &mut DeclareEntryGroup,
+) {
+    for declare_entry in &mut declare_entry_group.entries {
+        visitor.visit_declare_entry_mut(declare_entry);
+    }
+}
+
+pub fn walk_declare_body_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    declare_body: &mut DeclareBody,
+) {
+    match declare_body {
+        DeclareBody::Braced { statements, .. } | DeclareBody::Block { statements, .. } => {
+            visitor.visit_block_mut(statements);
+        }
+        DeclareBody::Expression { expression, .. } => visitor.visit_expression_mut(expression),
+        DeclareBody::Noop { .. } => {}
+    }
+}
+
+pub fn walk_declare_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    declare_statement: &mut DeclareStatement,
+) {
+    visitor.visit_declare_entry_group_mut(&mut declare_statement.entries);
+    visitor.visit_declare_body_mut(&mut declare_statement.body);
+}
+
+pub fn walk_unit_enum_case_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    unit_enum_case: &mut UnitEnumCase,
+) {
+    visitor.visit_comment_group_mut(&mut unit_enum_case.comments);
+    for attribute_group in &mut unit_enum_case.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_simple_identifier_mut(&mut unit_enum_case.name);
+}
+
+pub fn walk_unit_enum_member_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    unit_enum_member: &mut UnitEnumMember,
+) {
+    match unit_enum_member {
+        UnitEnumMember::Case(unit_enum_case) => visitor.visit_unit_enum_case_mut(unit_enum_case),
+        UnitEnumMember::Method(concrete_method) => {
+            visitor.visit_concrete_method_mut(concrete_method)
+        }
+        UnitEnumMember::Constant(classish_constant) => {
+            visitor.visit_classish_constant_mut(classish_constant)
+        }
+    }
+}
+
+pub fn walk_unit_enum_body_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    unit_enum_body: &mut UnitEnumBody,
+) {
+    for unit_enum_member in &mut unit_enum_body.members {
+        visitor.visit_unit_enum_member_mut(unit_enum_member);
+    }
+}
+
+pub fn walk_unit_enum_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    unit_enum_statement: &mut UnitEnumStatement,
+) {
+    visitor.visit_comment_group_mut(&mut unit_enum_statement.comments);
+    for attribute_group in &mut unit_enum_statement.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_simple_identifier_mut(&mut unit_enum_statement.name);
+    for simple_identifier in &mut unit_enum_statement.implements {
+        visitor.visit_simple_identifier_mut(simple_identifier);
+    }
+    visitor.visit_unit_enum_body_mut(&mut unit_enum_statement.body);
+}
+
+pub fn walk_backed_enum_case_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    backed_enum_case: &mut BackedEnumCase,
+) {
+    visitor.visit_comment_group_mut(&mut backed_enum_case.comments);
+    for attribute_group in &mut backed_enum_case.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_simple_identifier_mut(&mut backed_enum_case.name);
+    visitor.visit_expression_mut(&mut backed_enum_case.value);
+}
+
+pub fn walk_backed_enum_member_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    backed_enum_member: &mut BackedEnumMember,
+) {
+    match backed_enum_member {
+        BackedEnumMember::Case(backed_enum_case) => {
+            visitor.visit_backed_enum_case_mut(backed_enum_case)
+        }
+        BackedEnumMember::Method(concrete_method) => {
+            visitor.visit_concrete_method_mut(concrete_method)
+        }
+        BackedEnumMember::Constant(classish_constant) => {
+            visitor.visit_classish_constant_mut(classish_constant)
+        }
+    }
+}
+
+pub fn walk_backed_enum_body_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    backed_enum_body: &mut BackedEnumBody,
+) {
+    for backed_enum_member in &mut backed_enum_body.members {
+        visitor.visit_backed_enum_member_mut(backed_enum_member);
+    }
+}
+
+pub fn walk_backed_enum_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    backed_enum_statement: &mut BackedEnumStatement,
+) {
+    visitor.visit_comment_group_mut(&mut backed_enum_statement.comments);
+    for attribute_group in &mut backed_enum_statement.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_simple_identifier_mut(&mut backed_enum_statement.name);
+    visitor.visit_backed_enum_type_mut(&mut backed_enum_statement.backed_type);
+    for simple_identifier in &mut backed_enum_statement.implements {
+        visitor.visit_simple_identifier_mut(simple_identifier);
+    }
+    visitor.visit_backed_enum_body_mut(&mut backed_enum_statement.body);
+}
+
+pub fn walk_return_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, return_type: &mut ReturnType) {
+    visitor.visit_type_mut(&mut return_type.data_type);
+}
+
+pub fn walk_function_parameter_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    function_parameter: &mut FunctionParameter,
+) {
+    visitor.visit_comment_group_mut(&mut function_parameter.comments);
+    for attribute_group in &mut function_parameter.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    if let Some(data_type) = &mut function_parameter.data_type {
+        visitor.visit_type_mut(data_type);
+    }
+    visitor.visit_simple_variable_mut(&mut function_parameter.name);
+    if let Some(default) = &mut function_parameter.default {
+        visitor.visit_expression_mut(default);
+    }
+}
+
+pub fn walk_function_parameter_list_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    function_parameter_list: &mut FunctionParameterList,
+) {
+    visitor.visit_comment_group_mut(&mut function_parameter_list.comments);
+    for function_parameter in &mut function_parameter_list.parameters.inner {
+        visitor.visit_function_parameter_mut(function_parameter);
+    }
+}
+
+pub fn walk_function_body_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    function_body: &mut FunctionBody,
+) {
+    visitor.visit_comment_group_mut(&mut function_body.comments);
+    visitor.visit_block_mut(&mut function_body.statements);
+}
+
+pub fn walk_function_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    function_statement: &mut FunctionStatement,
+) {
+    visitor.visit_comment_group_mut(&mut function_statement.comments);
+    for attribute_group in &mut function_statement.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_simple_identifier_mut(&mut function_statement.name);
+    visitor.visit_function_parameter_list_mut(&mut function_statement.parameters);
+    if let Some(return_type) = &mut function_statement.return_type {
+        visitor.visit_return_type_mut(return_type);
+    }
+    visitor.visit_function_body_mut(&mut function_statement.body);
+}
+
+pub fn walk_closure_use_variable_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    closure_use_variable: &mut ClosureUseVariable,
+) {
+    visitor.visit_comment_group_mut(&mut closure_use_variable.comments);
+    visitor.visit_simple_variable_mut(&mut closure_use_variable.variable);
+}
+
+pub fn walk_closure_use_mut<V: VisitorMut + ?Sized>(visitor: &mut V, closure_use: &mut ClosureUse) {
+    visitor.visit_comment_group_mut(&mut closure_use.comments);
+    for closure_use_variable in &mut closure_use.variables.inner {
+        visitor.visit_closure_use_variable_mut(closure_use_variable);
+    }
+}
+
+pub fn walk_exit_expression_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    exit_expression: &mut ExitExpression,
+) {
+    if let Some(arguments) = &mut exit_expression.arguments {
+        visitor.visit_argument_list_mut(arguments);
+    }
+}
+
+pub fn walk_closure_mut<V: VisitorMut + ?Sized>(visitor: &mut V, closure: &mut Closure) {
+    visitor.visit_comment_group_mut(&mut closure.comments);
+    for attribute_group in &mut closure.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_function_parameter_list_mut(&mut closure.parameters);
+    if let Some(uses) = &mut closure.uses {
+        visitor.visit_closure_use_mut(uses);
+    }
+    if let Some(return_type) = &mut closure.return_type {
+        visitor.visit_return_type_mut(return_type);
+    }
+    visitor.visit_function_body_mut(&mut closure.body);
+}
+
+pub fn walk_arrow_function_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    arrow_function: &mut ArrowFunction,
+) {
+    visitor.visit_comment_group_mut(&mut arrow_function.comments);
+    for attribute_group in &mut arrow_function.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_function_parameter_list_mut(&mut arrow_function.parameters);
+    if let Some(return_type) = &mut arrow_function.return_type {
+        visitor.visit_return_type_mut(return_type);
+    }
+    visitor.visit_expression_mut(&mut arrow_function.body);
+}
+
+pub fn walk_constructor_parameter_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    constructor_parameter: &mut ConstructorParameter,
+) {
+    visitor.visit_comment_group_mut(&mut constructor_parameter.comments);
+    for attribute_group in &mut constructor_parameter.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_promoted_property_modifier_group_mut(&mut constructor_parameter.modifiers);
+    if let Some(data_type) = &mut constructor_parameter.data_type {
+        visitor.visit_type_mut(data_type);
+    }
+    visitor.visit_simple_variable_mut(&mut constructor_parameter.name);
+    if let Some(default) = &mut constructor_parameter.default {
+        visitor.visit_expression_mut(default);
+    }
+}
+
+pub fn walk_constructor_parameter_list_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    constructor_parameter_list: &mut ConstructorParameterList,
+) {
+    visitor.visit_comment_group_mut(&mut constructor_parameter_list.comments);
+    for constructor_parameter in &mut constructor_parameter_list.parameters.inner {
+        visitor.visit_constructor_parameter_mut(constructor_parameter);
+    }
+}
+
+pub fn walk_abstract_constructor_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    abstract_constructor: &mut AbstractConstructor,
+) {
+    visitor.visit_comment_group_mut(&mut abstract_constructor.comments);
+    for attribute_group in &mut abstract_constructor.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_method_modifier_group_mut(&mut abstract_constructor.modifiers);
+    visitor.visit_simple_identifier_mut(&mut abstract_constructor.name);
+    visitor.visit_function_parameter_list_mut(&mut abstract_constructor.parameters);
+}
+
+pub fn walk_concrete_constructor_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    concrete_constructor: &mut ConcreteConstructor,
+) {
+    visitor.visit_comment_group_mut(&mut concrete_constructor.comments);
+    for attribute_group in &mut concrete_constructor.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_method_modifier_group_mut(&mut concrete_constructor.modifiers);
+    visitor.visit_simple_identifier_mut(&mut concrete_constructor.name);
+    visitor.visit_constructor_parameter_list_mut(&mut concrete_constructor.parameters);
+    visitor.visit_method_body_mut(&mut concrete_constructor.body);
+}
+
+pub fn walk_abstract_method_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    abstract_method: &mut AbstractMethod,
+) {
+    visitor.visit_comment_group_mut(&mut abstract_method.comments);
+    for attribute_group in &mut abstract_method.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_method_modifier_group_mut(&mut abstract_method.modifiers);
+    visitor.visit_simple_identifier_mut(&mut abstract_method.name);
+    visitor.visit_function_parameter_list_mut(&mut abstract_method.parameters);
+    if let Some(return_type) = &mut abstract_method.return_type {
+        visitor.visit_return_type_mut(return_type);
+    }
+}
+
+pub fn walk_concrete_method_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    concrete_method: &mut ConcreteMethod,
+) {
+    visitor.visit_comment_group_mut(&mut concrete_method.comments);
+    for attribute_group in &mut concrete_method.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_method_modifier_group_mut(&mut concrete_method.modifiers);
+    visitor.visit_simple_identifier_mut(&mut concrete_method.name);
+    visitor.visit_function_parameter_list_mut(&mut concrete_method.parameters);
+    if let Some(return_type) = &mut concrete_method.return_type {
+        visitor.visit_return_type_mut(return_type);
+    }
+    visitor.visit_method_body_mut(&mut concrete_method.body);
+}
+
+pub fn walk_method_body_mut<V: VisitorMut + ?Sized>(visitor: &mut V, method_body: &mut MethodBody) {
+    visitor.visit_comment_group_mut(&mut method_body.comments);
+    visitor.visit_block_mut(&mut method_body.statements);
+}
+
+pub fn walk_label_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    label_statement: &mut LabelStatement,
+) {
+    visitor.visit_comment_group_mut(&mut label_statement.comments);
+    visitor.visit_simple_identifier_mut(&mut label_statement.label);
+}
+
+pub fn walk_goto_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    goto_statement: &mut GotoStatement,
+) {
+    visitor.visit_comment_group_mut(&mut goto_statement.comments);
+    visitor.visit_simple_identifier_mut(&mut goto_statement.label);
+}
+
+pub fn walk_identifier_mut<V: VisitorMut + ?Sized>(visitor: &mut V, identifier: &mut Identifier) {
+    match identifier {
+        Identifier::SimpleIdentifier(simple_identifier) => {
+            visitor.visit_simple_identifier_mut(simple_identifier)
+        }
+        Identifier::DynamicIdentifier(dynamic_identifier) => {
+            visitor.visit_dynamic_identifier_mut(dynamic_identifier)
+        }
+    }
+}
+
+pub fn walk_dynamic_identifier_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    dynamic_identifier: &mut DynamicIdentifier,
+) {
+    visitor.visit_expression_mut(&mut dynamic_identifier.expr);
+}
+
+pub fn walk_interface_member_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    interface_member: &mut InterfaceMember,
+) {
+    match interface_member {
+        InterfaceMember::Constant(classish_constant) => {
+            visitor.visit_classish_constant_mut(classish_constant)
+        }
+        InterfaceMember::Constructor(abstract_constructor) => {
+            visitor.visit_abstract_constructor_mut(abstract_constructor)
+        }
+        InterfaceMember::Method(abstract_method) => {
+            visitor.visit_abstract_method_mut(abstract_method)
+        }
+        InterfaceMember::Property(property) => visitor.visit_property_mut(property),
+    }
+}
+
+pub fn walk_interface_extends_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    interface_extends: &mut InterfaceExtends,
+) {
+    for simple_identifier in &mut interface_extends.parents.inner {
+        visitor.visit_simple_identifier_mut(simple_identifier);
+    }
+}
+
+pub fn walk_interface_body_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    interface_body: &mut InterfaceBody,
+) {
+    for interface_member in &mut interface_body.members {
+        visitor.visit_interface_member_mut(interface_member);
+    }
+}
+
+pub fn walk_interface_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    interface_statement: &mut InterfaceStatement,
+) {
+    visitor.visit_comment_group_mut(&mut interface_statement.comments);
+    for attribute_group in &mut interface_statement.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_simple_identifier_mut(&mut interface_statement.name);
+    if let Some(extends) = &mut interface_statement.extends {
+        visitor.visit_interface_extends_mut(extends);
+    }
+    visitor.visit_interface_body_mut(&mut interface_statement.body);
+}
+
+pub fn walk_literal_mut<V: VisitorMut + ?Sized>(visitor: &mut V, literal: &mut Literal) {
+    match literal {
+        Literal::String(literal_string) => visitor.visit_literal_string_mut(literal_string),
+        Literal::Integer(literal_integer) => visitor.visit_literal_integer_mut(literal_integer),
+        Literal::Float(literal_float) => visitor.visit_literal_float_mut(literal_float),
+    }
+}
+
+pub fn walk_foreach_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    foreach_statement: &mut ForeachStatement,
+) {
+    visitor.visit_foreach_statement_iterator_mut(&mut foreach_statement.iterator);
+    visitor.visit_foreach_statement_body_mut(&mut foreach_statement.body);
+}
+
+pub fn walk_foreach_statement_iterator_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    foreach_statement_iterator: &mut ForeachStatementIterator,
+) {
+    match foreach_statement_iterator {
+        ForeachStatementIterator::Value {
+            expression, value, ..
+        } => {
+            visitor.visit_expression_mut(expression);
+            visitor.visit_expression_mut(value);
+        }
+        ForeachStatementIterator::KeyAndValue {
+            expression,
+            key,
+            value,
+            ..
+        } => {
+            visitor.visit_expression_mut(expression);
+            visitor.visit_expression_mut(key);
+            visitor.visit_expression_mut(value);
+        }
+    }
+}
+
+pub fn walk_foreach_statement_body_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    foreach_statement_body: &mut ForeachStatementBody,
+) {
+    match foreach_statement_body {
+        ForeachStatementBody::Statement(statement) => visitor.visit_statement_mut(statement),
+        ForeachStatementBody::Block { statements, .. } => {
+            visitor.visit_block_mut(statements);
+        }
+    }
+}
+
+pub fn walk_for_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    for_statement: &mut ForStatement,
+) {
+    visitor.visit_for_statement_iterator_mut(&mut for_statement.iterator);
+    visitor.visit_for_statement_body_mut(&mut for_statement.body);
+}
+
+pub fn walk_for_statement_iterator_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    for_statement_iterator: &mut ForStatementIterator,
+) {
+    for expression in &mut for_statement_iterator.initializations.inner {
+        visitor.visit_expression_mut(expression);
+    }
+    for expression in &mut for_statement_iterator.conditions.inner {
+        visitor.visit_expression_mut(expression);
+    }
+    for expression in &mut for_statement_iterator.r#loop.inner {
+        visitor.visit_expression_mut(expression);
+    }
+}
+
+pub fn walk_for_statement_body_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    for_statement_body: &mut ForStatementBody,
+) {
+    match for_statement_body {
+        ForStatementBody::Statement(statement) => visitor.visit_statement_mut(statement),
+        ForStatementBody::Block { statements, .. } => {
+            visitor.visit_block_mut(statements);
+        }
+    }
+}
+
+pub fn walk_do_while_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    do_while_statement: &mut DoWhileStatement,
+) {
+    visitor.visit_statement_mut(&mut do_while_statement.body);
+    visitor.visit_expression_mut(&mut do_while_statement.condition);
+}
+
+pub fn walk_while_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    while_statement: &mut WhileStatement,
+) {
+    visitor.visit_expression_mut(&mut while_statement.condition);
+    visitor.visit_while_statement_body_mut(&mut while_statement.body);
+}
+
+pub fn walk_while_statement_body_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    while_statement_body: &mut WhileStatementBody,
+) {
+    match while_statement_body {
+        WhileStatementBody::Statement(statement) => visitor.visit_statement_mut(statement),
+        WhileStatementBody::Block { statements, .. } => {
+            visitor.visit_block_mut(statements);
+        }
+    }
+}
+
+pub fn walk_level_mut<V: VisitorMut + ?Sized>(visitor: &mut V, level: &mut Level) {
+    match level {
+        Level::Literal(literal_integer) => visitor.visit_literal_integer_mut(literal_integer),
+        Level::Parenthesized { level, .. } => visitor.visit_level_mut(level),
+    }
+}
+
+pub fn walk_break_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    break_statement: &mut BreakStatement,
+) {
+    if let Some(level) = &mut break_statement.level {
+        visitor.visit_level_mut(level);
+    }
+}
+
+pub fn walk_continue_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    continue_statement: &mut ContinueStatement,
+) {
+    if let Some(level) = &mut continue_statement.level {
+        visitor.visit_level_mut(level);
+    }
+}
+
+pub fn walk_static_var_mut<V: VisitorMut + ?Sized>(visitor: &mut V, static_var: &mut StaticVar) {
+    visitor.visit_variable_mut(&mut static_var.var);
+    if let Some(default) = &mut static_var.default {
+        visitor.visit_expression_mut(default);
+    }
+}
+
+pub fn walk_static_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    static_statement: &mut StaticStatement,
+) {
+    for static_var in &mut static_statement.vars {
+        visitor.visit_static_var_mut(static_var);
+    }
+}
+
+pub fn walk_switch_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    switch_statement: &mut SwitchStatement,
+) {
+    visitor.visit_expression_mut(&mut switch_statement.condition);
+    for case in &mut switch_statement.cases {
+        visitor.visit_case_mut(case);
+    }
+}
+
+pub fn walk_echo_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    echo_statement: &mut EchoStatement,
+) {
+    for expression in &mut echo_statement.values {
+        visitor.visit_expression_mut(expression);
+    }
+}
+
+pub fn walk_return_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    return_statement: &mut ReturnStatement,
+) {
+    if let Some(value) = &mut return_statement.value {
+        visitor.visit_expression_mut(value);
+    }
+}
+
+pub fn walk_use_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    use_statement: &mut UseStatement,
+) {
+    for r#use in &mut use_statement.uses {
+        visitor.visit_use_mut(r#use);
+    }
+}
+
+pub fn walk_group_use_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    group_use_statement: &mut GroupUseStatement,
+) {
+    visitor.visit_simple_identifier_mut(&mut group_use_statement.prefix);
+    for r#use in &mut group_use_statement.uses {
+        visitor.visit_use_mut(r#use);
+    }
+}
+
+pub fn walk_expression_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    expression_statement: &mut ExpressionStatement,
+) {
+    visitor.visit_expression_mut(&mut expression_statement.expression);
+}
+
+pub fn walk_global_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    global_statement: &mut GlobalStatement,
+) {
+    for variable in &mut global_statement.variables {
+        visitor.visit_variable_mut(variable);
+    }
+}
+
+pub fn walk_block_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    block_statement: &mut BlockStatement,
+) {
+    visitor.visit_block_mut(&mut block_statement.statements);
+}
+
+pub fn walk_case_mut<V: VisitorMut + ?Sized>(visitor: &mut V, case: &mut Case) {
+    if let Some(condition) = &mut case.condition {
+        visitor.visit_expression_mut(condition);
+    }
+    visitor.visit_block_mut(&mut case.body);
+}
+
+pub fn walk_use_mut<V: VisitorMut + ?Sized>(visitor: &mut V, r#use: &mut Use) {
+    visitor.visit_simple_identifier_mut(&mut r#use.name);
+    if let Some(alias) = &mut r#use.alias {
+        visitor.visit_simple_identifier_mut(alias);
+    }
+}
+
+pub fn walk_default_match_arm_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    default_match_arm: &mut DefaultMatchArm,
+) {
+    visitor.visit_expression_mut(&mut default_match_arm.body);
+}
+
+pub fn walk_match_arm_mut<V: VisitorMut + ?Sized>(visitor: &mut V, match_arm: &mut MatchArm) {
+    for expression in &mut match_arm.conditions {
+        visitor.visit_expression_mut(expression);
+    }
+    visitor.visit_expression_mut(&mut match_arm.body);
+}
+
+pub fn walk_string_part_mut<V: VisitorMut + ?Sized>(visitor: &mut V, string_part: &mut StringPart) {
+    match string_part {
+        StringPart::Literal(literal_string_part) => {
+            visitor.visit_literal_string_part_mut(literal_string_part)
+        }
+        StringPart::Expression(expression_string_part) => {
+            visitor.visit_expression_string_part_mut(expression_string_part)
+        }
+    }
+}
+
+pub fn walk_expression_string_part_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    expression_string_part: &mut ExpressionStringPart,
+) {
+    visitor.visit_expression_mut(&mut expression_string_part.expression);
+}
+
+pub fn walk_array_item_mut<V: VisitorMut + ?Sized>(visitor: &mut V, array_item: &mut ArrayItem) {
+    match array_item {
+        ArrayItem::Value { value }
+        | ArrayItem::ReferencedValue { value, .. }
+        | ArrayItem::SpreadValue { value, .. } => visitor.visit_expression_mut(value),
+        ArrayItem::KeyValue { key, value, .. }
+        | ArrayItem::ReferencedKeyValue { key, value, .. } => {
+            visitor.visit_expression_mut(key);
+            visitor.visit_expression_mut(value);
+        }
+        ArrayItem::Skipped => {}
+    }
+}
+
+pub fn walk_list_entry_mut<V: VisitorMut + ?Sized>(visitor: &mut V, list_entry: &mut ListEntry) {
+    match list_entry {
+        ListEntry::Value { value } => visitor.visit_expression_mut(value),
+        ListEntry::KeyValue { key, value, .. } => {
+            visitor.visit_expression_mut(key);
+            visitor.visit_expression_mut(value);
+        }
+        ListEntry::Skipped => {}
+    }
+}
+
+pub fn walk_promoted_property_modifier_group_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    promoted_property_modifier_group: &mut PromotedPropertyModifierGroup,
+) {
+    for promoted_property_modifier in &mut promoted_property_modifier_group.modifiers {
+        visitor.visit_promoted_property_modifier_mut(promoted_property_modifier);
+    }
+}
+
+pub fn walk_property_modifier_group_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    property_modifier_group: &mut PropertyModifierGroup,
+) {
+    for property_modifier in &mut property_modifier_group.modifiers {
+        visitor.visit_property_modifier_mut(property_modifier);
+    }
+}
+
+pub fn walk_method_modifier_group_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    method_modifier_group: &mut MethodModifierGroup,
+) {
+    for method_modifier in &mut method_modifier_group.modifiers {
+        visitor.visit_method_modifier_mut(method_modifier);
+    }
+}
+
+pub fn walk_class_modifier_group_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    class_modifier_group: &mut ClassModifierGroup,
+) {
+    for class_modifier in &mut class_modifier_group.modifiers {
+        visitor.visit_class_modifier_mut(class_modifier);
+    }
+}
+
+pub fn walk_constant_modifier_group_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    constant_modifier_group: &mut ConstantModifierGroup,
+) {
+    for constant_modifier in &mut constant_modifier_group.modifiers {
+        visitor.visit_constant_modifier_mut(constant_modifier);
+    }
+}
+
+pub fn walk_unbraced_namespace_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    unbraced_namespace: &mut UnbracedNamespace,
+) {
+    visitor.visit_simple_identifier_mut(&mut unbraced_namespace.name);
+    visitor.visit_block_mut(&mut unbraced_namespace.statements);
+}
+
+pub fn walk_braced_namespace_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    braced_namespace: &mut BracedNamespace,
+) {
+    if let Some(name) = &mut braced_namespace.name {
+        visitor.visit_simple_identifier_mut(name);
+    }
+    visitor.visit_braced_namespace_body_mut(&mut braced_namespace.body);
+}
+
+pub fn walk_braced_namespace_body_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    braced_namespace_body: &mut BracedNamespaceBody,
+) {
+    visitor.visit_block_mut(&mut braced_namespace_body.statements);
+}
+
+pub fn walk_namespace_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    namespace_statement: &mut NamespaceStatement,
+) {
+    match namespace_statement {
+        NamespaceStatement::Unbraced(unbraced_namespace) => {
+            visitor.visit_unbraced_namespace_mut(unbraced_namespace)
+        }
+        NamespaceStatement::Braced(braced_namespace) => {
+            visitor.visit_braced_namespace_mut(braced_namespace)
+        }
+    }
+}
+
+pub fn walk_arithmetic_operation_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    arithmetic_operation: &mut ArithmeticOperation,
+) {
+    match arithmetic_operation {
+        ArithmeticOperation::Addition { left, right, .. }
+        | ArithmeticOperation::Subtraction { left, right, .. }
+        | ArithmeticOperation::Multiplication { left, right, .. }
+        | ArithmeticOperation::Division { left, right, .. }
+        | ArithmeticOperation::Modulo { left, right, .. }
+        | ArithmeticOperation::Exponentiation { left, right, .. } => {
+            visitor.visit_expression_mut(left);
+            visitor.visit_expression_mut(right);
+        }
+        ArithmeticOperation::Negative { right, .. }
+        | ArithmeticOperation::Positive { right, .. }
+        | ArithmeticOperation::PreIncrement { right, .. }
+        | ArithmeticOperation::PreDecrement { right, .. } => visitor.visit_expression_mut(right),
+        ArithmeticOperation::PostIncrement { left, .. }
+        | ArithmeticOperation::PostDecrement { left, .. } => visitor.visit_expression_mut(left),
+    }
+}
+
+pub fn walk_assignment_operation_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    assignment_operation: &mut AssignmentOperation,
+) {
+    match assignment_operation {
+        AssignmentOperation::Assign { left, right, .. }
+        | AssignmentOperation::AssignByReference { left, right, .. }
+        | AssignmentOperation::Addition { left, right, .. }
+        | AssignmentOperation::Subtraction { left, right, .. }
+        | AssignmentOperation::Multiplication { left, right, .. }
+        | AssignmentOperation::Division { left, right, .. }
+        | AssignmentOperation::Modulo { left, right, .. }
+        | AssignmentOperation::Exponentiation { left, right, .. }
+        | AssignmentOperation::Concat { left, right, .. }
+        | AssignmentOperation::BitwiseAnd { left, right, .. }
+        | AssignmentOperation::BitwiseOr { left, right, .. }
+        | AssignmentOperation::BitwiseXor { left, right, .. }
+        | AssignmentOperation::LeftShift { left, right, .. }
+        | AssignmentOperation::RightShift { left, right, .. }
+        | AssignmentOperation::Coalesce { left, right, .. } => {
+            visitor.visit_expression_mut(left);
+            visitor.visit_expression_mut(right);
+        }
+    }
+}
+
+pub fn walk_bitwise_operation_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    bitwise_operation: &mut BitwiseOperation,
+) {
+    match bitwise_operation {
+        BitwiseOperation::And { left, right, .. }
+        | BitwiseOperation::Or { left, right, .. }
+        | BitwiseOperation::Xor { left, right, .. }
+        | BitwiseOperation::LeftShift { left, right, .. }
+        | BitwiseOperation::RightShift { left, right, .. } => {
+            visitor.visit_expression_mut(left);
+            visitor.visit_expression_mut(right);
+        }
+        BitwiseOperation::Not { right, .. } => visitor.visit_expression_mut(right),
+    }
+}
+
+pub fn walk_comparison_operation_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    comparison_operation: &mut ComparisonOperation,
+) {
+    match comparison_operation {
+        ComparisonOperation::Equal { left, right, .. }
+        | ComparisonOperation::Identical { left, right, .. }
+        | ComparisonOperation::NotEqual { left, right, .. }
+        | ComparisonOperation::AngledNotEqual { left, right, .. }
+        | ComparisonOperation::NotIdentical { left, right, .. }
+        | ComparisonOperation::LessThan { left, right, .. }
+        | ComparisonOperation::GreaterThan { left, right, .. }
+        | ComparisonOperation::LessThanOrEqual { left, right, .. }
+        | ComparisonOperation::GreaterThanOrEqual { left, right, .. }
+        | ComparisonOperation::Spaceship { left, right, .. } => {
+            visitor.visit_expression_mut(left);
+            visitor.visit_expression_mut(right);
+        }
+    }
+}
+
+pub fn walk_logical_operation_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    logical_operation: &mut LogicalOperation,
+) {
+    match logical_operation {
+        LogicalOperation::And { left, right, .. }
+        | LogicalOperation::Or { left, right, .. }
+        | LogicalOperation::LogicalAnd { left, right, .. }
+        | LogicalOperation::LogicalOr { left, right, .. }
+        | LogicalOperation::LogicalXor { left, right, .. } => {
+            visitor.visit_expression_mut(left);
+            visitor.visit_expression_mut(right);
+        }
+        LogicalOperation::Not { right, .. } => visitor.visit_expression_mut(right),
+    }
+}
+
+pub fn walk_property_mut<V: VisitorMut + ?Sized>(visitor: &mut V, property: &mut Property) {
+    visitor.visit_comment_group_mut(&mut property.comments);
+    for attribute_group in &mut property.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_property_modifier_group_mut(&mut property.modifiers);
+    if let Some(ty) = &mut property.r#type {
+        visitor.visit_type_mut(ty);
+    }
+    for property_entry in &mut property.entries {
+        visitor.visit_property_entry_mut(property_entry);
+    }
+    if let Some(hooks) = &mut property.hooks {
+        for property_hook in &mut hooks.hooks {
+            visitor.visit_property_hook_mut(property_hook);
+        }
+    }
+}
+
+pub fn walk_variable_property_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    variable_property: &mut VariableProperty,
+) {
+    visitor.visit_comment_group_mut(&mut variable_property.comments);
+    for attribute_group in &mut variable_property.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    if let Some(ty) = &mut variable_property.r#type {
+        visitor.visit_type_mut(ty);
+    }
+    for property_entry in &mut variable_property.entries {
+        visitor.visit_property_entry_mut(property_entry);
+    }
+}
+
+pub fn walk_property_entry_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    property_entry: &mut PropertyEntry,
+) {
+    match property_entry {
+        PropertyEntry::Uninitialized { variable } => visitor.visit_simple_variable_mut(variable),
+        PropertyEntry::Initialized {
+            variable, value, ..
+        } => {
+            visitor.visit_simple_variable_mut(variable);
+            visitor.visit_expression_mut(value);
+        }
+    }
+}
+
+pub fn walk_property_hook_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    property_hook: &mut PropertyHook,
+) {
+    visitor.visit_comment_group_mut(&mut property_hook.comments);
+    for attribute_group in &mut property_hook.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_simple_identifier_mut(&mut property_hook.name);
+    if let Some(parameters) = &mut property_hook.parameters {
+        visitor.visit_function_parameter_list_mut(parameters);
+    }
+    match &mut property_hook.body {
+        PropertyHookBody::Abstract { .. } => {}
+        PropertyHookBody::Expression { expression, .. } => visitor.visit_expression_mut(expression),
+        PropertyHookBody::Block(body) => visitor.visit_function_body_mut(body),
+    }
+}
+
+pub fn walk_trait_member_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    trait_member: &mut TraitMember,
+) {
+    match trait_member {
+        TraitMember::Constant(classish_constant) => {
+            visitor.visit_classish_constant_mut(classish_constant)
+        }
+        TraitMember::TraitUsage(trait_usage) => visitor.visit_trait_usage_mut(trait_usage),
+        TraitMember::Property(property) => visitor.visit_property_mut(property),
+        TraitMember::VariableProperty(variable_property) => {
+            visitor.visit_variable_property_mut(variable_property)
+        }
+        TraitMember::AbstractMethod(abstract_method) => {
+            visitor.visit_abstract_method_mut(abstract_method)
+        }
+        TraitMember::AbstractConstructor(abstract_constructor) => {
+            visitor.visit_abstract_constructor_mut(abstract_constructor)
+        }
+        TraitMember::ConcreteMethod(concrete_method) => {
+            visitor.visit_concrete_method_mut(concrete_method)
+        }
+        TraitMember::ConcreteConstructor(concrete_constructor) => {
+            visitor.visit_concrete_constructor_mut(concrete_constructor)
+        }
+    }
+}
+
+pub fn walk_trait_body_mut<V: VisitorMut + ?Sized>(visitor: &mut V, trait_body: &mut TraitBody) {
+    for trait_member in &mut trait_body.members {
+        visitor.visit_trait_member_mut(trait_member);
+    }
+}
+
+pub fn walk_trait_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    trait_statement: &mut TraitStatement,
+) {
+    visitor.visit_comment_group_mut(&mut trait_statement.comments);
+    for attribute_group in &mut trait_statement.attributes {
+        visitor.visit_attribute_group_mut(attribute_group);
+    }
+    visitor.visit_simple_identifier_mut(&mut trait_statement.name);
+    visitor.visit_trait_body_mut(&mut trait_statement.body);
+}
+
+pub fn walk_trait_usage_mut<V: VisitorMut + ?Sized>(visitor: &mut V, trait_usage: &mut TraitUsage) {
+    for simple_identifier in &mut trait_usage.traits {
+        visitor.visit_simple_identifier_mut(simple_identifier);
+    }
+    for trait_usage_adaptation in &mut trait_usage.adaptations {
+        visitor.visit_trait_usage_adaptation_mut(trait_usage_adaptation);
+    }
+}
+
+pub fn walk_trait_usage_adaptation_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    trait_usage_adaptation: &mut TraitUsageAdaptation,
+) {
+    match trait_usage_adaptation {
+        TraitUsageAdaptation::Alias {
+            r#trait,
+            method,
+            alias,
+            visibility,
+        } => {
+            if let Some(r#trait) = r#trait {
+                visitor.visit_simple_identifier_mut(r#trait);
+            }
+            visitor.visit_simple_identifier_mut(method);
+            visitor.visit_simple_identifier_mut(alias);
+            if let Some(visibility) = visibility {
+                visitor.visit_visibility_modifier_mut(visibility);
+            }
+        }
+        TraitUsageAdaptation::Visibility {
+            r#trait,
+            method,
+            visibility,
+        } => {
+            if let Some(r#trait) = r#trait {
+                visitor.visit_simple_identifier_mut(r#trait);
+            }
+            visitor.visit_simple_identifier_mut(method);
+            visitor.visit_visibility_modifier_mut(visibility);
+        }
+        TraitUsageAdaptation::Precedence {
+            r#trait,
+            method,
+            insteadof,
+        } => {
+            if let Some(r#trait) = r#trait {
+                visitor.visit_simple_identifier_mut(r#trait);
+            }
+            visitor.visit_simple_identifier_mut(method);
+            for simple_identifier in insteadof {
+                visitor.visit_simple_identifier_mut(simple_identifier);
+            }
+        }
+    }
+}
+
+pub fn walk_catch_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, catch_type: &mut CatchType) {
+    match catch_type {
+        CatchType::Identifier(simple_identifier) => {
+            visitor.visit_simple_identifier_mut(simple_identifier)
+        }
+        CatchType::Union(inner) => {
+            for simple_identifier in inner {
+                visitor.visit_simple_identifier_mut(simple_identifier);
+            }
+        }
+    }
+}
+
+pub fn walk_try_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    try_statement: &mut TryStatement,
+) {
+    visitor.visit_block_mut(&mut try_statement.body);
+    for catch_block in &mut try_statement.catches {
+        visitor.visit_catch_block_mut(catch_block);
+    }
+    if let Some(finally) = &mut try_statement.finally {
+        visitor.visit_finally_block_mut(finally);
+    }
+}
+
+pub fn walk_catch_block_mut<V: VisitorMut + ?Sized>(visitor: &mut V, catch_block: &mut CatchBlock) {
+    visitor.visit_catch_type_mut(&mut catch_block.types);
+    if let Some(var) = &mut catch_block.var {
+        visitor.visit_simple_variable_mut(var);
+    }
+    visitor.visit_block_mut(&mut catch_block.body);
+}
+
+pub fn walk_finally_block_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    finally_block: &mut FinallyBlock,
+) {
+    visitor.visit_block_mut(&mut finally_block.body);
+}
+
+pub fn walk_variable_mut<V: VisitorMut + ?Sized>(visitor: &mut V, variable: &mut Variable) {
+    match variable {
+        Variable::SimpleVariable(simple_variable) => {
+            visitor.visit_simple_variable_mut(simple_variable)
+        }
+        Variable::VariableVariable(variable_variable) => {
+            visitor.visit_variable_variable_mut(variable_variable)
+        }
+        Variable::BracedVariableVariable(braced_variable_variable) => {
+            visitor.visit_braced_variable_variable_mut(braced_variable_variable)
+        }
+    }
+}
+
+pub fn walk_variable_variable_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    variable_variable: &mut VariableVariable,
+) {
+    visitor.visit_variable_mut(&mut variable_variable.variable);
+}
+
+pub fn walk_braced_variable_variable_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    braced_variable_variable: &mut BracedVariableVariable,
+) {
+    visitor.visit_expression_mut(&mut braced_variable_variable.variable);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::walk_block_mut;
+    use super::walk_expression_mut;
+    use super::VisitorMut;
+    use crate::parser::ast::variables::SimpleVariable;
+    use crate::parser::ast::visitor::Visitor;
+    use crate::parser::ast::Block;
+    use crate::parser::ast::Expression;
+    use crate::parser::ast::Statement;
+
+    #[derive(Default)]
+    struct Variables(Vec<String>);
+
+    impl Visitor for Variables {
+        fn visit_simple_variable(&mut self, variable: &SimpleVariable) {
+            self.0.push(variable.name.to_string());
+        }
+    }
+
+    fn variables(program: &[Statement]) -> Vec<String> {
+        let mut variables = Variables::default();
+        variables.visit_program(program);
+
+        variables.0
+    }
+
+    #[test]
+    fn test_replace_nodes() {
+        struct Rewrite;
+
+        impl VisitorMut for Rewrite {
+            fn visit_expression_mut(&mut self, expression: &mut Expression) {
+                // `$a ?? $b` becomes `$b`.
+                if let Expression::Coalesce { rhs, .. } = expression {
+                    *expression = *rhs.clone();
+                }
+
+                walk_expression_mut(self, expression);
+            }
+
+            fn visit_simple_variable_mut(&mut self, variable: &mut SimpleVariable) {
+                variable.name = format!("{}_", variable.name).into();
+            }
+        }
+
+        let mut program =
+            crate::parse("<?php function a($b) { return $c ?? fn($d) => $e ?? $d; }").unwrap();
+        Rewrite.visit_program_mut(&mut program);
+
+        assert_eq!(variables(&program), vec!["$b_", "$d_", "$d_"]);
+    }
+
+    #[test]
+    fn test_remove_statements() {
+        struct RemoveEcho;
+
+        impl VisitorMut for RemoveEcho {
+            fn visit_block_mut(&mut self, block: &mut Block) {
+                block.retain(|statement| !matches!(statement, Statement::Echo(_)));
+
+                walk_block_mut(self, block);
+            }
+        }
+
+        let mut program = crate::parse(
+            "<?php echo $a; if ($b) { echo $c; $d(); } else { echo $e; } switch ($f) { case 1: echo $g; }",
+        )
+        .unwrap();
+        RemoveEcho.visit_program_mut(&mut program);
+
+        assert_eq!(variables(&program), vec!["$b", "$d", "$f"]);
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// A flag shared between a parse and its owner, used to cooperatively cancel the parse,
+/// optionally cancelled once a deadline is reached.
+///
+/// Cancellation is checked between top-level statements, and between files when scanning
+/// a project, so a statement which is already being parsed is always completed.
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// A token cancelled along with this one, and also once the given deadline is reached,
+    /// e.g. to bound the time spent parsing a single file.
+    pub fn with_deadline(&self, deadline: Instant) -> Self {
+        Self {
+            cancelled: self.cancelled.clone(),
+            deadline: Some(self.deadline.map_or(deadline, |own| own.min(deadline))),
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed) || self.is_expired()
+    }
+
+    /// Whether the deadline of the token is reached, see [`CancellationToken::with_deadline`].
+    pub fn is_expired(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+impl PartialEq for CancellationToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.cancelled, &other.cancelled) && self.deadline == other.deadline
+    }
+}
+
+impl Eq for CancellationToken {}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use std::time::Instant;
+
+    use super::CancellationToken;
+    use crate::ParserOptions;
+
+    #[test]
+    fn test_cancelled_parse() {
+        let cancellation = CancellationToken::new();
+        let options = ParserOptions::new().with_cancellation(cancellation.clone());
+        assert!(crate::parse_with_options("<?php echo 1;", &options).is_ok());
+
+        cancellation.clone().cancel();
+        let stack = crate::parse_with_options("<?php echo 1;", &options).unwrap_err();
+
+        assert!(stack.is_cancelled());
+        assert_eq!(stack.errors.len(), 1);
+        assert!(stack.partial.is_empty());
+        assert!(!crate::parse("<?php echo").unwrap_err().is_cancelled());
+    }
+
+    #[test]
+    fn test_deadline() {
+        let cancellation = CancellationToken::new();
+        let now = Instant::now();
+
+        let later = cancellation.with_deadline(now + Duration::from_secs(3600));
+        assert!(!later.is_cancelled());
+
+        let expired = cancellation.with_deadline(now);
+        assert!(expired.is_expired());
+        let options = ParserOptions::new().with_cancellation(expired);
+        assert!(crate::parse_with_options("<?php echo 1;", &options)
+            .unwrap_err()
+            .is_cancelled());
+
+        // The deadline doesn't cancel the tokens it was derived from.
+        assert!(!cancellation.is_cancelled());
+        cancellation.cancel();
+        assert!(later.is_cancelled());
+        assert!(!later.is_expired());
+    }
+}
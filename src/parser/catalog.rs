@@ -0,0 +1,478 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use crate::parser::error::ParseError;
+use crate::parser::error::ParseErrorStack;
+
+/// The English message templates, keyed by error code.
+///
+/// Codes with several messages use a `<code>.<variant>` key for their variants.
+/// Placeholders such as `{class}` are replaced by the error arguments of the same name.
+const ENGLISH: &[(&str, &str)] = &[
+    ("E001", "syntax error, {error}"),
+    ("E002", "unexpected {found}"),
+    ("E003", "unexpected {found}"),
+    ("E005", "unexpected {found}, expecting {expected}"),
+    (
+        "E006",
+        "unexpected identifier `{found}`, expecting `{expected}`",
+    ),
+    ("E007", "multiple `{modifier}` modifiers are not allowed"),
+    ("E008", "multiple visibility modifiers are not allowed"),
+    ("E009", "standalone type `{type}` cannot be nullable"),
+    ("E010", "standalone type `{type}` cannot be used in a union"),
+    (
+        "E011",
+        "standalone type `{type}` cannot be used in an intersection",
+    ),
+    ("E012", "cannot use `try` without `catch` or `finally`"),
+    (
+        "E013",
+        "promoted property `{class}::{property}` cannot declare variadic",
+    ),
+    (
+        "E014",
+        "missing type for readonly property `{class}::{property}`",
+    ),
+    (
+        "E015",
+        "cannot declare method `{class}::{method}` abstract, as `{name}` class is not abstract",
+    ),
+    ("E016", "cannot declare a constructor on enum `{enum}`"),
+    (
+        "E017",
+        "cannot declare magic method `{enum}::{method}` in an enum",
+    ),
+    (
+        "E018",
+        "case `{enum}::{case}` of backed enum `{name}` must have a value",
+    ),
+    (
+        "E019",
+        "case `{enum}::{case}` of unit enum `{name}` cannot have a value",
+    ),
+    ("E020", "cannot use '{modifier}' as constant modifier"),
+    (
+        "E021",
+        "cannot use '{modifier}' as an interface constant modifier",
+    ),
+    (
+        "E022",
+        "cannot use '{modifier}' as a promoted property modifier",
+    ),
+    ("E023", "cannot use '{modifier}' as a property modifier"),
+    ("E024", "cannot use '{modifier}' as a class modifier"),
+    ("E025", "cannot use '{modifier}' as a class method modifier"),
+    ("E026", "cannot use '{modifier}' as an enum method modifier"),
+    (
+        "E027",
+        "cannot use '{modifier}' as an interface method modifier",
+    ),
+    ("E028", "cannot declare a `final` class as `abstract`"),
+    (
+        "E029",
+        "cannot declare a `final` class member as `abstract`",
+    ),
+    ("E030", "cannot declare a `private` constant as `final`"),
+    (
+        "E032",
+        "cannot declare `readonly` property `{class}::{property}` as 'static'",
+    ),
+    (
+        "E033",
+        "readonly property `{class}::{property}` cannot have a default value",
+    ),
+    (
+        "E034",
+        "cannot mix braced and unbraced namespace declarations",
+    ),
+    (
+        "E035",
+        "cannot mix braced and unbraced namespace declarations",
+    ),
+    ("E035.nested", "cannot nest namespace declarations"),
+    (
+        "E037",
+        "property `{class}::{property}` cannot have type `{type}`",
+    ),
+    (
+        "E038",
+        "match expression cannot have more than one default arm",
+    ),
+    ("E039", "missing item definition after attribute(s)"),
+    ("E040", "cannot nest disjunctive normal form types"),
+    ("E041", "illegal spread operator usage"),
+    ("E042", "cannot assign reference to non-referencable value"),
+    ("E043", "cannot mix keyed and un-keyed list entries"),
+    (
+        "E044",
+        "cannot use positional argument after named argument",
+    ),
+    (
+        "E045",
+        "cannot use reserved keyword `{keyword}` as a type name",
+    ),
+    (
+        "E046",
+        "cannot use reserved keyword `{keyword}` as a goto label",
+    ),
+    (
+        "E047",
+        "cannot use reserved keyword `{keyword}` as a constant name",
+    ),
+    ("E048", "cannot use type `{type}` in current context"),
+    (
+        "E049",
+        "cannot use named argument, only positional arguments are accepted",
+    ),
+    ("E050", "only one argument are accepted"),
+    ("E051", "argument is required"),
+    ("E052", "cannot use `$this` outside of object context"),
+    ("E053", "cannot use `$this` in a static context"),
+    (
+        "E054",
+        "cannot use `{keyword}` when no class scope is active",
+    ),
+    (
+        "E055",
+        "cannot use `parent` as `{class}` does not have a parent",
+    ),
+    (
+        "E055.anonymous",
+        "cannot use `parent` as the anonymous class does not have a parent",
+    ),
+    (
+        "E056",
+        "`{keyword}` operator with non-integer operand is not supported",
+    ),
+    (
+        "E057",
+        "`{keyword}` operator accepts only positive integers",
+    ),
+    (
+        "E058",
+        "cannot use `{keyword}` outside of a loop or switch structure",
+    ),
+    ("E059", "cannot `{keyword}` {levels} levels"),
+    ("E060", "cannot assign the result of `new` by reference"),
+    (
+        "E061",
+        "cannot use positional argument after argument unpacking",
+    ),
+    (
+        "E062",
+        "`{key}` declaration must be the very first statement in the script",
+    ),
+    ("E063", "`strict_types` declaration must not use block mode"),
+    (
+        "E064",
+        "`strict_types` declaration value must be literally `0` or `1`",
+    ),
+    ("E065", "parsing was cancelled"),
+    ("E066", "missing `;` at the end of the statement"),
+    (
+        "E067.input_size",
+        "input exceeds the maximum size of {maximum} bytes",
+    ),
+    (
+        "E067.token_count",
+        "input exceeds the maximum of {maximum} tokens",
+    ),
+    (
+        "E067.error_count",
+        "too many errors, stopped after {maximum}",
+    ),
+    (
+        "E067.depth",
+        "input exceeds the maximum nesting depth of {maximum}",
+    ),
+    ("E068", "unexpected expression in string interpolation"),
+    (
+        "E069",
+        "cannot use `{name}` as `{alias}` because the name is already in use",
+    ),
+    (
+        "E069.function",
+        "cannot use function `{name}` as `{alias}` because the name is already in use",
+    ),
+    (
+        "E069.const",
+        "cannot use const `{name}` as `{alias}` because the name is already in use",
+    ),
+    (
+        "E070",
+        "cannot declare {kind} `{name}` because the name is already in use",
+    ),
+    ("E071", "using {feature} requires PHP {version} or later"),
+    ("E072", "{feature} was removed in PHP {version}"),
+    (
+        "E073",
+        "cannot apply `#[AllowDynamicProperties]` to readonly class `{class}`",
+    ),
+    ("E074", "duplicate type `{type}` is redundant"),
+    (
+        "E075",
+        "type `{type}` is redundant as it is more restrictive than type `{other}`",
+    ),
+    (
+        "E076",
+        "unknown hook `{hook}` for property `{class}::{property}`",
+    ),
+    (
+        "E077",
+        "cannot redeclare hook `{hook}` of property `{class}::{property}`",
+    ),
+    (
+        "E078.static",
+        "cannot declare hooks for static property `{class}::{property}`",
+    ),
+    (
+        "E078.readonly",
+        "cannot declare hooks for readonly property `{class}::{property}`",
+    ),
+    (
+        "E078.multiple",
+        "cannot declare hooks for property `{class}::{property}` declared along with other properties",
+    ),
+    (
+        "E079",
+        "abstract property `{class}::{property}` must have an abstract hook",
+    ),
+    (
+        "E079.interface",
+        "interface property `{class}::{property}` must have hooks",
+    ),
+    (
+        "E080.static",
+        "static property `{class}::{property}` cannot have asymmetric visibility",
+    ),
+    (
+        "E080.type",
+        "property `{class}::{property}` with asymmetric visibility must have a type",
+    ),
+    (
+        "E080.visibility",
+        "set visibility of property `{class}::{property}` cannot be weaker than its visibility",
+    ),
+    (
+        "E081",
+        "using {feature} requires enabling experimental syntax",
+    ),
+    ("E082", "unused suppression `{directive}`"),
+    (
+        "E083.statement",
+        "incomplete syntax tree, a statement failing to parse was skipped",
+    ),
+    (
+        "E083.expression",
+        "incomplete syntax tree, a missing expression was inserted",
+    ),
+    (
+        "E083.semicolon",
+        "incomplete syntax tree, a missing semicolon was inserted",
+    ),
+    (
+        "E083.member",
+        "incomplete syntax tree, a member failing to parse was dropped",
+    ),
+];
+
+/// The English template for the given key.
+pub(crate) fn english(key: &str) -> &'static str {
+    ENGLISH
+        .iter()
+        .find(|(candidate, _)| *candidate == key)
+        .map(|(_, template)| *template)
+        .unwrap_or_else(|| panic!("missing message template for `{}`", key))
+}
+
+/// Replace the placeholders of the given template with the arguments of the same name.
+///
+/// Placeholders without an argument are kept as is.
+pub(crate) fn render(template: &str, arguments: &BTreeMap<String, String>) -> String {
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        message.push_str(&rest[..start]);
+
+        let placeholder = &rest[start..];
+        let argument = placeholder
+            .find('}')
+            .and_then(|end| Some((end, arguments.get(&placeholder[1..end])?)));
+
+        match argument {
+            Some((end, value)) => {
+                message.push_str(value);
+                rest = &placeholder[end + 1..];
+            }
+            None => {
+                message.push('{');
+                rest = &placeholder[1..];
+            }
+        }
+    }
+
+    message.push_str(rest);
+
+    message
+}
+
+/// Message templates used to translate parse errors, keyed by error code.
+///
+/// Only the main message of an error is translated, the arguments it is rendered with, such as
+/// names and types, are language-independent. Errors without a template in the catalog keep
+/// their English message.
+///
+/// ```
+/// use php_parser_rs::parser::catalog::MessageCatalog;
+///
+/// let catalog = MessageCatalog::new()
+///     .with("E007", "les modificateurs `{modifier}` multiples ne sont pas autorisés");
+///
+/// let stack = php_parser_rs::parse("<?php final final class A {}").unwrap_err();
+///
+/// assert_eq!(
+///     stack.localized(&catalog).errors[0].message,
+///     "les modificateurs `final` multiples ne sont pas autorisés",
+/// );
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MessageCatalog {
+    templates: HashMap<String, String>,
+}
+
+impl MessageCatalog {
+    /// An empty catalog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The built-in English catalog, which can be used as a reference for the available keys
+    /// and placeholders.
+    pub fn english() -> Self {
+        Self {
+            templates: ENGLISH
+                .iter()
+                .map(|(key, template)| (key.to_string(), template.to_string()))
+                .collect(),
+        }
+    }
+
+    pub fn insert<TKey: ToString, TTemplate: ToString>(&mut self, key: TKey, template: TTemplate) {
+        self.templates.insert(key.to_string(), template.to_string());
+    }
+
+    pub fn with<TKey: ToString, TTemplate: ToString>(
+        mut self,
+        key: TKey,
+        template: TTemplate,
+    ) -> Self {
+        self.insert(key, template);
+
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.templates.get(key).map(String::as_str)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.templates.keys().map(String::as_str)
+    }
+
+    /// The message of the given error, rendered using this catalog.
+    pub fn message(&self, error: &ParseError) -> Option<String> {
+        self.get(&error.key())
+            .map(|template| render(template, &error.arguments))
+    }
+}
+
+impl ParseError {
+    /// Translate the message of this error using the given catalog.
+    pub fn localized(&self, catalog: &MessageCatalog) -> Self {
+        Self {
+            message: catalog
+                .message(self)
+                .unwrap_or_else(|| self.message.clone()),
+            ..self.clone()
+        }
+    }
+}
+
+impl ParseErrorStack {
+    /// Translate the messages of all errors using the given catalog, see [`ParseError::localized`].
+    pub fn localized(&self, catalog: &MessageCatalog) -> Self {
+        Self {
+            partial: self.partial.clone(),
+            errors: self
+                .errors
+                .iter()
+                .map(|error| error.localized(catalog))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::render;
+    use super::MessageCatalog;
+    use super::ENGLISH;
+
+    #[test]
+    fn test_localized_errors() {
+        let code = "<?php
+namespace A {
+    namespace B {}
+}
+";
+        let english = crate::parse(code).unwrap_err();
+        assert_eq!(english.errors[0].key(), "E035.nested");
+        assert_eq!(
+            english.localized(&MessageCatalog::english()),
+            english,
+            "the english catalog must reproduce the original messages"
+        );
+
+        let catalog = MessageCatalog::new().with("E035.nested", "espaces de noms imbriqués");
+        let localized = english.localized(&catalog);
+        assert_eq!(localized.errors[0].message, "espaces de noms imbriqués");
+        assert_eq!(localized.errors[0].span, english.errors[0].span);
+
+        let stack = crate::parse("<?php abstract final class A {}").unwrap_err();
+        let catalog = MessageCatalog::new().with("E028", "{missing} `final` et `abstract`");
+        assert_eq!(
+            stack.localized(&catalog).errors[0].message,
+            "{missing} `final` et `abstract`"
+        );
+        assert_eq!(
+            stack.localized(&MessageCatalog::new()).errors[0].message,
+            stack.errors[0].message
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let arguments = BTreeMap::from([
+            ("class".to_string(), "A".to_string()),
+            ("property".to_string(), "$b".to_string()),
+        ]);
+
+        assert_eq!(
+            render("`{class}::{property}` {unknown} {", &arguments),
+            "`A::$b` {unknown} {"
+        );
+
+        for (key, _) in ENGLISH {
+            assert_eq!(
+                ENGLISH.iter().filter(|(other, _)| other == key).count(),
+                1,
+                "duplicate template for `{}`",
+                key
+            );
+        }
+    }
+}
@@ -0,0 +1,389 @@
+use std::fmt::Display;
+
+use crate::lexer::token::Span;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::constant::ConstantStatement;
+use crate::parser::ast::control_flow::IfStatementBody;
+use crate::parser::ast::declares::DeclareBody;
+use crate::parser::ast::enums::BackedEnumCase;
+use crate::parser::ast::enums::UnitEnumCase;
+use crate::parser::ast::functions::AbstractConstructor;
+use crate::parser::ast::functions::AbstractMethod;
+use crate::parser::ast::goto::GotoStatement;
+use crate::parser::ast::loops::BreakStatement;
+use crate::parser::ast::loops::ContinueStatement;
+use crate::parser::ast::loops::DoWhileStatement;
+use crate::parser::ast::loops::ForStatementBody;
+use crate::parser::ast::loops::ForeachStatementBody;
+use crate::parser::ast::loops::WhileStatementBody;
+use crate::parser::ast::namespaces::UnbracedNamespace;
+use crate::parser::ast::properties::Property;
+use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::visitor::walk_abstract_constructor;
+use crate::parser::ast::visitor::walk_abstract_method;
+use crate::parser::ast::visitor::walk_backed_enum_case;
+use crate::parser::ast::visitor::walk_break_statement;
+use crate::parser::ast::visitor::walk_classish_constant;
+use crate::parser::ast::visitor::walk_constant_statement;
+use crate::parser::ast::visitor::walk_continue_statement;
+use crate::parser::ast::visitor::walk_declare_body;
+use crate::parser::ast::visitor::walk_do_while_statement;
+use crate::parser::ast::visitor::walk_echo_statement;
+use crate::parser::ast::visitor::walk_expression;
+use crate::parser::ast::visitor::walk_expression_statement;
+use crate::parser::ast::visitor::walk_for_statement_body;
+use crate::parser::ast::visitor::walk_foreach_statement_body;
+use crate::parser::ast::visitor::walk_goto_statement;
+use crate::parser::ast::visitor::walk_if_statement_body;
+use crate::parser::ast::visitor::walk_property;
+use crate::parser::ast::visitor::walk_return_statement;
+use crate::parser::ast::visitor::walk_unbraced_namespace;
+use crate::parser::ast::visitor::walk_unit_enum_case;
+use crate::parser::ast::visitor::walk_variable_property;
+use crate::parser::ast::visitor::walk_while_statement_body;
+use crate::parser::ast::visitor::Visitor;
+use crate::parser::ast::EchoStatement;
+use crate::parser::ast::ErrorStatement;
+use crate::parser::ast::Expression;
+use crate::parser::ast::ExpressionStatement;
+use crate::parser::ast::ReturnStatement;
+use crate::parser::ast::Statement;
+use crate::parser::error;
+use crate::parser::error::ParseError;
+
+/// A node guessed by error recovery, see [`crate::parser::parse_tolerant`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recovery {
+    /// A statement which failed to parse, replaced by an error statement.
+    Statement,
+    /// A missing expression, replaced by a placeholder, e.g. in `$a = ;`.
+    Expression,
+    /// A missing semicolon, e.g. between `$a = 1` and `$b = 2;` on the next line.
+    Semicolon,
+    /// A class-like member which failed to parse, dropped from its body.
+    Member,
+}
+
+impl Recovery {
+    /// The message variant of the incomplete syntax tree error, see [`crate::parser::catalog`].
+    pub fn variant(&self) -> &'static str {
+        match self {
+            Self::Statement => "statement",
+            Self::Expression => "expression",
+            Self::Semicolon => "semicolon",
+            Self::Member => "member",
+        }
+    }
+
+    /// The recovery of the given message variant, see [`Recovery::variant`].
+    pub fn from_variant(variant: &str) -> Option<Self> {
+        [
+            Self::Statement,
+            Self::Expression,
+            Self::Semicolon,
+            Self::Member,
+        ]
+        .into_iter()
+        .find(|recovery| recovery.variant() == variant)
+    }
+}
+
+impl Display for Recovery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.variant())
+    }
+}
+
+/// Check that the given program holds no node guessed by error recovery, failing with an
+/// incomplete syntax tree error (`E083`) for the first one otherwise.
+///
+/// Members dropped by error recovery leave no trace in the program, only the parse which
+/// produced it can report them, see [`crate::ParserOptions::require_complete`].
+///
+/// ```
+/// use php_parser_rs::parser::completeness::assert_complete;
+///
+/// assert!(assert_complete(&php_parser_rs::parse("<?php $a = 1;").unwrap()).is_ok());
+///
+/// let parse = php_parser_rs::parse_all("<?php $a = ;");
+/// assert_eq!(assert_complete(&parse.ast).unwrap_err().id, "E083");
+/// ```
+pub fn assert_complete(program: &[Statement]) -> Result<(), ParseError> {
+    let mut finder = Finder::default();
+    finder.visit_program(program);
+
+    finder
+        .semicolons
+        .into_iter()
+        .map(|span| (Recovery::Semicolon, span))
+        .chain(
+            finder
+                .statements
+                .into_iter()
+                .map(|span| (Recovery::Statement, span)),
+        )
+        .chain(
+            finder
+                .expressions
+                .into_iter()
+                .map(|span| (Recovery::Expression, span)),
+        )
+        .min_by_key(|(_, span)| span.position)
+        .map_or(Ok(()), |(recovery, span)| {
+            Err(error::incomplete_syntax_tree(recovery, span))
+        })
+}
+
+#[derive(Default)]
+struct Finder {
+    statements: Vec<Span>,
+    expressions: Vec<Span>,
+    semicolons: Vec<Span>,
+}
+
+impl Finder {
+    // The spans of semicolons inserted by error recovery are synthetic.
+    fn semicolon(&mut self, span: Span) {
+        if span.is_synthetic() {
+            self.semicolons.push(span);
+        }
+    }
+}
+
+impl Visitor for Finder {
+    fn visit_error_statement(&mut self, error_statement: &ErrorStatement) {
+        self.statements.push(error_statement.span);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Expression::Missing { span } = expression {
+            self.expressions.push(*span);
+        }
+
+        walk_expression(self, expression);
+    }
+
+    fn visit_expression_statement(&mut self, expression_statement: &ExpressionStatement) {
+        self.semicolon(expression_statement.ending.span());
+
+        walk_expression_statement(self, expression_statement);
+    }
+
+    fn visit_echo_statement(&mut self, echo_statement: &EchoStatement) {
+        self.semicolon(echo_statement.ending.span());
+
+        walk_echo_statement(self, echo_statement);
+    }
+
+    fn visit_return_statement(&mut self, return_statement: &ReturnStatement) {
+        self.semicolon(return_statement.ending.span());
+
+        walk_return_statement(self, return_statement);
+    }
+
+    fn visit_break_statement(&mut self, break_statement: &BreakStatement) {
+        self.semicolon(break_statement.ending.span());
+
+        walk_break_statement(self, break_statement);
+    }
+
+    fn visit_continue_statement(&mut self, continue_statement: &ContinueStatement) {
+        self.semicolon(continue_statement.ending.span());
+
+        walk_continue_statement(self, continue_statement);
+    }
+
+    fn visit_goto_statement(&mut self, goto_statement: &GotoStatement) {
+        self.semicolon(goto_statement.semicolon);
+
+        walk_goto_statement(self, goto_statement);
+    }
+
+    fn visit_do_while_statement(&mut self, do_while_statement: &DoWhileStatement) {
+        self.semicolon(do_while_statement.semicolon);
+
+        walk_do_while_statement(self, do_while_statement);
+    }
+
+    fn visit_constant_statement(&mut self, constant_statement: &ConstantStatement) {
+        self.semicolon(constant_statement.semicolon);
+
+        walk_constant_statement(self, constant_statement);
+    }
+
+    fn visit_classish_constant(&mut self, classish_constant: &ClassishConstant) {
+        self.semicolon(classish_constant.semicolon);
+
+        walk_classish_constant(self, classish_constant);
+    }
+
+    fn visit_unbraced_namespace(&mut self, unbraced_namespace: &UnbracedNamespace) {
+        self.semicolon(unbraced_namespace.end);
+
+        walk_unbraced_namespace(self, unbraced_namespace);
+    }
+
+    fn visit_declare_body(&mut self, declare_body: &DeclareBody) {
+        match declare_body {
+            DeclareBody::Noop { semicolon } | DeclareBody::Expression { semicolon, .. } => {
+                self.semicolon(*semicolon)
+            }
+            DeclareBody::Block {
+                end: (_, semicolon),
+                ..
+            } => self.semicolon(*semicolon),
+            _ => {}
+        }
+
+        walk_declare_body(self, declare_body);
+    }
+
+    fn visit_if_statement_body(&mut self, if_statement_body: &IfStatementBody) {
+        if let IfStatementBody::Block { ending, .. } = if_statement_body {
+            self.semicolon(ending.span());
+        }
+
+        walk_if_statement_body(self, if_statement_body);
+    }
+
+    fn visit_foreach_statement_body(&mut self, foreach_statement_body: &ForeachStatementBody) {
+        if let ForeachStatementBody::Block { ending, .. } = foreach_statement_body {
+            self.semicolon(ending.span());
+        }
+
+        walk_foreach_statement_body(self, foreach_statement_body);
+    }
+
+    fn visit_for_statement_body(&mut self, for_statement_body: &ForStatementBody) {
+        if let ForStatementBody::Block { ending, .. } = for_statement_body {
+            self.semicolon(ending.span());
+        }
+
+        walk_for_statement_body(self, for_statement_body);
+    }
+
+    fn visit_while_statement_body(&mut self, while_statement_body: &WhileStatementBody) {
+        if let WhileStatementBody::Block { ending, .. } = while_statement_body {
+            self.semicolon(ending.span());
+        }
+
+        walk_while_statement_body(self, while_statement_body);
+    }
+
+    fn visit_property(&mut self, property: &Property) {
+        self.semicolon(property.end);
+
+        walk_property(self, property);
+    }
+
+    fn visit_variable_property(&mut self, variable_property: &VariableProperty) {
+        self.semicolon(variable_property.end);
+
+        walk_variable_property(self, variable_property);
+    }
+
+    fn visit_abstract_method(&mut self, abstract_method: &AbstractMethod) {
+        self.semicolon(abstract_method.semicolon);
+
+        walk_abstract_method(self, abstract_method);
+    }
+
+    fn visit_abstract_constructor(&mut self, abstract_constructor: &AbstractConstructor) {
+        self.semicolon(abstract_constructor.semicolon);
+
+        walk_abstract_constructor(self, abstract_constructor);
+    }
+
+    fn visit_unit_enum_case(&mut self, unit_enum_case: &UnitEnumCase) {
+        self.semicolon(unit_enum_case.end);
+
+        walk_unit_enum_case(self, unit_enum_case);
+    }
+
+    fn visit_backed_enum_case(&mut self, backed_enum_case: &BackedEnumCase) {
+        self.semicolon(backed_enum_case.semicolon);
+
+        walk_backed_enum_case(self, backed_enum_case);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assert_complete;
+    use super::Recovery;
+    use crate::parser::suppressions::SuppressionMode;
+    use crate::ParserOptions;
+
+    fn recovery(code: &str) -> Option<(Recovery, usize)> {
+        crate::parse_all(code)
+            .assert_complete()
+            .err()
+            .map(|error| (error.recovery().unwrap(), error.span.line))
+    }
+
+    #[test]
+    fn test_assert_complete() {
+        assert_eq!(recovery("<?php\n$a = 1;\nclass A { public $b; }"), None);
+        assert_eq!(
+            recovery("<?php\n$a = 1;\n$b = ;\n$c = 3"),
+            Some((Recovery::Expression, 3))
+        );
+        assert_eq!(
+            recovery("<?php\n$a = 1\n$b = 2;"),
+            Some((Recovery::Semicolon, 2))
+        );
+        assert_eq!(
+            recovery("<?php\nif (1):\n    echo 1\n    return 2;\nendif;"),
+            Some((Recovery::Semicolon, 3))
+        );
+        assert_eq!(
+            recovery("<?php\nclass A {\n    const B = 1\n    public $c;\n}"),
+            Some((Recovery::Semicolon, 3))
+        );
+        assert_eq!(
+            recovery("<?php\n$a = 1;\n$b = 1 2;\n$c = ;"),
+            Some((Recovery::Statement, 3))
+        );
+
+        let error = assert_complete(&crate::parse_all("<?php $a = ;").ast).unwrap_err();
+        assert_eq!(error.id, "E083");
+        assert_eq!(error.variant.as_deref(), Some("expression"));
+    }
+
+    #[test]
+    fn test_require_complete() {
+        let code = "<?php
+// @parser-ignore-next-line
+$a = ;
+class A {
+    // @parser-ignore-next-line
+    public function () {}
+}
+";
+        let options = ParserOptions::new().with_suppressions(SuppressionMode::On);
+
+        assert!(crate::parse_all_with_options(code, &options)
+            .errors
+            .is_empty());
+
+        let parse =
+            crate::parse_all_with_options(code, &options.clone().with_require_complete(true));
+
+        assert_eq!(parse.errors.len(), 1);
+        assert_eq!(parse.errors[0].id, "E083");
+        assert_eq!(parse.errors[0].recovery(), Some(Recovery::Expression));
+        assert_eq!(parse.errors[0].span.line, 3);
+
+        // Dropped members leave no trace in the program.
+        let code = "<?php
+class A {
+    // @parser-ignore-next-line
+    public function () {}
+}
+";
+        let parse = crate::parse_all_with_options(code, &options.with_require_complete(true));
+
+        assert!(parse.assert_complete().is_ok());
+        assert_eq!(parse.errors[0].recovery(), Some(Recovery::Member));
+    }
+}
@@ -0,0 +1,539 @@
+//! A lossless concrete syntax tree.
+//!
+//! Unlike the [`ast`](crate::parser::ast), the tree keeps every token of the source, along
+//! with the whitespace and comments around them as trivia, so printing it reproduces the
+//! source byte for byte. Nodes are named after the AST nodes they cover, e.g. `function` or
+//! `assignment_operation`, and the [`Program`] is kept next to the tree.
+
+use std::fmt::Display;
+
+use serde_json::Value;
+
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::line_index::LineIndex;
+use crate::lexer::token::Span;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+use crate::lexer::Lexer;
+use crate::parser::ast::Program;
+use crate::parser::error::ParseErrorStack;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TriviaKind {
+    Whitespace,
+    SingleLineComment,
+    MultiLineComment,
+    HashMarkComment,
+    DocumentComment,
+}
+
+/// Whitespace or a comment between two tokens.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub span: Span,
+    pub text: ByteString,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SyntaxToken {
+    pub kind: TokenKind,
+    pub span: Span,
+    /// The source text of the token, see [`Token::raw`].
+    pub text: ByteString,
+    /// The trivia preceding the token, from the start of its line, or from the trivia
+    /// following the previous token on the same line.
+    pub leading: Vec<Trivia>,
+    /// The trivia following the token up to the end of its line, the newline included.
+    pub trailing: Vec<Trivia>,
+}
+
+impl SyntaxToken {
+    fn write(&self, output: &mut Vec<u8>) {
+        for trivia in self.leading.iter() {
+            output.extend_from_slice(&trivia.text);
+        }
+
+        output.extend_from_slice(&self.text);
+
+        for trivia in self.trailing.iter() {
+            output.extend_from_slice(&trivia.text);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SyntaxNode {
+    /// The name of the AST node, as serialized, e.g. `function` for a function statement, or
+    /// `program` for the root of the tree.
+    pub kind: String,
+    pub children: Vec<SyntaxElement>,
+}
+
+impl SyntaxNode {
+    /// The nodes directly within this node.
+    pub fn nodes(&self) -> impl Iterator<Item = &SyntaxNode> {
+        self.children.iter().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+
+    /// All nodes within this node, in source order, parents before their children.
+    pub fn descendants(&self) -> Vec<&SyntaxNode> {
+        let mut descendants = Vec::new();
+        for node in self.nodes() {
+            descendants.push(node);
+            descendants.extend(node.descendants());
+        }
+
+        descendants
+    }
+
+    /// All tokens within this node, in source order.
+    pub fn tokens(&self) -> Vec<&SyntaxToken> {
+        let mut tokens = Vec::new();
+        self.collect_tokens(&mut tokens);
+
+        tokens
+    }
+
+    fn collect_tokens<'a>(&'a self, tokens: &mut Vec<&'a SyntaxToken>) {
+        for child in self.children.iter() {
+            match child {
+                SyntaxElement::Node(node) => node.collect_tokens(tokens),
+                SyntaxElement::Token(token) => tokens.push(token),
+            }
+        }
+    }
+
+    /// The span of the first token of the node.
+    pub fn span(&self) -> Option<Span> {
+        self.tokens().first().map(|token| token.span)
+    }
+
+    /// The source text of the node, along with the trivia of its tokens.
+    pub fn text(&self) -> ByteString {
+        let mut output = Vec::new();
+        for token in self.tokens() {
+            token.write(&mut output);
+        }
+
+        output.into()
+    }
+}
+
+impl Display for SyntaxNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(&self.text()))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SyntaxTree {
+    pub root: SyntaxNode,
+    pub program: Program,
+}
+
+impl SyntaxTree {
+    /// The source the tree was parsed from.
+    pub fn text(&self) -> ByteString {
+        self.root.text()
+    }
+}
+
+impl Display for SyntaxTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.root)
+    }
+}
+
+/// Parse the given input into a lossless syntax tree.
+///
+/// ```
+/// let source = "<?php\n\n// greet\necho  'Hello';  # done\n";
+/// let tree = php_parser_rs::parser::cst::parse(source).unwrap();
+///
+/// assert_eq!(&tree.text()[..], source.as_bytes());
+///
+/// let echo = tree.root.nodes().nth(1).unwrap();
+/// assert_eq!(echo.kind, "echo");
+/// assert_eq!(echo.to_string(), "\n// greet\necho  'Hello';  # done\n");
+/// ```
+pub fn parse<B: ?Sized + AsRef<[u8]>>(input: &B) -> Result<SyntaxTree, ParseErrorStack> {
+    let source = input.as_ref();
+    let tokens = match Lexer::new().tokenize(source) {
+        Ok(tokens) => tokens,
+        Err(error) => {
+            return Err(ParseErrorStack {
+                errors: vec![error.into()],
+                partial: Vec::new(),
+            })
+        }
+    };
+
+    let program = crate::parser::construct(&tokens)?;
+
+    let tokens = syntax_tokens(source, &tokens);
+    let value = serde_json::to_value(&program).unwrap_or(Value::Null);
+
+    let mut builder = Builder {
+        positions: tokens.iter().map(|token| token.span.position).collect(),
+        tokens: tokens.into_iter().map(Some).collect(),
+    };
+    let end = builder.tokens.len();
+    let root = builder.node("program".to_string(), &value, 0, end);
+
+    Ok(SyntaxTree { root, program })
+}
+
+// The tokens of the source, with the trivia between them.
+fn syntax_tokens(source: &[u8], tokens: &[Token]) -> Vec<SyntaxToken> {
+    let index = LineIndex::new(source);
+
+    let mut syntax_tokens: Vec<SyntaxToken> = Vec::new();
+    let mut trivia = Vec::new();
+    let mut cursor = 0;
+
+    for token in tokens.iter().filter(|token| !token.is_synthetic()) {
+        let position = token.span.position;
+        if position > cursor {
+            trivia.push(Trivia {
+                kind: TriviaKind::Whitespace,
+                span: index.span(cursor),
+                text: source[cursor..position].into(),
+            });
+        }
+        cursor = position + token.raw().len();
+
+        let kind = match token.kind {
+            TokenKind::SingleLineComment => TriviaKind::SingleLineComment,
+            TokenKind::MultiLineComment => TriviaKind::MultiLineComment,
+            TokenKind::HashMarkComment => TriviaKind::HashMarkComment,
+            TokenKind::DocumentComment => TriviaKind::DocumentComment,
+            _ => {
+                let leading = match syntax_tokens.last_mut() {
+                    Some(previous) => {
+                        let (trailing, leading) = split_line(std::mem::take(&mut trivia), &index);
+                        previous.trailing = trailing;
+
+                        leading
+                    }
+                    None => std::mem::take(&mut trivia),
+                };
+
+                syntax_tokens.push(SyntaxToken {
+                    kind: token.kind.clone(),
+                    span: token.span,
+                    text: token.raw().into(),
+                    leading,
+                    trailing: Vec::new(),
+                });
+
+                continue;
+            }
+        };
+
+        trivia.push(Trivia {
+            kind,
+            span: token.span,
+            text: token.raw().into(),
+        });
+    }
+
+    syntax_tokens
+}
+
+// Split the trivia following a token into the trivia ending its line, and the trivia
+// preceding the next token.
+fn split_line(trivia: Vec<Trivia>, index: &LineIndex) -> (Vec<Trivia>, Vec<Trivia>) {
+    let mut trailing = Vec::new();
+    let mut pieces = trivia.into_iter();
+
+    for piece in pieces.by_ref() {
+        match piece.text.iter().position(|byte| *byte == b'\n') {
+            None => trailing.push(piece),
+            Some(newline) if piece.kind == TriviaKind::Whitespace => {
+                let position = piece.span.position + newline + 1;
+                let rest = &piece.text[newline + 1..];
+
+                trailing.push(Trivia {
+                    kind: TriviaKind::Whitespace,
+                    span: piece.span,
+                    text: piece.text[..=newline].into(),
+                });
+
+                let mut leading = Vec::new();
+                if !rest.is_empty() {
+                    leading.push(Trivia {
+                        kind: TriviaKind::Whitespace,
+                        span: index.span(position),
+                        text: rest.into(),
+                    });
+                }
+                leading.extend(pieces);
+
+                return (trailing, leading);
+            }
+            // a single line comment ends with the newline ending its line.
+            Some(newline) if newline + 1 == piece.text.len() => {
+                trailing.push(piece);
+
+                return (trailing, pieces.collect());
+            }
+            Some(_) => {
+                let mut leading = vec![piece];
+                leading.extend(pieces);
+
+                return (trailing, leading);
+            }
+        }
+    }
+
+    (trailing, Vec::new())
+}
+
+struct Builder {
+    // The tokens not yet moved into the tree.
+    tokens: Vec<Option<SyntaxToken>>,
+    positions: Vec<usize>,
+}
+
+impl Builder {
+    /// Build the node of the given serialized AST node, covering the tokens from `start` to
+    /// `end`.
+    fn node(&mut self, kind: String, value: &Value, start: usize, end: usize) -> SyntaxNode {
+        let mut candidates = Vec::new();
+        nested(value, &mut candidates);
+
+        // The nested nodes, ordered, without the nodes overlapping a previous one.
+        let mut ranges: Vec<(usize, usize, &Value)> = candidates
+            .into_iter()
+            .filter_map(|value| {
+                let (first, last) = self.range(value)?;
+
+                Some((first, last + 1, value))
+            })
+            .filter(|(first, last, _)| *first >= start && *last <= end)
+            .collect();
+        ranges.sort_by_key(|(first, last, _)| (*first, std::cmp::Reverse(*last)));
+
+        let mut children = Vec::new();
+        let mut cursor = start;
+        for (first, last, value) in ranges {
+            if first < cursor {
+                continue;
+            }
+
+            while cursor < first {
+                children.push(self.token(cursor));
+                cursor += 1;
+            }
+
+            children.push(Some(SyntaxElement::Node(self.node(
+                tag(value),
+                value,
+                first,
+                last,
+            ))));
+            cursor = last;
+        }
+
+        while cursor < end {
+            children.push(self.token(cursor));
+            cursor += 1;
+        }
+
+        SyntaxNode {
+            kind,
+            children: children.into_iter().flatten().collect(),
+        }
+    }
+
+    fn token(&mut self, index: usize) -> Option<SyntaxElement> {
+        self.tokens[index].take().map(SyntaxElement::Token)
+    }
+
+    /// The indices of the first and the last token of the given serialized node.
+    fn range(&self, value: &Value) -> Option<(usize, usize)> {
+        let mut range: Option<(usize, usize)> = None;
+        self.visit_spans(value, &mut |index| {
+            range = Some(match range {
+                Some((first, last)) => (first.min(index), last.max(index)),
+                None => (index, index),
+            });
+        });
+
+        range
+    }
+
+    fn visit_spans(&self, value: &Value, visit: &mut dyn FnMut(usize)) {
+        match value {
+            Value::Object(object) => {
+                let position = object.get("position").and_then(Value::as_u64);
+                let synthetic = object.get("synthetic").and_then(Value::as_bool);
+
+                if let (Some(position), true) = (position, object.contains_key("line")) {
+                    // spans of comments and synthetic spans are not the start of a token.
+                    if synthetic != Some(true) {
+                        if let Ok(index) = self.positions.binary_search(&(position as usize)) {
+                            visit(index);
+                        }
+                    }
+
+                    return;
+                }
+
+                for value in object.values() {
+                    self.visit_spans(value, visit);
+                }
+            }
+            Value::Array(values) => {
+                for value in values {
+                    self.visit_spans(value, visit);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// The name of a serialized enum variant, e.g. `{"type": "function", "value": {...}}`.
+fn tag(value: &Value) -> String {
+    value
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn is_tagged(value: &Value) -> bool {
+    value.get("type").is_some_and(Value::is_string)
+}
+
+// Collect the outermost enum variants within the fields of a serialized node.
+fn nested<'a>(value: &'a Value, nodes: &mut Vec<&'a Value>) {
+    let fields: Vec<&Value> = match value {
+        Value::Object(object) if is_tagged(value) => match object.get("value") {
+            Some(content) if object.len() == 2 => vec![content],
+            _ => object
+                .iter()
+                .filter(|(key, _)| *key != "type")
+                .map(|(_, value)| value)
+                .collect(),
+        },
+        _ => vec![value],
+    };
+
+    for field in fields {
+        outermost(field, nodes);
+    }
+}
+
+fn outermost<'a>(value: &'a Value, nodes: &mut Vec<&'a Value>) {
+    match value {
+        Value::Object(_) if is_tagged(value) => nodes.push(value),
+        Value::Object(object) => {
+            for value in object.values() {
+                outermost(value, nodes);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                outermost(value, nodes);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use super::SyntaxElement;
+    use super::TriviaKind;
+
+    #[test]
+    fn test_lossless() {
+        for source in [
+            "",
+            "<html><?php echo 1; ?></html>\n",
+            "<?php\r\n\r\n/** doc */\nfunction  foo( $a ) {\n\treturn \"a\\n$a\"; // one\n}\n",
+            "<?php\n$a = <<<EOT\n  {$b} x\n  EOT;\n__halt_compiler(); raw data",
+        ] {
+            let tree = parse(source).unwrap();
+
+            assert_eq!(&tree.text()[..], source.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_trivia() {
+        let tree = parse("<?php\n$a = 1; // one\n\n/* two */ $b = 2;\n").unwrap();
+        let tokens = tree.root.tokens();
+
+        let semicolon = tokens[4];
+        assert_eq!(semicolon.text, b";");
+        assert_eq!(
+            semicolon
+                .trailing
+                .iter()
+                .map(|trivia| (trivia.kind, String::from_utf8_lossy(&trivia.text)))
+                .collect::<Vec<_>>(),
+            vec![
+                (TriviaKind::Whitespace, " ".into()),
+                (TriviaKind::SingleLineComment, "// one\n".into()),
+            ]
+        );
+
+        let variable = tokens[5];
+        assert_eq!(variable.text, b"$b");
+        assert_eq!(
+            variable
+                .leading
+                .iter()
+                .map(|trivia| (trivia.kind, String::from_utf8_lossy(&trivia.text)))
+                .collect::<Vec<_>>(),
+            vec![
+                (TriviaKind::Whitespace, "\n".into()),
+                (TriviaKind::MultiLineComment, "/* two */".into()),
+                (TriviaKind::Whitespace, " ".into()),
+            ]
+        );
+        assert_eq!(variable.leading[1].span.line, 4);
+    }
+
+    #[test]
+    fn test_nodes() {
+        let tree = parse("<?php\nfunction foo() { return $a + 1; }\n").unwrap();
+
+        let kinds = tree
+            .root
+            .nodes()
+            .map(|node| node.kind.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(kinds, vec!["full_opening_tag", "function"]);
+
+        let function = tree.root.nodes().nth(1).unwrap();
+        assert_eq!(function.to_string(), "function foo() { return $a + 1; }\n");
+
+        let r#return = function.nodes().find(|node| node.kind == "return").unwrap();
+        assert_eq!(r#return.to_string(), "return $a + 1; ");
+        assert!(r#return
+            .descendants()
+            .iter()
+            .any(|node| node.kind == "arithmetic_operation"));
+
+        // the end of file is the last token of the tree.
+        assert!(matches!(
+            tree.root.children.last(),
+            Some(SyntaxElement::Token(token)) if token.text.is_empty()
+        ));
+    }
+}
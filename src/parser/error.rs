@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 
 use ariadne::{CharSet, Color, Config, Label, Report, ReportKind, Source};
@@ -7,13 +8,21 @@ use serde::Serialize;
 
 use crate::lexer::error::SyntaxError;
 use crate::lexer::token::{Span, Token, TokenKind};
+use crate::parser::ast::attributes::Attribute;
 use crate::parser::ast::attributes::AttributeGroup;
 use crate::parser::ast::data_type::Type;
+use crate::parser::ast::literals::Literal;
 use crate::parser::ast::modifiers::PromotedPropertyModifier;
 use crate::parser::ast::Program;
+use crate::parser::ast::UseKind;
+use crate::parser::completeness::Recovery;
+use crate::parser::limits::ResourceLimit;
+use crate::parser::options::PhpVersion;
 
 use super::ast::identifiers::SimpleIdentifier;
 use super::ast::variables::SimpleVariable;
+use super::catalog;
+use super::source_map::SpanMapper;
 use super::state::State;
 
 pub type ParseResult<T> = Result<T, ParseError>;
@@ -33,6 +42,37 @@ pub struct ParseErrorAnnotation {
     pub length: usize,
 }
 
+/// A declaration enclosing the location of an error.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum ErrorScope {
+    /// `None` for the global namespace.
+    Namespace {
+        name: Option<String>,
+    },
+    /// `None` for anonymous classes.
+    Class {
+        name: Option<String>,
+    },
+    Interface {
+        name: String,
+    },
+    Trait {
+        name: String,
+    },
+    Enum {
+        name: String,
+    },
+    Function {
+        name: String,
+    },
+    Method {
+        name: String,
+    },
+    Closure,
+    ArrowFunction,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ParseError {
     pub id: String,
@@ -40,6 +80,16 @@ pub struct ParseError {
     pub span: Span,
     pub annotations: Vec<ParseErrorAnnotation>,
     pub note: Option<String>,
+    /// The declarations enclosing the error, outermost first.
+    #[serde(default)]
+    pub scopes: Vec<ErrorScope>,
+    /// The values the message template was rendered with, such as names and types,
+    /// see [`catalog::MessageCatalog`].
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub arguments: BTreeMap<String, String>,
+    /// The message variant, for codes with several messages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -48,7 +98,66 @@ pub struct ParseErrorStack {
     pub errors: Vec<ParseError>,
 }
 
+/// A program along with every error found while parsing it, see [`crate::parser::parse_all`].
+///
+/// Unlike [`ParseErrorStack`], a program is always available, even when it is incomplete.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct Parse {
+    pub ast: Program,
+    pub errors: Vec<ParseError>,
+}
+
+impl Parse {
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Check that the program holds no node guessed by error recovery, see
+    /// [`crate::parser::completeness::assert_complete`].
+    pub fn assert_complete(&self) -> Result<(), ParseError> {
+        crate::parser::completeness::assert_complete(&self.ast)
+    }
+
+    pub fn into_result(self) -> Result<Program, ParseErrorStack> {
+        if self.errors.is_empty() {
+            Ok(self.ast)
+        } else {
+            Err(ParseErrorStack {
+                partial: self.ast,
+                errors: self.errors,
+            })
+        }
+    }
+}
+
+impl From<Result<Program, ParseErrorStack>> for Parse {
+    fn from(result: Result<Program, ParseErrorStack>) -> Self {
+        match result {
+            Ok(ast) => Self {
+                ast,
+                errors: Vec::new(),
+            },
+            Err(stack) => Self {
+                ast: stack.partial,
+                errors: stack.errors,
+            },
+        }
+    }
+}
+
 impl ParseErrorStack {
+    /// Whether the parse was stopped by a cancellation, rather than by an error in the input.
+    pub fn is_cancelled(&self) -> bool {
+        self.errors.iter().any(|error| error.id == "E065")
+    }
+
+    /// Whether the parse was stopped as a resource limit was exceeded, see [`ParseError::resource_limit`].
+    pub fn is_resource_limit_exceeded(&self) -> bool {
+        self.errors
+            .iter()
+            .any(|error| error.resource_limit().is_some())
+    }
+
     pub fn report<'a>(
         &self,
         source: &'a str,
@@ -64,6 +173,40 @@ impl ParseErrorStack {
 
         Ok(reports.join("\n"))
     }
+
+    /// Translate the spans of all errors using the given mapper, see [`ParseError::mapped`].
+    pub fn mapped(&self, mapper: &dyn SpanMapper) -> Self {
+        Self {
+            partial: self.partial.clone(),
+            errors: self
+                .errors
+                .iter()
+                .map(|error| error.mapped(mapper))
+                .collect(),
+        }
+    }
+
+    /// Render the errors against the original source the parsed source was generated from.
+    pub fn report_mapped<'a>(
+        &self,
+        mapper: &dyn SpanMapper,
+        original: &'a str,
+        origin: Option<&'a str>,
+        colored: bool,
+        ascii: bool,
+    ) -> std::io::Result<String> {
+        let mut reports = Vec::new();
+
+        for error in &self.errors {
+            reports.push(
+                error
+                    .mapped(mapper)
+                    .report(original, origin, colored, ascii)?,
+            );
+        }
+
+        Ok(reports.join("\n"))
+    }
 }
 
 impl ParseError {
@@ -74,9 +217,99 @@ impl ParseError {
             span,
             annotations: Vec::new(),
             note: None,
+            scopes: Vec::new(),
+            arguments: BTreeMap::new(),
+            variant: None,
         }
     }
 
+    /// Create an error with the English message for the given catalog key, e.g. `E035.nested`,
+    /// rendered with the given arguments.
+    pub fn templated(key: &str, span: Span, arguments: &[(&str, &dyn Display)]) -> Self {
+        let (id, variant) = match key.split_once('.') {
+            Some((id, variant)) => (id, Some(variant.to_string())),
+            None => (key, None),
+        };
+
+        let arguments = arguments
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        Self {
+            message: catalog::render(catalog::english(key), &arguments),
+            arguments,
+            variant,
+            ..Self::new(id, "", span)
+        }
+    }
+
+    /// The key of the message template of this error within a [`catalog::MessageCatalog`].
+    pub fn key(&self) -> String {
+        match &self.variant {
+            Some(variant) => format!("{}.{}", self.id, variant),
+            None => self.id.clone(),
+        }
+    }
+
+    /// Whether the error is an unexpected token (`E002`, `E003`, `E005` and `E006`), rather
+    /// than a construct which is recognised but not allowed.
+    pub fn is_unexpected_token(&self) -> bool {
+        matches!(self.id.as_str(), "E002" | "E003" | "E005" | "E006")
+    }
+
+    /// The resource limit exceeded, for resource limit errors (`E067`).
+    pub fn resource_limit(&self) -> Option<ResourceLimit> {
+        if self.id != "E067" {
+            return None;
+        }
+
+        ResourceLimit::from_variant(self.variant.as_deref()?)
+    }
+
+    /// The node guessed by error recovery, for incomplete syntax tree errors (`E083`).
+    pub fn recovery(&self) -> Option<Recovery> {
+        if self.id != "E083" {
+            return None;
+        }
+
+        Recovery::from_variant(self.variant.as_deref()?)
+    }
+
+    /// The name of the innermost named declaration enclosing the error, e.g. `App\User::save`
+    /// for an error within the `save` method of the `App\User` class.
+    pub fn symbol(&self) -> Option<String> {
+        let mut namespace = None;
+        let mut symbol: Option<String> = None;
+
+        for scope in &self.scopes {
+            let qualified = |name: &String| match &namespace {
+                Some(namespace) => format!("{}\\{}", namespace, name),
+                None => name.clone(),
+            };
+
+            match scope {
+                ErrorScope::Namespace { name } => namespace = name.clone(),
+                ErrorScope::Class { name: Some(name) }
+                | ErrorScope::Interface { name }
+                | ErrorScope::Trait { name }
+                | ErrorScope::Enum { name }
+                | ErrorScope::Function { name } => symbol = Some(qualified(name)),
+                ErrorScope::Method { name } => {
+                    symbol = Some(match symbol {
+                        Some(symbol) => format!("{}::{}", symbol, name),
+                        None => name.clone(),
+                    })
+                }
+                ErrorScope::Class { name: None }
+                | ErrorScope::Closure
+                | ErrorScope::ArrowFunction => {}
+            }
+        }
+
+        symbol
+    }
+
     pub fn highlight(mut self, position: usize, length: usize) -> Self {
         self.annotations.push(ParseErrorAnnotation {
             r#type: ParseErrorAnnotationType::Hint,
@@ -105,6 +338,44 @@ impl ParseError {
         self
     }
 
+    /// Translate the span and annotations of this error using the given mapper.
+    ///
+    /// Annotations which cannot be mapped are dropped, and the error is located at the start of
+    /// the original source when its span cannot be mapped.
+    pub fn mapped(&self, mapper: &dyn SpanMapper) -> Self {
+        let position = |position: usize| {
+            mapper
+                .map_span(Span::new(0, 0, position))
+                .map(|span| span.position)
+        };
+
+        Self {
+            id: self.id.clone(),
+            message: self.message.clone(),
+            span: mapper
+                .map_span(self.span)
+                .unwrap_or_else(|| Span::new(1, 1, 0)),
+            annotations: self
+                .annotations
+                .iter()
+                .filter_map(|annotation| {
+                    let start = position(annotation.position)?;
+                    let end = position(annotation.position + annotation.length)?;
+
+                    Some(ParseErrorAnnotation {
+                        position: start,
+                        length: end.saturating_sub(start),
+                        ..annotation.clone()
+                    })
+                })
+                .collect(),
+            note: self.note.clone(),
+            scopes: self.scopes.clone(),
+            arguments: self.arguments.clone(),
+            variant: self.variant.clone(),
+        }
+    }
+
     pub fn report<'a>(
         &self,
         source: &'a str,
@@ -132,7 +403,7 @@ impl ParseError {
                 origin,
                 annotation.position..annotation.position + annotation.length,
             ))
-            .with_order(order.try_into().unwrap());
+            .with_order(order.try_into().unwrap_or(i32::MAX));
 
             if !annotation.message.is_empty() {
                 label = label.with_message(&annotation.message);
@@ -167,7 +438,14 @@ impl ParseError {
     }
 }
 
-pub fn unexpected_token(expected: Vec<String>, found: &Token) -> ParseError {
+/// The name of the given class for messages, qualified with the current namespace.
+fn class_name(state: &mut State, class: Option<&SimpleIdentifier>) -> String {
+    class
+        .map(|class| state.named(class))
+        .unwrap_or_else(|| "anonymous@class".to_string())
+}
+
+pub(crate) fn unexpected_token(expected: Vec<String>, found: &Token) -> ParseError {
     let (found_name, eof) = match &found.kind {
         TokenKind::Eof => ("end of file".to_string(), true),
         kind => match kind {
@@ -184,9 +462,9 @@ pub fn unexpected_token(expected: Vec<String>, found: &Token) -> ParseError {
 
     if expected.is_empty() {
         return if eof {
-            ParseError::new("E002", format!("unexpected {}", found_name), found.span)
+            ParseError::templated("E002", found.span, &[("found", &found_name)])
         } else {
-            ParseError::new("E003", format!("unexpected {}", found_name), found.span).error(
+            ParseError::templated("E003", found.span, &[("found", &found_name)]).error(
                 "try removing this".to_string(),
                 found.span.position,
                 found.value.len(),
@@ -214,10 +492,10 @@ pub fn unexpected_token(expected: Vec<String>, found: &Token) -> ParseError {
         expected.join(", or ")
     };
 
-    ParseError::new(
+    ParseError::templated(
         "E005",
-        format!("unexpected {}, expecting {}", found_name, expected),
         found.span,
+        &[("found", &found_name), ("expected", &expected)],
     )
     .error(
         format!("expected {}", expected),
@@ -226,7 +504,11 @@ pub fn unexpected_token(expected: Vec<String>, found: &Token) -> ParseError {
     )
 }
 
-pub fn unexpected_identifier(expected: Vec<String>, found: String, span: Span) -> ParseError {
+pub(crate) fn unexpected_identifier(
+    expected: Vec<String>,
+    found: String,
+    span: Span,
+) -> ParseError {
     let length = expected.len();
     let expected = if length >= 2 {
         let (left, right) = expected.split_at(length - 1);
@@ -236,126 +518,112 @@ pub fn unexpected_identifier(expected: Vec<String>, found: String, span: Span) -
         expected.join("")
     };
 
-    ParseError::new(
-        "E006",
-        format!(
-            "unexpected identifier `{}`, expecting `{}`",
-            found, expected
-        ),
-        span,
-    )
-    .error(
+    ParseError::templated("E006", span, &[("found", &found), ("expected", &expected)]).error(
         format!("try replacing this with `{}`", expected),
         span.position,
         found.len(),
     )
 }
 
-pub fn multiple_modifiers(modifier: String, first: Span, second: Span) -> ParseError {
-    ParseError::new(
-        "E007",
-        format!("multiple `{}` modifiers are not allowed", modifier),
-        second,
-    )
-    .highlight(first.position, modifier.len())
-    .error("try removing this", second.position, modifier.len())
+pub(crate) fn multiple_modifiers(modifier: String, first: Span, second: Span) -> ParseError {
+    ParseError::templated("E007", second, &[("modifier", &modifier)])
+        .highlight(first.position, modifier.len())
+        .error("try removing this", second.position, modifier.len())
 }
 
-pub fn multiple_visibility_modifiers(first: (String, Span), second: (String, Span)) -> ParseError {
-    ParseError::new(
-        "E008",
-        "multiple visibility modifiers are not allowed",
-        second.1,
-    )
-    .highlight(first.1.position, first.0.len())
-    .error("try removing this", second.1.position, second.0.len())
+pub(crate) fn multiple_visibility_modifiers(
+    first: (String, Span),
+    second: (String, Span),
+) -> ParseError {
+    ParseError::templated("E008", second.1, &[])
+        .highlight(first.1.position, first.0.len())
+        .error("try removing this", second.1.position, second.0.len())
 }
 
-pub fn standalone_type_used_as_nullable(ty: &Type, span: Span) -> ParseError {
+pub(crate) fn standalone_type_used_as_nullable(ty: &Type, span: Span) -> ParseError {
     let type_span = ty.first_span();
     let type_string = ty.to_string();
 
-    ParseError::new(
-        "E009",
-        format!("standalone type `{}` cannot be nullable", type_string),
-        type_span,
-    )
-    .error("try removing this", span.position, 1)
-    .highlight(type_span.position, type_string.len())
-    .note("`never`, `void`, and `mixed` cannot be nullable")
+    ParseError::templated("E009", type_span, &[("type", &type_string)])
+        .error("try removing this", span.position, 1)
+        .highlight(type_span.position, type_string.len())
+        .note("`never`, `void`, and `mixed` cannot be nullable")
 }
 
-pub fn standalone_type_used_in_union(ty: &Type, span: Span) -> ParseError {
+pub(crate) fn standalone_type_used_in_union(ty: &Type, span: Span) -> ParseError {
     let type_span = ty.first_span();
     let type_string = ty.to_string();
 
-    ParseError::new(
-        "E010",
-        format!(
-            "standalone type `{}` cannot be used in a union",
-            type_string
-        ),
-        type_span,
-    )
-    .error(
-        format!("try using a type other than `{}`", type_string),
-        type_span.position,
-        type_string.len(),
-    )
-    .highlight(span.position, 1)
-    .note("`never`, `void`, `mixed`, and nullable types cannot be used in a union")
+    ParseError::templated("E010", type_span, &[("type", &type_string)])
+        .error(
+            format!("try using a type other than `{}`", type_string),
+            type_span.position,
+            type_string.len(),
+        )
+        .highlight(span.position, 1)
+        .note("`never`, `void`, `mixed`, and nullable types cannot be used in a union")
+}
+
+pub(crate) fn duplicate_type(ty: &Type, previous: &Type) -> ParseError {
+    let type_span = ty.first_span();
+    let type_string = ty.to_string();
+    let previous_span = previous.first_span();
+
+    ParseError::templated("E074", type_span, &[("type", &type_string)])
+        .error("try removing this", type_span.position, type_string.len())
+        .highlight(previous_span.position, previous.to_string().len())
 }
 
-pub fn standalone_type_used_in_intersection(ty: &Type, span: Span) -> ParseError {
+pub(crate) fn redundant_type(ty: &Type, other: &Type) -> ParseError {
     let type_span = ty.first_span();
     let type_string = ty.to_string();
+    let other_span = other.first_span();
+    let other_string = other.to_string();
 
-    ParseError::new(
-        "E011",
-        format!(
-            "standalone type `{}` cannot be used in an intersection",
-            type_string
-        ),
+    ParseError::templated(
+        "E075",
         type_span,
+        &[("type", &type_string), ("other", &other_string)],
     )
-    .error(
-        format!("try using a type other than `{}`", type_string),
-        type_span.position,
-        type_string.len(),
-    )
-    .highlight(span.position, 1)
-    .note("`never`, `void`, `mixed`, and nullable types cannot be used in an intersection")
+    .error("try removing this", type_span.position, type_string.len())
+    .highlight(other_span.position, other_string.len())
 }
 
-pub fn try_without_catch_or_finally(try_span: Span, last_right_brace: Span) -> ParseError {
-    ParseError::new(
-        "E012",
-        "cannot use `try` without `catch` or `finally`",
-        try_span,
-    )
-    .highlight(
+pub(crate) fn standalone_type_used_in_intersection(ty: &Type, span: Span) -> ParseError {
+    let type_span = ty.first_span();
+    let type_string = ty.to_string();
+
+    ParseError::templated("E011", type_span, &[("type", &type_string)])
+        .error(
+            format!("try using a type other than `{}`", type_string),
+            type_span.position,
+            type_string.len(),
+        )
+        .highlight(span.position, 1)
+        .note("`never`, `void`, `mixed`, and nullable types cannot be used in an intersection")
+}
+
+pub(crate) fn try_without_catch_or_finally(try_span: Span, last_right_brace: Span) -> ParseError {
+    ParseError::templated("E012", try_span, &[]).highlight(
         try_span.position,
         last_right_brace.position - try_span.position + 1,
     )
 }
 
-pub fn variadic_promoted_property(
+pub(crate) fn variadic_promoted_property(
     state: &mut State,
     class: Option<&SimpleIdentifier>,
     property: &SimpleVariable,
     span: Span,
     modifier: &PromotedPropertyModifier,
 ) -> ParseError {
-    let error = ParseError::new(
+    let error = ParseError::templated(
         "E013",
-        format!(
-            "promoted property `{}::{}` cannot declare variadic",
-            class
-                .map(|c| state.named(c))
-                .unwrap_or_else(|| "anonymous@class".to_string()),
-            property.name
-        ),
         span,
+        &[
+            ("class", &class_name(state, class)),
+            ("property", &property.name),
+        ],
     )
     .highlight(modifier.span().position, modifier.to_string().len())
     .highlight(property.span.position, property.name.len())
@@ -368,22 +636,19 @@ pub fn variadic_promoted_property(
     }
 }
 
-pub fn missing_type_for_readonly_property(
+pub(crate) fn missing_type_for_readonly_property(
     state: &mut State,
     class: Option<&SimpleIdentifier>,
     property: &SimpleVariable,
     readonly_span: Span,
 ) -> ParseError {
-    let error = ParseError::new(
+    let error = ParseError::templated(
         "E014",
-        format!(
-            "missing type for readonly property `{}::{}`",
-            class
-                .map(|c| state.named(c))
-                .unwrap_or_else(|| "anonymous@class".to_string()),
-            property.name
-        ),
         property.span,
+        &[
+            ("class", &class_name(state, class)),
+            ("property", &property.name),
+        ],
     )
     .error(
         format!("try adding a type before `{}`", property.name),
@@ -399,22 +664,21 @@ pub fn missing_type_for_readonly_property(
     }
 }
 
-pub fn abstract_method_on_a_non_abstract_class(
+pub(crate) fn abstract_method_on_a_non_abstract_class(
     state: &mut State,
     class: &SimpleIdentifier,
     method: &SimpleIdentifier,
     abstract_span: Span,
     semicolon_span: Span,
 ) -> ParseError {
-    ParseError::new(
+    ParseError::templated(
         "E015",
-        format!(
-            "cannot declare method `{}::{}` abstract, as `{}` class is not abstract",
-            state.named(&class),
-            method.value,
-            class,
-        ),
         semicolon_span,
+        &[
+            ("class", &state.named(&class)),
+            ("method", &method.value),
+            ("name", class),
+        ],
     )
     .error(
         "try removing this `abstract` modifier",
@@ -425,40 +689,29 @@ pub fn abstract_method_on_a_non_abstract_class(
     .highlight(method.span.position, method.value.len())
 }
 
-pub fn constructor_in_enum(
+pub(crate) fn constructor_in_enum(
     state: &mut State,
     r#enum: &SimpleIdentifier,
     constructor: &SimpleIdentifier,
 ) -> ParseError {
-    ParseError::new(
-        "E016",
-        format!(
-            "cannot declare a constructor on enum `{}`",
-            state.named(&r#enum)
-        ),
-        constructor.span,
-    )
-    .error(
-        "try removing this constructor",
-        constructor.span.position,
-        constructor.value.len(),
-    )
-    .highlight(r#enum.span.position, r#enum.value.len())
+    ParseError::templated("E016", constructor.span, &[("enum", &state.named(&r#enum))])
+        .error(
+            "try removing this constructor",
+            constructor.span.position,
+            constructor.value.len(),
+        )
+        .highlight(r#enum.span.position, r#enum.value.len())
 }
 
-pub fn magic_method_in_enum(
+pub(crate) fn magic_method_in_enum(
     state: &mut State,
     r#enum: &SimpleIdentifier,
     method: &SimpleIdentifier,
 ) -> ParseError {
-    ParseError::new(
+    ParseError::templated(
         "E017",
-        format!(
-            "cannot declare magic method `{}::{}` in an enum",
-            state.named(&r#enum),
-            method.value
-        ),
         method.span,
+        &[("enum", &state.named(&r#enum)), ("method", &method.value)],
     )
     .error(
         "try removing this magic method",
@@ -468,83 +721,71 @@ pub fn magic_method_in_enum(
     .highlight(r#enum.span.position, r#enum.value.len())
 }
 
-pub fn missing_case_value_for_backed_enum(
+pub(crate) fn missing_case_value_for_backed_enum(
     state: &mut State,
     r#enum: &SimpleIdentifier,
     case: &SimpleIdentifier,
     semicolon_span: Span,
 ) -> ParseError {
-    ParseError::new(
+    ParseError::templated(
         "E018",
-        format!(
-            "case `{}::{}` of backed enum `{}` must have a value",
-            state.named(&r#enum),
-            case,
-            r#enum
-        ),
         semicolon_span,
+        &[
+            ("enum", &state.named(&r#enum)),
+            ("case", case),
+            ("name", r#enum),
+        ],
     )
     .error("try adding a value", semicolon_span.position, 1)
     .highlight(case.span.position, case.value.len())
     .highlight(r#enum.span.position, r#enum.value.len())
 }
 
-pub fn case_value_for_unit_enum(
+pub(crate) fn case_value_for_unit_enum(
     state: &mut State,
     r#enum: &SimpleIdentifier,
     case: &SimpleIdentifier,
     equals_span: Span,
 ) -> ParseError {
-    ParseError::new(
+    ParseError::templated(
         "E019",
-        format!(
-            "case `{}::{}` of unit enum `{}` cannot have a value",
-            state.named(&r#enum),
-            case,
-            r#enum
-        ),
         equals_span,
+        &[
+            ("enum", &state.named(&r#enum)),
+            ("case", case),
+            ("name", r#enum),
+        ],
     )
     .error("try replacing this with `;`", equals_span.position, 1)
     .highlight(case.span.position, case.value.len())
     .highlight(r#enum.span.position, r#enum.value.len())
 }
 
-pub fn modifier_cannot_be_used_for_constant(modifier: String, modifier_span: Span) -> ParseError {
-    ParseError::new(
-        "E020",
-        format!("cannot use '{}' as constant modifier", modifier),
-        modifier_span,
-    )
-    .error("try removing this", modifier_span.position, modifier.len())
-    .note("only `public`, `protected`, `private`, and `final` modifiers can be used on constants")
+pub(crate) fn modifier_cannot_be_used_for_constant(
+    modifier: String,
+    modifier_span: Span,
+) -> ParseError {
+    ParseError::templated("E020", modifier_span, &[("modifier", &modifier)])
+        .error("try removing this", modifier_span.position, modifier.len())
+        .note(
+            "only `public`, `protected`, `private`, and `final` modifiers can be used on constants",
+        )
 }
 
-pub fn modifier_cannot_be_used_for_interface_constant(
+pub(crate) fn modifier_cannot_be_used_for_interface_constant(
     modifier: String,
     modifier_span: Span,
 ) -> ParseError {
-    ParseError::new(
-        "E021",
-        format!(
-            "cannot use '{}' as an interface constant modifier",
-            modifier
-        ),
-        modifier_span,
-    )
-    .error("try removing this", modifier_span.position, modifier.len())
-    .note("only `public`, and `final` modifiers can be used on interface constants")
+    ParseError::templated("E021", modifier_span, &[("modifier", &modifier)])
+        .error("try removing this", modifier_span.position, modifier.len())
+        .note("only `public`, and `final` modifiers can be used on interface constants")
 }
 
-pub fn modifier_cannot_be_used_for_promoted_property(
+pub(crate) fn modifier_cannot_be_used_for_promoted_property(
     modifier: String,
     modifier_span: Span,
 ) -> ParseError {
-    ParseError::new(
-        "E022",
-        format!("cannot use '{}' as a promoted property modifier", modifier),
-        modifier_span,
-    )
+    ParseError::templated("E022", modifier_span, &[("modifier", &modifier)])
     .error(
         "try removing this",
         modifier_span.position,
@@ -553,12 +794,11 @@ pub fn modifier_cannot_be_used_for_promoted_property(
     .note("only `public`, `protected`, `private`, and `readonly` modifiers can be used on promoted properties")
 }
 
-pub fn modifier_cannot_be_used_for_property(modifier: String, modifier_span: Span) -> ParseError {
-    ParseError::new(
-        "E023",
-        format!("cannot use '{}' as a property modifier", modifier),
-        modifier_span,
-    )
+pub(crate) fn modifier_cannot_be_used_for_property(
+    modifier: String,
+    modifier_span: Span,
+) -> ParseError {
+    ParseError::templated("E023", modifier_span, &[("modifier", &modifier)])
     .error(
         "try removing this",
         modifier_span.position,
@@ -567,25 +807,20 @@ pub fn modifier_cannot_be_used_for_property(modifier: String, modifier_span: Spa
     .note("only `public`, `protected`, `private`, `static`, and `readonly` modifiers can be used on properties")
 }
 
-pub fn modifier_cannot_be_used_for_class(modifier: String, modifier_span: Span) -> ParseError {
-    ParseError::new(
-        "E024",
-        format!("cannot use '{}' as a class modifier", modifier),
-        modifier_span,
-    )
-    .error("try removing this", modifier_span.position, modifier.len())
-    .note("only `final`, `abstract`, and `readonly` modifiers can be used on classes")
+pub(crate) fn modifier_cannot_be_used_for_class(
+    modifier: String,
+    modifier_span: Span,
+) -> ParseError {
+    ParseError::templated("E024", modifier_span, &[("modifier", &modifier)])
+        .error("try removing this", modifier_span.position, modifier.len())
+        .note("only `final`, `abstract`, and `readonly` modifiers can be used on classes")
 }
 
-pub fn modifier_cannot_be_used_for_class_method(
+pub(crate) fn modifier_cannot_be_used_for_class_method(
     modifier: String,
     modifier_span: Span,
 ) -> ParseError {
-    ParseError::new(
-        "E025",
-        format!("cannot use '{}' as a class method modifier", modifier),
-        modifier_span,
-    )
+    ParseError::templated("E025", modifier_span, &[("modifier", &modifier)])
     .error(
         "try removing this",
         modifier_span.position,
@@ -594,15 +829,11 @@ pub fn modifier_cannot_be_used_for_class_method(
     .note("only `public`, `protected`, `private`, `final`, `static`, and `abstract` modifiers can be used on class methods")
 }
 
-pub fn modifier_cannot_be_used_for_enum_method(
+pub(crate) fn modifier_cannot_be_used_for_enum_method(
     modifier: String,
     modifier_span: Span,
 ) -> ParseError {
-    ParseError::new(
-        "E026",
-        format!("cannot use '{}' as an enum method modifier", modifier),
-        modifier_span,
-    )
+    ParseError::templated("E026", modifier_span, &[("modifier", &modifier)])
     .error(
         "try removing this",
         modifier_span.position,
@@ -611,92 +842,65 @@ pub fn modifier_cannot_be_used_for_enum_method(
     .note("only `public`, `protected`, `private`, `final`, and `static` modifiers can be used on enum methods")
 }
 
-pub fn modifier_cannot_be_used_for_interface_method(
+pub(crate) fn modifier_cannot_be_used_for_interface_method(
     modifier: String,
     modifier_span: Span,
 ) -> ParseError {
-    ParseError::new(
-        "E027",
-        format!("cannot use '{}' as an interface method modifier", modifier),
-        modifier_span,
-    )
-    .error("try removing this", modifier_span.position, modifier.len())
-    .note("only `public`, and `static` modifiers can be used on interface methods")
+    ParseError::templated("E027", modifier_span, &[("modifier", &modifier)])
+        .error("try removing this", modifier_span.position, modifier.len())
+        .note("only `public`, and `static` modifiers can be used on interface methods")
 }
 
-pub fn final_and_abstract_modifiers_combined_for_class(
+pub(crate) fn final_and_abstract_modifiers_combined_for_class(
     final_span: Span,
     abstract_span: Span,
 ) -> ParseError {
-    ParseError::new(
-        "E028",
-        "cannot declare a `final` class as `abstract`",
-        abstract_span,
-    )
-    .highlight(final_span.position, "final".len())
-    .error(
-        "try removing this",
-        abstract_span.position,
-        "abstract".len(),
-    )
+    ParseError::templated("E028", abstract_span, &[])
+        .highlight(final_span.position, "final".len())
+        .error(
+            "try removing this",
+            abstract_span.position,
+            "abstract".len(),
+        )
 }
 
-pub fn final_and_abstract_modifiers_combined_for_class_member(
+pub(crate) fn final_and_abstract_modifiers_combined_for_class_member(
     final_span: Span,
     abstract_span: Span,
 ) -> ParseError {
-    ParseError::new(
-        "E029",
-        "cannot declare a `final` class member as `abstract`",
-        abstract_span,
-    )
-    .highlight(final_span.position, "final".len())
-    .error(
-        "try removing this",
-        abstract_span.position,
-        "abstract".len(),
-    )
+    ParseError::templated("E029", abstract_span, &[])
+        .highlight(final_span.position, "final".len())
+        .error(
+            "try removing this",
+            abstract_span.position,
+            "abstract".len(),
+        )
 }
 
-pub fn final_and_private_modifiers_combined_for_constant(
+pub(crate) fn final_and_private_modifiers_combined_for_constant(
     final_span: Span,
     private_span: Span,
 ) -> ParseError {
-    ParseError::new(
-        "E030",
-        "cannot declare a `private` constant as `final`",
-        final_span,
-    )
-    .highlight(private_span.position, "private".len())
-    .error("try removing this", final_span.position, "final".len())
-    .note("private constants cannot be final as they are not visible to other classes")
-}
-
-pub fn reached_unpredictable_state(span: Span) -> ParseError {
-    ParseError::new("E031", "reached unpredictable state", span).error(
-        "please report this as a bug",
-        span.position,
-        1,
-    )
+    ParseError::templated("E030", final_span, &[])
+        .highlight(private_span.position, "private".len())
+        .error("try removing this", final_span.position, "final".len())
+        .note("private constants cannot be final as they are not visible to other classes")
 }
 
-pub fn static_property_cannot_be_readonly(
+pub(crate) fn static_property_cannot_be_readonly(
     state: &mut State,
     class: Option<&SimpleIdentifier>,
     property: &SimpleVariable,
     static_span: Span,
     readonly_span: Span,
 ) -> ParseError {
-    let error = ParseError::new(
+    let error = ParseError::templated(
         "E032",
-        format!(
-            "cannot declare `readonly` property `{}::{}` as 'static'",
-            class
-                .map(|c| state.named(c))
-                .unwrap_or_else(|| "anonymous@class".to_string()),
-            property.name,
-        ),
         static_span,
+        &[
+            ("class", &class_name(state, class)),
+            ("property", &property.name),
+        ],
     )
     .highlight(property.span.position, property.name.len())
     .highlight(readonly_span.position, "readonly".len())
@@ -710,23 +914,20 @@ pub fn static_property_cannot_be_readonly(
     }
 }
 
-pub fn readonly_property_has_default_value(
+pub(crate) fn readonly_property_has_default_value(
     state: &mut State,
     class: Option<&SimpleIdentifier>,
     property: &SimpleVariable,
     readonly_span: Span,
     equals_span: Span,
 ) -> ParseError {
-    let error = ParseError::new(
+    let error = ParseError::templated(
         "E033",
-        format!(
-            "readonly property `{}::{}` cannot have a default value",
-            class
-                .map(|c| state.named(c))
-                .unwrap_or_else(|| "anonymous@class".to_string()),
-            property.name,
-        ),
         equals_span,
+        &[
+            ("class", &class_name(state, class)),
+            ("property", &property.name),
+        ],
     )
     .highlight(property.span.position, property.name.len())
     .highlight(readonly_span.position, "readonly".len())
@@ -740,33 +941,31 @@ pub fn readonly_property_has_default_value(
     }
 }
 
-pub fn unbraced_namespace_declarations_in_braced_context(span: Span) -> ParseError {
-    ParseError::new(
-        "E034",
-        "cannot mix braced and unbraced namespace declarations",
-        span,
+pub(crate) fn unbraced_namespace_declarations_in_braced_context(span: Span) -> ParseError {
+    ParseError::templated("E034", span, &[]).error(
+        "try replacing this `;` with `{`",
+        span.position,
+        1,
     )
-    .error("try replacing this `;` with `{`", span.position, 1)
 }
 
-pub fn braced_namespace_declarations_in_unbraced_context(span: Span) -> ParseError {
-    ParseError::new(
-        "E035",
-        "cannot mix braced and unbraced namespace declarations",
-        span,
+pub(crate) fn braced_namespace_declarations_in_unbraced_context(span: Span) -> ParseError {
+    ParseError::templated("E035", span, &[]).error(
+        "try replacing this `{` with `;`",
+        span.position,
+        1,
     )
-    .error("try replacing this `{` with `;`", span.position, 1)
 }
 
-pub fn nested_namespace_declarations(span: Span) -> ParseError {
-    ParseError::new("E035", "cannot nest namespace declarations", span).error(
+pub(crate) fn nested_namespace_declarations(span: Span) -> ParseError {
+    ParseError::templated("E035.nested", span, &[]).error(
         "try closing previous namespace with `}` before declaring a new one",
         span.position,
         1,
     )
 }
 
-pub fn forbidden_type_used_in_property(
+pub(crate) fn forbidden_type_used_in_property(
     state: &mut State,
     class: Option<&SimpleIdentifier>,
     property: &SimpleVariable,
@@ -775,17 +974,14 @@ pub fn forbidden_type_used_in_property(
     let type_string = ty.to_string();
     let type_span = ty.first_span();
 
-    let error = ParseError::new(
-        "E037".to_string(),
-        format!(
-            "property `{}::{}` cannot have type `{}`",
-            class
-                .map(|c| state.named(c))
-                .unwrap_or_else(|| "anonymous@class".to_string()),
-            property.name,
-            type_string
-        ),
+    let error = ParseError::templated(
+        "E037",
         type_span,
+        &[
+            ("class", &class_name(state, class)),
+            ("property", &property.name),
+            ("type", &type_string),
+        ],
     )
     .highlight(property.span.position, property.name.len())
     .error(
@@ -803,17 +999,13 @@ pub fn forbidden_type_used_in_property(
     }
 }
 
-pub fn match_expression_has_multiple_default_arms(first: Span, second: Span) -> ParseError {
-    ParseError::new(
-        "E038".to_string(),
-        "match expression cannot have more than one default arm",
-        second,
-    )
-    .highlight(first.position, "default".len())
-    .error("try removing this arm", second.position, "default".len())
+pub(crate) fn match_expression_has_multiple_default_arms(first: Span, second: Span) -> ParseError {
+    ParseError::templated("E038", second, &[])
+        .highlight(first.position, "default".len())
+        .error("try removing this arm", second.position, "default".len())
 }
 
-pub fn missing_item_definition_after_attributes(
+pub(crate) fn missing_item_definition_after_attributes(
     attributes: &Vec<AttributeGroup>,
     current: &Token,
 ) -> ParseError {
@@ -844,141 +1036,558 @@ pub fn missing_item_definition_after_attributes(
     });
 
     ParseError {
-        id: "E039".to_string(),
-        message: "missing item definition after attribute(s)".to_string(),
-        span: current.span,
         annotations,
-        note: None,
+        ..ParseError::templated("E039", current.span, &[])
     }
 }
 
-pub fn nested_disjunctive_normal_form_types(span: Span) -> ParseError {
-    ParseError::new(
-        "E040".to_string(),
-        "cannot nest disjunctive normal form types",
-        span,
-    )
-    .error("try removing this", span.position, 1)
+pub(crate) fn nested_disjunctive_normal_form_types(span: Span) -> ParseError {
+    ParseError::templated("E040", span, &[]).error("try removing this", span.position, 1)
 }
 
-pub fn illegal_spread_operator_usage(span: Span) -> ParseError {
-    ParseError::new("E041".to_string(), "illegal spread operator usage", span).error(
-        "try removing this",
-        span.position,
-        3,
-    )
+pub(crate) fn illegal_spread_operator_usage(span: Span) -> ParseError {
+    ParseError::templated("E041", span, &[]).error("try removing this", span.position, 3)
 }
 
-pub fn cannot_assign_reference_to_non_referencable_value(span: Span) -> ParseError {
-    ParseError::new(
-        "E042".to_string(),
-        "cannot assign reference to non-referencable value",
-        span,
-    )
-    .error("try removing this", span.position, 1)
+pub(crate) fn cannot_assign_reference_to_non_referencable_value(span: Span) -> ParseError {
+    ParseError::templated("E042", span, &[]).error("try removing this", span.position, 1)
 }
 
-pub fn mixing_keyed_and_unkeyed_list_entries(span: Span) -> ParseError {
-    ParseError::new(
-        "E043".to_string(),
-        "cannot mix keyed and un-keyed list entries",
-        span,
-    )
-    .error("", span.position, 1)
+pub(crate) fn mixing_keyed_and_unkeyed_list_entries(span: Span) -> ParseError {
+    ParseError::templated("E043", span, &[]).error("", span.position, 1)
 }
 
-pub fn cannot_use_positional_argument_after_named_argument(
+pub(crate) fn cannot_use_positional_argument_after_named_argument(
     span: Span,
     current_span: Span,
 ) -> ParseError {
-    ParseError::new(
-        "E044".to_string(),
-        "cannot use positional argument after named argument",
-        span,
-    )
-    .error(
+    ParseError::templated("E044", span, &[]).error(
         "try adding a name for this argument",
         span.position,
         current_span.position - span.position,
     )
 }
 
-pub fn cannot_use_reserved_keyword_as_a_type_name(span: Span, keyword: String) -> ParseError {
-    ParseError::new(
-        "E045".to_string(),
-        format!("cannot use reserved keyword `{}` as a type name", keyword),
-        span,
+pub(crate) fn cannot_use_positional_argument_after_argument_unpacking(
+    span: Span,
+    current_span: Span,
+) -> ParseError {
+    ParseError::templated("E061", span, &[]).error(
+        "try moving this argument before the unpacked argument",
+        span.position,
+        current_span.position - span.position,
     )
-    .error("try using a different name", span.position, keyword.len())
 }
 
-pub fn cannot_use_reserved_keyword_as_a_goto_label(span: Span, keyword: String) -> ParseError {
-    ParseError::new(
-        "E046".to_string(),
-        format!("cannot use reserved keyword `{}` as a goto label", keyword),
-        span,
+pub(crate) fn cannot_use_reserved_keyword_as_a_type_name(
+    span: Span,
+    keyword: String,
+) -> ParseError {
+    ParseError::templated("E045", span, &[("keyword", &keyword)]).error(
+        "try using a different name",
+        span.position,
+        keyword.len(),
     )
-    .error("try using a different name", span.position, keyword.len())
 }
 
-pub fn cannot_use_reserved_keyword_as_a_constant_name(span: Span, keyword: String) -> ParseError {
-    ParseError::new(
-        "E047".to_string(),
-        format!(
-            "cannot use reserved keyword `{}` as a constant name",
-            keyword
-        ),
-        span,
+pub(crate) fn cannot_use_reserved_keyword_as_a_goto_label(
+    span: Span,
+    keyword: String,
+) -> ParseError {
+    ParseError::templated("E046", span, &[("keyword", &keyword)]).error(
+        "try using a different name",
+        span.position,
+        keyword.len(),
     )
-    .error("try using a different name", span.position, keyword.len())
 }
 
-pub fn cannot_use_type_in_context(span: Span, ty: String) -> ParseError {
-    ParseError::new(
-        "E048".to_string(),
-        format!("cannot use type `{}` in current context", ty),
-        span,
+pub(crate) fn cannot_use_reserved_keyword_as_a_constant_name(
+    span: Span,
+    keyword: String,
+) -> ParseError {
+    ParseError::templated("E047", span, &[("keyword", &keyword)]).error(
+        "try using a different name",
+        span.position,
+        keyword.len(),
     )
-    .error("try using a different type", span.position, ty.len())
 }
 
-pub fn only_positional_arguments_are_accepted(span: Span, current_span: Span) -> ParseError {
-    ParseError::new(
-        "E049".to_string(),
-        "cannot use named argument, only positional arguments are accepted",
-        span,
+pub(crate) fn cannot_use_type_in_context(span: Span, ty: String) -> ParseError {
+    ParseError::templated("E048", span, &[("type", &ty)]).error(
+        "try using a different type",
+        span.position,
+        ty.len(),
     )
-    .error(
+}
+
+pub(crate) fn only_positional_arguments_are_accepted(span: Span, current_span: Span) -> ParseError {
+    ParseError::templated("E049", span, &[]).error(
         "try changing this to a positional argument",
         span.position,
         current_span.position - span.position,
     )
 }
 
-pub fn only_one_argument_is_accepted(span: Span, current_span: Span) -> ParseError {
-    ParseError::new("E050".to_string(), "only one argument are accepted", span).error(
+pub(crate) fn only_one_argument_is_accepted(span: Span, current_span: Span) -> ParseError {
+    ParseError::templated("E050", span, &[]).error(
         "try removing this argument",
         span.position,
         current_span.position - span.position,
     )
 }
 
-pub fn argument_is_required(span: Span, current_span: Span) -> ParseError {
-    ParseError::new("E051".to_string(), "argument is required", span).error(
+pub(crate) fn argument_is_required(span: Span, current_span: Span) -> ParseError {
+    ParseError::templated("E051", span, &[]).error(
         "try passing an argument",
         span.position,
         current_span.position - span.position,
     )
 }
 
+pub(crate) fn cannot_use_this_outside_of_object_context(span: Span) -> ParseError {
+    ParseError::templated("E052", span, &[]).error(
+        "`$this` is not available in this scope",
+        span.position,
+        5,
+    )
+}
+
+pub(crate) fn cannot_use_this_in_static_context(span: Span, r#static: Span) -> ParseError {
+    ParseError::templated("E053", span, &[])
+        .error("`$this` is not available in this scope", span.position, 5)
+        .highlight(r#static.position, 6)
+        .note("static methods and static closures are not bound to an object")
+}
+
+pub(crate) fn cannot_use_class_scoped_keyword_outside_of_class_scope(
+    span: Span,
+    keyword: &str,
+) -> ParseError {
+    ParseError::templated("E054", span, &[("keyword", &keyword)]).error(
+        format!("`{}` used outside of a class scope", keyword),
+        span.position,
+        keyword.len(),
+    )
+}
+
+pub(crate) fn cannot_use_parent_without_parent_class(
+    state: &mut State,
+    span: Span,
+    class: Option<&SimpleIdentifier>,
+) -> ParseError {
+    let error = match class {
+        Some(class) => ParseError::templated("E055", span, &[("class", &state.named(class))]),
+        None => ParseError::templated("E055.anonymous", span, &[]),
+    }
+    .error("`parent` used here", span.position, 6);
+
+    match class {
+        Some(class) => error.highlight(class.span.position, class.value.len()),
+        None => error,
+    }
+}
+
+pub(crate) fn loop_level_must_be_an_integer_literal(span: Span, keyword: &str) -> ParseError {
+    ParseError::templated("E056", span, &[("keyword", &keyword)]).error(
+        "try using an integer literal",
+        span.position,
+        1,
+    )
+}
+
+pub(crate) fn loop_level_must_be_positive(start: Span, end: Span, keyword: &str) -> ParseError {
+    ParseError::templated("E057", start, &[("keyword", &keyword)]).error(
+        "try removing the level, or using `1`",
+        start.position,
+        end.position - start.position + 1,
+    )
+}
+
+pub(crate) fn cannot_use_loop_keyword_outside_of_loop(
+    start: Span,
+    end: Span,
+    keyword: &str,
+) -> ParseError {
+    ParseError::templated("E058", start, &[("keyword", &keyword)]).error(
+        "try removing this statement",
+        start.position,
+        end.position - start.position + 1,
+    )
+}
+
+pub(crate) fn loop_level_exceeds_depth(
+    start: Span,
+    end: Span,
+    keyword: &str,
+    levels: usize,
+) -> ParseError {
+    ParseError::templated("E059", start, &[("keyword", &keyword), ("levels", &levels)]).error(
+        format!("`{}` targets more levels than are enclosing it", keyword),
+        start.position,
+        end.position - start.position + 1,
+    )
+}
+
+pub(crate) fn cannot_assign_new_by_reference(ampersand: Span, new: Span) -> ParseError {
+    ParseError::templated("E060", ampersand, &[])
+        .error("try removing this `&`", ampersand.position, 1)
+        .highlight(new.position, 3)
+        .note("assigning the result of `new` by reference was removed in PHP 7.0")
+}
+
+pub(crate) fn declare_must_be_first_statement(declare: Span, key: &SimpleIdentifier) -> ParseError {
+    ParseError::templated("E062", declare, &[("key", &key)])
+        .error(
+            "try moving this declaration to the top of the file",
+            key.span.position,
+            key.value.len(),
+        )
+        .highlight(declare.position, 7)
+}
+
+pub(crate) fn strict_types_must_not_use_block_mode(
+    declare: Span,
+    key: &SimpleIdentifier,
+) -> ParseError {
+    ParseError::templated("E063", declare, &[])
+        .error(
+            "try terminating this declaration with `;`",
+            key.span.position,
+            key.value.len(),
+        )
+        .highlight(declare.position, 7)
+}
+
+pub(crate) fn strict_types_value_must_be_zero_or_one(value: &Literal) -> ParseError {
+    let span = value.span();
+
+    ParseError::templated("E064", span, &[]).error(
+        "try using `0` or `1`",
+        span.position,
+        value.value().len(),
+    )
+}
+
+pub(crate) fn cancelled(span: Span) -> ParseError {
+    ParseError::templated("E065", span, &[])
+}
+
+pub(crate) fn missing_semicolon(span: Span, previous: &Token) -> ParseError {
+    ParseError::templated("E066", span, &[]).error(
+        "try adding `;` after this",
+        previous.span.position,
+        previous.value.len(),
+    )
+}
+
+pub(crate) fn unexpected_interpolated_expression(start: Span, end: Span) -> ParseError {
+    ParseError::templated("E068", start, &[])
+        .error(
+            "this expression cannot be interpolated",
+            start.position,
+            end.position - start.position,
+        )
+        .note("only variables, array indexes, property fetches and calls can be interpolated with `{$`")
+}
+
+pub(crate) fn name_already_in_use(
+    kind: &UseKind,
+    name: &str,
+    alias: &str,
+    identifier: &SimpleIdentifier,
+    previous: &SimpleIdentifier,
+    imported: bool,
+) -> ParseError {
+    let key = match kind {
+        UseKind::Function => "E069.function",
+        UseKind::Const => "E069.const",
+        _ => "E069",
+    };
+
+    ParseError::templated(key, identifier.span, &[("name", &name), ("alias", &alias)])
+        .highlight(previous.span.position, previous.value.len())
+        .error(
+            if imported {
+                "the name is already imported"
+            } else {
+                "the name is already declared"
+            },
+            identifier.span.position,
+            identifier.value.len(),
+        )
+        .note("try importing the name using a different alias")
+}
+
+pub(crate) fn cannot_declare_imported_name(
+    kind: &str,
+    name: &SimpleIdentifier,
+    qualified: &str,
+    import: &SimpleIdentifier,
+) -> ParseError {
+    ParseError::templated("E070", name.span, &[("kind", &kind), ("name", &qualified)])
+        .highlight(import.span.position, import.value.len())
+        .error(
+            format!("try renaming this {}", kind),
+            name.span.position,
+            name.value.len(),
+        )
+}
+
+pub(crate) fn unsupported_feature(
+    feature: &str,
+    version: PhpVersion,
+    target: PhpVersion,
+    span: Span,
+    length: usize,
+) -> ParseError {
+    ParseError::templated(
+        "E071",
+        span,
+        &[("feature", &feature), ("version", &version)],
+    )
+    .error(
+        format!("this is not supported by PHP {}", target),
+        span.position,
+        length,
+    )
+    .note(format!("the targeted version is PHP {}", target))
+}
+
+pub(crate) fn removed_feature(
+    feature: &str,
+    version: PhpVersion,
+    target: PhpVersion,
+    span: Span,
+    length: usize,
+) -> ParseError {
+    ParseError::templated(
+        "E072",
+        span,
+        &[("feature", &feature), ("version", &version)],
+    )
+    .error("this is no longer supported", span.position, length)
+    .note(format!("the targeted version is PHP {}", target))
+}
+
+pub(crate) fn experimental_feature(feature: &str, span: Span, length: usize) -> ParseError {
+    ParseError::templated("E081", span, &[("feature", &feature)])
+        .error(
+            "this is not part of a released version of PHP yet",
+            span.position,
+            length,
+        )
+        .note("try enabling `ParserOptions::experimental`")
+}
+
+pub(crate) fn unused_suppression(directive: &str, span: Span, length: usize) -> ParseError {
+    ParseError::templated("E082", span, &[("directive", &directive)]).error(
+        "no error is suppressed by this comment",
+        span.position,
+        length,
+    )
+}
+
+pub(crate) fn readonly_class_allows_dynamic_properties(
+    state: &mut State,
+    class: Option<&SimpleIdentifier>,
+    attribute: &Attribute,
+    readonly_span: Span,
+) -> ParseError {
+    ParseError::templated(
+        "E073",
+        attribute.name.span,
+        &[("class", &class_name(state, class))],
+    )
+    .error(
+        "readonly classes cannot have dynamic properties",
+        attribute.name.span.position,
+        attribute.name.value.len(),
+    )
+    .highlight(readonly_span.position, 8)
+}
+
+pub(crate) fn unknown_property_hook(
+    state: &mut State,
+    class: Option<&SimpleIdentifier>,
+    property: &SimpleVariable,
+    hook: &SimpleIdentifier,
+) -> ParseError {
+    ParseError::templated(
+        "E076",
+        hook.span,
+        &[
+            ("hook", &hook.value),
+            ("class", &class_name(state, class)),
+            ("property", &property.name),
+        ],
+    )
+    .error(
+        "expected `get` or `set`",
+        hook.span.position,
+        hook.value.len(),
+    )
+    .highlight(property.span.position, property.name.len())
+}
+
+pub(crate) fn duplicate_property_hook(
+    state: &mut State,
+    class: Option<&SimpleIdentifier>,
+    property: &SimpleVariable,
+    hook: &SimpleIdentifier,
+    previous: &SimpleIdentifier,
+) -> ParseError {
+    ParseError::templated(
+        "E077",
+        hook.span,
+        &[
+            ("hook", &hook.value),
+            ("class", &class_name(state, class)),
+            ("property", &property.name),
+        ],
+    )
+    .error(
+        "try removing this hook",
+        hook.span.position,
+        hook.value.len(),
+    )
+    .highlight(previous.span.position, previous.value.len())
+}
+
+/// A property which cannot have hooks, `reason` being `static`, `readonly` or `multiple`, and
+/// `span` the span of the modifier or of the other property.
+pub(crate) fn property_cannot_have_hooks(
+    state: &mut State,
+    class: Option<&SimpleIdentifier>,
+    property: &SimpleVariable,
+    reason: &str,
+    span: Span,
+    left_brace: Span,
+) -> ParseError {
+    let length = match reason {
+        "static" => "static".len(),
+        "readonly" => "readonly".len(),
+        _ => property.name.len(),
+    };
+
+    ParseError::templated(
+        &format!("E078.{}", reason),
+        left_brace,
+        &[
+            ("class", &class_name(state, class)),
+            ("property", &property.name),
+        ],
+    )
+    .highlight(property.span.position, property.name.len())
+    .highlight(span.position, length)
+    .error("hooks are declared here", left_brace.position, 1)
+}
+
+pub(crate) fn abstract_property_without_abstract_hook(
+    state: &mut State,
+    class: Option<&SimpleIdentifier>,
+    property: &SimpleVariable,
+    abstract_span: Span,
+) -> ParseError {
+    ParseError::templated(
+        "E079",
+        property.span,
+        &[
+            ("class", &class_name(state, class)),
+            ("property", &property.name),
+        ],
+    )
+    .error(
+        "try adding a hook without a body, e.g. `{ get; }`",
+        property.span.position,
+        property.name.len(),
+    )
+    .highlight(abstract_span.position, "abstract".len())
+}
+
+pub(crate) fn interface_property_without_hooks(
+    state: &mut State,
+    interface: &SimpleIdentifier,
+    property: &SimpleVariable,
+) -> ParseError {
+    ParseError::templated(
+        "E079.interface",
+        property.span,
+        &[
+            ("class", &class_name(state, Some(interface))),
+            ("property", &property.name),
+        ],
+    )
+    .error(
+        "try adding hooks, e.g. `{ get; }`",
+        property.span.position,
+        property.name.len(),
+    )
+    .highlight(interface.span.position, interface.value.len())
+}
+
+/// A property which cannot have the given set visibility, `reason` being `static`, `type` or
+/// `visibility`, and `span` the span of the static or visibility modifier, if any.
+pub(crate) fn invalid_set_visibility(
+    state: &mut State,
+    class: Option<&SimpleIdentifier>,
+    property: &SimpleVariable,
+    reason: &str,
+    set_visibility: (String, Span),
+    span: Option<(String, Span)>,
+) -> ParseError {
+    let (set_visibility, set_span) = set_visibility;
+    let error = ParseError::templated(
+        &format!("E080.{}", reason),
+        set_span,
+        &[
+            ("class", &class_name(state, class)),
+            ("property", &property.name),
+        ],
+    )
+    .highlight(property.span.position, property.name.len());
+
+    let error = match reason {
+        "type" => error
+            .highlight(set_span.position, set_visibility.len())
+            .error(
+                format!("try adding a type before `{}`", property.name),
+                property.span.position,
+                property.name.len(),
+            ),
+        _ => error.error("try removing this", set_span.position, set_visibility.len()),
+    };
+
+    if let Some((modifier, span)) = span {
+        error.highlight(span.position, modifier.len())
+    } else {
+        error
+    }
+}
+
+pub(crate) fn resource_limit_exceeded(
+    limit: ResourceLimit,
+    maximum: usize,
+    span: Span,
+) -> ParseError {
+    ParseError::templated(
+        &format!("E067.{}", limit.variant()),
+        span,
+        &[("maximum", &maximum)],
+    )
+}
+
+pub(crate) fn incomplete_syntax_tree(recovery: Recovery, span: Span) -> ParseError {
+    ParseError::templated(&format!("E083.{}", recovery.variant()), span, &[])
+        .note("the node was guessed by error recovery")
+}
+
 impl From<SyntaxError> for ParseError {
     fn from(e: SyntaxError) -> Self {
-        Self {
-            id: "E001".to_string(),
-            message: format!("syntax error, {}", e),
-            annotations: vec![],
-            span: e.span(),
-            note: None,
+        match e {
+            SyntaxError::TooManyTokens(maximum, span) => {
+                resource_limit_exceeded(ResourceLimit::TokenCount, maximum, span)
+            }
+            e => Self::templated("E001", e.span(), &[("error", &e)]),
         }
     }
 }
@@ -1,6 +1,7 @@
-use crate::expected_token_err;
 use crate::lexer::token::DocStringKind;
+use crate::lexer::token::Span;
 use crate::lexer::token::TokenKind;
+use crate::parser::ast::arguments::Argument;
 use crate::parser::ast::arguments::ArgumentPlaceholder;
 use crate::parser::ast::identifiers::DynamicIdentifier;
 use crate::parser::ast::identifiers::Identifier;
@@ -8,13 +9,15 @@ use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::literals::Literal;
 use crate::parser::ast::literals::LiteralFloat;
 use crate::parser::ast::literals::LiteralInteger;
-use crate::parser::ast::literals::LiteralString;
 use crate::parser::ast::operators::ArithmeticOperation;
 use crate::parser::ast::operators::AssignmentOperation;
 use crate::parser::ast::operators::BitwiseOperation;
 use crate::parser::ast::operators::ComparisonOperation;
 use crate::parser::ast::operators::LogicalOperation;
+use crate::parser::ast::ExitExpression;
+use crate::parser::ast::ExitKind;
 use crate::parser::ast::{Expression, MagicConstant};
+use crate::parser::completeness::Recovery;
 use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::internal::arrays;
@@ -26,9 +29,13 @@ use crate::parser::internal::identifiers;
 use crate::parser::internal::parameters;
 use crate::parser::internal::precedences::Associativity;
 use crate::parser::internal::precedences::Precedence;
+use crate::parser::internal::scopes;
 use crate::parser::internal::strings;
 use crate::parser::internal::utils;
 use crate::parser::internal::variables;
+use crate::parser::internal::versions;
+use crate::parser::macros::expected_token_err;
+use crate::parser::options::PhpVersion;
 use crate::parser::state::State;
 
 pub fn create(state: &mut State) -> ParseResult<Expression> {
@@ -44,7 +51,12 @@ fn clone_or_new_precedence(state: &mut State) -> ParseResult<Expression> {
 }
 
 fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expression> {
+    state.nested(|state| nested_for_precedence(state, precedence))
+}
+
+fn nested_for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expression> {
     let mut left = left(state, &precedence)?;
+    let mut concatenations = 0;
 
     loop {
         let current = state.stream.current();
@@ -62,6 +74,13 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                 break;
             }
 
+            if !matches!(
+                kind,
+                TokenKind::Increment | TokenKind::Decrement | TokenKind::DoubleQuestion
+            ) {
+                new_without_parentheses(state, &left)?;
+            }
+
             left = postfix(state, left, kind)?;
             continue;
         }
@@ -98,7 +117,9 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                         Expression::Ternary {
                             condition: Box::new(left),
                             question: span,
-                            then: Box::new(Expression::Noop),
+                            then: Box::new(Expression::Noop {
+                                span: span.advance(b"?"),
+                            }),
                             colon: op.span,
                             r#else: Box::new(r#else),
                         }
@@ -131,40 +152,50 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                     //        here, not any old expression.
                     let right = Box::new(for_precedence(state, rpred)?);
 
-                    Expression::AssignmentOperation(AssignmentOperation::Assign {
+                    if let Expression::New { new, .. } = right.as_ref() {
+                        let error = error::cannot_assign_new_by_reference(op.span, *new);
+
+                        state.record(error);
+                    }
+
+                    Expression::AssignmentOperation(AssignmentOperation::AssignByReference {
                         left: Box::new(left),
                         equals: span,
-                        right: Box::new(Expression::Reference {
-                            ampersand: op.span,
-                            right,
-                        }),
+                        ampersand: op.span,
+                        right,
                     })
                 }
                 TokenKind::Instanceof if op.kind == TokenKind::Self_ => {
+                    scopes::class_scoped_keyword(state, op.span, "self");
+
                     state.stream.next();
 
                     Expression::Instanceof {
                         left: Box::new(left),
                         instanceof: span,
-                        right: Box::new(Expression::Self_),
+                        right: Box::new(Expression::Self_ { span: op.span }),
                     }
                 }
                 TokenKind::Instanceof if op.kind == TokenKind::Parent => {
+                    scopes::class_scoped_keyword(state, op.span, "parent");
+
                     state.stream.next();
 
                     Expression::Instanceof {
                         left: Box::new(left),
                         instanceof: span,
-                        right: Box::new(Expression::Parent),
+                        right: Box::new(Expression::Parent { span: op.span }),
                     }
                 }
                 TokenKind::Instanceof if op.kind == TokenKind::Static => {
+                    scopes::class_scoped_keyword(state, op.span, "static");
+
                     state.stream.next();
 
                     Expression::Instanceof {
                         left: Box::new(left),
                         instanceof: span,
-                        right: Box::new(Expression::Static),
+                        right: Box::new(Expression::Static { span: op.span }),
                     }
                 }
                 TokenKind::Instanceof if op.kind == TokenKind::Enum => {
@@ -329,6 +360,14 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                             })
                         }
                         TokenKind::DoubleQuestionEquals => {
+                            versions::requires(
+                                state,
+                                PhpVersion::Php74,
+                                "the null coalescing assignment operator",
+                                span,
+                                3,
+                            );
+
                             Expression::AssignmentOperation(AssignmentOperation::Coalesce {
                                 left,
                                 coalesce_equals: span,
@@ -478,17 +517,39 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                                 right,
                             })
                         }
-                        TokenKind::Dot => Expression::Concat {
-                            left,
-                            dot: span,
-                            right,
-                        },
+                        TokenKind::Dot => {
+                            concatenations += 1;
+
+                            let concat = Expression::Concat {
+                                left,
+                                dot: span,
+                                right,
+                            };
+
+                            if concatenations == CONCATENATION_CHUNK && state.max_depth.is_some() {
+                                concatenations = 0;
+
+                                chunked(concat)
+                            } else {
+                                concat
+                            }
+                        }
                         TokenKind::Instanceof => Expression::Instanceof {
                             left,
                             instanceof: span,
                             right,
                         },
-                        _ => todo!(),
+                        TokenKind::PipeGreaterThan => {
+                            versions::experimental(state, "the pipe operator", span, 2);
+
+                            Expression::Pipe {
+                                left,
+                                pipe_greater_than: span,
+                                right,
+                            }
+                        }
+                        // Guarded by `is_infix`.
+                        _ => unreachable!("infix: {:?}", kind),
                     }
                 }
             };
@@ -502,6 +563,56 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
     Ok(left)
 }
 
+/// The number of operands of a concatenation chain split into their own operand, see [`chunked`].
+pub(in crate::parser) const CONCATENATION_CHUNK: usize = 1024;
+
+/// Split the last operands of a concatenation chain into their own right operand, bounding the
+/// depth of the tree of files chaining thousands of concatenations, e.g. obfuscated code, which
+/// would otherwise overflow the stack of anything recursing through it, even dropping it.
+///
+/// `$a . $b . $c . $d` is `(($a . $b) . $c) . $d`, and with chunks of 3 operands
+/// `$a . (($b . $c) . $d)`, which evaluates the same operands in the same order, into the
+/// same string.
+///
+/// As the grouping differs from the one written, e.g. for a printer, chains are only split
+/// when parsing with a maximum depth, see [`crate::ResourceLimits::max_depth`].
+fn chunked(concat: Expression) -> Expression {
+    let mut operands = Vec::with_capacity(CONCATENATION_CHUNK);
+    let mut base = concat;
+
+    while operands.len() < CONCATENATION_CHUNK {
+        match base {
+            Expression::Concat { left, dot, right } => {
+                operands.push((dot, *right));
+                base = *left;
+            }
+            other => {
+                base = other;
+
+                break;
+            }
+        }
+    }
+
+    let mut operands = operands.into_iter().rev();
+    let (dot, first) = match operands.next() {
+        Some(operand) => operand,
+        None => return base,
+    };
+
+    let chunk = operands.fold(first, |left, (dot, right)| Expression::Concat {
+        left: Box::new(left),
+        dot,
+        right: Box::new(right),
+    });
+
+    Expression::Concat {
+        left: Box::new(base),
+        dot,
+        right: Box::new(chunk),
+    }
+}
+
 fn left(state: &mut State, precedence: &Precedence) -> ParseResult<Expression> {
     if state.stream.is_eof() {
         return Err(error::unexpected_token(vec![], state.stream.current()));
@@ -588,7 +699,9 @@ expressions! {
         let eval = state.stream.current().span;
         state.stream.next();
 
-        let argument = Box::new(parameters::single_argument(state, true, true).unwrap()?);
+        let argument = parameters::single_argument(state, true, true)
+            .unwrap_or_else(|| expected_token_err!("(", state))?;
+        let argument = Box::new(argument);
 
         Ok(Expression::Eval { eval, argument })
     })
@@ -598,42 +711,28 @@ expressions! {
         let empty = state.stream.current().span;
         state.stream.next();
 
-        let argument = Box::new(parameters::single_argument(state, true, true).unwrap()?);
+        let argument = parameters::single_argument(state, true, true)
+            .unwrap_or_else(|| expected_token_err!("(", state))?;
+        let argument = Box::new(argument);
 
         Ok(Expression::Empty { empty, argument })
     })
 
     #[before(exit), current(TokenKind::Die)]
     die({
-        let die = state.stream.current().span;
-        state.stream.next();
-
-        let argument = match parameters::single_argument(state, false, true) {
-            Some(arg) => Some(Box::new(arg?)),
-            None => None,
-        };
-
-        Ok(Expression::Die { die, argument })
+        exit_expression(state, ExitKind::Die)
     })
 
     #[before(isset), current(TokenKind::Exit)]
     exit({
-        let exit = state.stream.current().span;
-        state.stream.next();
-
-        let argument = match parameters::single_argument(state, false, true) {
-            Some(arg) => Some(Box::new(arg?)),
-            None => None,
-        };
-
-        Ok(Expression::Exit { exit, argument })
+        exit_expression(state, ExitKind::Exit)
     })
 
     #[before(unset), current(TokenKind::Isset), peek(TokenKind::LeftParen)]
     isset({
         let isset = state.stream.current().span;
         state.stream.next();
-        let arguments = parameters::argument_list(state)?;
+        let arguments = Box::new(parameters::argument_list(state)?);
 
         Ok(Expression::Isset { isset, arguments})
     })
@@ -642,7 +741,7 @@ expressions! {
     unset({
         let unset = state.stream.current().span;
         state.stream.next();
-        let arguments = parameters::argument_list(state)?;
+        let arguments = Box::new(parameters::argument_list(state)?);
 
         Ok(Expression::Unset { unset, arguments})
     })
@@ -689,56 +788,61 @@ expressions! {
         arrays::list_expression(state)
     })
 
-    #[before(throw), current(TokenKind::New), peek(TokenKind::Class | TokenKind::Attribute)]
+    #[before(throw), current(TokenKind::New), peek(TokenKind::Class | TokenKind::Attribute | TokenKind::Readonly)]
     anonymous_class({
         classes::parse_anonymous(state, None)
     })
 
     #[before(r#yield), current(TokenKind::Throw)]
     throw({
+        let throw = state.stream.current().span;
         state.stream.next();
 
         Ok(Expression::Throw {
+            throw,
             value: Box::new(for_precedence(state, Precedence::Lowest)?)
         })
     })
 
     #[before(clone), current(TokenKind::Yield)]
     r#yield({
+        let r#yield = state.stream.current().span;
         state.stream.next();
         if state.stream.current().kind == TokenKind::SemiColon || state.stream.current().kind == TokenKind::RightParen {
             Ok(Expression::Yield {
+                r#yield,
                 key: None,
                 value: None,
             })
         } else {
-            let mut from = false;
+            let mut from = None;
 
             if state.stream.current().kind == TokenKind::From {
+                from = Some(state.stream.current().span);
                 state.stream.next();
-                from = true;
             }
 
             let mut key = None;
             let mut value = Box::new(for_precedence(
                 state,
-                if from {
+                if from.is_some() {
                     Precedence::YieldFrom
                 } else {
                     Precedence::Yield
                 },
             )?);
 
-            if state.stream.current().kind == TokenKind::DoubleArrow && !from {
+            if state.stream.current().kind == TokenKind::DoubleArrow && from.is_none() {
                 state.stream.next();
                 key = Some(value.clone());
                 value = Box::new(for_precedence(state, Precedence::Yield)?);
             }
 
-            if from {
-                Ok(Expression::YieldFrom { value })
+            if let Some(from) = from {
+                Ok(Expression::YieldFrom { r#yield, from, value })
             } else {
                 Ok(Expression::Yield {
+                    r#yield,
                     key,
                     value: Some(value),
                 })
@@ -748,34 +852,39 @@ expressions! {
 
     #[before(r#true), current(TokenKind::Clone)]
     clone({
+        let clone = state.stream.current().span;
         state.stream.next();
 
         let target = for_precedence(state, Precedence::CloneOrNew)?;
 
         Ok(Expression::Clone {
+            clone,
             target: Box::new(target),
         })
     })
 
     #[before(r#false), current(TokenKind::True)]
     r#true({
+        let span = state.stream.current().span;
         state.stream.next();
 
-        Ok(Expression::Bool { value: true })
+        Ok(Expression::Bool { value: true, span })
     })
 
     #[before(null), current(TokenKind::False)]
     r#false({
+        let span = state.stream.current().span;
         state.stream.next();
 
-        Ok(Expression::Bool { value: false })
+        Ok(Expression::Bool { value: false, span })
     })
 
     #[before(literal_integer), current(TokenKind::Null)]
     null({
+        let span = state.stream.current().span;
         state.stream.next();
 
-        Ok(Expression::Null)
+        Ok(Expression::Null { span })
     })
 
     #[before(literal_float), current(TokenKind::LiteralInteger)]
@@ -788,7 +897,7 @@ expressions! {
             Ok(Expression::Literal(Literal::Integer(
                 LiteralInteger {
                     span: current.span,
-                    value: current.value.clone()
+                    value: current.value.to_byte_string()
                 }
             )))
         } else {
@@ -806,7 +915,7 @@ expressions! {
             Ok(Expression::Literal(
                 Literal::Float(LiteralFloat {
                     span: current.span,
-                    value: current.value.clone()
+                    value: current.value.to_byte_string()
                 })
             ))
         } else {
@@ -822,10 +931,7 @@ expressions! {
             state.stream.next();
 
             Ok(Expression::Literal(
-                Literal::String(LiteralString {
-                    span: current.span,
-                    value: current.value.clone()
-                })
+                Literal::String(strings::literal(state, current))
             ))
         } else {
             unreachable!("{}:{}", file!(), line!());
@@ -859,14 +965,23 @@ expressions! {
 
     #[before(self_identifier), current(TokenKind::Static)]
     static_postfix({
+        let span = state.stream.current().span;
+        if state.stream.peek().kind == TokenKind::DoubleColon {
+            scopes::class_scoped_keyword(state, span, "static");
+        }
+
         state.stream.next();
 
-        postfix(state, Expression::Static, &TokenKind::DoubleColon)
+        postfix(state, Expression::Static { span }, &TokenKind::DoubleColon)
     })
 
     #[before(parent_identifier), current(TokenKind::Self_)]
     self_identifier({
         let span = state.stream.current().span;
+        if state.stream.peek().kind == TokenKind::DoubleColon {
+            scopes::class_scoped_keyword(state, span, "self");
+        }
+
         state.stream.next();
 
         Ok(Expression::Identifier(Identifier::SimpleIdentifier( SimpleIdentifier {
@@ -878,6 +993,10 @@ expressions! {
     #[before(left_parenthesis), current(TokenKind::Parent)]
     parent_identifier({
         let span = state.stream.current().span;
+        if state.stream.peek().kind == TokenKind::DoubleColon {
+            scopes::class_scoped_keyword(state, span, "parent");
+        }
+
         state.stream.next();
 
         Ok(Expression::Identifier(Identifier::SimpleIdentifier( SimpleIdentifier {
@@ -919,25 +1038,34 @@ expressions! {
 
         state.stream.next();
 
-        if state.stream.current().kind == TokenKind::Class || state.stream.current().kind == TokenKind::Attribute {
+        if matches!(state.stream.current().kind, TokenKind::Class | TokenKind::Attribute | TokenKind::Readonly) {
             return classes::parse_anonymous(state, Some(new));
         };
 
         let target = match state.stream.current().kind {
             TokenKind::Self_ => {
+                let span = state.stream.current().span;
+                scopes::class_scoped_keyword(state, span, "self");
+
                 state.stream.next();
 
-                Expression::Self_
+                Expression::Self_ { span }
             }
             TokenKind::Static => {
+                let span = state.stream.current().span;
+                scopes::class_scoped_keyword(state, span, "static");
+
                 state.stream.next();
 
-                Expression::Static
+                Expression::Static { span }
             }
             TokenKind::Parent => {
+                let span = state.stream.current().span;
+                scopes::class_scoped_keyword(state, span, "parent");
+
                 state.stream.next();
 
-                Expression::Parent
+                Expression::Parent { span }
             }
             TokenKind::Enum => {
                 let span = state.stream.current().span;
@@ -956,8 +1084,10 @@ expressions! {
             _ => clone_or_new_precedence(state)?,
         };
 
+        let target = class_name_reference(state, target)?;
+
         let arguments = if state.stream.current().kind == TokenKind::LeftParen {
-            Some(parameters::argument_list(state)?)
+            Some(Box::new(parameters::argument_list(state)?))
         } else {
             None
         };
@@ -1069,14 +1199,21 @@ expressions! {
         let current = state.stream.current();
 
         let span = current.span;
+        let length = current.value.len();
         let kind = current.kind.clone().into();
 
+        if matches!(current.kind, TokenKind::RealCast | TokenKind::UnsetCast) {
+            let feature = format!("the `{}` cast", current.kind);
+            versions::removed(state, PhpVersion::Php80, &feature, span, length);
+        }
+
         state.stream.next();
 
         let rhs = for_precedence(state, Precedence::Prefix)?;
 
         Ok(Expression::Cast {
             cast: span,
+            length,
             kind,
             value: Box::new(rhs),
         })
@@ -1150,8 +1287,36 @@ expressions! {
 
 fn unexpected_token(state: &mut State, _: &Precedence) -> ParseResult<Expression> {
     let current = state.stream.current();
+    let error = error::unexpected_token(vec![], current);
+
+    // In recovering mode, an expression which is missing before a token ending it, e.g. in
+    // `$a = ;` or `if ($a == ) {}`, is replaced by a placeholder, so the enclosing node is kept.
+    if state.recovering
+        && matches!(
+            current.kind,
+            TokenKind::SemiColon
+                | TokenKind::Comma
+                | TokenKind::Colon
+                | TokenKind::DoubleArrow
+                | TokenKind::RightParen
+                | TokenKind::RightBracket
+                | TokenKind::RightBrace
+                | TokenKind::CloseTag
+        )
+    {
+        state.record(error);
+
+        let span = Span::synthetic(
+            current.span.line,
+            current.span.column,
+            current.span.position,
+        );
+        state.recovered(Recovery::Expression, span);
+
+        return Ok(Expression::Missing { span });
+    }
 
-    Err(error::unexpected_token(vec![], current))
+    Err(error)
 }
 
 fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Expression> {
@@ -1175,14 +1340,21 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
             {
                 let start = utils::skip(state, TokenKind::LeftParen)?;
                 let ellipsis = utils::skip(state, TokenKind::Ellipsis)?;
+                versions::requires(
+                    state,
+                    PhpVersion::Php81,
+                    "first-class callable syntax",
+                    ellipsis,
+                    3,
+                );
                 let end = utils::skip(state, TokenKind::RightParen)?;
 
-                let placeholder = ArgumentPlaceholder {
+                let placeholder = Box::new(ArgumentPlaceholder {
                     comments: state.stream.comments(),
                     left_parenthesis: start,
                     ellipsis,
                     right_parenthesis: end,
-                };
+                });
 
                 Expression::FunctionClosureCreation {
                     target: Box::new(lhs),
@@ -1251,14 +1423,21 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
                 {
                     let start = utils::skip(state, TokenKind::LeftParen)?;
                     let ellipsis = utils::skip(state, TokenKind::Ellipsis)?;
+                    versions::requires(
+                        state,
+                        PhpVersion::Php81,
+                        "first-class callable syntax",
+                        ellipsis,
+                        3,
+                    );
                     let end = utils::skip(state, TokenKind::RightParen)?;
 
-                    let placeholder = ArgumentPlaceholder {
+                    let placeholder = Box::new(ArgumentPlaceholder {
                         comments: state.stream.comments(),
                         left_parenthesis: start,
                         ellipsis,
                         right_parenthesis: end,
-                    };
+                    });
 
                     match property {
                         Expression::Identifier(identifier) => {
@@ -1280,7 +1459,7 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
                         _ => unreachable!(),
                     }
                 } else {
-                    let arguments = parameters::argument_list(state)?;
+                    let arguments = Box::new(parameters::argument_list(state)?);
 
                     match property {
                         Expression::Identifier(identifier) => Expression::StaticMethodCall {
@@ -1300,11 +1479,24 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
                 }
             } else {
                 match property {
-                    Expression::Identifier(identifier) => Expression::ConstantFetch {
-                        target: lhs,
-                        double_colon: span,
-                        constant: identifier,
-                    },
+                    Expression::Identifier(identifier) => {
+                        // `Foo::{$name}`
+                        if let Identifier::DynamicIdentifier(dynamic) = &identifier {
+                            versions::requires(
+                                state,
+                                PhpVersion::Php83,
+                                "dynamic class constant fetch",
+                                dynamic.start,
+                                1,
+                            );
+                        }
+
+                        Expression::ConstantFetch {
+                            target: lhs,
+                            double_colon: span,
+                            constant: identifier,
+                        }
+                    }
                     Expression::Variable(variable) => Expression::StaticPropertyFetch {
                         target: lhs,
                         double_colon: span,
@@ -1316,39 +1508,17 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
         }
         TokenKind::Arrow | TokenKind::QuestionArrow => {
             let span = state.stream.current().span;
-            state.stream.next();
-
-            let property = match state.stream.current().kind {
-                TokenKind::Variable | TokenKind::Dollar | TokenKind::DollarLeftBrace => {
-                    Expression::Variable(variables::dynamic_variable(state)?)
-                }
-                _ if identifiers::is_identifier_maybe_reserved(&state.stream.current().kind) => {
-                    Expression::Identifier(Identifier::SimpleIdentifier(
-                        identifiers::identifier_maybe_reserved(state)?,
-                    ))
-                }
-                TokenKind::LeftBrace => {
-                    let start = state.stream.current().span;
-                    state.stream.next();
-
-                    let name = create(state)?;
+            if op == &TokenKind::QuestionArrow {
+                versions::requires(state, PhpVersion::Php80, "the nullsafe operator", span, 3);
+            }
 
-                    let end = utils::skip_right_brace(state)?;
+            state.stream.next();
 
-                    Expression::Identifier(Identifier::DynamicIdentifier(DynamicIdentifier {
-                        start,
-                        expr: Box::new(name),
-                        end,
-                    }))
-                }
-                _ => {
-                    return expected_token_err!(["`{`", "`$`", "an identifier"], state);
-                }
-            };
+            let property = property_name(state)?;
 
             if state.stream.current().kind == TokenKind::LeftParen {
                 if op == &TokenKind::QuestionArrow {
-                    let arguments = parameters::argument_list(state)?;
+                    let arguments = Box::new(parameters::argument_list(state)?);
 
                     Expression::NullsafeMethodCall {
                         target: Box::new(lhs),
@@ -1363,14 +1533,21 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
                     {
                         let start = utils::skip(state, TokenKind::LeftParen)?;
                         let ellipsis = utils::skip(state, TokenKind::Ellipsis)?;
+                        versions::requires(
+                            state,
+                            PhpVersion::Php81,
+                            "first-class callable syntax",
+                            ellipsis,
+                            3,
+                        );
                         let end = utils::skip(state, TokenKind::RightParen)?;
 
-                        let placeholder = ArgumentPlaceholder {
+                        let placeholder = Box::new(ArgumentPlaceholder {
                             comments: state.stream.comments(),
                             left_parenthesis: start,
                             ellipsis,
                             right_parenthesis: end,
-                        };
+                        });
 
                         Expression::MethodClosureCreation {
                             target: Box::new(lhs),
@@ -1379,7 +1556,7 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
                             placeholder,
                         }
                     } else {
-                        let arguments = parameters::argument_list(state)?;
+                        let arguments = Box::new(parameters::argument_list(state)?);
 
                         Expression::MethodCall {
                             target: Box::new(lhs),
@@ -1421,10 +1598,171 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
                 decrement: span,
             })
         }
-        _ => todo!("postfix: {:?}", op),
+        // Guarded by `is_postfix`.
+        _ => unreachable!("postfix: {:?}", op),
+    })
+}
+
+/// Parse `exit` or `die`, along with its optional parentheses and single positional argument.
+fn exit_expression(state: &mut State, kind: ExitKind) -> ParseResult<Expression> {
+    let keyword = state.stream.current().span;
+    state.stream.next();
+
+    let arguments = if state.stream.current().kind == TokenKind::LeftParen {
+        let arguments = parameters::argument_list(state)?;
+
+        if let Some(argument) = arguments.arguments.get(1) {
+            return Err(error::only_one_argument_is_accepted(
+                argument.start(),
+                arguments.right_parenthesis,
+            ));
+        }
+
+        if let Some(argument @ Argument::Named { .. }) = arguments.arguments.first() {
+            return Err(error::only_positional_arguments_are_accepted(
+                argument.start(),
+                arguments.right_parenthesis,
+            ));
+        }
+
+        Some(arguments)
+    } else {
+        None
+    };
+
+    Ok(Expression::Exit(Box::new(ExitExpression {
+        kind,
+        keyword,
+        arguments,
+    })))
+}
+
+/// The name of a property or method following `->` or `?->`, e.g. `bar`, `$bar`, or `{$bar}`.
+fn property_name(state: &mut State) -> ParseResult<Expression> {
+    Ok(match state.stream.current().kind {
+        TokenKind::Variable | TokenKind::Dollar | TokenKind::DollarLeftBrace => {
+            Expression::Variable(variables::dynamic_variable(state)?)
+        }
+        _ if identifiers::is_identifier_maybe_reserved(&state.stream.current().kind) => {
+            Expression::Identifier(Identifier::SimpleIdentifier(
+                identifiers::identifier_maybe_reserved(state)?,
+            ))
+        }
+        TokenKind::LeftBrace => {
+            let start = state.stream.current().span;
+            state.stream.next();
+
+            let name = create(state)?;
+
+            let end = utils::skip_right_brace(state)?;
+
+            Expression::Identifier(Identifier::DynamicIdentifier(DynamicIdentifier {
+                start,
+                expr: Box::new(name),
+                end,
+            }))
+        }
+        _ => {
+            return expected_token_err!(["`{`", "`$`", "an identifier"], state);
+        }
     })
 }
 
+/// Extend the class name of a `new` expression with the property fetches and array accesses
+/// following it, e.g. `new $a->b['c']()`, as the arguments are never the ones of a call.
+fn class_name_reference(state: &mut State, mut target: Expression) -> ParseResult<Expression> {
+    loop {
+        let current = state.stream.current();
+        let name = matches!(
+            target,
+            Expression::Identifier(_)
+                | Expression::Self_ { .. }
+                | Expression::Static { .. }
+                | Expression::Parent { .. }
+        );
+
+        target = match current.kind {
+            TokenKind::LeftBracket if !name && !target.is_parenthesized() => {
+                postfix(state, target, &current.kind)?
+            }
+            TokenKind::Arrow | TokenKind::QuestionArrow if !name && !target.is_parenthesized() => {
+                if current.kind == TokenKind::QuestionArrow {
+                    versions::requires(
+                        state,
+                        PhpVersion::Php80,
+                        "the nullsafe operator",
+                        current.span,
+                        3,
+                    );
+                }
+
+                state.stream.next();
+                let property = Box::new(property_name(state)?);
+
+                if current.kind == TokenKind::QuestionArrow {
+                    Expression::NullsafePropertyFetch {
+                        target: Box::new(target),
+                        question_arrow: current.span,
+                        property,
+                    }
+                } else {
+                    Expression::PropertyFetch {
+                        target: Box::new(target),
+                        arrow: current.span,
+                        property,
+                    }
+                }
+            }
+            TokenKind::DoubleColon
+                if !target.is_parenthesized()
+                    && matches!(
+                        state.stream.peek().kind,
+                        TokenKind::Variable | TokenKind::Dollar | TokenKind::DollarLeftBrace
+                    ) =>
+            {
+                let double_colon = utils::skip_double_colon(state)?;
+
+                Expression::StaticPropertyFetch {
+                    target: Box::new(target),
+                    double_colon,
+                    property: variables::dynamic_variable(state)?,
+                }
+            }
+            _ => return Ok(target),
+        };
+    }
+}
+
+/// Check the member access, array access, or call on a `new` expression which isn't wrapped
+/// in parentheses, e.g. `new Foo()->bar()`, which is only allowed since PHP 8.4, and only
+/// when the arguments of the constructor are given, or for anonymous classes.
+fn new_without_parentheses(state: &mut State, new: &Expression) -> ParseResult<()> {
+    let span = match new {
+        Expression::New {
+            new,
+            target,
+            arguments,
+        } => {
+            if arguments.is_none() && !matches!(target.as_ref(), Expression::AnonymousClass(_)) {
+                return Err(error::unexpected_token(vec![], state.stream.current()));
+            }
+
+            *new
+        }
+        _ => return Ok(()),
+    };
+
+    versions::requires(
+        state,
+        PhpVersion::Php84,
+        "member access on new expressions without parentheses",
+        span,
+        3,
+    );
+
+    Ok(())
+}
+
 fn is_infix(t: &TokenKind) -> bool {
     matches!(
         t,
@@ -1452,6 +1790,7 @@ fn is_infix(t: &TokenKind) -> bool {
             | TokenKind::Plus
             | TokenKind::Minus
             | TokenKind::Dot
+            | TokenKind::PipeGreaterThan
             | TokenKind::LessThan
             | TokenKind::GreaterThan
             | TokenKind::LessThanEquals
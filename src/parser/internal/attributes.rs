@@ -1,3 +1,5 @@
+use smallvec::SmallVec;
+
 use crate::lexer::token::TokenKind;
 use crate::parser::ast::attributes::Attribute;
 use crate::parser::ast::attributes::AttributeGroup;
@@ -5,6 +7,10 @@ use crate::parser::error::ParseResult;
 use crate::parser::internal::identifiers;
 use crate::parser::internal::parameters;
 use crate::parser::internal::utils;
+use crate::parser::internal::versions;
+use crate::parser::macros::scoped;
+use crate::parser::options::PhpVersion;
+use crate::parser::state::Scope;
 use crate::parser::state::State;
 
 pub fn gather_attributes(state: &mut State) -> ParseResult<bool> {
@@ -13,39 +19,45 @@ pub fn gather_attributes(state: &mut State) -> ParseResult<bool> {
     }
 
     let start = state.stream.current().span;
-    let mut members = vec![];
+    versions::requires(state, PhpVersion::Php80, "attributes", start, 2);
 
     state.stream.next();
 
-    loop {
-        let start = state.stream.current().span;
-        let name = identifiers::full_type_name_including_self(state)?;
-        let arguments = if state.stream.current().kind == TokenKind::LeftParen {
-            Some(parameters::argument_list(state)?)
-        } else {
-            None
-        };
-        let end = state.stream.current().span;
-
-        members.push(Attribute {
-            start,
-            name,
-            arguments,
-            end,
-        });
-
-        if state.stream.current().kind == TokenKind::Comma {
-            state.stream.next();
-
-            if state.stream.current().kind == TokenKind::RightBracket {
-                break;
+    let members = scoped!(state, Scope::Attribute, {
+        let mut members = SmallVec::new();
+
+        loop {
+            let start = state.stream.current().span;
+            let name = identifiers::full_type_name_including_self(state)?;
+            let arguments = if state.stream.current().kind == TokenKind::LeftParen {
+                Some(parameters::argument_list(state)?)
+            } else {
+                None
+            };
+            let end = state.stream.current().span;
+
+            members.push(Attribute {
+                start,
+                name,
+                arguments,
+                end,
+            });
+
+            if state.stream.current().kind == TokenKind::Comma {
+                state.stream.next();
+
+                if state.stream.current().kind == TokenKind::RightBracket {
+                    break;
+                }
+
+                continue;
             }
 
-            continue;
+            break;
         }
 
-        break;
-    }
+        members
+    });
 
     let end = utils::skip_right_bracket(state)?;
 
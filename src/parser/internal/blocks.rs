@@ -4,6 +4,7 @@ use crate::parser;
 use crate::parser::ast::BlockStatement;
 use crate::parser::ast::Statement;
 use crate::parser::error::ParseResult;
+use crate::parser::internal::recovery;
 use crate::parser::internal::utils;
 use crate::parser::state::State;
 
@@ -34,7 +35,7 @@ pub fn multiple_statements_until(
             continue;
         }
 
-        statements.push(parser::statement(state)?);
+        statements.push(recovery::statement(state, parser::statement)?);
         current = state.stream.current();
     }
 
@@ -56,7 +57,7 @@ pub fn multiple_statements_until_any(
             continue;
         }
 
-        statements.push(parser::statement(state)?);
+        statements.push(recovery::statement(state, parser::statement)?);
         current = state.stream.current();
     }
 
@@ -1,5 +1,6 @@
 use crate::lexer::token::Span;
 use crate::lexer::token::TokenKind;
+use crate::parser::ast::attributes::AttributeGroup;
 use crate::parser::ast::classes::AnonymousClass;
 use crate::parser::ast::classes::AnonymousClassBody;
 use crate::parser::ast::classes::AnonymousClassMember;
@@ -11,6 +12,7 @@ use crate::parser::ast::classes::ClassStatement;
 use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::Expression;
 use crate::parser::ast::Statement;
+use crate::parser::ast::UseKind;
 use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::internal::attributes;
@@ -22,16 +24,39 @@ use crate::parser::internal::identifiers;
 use crate::parser::internal::modifiers;
 use crate::parser::internal::parameters;
 use crate::parser::internal::properties;
+use crate::parser::internal::recovery;
+use crate::parser::internal::symbols;
 use crate::parser::internal::traits;
 use crate::parser::internal::utils;
+use crate::parser::internal::versions;
+use crate::parser::macros::scoped;
+use crate::parser::options::PhpVersion;
+use crate::parser::state::Scope;
 use crate::parser::state::State;
 
 pub fn parse(state: &mut State) -> ParseResult<Statement> {
     let attributes = state.get_attributes();
 
+    let comments = state.stream.comments();
     let modifiers = modifiers::class_group(modifiers::collect(state)?)?;
+    if let Some(modifier) = modifiers.get_readonly() {
+        versions::requires(
+            state,
+            PhpVersion::Php82,
+            "readonly classes",
+            modifier.span(),
+            8,
+        );
+    }
+
     let class = utils::skip(state, TokenKind::Class)?;
     let name = identifiers::type_identifier(state)?;
+    symbols::declare(state, UseKind::Normal, "class", &name);
+    let readonly = modifiers.get_readonly().map(|modifier| modifier.span());
+    if let Some(readonly) = readonly {
+        dynamic_properties(state, &attributes, Some(&name), readonly);
+    }
+
     let current = state.stream.current();
     let extends = if current.kind == TokenKind::Extends {
         let span = current.span;
@@ -67,20 +92,27 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
     };
 
     let has_abstract = modifiers.has_abstract();
-    let body = ClassBody {
-        left_brace: utils::skip_left_brace(state)?,
-        members: {
-            let mut members = Vec::new();
-            while state.stream.current().kind != TokenKind::RightBrace {
-                members.push(member(state, has_abstract, &name)?);
-            }
+    let body = scoped!(state, Scope::Class(name.clone(), extends.is_some()), {
+        ClassBody {
+            left_brace: utils::skip_left_brace(state)?,
+            members: {
+                let mut members = Vec::new();
+                while state.stream.current().kind != TokenKind::RightBrace {
+                    if let Some(member) = recovery::member(state, |state| {
+                        member(state, has_abstract, readonly, &name)
+                    })? {
+                        members.push(member);
+                    }
+                }
 
-            members
-        },
-        right_brace: utils::skip_right_brace(state)?,
-    };
+                members
+            },
+            right_brace: utils::skip_right_brace(state)?,
+        }
+    });
 
-    Ok(Statement::Class(ClassStatement {
+    Ok(Statement::Class(Box::new(ClassStatement {
+        comments,
         class,
         name,
         modifiers,
@@ -88,7 +120,7 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
         implements,
         attributes,
         body,
-    }))
+    })))
 }
 
 pub fn parse_anonymous(state: &mut State, span: Option<Span>) -> ParseResult<Expression> {
@@ -101,10 +133,26 @@ pub fn parse_anonymous(state: &mut State, span: Option<Span>) -> ParseResult<Exp
 
     let attributes = state.get_attributes();
 
+    let readonly = if state.stream.current().kind == TokenKind::Readonly {
+        let readonly = utils::skip(state, TokenKind::Readonly)?;
+        versions::requires(
+            state,
+            PhpVersion::Php83,
+            "readonly anonymous classes",
+            readonly,
+            8,
+        );
+        dynamic_properties(state, &attributes, None, readonly);
+
+        Some(readonly)
+    } else {
+        None
+    };
+
     let class = utils::skip(state, TokenKind::Class)?;
 
     let arguments = if state.stream.current().kind == TokenKind::LeftParen {
-        Some(parameters::argument_list(state)?)
+        Some(Box::new(parameters::argument_list(state)?))
     } else {
         None
     };
@@ -139,34 +187,43 @@ pub fn parse_anonymous(state: &mut State, span: Option<Span>) -> ParseResult<Exp
         None
     };
 
-    let body = AnonymousClassBody {
-        left_brace: utils::skip_left_brace(state)?,
-        members: {
-            let mut members = Vec::new();
-            while state.stream.current().kind != TokenKind::RightBrace {
-                members.push(anonymous_member(state)?);
-            }
-            members
-        },
-        right_brace: utils::skip_right_brace(state)?,
-    };
+    let body = scoped!(state, Scope::AnonymousClass(extends.is_some()), {
+        AnonymousClassBody {
+            left_brace: utils::skip_left_brace(state)?,
+            members: {
+                let mut members = Vec::new();
+                while state.stream.current().kind != TokenKind::RightBrace {
+                    if let Some(member) =
+                        recovery::member(state, |state| anonymous_member(state, readonly))?
+                    {
+                        members.push(member);
+                    }
+                }
+                members
+            },
+            right_brace: utils::skip_right_brace(state)?,
+        }
+    });
 
     Ok(Expression::New {
-        target: Box::new(Expression::AnonymousClass(AnonymousClass {
+        target: Box::new(Expression::AnonymousClass(Box::new(AnonymousClass {
+            attributes,
+            readonly,
             class,
             extends,
             implements,
-            attributes,
             body,
-        })),
+        }))),
         new,
         arguments,
     })
 }
 
-fn member(
+/// Parse a member of a class, `readonly` being the span of the `readonly` modifier of the class.
+pub fn member(
     state: &mut State,
     has_abstract: bool,
+    readonly: Option<Span>,
     name: &SimpleIdentifier,
 ) -> ParseResult<ClassMember> {
     let has_attributes = attributes::gather_attributes(state)?;
@@ -176,7 +233,8 @@ fn member(
     }
 
     if state.stream.current().kind == TokenKind::Var {
-        return properties::parse_var(state, Some(name)).map(ClassMember::VariableProperty);
+        return properties::parse_var(state, Some(name), readonly)
+            .map(ClassMember::VariableProperty);
     }
 
     let modifiers = modifiers::collect(state)?;
@@ -228,10 +286,13 @@ fn member(
     // e.g: public static
     let modifiers = modifiers::property_group(modifiers)?;
 
-    properties::parse(state, Some(name), modifiers).map(ClassMember::Property)
+    properties::parse(state, Some(name), readonly, modifiers).map(ClassMember::Property)
 }
 
-fn anonymous_member(state: &mut State) -> ParseResult<AnonymousClassMember> {
+fn anonymous_member(
+    state: &mut State,
+    readonly: Option<Span>,
+) -> ParseResult<AnonymousClassMember> {
     let has_attributes = attributes::gather_attributes(state)?;
 
     if !has_attributes && state.stream.current().kind == TokenKind::Use {
@@ -239,7 +300,8 @@ fn anonymous_member(state: &mut State) -> ParseResult<AnonymousClassMember> {
     }
 
     if state.stream.current().kind == TokenKind::Var {
-        return properties::parse_var(state, None).map(AnonymousClassMember::VariableProperty);
+        return properties::parse_var(state, None, readonly)
+            .map(AnonymousClassMember::VariableProperty);
     }
 
     let modifiers = modifiers::collect(state)?;
@@ -271,5 +333,22 @@ fn anonymous_member(state: &mut State) -> ParseResult<AnonymousClassMember> {
     // e.g: public static
     let modifiers = modifiers::property_group(modifiers)?;
 
-    properties::parse(state, None, modifiers).map(AnonymousClassMember::Property)
+    properties::parse(state, None, readonly, modifiers).map(AnonymousClassMember::Property)
+}
+
+/// Validate that a readonly class doesn't allow dynamic properties, which it cannot have.
+fn dynamic_properties(
+    state: &mut State,
+    attributes: &[AttributeGroup],
+    class: Option<&SimpleIdentifier>,
+    readonly: Span,
+) {
+    for attribute in attributes.iter().flat_map(|group| group.members.iter()) {
+        if symbols::resolve(state, &attribute.name).eq_ignore_ascii_case("AllowDynamicProperties") {
+            let error =
+                error::readonly_class_allows_dynamic_properties(state, class, attribute, readonly);
+
+            state.record(error);
+        }
+    }
 }
@@ -3,10 +3,16 @@ use crate::parser::ast::constant::ClassishConstant;
 use crate::parser::ast::constant::ConstantEntry;
 use crate::parser::ast::constant::ConstantStatement;
 use crate::parser::ast::modifiers::ConstantModifierGroup;
+use crate::parser::ast::UseKind;
+use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::expressions;
+use crate::parser::internal::data_type;
 use crate::parser::internal::identifiers;
+use crate::parser::internal::symbols;
 use crate::parser::internal::utils;
+use crate::parser::internal::versions;
+use crate::parser::options::PhpVersion;
 use crate::parser::state::State;
 
 pub fn parse(state: &mut State) -> ParseResult<ConstantStatement> {
@@ -17,6 +23,7 @@ pub fn parse(state: &mut State) -> ParseResult<ConstantStatement> {
 
     loop {
         let name = identifiers::constant_identifier(state)?;
+        symbols::declare(state, UseKind::Const, "constant", &name);
         let span = utils::skip(state, TokenKind::Equals)?;
         let value = expressions::create(state)?;
 
@@ -47,11 +54,63 @@ pub fn classish(
     state: &mut State,
     modifiers: ConstantModifierGroup,
 ) -> ParseResult<ClassishConstant> {
+    if let Some(modifier) = modifiers
+        .modifiers
+        .iter()
+        .find(|modifier| modifier.kind().is_visibility())
+    {
+        versions::requires(
+            state,
+            PhpVersion::Php71,
+            "class constant visibility",
+            modifier.span(),
+            modifier.kind().to_string().len(),
+        );
+    }
+
+    if let Some(modifier) = modifiers.get_final() {
+        versions::requires(
+            state,
+            PhpVersion::Php81,
+            "final class constants",
+            modifier.span(),
+            5,
+        );
+    }
+
     let attributes = state.get_attributes();
 
     let comments = state.stream.comments();
     let start = utils::skip(state, TokenKind::Const)?;
 
+    // `const int FOO = 1;`, unlike `const FOO = 1;`, has a type before the name.
+    let data_type = if matches!(
+        state.stream.current().kind,
+        TokenKind::Question | TokenKind::LeftParen
+    ) || state.stream.peek().kind != TokenKind::Equals
+    {
+        data_type::optional_data_type(state)?
+    } else {
+        None
+    };
+
+    if let Some(ty) = &data_type {
+        versions::requires(
+            state,
+            PhpVersion::Php83,
+            "typed class constants",
+            ty.first_span(),
+            ty.to_string().len(),
+        );
+
+        if ty.includes_callable() || ty.is_bottom() {
+            state.record(error::cannot_use_type_in_context(
+                ty.first_span(),
+                ty.to_string(),
+            ));
+        }
+    }
+
     let mut entries = vec![];
 
     loop {
@@ -79,6 +138,7 @@ pub fn classish(
         attributes,
         modifiers,
         r#const: start,
+        data_type,
         entries,
         semicolon: end,
     })
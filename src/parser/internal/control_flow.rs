@@ -1,4 +1,5 @@
-use crate::expected_token_err;
+use crate::parser::internal::versions;
+use crate::parser::macros::expected_token_err;
 
 use crate::lexer::token::TokenKind;
 use crate::parser;
@@ -13,22 +14,26 @@ use crate::parser::ast::Case;
 use crate::parser::ast::DefaultMatchArm;
 use crate::parser::ast::Expression;
 use crate::parser::ast::MatchArm;
+use crate::parser::ast::MatchExpression;
 use crate::parser::ast::Statement;
 use crate::parser::ast::SwitchStatement;
 use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::expressions;
 use crate::parser::internal::blocks;
+use crate::parser::internal::recovery;
 use crate::parser::internal::utils;
+use crate::parser::macros::scoped;
+use crate::parser::options::PhpVersion;
+use crate::parser::state::Scope;
 use crate::parser::state::State;
 
 pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
     let keyword = utils::skip(state, TokenKind::Match)?;
+    versions::requires(state, PhpVersion::Php80, "match expressions", keyword, 5);
 
     let (left_parenthesis, condition, right_parenthesis) =
-        utils::parenthesized(state, &|state: &mut State| {
-            expressions::create(state).map(Box::new)
-        })?;
+        utils::parenthesized(state, &|state: &mut State| expressions::create(state))?;
 
     let left_brace = utils::skip_left_brace(state)?;
 
@@ -96,7 +101,7 @@ pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
 
     let right_brace = utils::skip_right_brace(state)?;
 
-    Ok(Expression::Match {
+    Ok(Expression::Match(Box::new(MatchExpression {
         keyword,
         left_parenthesis,
         condition,
@@ -105,7 +110,7 @@ pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
         default,
         arms,
         right_brace,
-    })
+    })))
 }
 
 pub fn switch_statement(state: &mut State) -> ParseResult<Statement> {
@@ -122,55 +127,59 @@ pub fn switch_statement(state: &mut State) -> ParseResult<Statement> {
         TokenKind::RightBrace
     };
 
-    let mut cases = Vec::new();
-    while state.stream.current().kind != end_token {
-        match state.stream.current().kind {
-            TokenKind::Case => {
-                state.stream.next();
+    let cases = scoped!(state, Scope::Switch, {
+        let mut cases = Vec::new();
+        while state.stream.current().kind != end_token {
+            match state.stream.current().kind {
+                TokenKind::Case => {
+                    state.stream.next();
 
-                let condition = expressions::create(state)?;
+                    let condition = expressions::create(state)?;
 
-                utils::skip_any_of(state, &[TokenKind::Colon, TokenKind::SemiColon])?;
+                    utils::skip_any_of(state, &[TokenKind::Colon, TokenKind::SemiColon])?;
 
-                let mut body = Block::new();
+                    let mut body = Block::new();
 
-                while state.stream.current().kind != TokenKind::Case
-                    && state.stream.current().kind != TokenKind::Default
-                    && state.stream.current().kind != TokenKind::RightBrace
-                    && state.stream.current().kind != end_token
-                {
-                    body.push(parser::statement(state)?);
+                    while state.stream.current().kind != TokenKind::Case
+                        && state.stream.current().kind != TokenKind::Default
+                        && state.stream.current().kind != TokenKind::RightBrace
+                        && state.stream.current().kind != end_token
+                    {
+                        body.push(recovery::statement(state, parser::statement)?);
+                    }
+
+                    cases.push(Case {
+                        condition: Some(condition),
+                        body,
+                    });
                 }
+                TokenKind::Default => {
+                    state.stream.next();
 
-                cases.push(Case {
-                    condition: Some(condition),
-                    body,
-                });
-            }
-            TokenKind::Default => {
-                state.stream.next();
+                    utils::skip_any_of(state, &[TokenKind::Colon, TokenKind::SemiColon])?;
 
-                utils::skip_any_of(state, &[TokenKind::Colon, TokenKind::SemiColon])?;
+                    let mut body = Block::new();
 
-                let mut body = Block::new();
+                    while state.stream.current().kind != TokenKind::Case
+                        && state.stream.current().kind != TokenKind::Default
+                        && state.stream.current().kind != end_token
+                    {
+                        body.push(recovery::statement(state, parser::statement)?);
+                    }
 
-                while state.stream.current().kind != TokenKind::Case
-                    && state.stream.current().kind != TokenKind::Default
-                    && state.stream.current().kind != end_token
-                {
-                    body.push(parser::statement(state)?);
+                    cases.push(Case {
+                        condition: None,
+                        body,
+                    });
+                }
+                _ => {
+                    return expected_token_err!(["`case`", "`default`"], state);
                 }
-
-                cases.push(Case {
-                    condition: None,
-                    body,
-                });
-            }
-            _ => {
-                return expected_token_err!(["`case`", "`default`"], state);
             }
         }
-    }
+
+        cases
+    });
 
     if end_token == TokenKind::EndSwitch {
         utils::skip(state, TokenKind::EndSwitch)?;
@@ -179,13 +188,13 @@ pub fn switch_statement(state: &mut State) -> ParseResult<Statement> {
         utils::skip_right_brace(state)?;
     }
 
-    Ok(Statement::Switch(SwitchStatement {
+    Ok(Statement::Switch(Box::new(SwitchStatement {
         switch,
         left_parenthesis,
         condition,
         right_parenthesis,
         cases,
-    }))
+    })))
 }
 
 pub fn if_statement(state: &mut State) -> ParseResult<Statement> {
@@ -194,7 +203,7 @@ pub fn if_statement(state: &mut State) -> ParseResult<Statement> {
     let (left_parenthesis, condition, right_parenthesis) =
         utils::parenthesized(state, &expressions::create)?;
 
-    Ok(Statement::If(IfStatement {
+    Ok(Statement::If(Box::new(IfStatement {
         r#if,
         left_parenthesis,
         condition,
@@ -204,7 +213,7 @@ pub fn if_statement(state: &mut State) -> ParseResult<Statement> {
         } else {
             if_statement_statement_body(state)?
         },
-    }))
+    })))
 }
 
 fn if_statement_statement_body(state: &mut State) -> ParseResult<IfStatementBody> {
@@ -1,13 +1,26 @@
-use crate::expected_token;
+use crate::lexer::token::Span;
+use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
 use crate::parser::ast::data_type::Type;
 use crate::parser::error;
 use crate::parser::error::ParseResult;
+use crate::parser::internal::scopes;
 use crate::parser::internal::utils;
+use crate::parser::internal::versions;
+use crate::parser::macros::expected_token;
+use crate::parser::macros::peek_token;
 use crate::parser::state::State;
-use crate::peek_token;
 
 pub fn data_type(state: &mut State) -> ParseResult<Type> {
+    let ty = any_data_type(state)?;
+
+    redundancies(state, &ty);
+    versions::data_type(state, &ty);
+
+    Ok(ty)
+}
+
+fn any_data_type(state: &mut State) -> ParseResult<Type> {
     if state.stream.current().kind == TokenKind::Question {
         return nullable(state);
     }
@@ -35,7 +48,26 @@ pub fn data_type(state: &mut State) -> ParseResult<Type> {
     Ok(ty)
 }
 
+/// Parse the optional type of a parameter or property, which unlike a return type cannot be `static`.
 pub fn optional_data_type(state: &mut State) -> ParseResult<Option<Type>> {
+    let ty = optional_data_type_with_static(state)?;
+
+    if let Some(ty) = &ty {
+        redundancies(state, ty);
+        versions::data_type(state, ty);
+    }
+
+    if let Some(span) = ty.as_ref().and_then(static_reference) {
+        state.record(error::cannot_use_type_in_context(
+            span,
+            "static".to_string(),
+        ));
+    }
+
+    Ok(ty)
+}
+
+fn optional_data_type_with_static(state: &mut State) -> ParseResult<Option<Type>> {
     if state.stream.current().kind == TokenKind::Question {
         return nullable(state).map(Some);
     }
@@ -90,6 +122,60 @@ fn dnf(state: &mut State) -> ParseResult<Type> {
     ], state, ["|", "&"])
 }
 
+/// Validate that no member of a union or an intersection is redundant, like PHP does when
+/// compiling a declaration, e.g. `A|B|A`, `(A&B)|(B&A)`, or `(A&B)|A`.
+fn redundancies(state: &mut State, ty: &Type) {
+    let types = match ty {
+        Type::Union(types) | Type::Intersection(types) => types,
+        _ => return,
+    };
+
+    for (index, member) in types.iter().enumerate() {
+        redundancies(state, member);
+
+        for previous in &types[..index] {
+            if key(member) == key(previous) {
+                state.record(error::duplicate_type(member, previous));
+
+                continue;
+            }
+
+            if !matches!(ty, Type::Union(_)) {
+                continue;
+            }
+
+            // an intersection within a union is redundant with any of its own members.
+            for (intersection, other) in [(member, previous), (previous, member)] {
+                if let Type::Intersection(inner) = intersection {
+                    if inner.iter().any(|inner| key(inner) == key(other)) {
+                        state.record(error::redundant_type(intersection, other));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The name of a type, independent of its case and of the order of the members of a union
+/// or an intersection.
+fn key(ty: &Type) -> String {
+    match ty {
+        Type::Union(types) | Type::Intersection(types) => {
+            let mut keys = types.iter().map(key).collect::<Vec<String>>();
+            keys.sort();
+
+            let separator = if matches!(ty, Type::Union(_)) {
+                "|"
+            } else {
+                "&"
+            };
+
+            format!("({})", keys.join(separator))
+        }
+        _ => ty.to_string().to_ascii_lowercase(),
+    }
+}
+
 fn optional_simple_data_type(state: &mut State) -> ParseResult<Option<Type>> {
     let current = state.stream.current();
 
@@ -128,18 +214,24 @@ fn optional_simple_data_type(state: &mut State) -> ParseResult<Option<Type>> {
             let span = current.span;
             state.stream.next();
 
+            scopes::class_scoped_keyword(state, span, "static");
+
             Ok(Some(Type::StaticReference(span)))
         }
         TokenKind::Self_ => {
             let span = current.span;
             state.stream.next();
 
+            scopes::class_scoped_keyword(state, span, "self");
+
             Ok(Some(Type::SelfReference(span)))
         }
         TokenKind::Parent => {
             let span = current.span;
             state.stream.next();
 
+            scopes::class_scoped_keyword(state, span, "parent");
+
             Ok(Some(Type::ParentReference(span)))
         }
         TokenKind::Enum | TokenKind::From => {
@@ -176,7 +268,7 @@ fn optional_simple_data_type(state: &mut State) -> ParseResult<Option<Type>> {
             }
         }
         TokenKind::QualifiedIdentifier | TokenKind::FullyQualifiedIdentifier => {
-            let name = current.value.clone();
+            let name = current.value.to_byte_string();
             let span = current.span;
             state.stream.next();
 
@@ -186,6 +278,15 @@ fn optional_simple_data_type(state: &mut State) -> ParseResult<Option<Type>> {
     }
 }
 
+fn static_reference(ty: &Type) -> Option<Span> {
+    match ty {
+        Type::StaticReference(span) => Some(*span),
+        Type::Nullable(_, inner) => static_reference(inner),
+        Type::Union(inner) | Type::Intersection(inner) => inner.iter().find_map(static_reference),
+        _ => None,
+    }
+}
+
 fn simple_data_type(state: &mut State) -> ParseResult<Type> {
     // TODO(azjezz): add a better error message here.
     optional_simple_data_type(state)?.ok_or_else(|| expected_token!(["a type"], state))
@@ -334,3 +435,312 @@ fn intersection(state: &mut State, other: Type, within_dnf: bool) -> ParseResult
 
     Ok(Type::Intersection(types))
 }
+
+/// Prepare the tokens of a docblock type for [`docblock_type`]: dashed names, such as
+/// `class-string`, are joined into a single identifier, and `>>` is split into two `>`.
+pub fn docblock_tokens(tokens: Vec<Token>) -> Vec<Token> {
+    let mut prepared: Vec<Token> = Vec::with_capacity(tokens.len());
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        if token.kind == TokenKind::RightShift {
+            let span = token.span;
+            let next = Span::new(span.line, span.column + 1, span.position + 1);
+
+            for span in [span, next] {
+                prepared.push(Token {
+                    kind: TokenKind::GreaterThan,
+                    span,
+                    value: b">".into(),
+                    raw: None,
+                });
+            }
+
+            continue;
+        }
+
+        let dashed = token.kind == TokenKind::Minus
+            && prepared
+                .last()
+                .is_some_and(|previous| is_word(previous) && adjacent(previous, &token))
+            && tokens
+                .peek()
+                .is_some_and(|next| is_word(next) && adjacent(&token, next));
+
+        match prepared.last_mut() {
+            Some(previous) if dashed => {
+                let next = tokens.next().unwrap();
+
+                let mut value = previous.value.to_vec();
+                value.push(b'-');
+                value.extend_from_slice(&next.value);
+
+                previous.kind = TokenKind::Identifier;
+                previous.value = value.into();
+                previous.raw = None;
+            }
+            _ => prepared.push(token),
+        }
+    }
+
+    prepared
+}
+
+fn is_word(token: &Token) -> bool {
+    token.kind != TokenKind::Variable
+        && token
+            .value
+            .first()
+            .is_some_and(|byte| byte.is_ascii_alphabetic() || b"_\\".contains(byte))
+        && token
+            .value
+            .iter()
+            .all(|byte| byte.is_ascii_alphanumeric() || b"_-\\".contains(byte))
+}
+
+fn adjacent(left: &Token, right: &Token) -> bool {
+    left.span.position + left.raw().len() == right.span.position
+}
+
+/// Parse a type in the docblock grammar, e.g. `array<int, Foo>|null`, as understood by static
+/// analysers, erasing what can't be expressed by a native type.
+///
+/// Generic arguments, array shapes, callable signatures and ranges are dropped, e.g.
+/// `list<Foo>` and `Foo[]` are arrays, `class-string<Foo>` is a string, `int<0, max>` an
+/// integer and `callable(int): void` a callable. Pseudo-types which are unions of native types,
+/// such as `array-key` and `scalar`, are expanded, and the remaining ones, such as `resource`
+/// or `key-of<T>`, are `mixed`. Literal values are their native type, and `$this` is `static`.
+///
+/// Unlike native types, nullable types may be used within unions and intersections.
+pub fn docblock_type(state: &mut State) -> ParseResult<Type> {
+    let ty = docblock_intersection(state)?;
+
+    if state.stream.current().kind != TokenKind::Pipe {
+        return Ok(ty);
+    }
+
+    let mut types = vec![ty];
+    while state.stream.current().kind == TokenKind::Pipe {
+        state.stream.next();
+
+        types.push(docblock_intersection(state)?);
+    }
+
+    Ok(Type::Union(types))
+}
+
+fn docblock_intersection(state: &mut State) -> ParseResult<Type> {
+    let ty = docblock_postfix(state)?;
+
+    if state.stream.current().kind != TokenKind::Ampersand {
+        return Ok(ty);
+    }
+
+    let mut types = vec![ty];
+    while state.stream.current().kind == TokenKind::Ampersand {
+        state.stream.next();
+
+        types.push(docblock_postfix(state)?);
+    }
+
+    Ok(Type::Intersection(types))
+}
+
+fn docblock_postfix(state: &mut State) -> ParseResult<Type> {
+    if state.stream.current().kind == TokenKind::Question {
+        let span = state.stream.current().span;
+        state.stream.next();
+
+        return Ok(Type::Nullable(span, Box::new(docblock_postfix(state)?)));
+    }
+
+    let mut ty = docblock_atom(state)?;
+
+    // `Foo[]`
+    while state.stream.current().kind == TokenKind::LeftBracket
+        && state.stream.peek().kind == TokenKind::RightBracket
+    {
+        let span = state.stream.current().span;
+        state.stream.next();
+        state.stream.next();
+
+        ty = Type::Array(span);
+    }
+
+    Ok(ty)
+}
+
+fn docblock_atom(state: &mut State) -> ParseResult<Type> {
+    let current = state.stream.current();
+    let span = current.span;
+
+    match &current.kind {
+        TokenKind::LeftParen => {
+            state.stream.next();
+            let ty = docblock_type(state)?;
+            utils::skip_right_parenthesis(state)?;
+
+            return Ok(ty);
+        }
+        TokenKind::LiteralInteger => {
+            state.stream.next();
+
+            return Ok(Type::Integer(span));
+        }
+        TokenKind::LiteralFloat => {
+            state.stream.next();
+
+            return Ok(Type::Float(span));
+        }
+        TokenKind::LiteralString => {
+            state.stream.next();
+
+            return Ok(Type::String(span));
+        }
+        TokenKind::Minus
+            if matches!(
+                state.stream.peek().kind,
+                TokenKind::LiteralInteger | TokenKind::LiteralFloat
+            ) =>
+        {
+            state.stream.next();
+
+            return docblock_atom(state);
+        }
+        TokenKind::Variable if current.value[..] == b"$this"[..] => {
+            state.stream.next();
+
+            return Ok(Type::StaticReference(span));
+        }
+        _ if is_word(current) => {}
+        _ => return Err(expected_token!(["a type"], state)),
+    }
+
+    let name = current.value.to_byte_string();
+    state.stream.next();
+
+    // `Foo::BAR`, `Foo::*`
+    if state.stream.current().kind == TokenKind::DoubleColon {
+        state.stream.next();
+        state.stream.next();
+
+        return Ok(Type::Mixed(span));
+    }
+
+    // `array<int, Foo>`, `int<0, max>`
+    if state.stream.current().kind == TokenKind::LessThan {
+        state.stream.next();
+
+        loop {
+            docblock_type(state)?;
+
+            if state.stream.current().kind != TokenKind::Comma {
+                break;
+            }
+
+            state.stream.next();
+        }
+
+        utils::skip(state, TokenKind::GreaterThan)?;
+    }
+
+    // `array{0: int, name?: string}`, `callable(int, string=): void`
+    if matches!(
+        state.stream.current().kind,
+        TokenKind::LeftBrace | TokenKind::LeftParen
+    ) || is_cast(state.stream.current())
+    {
+        skip_balanced(state)?;
+
+        if state.stream.current().kind == TokenKind::Colon {
+            state.stream.next();
+
+            docblock_postfix(state)?;
+        }
+    }
+
+    let lowered = name.to_ascii_lowercase();
+    let ty = match lowered.as_slice() {
+        b"int" | b"integer" | b"positive-int" | b"negative-int" | b"non-positive-int"
+        | b"non-negative-int" | b"non-zero-int" | b"int-mask" | b"int-mask-of" => {
+            Type::Integer(span)
+        }
+        b"float" | b"double" => Type::Float(span),
+        b"bool" | b"boolean" => Type::Boolean(span),
+        b"string"
+        | b"class-string"
+        | b"interface-string"
+        | b"trait-string"
+        | b"enum-string"
+        | b"callable-string"
+        | b"numeric-string"
+        | b"non-empty-string"
+        | b"non-falsy-string"
+        | b"truthy-string"
+        | b"literal-string"
+        | b"lowercase-string"
+        | b"non-empty-lowercase-string" => Type::String(span),
+        b"array" | b"list" | b"non-empty-array" | b"non-empty-list" => Type::Array(span),
+        b"iterable" => Type::Iterable(span),
+        b"callable" | b"pure-callable" => Type::Callable(span),
+        b"object" => Type::Object(span),
+        b"void" => Type::Void(span),
+        b"never" | b"never-return" | b"never-returns" | b"no-return" | b"noreturn" => {
+            Type::Never(span)
+        }
+        b"null" => Type::Null(span),
+        b"true" => Type::True(span),
+        b"false" => Type::False(span),
+        b"static" => Type::StaticReference(span),
+        b"self" => Type::SelfReference(span),
+        b"parent" => Type::ParentReference(span),
+        b"array-key" => Type::Union(vec![Type::Integer(span), Type::String(span)]),
+        b"numeric" => Type::Union(vec![
+            Type::Integer(span),
+            Type::Float(span),
+            Type::String(span),
+        ]),
+        b"scalar" => Type::Union(vec![
+            Type::Integer(span),
+            Type::Float(span),
+            Type::String(span),
+            Type::Boolean(span),
+        ]),
+        _ if lowered.contains(&b'-') || lowered.ends_with(b"resource") => Type::Mixed(span),
+        b"mixed" => Type::Mixed(span),
+        _ => Type::Named(span, name),
+    };
+
+    Ok(ty)
+}
+
+/// Whether the token is a cast, e.g. `(int)`, which is a parenthesized group on its own.
+fn is_cast(token: &Token) -> bool {
+    token.value.len() > 2 && token.value.starts_with(b"(") && token.value.ends_with(b")")
+}
+
+/// Skip a parenthesized or braced group of tokens, along with the groups nested within it.
+fn skip_balanced(state: &mut State) -> ParseResult<()> {
+    if is_cast(state.stream.current()) {
+        state.stream.next();
+
+        return Ok(());
+    }
+
+    let mut depth = 0usize;
+
+    loop {
+        match state.stream.current().kind {
+            TokenKind::LeftParen | TokenKind::LeftBrace | TokenKind::LeftBracket => depth += 1,
+            TokenKind::RightParen | TokenKind::RightBrace | TokenKind::RightBracket => depth -= 1,
+            TokenKind::Eof => return Err(expected_token!([")", "}"], state)),
+            _ => {}
+        }
+
+        state.stream.next();
+
+        if depth == 0 {
+            return Ok(());
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use smallvec::SmallVec;
+
 use crate::lexer::token::Span;
 use crate::lexer::token::TokenKind;
 use crate::parser::ast::enums::BackedEnumBody;
@@ -12,6 +14,7 @@ use crate::parser::ast::enums::UnitEnumStatement;
 use crate::parser::ast::functions::ConcreteMethod;
 use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::Statement;
+use crate::parser::ast::UseKind;
 use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::expressions;
@@ -21,13 +24,22 @@ use crate::parser::internal::functions;
 use crate::parser::internal::functions::Method;
 use crate::parser::internal::identifiers;
 use crate::parser::internal::modifiers;
+use crate::parser::internal::recovery;
+use crate::parser::internal::symbols;
 use crate::parser::internal::utils;
+use crate::parser::internal::versions;
+use crate::parser::macros::scoped;
+use crate::parser::options::PhpVersion;
+use crate::parser::state::Scope;
 use crate::parser::state::State;
 
 pub fn parse(state: &mut State) -> ParseResult<Statement> {
+    let comments = state.stream.comments();
     let span = utils::skip(state, TokenKind::Enum)?;
+    versions::requires(state, PhpVersion::Php81, "enums", span, 4);
 
     let name = identifiers::type_identifier(state)?;
+    symbols::declare(state, UseKind::Normal, "enum", &name);
 
     let backed_type: Option<BackedEnumType> = if state.stream.current().kind == TokenKind::Colon {
         let span = utils::skip_colon(state)?;
@@ -59,51 +71,61 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
 
     let attributes = state.get_attributes();
     if let Some(backed_type) = backed_type {
-        let body = BackedEnumBody {
-            left_brace: utils::skip_left_brace(state)?,
-            members: {
-                let mut members = Vec::new();
-                while state.stream.current().kind != TokenKind::RightBrace {
-                    if let Some(member) = backed_member(state, &name)? {
-                        members.push(member);
+        let body = scoped!(state, Scope::Enum(name.clone()), {
+            BackedEnumBody {
+                left_brace: utils::skip_left_brace(state)?,
+                members: {
+                    let mut members = Vec::new();
+                    while state.stream.current().kind != TokenKind::RightBrace {
+                        if let Some(member) =
+                            recovery::member(state, |state| backed_member(state, &name))?.flatten()
+                        {
+                            members.push(member);
+                        }
                     }
-                }
 
-                members
-            },
-            right_brace: utils::skip_right_brace(state)?,
-        };
+                    members
+                },
+                right_brace: utils::skip_right_brace(state)?,
+            }
+        });
 
-        Ok(Statement::BackedEnum(BackedEnumStatement {
+        Ok(Statement::BackedEnum(Box::new(BackedEnumStatement {
+            comments,
             r#enum: span,
             name,
             backed_type,
             attributes,
             implements,
             body,
-        }))
+        })))
     } else {
-        let body = UnitEnumBody {
-            left_brace: utils::skip_left_brace(state)?,
-            members: {
-                let mut members = Vec::new();
-                while state.stream.current().kind != TokenKind::RightBrace {
-                    if let Some(member) = unit_member(state, &name)? {
-                        members.push(member);
+        let body = scoped!(state, Scope::Enum(name.clone()), {
+            UnitEnumBody {
+                left_brace: utils::skip_left_brace(state)?,
+                members: {
+                    let mut members = Vec::new();
+                    while state.stream.current().kind != TokenKind::RightBrace {
+                        if let Some(member) =
+                            recovery::member(state, |state| unit_member(state, &name))?.flatten()
+                        {
+                            members.push(member);
+                        }
                     }
-                }
-                members
-            },
-            right_brace: utils::skip_right_brace(state)?,
-        };
+                    members
+                },
+                right_brace: utils::skip_right_brace(state)?,
+            }
+        });
 
-        Ok(Statement::UnitEnum(UnitEnumStatement {
+        Ok(Statement::UnitEnum(Box::new(UnitEnumStatement {
+            comments,
             r#enum: span,
             name,
             attributes,
             implements,
             body,
-        }))
+        })))
     }
 }
 
@@ -117,6 +139,7 @@ fn unit_member(
     if current.kind == TokenKind::Case {
         let attributes = state.get_attributes();
 
+        let comments = state.stream.comments();
         let start = current.span;
         state.stream.next();
 
@@ -139,6 +162,7 @@ fn unit_member(
         let end = utils::skip_semicolon(state)?;
 
         return Ok(Some(UnitEnumMember::Case(UnitEnumCase {
+            comments,
             start,
             end,
             name,
@@ -154,7 +178,8 @@ fn unit_member(
             .map(Some);
     }
 
-    method(state, modifiers, enum_name).map(|method| method.map(UnitEnumMember::Method))
+    method(state, modifiers, enum_name)
+        .map(|method| method.map(|method| UnitEnumMember::Method(Box::new(method))))
 }
 
 fn backed_member(
@@ -167,6 +192,7 @@ fn backed_member(
     if current.kind == TokenKind::Case {
         let attributes = state.get_attributes();
 
+        let comments = state.stream.comments();
         let case = current.span;
         state.stream.next();
 
@@ -192,6 +218,7 @@ fn backed_member(
         let semicolon = utils::skip_semicolon(state)?;
 
         return Ok(Some(BackedEnumMember::Case(BackedEnumCase {
+            comments,
             attributes,
             case,
             name,
@@ -209,12 +236,13 @@ fn backed_member(
             .map(Some);
     }
 
-    method(state, modifiers, enum_name).map(|method| method.map(BackedEnumMember::Method))
+    method(state, modifiers, enum_name)
+        .map(|method| method.map(|method| BackedEnumMember::Method(Box::new(method))))
 }
 
 fn method(
     state: &mut State,
-    modifiers: Vec<(Span, TokenKind)>,
+    modifiers: SmallVec<[(Span, TokenKind); 2]>,
     enum_name: &SimpleIdentifier,
 ) -> ParseResult<Option<ConcreteMethod>> {
     let method = functions::method(
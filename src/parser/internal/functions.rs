@@ -15,14 +15,20 @@ use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::modifiers::MethodModifierGroup;
 use crate::parser::ast::Expression;
 use crate::parser::ast::Statement;
+use crate::parser::ast::UseKind;
 use crate::parser::error::ParseResult;
 use crate::parser::expressions;
 use crate::parser::internal::blocks;
 use crate::parser::internal::data_type;
 use crate::parser::internal::identifiers;
 use crate::parser::internal::parameters;
+use crate::parser::internal::symbols;
 use crate::parser::internal::utils;
 use crate::parser::internal::variables;
+use crate::parser::internal::versions;
+use crate::parser::macros::scoped;
+use crate::parser::options::PhpVersion;
+use crate::parser::state::Scope;
 use crate::parser::state::State;
 
 pub enum MethodType {
@@ -61,62 +67,67 @@ pub fn anonymous_function(state: &mut State) -> ParseResult<Expression> {
         None
     };
 
-    let parameters = parameters::function_parameter_list(state)?;
+    let (parameters, uses, return_type, body) =
+        scoped!(state, Scope::AnonymousFunction(r#static), {
+            let parameters = parameters::function_parameter_list(state)?;
 
-    let current = state.stream.current();
-    let uses = if current.kind == TokenKind::Use {
-        state.stream.next();
+            let current = state.stream.current();
+            let uses = if current.kind == TokenKind::Use {
+                state.stream.next();
+
+                Some(ClosureUse {
+                    comments: state.stream.comments(),
+                    r#use: current.span,
+                    left_parenthesis: utils::skip_left_parenthesis(state)?,
+                    variables: utils::comma_separated::<ClosureUseVariable>(
+                        state,
+                        &|state| {
+                            let use_comments = state.stream.comments();
+                            let current = state.stream.current();
+                            let use_ampersand = if current.kind == TokenKind::Ampersand {
+                                state.stream.next();
+
+                                Some(current.span)
+                            } else {
+                                None
+                            };
+
+                            let var = variables::simple_variable(state)?;
+
+                            Ok(ClosureUseVariable {
+                                comments: use_comments,
+                                variable: var,
+                                ampersand: use_ampersand,
+                            })
+                        },
+                        TokenKind::RightParen,
+                    )?,
+                    right_parenthesis: utils::skip_right_parenthesis(state)?,
+                })
+            } else {
+                None
+            };
 
-        Some(ClosureUse {
-            comments: state.stream.comments(),
-            r#use: current.span,
-            left_parenthesis: utils::skip_left_parenthesis(state)?,
-            variables: utils::comma_separated::<ClosureUseVariable>(
-                state,
-                &|state| {
-                    let use_comments = state.stream.comments();
-                    let current = state.stream.current();
-                    let use_ampersand = if current.kind == TokenKind::Ampersand {
-                        state.stream.next();
-
-                        Some(current.span)
-                    } else {
-                        None
-                    };
-
-                    let var = variables::simple_variable(state)?;
-
-                    Ok(ClosureUseVariable {
-                        comments: use_comments,
-                        variable: var,
-                        ampersand: use_ampersand,
-                    })
-                },
-                TokenKind::RightParen,
-            )?,
-            right_parenthesis: utils::skip_right_parenthesis(state)?,
-        })
-    } else {
-        None
-    };
+            let return_type = if state.stream.current().kind == TokenKind::Colon {
+                Some(ReturnType {
+                    colon: utils::skip_colon(state)?,
+                    data_type: data_type::data_type(state)?,
+                })
+            } else {
+                None
+            };
 
-    let return_type = if state.stream.current().kind == TokenKind::Colon {
-        Some(ReturnType {
-            colon: utils::skip_colon(state)?,
-            data_type: data_type::data_type(state)?,
-        })
-    } else {
-        None
-    };
+            let body = FunctionBody {
+                comments: state.stream.comments(),
+                left_brace: utils::skip_left_brace(state)?,
+                statements: blocks::multiple_statements_until(state, &TokenKind::RightBrace)?,
+                right_brace: utils::skip_right_brace(state)?,
+            };
 
-    let body = FunctionBody {
-        comments: state.stream.comments(),
-        left_brace: utils::skip_left_brace(state)?,
-        statements: blocks::multiple_statements_until(state, &TokenKind::RightBrace)?,
-        right_brace: utils::skip_right_brace(state)?,
-    };
+            (parameters, uses, return_type, body)
+        });
 
-    Ok(Expression::Closure(Closure {
+    Ok(Expression::Closure(Box::new(Closure {
         comments,
         function,
         attributes,
@@ -126,7 +137,7 @@ pub fn anonymous_function(state: &mut State) -> ParseResult<Expression> {
         body,
         r#static,
         ampersand,
-    }))
+    })))
 }
 
 pub fn arrow_function(state: &mut State) -> ParseResult<Expression> {
@@ -141,6 +152,7 @@ pub fn arrow_function(state: &mut State) -> ParseResult<Expression> {
     };
 
     let r#fn = utils::skip(state, TokenKind::Fn)?;
+    versions::requires(state, PhpVersion::Php74, "arrow functions", r#fn, 2);
 
     let current = state.stream.current();
     let ampersand = if state.stream.current().kind == TokenKind::Ampersand {
@@ -152,21 +164,26 @@ pub fn arrow_function(state: &mut State) -> ParseResult<Expression> {
     };
 
     let attributes = state.get_attributes();
-    let parameters = parameters::function_parameter_list(state)?;
-    let return_type = if state.stream.current().kind == TokenKind::Colon {
-        Some(ReturnType {
-            colon: utils::skip_colon(state)?,
-            data_type: data_type::data_type(state)?,
-        })
-    } else {
-        None
-    };
+    let (parameters, return_type, double_arrow, body) =
+        scoped!(state, Scope::ArrowFunction(r#static), {
+            let parameters = parameters::function_parameter_list(state)?;
+            let return_type = if state.stream.current().kind == TokenKind::Colon {
+                Some(ReturnType {
+                    colon: utils::skip_colon(state)?,
+                    data_type: data_type::data_type(state)?,
+                })
+            } else {
+                None
+            };
+
+            let double_arrow = utils::skip(state, TokenKind::DoubleArrow)?;
 
-    let double_arrow = utils::skip(state, TokenKind::DoubleArrow)?;
+            let body = Box::new(expressions::create(state)?);
 
-    let body = Box::new(expressions::create(state)?);
+            (parameters, return_type, double_arrow, body)
+        });
 
-    Ok(Expression::ArrowFunction(ArrowFunction {
+    Ok(Expression::ArrowFunction(Box::new(ArrowFunction {
         comments,
         attributes,
         r#static,
@@ -176,7 +193,7 @@ pub fn arrow_function(state: &mut State) -> ParseResult<Expression> {
         return_type,
         double_arrow,
         body,
-    }))
+    })))
 }
 
 pub fn function(state: &mut State) -> ParseResult<Statement> {
@@ -194,29 +211,34 @@ pub fn function(state: &mut State) -> ParseResult<Statement> {
     };
 
     let name = identifiers::identifier_maybe_soft_reserved(state)?;
+    symbols::declare(state, UseKind::Function, "function", &name);
 
     // get attributes before processing parameters, otherwise
     // parameters will steal attributes of this function.
     let attributes = state.get_attributes();
 
-    let parameters = parameters::function_parameter_list(state)?;
-    let return_type = if state.stream.current().kind == TokenKind::Colon {
-        Some(ReturnType {
-            colon: utils::skip_colon(state)?,
-            data_type: data_type::data_type(state)?,
-        })
-    } else {
-        None
-    };
+    let (parameters, return_type, body) = scoped!(state, Scope::Function(name.clone()), {
+        let parameters = parameters::function_parameter_list(state)?;
+        let return_type = if state.stream.current().kind == TokenKind::Colon {
+            Some(ReturnType {
+                colon: utils::skip_colon(state)?,
+                data_type: data_type::data_type(state)?,
+            })
+        } else {
+            None
+        };
 
-    let body = FunctionBody {
-        comments: state.stream.comments(),
-        left_brace: utils::skip_left_brace(state)?,
-        statements: blocks::multiple_statements_until(state, &TokenKind::RightBrace)?,
-        right_brace: utils::skip_right_brace(state)?,
-    };
+        let body = FunctionBody {
+            comments: state.stream.comments(),
+            left_brace: utils::skip_left_brace(state)?,
+            statements: blocks::multiple_statements_until(state, &TokenKind::RightBrace)?,
+            right_brace: utils::skip_right_brace(state)?,
+        };
+
+        (parameters, return_type, body)
+    });
 
-    Ok(Statement::Function(FunctionStatement {
+    Ok(Statement::Function(Box::new(FunctionStatement {
         comments,
         function,
         name,
@@ -225,7 +247,7 @@ pub fn function(state: &mut State) -> ParseResult<Statement> {
         return_type,
         body,
         ampersand,
-    }))
+    })))
 }
 
 pub fn method(
@@ -254,81 +276,88 @@ pub fn method(
         MethodType::DependingOnModifiers => !modifiers.has_abstract(),
     };
 
-    if name.to_string().to_lowercase() == "__construct" {
-        return if has_body {
-            let parameters = parameters::constructor_parameter_list(state, class)?;
-            let body = MethodBody {
-                comments: state.stream.comments(),
-                left_brace: utils::skip_left_brace(state)?,
-                statements: blocks::multiple_statements_until(state, &TokenKind::RightBrace)?,
-                right_brace: utils::skip_right_brace(state)?,
-            };
-
-            Ok(Method::ConcreteConstructor(ConcreteConstructor {
-                comments,
-                attributes,
-                modifiers,
-                function,
-                ampersand,
-                name,
-                parameters,
-                body,
-            }))
+    let r#static = modifiers.get_static().map(|modifier| modifier.span());
+
+    scoped!(state, Scope::Method(name.clone(), r#static), {
+        if name.to_string().to_lowercase() == "__construct" {
+            if has_body {
+                let parameters = parameters::constructor_parameter_list(state, class)?;
+                let body = MethodBody {
+                    comments: state.stream.comments(),
+                    left_brace: utils::skip_left_brace(state)?,
+                    statements: blocks::multiple_statements_until(state, &TokenKind::RightBrace)?,
+                    right_brace: utils::skip_right_brace(state)?,
+                };
+
+                Ok(Method::ConcreteConstructor(ConcreteConstructor {
+                    comments,
+                    attributes,
+                    modifiers,
+                    function,
+                    ampersand,
+                    name,
+                    parameters,
+                    body,
+                }))
+            } else {
+                let parameters = parameters::function_parameter_list(state)?;
+                let semicolon = utils::skip_semicolon(state)?;
+
+                Ok(Method::AbstractConstructor(AbstractConstructor {
+                    comments,
+                    attributes,
+                    modifiers,
+                    function,
+                    ampersand,
+                    name,
+                    parameters,
+                    semicolon,
+                }))
+            }
         } else {
             let parameters = parameters::function_parameter_list(state)?;
-            let semicolon = utils::skip_semicolon(state)?;
-
-            Ok(Method::AbstractConstructor(AbstractConstructor {
-                comments,
-                attributes,
-                modifiers,
-                function,
-                ampersand,
-                name,
-                parameters,
-                semicolon,
-            }))
-        };
-    }
-
-    let parameters = parameters::function_parameter_list(state)?;
-    let return_type = if state.stream.current().kind == TokenKind::Colon {
-        Some(ReturnType {
-            colon: utils::skip_colon(state)?,
-            data_type: data_type::data_type(state)?,
-        })
-    } else {
-        None
-    };
+            let return_type = if state.stream.current().kind == TokenKind::Colon {
+                Some(ReturnType {
+                    colon: utils::skip_colon(state)?,
+                    data_type: data_type::data_type(state)?,
+                })
+            } else {
+                None
+            };
 
-    if has_body {
-        Ok(Method::Concrete(ConcreteMethod {
-            comments,
-            attributes,
-            modifiers,
-            function,
-            ampersand,
-            name,
-            parameters,
-            return_type,
-            body: MethodBody {
-                comments: state.stream.comments(),
-                left_brace: utils::skip_left_brace(state)?,
-                statements: blocks::multiple_statements_until(state, &TokenKind::RightBrace)?,
-                right_brace: utils::skip_right_brace(state)?,
-            },
-        }))
-    } else {
-        Ok(Method::Abstract(AbstractMethod {
-            comments,
-            attributes,
-            modifiers,
-            function,
-            ampersand,
-            name,
-            parameters,
-            return_type,
-            semicolon: utils::skip_semicolon(state)?,
-        }))
-    }
+            if has_body {
+                Ok(Method::Concrete(ConcreteMethod {
+                    comments,
+                    attributes,
+                    modifiers,
+                    function,
+                    ampersand,
+                    name,
+                    parameters,
+                    return_type,
+                    body: MethodBody {
+                        comments: state.stream.comments(),
+                        left_brace: utils::skip_left_brace(state)?,
+                        statements: blocks::multiple_statements_until(
+                            state,
+                            &TokenKind::RightBrace,
+                        )?,
+                        right_brace: utils::skip_right_brace(state)?,
+                    },
+                }))
+            } else {
+                Ok(Method::Abstract(AbstractMethod {
+                    comments,
+                    attributes,
+                    modifiers,
+                    function,
+                    ampersand,
+                    name,
+                    parameters,
+                    return_type,
+                    semicolon: utils::skip_semicolon(state)?,
+                }))
+            }
+        }
+    })
 }
@@ -4,7 +4,7 @@ use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::state::State;
 
-use crate::peek_token;
+use crate::parser::macros::peek_token;
 
 pub fn identifier_of(state: &mut State, kinds: &[&str]) -> ParseResult<SimpleIdentifier> {
     let ident = identifier(state)?;
@@ -33,7 +33,7 @@ pub fn type_identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
             Ok(SimpleIdentifier {
                 span,
-                value: current.value.clone(),
+                value: current.value.to_byte_string(),
             })
         }
         TokenKind::Enum | TokenKind::From => {
@@ -88,7 +88,7 @@ pub fn label_identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
             Ok(SimpleIdentifier {
                 span,
-                value: current.value.clone(),
+                value: current.value.to_byte_string(),
             })
         }
         TokenKind::Enum | TokenKind::From => {
@@ -144,7 +144,7 @@ pub fn constant_identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
             Ok(SimpleIdentifier {
                 span,
-                value: current.value.clone(),
+                value: current.value.to_byte_string(),
             })
         }
         TokenKind::Enum | TokenKind::From | TokenKind::Self_ | TokenKind::Parent => {
@@ -185,7 +185,7 @@ pub fn identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
         Ok(SimpleIdentifier {
             span,
-            value: current.value.clone(),
+            value: current.value.to_byte_string(),
         })
     } else {
         Err(error::unexpected_token(
@@ -199,7 +199,7 @@ pub fn identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
 pub fn name(state: &mut State) -> ParseResult<SimpleIdentifier> {
     let name = peek_token!([
         TokenKind::Identifier | TokenKind::QualifiedIdentifier => {
-            state.stream.current().value.clone()
+            state.stream.current().value.to_byte_string()
         },
     ], state, "an identifier");
 
@@ -219,7 +219,7 @@ pub fn optional_name(state: &mut State) -> Option<SimpleIdentifier> {
 
             Some(SimpleIdentifier {
                 span: current.span,
-                value: current.value.clone(),
+                value: current.value.to_byte_string(),
             })
         }
         t if is_reserved_identifier(t) => {
@@ -227,7 +227,7 @@ pub fn optional_name(state: &mut State) -> Option<SimpleIdentifier> {
 
             Some(SimpleIdentifier {
                 span: current.span,
-                value: current.value.clone(),
+                value: current.value.to_byte_string(),
             })
         }
         _ => None,
@@ -247,7 +247,7 @@ pub fn full_name(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
             Ok(SimpleIdentifier {
                 span,
-                value: current.value.clone(),
+                value: current.value.to_byte_string(),
             })
         }
         _ => Err(error::unexpected_token(
@@ -270,7 +270,7 @@ pub fn full_type_name(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
             Ok(SimpleIdentifier {
                 span,
-                value: current.value.clone(),
+                value: current.value.to_byte_string(),
             })
         }
         TokenKind::Enum | TokenKind::From => {
@@ -327,7 +327,7 @@ pub fn full_type_name_including_self(state: &mut State) -> ParseResult<SimpleIde
 
             Ok(SimpleIdentifier {
                 span,
-                value: current.value.clone(),
+                value: current.value.to_byte_string(),
             })
         }
         TokenKind::Enum
@@ -5,6 +5,8 @@ use crate::parser::ast::interfaces::InterfaceExtends;
 use crate::parser::ast::interfaces::InterfaceMember;
 use crate::parser::ast::interfaces::InterfaceStatement;
 use crate::parser::ast::Statement;
+use crate::parser::ast::UseKind;
+use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::internal::attributes;
 use crate::parser::internal::constants;
@@ -13,13 +15,20 @@ use crate::parser::internal::functions::Method;
 use crate::parser::internal::functions::MethodType;
 use crate::parser::internal::identifiers;
 use crate::parser::internal::modifiers;
+use crate::parser::internal::properties;
+use crate::parser::internal::recovery;
+use crate::parser::internal::symbols;
 use crate::parser::internal::utils;
+use crate::parser::macros::scoped;
+use crate::parser::state::Scope;
 use crate::parser::state::State;
 
 pub fn parse(state: &mut State) -> ParseResult<Statement> {
+    let comments = state.stream.comments();
     let span = utils::skip(state, TokenKind::Interface)?;
 
     let name = identifiers::type_identifier(state)?;
+    symbols::declare(state, UseKind::Normal, "interface", &name);
 
     let current = state.stream.current();
     let extends = if current.kind == TokenKind::Extends {
@@ -42,26 +51,31 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
 
     let attributes = state.get_attributes();
 
-    let body = InterfaceBody {
-        left_brace: utils::skip_left_brace(state)?,
-        members: {
-            let mut members = Vec::new();
-            while state.stream.current().kind != TokenKind::RightBrace {
-                members.push(member(state, &name)?);
-            }
-
-            members
-        },
-        right_brace: utils::skip_right_brace(state)?,
-    };
+    let body = scoped!(state, Scope::Interface(name.clone()), {
+        InterfaceBody {
+            left_brace: utils::skip_left_brace(state)?,
+            members: {
+                let mut members = Vec::new();
+                while state.stream.current().kind != TokenKind::RightBrace {
+                    if let Some(member) = recovery::member(state, |state| member(state, &name))? {
+                        members.push(member);
+                    }
+                }
+
+                members
+            },
+            right_brace: utils::skip_right_brace(state)?,
+        }
+    });
 
-    Ok(Statement::Interface(InterfaceStatement {
+    Ok(Statement::Interface(Box::new(InterfaceStatement {
+        comments,
         interface: span,
         name,
         attributes,
         extends,
         body,
-    }))
+    })))
 }
 
 fn member(state: &mut State, interface_name: &SimpleIdentifier) -> ParseResult<InterfaceMember> {
@@ -72,6 +86,25 @@ fn member(state: &mut State, interface_name: &SimpleIdentifier) -> ParseResult<I
     if state.stream.current().kind == TokenKind::Const {
         constants::classish(state, modifiers::interface_constant_group(modifiers)?)
             .map(InterfaceMember::Constant)
+    } else if state.stream.current().kind != TokenKind::Function {
+        let property = properties::parse(
+            state,
+            Some(interface_name),
+            None,
+            modifiers::property_group(modifiers)?,
+        )?;
+
+        if !property.is_hooked() {
+            let error = error::interface_property_without_hooks(
+                state,
+                interface_name,
+                property.entries[0].variable(),
+            );
+
+            state.record(error);
+        }
+
+        Ok(InterfaceMember::Property(property))
     } else {
         let method = method(
             state,
@@ -15,10 +15,14 @@ use crate::parser::ast::loops::Level;
 use crate::parser::ast::loops::WhileStatement;
 use crate::parser::ast::loops::WhileStatementBody;
 use crate::parser::ast::Statement;
+use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::expressions;
 use crate::parser::internal::blocks;
+use crate::parser::internal::scopes;
 use crate::parser::internal::utils;
+use crate::parser::macros::scoped;
+use crate::parser::state::Scope;
 use crate::parser::state::State;
 
 pub fn foreach_statement(state: &mut State) -> ParseResult<Statement> {
@@ -75,24 +79,26 @@ pub fn foreach_statement(state: &mut State) -> ParseResult<Statement> {
             }
         })?;
 
-    let body = if state.stream.current().kind == TokenKind::Colon {
-        ForeachStatementBody::Block {
-            colon: utils::skip_colon(state)?,
-            statements: blocks::multiple_statements_until(state, &TokenKind::EndForeach)?,
-            endforeach: utils::skip(state, TokenKind::EndForeach)?,
-            ending: utils::skip_ending(state)?,
+    let body = scoped!(state, Scope::Loop, {
+        if state.stream.current().kind == TokenKind::Colon {
+            ForeachStatementBody::Block {
+                colon: utils::skip_colon(state)?,
+                statements: blocks::multiple_statements_until(state, &TokenKind::EndForeach)?,
+                endforeach: utils::skip(state, TokenKind::EndForeach)?,
+                ending: utils::skip_ending(state)?,
+            }
+        } else {
+            ForeachStatementBody::Statement(parser::statement(state).map(Box::new)?)
         }
-    } else {
-        ForeachStatementBody::Statement(parser::statement(state).map(Box::new)?)
-    };
+    });
 
-    Ok(Statement::Foreach(ForeachStatement {
+    Ok(Statement::Foreach(Box::new(ForeachStatement {
         foreach,
         left_parenthesis,
         iterator,
         right_parenthesis,
         body,
-    }))
+    })))
 }
 
 pub fn for_statement(state: &mut State) -> ParseResult<Statement> {
@@ -125,30 +131,34 @@ pub fn for_statement(state: &mut State) -> ParseResult<Statement> {
         })
     })?;
 
-    let body = if state.stream.current().kind == TokenKind::Colon {
-        ForStatementBody::Block {
-            colon: utils::skip_colon(state)?,
-            statements: blocks::multiple_statements_until(state, &TokenKind::EndFor)?,
-            endfor: utils::skip(state, TokenKind::EndFor)?,
-            ending: utils::skip_ending(state)?,
+    let body = scoped!(state, Scope::Loop, {
+        if state.stream.current().kind == TokenKind::Colon {
+            ForStatementBody::Block {
+                colon: utils::skip_colon(state)?,
+                statements: blocks::multiple_statements_until(state, &TokenKind::EndFor)?,
+                endfor: utils::skip(state, TokenKind::EndFor)?,
+                ending: utils::skip_ending(state)?,
+            }
+        } else {
+            ForStatementBody::Statement(parser::statement(state).map(Box::new)?)
         }
-    } else {
-        ForStatementBody::Statement(parser::statement(state).map(Box::new)?)
-    };
+    });
 
-    Ok(Statement::For(ForStatement {
+    Ok(Statement::For(Box::new(ForStatement {
         r#for,
         left_parenthesis,
         iterator,
         right_parenthesis,
         body,
-    }))
+    })))
 }
 
 pub fn do_while_statement(state: &mut State) -> ParseResult<Statement> {
     let r#do = utils::skip(state, TokenKind::Do)?;
 
-    let body = parser::statement(state).map(Box::new)?;
+    let body = scoped!(state, Scope::Loop, {
+        parser::statement(state).map(Box::new)?
+    });
 
     let r#while = utils::skip(state, TokenKind::While)?;
 
@@ -157,7 +167,7 @@ pub fn do_while_statement(state: &mut State) -> ParseResult<Statement> {
             utils::parenthesized(state, &expressions::create)
         })?;
 
-    Ok(Statement::DoWhile(DoWhileStatement {
+    Ok(Statement::DoWhile(Box::new(DoWhileStatement {
         r#do,
         body,
         r#while,
@@ -165,7 +175,7 @@ pub fn do_while_statement(state: &mut State) -> ParseResult<Statement> {
         condition,
         right_parenthesis,
         semicolon,
-    }))
+    })))
 }
 
 pub fn while_statement(state: &mut State) -> ParseResult<Statement> {
@@ -174,75 +184,100 @@ pub fn while_statement(state: &mut State) -> ParseResult<Statement> {
     let (left_parenthesis, condition, right_parenthesis) =
         utils::parenthesized(state, &expressions::create)?;
 
-    let body = if state.stream.current().kind == TokenKind::Colon {
-        WhileStatementBody::Block {
-            colon: utils::skip_colon(state)?,
-            statements: blocks::multiple_statements_until(state, &TokenKind::EndWhile)?,
-            endwhile: utils::skip(state, TokenKind::EndWhile)?,
-            ending: utils::skip_ending(state)?,
+    let body = scoped!(state, Scope::Loop, {
+        if state.stream.current().kind == TokenKind::Colon {
+            WhileStatementBody::Block {
+                colon: utils::skip_colon(state)?,
+                statements: blocks::multiple_statements_until(state, &TokenKind::EndWhile)?,
+                endwhile: utils::skip(state, TokenKind::EndWhile)?,
+                ending: utils::skip_ending(state)?,
+            }
+        } else {
+            WhileStatementBody::Statement(parser::statement(state).map(Box::new)?)
         }
-    } else {
-        WhileStatementBody::Statement(parser::statement(state).map(Box::new)?)
-    };
+    });
 
-    Ok(Statement::While(WhileStatement {
+    Ok(Statement::While(Box::new(WhileStatement {
         r#while,
         left_parenthesis,
         condition,
         right_parenthesis,
         body,
-    }))
+    })))
 }
 
 pub fn continue_statement(state: &mut State) -> ParseResult<Statement> {
+    let r#continue = utils::skip(state, TokenKind::Continue)?;
+    let level = maybe_loop_level(state, "continue")?;
+    let ending = utils::skip_ending(state)?;
+
+    scopes::loop_level(state, "continue", r#continue, ending.span(), level.as_ref());
+
     Ok(Statement::Continue(ContinueStatement {
-        r#continue: utils::skip(state, TokenKind::Continue)?,
-        level: maybe_loop_level(state)?,
-        ending: utils::skip_ending(state)?,
+        r#continue,
+        level,
+        ending,
     }))
 }
 
 pub fn break_statement(state: &mut State) -> ParseResult<Statement> {
+    let r#break = utils::skip(state, TokenKind::Break)?;
+    let level = maybe_loop_level(state, "break")?;
+    let ending = utils::skip_ending(state)?;
+
+    scopes::loop_level(state, "break", r#break, ending.span(), level.as_ref());
+
     Ok(Statement::Break(BreakStatement {
-        r#break: utils::skip(state, TokenKind::Break)?,
-        level: maybe_loop_level(state)?,
-        ending: utils::skip_ending(state)?,
+        r#break,
+        level,
+        ending,
     }))
 }
 
-fn maybe_loop_level(state: &mut State) -> ParseResult<Option<Level>> {
+fn maybe_loop_level(state: &mut State, keyword: &str) -> ParseResult<Option<Level>> {
     let current = &state.stream.current().kind;
 
-    Ok(
-        if current == &TokenKind::SemiColon || current == &TokenKind::CloseTag {
-            None
-        } else {
-            Some(loop_level(state)?)
-        },
-    )
+    if current == &TokenKind::SemiColon || current == &TokenKind::CloseTag {
+        Ok(None)
+    } else {
+        loop_level(state, keyword)
+    }
 }
 
-fn loop_level(state: &mut State) -> ParseResult<Level> {
+fn loop_level(state: &mut State, keyword: &str) -> ParseResult<Option<Level>> {
+    let current = state.stream.current();
     if let Token {
         kind: TokenKind::LiteralInteger,
         span,
         value,
-    } = state.stream.current()
+        ..
+    } = current
     {
         state.stream.next();
 
-        return Ok(Level::Literal(LiteralInteger {
-            value: value.clone(),
+        return Ok(Some(Level::Literal(LiteralInteger {
+            value: value.to_byte_string(),
             span: *span,
-        }));
+        })));
+    }
+
+    if current.kind != TokenKind::LeftParen {
+        // parse the level, but don't do anything with it.
+        let _ = expressions::create(state)?;
+
+        let error = error::loop_level_must_be_an_integer_literal(current.span, keyword);
+
+        state.record(error);
+
+        return Ok(None);
     }
 
     let (left_parenthesis, level, right_parenthesis) =
-        utils::parenthesized(state, &|state| loop_level(state).map(Box::new))?;
+        utils::parenthesized(state, &|state| loop_level(state, keyword))?;
 
-    Ok(Level::Parenthesized {
+    Ok(level.map(|level| Level::Parenthesized {
         left_parenthesis,
-        level,
+        level: Box::new(level),
         right_parenthesis,
-    })
+    }))
 }
@@ -16,9 +16,13 @@ pub(in crate::parser) mod namespaces;
 pub(in crate::parser) mod parameters;
 pub(in crate::parser) mod precedences;
 pub(in crate::parser) mod properties;
+pub(in crate::parser) mod recovery;
+pub(in crate::parser) mod scopes;
 pub(in crate::parser) mod strings;
+pub(in crate::parser) mod symbols;
 pub(in crate::parser) mod traits;
 pub(in crate::parser) mod try_block;
 pub(in crate::parser) mod uses;
 pub(in crate::parser) mod utils;
 pub(in crate::parser) mod variables;
+pub(in crate::parser) mod versions;
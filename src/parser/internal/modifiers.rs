@@ -1,3 +1,5 @@
+use smallvec::SmallVec;
+
 use crate::lexer::token::Span;
 use crate::lexer::token::TokenKind;
 use crate::parser::ast::modifiers::ClassModifier;
@@ -15,7 +17,7 @@ use crate::parser::error::ParseResult;
 use crate::parser::state::State;
 
 #[inline(always)]
-pub fn class_group(input: Vec<(Span, TokenKind)>) -> ParseResult<ClassModifierGroup> {
+pub fn class_group(input: SmallVec<[(Span, TokenKind); 2]>) -> ParseResult<ClassModifierGroup> {
     let mut final_span = None;
     let mut abstract_span = None;
 
@@ -49,13 +51,13 @@ pub fn class_group(input: Vec<(Span, TokenKind)>) -> ParseResult<ClassModifierGr
                 *span,
             )),
         })
-        .collect::<ParseResult<Vec<ClassModifier>>>()?;
+        .collect::<ParseResult<SmallVec<_>>>()?;
 
     Ok(ClassModifierGroup { modifiers })
 }
 
 #[inline(always)]
-pub fn method_group(input: Vec<(Span, TokenKind)>) -> ParseResult<MethodModifierGroup> {
+pub fn method_group(input: SmallVec<[(Span, TokenKind); 2]>) -> ParseResult<MethodModifierGroup> {
     let mut final_span = None;
     let mut abstract_span = None;
 
@@ -96,13 +98,15 @@ pub fn method_group(input: Vec<(Span, TokenKind)>) -> ParseResult<MethodModifier
                 *span,
             )),
         })
-        .collect::<ParseResult<Vec<MethodModifier>>>()?;
+        .collect::<ParseResult<SmallVec<_>>>()?;
 
     Ok(MethodModifierGroup { modifiers })
 }
 
 #[inline(always)]
-pub fn interface_method_group(input: Vec<(Span, TokenKind)>) -> ParseResult<MethodModifierGroup> {
+pub fn interface_method_group(
+    input: SmallVec<[(Span, TokenKind); 2]>,
+) -> ParseResult<MethodModifierGroup> {
     let modifiers = input
         .iter()
         .map(|(span, token)| match token {
@@ -113,13 +117,15 @@ pub fn interface_method_group(input: Vec<(Span, TokenKind)>) -> ParseResult<Meth
                 *span,
             )),
         })
-        .collect::<ParseResult<Vec<MethodModifier>>>()?;
+        .collect::<ParseResult<SmallVec<_>>>()?;
 
     Ok(MethodModifierGroup { modifiers })
 }
 
 #[inline(always)]
-pub fn enum_method_group(input: Vec<(Span, TokenKind)>) -> ParseResult<MethodModifierGroup> {
+pub fn enum_method_group(
+    input: SmallVec<[(Span, TokenKind); 2]>,
+) -> ParseResult<MethodModifierGroup> {
     let modifiers = input
         .iter()
         .map(|(span, token)| match token {
@@ -133,13 +139,15 @@ pub fn enum_method_group(input: Vec<(Span, TokenKind)>) -> ParseResult<MethodMod
                 *span,
             )),
         })
-        .collect::<ParseResult<Vec<MethodModifier>>>()?;
+        .collect::<ParseResult<SmallVec<_>>>()?;
 
     Ok(MethodModifierGroup { modifiers })
 }
 
 #[inline(always)]
-pub fn property_group(input: Vec<(Span, TokenKind)>) -> ParseResult<PropertyModifierGroup> {
+pub fn property_group(
+    input: SmallVec<[(Span, TokenKind); 2]>,
+) -> ParseResult<PropertyModifierGroup> {
     let modifiers = input
         .iter()
         .map(|(span, token)| match token {
@@ -148,19 +156,24 @@ pub fn property_group(input: Vec<(Span, TokenKind)>) -> ParseResult<PropertyModi
             TokenKind::Public => Ok(PropertyModifier::Public(*span)),
             TokenKind::Protected => Ok(PropertyModifier::Protected(*span)),
             TokenKind::Private => Ok(PropertyModifier::Private(*span)),
+            TokenKind::PublicSet => Ok(PropertyModifier::PublicSet(*span)),
+            TokenKind::ProtectedSet => Ok(PropertyModifier::ProtectedSet(*span)),
+            TokenKind::PrivateSet => Ok(PropertyModifier::PrivateSet(*span)),
+            TokenKind::Abstract => Ok(PropertyModifier::Abstract(*span)),
+            TokenKind::Final => Ok(PropertyModifier::Final(*span)),
             _ => Err(error::modifier_cannot_be_used_for_property(
                 token.to_string(),
                 *span,
             )),
         })
-        .collect::<ParseResult<Vec<PropertyModifier>>>()?;
+        .collect::<ParseResult<SmallVec<_>>>()?;
 
     Ok(PropertyModifierGroup { modifiers })
 }
 
 #[inline(always)]
 pub fn promoted_property_group(
-    input: Vec<(Span, TokenKind)>,
+    input: SmallVec<[(Span, TokenKind); 2]>,
 ) -> ParseResult<PromotedPropertyModifierGroup> {
     let modifiers = input
         .iter()
@@ -169,17 +182,22 @@ pub fn promoted_property_group(
             TokenKind::Private => Ok(PromotedPropertyModifier::Private(*span)),
             TokenKind::Protected => Ok(PromotedPropertyModifier::Protected(*span)),
             TokenKind::Public => Ok(PromotedPropertyModifier::Public(*span)),
+            TokenKind::PublicSet => Ok(PromotedPropertyModifier::PublicSet(*span)),
+            TokenKind::ProtectedSet => Ok(PromotedPropertyModifier::ProtectedSet(*span)),
+            TokenKind::PrivateSet => Ok(PromotedPropertyModifier::PrivateSet(*span)),
             _ => Err(error::modifier_cannot_be_used_for_promoted_property(
                 token.to_string(),
                 *span,
             )),
         })
-        .collect::<ParseResult<Vec<PromotedPropertyModifier>>>()?;
+        .collect::<ParseResult<SmallVec<_>>>()?;
 
     Ok(PromotedPropertyModifierGroup { modifiers })
 }
 
-pub fn constant_group(input: Vec<(Span, TokenKind)>) -> ParseResult<ConstantModifierGroup> {
+pub fn constant_group(
+    input: SmallVec<[(Span, TokenKind); 2]>,
+) -> ParseResult<ConstantModifierGroup> {
     let mut final_span = None;
     let mut private_span = None;
 
@@ -195,7 +213,7 @@ pub fn constant_group(input: Vec<(Span, TokenKind)>) -> ParseResult<ConstantModi
                         final_span, *span,
                     ))
                 } else {
-                    Ok(ConstantModifier::Final(*span))
+                    Ok(ConstantModifier::Private(*span))
                 }
             }
             TokenKind::Final => {
@@ -214,13 +232,13 @@ pub fn constant_group(input: Vec<(Span, TokenKind)>) -> ParseResult<ConstantModi
                 *span,
             )),
         })
-        .collect::<ParseResult<Vec<ConstantModifier>>>()?;
+        .collect::<ParseResult<SmallVec<_>>>()?;
 
     Ok(ConstantModifierGroup { modifiers })
 }
 
 pub fn interface_constant_group(
-    input: Vec<(Span, TokenKind)>,
+    input: SmallVec<[(Span, TokenKind); 2]>,
 ) -> ParseResult<ConstantModifierGroup> {
     let modifiers = input
         .iter()
@@ -232,15 +250,15 @@ pub fn interface_constant_group(
                 *span,
             )),
         })
-        .collect::<ParseResult<Vec<ConstantModifier>>>()?;
+        .collect::<ParseResult<SmallVec<_>>>()?;
 
     Ok(ConstantModifierGroup { modifiers })
 }
 
-pub fn collect(state: &mut State) -> ParseResult<Vec<(Span, TokenKind)>> {
-    let mut collected: Vec<(Span, TokenKind)> = vec![];
+pub fn collect(state: &mut State) -> ParseResult<SmallVec<[(Span, TokenKind); 2]>> {
+    let mut collected: SmallVec<[(Span, TokenKind); 2]> = SmallVec::new();
 
-    let collectable_tokens = vec![
+    let collectable_tokens = [
         TokenKind::Private,
         TokenKind::Protected,
         TokenKind::Public,
@@ -255,6 +273,16 @@ pub fn collect(state: &mut State) -> ParseResult<Vec<(Span, TokenKind)>> {
     let mut current_span = current.span;
 
     while collectable_tokens.contains(&current_kind) {
+        // `private(set)`, `protected(set)` and `public(set)` are collected as a single modifier.
+        let set = is_set_visibility(state);
+        if set {
+            current_kind = match current_kind {
+                TokenKind::Private => TokenKind::PrivateSet,
+                TokenKind::Protected => TokenKind::ProtectedSet,
+                _ => TokenKind::PublicSet,
+            };
+        }
+
         if let Some((span, _)) = collected.iter().find(|(_, kind)| kind == &current_kind) {
             return Err(error::multiple_modifiers(
                 current_kind.to_string(),
@@ -281,6 +309,25 @@ pub fn collect(state: &mut State) -> ParseResult<Vec<(Span, TokenKind)>> {
             }
         }
 
+        if set {
+            if let Some((span, visibility)) = collected.iter().find(|(_, kind)| {
+                matches!(
+                    kind,
+                    TokenKind::PublicSet | TokenKind::ProtectedSet | TokenKind::PrivateSet
+                )
+            }) {
+                state.record(error::multiple_visibility_modifiers(
+                    (visibility.to_string(), *span),
+                    (current_kind.to_string(), current_span),
+                ));
+            }
+
+            // skip `(`, `set` and `)`.
+            state.stream.next();
+            state.stream.next();
+            state.stream.next();
+        }
+
         collected.push((current_span, current_kind));
 
         state.stream.next();
@@ -292,3 +339,19 @@ pub fn collect(state: &mut State) -> ParseResult<Vec<(Span, TokenKind)>> {
 
     Ok(collected)
 }
+
+fn is_set_visibility(state: &State) -> bool {
+    if !matches!(
+        state.stream.current().kind,
+        TokenKind::Public | TokenKind::Protected | TokenKind::Private
+    ) || state.stream.peek().kind != TokenKind::LeftParen
+    {
+        return false;
+    }
+
+    let set = state.stream.lookahead(1);
+
+    set.kind == TokenKind::Identifier
+        && set.value.eq_ignore_ascii_case(b"set")
+        && state.stream.lookahead(2).kind == TokenKind::RightParen
+}
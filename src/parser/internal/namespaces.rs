@@ -11,11 +11,12 @@ use crate::parser::ast::Statement;
 use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::internal::identifiers;
+use crate::parser::internal::recovery;
 use crate::parser::internal::utils;
+use crate::parser::macros::scoped;
 use crate::parser::state::NamespaceType;
 use crate::parser::state::Scope;
 use crate::parser::state::State;
-use crate::scoped;
 
 pub fn namespace(state: &mut State) -> ParseResult<Statement> {
     let start = utils::skip(state, TokenKind::Namespace)?;
@@ -59,7 +60,7 @@ fn unbraced_namespace(
         // `namespace` token as a top level statement, this namespace scope ends.
         // otherwise we will end up with nested namespace statements.
         while state.stream.current().kind != TokenKind::Namespace && !state.stream.is_eof() {
-            statements.push(parser::top_level_statement(state)?);
+            statements.push(recovery::statement(state, parser::top_level_statement)?);
         }
 
         statements
@@ -85,7 +86,7 @@ fn braced_namespace(
 
         let mut statements = Block::new();
         while state.stream.current().kind != TokenKind::RightBrace && !state.stream.is_eof() {
-            statements.push(parser::top_level_statement(state)?);
+            statements.push(recovery::statement(state, parser::top_level_statement)?);
         }
 
         let end = utils::skip_right_brace(state)?;
@@ -6,6 +6,7 @@ use crate::parser::ast::functions::ConstructorParameterList;
 use crate::parser::ast::functions::FunctionParameter;
 use crate::parser::ast::functions::FunctionParameterList;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::modifiers::ModifierKind;
 use crate::parser::error;
 use crate::parser::error::ParseError;
 use crate::parser::error::ParseResult;
@@ -14,8 +15,11 @@ use crate::parser::internal::attributes;
 use crate::parser::internal::data_type;
 use crate::parser::internal::identifiers;
 use crate::parser::internal::modifiers;
+use crate::parser::internal::properties;
 use crate::parser::internal::utils;
 use crate::parser::internal::variables;
+use crate::parser::internal::versions;
+use crate::parser::options::PhpVersion;
 use crate::parser::state::State;
 
 pub fn function_parameter_list(state: &mut State) -> Result<FunctionParameterList, ParseError> {
@@ -90,6 +94,35 @@ pub fn constructor_parameter_list(
             attributes::gather_attributes(state)?;
 
             let modifiers = modifiers::promoted_property_group(modifiers::collect(state)?)?;
+            if let Some(modifier) = modifiers.modifiers.first() {
+                versions::requires(
+                    state,
+                    PhpVersion::Php80,
+                    "constructor property promotion",
+                    modifier.span(),
+                    modifier.kind().to_string().len(),
+                );
+            }
+
+            if let Some(modifier) = modifiers.get_readonly() {
+                versions::requires(
+                    state,
+                    PhpVersion::Php81,
+                    "readonly properties",
+                    modifier.span(),
+                    8,
+                );
+            }
+
+            if let Some(modifier) = modifiers.get_set_visibility() {
+                versions::requires(
+                    state,
+                    PhpVersion::Php84,
+                    "asymmetric visibility",
+                    modifier.span(),
+                    modifier.kind().to_string().len(),
+                );
+            }
 
             let ty = data_type::optional_data_type(state)?;
 
@@ -149,6 +182,29 @@ pub fn constructor_parameter_list(
                 }
             }
 
+            if let Some(modifier) = modifiers.get_set_visibility() {
+                let visibility = modifiers
+                    .modifiers
+                    .iter()
+                    .find(|modifier| {
+                        matches!(
+                            modifier.kind(),
+                            ModifierKind::Public | ModifierKind::Protected | ModifierKind::Private
+                        )
+                    })
+                    .map(|modifier| (modifier.kind(), modifier.span()));
+
+                properties::set_visibility(
+                    state,
+                    class,
+                    &var,
+                    ty.as_ref(),
+                    (modifier.kind(), modifier.span()),
+                    visibility,
+                    None,
+                );
+            }
+
             let mut default = None;
             if state.stream.current().kind == TokenKind::Equals {
                 state.stream.next();
@@ -185,6 +241,7 @@ pub fn argument_list(state: &mut State) -> ParseResult<ArgumentList> {
 
     let mut arguments = Vec::new();
     let mut has_used_named_arguments = false;
+    let mut has_used_argument_unpacking = false;
 
     while !state.stream.is_eof() && state.stream.current().kind != TokenKind::RightParen {
         let span = state.stream.current().span;
@@ -196,6 +253,15 @@ pub fn argument_list(state: &mut State) -> ParseResult<ArgumentList> {
                 span,
                 state.stream.current().span,
             ));
+        } else if argument.is_spread() {
+            has_used_argument_unpacking = true;
+        } else if has_used_argument_unpacking {
+            return Err(
+                error::cannot_use_positional_argument_after_argument_unpacking(
+                    span,
+                    state.stream.current().span,
+                ),
+            );
         }
 
         arguments.push(argument);
@@ -217,19 +283,31 @@ pub fn argument_list(state: &mut State) -> ParseResult<ArgumentList> {
     })
 }
 
+/// Parse a single parenthesized argument, e.g. of `empty($a)`.
+///
+/// `None` is returned when the input is not a single argument, unless it is required, in which
+/// case the error is returned instead.
 pub fn single_argument(
     state: &mut State,
     required: bool,
     only_positional: bool,
 ) -> Option<ParseResult<SingleArgument>> {
     let comments = state.stream.comments();
-    let start = utils::skip_left_parenthesis(state).ok()?;
+    let start = match utils::skip_left_parenthesis(state) {
+        Ok(start) => start,
+        Err(error) if required => return Some(Err(error)),
+        Err(_) => return None,
+    };
 
     let mut first_argument = None;
 
     while !state.stream.is_eof() && state.stream.current().kind != TokenKind::RightParen {
         let span = state.stream.current().span;
-        let (named, argument) = argument(state).ok()?;
+        let (named, argument) = match argument(state) {
+            Ok(argument) => argument,
+            Err(error) if required => return Some(Err(error)),
+            Err(_) => return None,
+        };
         if only_positional && named {
             return Some(Err(error::only_positional_arguments_are_accepted(
                 span,
@@ -260,19 +338,24 @@ pub fn single_argument(
         )));
     }
 
-    let end = utils::skip_right_parenthesis(state).ok()?;
-
-    first_argument.as_ref()?;
+    let end = match utils::skip_right_parenthesis(state) {
+        Ok(end) => end,
+        Err(error) if required => return Some(Err(error)),
+        Err(_) => return None,
+    };
 
     Some(Ok(SingleArgument {
         comments,
         left_parenthesis: start,
         right_parenthesis: end,
-        argument: first_argument.unwrap(),
+        argument: first_argument?,
     }))
 }
 
 fn argument(state: &mut State) -> ParseResult<(bool, Argument)> {
+    let comments = state.stream.comments();
+    let start = state.stream.current().span;
+
     if identifiers::is_identifier_maybe_reserved(&state.stream.current().kind)
         && state.stream.peek().kind == TokenKind::Colon
     {
@@ -284,15 +367,18 @@ fn argument(state: &mut State) -> ParseResult<(bool, Argument)> {
             None
         };
         let value = expressions::create(state)?;
+        let end = state.stream.previous().span;
 
         return Ok((
             true,
             Argument::Named {
-                comments: state.stream.comments(),
+                comments,
+                start,
                 name,
                 colon,
                 ellipsis,
                 value,
+                end,
             },
         ));
     }
@@ -304,13 +390,16 @@ fn argument(state: &mut State) -> ParseResult<(bool, Argument)> {
     };
 
     let value = expressions::create(state)?;
+    let end = state.stream.previous().span;
 
     Ok((
         false,
         Argument::Positional {
-            comments: state.stream.comments(),
+            comments,
+            start,
             ellipsis,
             value,
+            end,
         },
     ))
 }
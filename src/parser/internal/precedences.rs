@@ -27,6 +27,7 @@ pub enum Precedence {
     BitwiseAnd,
     Equality,
     LtGt,
+    Pipe,
     Concat,
     BitShift,
     AddSub,
@@ -51,6 +52,7 @@ impl Precedence {
             Plus | Minus => Self::AddSub,
             LeftShift | RightShift => Self::BitShift,
             Dot => Self::Concat,
+            PipeGreaterThan => Self::Pipe,
             LessThan | LessThanEquals | GreaterThan | GreaterThanEquals => Self::LtGt,
             DoubleEquals | BangEquals | TripleEquals | BangDoubleEquals | AngledLeftRight
             | Spaceship => Self::Equality,
@@ -68,7 +70,7 @@ impl Precedence {
             LogicalAnd => Self::KeyAnd,
             LogicalOr => Self::KeyOr,
             LogicalXor => Self::KeyXor,
-            _ => unimplemented!("precedence for op {:?}", kind),
+            _ => unreachable!("precedence for op {:?}", kind),
         }
     }
 
@@ -80,7 +82,7 @@ impl Precedence {
             Increment | Decrement => Self::IncDec,
             LeftParen | LeftBracket => Self::CallDim,
             Arrow | QuestionArrow | DoubleColon => Self::ObjectAccess,
-            _ => unimplemented!("postfix precedence for op {:?}", kind),
+            _ => unreachable!("postfix precedence for op {:?}", kind),
         }
     }
 
@@ -91,6 +93,7 @@ impl Precedence {
             | Self::AddSub
             | Self::BitShift
             | Self::Concat
+            | Self::Pipe
             | Self::BitwiseAnd
             | Self::BitwiseOr
             | Self::BitwiseXor
@@ -1,23 +1,83 @@
+use crate::lexer::token::Span;
 use crate::lexer::token::TokenKind;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::functions::FunctionBody;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::modifiers::ModifierKind;
+use crate::parser::ast::modifiers::PropertyModifier;
 use crate::parser::ast::modifiers::PropertyModifierGroup;
 use crate::parser::ast::properties::Property;
 use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::properties::PropertyHook;
+use crate::parser::ast::properties::PropertyHookBody;
+use crate::parser::ast::properties::PropertyHookList;
 use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::variables::SimpleVariable;
 use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::expressions;
+use crate::parser::internal::attributes;
+use crate::parser::internal::blocks;
 use crate::parser::internal::data_type;
+use crate::parser::internal::identifiers;
+use crate::parser::internal::parameters;
 use crate::parser::internal::utils;
 use crate::parser::internal::variables;
+use crate::parser::internal::versions;
+use crate::parser::macros::scoped;
+use crate::parser::options::PhpVersion;
+use crate::parser::state::Scope;
 use crate::parser::state::State;
 
+/// Parse a property declaration, `readonly_class` being the span of the `readonly` modifier of
+/// the class, whose properties are all readonly.
 pub fn parse(
     state: &mut State,
     class_name: Option<&SimpleIdentifier>,
+    readonly_class: Option<Span>,
     modifiers: PropertyModifierGroup,
 ) -> ParseResult<Property> {
+    let comments = state.stream.comments();
+    // taken before the hooks, which have attributes of their own.
+    let attributes = state.get_attributes();
+    if let Some(modifier) = modifiers.get_readonly() {
+        versions::requires(
+            state,
+            PhpVersion::Php81,
+            "readonly properties",
+            modifier.span(),
+            8,
+        );
+    }
+
+    for modifier in &modifiers.modifiers {
+        match modifier {
+            PropertyModifier::Abstract(span) => {
+                versions::requires(state, PhpVersion::Php84, "abstract properties", *span, 8)
+            }
+            PropertyModifier::Final(span) => {
+                versions::requires(state, PhpVersion::Php84, "final properties", *span, 5)
+            }
+            PropertyModifier::PublicSet(span)
+            | PropertyModifier::ProtectedSet(span)
+            | PropertyModifier::PrivateSet(span) => versions::requires(
+                state,
+                PhpVersion::Php84,
+                "asymmetric visibility",
+                *span,
+                modifier.kind().to_string().len(),
+            ),
+            _ => {}
+        }
+    }
+
+    let readonly = modifiers
+        .get_readonly()
+        .map(|modifier| modifier.span())
+        .or(readonly_class);
+
     let ty = data_type::optional_data_type(state)?;
+    typed(state, &ty);
 
     let mut entries = vec![];
     let mut type_checked = false;
@@ -26,18 +86,41 @@ pub fn parse(
 
         if !type_checked {
             type_checked = true;
-            if modifiers.has_readonly() && modifiers.has_static() {
+            if let (Some(readonly), Some(modifier)) = (readonly, modifiers.get_static()) {
                 let error = error::static_property_cannot_be_readonly(
                     state,
                     class_name,
                     &variable,
-                    modifiers.get_static().unwrap().span(),
-                    modifiers.get_readonly().unwrap().span(),
+                    modifier.span(),
+                    readonly,
                 );
 
                 state.record(error);
             }
 
+            if let Some(modifier) = modifiers.get_set_visibility() {
+                let visibility = modifiers
+                    .modifiers
+                    .iter()
+                    .find(|modifier| {
+                        matches!(
+                            modifier.kind(),
+                            ModifierKind::Public | ModifierKind::Protected | ModifierKind::Private
+                        )
+                    })
+                    .map(|modifier| (modifier.kind(), modifier.span()));
+
+                set_visibility(
+                    state,
+                    class_name,
+                    &variable,
+                    ty.as_ref(),
+                    (modifier.kind(), modifier.span()),
+                    visibility,
+                    modifiers.get_static().map(|modifier| modifier.span()),
+                );
+            }
+
             match &ty {
                 Some(ty) => {
                     if ty.includes_callable() || ty.is_bottom() {
@@ -52,12 +135,9 @@ pub fn parse(
                     }
                 }
                 None => {
-                    if let Some(modifier) = modifiers.get_readonly() {
+                    if let Some(readonly) = readonly {
                         let error = error::missing_type_for_readonly_property(
-                            state,
-                            class_name,
-                            &variable,
-                            modifier.span(),
+                            state, class_name, &variable, readonly,
                         );
 
                         state.record(error);
@@ -68,12 +148,12 @@ pub fn parse(
 
         let current = state.stream.current();
         if current.kind == TokenKind::Equals {
-            if let Some(modifier) = modifiers.get_readonly() {
+            if let Some(readonly) = readonly {
                 let error = error::readonly_property_has_default_value(
                     state,
                     class_name,
                     &variable,
-                    modifier.span(),
+                    readonly,
                     current.span,
                 );
 
@@ -99,24 +179,245 @@ pub fn parse(
         }
     }
 
-    let end = utils::skip_semicolon(state)?;
+    let hooks = if state.stream.current().kind == TokenKind::LeftBrace {
+        Some(hooks(state, class_name, &modifiers, readonly, &entries)?)
+    } else {
+        None
+    };
+
+    if let Some(modifier) = modifiers.get_abstract() {
+        if !hooks
+            .as_ref()
+            .is_some_and(|hooks| hooks.hooks.iter().any(|hook| hook.is_abstract()))
+        {
+            let error = error::abstract_property_without_abstract_hook(
+                state,
+                class_name,
+                entries[0].variable(),
+                modifier.span(),
+            );
+
+            state.record(error);
+        }
+    }
+
+    let end = match &hooks {
+        Some(hooks) => hooks.right_brace,
+        None => utils::skip_semicolon(state)?,
+    };
 
     Ok(Property {
+        comments,
         r#type: ty,
         modifiers,
-        attributes: state.get_attributes(),
+        attributes,
         entries,
+        hooks,
         end,
     })
 }
 
+/// Validate the set visibility of a property, `visibility` being its other visibility modifier,
+/// if any, and `static_span` the span of its `static` modifier, if any.
+pub(crate) fn set_visibility(
+    state: &mut State,
+    class_name: Option<&SimpleIdentifier>,
+    variable: &SimpleVariable,
+    ty: Option<&Type>,
+    set: (ModifierKind, Span),
+    visibility: Option<(ModifierKind, Span)>,
+    static_span: Option<Span>,
+) {
+    let (set_kind, set_span) = set;
+    let error = if let Some(static_span) = static_span {
+        Some(error::invalid_set_visibility(
+            state,
+            class_name,
+            variable,
+            "static",
+            (set_kind.to_string(), set_span),
+            Some(("static".to_string(), static_span)),
+        ))
+    } else if ty.is_none() {
+        Some(error::invalid_set_visibility(
+            state,
+            class_name,
+            variable,
+            "type",
+            (set_kind.to_string(), set_span),
+            None,
+        ))
+    } else {
+        match visibility {
+            Some((kind, span)) if restriction(set_kind) < restriction(kind) => {
+                Some(error::invalid_set_visibility(
+                    state,
+                    class_name,
+                    variable,
+                    "visibility",
+                    (set_kind.to_string(), set_span),
+                    Some((kind.to_string(), span)),
+                ))
+            }
+            _ => None,
+        }
+    };
+
+    if let Some(error) = error {
+        state.record(error);
+    }
+}
+
+/// How restrictive a visibility is, from `public` to `private`.
+fn restriction(kind: ModifierKind) -> u8 {
+    match kind {
+        ModifierKind::Protected | ModifierKind::ProtectedSet => 1,
+        ModifierKind::Private | ModifierKind::PrivateSet => 2,
+        _ => 0,
+    }
+}
+
+/// Parse the hooks of a property, following its declaration.
+fn hooks(
+    state: &mut State,
+    class_name: Option<&SimpleIdentifier>,
+    modifiers: &PropertyModifierGroup,
+    readonly: Option<Span>,
+    entries: &[PropertyEntry],
+) -> ParseResult<PropertyHookList> {
+    let left_brace = utils::skip_left_brace(state)?;
+    versions::requires(state, PhpVersion::Php84, "property hooks", left_brace, 1);
+
+    let property = entries[entries.len() - 1].variable();
+    let reason = if let Some(modifier) = modifiers.get_static() {
+        Some(("static", modifier.span()))
+    } else if let Some(readonly) = readonly {
+        Some(("readonly", readonly))
+    } else if entries.len() > 1 {
+        Some(("multiple", entries[0].variable().span))
+    } else {
+        None
+    };
+
+    if let Some((reason, span)) = reason {
+        let error = error::property_cannot_have_hooks(
+            state, class_name, property, reason, span, left_brace,
+        );
+
+        state.record(error);
+    }
+
+    let mut hooks: Vec<PropertyHook> = vec![];
+    while state.stream.current().kind != TokenKind::RightBrace {
+        let hook = hook(state)?;
+
+        if !matches!(&hook.name.value.bytes[..], b"get" | b"set") {
+            let error = error::unknown_property_hook(state, class_name, property, &hook.name);
+
+            state.record(error);
+        } else if let Some(previous) = hooks
+            .iter()
+            .find(|previous| previous.name.value == hook.name.value)
+        {
+            let error = error::duplicate_property_hook(
+                state,
+                class_name,
+                property,
+                &hook.name,
+                &previous.name,
+            );
+
+            state.record(error);
+        }
+
+        hooks.push(hook);
+    }
+
+    Ok(PropertyHookList {
+        left_brace,
+        hooks,
+        right_brace: utils::skip_right_brace(state)?,
+    })
+}
+
+fn hook(state: &mut State) -> ParseResult<PropertyHook> {
+    attributes::gather_attributes(state)?;
+
+    let comments = state.stream.comments();
+    let attributes = state.get_attributes();
+
+    let r#final = if state.stream.current().kind == TokenKind::Final {
+        let span = state.stream.current().span;
+        state.stream.next();
+
+        Some(span)
+    } else {
+        None
+    };
+
+    let ampersand = if state.stream.current().kind == TokenKind::Ampersand {
+        let span = state.stream.current().span;
+        state.stream.next();
+
+        Some(span)
+    } else {
+        None
+    };
+
+    let name = identifiers::identifier_maybe_reserved(state)?;
+
+    let (parameters, body) = scoped!(state, Scope::Method(name.clone(), None), {
+        let parameters = if state.stream.current().kind == TokenKind::LeftParen {
+            Some(parameters::function_parameter_list(state)?)
+        } else {
+            None
+        };
+
+        let current = state.stream.current();
+        let body = match current.kind {
+            TokenKind::SemiColon => {
+                let semicolon = current.span;
+                state.stream.next();
+
+                PropertyHookBody::Abstract { semicolon }
+            }
+            TokenKind::DoubleArrow => PropertyHookBody::Expression {
+                double_arrow: utils::skip_double_arrow(state)?,
+                expression: expressions::create(state)?,
+                semicolon: utils::skip_semicolon(state)?,
+            },
+            _ => PropertyHookBody::Block(FunctionBody {
+                comments: state.stream.comments(),
+                left_brace: utils::skip_left_brace(state)?,
+                statements: blocks::multiple_statements_until(state, &TokenKind::RightBrace)?,
+                right_brace: utils::skip_right_brace(state)?,
+            }),
+        };
+
+        (parameters, body)
+    });
+
+    Ok(PropertyHook {
+        comments,
+        attributes,
+        r#final,
+        ampersand,
+        name,
+        parameters,
+        body,
+    })
+}
+
 pub fn parse_var(
     state: &mut State,
     class_name: Option<&SimpleIdentifier>,
+    readonly_class: Option<Span>,
 ) -> ParseResult<VariableProperty> {
+    let comments = state.stream.comments();
     utils::skip(state, TokenKind::Var)?;
 
     let ty = data_type::optional_data_type(state)?;
+    typed(state, &ty);
 
     let mut entries = vec![];
     let mut type_checked = false;
@@ -126,16 +427,27 @@ pub fn parse_var(
         if !type_checked {
             type_checked = true;
 
-            if let Some(ty) = &ty {
-                if ty.includes_callable() || ty.is_bottom() {
-                    let error = error::forbidden_type_used_in_property(
-                        state,
-                        class_name,
-                        &variable,
-                        ty.clone(),
-                    );
+            match &ty {
+                Some(ty) => {
+                    if ty.includes_callable() || ty.is_bottom() {
+                        let error = error::forbidden_type_used_in_property(
+                            state,
+                            class_name,
+                            &variable,
+                            ty.clone(),
+                        );
+
+                        state.record(error);
+                    }
+                }
+                None => {
+                    if let Some(readonly) = readonly_class {
+                        let error = error::missing_type_for_readonly_property(
+                            state, class_name, &variable, readonly,
+                        );
 
-                    state.record(error);
+                        state.record(error);
+                    }
                 }
             }
         }
@@ -143,6 +455,14 @@ pub fn parse_var(
         let current = state.stream.current();
         if current.kind == TokenKind::Equals {
             let span = current.span;
+            if let Some(readonly) = readonly_class {
+                let error = error::readonly_property_has_default_value(
+                    state, class_name, &variable, readonly, span,
+                );
+
+                state.record(error);
+            }
+
             state.stream.next();
             let value = expressions::create(state)?;
 
@@ -165,9 +485,22 @@ pub fn parse_var(
     let end = utils::skip_semicolon(state)?;
 
     Ok(VariableProperty {
+        comments,
         r#type: ty,
         attributes: state.get_attributes(),
         entries,
         end,
     })
 }
+
+fn typed(state: &mut State, ty: &Option<Type>) {
+    if let Some(ty) = ty {
+        versions::requires(
+            state,
+            PhpVersion::Php74,
+            "typed properties",
+            ty.first_span(),
+            ty.to_string().len(),
+        );
+    }
+}
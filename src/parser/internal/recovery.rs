@@ -0,0 +1,307 @@
+use crate::lexer::token::Span;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+use crate::parser::ast::ErrorStatement;
+use crate::parser::ast::Statement;
+use crate::parser::completeness::Recovery;
+use crate::parser::error;
+use crate::parser::error::ParseResult;
+use crate::parser::state::State;
+
+/// Parse a member of a class-like body.
+///
+/// In recovering mode, a member which fails to parse is dropped: the error is recorded, and
+/// tokens are skipped up to the start of the next member, or the end of the body, so the
+/// remaining members are still parsed.
+pub fn member<T>(
+    state: &mut State,
+    parse: impl FnOnce(&mut State) -> ParseResult<T>,
+) -> ParseResult<Option<T>> {
+    let span = state.stream.current().span;
+    let member = recover(state, parse, skip_to_member)?;
+    if member.is_none() {
+        state.recovered(Recovery::Member, span);
+    }
+
+    Ok(member)
+}
+
+/// Parse a statement of a statement list, e.g. the top-level statements, or the body of a block.
+///
+/// In recovering mode, a statement which fails to parse is replaced by an error statement: the
+/// error is recorded, and tokens are skipped up to the next statement boundary, so the remaining
+/// statements are still parsed. The boundary is a `;`, or the `}` closing a block opened by the
+/// statement, which are skipped, or the end of the enclosing block.
+pub fn statement(
+    state: &mut State,
+    parse: impl FnOnce(&mut State) -> ParseResult<Statement>,
+) -> ParseResult<Statement> {
+    let start = state.stream.cursor();
+
+    match recover(state, parse, skip_to_statement)? {
+        Some(statement) => Ok(statement),
+        None => {
+            let tokens = state
+                .stream
+                .consumed_since(start)
+                .iter()
+                .map(|token| token.clone().into_owned())
+                .collect::<Vec<_>>();
+
+            let span = tokens
+                .first()
+                .map_or(state.stream.current().span, |token| token.span);
+            state.recovered(Recovery::Statement, span);
+
+            Ok(Statement::Error(ErrorStatement { span, tokens }))
+        }
+    }
+}
+
+fn recover<T>(
+    state: &mut State,
+    parse: impl FnOnce(&mut State) -> ParseResult<T>,
+    skip: fn(&mut State, usize),
+) -> ParseResult<Option<T>> {
+    if !state.recovering {
+        return parse(state).map(Some);
+    }
+
+    let start = state.stream.cursor();
+    let scopes = state.stack.len();
+    let errors = state.errors.len();
+
+    let result = match parse(state) {
+        // A construct which consumed no token would fail at the same token again, forever, e.g.
+        // a `}` at the top level, parsed as a missing expression followed by a missing `;`.
+        Ok(_) if state.stream.cursor() == start => {
+            state.errors.truncate(errors);
+
+            Err(error::unexpected_token(vec![], state.stream.current()))
+        }
+        result => result,
+    };
+
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(error) if state.stream.is_eof() => Err(error),
+        // Exceeding a resource limit stops the parse.
+        Err(error) if error.resource_limit().is_some() => Err(error),
+        Err(error) => {
+            // An error at the position of the previous one is a consequence of it, e.g. a
+            // missing expression followed by an unexpected token.
+            if state.errors.last().map(|last| last.span) != Some(error.span) {
+                state.record(error);
+            }
+
+            // Scopes entered by the failed construct are not exited when an error is propagated.
+            state.stack.truncate(scopes);
+            state.get_attributes();
+
+            skip(state, start);
+
+            Ok(None)
+        }
+    }
+}
+
+/// Skip to the next token which may start a member, or to the closing brace of the body,
+/// the member having started at the given cursor position.
+fn skip_to_member(state: &mut State, start: usize) {
+    // The number of braces opened by the member which are still open, e.g. the body of a method.
+    let mut depth = opened_braces(state, start);
+
+    // The member must not fail again at the same token.
+    if state.stream.cursor() == start {
+        state.stream.next();
+    }
+
+    while !state.stream.is_eof() {
+        let kind = &state.stream.current().kind;
+
+        match kind {
+            TokenKind::RightBrace if depth == 0 => return,
+            TokenKind::RightBrace => depth -= 1,
+            TokenKind::LeftBrace | TokenKind::DollarLeftBrace => depth += 1,
+            _ if depth == 0 && is_member_start(kind) => return,
+            _ => {}
+        }
+
+        state.stream.next();
+    }
+}
+
+/// Skip past the next statement boundary, the statement having started at the given cursor
+/// position.
+fn skip_to_statement(state: &mut State, start: usize) {
+    // The statement must not fail again at the same token.
+    if state.stream.cursor() == start {
+        state.stream.next();
+    }
+
+    // The number of braces opened by the statement which are still open, e.g. the body of an `if`.
+    let mut depth = opened_braces(state, start);
+
+    while !state.stream.is_eof() {
+        match &state.stream.current().kind {
+            TokenKind::SemiColon if depth == 0 => {
+                state.stream.next();
+
+                return;
+            }
+            TokenKind::RightBrace if depth == 0 => return,
+            TokenKind::RightBrace => {
+                depth -= 1;
+                state.stream.next();
+
+                if depth == 0 {
+                    return;
+                }
+
+                continue;
+            }
+            TokenKind::LeftBrace | TokenKind::DollarLeftBrace => depth += 1,
+            kind if depth == 0 && is_block_end(kind) => return,
+            _ => {}
+        }
+
+        state.stream.next();
+    }
+}
+
+/// The number of braces opened since the given cursor position which are still open.
+fn opened_braces(state: &State, start: usize) -> usize {
+    state
+        .stream
+        .consumed_since(start)
+        .iter()
+        .fold(0usize, |depth, token| match token.kind {
+            TokenKind::LeftBrace | TokenKind::DollarLeftBrace => depth + 1,
+            TokenKind::RightBrace => depth.saturating_sub(1),
+            _ => depth,
+        })
+}
+
+/// Whether the given token ends the enclosing statement list, other than a `}`.
+fn is_block_end(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::CloseTag
+            | TokenKind::Case
+            | TokenKind::Default
+            | TokenKind::Else
+            | TokenKind::ElseIf
+            | TokenKind::EndIf
+            | TokenKind::EndWhile
+            | TokenKind::EndFor
+            | TokenKind::EndForeach
+            | TokenKind::EndSwitch
+            | TokenKind::EndDeclare
+    )
+}
+
+fn is_member_start(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::Public
+            | TokenKind::Protected
+            | TokenKind::Private
+            | TokenKind::Static
+            | TokenKind::Abstract
+            | TokenKind::Final
+            | TokenKind::Readonly
+            | TokenKind::Var
+            | TokenKind::Function
+            | TokenKind::Const
+            | TokenKind::Case
+            | TokenKind::Use
+            | TokenKind::Attribute
+    )
+}
+
+/// In recovering mode, accept a missing semicolon when the current token starts a statement
+/// on a later line than the previous token, e.g. `$a = 1` followed by `$b = 2;` on the next line.
+///
+/// The error is recorded, and the span of a synthetic semicolon following the previous token
+/// is returned.
+pub fn missing_semicolon(state: &mut State) -> Option<Span> {
+    if !state.recovering {
+        return None;
+    }
+
+    let current = state.stream.current();
+    let previous = state
+        .stream
+        .consumed_since(0)
+        .iter()
+        .rev()
+        .find(|token| !is_comment(&token.kind))?;
+
+    let semicolon = Token::synthetic(TokenKind::SemiColon, end_of(previous));
+    if current.span.line <= semicolon.span.line || !is_statement_start(&current.kind) {
+        return None;
+    }
+
+    state.record(error::missing_semicolon(semicolon.span, previous));
+    state.recovered(Recovery::Semicolon, semicolon.span);
+
+    Some(semicolon.span)
+}
+
+/// The zero-width span following the given token.
+fn end_of(token: &Token) -> Span {
+    let end = token.span.advance(&token.value);
+
+    Span::new(end.line, end.column, end.position)
+}
+
+fn is_comment(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::SingleLineComment
+            | TokenKind::HashMarkComment
+            | TokenKind::MultiLineComment
+            | TokenKind::DocumentComment
+    )
+}
+
+fn is_statement_start(kind: &TokenKind) -> bool {
+    is_member_start(kind)
+        || matches!(
+            kind,
+            TokenKind::Variable
+                | TokenKind::Dollar
+                | TokenKind::Identifier
+                | TokenKind::QualifiedIdentifier
+                | TokenKind::FullyQualifiedIdentifier
+                | TokenKind::RightBrace
+                | TokenKind::Echo
+                | TokenKind::Print
+                | TokenKind::Return
+                | TokenKind::If
+                | TokenKind::For
+                | TokenKind::Foreach
+                | TokenKind::While
+                | TokenKind::Do
+                | TokenKind::Switch
+                | TokenKind::Try
+                | TokenKind::Throw
+                | TokenKind::Break
+                | TokenKind::Continue
+                | TokenKind::Goto
+                | TokenKind::Global
+                | TokenKind::Unset
+                | TokenKind::Class
+                | TokenKind::Interface
+                | TokenKind::Trait
+                | TokenKind::Enum
+                | TokenKind::Namespace
+                | TokenKind::Declare
+                | TokenKind::Include
+                | TokenKind::IncludeOnce
+                | TokenKind::Require
+                | TokenKind::RequireOnce
+                | TokenKind::New
+                | TokenKind::Yield
+        )
+}
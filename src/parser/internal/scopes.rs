@@ -0,0 +1,122 @@
+use crate::lexer::token::Span;
+use crate::parser::ast::loops::Level;
+use crate::parser::error;
+use crate::parser::state::Scope;
+use crate::parser::state::State;
+
+/// Validate a usage of `$this` against the current scope stack.
+///
+/// Non-static closures and arrow functions are not validated, since they
+/// may be bound to an object at runtime using `Closure::bind()`, and neither
+/// is code outside of any function, e.g. a template included from a method.
+pub fn this(state: &mut State, span: Span) {
+    let scope = state
+        .stack
+        .iter()
+        .rev()
+        .find(|scope| {
+            !matches!(
+                scope,
+                Scope::Namespace(_)
+                    | Scope::BracedNamespace(_)
+                    | Scope::Attribute
+                    | Scope::Loop
+                    | Scope::Switch
+            )
+        })
+        .cloned();
+
+    let error = match scope {
+        None => return,
+        Some(
+            Scope::AnonymousFunction(None) | Scope::ArrowFunction(None) | Scope::Method(_, None),
+        ) => {
+            return;
+        }
+        Some(
+            Scope::AnonymousFunction(Some(r#static))
+            | Scope::ArrowFunction(Some(r#static))
+            | Scope::Method(_, Some(r#static)),
+        ) => error::cannot_use_this_in_static_context(span, r#static),
+        _ => error::cannot_use_this_outside_of_object_context(span),
+    };
+
+    state.record(error);
+}
+
+/// Validate a usage of `self`, `static`, or `parent` against the current scope stack.
+///
+/// The class scope of closures, arrow functions, attributes, and code outside of
+/// any function, e.g. a template included from a method, is not known at compile
+/// time, therefore they are not validated.
+pub fn class_scoped_keyword(state: &mut State, span: Span, keyword: &str) {
+    let scope = state
+        .stack
+        .iter()
+        .rev()
+        .find(|scope| {
+            !matches!(
+                scope,
+                Scope::Namespace(_) | Scope::BracedNamespace(_) | Scope::Method(..)
+            )
+        })
+        .cloned();
+
+    let error = match scope {
+        Some(Scope::Class(class, false) | Scope::Enum(class)) if keyword == "parent" => {
+            error::cannot_use_parent_without_parent_class(state, span, Some(&class))
+        }
+        Some(Scope::AnonymousClass(false)) if keyword == "parent" => {
+            error::cannot_use_parent_without_parent_class(state, span, None)
+        }
+        Some(Scope::Function(_)) => {
+            error::cannot_use_class_scoped_keyword_outside_of_class_scope(span, keyword)
+        }
+        _ => return,
+    };
+
+    state.record(error);
+}
+
+/// Validate that `break` or `continue` is used within enough nested loop or switch structures.
+pub fn loop_level(state: &mut State, keyword: &str, start: Span, end: Span, level: Option<&Level>) {
+    let levels = match level {
+        Some(level) => match level.value() {
+            Some(0) => {
+                state.record(error::loop_level_must_be_positive(start, end, keyword));
+
+                return;
+            }
+            Some(levels) => levels,
+            None => {
+                let span = match level {
+                    Level::Literal(literal) => literal.span,
+                    Level::Parenthesized {
+                        left_parenthesis, ..
+                    } => *left_parenthesis,
+                };
+
+                state.record(error::loop_level_must_be_an_integer_literal(span, keyword));
+
+                return;
+            }
+        },
+        None => 1,
+    };
+
+    let depth = state
+        .stack
+        .iter()
+        .rev()
+        .filter(|scope| !matches!(scope, Scope::Namespace(_) | Scope::BracedNamespace(_)))
+        .take_while(|scope| matches!(scope, Scope::Loop | Scope::Switch))
+        .count();
+
+    if depth == 0 {
+        state.record(error::cannot_use_loop_keyword_outside_of_loop(
+            start, end, keyword,
+        ));
+    } else if levels > depth {
+        state.record(error::loop_level_exceeds_depth(start, end, keyword, levels));
+    }
+}
@@ -1,7 +1,8 @@
-use crate::expect_token;
-use crate::expected_token_err;
+use crate::lexer::byte_string::ByteString;
 use crate::lexer::error::SyntaxError;
 use crate::lexer::token::DocStringIndentationKind;
+use crate::lexer::token::Span;
+use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
 use crate::parser::ast::identifiers::Identifier;
 use crate::parser::ast::literals::Literal;
@@ -10,16 +11,43 @@ use crate::parser::ast::literals::LiteralString;
 use crate::parser::ast::operators::ArithmeticOperation;
 use crate::parser::ast::variables::Variable;
 use crate::parser::ast::Expression;
+use crate::parser::ast::ExpressionStringPart;
+use crate::parser::ast::InterpolationSyntax;
+use crate::parser::ast::LiteralStringPart;
 use crate::parser::ast::StringPart;
+use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::expressions::create;
 use crate::parser::internal::identifiers;
 use crate::parser::internal::utils;
 use crate::parser::internal::variables;
+use crate::parser::macros::expected_token_err;
 use crate::parser::state::State;
 
+/// The string literal of the given token, whose value is left empty when the literal exceeds
+/// the maximum literal length.
+pub fn literal(state: &State, token: &Token) -> LiteralString {
+    let length = token.raw().len();
+
+    if state.max_literal_length.is_some_and(|max| length > max) {
+        return LiteralString {
+            value: ByteString::default(),
+            span: token.span,
+            deferred: Some(length),
+        };
+    }
+
+    LiteralString {
+        value: token.value.to_byte_string(),
+        span: token.span,
+        deferred: None,
+    }
+}
+
 #[inline(always)]
 pub fn interpolated(state: &mut State) -> ParseResult<Expression> {
+    // the opening quote is the first byte of the first part.
+    let start = state.stream.current().span;
     let mut parts = Vec::new();
 
     while state.stream.current().kind != TokenKind::DoubleQuote {
@@ -28,13 +56,15 @@ pub fn interpolated(state: &mut State) -> ParseResult<Expression> {
         }
     }
 
+    let end = state.stream.current().span;
     state.stream.next();
 
-    Ok(Expression::InterpolatedString { parts })
+    Ok(Expression::InterpolatedString { start, parts, end })
 }
 
 #[inline(always)]
 pub fn shell_exec(state: &mut State) -> ParseResult<Expression> {
+    let start = state.stream.current().span;
     state.stream.next();
 
     let mut parts = Vec::new();
@@ -45,9 +75,10 @@ pub fn shell_exec(state: &mut State) -> ParseResult<Expression> {
         }
     }
 
+    let end = state.stream.current().span;
     state.stream.next();
 
-    Ok(Expression::ShellExec { parts })
+    Ok(Expression::ShellExec { start, parts, end })
 }
 
 #[inline(always)]
@@ -63,87 +94,49 @@ pub fn heredoc(state: &mut State) -> ParseResult<Expression> {
         }
     }
 
-    let (indentation_type, indentation_amount) = match &state.stream.current().kind {
-        TokenKind::EndDocString(indentation_type, indentation_amount) => {
-            (indentation_type.clone(), *indentation_amount)
-        }
-        _ => unreachable!(),
-    };
-
-    state.stream.next();
-
-    let mut new_line = true;
-    if indentation_type != DocStringIndentationKind::None {
-        let indentation_char: u8 = indentation_type.into();
+    let end = state.stream.current().span;
+    strip_indentation(state, span, &mut parts)?;
 
-        for part in parts.iter_mut() {
-            // We only need to strip and validate indentation
-            // for individual lines, so we can skip checks if
-            // we know we're not on a new line.
-            if !new_line {
-                continue;
-            }
-
-            match part {
-                StringPart::Literal(bytes) => {
-                    // 1. If this line doesn't start with any whitespace,
-                    //    we can return an error early because we know
-                    //    the label was indented.
-                    if !bytes.starts_with(&[b' ']) && !bytes.starts_with(&[b'\t']) {
-                        return Err(SyntaxError::InvalidDocBodyIndentationLevel(
-                            indentation_amount,
-                            span,
-                        )
-                        .into());
-                    }
+    Ok(Expression::Heredoc {
+        start: span,
+        parts,
+        end,
+    })
+}
 
-                    // 2. If this line doesn't start with the correct
-                    //    type of whitespace, we can also return an error.
-                    if !bytes.starts_with(&[indentation_char]) {
-                        return Err(SyntaxError::InvalidDocIndentation(span).into());
-                    }
+#[inline(always)]
+pub fn nowdoc(state: &mut State) -> ParseResult<Expression> {
+    let span = state.stream.current().span;
+    state.stream.next();
 
-                    // 3. We now know that the whitespace at the start of
-                    //    this line is correct, so we need to check that the
-                    //    amount of whitespace is correct too. In this case,
-                    //    the amount of whitespace just needs to be at least
-                    //    the same, so we can create a vector containing the
-                    //    minimum and check using `starts_with()`.
-                    let expected_whitespace_buffer = vec![indentation_char; indentation_amount];
-                    if !bytes.starts_with(&expected_whitespace_buffer) {
-                        return Err(SyntaxError::InvalidDocBodyIndentationLevel(
-                            indentation_amount,
-                            span,
-                        )
-                        .into());
-                    }
+    let mut parts = Vec::new();
 
-                    // 4. All of the above checks have passed, so we know
-                    //    there are no more possible errors. Let's now
-                    //    strip the leading whitespace accordingly.
-                    *bytes = bytes
-                        .strip_prefix(&expected_whitespace_buffer[..])
-                        .unwrap()
-                        .into();
-                    new_line = bytes.ends_with(&[b'\n']);
-                }
-                _ => continue,
-            }
+    while state.stream.current().kind == TokenKind::StringPart {
+        if let Some(part) = part(state)? {
+            parts.push(part);
         }
     }
 
-    Ok(Expression::Heredoc { parts })
-}
-
-#[inline(always)]
-pub fn nowdoc(state: &mut State) -> ParseResult<Expression> {
-    let span = state.stream.current().span;
+    if !matches!(state.stream.current().kind, TokenKind::EndDocString(_, _)) {
+        return expected_token_err!("constant string", state);
+    }
 
-    state.stream.next();
+    let end = state.stream.current().span;
+    strip_indentation(state, span, &mut parts)?;
 
-    let mut string_part = state.stream.current().value.clone();
-    expect_token!([TokenKind::StringPart => ()], state, "constant string");
+    Ok(Expression::Nowdoc {
+        start: span,
+        parts,
+        end,
+    })
+}
 
+/// Consume the closing label of a heredoc or nowdoc, and strip its indentation
+/// from the start of every line in the body.
+///
+/// The lexer emits each line of the body as a separate literal part, so the
+/// span of a stripped literal only needs to be moved past the removed whitespace.
+fn strip_indentation(state: &mut State, span: Span, parts: &mut [StringPart]) -> ParseResult<()> {
     let (indentation_type, indentation_amount) = match &state.stream.current().kind {
         TokenKind::EndDocString(indentation_type, indentation_amount) => {
             (indentation_type.clone(), *indentation_amount)
@@ -153,75 +146,89 @@ pub fn nowdoc(state: &mut State) -> ParseResult<Expression> {
 
     state.stream.next();
 
-    if indentation_type != DocStringIndentationKind::None {
-        let indentation_char: u8 = indentation_type.into();
+    if indentation_type == DocStringIndentationKind::None {
+        return Ok(());
+    }
 
-        let mut lines = string_part
-            .split(|b| *b == b'\n')
-            .map(|s| s.to_vec())
-            .collect::<Vec<Vec<u8>>>();
+    let indentation_char: u8 = indentation_type.into();
+    let expected_whitespace_buffer = vec![indentation_char; indentation_amount];
 
-        for line in lines.iter_mut() {
-            if line.is_empty() {
-                continue;
-            }
+    let mut new_line = true;
+    for part in parts.iter_mut() {
+        match part {
+            // We only need to strip and validate indentation
+            // for individual lines, so we can skip checks if
+            // we know we're not on a new line.
+            StringPart::Literal(literal) if new_line => {
+                new_line = literal.value.ends_with(b"\n");
 
-            // 1. If this line doesn't start with any whitespace,
-            //    we can return an error early because we know
-            //    the label was indented.
-            if !line.starts_with(&[b' ']) && !line.starts_with(&[b'\t']) {
-                return Err(
-                    SyntaxError::InvalidDocBodyIndentationLevel(indentation_amount, span).into(),
-                );
-            }
+                // Empty lines don't need to be indented.
+                if literal.value == b"\n" {
+                    continue;
+                }
 
-            // 2. If this line doesn't start with the correct
-            //    type of whitespace, we can also return an error.
-            if !line.starts_with(&[indentation_char]) {
-                return Err(SyntaxError::InvalidDocIndentation(span).into());
-            }
+                // 1. If this line doesn't start with any whitespace,
+                //    we can return an error early because we know
+                //    the label was indented.
+                if !literal.value.starts_with(b" ") && !literal.value.starts_with(b"\t") {
+                    return Err(SyntaxError::InvalidDocBodyIndentationLevel(
+                        indentation_amount,
+                        span,
+                    )
+                    .into());
+                }
 
-            // 3. We now know that the whitespace at the start of
-            //    this line is correct, so we need to check that the
-            //    amount of whitespace is correct too. In this case,
-            //    the amount of whitespace just needs to be at least
-            //    the same, so we can create a vector containing the
-            //    minimum and check using `starts_with()`.
-            let expected_whitespace_buffer = vec![indentation_char; indentation_amount];
-            if !line.starts_with(&expected_whitespace_buffer) {
+                // 2. If this line doesn't start with the correct
+                //    type of whitespace, we can also return an error.
+                if !literal.value.starts_with(&[indentation_char]) {
+                    return Err(SyntaxError::InvalidDocIndentation(span).into());
+                }
+
+                // 3. We now know that the whitespace at the start of
+                //    this line is correct, so we need to check that the
+                //    amount of whitespace is correct too. In this case,
+                //    the amount of whitespace just needs to be at least
+                //    the same, so we can check using `starts_with()`.
+                if !literal.value.starts_with(&expected_whitespace_buffer) {
+                    return Err(SyntaxError::InvalidDocBodyIndentationLevel(
+                        indentation_amount,
+                        span,
+                    )
+                    .into());
+                }
+
+                // 4. All of the above checks have passed, so we know
+                //    there are no more possible errors. Let's now
+                //    strip the leading whitespace accordingly.
+                literal.value = literal.value[indentation_amount..].into();
+                literal.span.column += indentation_amount;
+                literal.span.position += indentation_amount;
+            }
+            StringPart::Literal(literal) => {
+                new_line = literal.value.ends_with(b"\n");
+            }
+            // An interpolated expression can't be the indentation of a line.
+            StringPart::Expression(_) if new_line => {
                 return Err(
                     SyntaxError::InvalidDocBodyIndentationLevel(indentation_amount, span).into(),
                 );
             }
-
-            // 4. All of the above checks have passed, so we know
-            //    there are no more possible errors. Let's now
-            //    strip the leading whitespace accordingly.
-            *line = line
-                .strip_prefix(&expected_whitespace_buffer[..])
-                .unwrap()
-                .into();
-        }
-
-        let mut bytes = Vec::new();
-        for (i, line) in lines.iter().enumerate() {
-            bytes.extend(line);
-            if i < lines.len() - 1 {
-                bytes.push(b'\n');
-            }
+            StringPart::Expression(_) => {}
         }
-        string_part = bytes.into();
     }
 
-    Ok(Expression::Nowdoc { value: string_part })
+    Ok(())
 }
 
 fn part(state: &mut State) -> ParseResult<Option<StringPart>> {
     Ok(match &state.stream.current().kind {
         TokenKind::StringPart => {
-            let s = state.stream.current().value.clone();
-            let part = if s.len() > 0 {
-                Some(StringPart::Literal(s))
+            let current = state.stream.current();
+            let part = if !current.value.is_empty() {
+                Some(StringPart::Literal(LiteralStringPart {
+                    span: current.span,
+                    value: current.value.to_byte_string(),
+                }))
             } else {
                 None
             };
@@ -230,21 +237,43 @@ fn part(state: &mut State) -> ParseResult<Option<StringPart>> {
             part
         }
         TokenKind::DollarLeftBrace => {
+            let start = state.stream.current().span;
             let variable = variables::dynamic_variable(state)?;
-
-            Some(StringPart::Expression(Box::new(Expression::Variable(
-                variable,
-            ))))
+            let end = state.stream.previous().span;
+
+            Some(StringPart::Expression(ExpressionStringPart {
+                syntax: InterpolationSyntax::DollarBrace,
+                start,
+                expression: Box::new(Expression::Variable(variable)),
+                end,
+            }))
         }
         TokenKind::LeftBrace => {
             // "{$expr}"
-            state.stream.next();
+            let start = utils::skip_left_brace(state)?;
+            let expression_start = state.stream.current().span;
             let e = create(state)?;
-            utils::skip_right_brace(state)?;
-            Some(StringPart::Expression(Box::new(e)))
+            let end = utils::skip_right_brace(state)?;
+
+            // The lexer only produces `{` when it is followed by `$`, but the expression
+            // must also be a variable, `"{$a + 1}"` and `"{$a::B}"` are rejected by PHP.
+            if !is_interpolable(&e) {
+                state.record(error::unexpected_interpolated_expression(
+                    expression_start,
+                    end,
+                ));
+            }
+
+            Some(StringPart::Expression(ExpressionStringPart {
+                syntax: InterpolationSyntax::Complex,
+                start,
+                expression: Box::new(e),
+                end,
+            }))
         }
         TokenKind::Variable => {
             // "$expr", "$expr[0]", "$expr[name]", "$expr->a"
+            let start = state.stream.current().span;
             let variable = Expression::Variable(variables::dynamic_variable(state)?);
             let current = state.stream.current();
             let e = match &current.kind {
@@ -260,7 +289,7 @@ fn part(state: &mut State) -> ParseResult<Option<StringPart>> {
 
                             Expression::Literal(Literal::Integer(LiteralInteger {
                                 span: current.span,
-                                value: current.value.clone(),
+                                value: current.value.to_byte_string(),
                             }))
                         }
                         TokenKind::Minus => {
@@ -275,7 +304,7 @@ fn part(state: &mut State) -> ParseResult<Option<StringPart>> {
                                     right: Box::new(Expression::Literal(Literal::Integer(
                                         LiteralInteger {
                                             span: literal.span,
-                                            value: literal.value.clone(),
+                                            value: literal.value.to_byte_string(),
                                         },
                                     ))),
                                 })
@@ -288,7 +317,8 @@ fn part(state: &mut State) -> ParseResult<Option<StringPart>> {
 
                             Expression::Literal(Literal::String(LiteralString {
                                 span: current.span,
-                                value: current.value.clone(),
+                                value: current.value.to_byte_string(),
+                                deferred: None,
                             }))
                         }
                         TokenKind::Variable => Expression::Variable(Variable::SimpleVariable(
@@ -335,10 +365,41 @@ fn part(state: &mut State) -> ParseResult<Option<StringPart>> {
                 }
                 _ => variable,
             };
-            Some(StringPart::Expression(Box::new(e)))
+            let end = state.stream.previous().span;
+
+            Some(StringPart::Expression(ExpressionStringPart {
+                syntax: InterpolationSyntax::Simple,
+                start,
+                expression: Box::new(e),
+                end,
+            }))
         }
         _ => {
             return expected_token_err!(["`${`", "`{$", "`\"`", "a variable"], state);
         }
     })
 }
+
+/// Whether the given expression can be interpolated using the `"{$expr}"` syntax.
+///
+/// PHP only accepts a variable there, optionally followed by array indexes, property
+/// fetches and calls, e.g. `"{$a->b()[0]}"` or `"{$a::$b}"`.
+fn is_interpolable(expression: &Expression) -> bool {
+    match expression {
+        Expression::Variable(_) => true,
+        Expression::ArrayIndex { array: target, .. }
+        | Expression::FunctionCall { target, .. }
+        | Expression::FunctionClosureCreation { target, .. }
+        | Expression::MethodCall { target, .. }
+        | Expression::MethodClosureCreation { target, .. }
+        | Expression::NullsafeMethodCall { target, .. }
+        | Expression::StaticMethodCall { target, .. }
+        | Expression::StaticVariableMethodCall { target, .. }
+        | Expression::StaticMethodClosureCreation { target, .. }
+        | Expression::StaticVariableMethodClosureCreation { target, .. }
+        | Expression::PropertyFetch { target, .. }
+        | Expression::NullsafePropertyFetch { target, .. }
+        | Expression::StaticPropertyFetch { target, .. } => is_interpolable(target),
+        _ => false,
+    }
+}
@@ -0,0 +1,157 @@
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::UseKind;
+use crate::parser::error;
+use crate::parser::state::Import;
+use crate::parser::state::State;
+
+/// Validate an import against the names already imported in the current namespace,
+/// and the names declared in the file.
+///
+/// Like PHP, importing a name declared in the current namespace is only allowed
+/// when the import refers to that declaration, e.g. `class B {} use A\B;` in `namespace A`.
+pub fn import(
+    state: &mut State,
+    kind: &UseKind,
+    prefix: Option<&SimpleIdentifier>,
+    name: &SimpleIdentifier,
+    alias: Option<&SimpleIdentifier>,
+) {
+    let qualified = match prefix {
+        Some(prefix) => format!(
+            "{}\\{}",
+            prefix.value.to_string().trim_end_matches('\\'),
+            name
+        ),
+        None => name.to_string(),
+    };
+    let qualified = qualified.trim_start_matches('\\').to_string();
+
+    let identifier = alias.unwrap_or(name).clone();
+    let alias = match alias {
+        Some(alias) => alias.to_string(),
+        None => match qualified.rfind('\\') {
+            Some(position) => qualified[position + 1..].to_string(),
+            None => qualified.clone(),
+        },
+    };
+
+    // `use Foo;` in the global namespace has no effect, PHP only warns about it.
+    if prefix.is_none() && qualified == alias && state.named(&alias) == alias {
+        return;
+    }
+
+    let key = (*kind, state.names.intern(&normalize(kind, &alias)));
+    let target = state.names.intern(&normalize(kind, &qualified));
+
+    let local = normalize(kind, &state.named(&alias));
+    if let Some(local) = state.names.get(&local).filter(|local| *local != target) {
+        if let Some(declaration) = state.declarations.get(&(*kind, local)) {
+            let error = error::name_already_in_use(
+                kind,
+                &qualified,
+                &alias,
+                &identifier,
+                declaration,
+                false,
+            );
+
+            state.record(error);
+
+            return;
+        }
+    }
+
+    if let Some(previous) = state.imports.get(&key) {
+        let error = error::name_already_in_use(
+            kind,
+            &qualified,
+            &alias,
+            &identifier,
+            &previous.identifier,
+            true,
+        );
+
+        state.record(error);
+
+        return;
+    }
+
+    state.imports.insert(
+        key,
+        Import {
+            name: target,
+            identifier,
+        },
+    );
+}
+
+/// Validate a declaration against the names imported in the current namespace, and
+/// record it for the imports that follow.
+///
+/// `kind` is the kind of declaration reported in the error, e.g. `class` or `function`.
+pub fn declare(state: &mut State, table: UseKind, kind: &str, name: &SimpleIdentifier) {
+    let qualified = state.named(name);
+    let target = state.names.intern(&normalize(&table, &qualified));
+
+    if let Some(import) = state
+        .names
+        .get(&normalize(&table, &name.to_string()))
+        .and_then(|alias| state.imports.get(&(table, alias)))
+    {
+        if import.name != target {
+            let error =
+                error::cannot_declare_imported_name(kind, name, &qualified, &import.identifier);
+
+            state.record(error);
+        }
+    }
+
+    state
+        .declarations
+        .entry((table, target))
+        .or_insert_with(|| name.clone());
+}
+
+/// Resolve a class name against the imports of the current namespace, or the namespace itself,
+/// returning the fully qualified name without a leading backslash.
+///
+/// Imported names are returned as recorded in the symbol table, so the result is meant to be
+/// compared case-insensitively.
+pub fn resolve(state: &State, name: &SimpleIdentifier) -> String {
+    let name = name.to_string();
+    if let Some(name) = name.strip_prefix('\\') {
+        return name.to_string();
+    }
+
+    let (first, rest) = match name.find('\\') {
+        Some(position) => (&name[..position], &name[position..]),
+        None => (name.as_str(), ""),
+    };
+
+    match state
+        .names
+        .get(&normalize(&UseKind::Normal, first))
+        .and_then(|alias| state.imports.get(&(UseKind::Normal, alias)))
+    {
+        Some(import) => format!("{}{}", state.names.resolve(import.name), rest),
+        None => state.named(&name),
+    }
+}
+
+/// The key of a name in its symbol table.
+///
+/// Class and function names are case-insensitive, constant names are case-sensitive,
+/// apart from their namespace.
+fn normalize(kind: &UseKind, name: &str) -> String {
+    match kind {
+        UseKind::Const => match name.rfind('\\') {
+            Some(position) => format!(
+                "{}{}",
+                name[..position].to_ascii_lowercase(),
+                &name[position..]
+            ),
+            None => name.to_string(),
+        },
+        _ => name.to_ascii_lowercase(),
+    }
+}
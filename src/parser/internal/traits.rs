@@ -1,4 +1,5 @@
-use crate::expect_token;
+use smallvec::SmallVec;
+
 use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
 use crate::parser::ast::identifiers::SimpleIdentifier;
@@ -9,6 +10,7 @@ use crate::parser::ast::traits::TraitStatement;
 use crate::parser::ast::traits::TraitUsage;
 use crate::parser::ast::traits::TraitUsageAdaptation;
 use crate::parser::ast::Statement;
+use crate::parser::ast::UseKind;
 use crate::parser::error::ParseResult;
 use crate::parser::internal::attributes;
 use crate::parser::internal::constants;
@@ -18,14 +20,19 @@ use crate::parser::internal::functions::MethodType;
 use crate::parser::internal::identifiers;
 use crate::parser::internal::modifiers;
 use crate::parser::internal::properties;
+use crate::parser::internal::recovery;
+use crate::parser::internal::symbols;
 use crate::parser::internal::utils;
+use crate::parser::macros::expect_token;
+use crate::parser::macros::peek_token;
+use crate::parser::macros::scoped;
+use crate::parser::state::Scope;
 use crate::parser::state::State;
-use crate::peek_token;
 
 pub fn usage(state: &mut State) -> ParseResult<TraitUsage> {
     let span = utils::skip(state, TokenKind::Use)?;
 
-    let mut traits = Vec::new();
+    let mut traits = SmallVec::new();
 
     while state.stream.current().kind != TokenKind::SemiColon
         && state.stream.current().kind != TokenKind::LeftBrace
@@ -160,28 +167,36 @@ pub fn usage(state: &mut State) -> ParseResult<TraitUsage> {
 }
 
 pub fn parse(state: &mut State) -> ParseResult<Statement> {
+    let comments = state.stream.comments();
     let span = utils::skip(state, TokenKind::Trait)?;
     let name = identifiers::type_identifier(state)?;
+    symbols::declare(state, UseKind::Normal, "trait", &name);
     let attributes = state.get_attributes();
 
-    let body = TraitBody {
-        left_brace: utils::skip_left_brace(state)?,
-        members: {
-            let mut members = Vec::new();
-            while state.stream.current().kind != TokenKind::RightBrace && !state.stream.is_eof() {
-                members.push(member(state, &name)?);
-            }
-            members
-        },
-        right_brace: utils::skip_right_brace(state)?,
-    };
+    let body = scoped!(state, Scope::Trait(name.clone()), {
+        TraitBody {
+            left_brace: utils::skip_left_brace(state)?,
+            members: {
+                let mut members = Vec::new();
+                while state.stream.current().kind != TokenKind::RightBrace && !state.stream.is_eof()
+                {
+                    if let Some(member) = recovery::member(state, |state| member(state, &name))? {
+                        members.push(member);
+                    }
+                }
+                members
+            },
+            right_brace: utils::skip_right_brace(state)?,
+        }
+    });
 
-    Ok(Statement::Trait(TraitStatement {
+    Ok(Statement::Trait(Box::new(TraitStatement {
+        comments,
         r#trait: span,
         name,
         attributes,
         body,
-    }))
+    })))
 }
 
 fn member(state: &mut State, class_name: &SimpleIdentifier) -> ParseResult<TraitMember> {
@@ -192,7 +207,8 @@ fn member(state: &mut State, class_name: &SimpleIdentifier) -> ParseResult<Trait
     }
 
     if state.stream.current().kind == TokenKind::Var {
-        return properties::parse_var(state, Some(class_name)).map(TraitMember::VariableProperty);
+        return properties::parse_var(state, Some(class_name), None)
+            .map(TraitMember::VariableProperty);
     }
 
     let modifiers = modifiers::collect(state)?;
@@ -221,6 +237,7 @@ fn member(state: &mut State, class_name: &SimpleIdentifier) -> ParseResult<Trait
     properties::parse(
         state,
         Some(class_name),
+        None,
         modifiers::property_group(modifiers)?,
     )
     .map(TraitMember::Property)
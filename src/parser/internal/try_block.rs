@@ -83,13 +83,13 @@ pub fn try_block(state: &mut State) -> ParseResult<Statement> {
 
     let end = state.stream.current().span;
 
-    Ok(Statement::Try(TryStatement {
+    Ok(Statement::Try(Box::new(TryStatement {
         start,
         end,
         body,
         catches,
         finally,
-    }))
+    })))
 }
 
 #[inline(always)]
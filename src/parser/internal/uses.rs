@@ -7,6 +7,7 @@ use crate::parser::ast::UseStatement;
 use crate::parser::error;
 use crate::parser::error::ParseResult;
 use crate::parser::internal::identifiers;
+use crate::parser::internal::symbols;
 use crate::parser::internal::utils;
 use crate::parser::state::State;
 
@@ -63,6 +64,14 @@ pub fn use_statement(state: &mut State) -> ParseResult<Statement> {
                 alias = Some(identifiers::type_identifier(state)?);
             }
 
+            symbols::import(
+                state,
+                use_kind.as_ref().unwrap_or(&kind),
+                Some(&prefix),
+                &name,
+                alias.as_ref(),
+            );
+
             uses.push(Use {
                 name,
                 kind: use_kind,
@@ -93,6 +102,8 @@ pub fn use_statement(state: &mut State) -> ParseResult<Statement> {
                 alias = Some(identifiers::type_identifier(state)?);
             }
 
+            symbols::import(state, &kind, None, &name, alias.as_ref());
+
             uses.push(Use {
                 name,
                 kind: None,
@@ -4,6 +4,7 @@ use crate::parser::ast::utils::CommaSeparated;
 use crate::parser::ast::Ending;
 use crate::parser::error;
 use crate::parser::error::ParseResult;
+use crate::parser::internal::recovery;
 use crate::parser::state::State;
 
 pub fn skip_ending(state: &mut State) -> ParseResult<Ending> {
@@ -17,6 +18,8 @@ pub fn skip_ending(state: &mut State) -> ParseResult<Ending> {
         state.stream.next();
 
         Ok(Ending::Semicolon(current.span))
+    } else if let Some(span) = recovery::missing_semicolon(state) {
+        Ok(Ending::Semicolon(span))
     } else {
         Err(error::unexpected_token(vec![";".to_string()], current))
     }
@@ -29,6 +32,8 @@ pub fn skip_semicolon(state: &mut State) -> ParseResult<Span> {
         state.stream.next();
 
         Ok(current.span)
+    } else if let Some(span) = recovery::missing_semicolon(state) {
+        Ok(span)
     } else {
         Err(error::unexpected_token(vec!["`;`".to_string()], current))
     }
@@ -107,7 +112,7 @@ pub fn skip_any_of(state: &mut State, kinds: &[TokenKind]) -> ParseResult<Span>
 /// and then skip the right parenthesis.
 pub fn parenthesized<T>(
     state: &mut State,
-    func: &(dyn Fn(&mut State) -> ParseResult<T>),
+    func: &dyn Fn(&mut State) -> ParseResult<T>,
 ) -> ParseResult<(Span, T, Span)> {
     let left_parenthesis = skip_left_parenthesis(state)?;
     let inner = func(state)?;
@@ -122,7 +127,7 @@ pub fn parenthesized<T>(
 /// and then skip the right brace.
 pub fn braced<T>(
     state: &mut State,
-    func: &(dyn Fn(&mut State) -> ParseResult<T>),
+    func: &dyn Fn(&mut State) -> ParseResult<T>,
 ) -> ParseResult<(Span, T, Span)> {
     let left_brace = skip_left_brace(state)?;
     let inner = func(state)?;
@@ -133,7 +138,7 @@ pub fn braced<T>(
 
 pub fn semicolon_terminated<T>(
     state: &mut State,
-    func: &(dyn Fn(&mut State) -> ParseResult<T>),
+    func: &dyn Fn(&mut State) -> ParseResult<T>,
 ) -> ParseResult<(Span, T)> {
     let inner = func(state)?;
     let semicolon = skip_semicolon(state)?;
@@ -143,7 +148,7 @@ pub fn semicolon_terminated<T>(
 /// Parse a comma-separated list of items, allowing a trailing comma.
 pub fn comma_separated<T>(
     state: &mut State,
-    func: &(dyn Fn(&mut State) -> ParseResult<T>),
+    func: &dyn Fn(&mut State) -> ParseResult<T>,
     until: TokenKind,
 ) -> ParseResult<CommaSeparated<T>> {
     let mut inner: Vec<T> = vec![];
@@ -171,7 +176,7 @@ pub fn comma_separated<T>(
 /// Parse a comma-separated list of items, not allowing trailing commas.
 pub fn comma_separated_no_trailing<T>(
     state: &mut State,
-    func: &(dyn Fn(&mut State) -> ParseResult<T>),
+    func: &dyn Fn(&mut State) -> ParseResult<T>,
     until: TokenKind,
 ) -> ParseResult<CommaSeparated<T>> {
     let mut inner: Vec<T> = vec![];
@@ -205,7 +210,7 @@ pub fn comma_separated_no_trailing<T>(
 /// Parse a comma-separated list of items, requiring at least one item, and not allowing trailing commas.
 pub fn at_least_one_comma_separated_no_trailing<T>(
     state: &mut State,
-    func: &(dyn Fn(&mut State) -> ParseResult<T>),
+    func: &dyn Fn(&mut State) -> ParseResult<T>,
 ) -> ParseResult<CommaSeparated<T>> {
     let mut inner: Vec<T> = vec![];
     let mut commas: Vec<Span> = vec![];
@@ -1,4 +1,3 @@
-use crate::expected_token_err;
 use crate::lexer::token::TokenKind;
 use crate::parser::ast::variables::BracedVariableVariable;
 use crate::parser::ast::variables::SimpleVariable;
@@ -6,14 +5,16 @@ use crate::parser::ast::variables::Variable;
 use crate::parser::ast::variables::VariableVariable;
 use crate::parser::error::ParseResult;
 use crate::parser::expressions;
+use crate::parser::internal::scopes;
 use crate::parser::internal::utils;
+use crate::parser::macros::expected_token_err;
 use crate::parser::state::State;
 
 pub fn simple_variable(state: &mut State) -> ParseResult<SimpleVariable> {
     let current = state.stream.current();
     if let TokenKind::Variable = &current.kind {
         let span = current.span;
-        let name = current.value.clone();
+        let name = current.value.to_byte_string();
         state.stream.next();
 
         return Ok(SimpleVariable { span, name });
@@ -27,9 +28,13 @@ pub fn dynamic_variable(state: &mut State) -> ParseResult<Variable> {
     match &current.kind {
         TokenKind::Variable => {
             let span = current.span;
-            let name = current.value.clone();
+            let name = current.value.to_byte_string();
             state.stream.next();
 
+            if name == b"$this" {
+                scopes::this(state, span);
+            }
+
             Ok(Variable::SimpleVariable(SimpleVariable { span, name }))
         }
         TokenKind::DollarLeftBrace => {
@@ -66,7 +71,7 @@ pub fn dynamic_variable(state: &mut State) -> ParseResult<Variable> {
             let span = current.span;
             state.stream.next();
 
-            let variable = dynamic_variable(state)?;
+            let variable = state.nested(dynamic_variable)?;
 
             Ok(Variable::VariableVariable(VariableVariable {
                 span,
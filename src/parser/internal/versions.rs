@@ -0,0 +1,121 @@
+use crate::lexer::token::Span;
+use crate::parser::ast::data_type::Type;
+use crate::parser::error;
+use crate::parser::options::PhpVersion;
+use crate::parser::state::State;
+
+/// Record an unsupported feature error when the targeted version of PHP is older than the
+/// version introducing the given feature.
+pub fn requires(state: &mut State, version: PhpVersion, feature: &str, span: Span, length: usize) {
+    if let Some(target) = state.php_version.filter(|target| *target < version) {
+        state.record(error::unsupported_feature(
+            feature, version, target, span, length,
+        ));
+    }
+}
+
+/// Record a removed feature error when the targeted version of PHP is the version removing
+/// the given feature, or a later one.
+pub fn removed(state: &mut State, version: PhpVersion, feature: &str, span: Span, length: usize) {
+    if let Some(target) = state.php_version.filter(|target| *target >= version) {
+        state.record(error::removed_feature(
+            feature, version, target, span, length,
+        ));
+    }
+}
+
+/// Record an experimental feature error when experimental syntax is not accepted.
+pub fn experimental(state: &mut State, feature: &str, span: Span, length: usize) {
+    if !state.experimental {
+        state.record(error::experimental_feature(feature, span, length));
+    }
+}
+
+/// Validate a parameter, property, or return type against the targeted version of PHP.
+pub fn data_type(state: &mut State, ty: &Type) {
+    if state.php_version.is_none() {
+        return;
+    }
+
+    match ty {
+        Type::Nullable(span, inner) => {
+            requires(state, PhpVersion::Php71, "nullable types", *span, 1);
+
+            data_type(state, inner);
+        }
+        Type::Union(types) => {
+            requires(
+                state,
+                PhpVersion::Php80,
+                "union types",
+                ty.first_span(),
+                ty.to_string().len(),
+            );
+
+            members(state, types, |member| {
+                matches!(member, Type::Intersection(_))
+            });
+        }
+        Type::Intersection(types) => {
+            requires(
+                state,
+                PhpVersion::Php81,
+                "intersection types",
+                ty.first_span(),
+                ty.to_string().len(),
+            );
+
+            members(state, types, |member| matches!(member, Type::Union(_)));
+        }
+        _ => simple_data_type(state, ty, true),
+    }
+}
+
+/// Validate the members of a union or an intersection, where the given nested types are
+/// disjunctive normal form types.
+fn members(state: &mut State, types: &[Type], dnf: impl Fn(&Type) -> bool) {
+    for member in types {
+        if dnf(member) {
+            requires(
+                state,
+                PhpVersion::Php82,
+                "disjunctive normal form types",
+                member.first_span(),
+                member.to_string().len(),
+            );
+        }
+
+        match member {
+            Type::Union(types) | Type::Intersection(types) => {
+                for ty in types {
+                    simple_data_type(state, ty, false);
+                }
+            }
+            _ => simple_data_type(state, member, false),
+        }
+    }
+}
+
+fn simple_data_type(state: &mut State, ty: &Type, standalone: bool) {
+    let (version, feature) = match ty {
+        Type::Void(_) => (PhpVersion::Php71, "the `void` type"),
+        Type::Iterable(_) => (PhpVersion::Php71, "the `iterable` type"),
+        Type::Object(_) => (PhpVersion::Php72, "the `object` type"),
+        Type::Mixed(_) => (PhpVersion::Php80, "the `mixed` type"),
+        Type::StaticReference(_) => (PhpVersion::Php80, "the `static` return type"),
+        Type::Never(_) => (PhpVersion::Php81, "the `never` type"),
+        Type::True(_) => (PhpVersion::Php82, "the `true` type"),
+        Type::Null(_) | Type::False(_) if standalone => {
+            (PhpVersion::Php82, "standalone `null` and `false` types")
+        }
+        _ => return,
+    };
+
+    requires(
+        state,
+        version,
+        feature,
+        ty.first_span(),
+        ty.to_string().len(),
+    );
+}
@@ -0,0 +1,358 @@
+use std::fmt::Display;
+
+/// The maximum nesting depth of expressions and statements of [`ResourceLimits::untrusted`],
+/// see [`ResourceLimits::max_depth`].
+///
+/// It fits the default stack size of a spawned thread, 2 MiB, in release builds. Debug builds
+/// use far more stack per level, and need a 16 MiB stack, a lower limit, or the `stacker`
+/// feature, with which [`STACKER_MAX_DEPTH`] is used instead.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// The maximum nesting depth of expressions and statements of [`ResourceLimits::untrusted`]
+/// with the `stacker` feature, see [`ResourceLimits::max_depth`].
+///
+/// With the `stacker` feature, the parser and the [`crate::traverser::Visitor`] grow the stack
+/// on demand rather than overflowing it, so the limit can be raised further, e.g. for generated
+/// code. The derived implementations of the AST, such as `Clone`, `Debug` and `Drop`, still
+/// recurse on the stack of the caller.
+pub const STACKER_MAX_DEPTH: usize = 1024;
+
+/// The stack left, below which a new segment is allocated before recursing.
+#[cfg(feature = "stacker")]
+const STACK_RED_ZONE: usize = 256 * 1024;
+
+/// The size of the stack segments allocated when growing the stack.
+#[cfg(feature = "stacker")]
+const STACK_SEGMENT_SIZE: usize = 4 * 1024 * 1024;
+
+/// Run the given function, with the `stacker` feature first growing the stack when it is
+/// close to overflowing.
+#[inline(always)]
+pub(crate) fn grow_stack<T>(f: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "stacker")]
+    return stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, f);
+
+    #[cfg(not(feature = "stacker"))]
+    f()
+}
+
+/// Limits guarding against adversarial inputs, e.g. when parsing untrusted code in a server,
+/// see [`crate::ParserOptions::with_limits`].
+///
+/// Exceeding a limit stops the parse with a resource limit error (`E067`), see
+/// [`crate::parser::error::ParseError::resource_limit`], except for the maximum literal
+/// length. All limits are disabled by default.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// The maximum size of the input, in bytes, checked before lexing.
+    pub max_input_size: Option<usize>,
+    /// The maximum number of tokens, checked while lexing.
+    pub max_tokens: Option<usize>,
+    /// The maximum number of errors, further errors are dropped and parsing stops before
+    /// the next top-level statement.
+    pub max_errors: Option<usize>,
+    /// The maximum length of a string literal, in bytes. Longer literals are not an error,
+    /// their value is left empty rather than copied into the AST, see
+    /// [`crate::parser::ast::literals::LiteralString::source`].
+    pub max_literal_length: Option<usize>,
+    /// The maximum nesting depth of expressions and statements, e.g. of parentheses in
+    /// `((((1))))`, as the parser recurses into nested constructs and would overflow the
+    /// stack, unless growing it with the `stacker` feature, see [`DEFAULT_MAX_DEPTH`].
+    ///
+    /// It also bounds the depth of the tree of long concatenation chains, whose last
+    /// operands are grouped into their own right operand every 1024 operands, e.g.
+    /// `$a . ($b . $c)` rather than `($a . $b) . $c`, evaluating into the same string.
+    pub max_depth: Option<usize>,
+}
+
+impl ResourceLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The limits of [`crate::parse_untrusted`]: inputs of up to 8 MiB, a million tokens and
+    /// a hundred errors, at a depth of [`DEFAULT_MAX_DEPTH`], or [`STACKER_MAX_DEPTH`] with the
+    /// `stacker` feature.
+    pub fn untrusted() -> Self {
+        let max_depth = if cfg!(feature = "stacker") {
+            STACKER_MAX_DEPTH
+        } else {
+            DEFAULT_MAX_DEPTH
+        };
+
+        Self::new()
+            .with_max_input_size(8 * 1024 * 1024)
+            .with_max_tokens(1_000_000)
+            .with_max_errors(100)
+            .with_max_depth(max_depth)
+    }
+
+    pub fn with_max_input_size(mut self, max: usize) -> Self {
+        self.max_input_size = Some(max);
+
+        self
+    }
+
+    pub fn with_max_tokens(mut self, max: usize) -> Self {
+        self.max_tokens = Some(max);
+
+        self
+    }
+
+    pub fn with_max_errors(mut self, max: usize) -> Self {
+        self.max_errors = Some(max);
+
+        self
+    }
+
+    pub fn with_max_literal_length(mut self, max: usize) -> Self {
+        self.max_literal_length = Some(max);
+
+        self
+    }
+
+    pub fn with_max_depth(mut self, max: usize) -> Self {
+        self.max_depth = Some(max);
+
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceLimit {
+    InputSize,
+    TokenCount,
+    ErrorCount,
+    Depth,
+}
+
+impl ResourceLimit {
+    /// The message variant of the resource limit error, see [`crate::parser::catalog`].
+    pub fn variant(&self) -> &'static str {
+        match self {
+            Self::InputSize => "input_size",
+            Self::TokenCount => "token_count",
+            Self::ErrorCount => "error_count",
+            Self::Depth => "depth",
+        }
+    }
+
+    pub fn from_variant(variant: &str) -> Option<Self> {
+        [
+            Self::InputSize,
+            Self::TokenCount,
+            Self::ErrorCount,
+            Self::Depth,
+        ]
+        .into_iter()
+        .find(|limit| limit.variant() == variant)
+    }
+}
+
+impl Display for ResourceLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.variant())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResourceLimit;
+    use super::ResourceLimits;
+    use crate::parser::ast::literals::Literal;
+    use crate::parser::ast::literals::LiteralString;
+    use crate::parser::ast::Expression;
+    use crate::parser::ast::Program;
+    use crate::parser::ast::Statement;
+    use crate::parser::ast::StaticVar;
+    use crate::parser::error::ParseErrorStack;
+    use crate::ParserOptions;
+
+    fn parse_with_limits(code: &str, limits: ResourceLimits) -> Result<Program, ParseErrorStack> {
+        crate::parse_with_options(code, &ParserOptions::new().with_limits(limits))
+    }
+
+    #[test]
+    fn test_resource_limits() {
+        let code = "<?php
+class A {
+    public function a() {}
+    public function b() {}
+}
+";
+        let parse = |limits: ResourceLimits| parse_with_limits(code, limits);
+
+        assert!(parse(ResourceLimits::new()).is_ok());
+        assert!(parse(
+            ResourceLimits::new()
+                .with_max_input_size(code.len())
+                .with_max_tokens(19)
+                .with_max_errors(0)
+        )
+        .is_ok());
+
+        let stack = parse(ResourceLimits::new().with_max_input_size(code.len() - 1)).unwrap_err();
+        assert_eq!(stack.errors.len(), 1);
+        assert_eq!(stack.errors[0].id, "E067");
+        assert_eq!(
+            stack.errors[0].resource_limit(),
+            Some(ResourceLimit::InputSize)
+        );
+        assert!(stack.is_resource_limit_exceeded());
+
+        let stack = parse(ResourceLimits::new().with_max_tokens(18)).unwrap_err();
+        assert_eq!(
+            stack.errors[0].resource_limit(),
+            Some(ResourceLimit::TokenCount)
+        );
+        assert_eq!(stack.errors[0].span.line, 5);
+        assert_eq!(
+            stack.errors[0].message,
+            "input exceeds the maximum of 18 tokens"
+        );
+
+        let broken = "<?php
+function a(): ?void {}
+function b(): ?never {}
+function c(): ?mixed {}
+function d(): ?void {}
+";
+        let stack = parse_with_limits(broken, ResourceLimits::new()).unwrap_err();
+        assert_eq!(stack.errors.len(), 4);
+        assert!(!stack.is_resource_limit_exceeded());
+
+        let stack =
+            parse_with_limits(broken, ResourceLimits::new().with_max_errors(2)).unwrap_err();
+        let errors = stack
+            .errors
+            .iter()
+            .map(|error| (error.id.as_str(), error.resource_limit()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                ("E009", None),
+                ("E009", None),
+                ("E067", Some(ResourceLimit::ErrorCount))
+            ]
+        );
+        assert_eq!(stack.errors[2].span.line, 5);
+        // The function holding the dropped error is parsed, the next one isn't.
+        assert_eq!(stack.partial.len(), 4);
+    }
+
+    #[test]
+    fn test_limits_with_options() {
+        let code = "<?php enum Suit {} function a(): ?void {} function b(): ?void {}";
+        let options = ParserOptions::new()
+            .with_php_version(crate::PhpVersion::Php80)
+            .with_limits(ResourceLimits::new().with_max_errors(1));
+
+        let stack = crate::parse_tolerant_with_options(code, &options).unwrap_err();
+        let errors = stack
+            .errors
+            .iter()
+            .map(|error| error.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(errors, vec!["E071", "E067"]);
+
+        let mut parser = crate::Parser::new().with_options(options.clone());
+        assert_eq!(
+            parser.parse(code),
+            crate::parse_with_options(code, &options)
+        );
+
+        let options = options.with_limits(ResourceLimits::new().with_max_tokens(4));
+        let mut parser = crate::Parser::new().with_options(options);
+        let stack = parser.parse(code).unwrap_err();
+        assert_eq!(
+            stack.errors[0].resource_limit(),
+            Some(ResourceLimit::TokenCount)
+        );
+        assert_eq!(parser.token_count(), 0);
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let nested = |depth: usize| format!("<?php {}1{};", "(".repeat(depth), ")".repeat(depth));
+        let limits = ResourceLimits::new().with_max_depth(8);
+
+        assert!(parse_with_limits(&nested(6), limits).is_ok());
+
+        let stack = parse_with_limits(&nested(20), limits).unwrap_err();
+        assert_eq!(stack.errors.len(), 1);
+        assert_eq!(stack.errors[0].resource_limit(), Some(ResourceLimit::Depth));
+        assert_eq!(
+            stack.errors[0].message,
+            "input exceeds the maximum nesting depth of 8"
+        );
+        assert_eq!(stack.errors[0].span.column, 14);
+
+        // Statements count towards the depth.
+        let code = "<?php if (1) { if (2) { if (3) { if (4) { f(g(h(i(1)))); } } } } echo 1;";
+        let stack = parse_with_limits(code, limits).unwrap_err();
+        assert!(stack.is_resource_limit_exceeded());
+        // Only the opening tag is parsed.
+        assert_eq!(stack.partial.len(), 1);
+
+        // The depth is only limited when asked to, with a stack large enough for a debug build.
+        let code = format!(
+            "<?php {} echo 1; {}",
+            "if (1) {".repeat(100),
+            "}".repeat(100)
+        );
+        let result = std::thread::Builder::new()
+            .stack_size(64 * 1024 * 1024)
+            .spawn(move || crate::parse(&code).map(|program| program.len()))
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(result, Ok(2));
+    }
+
+    #[cfg(feature = "stacker")]
+    #[test]
+    fn test_stack_growth() {
+        let code = format!("<?php {}1{};", "(".repeat(2000), ")".repeat(2000));
+        let limits = ResourceLimits::new().with_max_depth(usize::MAX);
+
+        // Far deeper than the stack of the thread would allow without growing it.
+        let result = std::thread::Builder::new()
+            .stack_size(128 * 1024)
+            .spawn(move || parse_with_limits(&code, limits))
+            .unwrap()
+            .join()
+            .unwrap();
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_max_literal_length() {
+        let code = "<?php static $a = 'ab', $b = \"cd\\n\";";
+        let limits = ResourceLimits::new().with_max_literal_length(4);
+
+        let literals = match &parse_with_limits(code, limits).unwrap()[1] {
+            Statement::Static(statement) => statement
+                .vars
+                .iter()
+                .map(|var| match var {
+                    StaticVar {
+                        default: Some(Expression::Literal(Literal::String(literal))),
+                        ..
+                    } => literal.clone(),
+                    var => panic!("unexpected variable: {var:?}"),
+                })
+                .collect::<Vec<LiteralString>>(),
+            statement => panic!("unexpected statement: {statement:?}"),
+        };
+
+        assert_eq!(literals[0].value, b"'ab'");
+        assert!(!literals[0].is_deferred());
+        assert_eq!(literals[0].source(code.as_bytes()), None);
+
+        assert!(literals[1].value.is_empty());
+        assert_eq!(literals[1].deferred, Some(6));
+        assert_eq!(literals[1].source(code.as_bytes()), Some(&b"\"cd\\n\""[..]));
+    }
+}
@@ -1,4 +1,3 @@
-#[macro_export]
 macro_rules! peek_token {
     ([ $($(|)? $( $pattern:pat_param )|+ $( if $guard: expr )? => $out:expr),+ $(,)? ], $state:expr, [ $($message:literal),+ $(,)? ]) => {{
         match &$state.stream.current().kind {
@@ -6,24 +5,23 @@ macro_rules! peek_token {
                 $( $pattern )|+ $( if $guard )? => $out,
             )+
             _ => {
-                return $crate::expected_token_err!([ $($message,)+ ], $state);
+                return $crate::parser::macros::expected_token_err!([ $($message,)+ ], $state);
             }
         }
     }};
     ([ $($(|)? $( $pattern:pat_param )|+ $( if $guard: expr )?),+ $(,)? ], $state:expr, [ $($message:literal),+ $(,)? ]) => {{
         if !matches!($state.stream.current().kind, $( $pattern )|+ $( if $guard )?) {
-            return $crate::expected_token_err!([ $($message,)+ ], $state);
+            return $crate::parser::macros::expected_token_err!([ $($message,)+ ], $state);
         }
     }};
     ([ $($(|)? $( $pattern:pat_param )|+ $( if $guard: expr )? => $out:expr),+ $(,)? ], $state:expr, $message:literal) => {
-        $crate::peek_token!([ $($( $pattern )|+ $( if $guard )? => $out,)+ ], $state, [$message])
+        $crate::parser::macros::peek_token!([ $($( $pattern )|+ $( if $guard )? => $out,)+ ], $state, [$message])
     };
     ([ $($(|)? $( $pattern:pat_param )|+ $( if $guard: expr )?),+ $(,)? ], $state:expr, $message:literal) => {
-        $crate::peek_token!([ $($( $pattern )|+ $( if $guard )?,)+ ], $state, [$message])
+        $crate::parser::macros::peek_token!([ $($( $pattern )|+ $( if $guard )?,)+ ], $state, [$message])
     };
 }
 
-#[macro_export]
 macro_rules! expect_token {
     ([ $($(|)? $( $pattern:pat_param )|+ $( if $guard: expr )? => $out:expr),+ $(,)? ], $state:expr, [ $($message:literal),+ $(,)? ]) => {{
         let token = $state.stream.current();
@@ -43,11 +41,10 @@ macro_rules! expect_token {
         }
     }};
     ([ $($(|)? $( $pattern:pat_param )|+ $( if $guard: expr )? => $out:expr),+ $(,)? ], $state:expr, $message:literal) => {
-        $crate::expect_token!([ $($( $pattern )|+ $( if $guard )? => $out,)+ ], $state, [$message])
+        $crate::parser::macros::expect_token!([ $($( $pattern )|+ $( if $guard )? => $out,)+ ], $state, [$message])
     };
 }
 
-#[macro_export]
 macro_rules! expect_literal {
     ($state:expr) => {{
         let current = $state.stream.current();
@@ -59,7 +56,7 @@ macro_rules! expect_literal {
                 $crate::parser::ast::literals::Literal::Integer(
                     $crate::parser::ast::literals::LiteralInteger {
                         span: current.span,
-                        value: current.value.clone(),
+                        value: current.value.to_byte_string(),
                     },
                 )
             }
@@ -69,7 +66,7 @@ macro_rules! expect_literal {
                 $crate::parser::ast::literals::Literal::Float(
                     $crate::parser::ast::literals::LiteralFloat {
                         span: current.span,
-                        value: current.value.clone(),
+                        value: current.value.to_byte_string(),
                     },
                 )
             }
@@ -77,31 +74,26 @@ macro_rules! expect_literal {
                 $state.stream.next();
 
                 $crate::parser::ast::literals::Literal::String(
-                    $crate::parser::ast::literals::LiteralString {
-                        span: current.span,
-                        value: current.value.clone(),
-                    },
+                    $crate::parser::internal::strings::literal($state, current),
                 )
             }
             _ => {
-                return $crate::expected_token_err!(["a literal"], $state);
+                return $crate::parser::macros::expected_token_err!(["a literal"], $state);
             }
         }
     }};
 }
 
-#[macro_export]
 macro_rules! expected_token_err {
     ([ $($expected:literal),+ $(,)? ], $state:expr $(,)?) => {{
-        Err($crate::expected_token!([$($expected),+], $state))
+        Err($crate::parser::macros::expected_token!([$($expected),+], $state))
     }};
 
     ($expected:literal, $state:expr $(,)?) => {
-        $crate::expected_token_err!([$expected], $state)
+        $crate::parser::macros::expected_token_err!([$expected], $state)
     };
 }
 
-#[macro_export]
 macro_rules! expected_token {
     ([ $($expected:literal),+ $(,)? ], $state:expr $(,)?) => {{
         $crate::parser::error::unexpected_token(
@@ -111,25 +103,10 @@ macro_rules! expected_token {
     }};
 
     ($expected:literal, $state:expr $(,)?) => {
-        $crate::expected_token!([$expected], $state)
+        $crate::parser::macros::expected_token!([$expected], $state)
     };
 }
 
-#[macro_export]
-macro_rules! expected_scope {
-    ([ $($(|)? $( $pattern:pat_param )|+ $( if $guard: expr )? => $out:expr),+ $(,)? ], $state:expr) => {{
-        match $state.scope().cloned()? {
-            $(
-                $( $pattern )|+ $( if $guard )? => $out,
-            )+
-            _ => {
-                return Err($crate::parser::error::reached_unpredictable_state($state.stream.current().span));
-            }
-        }
-    }};
-}
-
-#[macro_export]
 macro_rules! scoped {
     ($state:expr, $scope:expr, $block:block) => {{
         $state.enter($scope);
@@ -141,3 +118,10 @@ macro_rules! scoped {
         result
     }};
 }
+
+pub(crate) use expect_literal;
+pub(crate) use expect_token;
+pub(crate) use expected_token;
+pub(crate) use expected_token_err;
+pub(crate) use peek_token;
+pub(crate) use scoped;
@@ -1,14 +1,21 @@
-use crate::expect_literal;
 use crate::lexer::token::OpenTagKind;
+use crate::lexer::token::Span;
 use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
 use crate::lexer::Lexer;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::data_type::Type;
 use crate::parser::ast::declares::DeclareBody;
 use crate::parser::ast::declares::DeclareEntry;
 use crate::parser::ast::declares::DeclareEntryGroup;
 use crate::parser::ast::declares::DeclareStatement;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::literals::Literal;
 use crate::parser::ast::variables::Variable;
 use crate::parser::ast::{Program, Statement, StaticVar};
+use crate::parser::cancellation::CancellationToken;
+use crate::parser::error::Parse;
+use crate::parser::error::ParseError;
 use crate::parser::error::ParseErrorStack;
 use crate::parser::error::ParseResult;
 use crate::parser::internal::attributes;
@@ -16,6 +23,7 @@ use crate::parser::internal::blocks;
 use crate::parser::internal::classes;
 use crate::parser::internal::constants;
 use crate::parser::internal::control_flow;
+use crate::parser::internal::data_type;
 use crate::parser::internal::enums;
 use crate::parser::internal::functions;
 use crate::parser::internal::goto;
@@ -23,17 +31,27 @@ use crate::parser::internal::identifiers;
 use crate::parser::internal::interfaces;
 use crate::parser::internal::loops;
 use crate::parser::internal::namespaces;
+use crate::parser::internal::recovery;
 use crate::parser::internal::traits;
 use crate::parser::internal::try_block;
 use crate::parser::internal::uses;
 use crate::parser::internal::utils;
 use crate::parser::internal::variables;
+use crate::parser::limits::ResourceLimit;
+use crate::parser::limits::ResourceLimits;
+use crate::parser::macros::expect_literal;
+use crate::parser::options::ParserOptions;
+use crate::parser::state::Buffers;
+use crate::parser::state::Scope;
 use crate::parser::state::State;
+use crate::parser::suppressions::Suppressions;
+use crate::parser::telemetry::Telemetry;
 
 pub use crate::lexer::stream::TokenStream;
 
 use self::ast::EchoStatement;
 use self::ast::ExpressionStatement;
+use self::ast::ForeignChunk;
 use self::ast::GlobalStatement;
 use self::ast::HaltCompiler;
 use self::ast::ReturnStatement;
@@ -41,7 +59,17 @@ use self::ast::StaticStatement;
 use self::internal::precedences::Precedence;
 
 pub mod ast;
+pub mod cancellation;
+pub mod catalog;
+pub mod completeness;
+pub mod cst;
 pub mod error;
+pub mod limits;
+pub mod options;
+pub mod reusable;
+pub mod source_map;
+pub mod suppressions;
+pub mod telemetry;
 
 mod expressions;
 mod internal;
@@ -49,8 +77,209 @@ mod macros;
 mod state;
 
 pub fn parse<B: ?Sized + AsRef<[u8]>>(input: &B) -> Result<Program, ParseErrorStack> {
-    let lexer = Lexer::new();
-    let tokens = match lexer.tokenize(input) {
+    parse_with_options(input, &ParserOptions::new())
+}
+
+/// Parse input from an untrusted source, e.g. a file uploaded to a web service, with the
+/// limits of [`ResourceLimits::untrusted`], see [`ParserOptions::untrusted`].
+///
+/// Like every parse function, it never panics on any input, valid PHP or not. The limits
+/// additionally bound the time and memory spent on adversarial inputs.
+pub fn parse_untrusted<B: ?Sized + AsRef<[u8]>>(input: &B) -> Result<Program, ParseErrorStack> {
+    parse_with_options(input, &ParserOptions::untrusted())
+}
+
+/// Parse the given input with the given options, e.g. targeting a version of PHP, within
+/// resource limits, or with a cancellation token.
+///
+/// Syntax introduced after the targeted version is reported as an unsupported feature error
+/// (`E071`), and syntax removed in or before it, such as the `(unset)` cast, as a removed
+/// feature error (`E072`). Without a targeted version, the syntax of any version is accepted.
+///
+/// Exceeding any of the [`ParserOptions::limits`] stops the parse with a resource limit error
+/// (`E067`). The maximum number of errors is checked between top-level statements, so the
+/// program holding the last recorded error is still parsed.
+///
+/// ```
+/// use php_parser_rs::ParserOptions;
+/// use php_parser_rs::PhpVersion;
+///
+/// let code = "<?php enum Suit { case Hearts; }";
+/// let options = ParserOptions::new().with_php_version(PhpVersion::Php80);
+///
+/// let stack = php_parser_rs::parse_with_options(code, &options).unwrap_err();
+/// assert_eq!(stack.errors[0].message, "using enums requires PHP 8.1 or later");
+/// ```
+pub fn parse_with_options<B: ?Sized + AsRef<[u8]>>(
+    input: &B,
+    options: &ParserOptions,
+) -> Result<Program, ParseErrorStack> {
+    parse_program(input.as_ref(), false, options)
+}
+
+fn parse_program(
+    input: &[u8],
+    recovering: bool,
+    options: &ParserOptions,
+) -> Result<Program, ParseErrorStack> {
+    match lexer(input, &options.limits)?.tokenize(input) {
+        Ok(tokens) => construct_program(&tokens, recovering, options),
+        Err(error) => Err(ParseErrorStack {
+            errors: vec![error.into()],
+            partial: Vec::new(),
+        }),
+    }
+}
+
+/// The lexer of the given input, stopping with a resource limit error (`E067`) when the input
+/// exceeds the maximum size, or once it exceeds the maximum number of tokens.
+fn lexer(input: &[u8], limits: &ResourceLimits) -> Result<Lexer, ParseErrorStack> {
+    if let Some(max) = limits.max_input_size.filter(|max| input.len() > *max) {
+        return Err(ParseErrorStack {
+            errors: vec![error::resource_limit_exceeded(
+                ResourceLimit::InputSize,
+                max,
+                Span::new(1, 1, 0),
+            )],
+            partial: Vec::new(),
+        });
+    }
+
+    Ok(match limits.max_tokens {
+        Some(max) => Lexer::new().with_max_tokens(max),
+        None => Lexer::new(),
+    })
+}
+
+/// Parse the given template, capturing the regions of inline HTML delimited by any of the
+/// given pairs of opening and closing markers as `ForeignChunk` statements.
+///
+/// See [`Lexer::with_foreign_markers`].
+pub fn parse_template<B: ?Sized + AsRef<[u8]>>(
+    input: &B,
+    markers: &[(&str, &str)],
+) -> Result<Program, ParseErrorStack> {
+    let lexer = markers.iter().fold(Lexer::new(), |lexer, (open, close)| {
+        lexer.with_foreign_markers(*open, *close)
+    });
+
+    match lexer.tokenize(input) {
+        Ok(tokens) => construct(&tokens),
+        Err(error) => Err(ParseErrorStack {
+            errors: vec![error.into()],
+            partial: Vec::new(),
+        }),
+    }
+}
+
+/// Parse a single class member, e.g. `public function __construct(private int $id) {}`,
+/// without wrapping it in a class.
+///
+/// The input is PHP code without an open tag. The member is parsed as if it was declared
+/// within an abstract class extending another class, so abstract methods and `parent` are allowed.
+pub fn parse_class_member<B: ?Sized + AsRef<[u8]>>(
+    input: &B,
+) -> Result<ClassMember, ParseErrorStack> {
+    let class = snippet_identifier("class@anonymous");
+
+    snippet(input, vec![Scope::Class(class.clone(), true)], |state| {
+        classes::member(state, true, None, &class)
+    })
+}
+
+/// Parse the statements of a method body, e.g. `return $this->name;`, without wrapping them
+/// in a method and a class.
+///
+/// The input is PHP code without an open tag or braces. The statements are parsed as if they
+/// were within a non-static method of a class extending another class.
+pub fn parse_method_body<B: ?Sized + AsRef<[u8]>>(input: &B) -> Result<Program, ParseErrorStack> {
+    let scopes = vec![
+        Scope::Class(snippet_identifier("class@anonymous"), true),
+        Scope::Method(snippet_identifier("method@anonymous"), None),
+    ];
+
+    let mut statements = Program::new();
+    let result = snippet(input, scopes, |state| {
+        while !state.stream.is_eof() {
+            statements.push(statement(state)?);
+        }
+
+        Ok(())
+    });
+
+    match result {
+        Ok(()) => Ok(statements),
+        Err(mut stack) => {
+            stack.partial = statements;
+
+            Err(stack)
+        }
+    }
+}
+
+/// Parse a type, e.g. `?Foo`, `int|string` or `(A&B)|null`, in the native syntax of parameter,
+/// property and return types.
+///
+/// The type is parsed as if it was the return type of a method of a class extending another
+/// class, so `self`, `parent` and `static` are allowed.
+///
+/// ```
+/// let ty = php_parser_rs::parse_type("?Foo").unwrap();
+/// assert_eq!(ty.to_string(), "?Foo");
+///
+/// assert!(php_parser_rs::parse_type("?mixed").is_err());
+/// ```
+pub fn parse_type<B: ?Sized + AsRef<[u8]>>(input: &B) -> Result<Type, ParseErrorStack> {
+    let class = snippet_identifier("class@anonymous");
+
+    snippet(input, vec![Scope::Class(class, true)], data_type::data_type)
+}
+
+/// Parse a type in the docblock grammar of static analysers, e.g. `array<int, Foo>|null`, as
+/// written in `@param`, `@return` and `@var` tags.
+///
+/// What can't be expressed by a native type, such as generic arguments and array shapes, is
+/// erased, e.g. `list<Foo>` is an array, and `class-string<Foo>` a string.
+///
+/// ```
+/// let ty = php_parser_rs::parse_docblock_type("?array<int, Foo>|null").unwrap();
+/// assert_eq!(ty.to_string(), "?array|null");
+/// ```
+pub fn parse_docblock_type<B: ?Sized + AsRef<[u8]>>(input: &B) -> Result<Type, ParseErrorStack> {
+    let class = snippet_identifier("class@anonymous");
+
+    let tokens = match Lexer::new().with_scripting().tokenize(input) {
+        Ok(tokens) => data_type::docblock_tokens(tokens),
+        Err(error) => {
+            return Err(ParseErrorStack {
+                errors: vec![error.into()],
+                partial: Vec::new(),
+            })
+        }
+    };
+
+    snippet_tokens(
+        &tokens,
+        vec![Scope::Class(class, true)],
+        data_type::docblock_type,
+    )
+}
+
+fn snippet_identifier(value: &str) -> SimpleIdentifier {
+    SimpleIdentifier {
+        span: Span::new(1, 1, 0),
+        value: value.into(),
+    }
+}
+
+/// Parse a snippet of PHP code without an open tag within the given scopes,
+/// expecting the whole input to be consumed.
+fn snippet<B: ?Sized + AsRef<[u8]>, T>(
+    input: &B,
+    scopes: Vec<Scope>,
+    parse: impl FnOnce(&mut State) -> ParseResult<T>,
+) -> Result<T, ParseErrorStack> {
+    let tokens = match Lexer::new().with_scripting().tokenize(input) {
         Ok(tokens) => tokens,
         Err(error) => {
             return Err(ParseErrorStack {
@@ -60,33 +289,202 @@ pub fn parse<B: ?Sized + AsRef<[u8]>>(input: &B) -> Result<Program, ParseErrorSt
         }
     };
 
-    construct(&tokens)
+    snippet_tokens(&tokens, scopes, parse)
+}
+
+/// Parse the tokens of a snippet, see [`snippet`].
+fn snippet_tokens<T>(
+    tokens: &[Token],
+    scopes: Vec<Scope>,
+    parse: impl FnOnce(&mut State) -> ParseResult<T>,
+) -> Result<T, ParseErrorStack> {
+    let mut stream = TokenStream::new(tokens);
+    let mut state = State::new(&mut stream);
+    for scope in scopes {
+        state.enter(scope);
+    }
+
+    let result = parse(&mut state).and_then(|value| {
+        if state.stream.is_eof() {
+            Ok(value)
+        } else {
+            Err(error::unexpected_token(vec![], state.stream.current()))
+        }
+    });
+
+    match result {
+        Ok(value) if state.errors.is_empty() => Ok(value),
+        Ok(_) => Err(ParseErrorStack {
+            errors: state.errors,
+            partial: Vec::new(),
+        }),
+        Err(error) => {
+            state.record(error);
+
+            Err(ParseErrorStack {
+                errors: state.errors,
+                partial: Vec::new(),
+            })
+        }
+    }
+}
+
+/// Parse the given input, recovering from syntax errors where possible instead of stopping
+/// at the first one.
+///
+/// A statement which fails to parse is replaced by a [`Statement::Error`] holding its tokens, and
+/// parsing resynchronizes at the next statement boundary, a `;` or the `}` closing the statement's
+/// block, then continues with the next statement. An expression which is missing, e.g. in
+/// `$a = ;`, is replaced by an [`ast::Expression::Missing`] placeholder. A class, interface,
+/// trait, or enum member which fails to parse is dropped, and parsing continues with the next
+/// member. When any error occurred, the returned
+/// stack holds all errors, and the recovered program as `partial`.
+pub fn parse_tolerant<B: ?Sized + AsRef<[u8]>>(input: &B) -> Result<Program, ParseErrorStack> {
+    parse_tolerant_with_options(input, &ParserOptions::new())
+}
+
+/// Parse the given input, collecting every error found in it, as a linter would report them.
+///
+/// The parser recovers from errors as described in [`parse_tolerant`]. An input which can't be
+/// tokenized results in a single error and an empty program.
+pub fn parse_all<B: ?Sized + AsRef<[u8]>>(input: &B) -> Parse {
+    parse_tolerant(input).into()
+}
+
+/// Parse the given input as [`parse_tolerant`] does, with the given options, see
+/// [`parse_with_options`].
+pub fn parse_tolerant_with_options<B: ?Sized + AsRef<[u8]>>(
+    input: &B,
+    options: &ParserOptions,
+) -> Result<Program, ParseErrorStack> {
+    parse_program(input.as_ref(), true, options)
+}
+
+/// Parse the given input as [`parse_all`] does, with the given options.
+pub fn parse_all_with_options<B: ?Sized + AsRef<[u8]>>(
+    input: &B,
+    options: &ParserOptions,
+) -> Parse {
+    parse_tolerant_with_options(input, options).into()
 }
 
 pub fn construct(tokens: &[Token]) -> Result<Program, ParseErrorStack> {
+    construct_with_options(tokens, &ParserOptions::new())
+}
+
+/// Construct a program from the given tokens with the given options, see
+/// [`parse_with_options`].
+///
+/// The maximum input size and number of tokens are checked while lexing, so they don't
+/// apply to tokens lexed beforehand.
+pub fn construct_with_options(
+    tokens: &[Token],
+    options: &ParserOptions,
+) -> Result<Program, ParseErrorStack> {
+    construct_program(tokens, false, options)
+}
+
+fn construct_program(
+    tokens: &[Token],
+    recovering: bool,
+    options: &ParserOptions,
+) -> Result<Program, ParseErrorStack> {
+    construct_reusing(
+        tokens,
+        recovering,
+        options,
+        options.telemetry.as_ref(),
+        &mut Buffers::default(),
+    )
+}
+
+/// Construct a program from the given tokens as [`construct_program`] does, parsing with the
+/// given buffers, which are handed back cleared once done, reporting unexpected token
+/// errors to the given telemetry hook.
+fn construct_reusing(
+    tokens: &[Token],
+    recovering: bool,
+    options: &ParserOptions,
+    telemetry: Option<&Telemetry>,
+    buffers: &mut Buffers,
+) -> Result<Program, ParseErrorStack> {
     let mut stream = TokenStream::new(tokens);
     let mut state = State::new(&mut stream);
+    state.reuse(buffers);
+    state.recovering = recovering;
+    state.max_errors = options.limits.max_errors;
+    state.max_literal_length = options.limits.max_literal_length;
+    state.max_depth = options.limits.max_depth;
+    state.php_version = options.php_version;
+    state.experimental = options.experimental;
+    state.suppressions = Suppressions::new(tokens, options.suppressions);
+    state.require_complete = options.require_complete;
+    state.telemetry = telemetry.cloned();
 
+    let result = construct_statements(&mut state, options.cancellation.as_ref());
+    state.release(buffers);
+
+    result
+}
+
+fn construct_statements(
+    state: &mut State,
+    cancellation: Option<&CancellationToken>,
+) -> Result<Program, ParseErrorStack> {
     let mut program = Program::new();
+    // Whether only opening tags and comments were parsed so far.
+    let mut leading = true;
+
+    while !state.stream.is_eof() && !state.errors_exceeded {
+        if cancellation.is_some_and(|cancellation| cancellation.is_cancelled()) {
+            let mut previous = std::mem::take(&mut state.errors);
+            previous.push(error::cancelled(state.stream.current().span));
+
+            return Err(ParseErrorStack {
+                errors: previous,
+                partial: program,
+            });
+        }
 
-    while !state.stream.is_eof() {
-        let statement = match top_level_statement(&mut state) {
+        state.first_statement = leading;
+
+        let statement = match recovery::statement(state, top_level_statement) {
             Ok(statement) => statement,
             Err(error) => {
-                let mut previous = state.errors;
-                previous.push(error);
+                // Scopes are not exited when an error is propagated, so the stack
+                // still holds the scopes enclosing the error.
+                state.record_unsuppressed(error);
 
                 return Err(ParseErrorStack {
-                    errors: previous,
+                    errors: errors(state),
                     partial: program,
                 });
             }
         };
 
+        leading = leading
+            && matches!(
+                statement,
+                Statement::FullOpeningTag(_)
+                    | Statement::ShortOpeningTag(_)
+                    | Statement::Comment(_)
+            );
         program.push(statement);
     }
 
-    let errors = state.errors;
+    if let Some(suppressions) = state.suppressions.take() {
+        for error in suppressions.unused() {
+            state.record(error);
+        }
+    }
+
+    if let (true, Some((recovery, span))) = (state.require_complete, state.recovery) {
+        if state.errors.is_empty() {
+            state.record_unsuppressed(error::incomplete_syntax_tree(recovery, span));
+        }
+    }
+
+    let errors = errors(state);
     if !errors.is_empty() {
         return Err(ParseErrorStack {
             errors,
@@ -94,10 +492,28 @@ pub fn construct(tokens: &[Token]) -> Result<Program, ParseErrorStack> {
         });
     }
 
-    Ok(program.to_vec())
+    Ok(program)
+}
+
+/// The errors recorded by the given state, followed by a resource limit error when
+/// errors were dropped.
+fn errors(state: &mut State) -> Vec<ParseError> {
+    let mut errors = std::mem::take(&mut state.errors);
+    if let Some(max) = state.max_errors.filter(|_| state.errors_exceeded) {
+        errors.push(error::resource_limit_exceeded(
+            ResourceLimit::ErrorCount,
+            max,
+            state.stream.current().span,
+        ));
+    }
+
+    errors
 }
 
 fn top_level_statement(state: &mut State) -> ParseResult<Statement> {
+    // Only the statement itself may be the first one, not the statements nested within it.
+    let first_statement = std::mem::take(&mut state.first_statement);
+
     let statement = match &state.stream.current().kind {
         TokenKind::Namespace => namespaces::namespace(state)?,
         TokenKind::Use => uses::use_statement(state)?,
@@ -106,7 +522,7 @@ fn top_level_statement(state: &mut State) -> ParseResult<Statement> {
             state.stream.next();
 
             let content = if let TokenKind::InlineHtml = state.stream.current().kind.clone() {
-                let content = state.stream.current().value.clone();
+                let content = state.stream.current().value.to_byte_string();
                 state.stream.next();
                 Some(content)
             } else {
@@ -115,32 +531,41 @@ fn top_level_statement(state: &mut State) -> ParseResult<Statement> {
 
             Statement::HaltCompiler(HaltCompiler { content })
         }
-        _ => statement(state)?,
+        _ => {
+            state.first_statement = first_statement;
+
+            statement(state)?
+        }
     };
 
     Ok(statement)
 }
 
 fn statement(state: &mut State) -> ParseResult<Statement> {
+    state.nested(nested_statement)
+}
+
+fn nested_statement(state: &mut State) -> ParseResult<Statement> {
+    let first_statement = std::mem::take(&mut state.first_statement);
     let has_attributes = attributes::gather_attributes(state)?;
 
     let current = state.stream.current();
     let peek = state.stream.peek();
-    let statement = if has_attributes {
+    if has_attributes {
         match &current.kind {
-            TokenKind::Abstract => classes::parse(state)?,
-            TokenKind::Readonly if peek.kind != TokenKind::LeftParen => classes::parse(state)?,
-            TokenKind::Final => classes::parse(state)?,
-            TokenKind::Class => classes::parse(state)?,
-            TokenKind::Interface => interfaces::parse(state)?,
-            TokenKind::Trait => traits::parse(state)?,
+            TokenKind::Abstract => classes::parse(state),
+            TokenKind::Readonly if peek.kind != TokenKind::LeftParen => classes::parse(state),
+            TokenKind::Final => classes::parse(state),
+            TokenKind::Class => classes::parse(state),
+            TokenKind::Interface => interfaces::parse(state),
+            TokenKind::Trait => traits::parse(state),
             TokenKind::Enum
                 if !matches!(
                     peek.kind,
                     TokenKind::LeftParen | TokenKind::DoubleColon | TokenKind::Colon,
                 ) =>
             {
-                enums::parse(state)?
+                enums::parse(state)
             }
             TokenKind::Function
                 if identifiers::is_identifier_maybe_soft_reserved(&peek.kind)
@@ -156,15 +581,15 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                         }));
                     }
 
-                    functions::function(state)?
+                    functions::function(state)
                 } else {
-                    functions::function(state)?
+                    functions::function(state)
                 }
             }
-            _ => Statement::Expression(ExpressionStatement {
+            _ => Ok(Statement::Expression(ExpressionStatement {
                 expression: expressions::attributes(state, &Precedence::Lowest)?,
                 ending: utils::skip_ending(state)?,
-            }),
+            })),
         }
     } else {
         match &current.kind {
@@ -172,39 +597,39 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                 let span = current.span;
                 state.stream.next();
 
-                Statement::EchoOpeningTag(span)
+                Ok(Statement::EchoOpeningTag(span))
             }
             TokenKind::OpenTag(OpenTagKind::Full) => {
                 let span = current.span;
                 state.stream.next();
 
-                Statement::FullOpeningTag(span)
+                Ok(Statement::FullOpeningTag(span))
             }
             TokenKind::OpenTag(OpenTagKind::Short) => {
                 let span = current.span;
                 state.stream.next();
 
-                Statement::ShortOpeningTag(span)
+                Ok(Statement::ShortOpeningTag(span))
             }
             TokenKind::CloseTag => {
                 let span = current.span;
                 state.stream.next();
 
-                Statement::ClosingTag(span)
+                Ok(Statement::ClosingTag(span))
             }
-            TokenKind::Abstract => classes::parse(state)?,
-            TokenKind::Readonly if peek.kind != TokenKind::LeftParen => classes::parse(state)?,
-            TokenKind::Final => classes::parse(state)?,
-            TokenKind::Class => classes::parse(state)?,
-            TokenKind::Interface => interfaces::parse(state)?,
-            TokenKind::Trait => traits::parse(state)?,
+            TokenKind::Abstract => classes::parse(state),
+            TokenKind::Readonly if peek.kind != TokenKind::LeftParen => classes::parse(state),
+            TokenKind::Final => classes::parse(state),
+            TokenKind::Class => classes::parse(state),
+            TokenKind::Interface => interfaces::parse(state),
+            TokenKind::Trait => traits::parse(state),
             TokenKind::Enum
                 if !matches!(
                     peek.kind,
                     TokenKind::LeftParen | TokenKind::DoubleColon | TokenKind::Colon,
                 ) =>
             {
-                enums::parse(state)?
+                enums::parse(state)
             }
             TokenKind::Function
                 if identifiers::is_identifier_maybe_soft_reserved(&peek.kind)
@@ -220,17 +645,17 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                         }));
                     }
 
-                    functions::function(state)?
+                    functions::function(state)
                 } else {
-                    functions::function(state)?
+                    functions::function(state)
                 }
             }
-            TokenKind::Goto => goto::goto_statement(state)?,
+            TokenKind::Goto => goto::goto_statement(state),
             token
                 if identifiers::is_identifier_maybe_reserved(token)
                     && peek.kind == TokenKind::Colon =>
             {
-                goto::label_statement(state)?
+                goto::label_statement(state)
             }
             TokenKind::Declare => {
                 let span = utils::skip(state, TokenKind::Declare)?;
@@ -243,6 +668,12 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                         let span = utils::skip(state, TokenKind::Equals)?;
                         let value = expect_literal!(state);
 
+                        if key.value.eq_ignore_ascii_case(b"strict_types")
+                            && !matches!(&value, Literal::Integer(integer) if integer.value == b"0" || integer.value == b"1")
+                        {
+                            state.record(error::strict_types_value_must_be_zero_or_one(&value));
+                        }
+
                         entries.push(DeclareEntry {
                             key,
                             equals: span,
@@ -308,11 +739,27 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                     }
                 };
 
-                Statement::Declare(DeclareStatement {
+                let block_mode = !matches!(body, DeclareBody::Noop { .. });
+                for entry in entries.entries.iter() {
+                    let strict_types = entry.key.value.eq_ignore_ascii_case(b"strict_types");
+                    let encoding = entry.key.value.eq_ignore_ascii_case(b"encoding");
+
+                    if (strict_types || encoding) && !first_statement {
+                        state.record(error::declare_must_be_first_statement(span, &entry.key));
+                    }
+
+                    if strict_types && block_mode {
+                        state.record(error::strict_types_must_not_use_block_mode(
+                            span, &entry.key,
+                        ));
+                    }
+                }
+
+                Ok(Statement::Declare(Box::new(DeclareStatement {
                     declare: span,
                     entries,
                     body,
-                })
+                })))
             }
             TokenKind::Global => {
                 let span = current.span;
@@ -331,10 +778,10 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                 }
 
                 utils::skip_semicolon(state)?;
-                Statement::Global(GlobalStatement {
+                Ok(Statement::Global(GlobalStatement {
                     global: span,
                     variables,
-                })
+                }))
             }
             TokenKind::Static if matches!(peek.kind, TokenKind::Variable) => {
                 state.stream.next();
@@ -366,30 +813,40 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
 
                 utils::skip_semicolon(state)?;
 
-                Statement::Static(StaticStatement { vars })
+                Ok(Statement::Static(StaticStatement { vars }))
             }
             TokenKind::InlineHtml => {
-                let html = state.stream.current().value.clone();
+                let html = state.stream.current().value.to_byte_string();
+                state.stream.next();
+
+                Ok(Statement::InlineHtml(html))
+            }
+            TokenKind::ForeignChunk => {
+                let token = state.stream.current();
+                let chunk = ForeignChunk {
+                    span: token.span,
+                    value: token.value.to_byte_string(),
+                };
                 state.stream.next();
 
-                Statement::InlineHtml(html)
+                Ok(Statement::ForeignChunk(chunk))
             }
-            TokenKind::Do => loops::do_while_statement(state)?,
-            TokenKind::While => loops::while_statement(state)?,
-            TokenKind::For => loops::for_statement(state)?,
-            TokenKind::Foreach => loops::foreach_statement(state)?,
-            TokenKind::Continue => loops::continue_statement(state)?,
-            TokenKind::Break => loops::break_statement(state)?,
-            TokenKind::Switch => control_flow::switch_statement(state)?,
-            TokenKind::If => control_flow::if_statement(state)?,
-            TokenKind::Try => try_block::try_block(state)?,
-            TokenKind::LeftBrace => blocks::block_statement(state)?,
+            TokenKind::Do => loops::do_while_statement(state),
+            TokenKind::While => loops::while_statement(state),
+            TokenKind::For => loops::for_statement(state),
+            TokenKind::Foreach => loops::foreach_statement(state),
+            TokenKind::Continue => loops::continue_statement(state),
+            TokenKind::Break => loops::break_statement(state),
+            TokenKind::Switch => control_flow::switch_statement(state),
+            TokenKind::If => control_flow::if_statement(state),
+            TokenKind::Try => try_block::try_block(state),
+            TokenKind::LeftBrace => blocks::block_statement(state),
             TokenKind::SemiColon => {
                 let start = current.span;
 
                 state.stream.next();
 
-                Statement::Noop(start)
+                Ok(Statement::Noop(start))
             }
             TokenKind::Echo => {
                 state.stream.next();
@@ -405,11 +862,11 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                     }
                 }
 
-                Statement::Echo(EchoStatement {
+                Ok(Statement::Echo(EchoStatement {
                     echo: current.span,
                     values,
                     ending: utils::skip_ending(state)?,
-                })
+                }))
             }
             TokenKind::Return => {
                 state.stream.next();
@@ -423,18 +880,556 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                     expressions::create(state).map(Some)?
                 };
 
-                Statement::Return(ReturnStatement {
+                Ok(Statement::Return(Box::new(ReturnStatement {
                     r#return: current.span,
                     value,
                     ending: utils::skip_ending(state)?,
-                })
+                })))
             }
-            _ => Statement::Expression(ExpressionStatement {
+            _ => Ok(Statement::Expression(ExpressionStatement {
                 expression: expressions::create(state)?,
                 ending: utils::skip_ending(state)?,
-            }),
+            })),
         }
-    };
+    }
+}
 
-    Ok(statement)
+#[cfg(test)]
+mod tests {
+    use super::parse_all;
+    use super::parse_class_member;
+    use super::parse_docblock_type;
+    use super::parse_method_body;
+    use super::parse_template;
+    use super::parse_tolerant;
+    use super::parse_type;
+    use crate::parser::ast::classes::ClassMember;
+    use crate::parser::ast::data_type::Type;
+    use crate::parser::ast::namespaces::NamespaceStatement;
+    use crate::parser::ast::operators::AssignmentOperation;
+    use crate::parser::ast::Ending;
+    use crate::parser::ast::Expression;
+    use crate::parser::ast::ExpressionStatement;
+    use crate::parser::ast::Statement;
+    use crate::parser::error::ErrorScope;
+
+    #[test]
+    fn test_template_foreign_chunks() {
+        let program = parse_template(
+            "<p>{{ '<?php' }}</p><% if x %><?php echo $a; ?>{{ unterminated",
+            &[("{{", "}}"), ("<%", "%>")],
+        )
+        .unwrap();
+
+        let chunks = program
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::ForeignChunk(chunk) => Some(chunk.value.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            chunks,
+            vec!["{{ '<?php' }}", "<% if x %>", "{{ unterminated"]
+        );
+        assert!(matches!(&program[0], Statement::InlineHtml(html) if html == b"<p>"));
+        assert!(matches!(&program[2], Statement::InlineHtml(html) if html == b"</p>"));
+        assert!(matches!(program[4], Statement::FullOpeningTag(_)));
+        assert!(crate::parse("<p>{{ '<?php' }}</p>").is_err());
+    }
+
+    #[test]
+    fn test_class_member_snippets() {
+        let member = parse_class_member(
+            "#[Pure]\npublic function name(): string { return parent::name(); }",
+        )
+        .unwrap();
+        match member {
+            ClassMember::ConcreteMethod(method) => {
+                assert_eq!(method.name.value, b"name");
+                assert_eq!(method.function.line, 2);
+                assert_eq!(method.function.column, 8);
+                assert_eq!(method.attributes.len(), 1);
+            }
+            member => panic!("unexpected member {:?}", member),
+        }
+
+        assert!(matches!(
+            parse_class_member("abstract protected function handle(): void;"),
+            Ok(ClassMember::AbstractMethod(_))
+        ));
+        assert!(matches!(
+            parse_class_member("private const A = 1, B = 2;"),
+            Ok(ClassMember::Constant(_))
+        ));
+        assert!(matches!(
+            parse_class_member("use A, B { A::foo insteadof B; }"),
+            Ok(ClassMember::TraitUsage(_))
+        ));
+
+        let stack = parse_class_member("public $a; public $b;").unwrap_err();
+        assert_eq!(stack.errors[0].span.position, 11);
+        assert!(parse_class_member("<?php public $a;").is_err());
+    }
+
+    #[test]
+    fn test_method_body_snippets() {
+        let statements =
+            parse_method_body("$name = $this->name;\nreturn static::format($name);").unwrap();
+        assert_eq!(statements.len(), 2);
+        assert!(matches!(statements[1], Statement::Return(_)));
+        assert!(parse_method_body("").unwrap().is_empty());
+
+        let stack = parse_method_body("echo 1; echo").unwrap_err();
+        assert_eq!(stack.partial.len(), 1);
+    }
+
+    #[test]
+    fn test_type_snippets() {
+        for (input, expected) in [
+            ("int", "int"),
+            ("?\\Foo\\Bar", "?\\Foo\\Bar"),
+            ("int|string|null", "int|string|null"),
+            ("(A&B)|null", "(A&B)|null"),
+            ("C|(A&B)|(A&D)", "C|(A&B)|(A&D)"),
+            ("static", "static"),
+        ] {
+            assert_eq!(parse_type(input).unwrap().to_string(), expected);
+        }
+
+        match parse_type("  Foo|false").unwrap() {
+            Type::Union(types) => assert_eq!(types[0].first_span().position, 2),
+            ty => panic!("unexpected type {:?}", ty),
+        }
+
+        assert_eq!(parse_type("?void").unwrap_err().errors[0].id, "E009");
+        assert_eq!(parse_type("A|B|a").unwrap_err().errors[0].id, "E074");
+        assert_eq!(parse_type("(A&B)|(B&A)").unwrap_err().errors[0].id, "E074");
+        assert_eq!(parse_type("(A&B)|B").unwrap_err().errors[0].id, "E075");
+        assert!(parse_type("(A)|B").is_err());
+        assert!(parse_type("(A&B)").is_err());
+        assert!(parse_type("int|").is_err());
+        assert!(parse_type("int string").is_err());
+        assert!(parse_type("array<int>").is_err());
+        assert!(parse_type("").is_err());
+    }
+
+    #[test]
+    fn test_docblock_type_snippets() {
+        for (input, expected) in [
+            ("?array<int, Foo>|null", "?array|null"),
+            ("Foo[][]|list<array<string, list<int>>>", "array|array"),
+            ("class-string<Foo>|non-empty-string", "string|string"),
+            ("array{0: int, name?: string}", "array"),
+            (
+                "int<0, max>|positive-int|-1|1.5|'a'",
+                "int|int|int|float|string",
+            ),
+            ("callable(int, string): void", "callable"),
+            ("Closure(int): (A&B)", "Closure"),
+            ("array-key", "int|string"),
+            (
+                "key-of<T>|resource|Foo::BAR|Foo::*",
+                "mixed|mixed|mixed|mixed",
+            ),
+            ("$this|static|self", "static|static|self"),
+            ("Collection<int, \\Foo>&Countable", "Collection&Countable"),
+        ] {
+            assert_eq!(
+                parse_docblock_type(input).unwrap().to_string(),
+                expected,
+                "docblock type `{}`",
+                input
+            );
+        }
+
+        assert!(parse_docblock_type("array<int").is_err());
+        assert!(parse_docblock_type("array{int").is_err());
+        assert!(parse_docblock_type("Foo Bar").is_err());
+    }
+
+    #[test]
+    fn test_error_scopes() {
+        let stack =
+            crate::parse("<?php namespace App; class User { public function save() { $a = ; } }")
+                .unwrap_err();
+        assert_eq!(
+            stack.errors[0].scopes,
+            vec![
+                ErrorScope::Namespace {
+                    name: Some("App".to_string())
+                },
+                ErrorScope::Class {
+                    name: Some("User".to_string())
+                },
+                ErrorScope::Method {
+                    name: "save".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            stack.errors[0].symbol(),
+            Some("App\\User::save".to_string())
+        );
+
+        // Errors recorded without stopping the parser.
+        let stack = crate::parse("<?php class A { public static function b() { return $this; } }")
+            .unwrap_err();
+        assert_eq!(stack.errors[0].symbol(), Some("A::b".to_string()));
+
+        let stack = crate::parse(
+            "<?php namespace A { function f() { $x = function () { return 1 + ; }; } }",
+        )
+        .unwrap_err();
+        assert_eq!(stack.errors[0].scopes.last(), Some(&ErrorScope::Closure));
+        assert_eq!(stack.errors[0].symbol(), Some("A\\f".to_string()));
+
+        let stack = crate::parse("<?php echo ;").unwrap_err();
+        assert!(stack.errors[0].scopes.is_empty());
+        assert_eq!(stack.errors[0].symbol(), None);
+    }
+
+    #[test]
+    fn test_class_body_recovery() {
+        let code = "<?php
+            class A {
+                public function a() { if ($a) { $a = ; } return 1; }
+                public function b() {}
+                public $c = ;
+                const D = 1;
+            }
+
+            enum E { case A; case = ; case B; }
+
+            interface F { public function g() }
+        ";
+
+        assert_eq!(crate::parse(code).unwrap_err().errors.len(), 1);
+
+        let stack = parse_tolerant(code).unwrap_err();
+        let lines = stack
+            .errors
+            .iter()
+            .map(|error| error.span.line)
+            .collect::<Vec<usize>>();
+        assert_eq!(lines, vec![3, 5, 9, 11]);
+        assert_eq!(stack.errors[0].symbol(), Some("A::a".to_string()));
+
+        let members = stack
+            .partial
+            .iter()
+            .map(|statement| match statement {
+                Statement::Class(class) => class.body.members.len(),
+                Statement::UnitEnum(r#enum) => r#enum.body.members.len(),
+                Statement::Interface(interface) => interface.body.members.len(),
+                _ => 0,
+            })
+            .collect::<Vec<usize>>();
+        // The opening tag, and the members of `A`, `E`, and `F`, the missing default value of
+        // `A::$c` being replaced by a placeholder.
+        assert_eq!(members, vec![0, 4, 2, 0]);
+
+        // A body which is never closed can not be recovered.
+        let stack = parse_tolerant("<?php class A { public function a() { $a = ; }").unwrap_err();
+        assert_eq!(stack.errors.len(), 2);
+    }
+
+    #[test]
+    fn test_statement_recovery() {
+        let code = "<?php
+$a = 1 2;
+echo 1;
+if ($a 1) {
+    foo();
+}
+function b() {
+    $c = 1 2;
+    return $c;
+}
+switch ($a) {
+    case 1:
+        $d = 1 2;
+    case 2:
+        break;
+}
+namespace C {
+    $e = 1 2;
+    echo 2;
+}
+";
+
+        assert_eq!(crate::parse(code).unwrap_err().errors.len(), 1);
+
+        let stack = parse_tolerant(code).unwrap_err();
+        let lines = stack
+            .errors
+            .iter()
+            .map(|error| error.span.line)
+            .collect::<Vec<usize>>();
+        assert_eq!(lines, vec![2, 4, 8, 13, 18]);
+
+        let statements = stack
+            .partial
+            .iter()
+            .map(|statement| match statement {
+                Statement::Echo(_) => "echo",
+                Statement::Error(_) => "error",
+                Statement::Function(function) => {
+                    assert!(matches!(
+                        function.body.statements[..],
+                        [Statement::Error(_), Statement::Return(_)]
+                    ));
+
+                    "function"
+                }
+                Statement::Switch(switch) => {
+                    assert!(matches!(switch.cases[0].body[..], [Statement::Error(_)]));
+
+                    "switch"
+                }
+                Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                    assert!(matches!(
+                        namespace.body.statements[..],
+                        [Statement::Error(_), Statement::Echo(_)]
+                    ));
+
+                    "namespace"
+                }
+                _ => "other",
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statements,
+            vec![
+                "other",
+                "error",
+                "echo",
+                "error",
+                "function",
+                "switch",
+                "namespace"
+            ]
+        );
+
+        match &stack.partial[1] {
+            Statement::Error(statement) => {
+                let tokens = statement
+                    .tokens
+                    .iter()
+                    .map(|token| token.value.to_string())
+                    .collect::<Vec<_>>();
+                assert_eq!(tokens, vec!["$a", "=", "1", "2", ";"]);
+                assert_eq!((statement.span.line, statement.span.column), (2, 1));
+            }
+            statement => panic!("unexpected statement: {statement:?}"),
+        }
+
+        // The `if` statement is skipped up to the `}` closing its body.
+        match &stack.partial[3] {
+            Statement::Error(statement) => {
+                assert_eq!(statement.tokens.len(), 11);
+            }
+            statement => panic!("unexpected statement: {statement:?}"),
+        }
+
+        // Tokens are skipped up to the next `;`, even when they look like a statement.
+        let stack = parse_tolerant("<?php ) echo 1; echo 2;").unwrap_err();
+        assert_eq!(stack.errors.len(), 1);
+        assert_eq!(stack.partial.len(), 3);
+        assert!(matches!(stack.partial[2], Statement::Echo(_)));
+    }
+
+    #[test]
+    fn test_missing_expression_recovery() {
+        let stack = parse_tolerant("<?php $a = ; if ($a == ) {}").unwrap_err();
+        let errors = stack
+            .errors
+            .iter()
+            .map(|error| (error.id.as_str(), error.span.column))
+            .collect::<Vec<_>>();
+        assert_eq!(errors, vec![("E003", 12), ("E003", 24)]);
+
+        match &stack.partial[1] {
+            Statement::Expression(ExpressionStatement {
+                expression:
+                    Expression::AssignmentOperation(AssignmentOperation::Assign { right, .. }),
+                ..
+            }) => match right.as_ref() {
+                Expression::Missing { span } => {
+                    assert!(span.is_synthetic());
+                    assert_eq!((span.line, span.column, span.position), (1, 12, 11));
+                }
+                expression => panic!("unexpected expression: {expression:?}"),
+            },
+            statement => panic!("unexpected statement: {statement:?}"),
+        }
+        assert!(matches!(stack.partial[2], Statement::If(_)));
+
+        // A missing expression is an error when not recovering.
+        assert!(crate::parse("<?php $a = ;").is_err());
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let code = "<?php
+function a(): ?void {}
+$b = ;
+echo 1;
+";
+
+        let parse = parse_all(code);
+        let errors = parse
+            .errors
+            .iter()
+            .map(|error| (error.id.as_str(), error.span.line))
+            .collect::<Vec<_>>();
+        assert_eq!(errors, vec![("E009", 2), ("E003", 3)]);
+        assert!(parse.has_errors());
+        assert_eq!(parse.ast.len(), 4);
+        assert!(matches!(parse.ast[3], Statement::Echo(_)));
+
+        let parse = parse_all("<?php echo 1;");
+        assert!(!parse.has_errors());
+        assert_eq!(parse.into_result(), crate::parse("<?php echo 1;"));
+
+        let parse = parse_all("<?php $a = 'b");
+        assert_eq!(parse.errors.len(), 1);
+        assert!(parse.ast.is_empty());
+        assert!(parse.into_result().is_err());
+    }
+
+    #[test]
+    fn test_missing_semicolon_recovery() {
+        let code = "<?php
+$a = 1 // one
+$b = foo($a)
+if ($a) {
+    echo $b
+}
+class A {
+    const B = 1
+    public $c = [1, 2];
+}
+$c = 1 + 2 3;
+";
+
+        assert_eq!(crate::parse(code).unwrap_err().errors.len(), 1);
+
+        let stack = parse_tolerant(code).unwrap_err();
+        let errors = stack
+            .errors
+            .iter()
+            .map(|error| (error.id.as_str(), error.span.line, error.span.column))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                ("E066", 2, 7),
+                ("E066", 3, 13),
+                ("E066", 5, 12),
+                ("E066", 8, 16),
+                ("E005", 11, 12),
+            ]
+        );
+        assert_eq!(stack.partial.len(), 6);
+        assert!(matches!(stack.partial[5], Statement::Error(_)));
+
+        match &stack.partial[1] {
+            Statement::Expression(ExpressionStatement {
+                ending: Ending::Semicolon(span),
+                ..
+            }) => {
+                assert!(span.is_synthetic());
+                assert_eq!((span.line, span.column, span.position), (2, 7, 12));
+            }
+            statement => panic!("unexpected statement: {statement:?}"),
+        }
+        assert!(stack.errors[0].span.is_synthetic());
+    }
+
+    #[test]
+    fn test_long_concatenations_are_chunked() {
+        fn depth(expression: &Expression) -> usize {
+            match expression {
+                Expression::Concat { left, right, .. } => 1 + depth(left).max(depth(right)),
+                _ => 0,
+            }
+        }
+
+        fn operands(expression: &Expression, values: &mut Vec<String>) {
+            match expression {
+                Expression::Concat { left, right, .. } => {
+                    operands(left, values);
+                    operands(right, values);
+                }
+                Expression::Literal(literal) => values.push(literal.value().to_string()),
+                expression => panic!("unexpected expression: {expression:?}"),
+            }
+        }
+
+        let values = (0..3000).map(|i| format!("'{}'", i)).collect::<Vec<_>>();
+        let code = format!("<?php $a = {};", values.join(" . "));
+        let options = crate::ParserOptions::new()
+            .with_limits(crate::ResourceLimits::new().with_max_depth(64));
+
+        for (program, chunked) in [
+            (crate::parse_with_options(&code, &options).unwrap(), true),
+            (crate::parse(&code).unwrap(), false),
+        ] {
+            match &program[1] {
+                Statement::Expression(ExpressionStatement {
+                    expression:
+                        Expression::AssignmentOperation(AssignmentOperation::Assign { right, .. }),
+                    ..
+                }) => {
+                    if chunked {
+                        assert!(depth(right) < 2 * super::expressions::CONCATENATION_CHUNK);
+                    } else {
+                        // The grouping written is kept without a maximum depth.
+                        assert_eq!(depth(right), values.len() - 1);
+                    }
+
+                    let mut found = vec![];
+                    operands(right, &mut found);
+                    assert_eq!(found, values);
+                }
+                statement => panic!("unexpected statement: {statement:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_untrusted_input() {
+        // Inputs which used to panic, or loop forever when recovering.
+        let inputs: &[&[u8]] = &[
+            b"<?php <<<EOF",
+            b"<?php <<<'EOF",
+            b"<?php \\readonly",
+            b"<?php empty(emexitpty$a));",
+            b"<?php eval",
+            b"<?php a b;\n}",
+            b"<?php \"$a->\xff\"",
+            b"<?php \xff\xfe",
+        ];
+
+        for input in inputs {
+            assert!(crate::parse_untrusted(input).is_err());
+            assert!(parse_tolerant(input).is_err());
+            assert!(
+                crate::parse_tolerant_with_options(input, &crate::ParserOptions::untrusted())
+                    .is_err()
+            );
+            assert!(parse_all(input).has_errors());
+        }
+
+        assert!(crate::parse_untrusted("<?php echo 1;").is_ok());
+
+        let input = format!("<?php {}", " ".repeat(9 * 1024 * 1024));
+        let stack = crate::parse_untrusted(&input).unwrap_err();
+        assert_eq!(
+            stack.errors[0].resource_limit(),
+            Some(crate::parser::limits::ResourceLimit::InputSize)
+        );
+    }
 }
@@ -0,0 +1,342 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::parser::cancellation::CancellationToken;
+use crate::parser::limits::ResourceLimits;
+use crate::parser::suppressions::SuppressionMode;
+use crate::parser::telemetry::Telemetry;
+use crate::parser::telemetry::UnknownConstruct;
+
+/// The options of a parse, see [`crate::parse_with_options`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ParserOptions {
+    /// The version of PHP the code targets, the syntax of every version is accepted when
+    /// there is none.
+    ///
+    /// Syntax introduced after the target version is reported as an unsupported feature
+    /// error (`E071`), and syntax removed in or before it as a removed feature error (`E072`).
+    pub php_version: Option<PhpVersion>,
+    /// Whether to accept experimental syntax, which is not part of a released version of PHP
+    /// yet, such as the pipe operator (`|>`).
+    ///
+    /// Experimental syntax is reported as an experimental feature error (`E081`) otherwise.
+    pub experimental: bool,
+    /// Whether to apply the suppression comments of the input, such as
+    /// `// @parser-ignore-next-line`, see [`crate::parser::suppressions::Suppression`].
+    pub suppressions: SuppressionMode,
+    /// Whether to guarantee that the program holds no node guessed by error recovery, such
+    /// as an error statement, e.g. when suppression comments hide the errors of
+    /// [`crate::parse_all_with_options`].
+    ///
+    /// Such a program is reported as an incomplete syntax tree error (`E083`), which can't be
+    /// suppressed, unless other errors are reported.
+    pub require_complete: bool,
+    /// The hook to report every unexpected token error to, along with the surrounding tokens
+    /// and declarations, including the errors recovered from by [`crate::parse_all_with_options`].
+    pub telemetry: Option<Telemetry>,
+    /// The limits guarding against adversarial inputs, see [`ResourceLimits`].
+    pub limits: ResourceLimits,
+    /// The token cancelling the parse with a cancellation error (`E065`), checked between
+    /// top-level statements.
+    pub cancellation: Option<CancellationToken>,
+}
+
+impl ParserOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The options of [`crate::parse_untrusted`], accepting the syntax of every version
+    /// within the limits of [`ResourceLimits::untrusted`].
+    pub fn untrusted() -> Self {
+        Self::new().with_limits(ResourceLimits::untrusted())
+    }
+
+    pub fn with_php_version(mut self, php_version: PhpVersion) -> Self {
+        self.php_version = Some(php_version);
+
+        self
+    }
+
+    pub fn with_experimental(mut self, experimental: bool) -> Self {
+        self.experimental = experimental;
+
+        self
+    }
+
+    pub fn with_suppressions(mut self, suppressions: SuppressionMode) -> Self {
+        self.suppressions = suppressions;
+
+        self
+    }
+
+    pub fn with_require_complete(mut self, require_complete: bool) -> Self {
+        self.require_complete = require_complete;
+
+        self
+    }
+
+    /// Report every unexpected token error of each parse to the given hook, e.g. to aggregate
+    /// the constructs failing to parse across a corpus of real-world code.
+    pub fn with_telemetry(
+        mut self,
+        hook: impl for<'a> Fn(&UnknownConstruct<'a>) + Send + Sync + 'static,
+    ) -> Self {
+        self.telemetry = Some(Telemetry::new(hook));
+
+        self
+    }
+
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+
+        self
+    }
+
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = Some(cancellation);
+
+        self
+    }
+}
+
+/// A version of PHP, the latest one by default.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PhpVersion {
+    Php70,
+    Php71,
+    Php72,
+    Php73,
+    Php74,
+    Php80,
+    Php81,
+    Php82,
+    Php83,
+    #[default]
+    Php84,
+}
+
+impl PhpVersion {
+    pub const ALL: [PhpVersion; 10] = [
+        Self::Php70,
+        Self::Php71,
+        Self::Php72,
+        Self::Php73,
+        Self::Php74,
+        Self::Php80,
+        Self::Php81,
+        Self::Php82,
+        Self::Php83,
+        Self::Php84,
+    ];
+
+    /// The version as written in a `composer.json` constraint, e.g. `8.1`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Php70 => "7.0",
+            Self::Php71 => "7.1",
+            Self::Php72 => "7.2",
+            Self::Php73 => "7.3",
+            Self::Php74 => "7.4",
+            Self::Php80 => "8.0",
+            Self::Php81 => "8.1",
+            Self::Php82 => "8.2",
+            Self::Php83 => "8.3",
+            Self::Php84 => "8.4",
+        }
+    }
+}
+
+impl Display for PhpVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for PhpVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|version| version.as_str() == s)
+            .ok_or_else(|| format!("unknown PHP version `{}`", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParserOptions;
+    use super::PhpVersion;
+    use crate::parser::ast::operators::AssignmentOperation;
+    use crate::parser::ast::Expression;
+    use crate::parser::ast::Statement;
+
+    fn errors(code: &str, php_version: PhpVersion) -> Vec<String> {
+        let options = ParserOptions::new().with_php_version(php_version);
+
+        match crate::parse_with_options(code, &options) {
+            Ok(_) => vec![],
+            Err(stack) => stack
+                .errors
+                .iter()
+                .map(|error| format!("{} {}", error.id, error.message))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_php_version() {
+        let code = r#"<?php
+#[Attribute]
+final readonly class User
+{
+    final public const ADMIN = 'admin';
+
+    public function __construct(private readonly int $id, public ?string $name = null) {}
+
+    public function name(): string|false
+    {
+        return $this?->name ?? match ($this->id) { 1 => 'root', default => false };
+    }
+}
+
+enum Suit { case Hearts; }
+
+$names ??= array_map(fn(User $user): mixed => $user->name(...), []);
+"#;
+
+        assert_eq!(errors(code, PhpVersion::Php84), Vec::<String>::new());
+        assert_eq!(
+            errors(code, PhpVersion::Php81),
+            vec!["E071 using readonly classes requires PHP 8.2 or later"]
+        );
+        assert_eq!(
+            errors(code, PhpVersion::Php74),
+            vec![
+                "E071 using attributes requires PHP 8.0 or later",
+                "E071 using readonly classes requires PHP 8.2 or later",
+                "E071 using final class constants requires PHP 8.1 or later",
+                "E071 using constructor property promotion requires PHP 8.0 or later",
+                "E071 using readonly properties requires PHP 8.1 or later",
+                "E071 using constructor property promotion requires PHP 8.0 or later",
+                "E071 using union types requires PHP 8.0 or later",
+                "E071 using the nullsafe operator requires PHP 8.0 or later",
+                "E071 using match expressions requires PHP 8.0 or later",
+                "E071 using enums requires PHP 8.1 or later",
+                "E071 using the `mixed` type requires PHP 8.0 or later",
+                "E071 using first-class callable syntax requires PHP 8.1 or later",
+            ]
+        );
+
+        let code = "<?php
+class A { public const B = 1; public ?int $c; public function d(): void {} }
+$e ??= fn() => (unset) $e;
+";
+        assert_eq!(errors(code, PhpVersion::Php74), Vec::<String>::new());
+        assert_eq!(
+            errors(code, PhpVersion::Php70),
+            vec![
+                "E071 using class constant visibility requires PHP 7.1 or later",
+                "E071 using nullable types requires PHP 7.1 or later",
+                "E071 using typed properties requires PHP 7.4 or later",
+                "E071 using the `void` type requires PHP 7.1 or later",
+                "E071 using arrow functions requires PHP 7.4 or later",
+                "E071 using the null coalescing assignment operator requires PHP 7.4 or later",
+            ]
+        );
+        assert_eq!(
+            errors(code, PhpVersion::Php80),
+            vec!["E072 the `(unset)` cast was removed in PHP 8.0"]
+        );
+        assert!(crate::parse(code).is_ok());
+
+        let code =
+            "<?php $a = new readonly class { public function __construct(public int $b) {} };";
+        assert_eq!(errors(code, PhpVersion::Php83), Vec::<String>::new());
+        assert_eq!(
+            errors(code, PhpVersion::Php82),
+            vec!["E071 using readonly anonymous classes requires PHP 8.3 or later"]
+        );
+
+        let code = "<?php interface A { const string B = 'b'; const C = 'c'; }";
+        assert_eq!(errors(code, PhpVersion::Php83), Vec::<String>::new());
+        assert_eq!(
+            errors(code, PhpVersion::Php82),
+            vec!["E071 using typed class constants requires PHP 8.3 or later"]
+        );
+
+        let code = "<?php Foo::{$a}; Foo::{$a}(); Foo::$a;";
+        assert_eq!(errors(code, PhpVersion::Php83), Vec::<String>::new());
+        assert_eq!(
+            errors(code, PhpVersion::Php82),
+            vec!["E071 using dynamic class constant fetch requires PHP 8.3 or later"]
+        );
+
+        let code =
+            "<?php abstract class A { abstract public int $b { get; } final public int $c; }";
+        assert_eq!(errors(code, PhpVersion::Php84), Vec::<String>::new());
+        assert_eq!(
+            errors(code, PhpVersion::Php83),
+            vec![
+                "E071 using abstract properties requires PHP 8.4 or later",
+                "E071 using property hooks requires PHP 8.4 or later",
+                "E071 using final properties requires PHP 8.4 or later",
+            ]
+        );
+
+        let code = "<?php class A { public private(set) int $b; function __construct(protected(set) int $c) {} }";
+        assert_eq!(errors(code, PhpVersion::Php84), Vec::<String>::new());
+        assert_eq!(
+            errors(code, PhpVersion::Php83),
+            vec![
+                "E071 using asymmetric visibility requires PHP 8.4 or later",
+                "E071 using asymmetric visibility requires PHP 8.4 or later",
+            ]
+        );
+
+        let code = "<?php new Foo()->bar(); new class {}::BAZ; (new Foo)->bar(); new $a->b();";
+        assert_eq!(errors(code, PhpVersion::Php84), Vec::<String>::new());
+        assert_eq!(
+            errors(code, PhpVersion::Php83),
+            vec![
+                "E071 using member access on new expressions without parentheses requires PHP 8.4 or later",
+                "E071 using member access on new expressions without parentheses requires PHP 8.4 or later",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_experimental() {
+        let code = "<?php $a = $b |> trim(...) |> strlen(...);";
+        let options = ParserOptions::new().with_experimental(true);
+        let program = crate::parse_with_options(code, &options).unwrap();
+
+        match &program[1] {
+            Statement::Expression(statement) => match &statement.expression {
+                Expression::AssignmentOperation(AssignmentOperation::Assign { right, .. }) => {
+                    assert!(matches!(right.as_ref(), Expression::Pipe { left, .. }
+                        if matches!(left.as_ref(), Expression::Pipe { .. })));
+                }
+                expression => panic!("expected an assignment, found {:?}", expression),
+            },
+            statement => panic!("expected an expression statement, found {:?}", statement),
+        }
+        assert_eq!(
+            errors(code, PhpVersion::Php84),
+            vec![
+                "E081 using the pipe operator requires enabling experimental syntax",
+                "E081 using the pipe operator requires enabling experimental syntax",
+            ]
+        );
+        assert!(crate::parse(code).is_err());
+    }
+
+    #[test]
+    fn test_php_version_from_str() {
+        assert_eq!("8.1".parse(), Ok(PhpVersion::Php81));
+        assert_eq!(PhpVersion::Php74.to_string(), "7.4");
+        assert!("5.6".parse::<PhpVersion>().is_err());
+        assert_eq!(PhpVersion::default(), PhpVersion::Php84);
+    }
+}
@@ -0,0 +1,202 @@
+use std::mem;
+
+use crate::lexer::token::Token;
+use crate::parser::ast::Program;
+use crate::parser::construct_reusing;
+use crate::parser::error::ParseErrorStack;
+use crate::parser::lexer;
+use crate::parser::options::ParserOptions;
+use crate::parser::state::Buffers;
+use crate::parser::telemetry::Telemetry;
+use crate::parser::telemetry::UnknownConstruct;
+
+/// A parser which retains its internal buffers across parses, e.g. when parsing every file
+/// of a project, so their capacity is allocated once rather than once per file.
+///
+/// The token buffer, the scope stack, and the tables of imported and declared names are
+/// cleared after each parse. A parse never observes the state of a previous one, and produces
+/// the same result as [`crate::parse`] or [`crate::parse_with_options`].
+///
+/// ```
+/// use php_parser_rs::Parser;
+///
+/// let mut parser = Parser::new();
+///
+/// assert!(parser.parse("<?php use Foo\\Bar; echo 1;").is_ok());
+/// assert!(parser.parse("<?php use Foo\\Bar; echo 2;").is_ok());
+/// ```
+#[derive(Debug, Default)]
+pub struct Parser {
+    // Always empty, only its capacity is kept across parses.
+    tokens: Vec<Token<'static>>,
+    // The number of tokens of the last parsed input.
+    token_count: usize,
+    buffers: Buffers,
+    options: ParserOptions,
+    telemetry: Option<Telemetry>,
+}
+
+impl Parser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+
+        self
+    }
+
+    /// The options of the next parses, e.g. to give each parse its own cancellation token.
+    pub fn options_mut(&mut self) -> &mut ParserOptions {
+        &mut self.options
+    }
+
+    /// Report every unexpected token error of each parse to the given hook, as
+    /// [`ParserOptions::with_telemetry`] does, in place of the hook of the options.
+    pub fn with_telemetry(
+        mut self,
+        hook: impl for<'a> Fn(&UnknownConstruct<'a>) + Send + Sync + 'static,
+    ) -> Self {
+        self.telemetry = Some(Telemetry::new(hook));
+
+        self
+    }
+
+    pub fn parse<B: ?Sized + AsRef<[u8]>>(
+        &mut self,
+        input: &B,
+    ) -> Result<Program, ParseErrorStack> {
+        let lexer = match lexer(input.as_ref(), &self.options.limits) {
+            Ok(lexer) => lexer,
+            Err(stack) => {
+                self.token_count = 0;
+
+                return Err(stack);
+            }
+        };
+        let mut tokens = recycle(mem::take(&mut self.tokens));
+
+        let result = match lexer.tokenize_into(input, &mut tokens) {
+            Ok(()) => construct_reusing(
+                &tokens,
+                false,
+                &self.options,
+                self.telemetry.as_ref().or(self.options.telemetry.as_ref()),
+                &mut self.buffers,
+            ),
+            Err(error) => {
+                tokens.clear();
+
+                Err(ParseErrorStack {
+                    errors: vec![error.into()],
+                    partial: Vec::new(),
+                })
+            }
+        };
+
+        self.token_count = tokens.len();
+        self.tokens = recycle(tokens);
+
+        result
+    }
+
+    /// The number of tokens of the last parsed input, which is zero if it could not be
+    /// tokenized.
+    pub fn token_count(&self) -> usize {
+        self.token_count
+    }
+}
+
+/// Empty the buffer, and reuse its allocation for tokens borrowing from another input.
+fn recycle<'a>(mut tokens: Vec<Token<'_>>) -> Vec<Token<'a>> {
+    tokens.clear();
+
+    // Collecting an iterator over a vector into a vector of the same layout reuses its
+    // allocation, and the iterator is empty.
+    tokens.into_iter().map(|_| unreachable!()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Parser;
+    use crate::ParserOptions;
+    use crate::PhpVersion;
+
+    #[test]
+    fn test_reuse_matches_parse() {
+        let inputs = [
+            "<?php namespace A; use Foo\\Bar; class Baz { function qux() { return Bar::create(); } }",
+            "<?php use Foo\\Bar; function bar() {}",
+            "<?php class Foo { function bar() { if ( } }",
+            "<?php use Foo\\Bar; use Baz\\Bar;",
+            "<?php use Foo\\Bar; echo parent::class;",
+            "<?php echo 'unterminated",
+            "<?php echo 1;",
+        ];
+
+        let mut parser = Parser::new();
+        for input in inputs {
+            assert_eq!(parser.parse(input), crate::parse(input), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_reuse_with_options() {
+        let options = ParserOptions::new().with_php_version(PhpVersion::Php80);
+        let mut parser = Parser::new().with_options(options.clone());
+
+        for input in ["<?php enum Suit {}", "<?php echo 1;", "<?php enum Suit {}"] {
+            assert_eq!(
+                parser.parse(input),
+                crate::parse_with_options(input, &options)
+            );
+        }
+    }
+
+    #[test]
+    fn test_tokens() {
+        let mut parser = Parser::new();
+
+        parser.parse("<?php echo 1, 2, 3;").unwrap();
+        assert_eq!(parser.token_count(), 9);
+        let capacity = parser.tokens.capacity();
+
+        parser.parse("<?php echo 1;").unwrap();
+        assert_eq!(parser.token_count(), 5);
+        assert_eq!(parser.tokens.capacity(), capacity);
+
+        parser.parse("<?php echo 'unterminated").unwrap_err();
+        assert_eq!(parser.token_count(), 0);
+    }
+
+    #[test]
+    fn test_telemetry() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut parser = Parser::new().with_telemetry(move |construct| {
+            sender
+                .send((
+                    construct.shape(),
+                    construct.token().span.line,
+                    construct.error.symbol(),
+                ))
+                .unwrap();
+        });
+
+        parser.parse("<?php echo 1;").unwrap();
+        // Errors other than unexpected tokens are not reported.
+        parser.parse("<?php function a(): ?void {}").unwrap_err();
+        parser
+            .parse("<?php\nnamespace App;\nclass User {\n    function save() { $a = ?; }\n}\n")
+            .unwrap_err();
+
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            vec![(
+                "{ function Identifier ( ) { Variable = >?< ; } } [end of file]".to_string(),
+                4,
+                Some("App\\User::save".to_string())
+            )]
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use crate::lexer::line_index::LineIndex;
+use crate::lexer::token::Span;
+
+/// Translates spans of a generated PHP source back to the source it was generated from,
+/// e.g. a template compiled to PHP.
+pub trait SpanMapper {
+    /// The span in the original source corresponding to the given span of the generated
+    /// source, or `None` if the span has no corresponding location.
+    fn map_span(&self, span: Span) -> Option<Span>;
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Segment {
+    generated: usize,
+    original: usize,
+    length: usize,
+}
+
+/// A [`SpanMapper`] built from segments of the generated source copied from the original source.
+///
+/// Positions between two segments, e.g. code emitted by a template compiler,
+/// are mapped to the end of the preceding segment.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SourceMap {
+    original: LineIndex,
+    segments: Vec<Segment>,
+}
+
+impl SourceMap {
+    pub fn new<B: ?Sized + AsRef<[u8]>>(original: &B) -> Self {
+        Self {
+            original: LineIndex::new(original),
+            segments: Vec::new(),
+        }
+    }
+
+    /// Register `length` bytes at the `generated` position of the generated source
+    /// as copied from the `original` position of the original source.
+    pub fn add(&mut self, generated: usize, original: usize, length: usize) {
+        let segment = Segment {
+            generated,
+            original,
+            length,
+        };
+
+        let index = self
+            .segments
+            .partition_point(|existing| existing.generated <= generated);
+        self.segments.insert(index, segment);
+    }
+
+    /// The position in the original source corresponding to the given position of the generated source.
+    pub fn map_position(&self, position: usize) -> Option<usize> {
+        let index = self
+            .segments
+            .partition_point(|segment| segment.generated <= position);
+        let segment = self.segments.get(index.checked_sub(1)?)?;
+
+        let offset = (position - segment.generated).min(segment.length);
+
+        Some(self.original.span(segment.original + offset).position)
+    }
+}
+
+impl SpanMapper for SourceMap {
+    fn map_span(&self, span: Span) -> Option<Span> {
+        let position = self.map_position(span.position)?;
+
+        Some(self.original.span(position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SourceMap;
+    use super::SpanMapper;
+    use crate::lexer::token::Span;
+
+    // The template `<p>{{ $name }}</p>\n{{ $x + }}`, compiled to PHP.
+    const ORIGINAL: &str = "<p>{{ $name }}</p>\n{{ $x + }}";
+    const GENERATED: &str = "<p><?php echo e($name); ?></p>\n<?php echo e($x + ); ?>";
+
+    fn source_map() -> SourceMap {
+        let mut map = SourceMap::new(ORIGINAL);
+        // `<p>`
+        map.add(0, 0, 3);
+        // `$name`
+        map.add(16, 6, 5);
+        // `</p>\n`
+        map.add(26, 14, 5);
+        // `$x + `
+        map.add(44, 22, 5);
+
+        map
+    }
+
+    #[test]
+    fn test_map_spans() {
+        let map = source_map();
+        assert_eq!(&GENERATED[16..21], "$name");
+        assert_eq!(&GENERATED[44..49], "$x + ");
+
+        assert_eq!(map.map_span(Span::new(1, 17, 16)), Some(Span::new(1, 7, 6)));
+        assert_eq!(
+            map.map_span(Span::new(2, 14, 44)),
+            Some(Span::new(2, 4, 22))
+        );
+        // `);` following `$name`, emitted by the compiler.
+        assert_eq!(map.map_position(21), Some(11));
+        assert_eq!(map.map_position(22), Some(11));
+
+        let empty = SourceMap::new("");
+        assert_eq!(empty.map_span(Span::new(1, 1, 0)), None);
+    }
+
+    #[test]
+    fn test_report_in_original_coordinates() {
+        let stack = crate::parse(GENERATED).unwrap_err();
+        let mapped = stack.mapped(&source_map());
+
+        assert_eq!(mapped.errors[0].span.line, 2);
+        assert_eq!(mapped.errors[0].span.column, 9);
+
+        let report = stack
+            .report_mapped(&source_map(), ORIGINAL, Some("view.blade.php"), false, true)
+            .unwrap();
+        assert!(report.contains("view.blade.php:2:9"), "{}", report);
+    }
+}
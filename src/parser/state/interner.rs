@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A name stored by an [`Interner`], which compares by id rather than by content.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Symbol(u32);
+
+/// The names of the symbol tables of a parse, each stored once however often it occurs.
+#[derive(Debug, Default)]
+pub struct Interner {
+    symbols: HashMap<Arc<str>, Symbol>,
+    names: Vec<Arc<str>>,
+}
+
+impl Interner {
+    /// The symbol of the given name, storing the name unless it already is.
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(name) {
+            return *symbol;
+        }
+
+        let symbol = Symbol(self.names.len() as u32);
+        let name: Arc<str> = Arc::from(name);
+        self.names.push(name.clone());
+        self.symbols.insert(name, symbol);
+
+        symbol
+    }
+
+    /// The symbol of the given name, if it is stored.
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.symbols.get(name).copied()
+    }
+
+    /// The name of the given symbol.
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.names[symbol.0 as usize]
+    }
+
+    /// Forget every name, keeping the capacity of the tables.
+    pub fn clear(&mut self) {
+        self.symbols.clear();
+        self.names.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Interner;
+
+    #[test]
+    fn test_intern() {
+        let mut interner = Interner::default();
+
+        let foo = interner.intern("app\\foo");
+        let bar = interner.intern("app\\bar");
+
+        assert_eq!(interner.intern("app\\foo"), foo);
+        assert_ne!(foo, bar);
+        assert_eq!(interner.get("app\\bar"), Some(bar));
+        assert_eq!(interner.get("app\\baz"), None);
+        assert_eq!(interner.resolve(foo), "app\\foo");
+
+        interner.clear();
+        assert_eq!(interner.get("app\\foo"), None);
+    }
+}
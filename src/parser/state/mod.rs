@@ -1,10 +1,25 @@
+use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::fmt::Display;
 
 use crate::lexer::stream::TokenStream;
+use crate::lexer::token::Span;
 use crate::parser::ast::attributes::AttributeGroup;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::UseKind;
+use crate::parser::completeness::Recovery;
+use crate::parser::error::ErrorScope;
 use crate::parser::error::ParseError;
+use crate::parser::error::ParseResult;
+use crate::parser::limits::grow_stack;
+use crate::parser::limits::ResourceLimit;
+use crate::parser::options::PhpVersion;
+use crate::parser::state::interner::Interner;
+use crate::parser::state::interner::Symbol;
+use crate::parser::suppressions::Suppressions;
+use crate::parser::telemetry::Telemetry;
+
+pub mod interner;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum NamespaceType {
@@ -16,6 +31,40 @@ pub enum NamespaceType {
 pub enum Scope {
     Namespace(SimpleIdentifier),
     BracedNamespace(Option<SimpleIdentifier>),
+
+    Attribute,
+    Class(SimpleIdentifier, bool),
+    AnonymousClass(bool),
+    Interface(SimpleIdentifier),
+    Trait(SimpleIdentifier),
+    Enum(SimpleIdentifier),
+
+    Function(SimpleIdentifier),
+    Method(SimpleIdentifier, Option<Span>),
+    AnonymousFunction(Option<Span>),
+    ArrowFunction(Option<Span>),
+
+    Loop,
+    Switch,
+}
+
+/// A name imported by a `use` statement.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Import {
+    /// The fully qualified name that is imported, normalized as the keys of its table.
+    pub name: Symbol,
+    /// The alias, or the name when it is not aliased.
+    pub identifier: SimpleIdentifier,
+}
+
+/// The buffers of a [`State`] which outlive a single parse, so their capacity can be reused
+/// by the next one, see [`crate::Parser`].
+#[derive(Debug, Default)]
+pub struct Buffers {
+    stack: VecDeque<Scope>,
+    names: Interner,
+    imports: HashMap<(UseKind, Symbol), Import>,
+    declarations: HashMap<(UseKind, Symbol), SimpleIdentifier>,
 }
 
 #[derive(Debug)]
@@ -25,6 +74,39 @@ pub struct State<'a> {
     pub attributes: Vec<AttributeGroup>,
     pub namespace_type: Option<NamespaceType>,
     pub errors: Vec<ParseError>,
+    /// Whether to recover from syntax errors where possible, recording them instead of stopping.
+    pub recovering: bool,
+    /// The maximum number of errors to record, further errors are dropped.
+    pub max_errors: Option<usize>,
+    /// Whether errors were dropped, as the maximum number of errors was reached.
+    pub errors_exceeded: bool,
+    /// The names of the symbol tables, normalized as their keys.
+    pub names: Interner,
+    /// The names imported in the current namespace, by kind and alias.
+    pub imports: HashMap<(UseKind, Symbol), Import>,
+    /// The classes, functions, and constants declared so far, by kind and fully qualified name.
+    pub declarations: HashMap<(UseKind, Symbol), SimpleIdentifier>,
+    /// The maximum length of a string literal whose value is copied into the AST.
+    pub max_literal_length: Option<usize>,
+    /// The number of expressions and statements being parsed which enclose the current one.
+    pub depth: usize,
+    /// The maximum depth, past which the parse stops rather than overflowing the stack.
+    pub max_depth: Option<usize>,
+    /// The version of PHP the code targets, syntax of any version is accepted when there is none.
+    pub php_version: Option<PhpVersion>,
+    /// Whether experimental syntax is accepted.
+    pub experimental: bool,
+    /// The suppression comments of the input, when they are applied.
+    pub suppressions: Option<Suppressions>,
+    /// Whether a program holding nodes guessed by error recovery is an error.
+    pub require_complete: bool,
+    /// The first node guessed by error recovery.
+    pub recovery: Option<(Recovery, Span)>,
+    /// The hook to report unexpected token errors to, as they are recorded.
+    pub telemetry: Option<Telemetry>,
+    /// Whether the statement about to be parsed is the first one of the file, apart from
+    /// opening tags and comments, where `declare(strict_types=1)` must appear.
+    pub first_statement: bool,
 }
 
 impl<'a> State<'a> {
@@ -35,9 +117,46 @@ impl<'a> State<'a> {
             namespace_type: None,
             attributes: vec![],
             errors: vec![],
+            recovering: false,
+            max_errors: None,
+            errors_exceeded: false,
+            names: Interner::default(),
+            imports: HashMap::new(),
+            declarations: HashMap::new(),
+            max_literal_length: None,
+            depth: 0,
+            max_depth: None,
+            php_version: None,
+            experimental: false,
+            suppressions: None,
+            require_complete: false,
+            recovery: None,
+            telemetry: None,
+            first_statement: false,
         }
     }
 
+    /// Use the given buffers, which must have been released by a previous state or be empty.
+    pub fn reuse(&mut self, buffers: &mut Buffers) {
+        self.stack = std::mem::take(&mut buffers.stack);
+        self.names = std::mem::take(&mut buffers.names);
+        self.imports = std::mem::take(&mut buffers.imports);
+        self.declarations = std::mem::take(&mut buffers.declarations);
+    }
+
+    /// Clear the buffers of this state, and hand them over to the given ones.
+    pub fn release(&mut self, buffers: &mut Buffers) {
+        self.stack.clear();
+        self.names.clear();
+        self.imports.clear();
+        self.declarations.clear();
+
+        buffers.stack = std::mem::take(&mut self.stack);
+        buffers.names = std::mem::take(&mut self.names);
+        buffers.imports = std::mem::take(&mut self.imports);
+        buffers.declarations = std::mem::take(&mut self.declarations);
+    }
+
     pub fn attribute(&mut self, attr: AttributeGroup) {
         self.attributes.push(attr);
     }
@@ -50,10 +169,114 @@ impl<'a> State<'a> {
         attributes
     }
 
-    pub fn record(&mut self, error: ParseError) {
+    /// Parse a nested expression or statement with the given function, failing with a resource
+    /// limit error when the maximum depth is reached.
+    pub fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> ParseResult<T>) -> ParseResult<T> {
+        if let Some(max_depth) = self.max_depth.filter(|max_depth| self.depth >= *max_depth) {
+            return Err(crate::parser::error::resource_limit_exceeded(
+                ResourceLimit::Depth,
+                max_depth,
+                self.stream.current().span,
+            ));
+        }
+
+        self.depth += 1;
+        let result = grow_stack(|| parse(self));
+        self.depth -= 1;
+
+        result
+    }
+
+    /// Record that error recovery guessed a node, unless it already did.
+    pub fn recovered(&mut self, recovery: Recovery, span: Span) {
+        self.recovery.get_or_insert((recovery, span));
+    }
+
+    /// Record an error, along with the scopes enclosing it unless already set.
+    ///
+    /// The error is dropped when the maximum number of errors was reached, or when a
+    /// suppression comment matches it, after it is reported to the telemetry hook.
+    pub fn record(&mut self, mut error: ParseError) {
+        if error.scopes.is_empty() {
+            error.scopes = self.scopes();
+        }
+
+        if let Some(telemetry) = &self.telemetry {
+            telemetry.report(self.stream.tokens(), &error);
+        }
+
+        if let Some(suppressions) = &mut self.suppressions {
+            if suppressions.suppress(&error) {
+                return;
+            }
+        }
+
+        self.push(error);
+    }
+
+    /// Record an error as [`State::record`] does, regardless of the suppression comments,
+    /// e.g. the error stopping the parse, as the program is incomplete.
+    pub fn record_unsuppressed(&mut self, mut error: ParseError) {
+        if error.scopes.is_empty() {
+            error.scopes = self.scopes();
+        }
+
+        if let Some(telemetry) = &self.telemetry {
+            telemetry.report(self.stream.tokens(), &error);
+        }
+
+        self.push(error);
+    }
+
+    fn push(&mut self, error: ParseError) {
+        if self.max_errors.is_some_and(|max| self.errors.len() >= max) {
+            self.errors_exceeded = true;
+
+            return;
+        }
+
         self.errors.push(error);
     }
 
+    /// The declarations enclosing the current position, outermost first.
+    pub fn scopes(&self) -> Vec<ErrorScope> {
+        self.stack
+            .iter()
+            .filter_map(|scope| {
+                Some(match scope {
+                    Scope::Namespace(name) => ErrorScope::Namespace {
+                        name: Some(name.to_string()),
+                    },
+                    Scope::BracedNamespace(name) => ErrorScope::Namespace {
+                        name: name.as_ref().map(|name| name.to_string()),
+                    },
+                    Scope::Class(name, _) => ErrorScope::Class {
+                        name: Some(name.to_string()),
+                    },
+                    Scope::AnonymousClass(_) => ErrorScope::Class { name: None },
+                    Scope::Interface(name) => ErrorScope::Interface {
+                        name: name.to_string(),
+                    },
+                    Scope::Trait(name) => ErrorScope::Trait {
+                        name: name.to_string(),
+                    },
+                    Scope::Enum(name) => ErrorScope::Enum {
+                        name: name.to_string(),
+                    },
+                    Scope::Function(name) => ErrorScope::Function {
+                        name: name.to_string(),
+                    },
+                    Scope::Method(name, _) => ErrorScope::Method {
+                        name: name.to_string(),
+                    },
+                    Scope::AnonymousFunction(_) => ErrorScope::Closure,
+                    Scope::ArrowFunction(_) => ErrorScope::ArrowFunction,
+                    Scope::Attribute | Scope::Loop | Scope::Switch => return None,
+                })
+            })
+            .collect()
+    }
+
     /// Return the namespace type used in the current state
     ///
     /// The namespace type is retrieve from the last entered
@@ -82,10 +305,13 @@ impl<'a> State<'a> {
         match &scope {
             Scope::Namespace(_) => {
                 self.namespace_type = Some(NamespaceType::Unbraced);
+                self.imports.clear();
             }
             Scope::BracedNamespace(_) => {
                 self.namespace_type = Some(NamespaceType::Braced);
+                self.imports.clear();
             }
+            _ => {}
         }
 
         self.stack.push_back(scope);
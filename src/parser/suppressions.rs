@@ -0,0 +1,449 @@
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::lexer::token::Span;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+use crate::parser::error;
+use crate::parser::error::ParseError;
+
+const IGNORE: &[u8] = b"@parser-ignore";
+
+const NEXT_LINE: &[u8] = b"-next-line";
+
+/// How the suppression comments of the input are handled, see [`Suppression`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SuppressionMode {
+    /// Suppression comments are ordinary comments.
+    #[default]
+    Off,
+    /// Errors matching a suppression comment are not reported.
+    On,
+    /// Errors matching a suppression comment are not reported, and an unused suppression
+    /// error (`E082`) is reported for every suppressed code which matched no error.
+    ReportUnused,
+}
+
+impl FromStr for SuppressionMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Self::Off),
+            "on" => Ok(Self::On),
+            "report-unused" => Ok(Self::ReportUnused),
+            _ => Err(format!(
+                "unknown suppression mode `{}`, expecting `off`, `on` or `report-unused`",
+                s
+            )),
+        }
+    }
+}
+
+/// The part of the input a suppression applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuppressionScope {
+    /// The line following the comment, for `@parser-ignore-next-line`.
+    Line(usize),
+    /// The byte range of the statement or member following the comment, i.e. the node the
+    /// comment is attached to, for `@parser-ignore`.
+    Range(Range<usize>),
+}
+
+/// A comment suppressing errors, such as `// @parser-ignore-next-line` or
+/// `/** @parser-ignore E071 */`.
+///
+/// The directive is followed by the codes of the suppressed errors, separated by spaces or
+/// commas, every error being suppressed when there are none. The error stopping a parse is
+/// never suppressed, as the program is incomplete.
+///
+/// ```
+/// use php_parser_rs::parser::suppressions::SuppressionMode;
+/// use php_parser_rs::ParserOptions;
+/// use php_parser_rs::PhpVersion;
+///
+/// let options = ParserOptions::new()
+///     .with_php_version(PhpVersion::Php74)
+///     .with_suppressions(SuppressionMode::On);
+///
+/// let code = "<?php\n// @parser-ignore-next-line E071\nenum Suit {}";
+/// assert!(php_parser_rs::parse_with_options(code, &options).is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suppression {
+    /// The span of the directive within the comment.
+    pub span: Span,
+    /// The directive, as written in the comment.
+    pub directive: String,
+    /// The codes of the suppressed errors.
+    pub codes: Vec<String>,
+    pub scope: SuppressionScope,
+}
+
+impl Suppression {
+    /// The suppressions within the comments of the given tokens.
+    pub fn collect(tokens: &[Token]) -> Vec<Suppression> {
+        let mut suppressions = Vec::new();
+
+        for (index, token) in tokens.iter().enumerate() {
+            if !is_comment(&token.kind) {
+                continue;
+            }
+
+            let comment = &token.value[..];
+            let mut content = comment;
+            while let Some(offset) = find(content, IGNORE) {
+                let before = content[..offset].last();
+                let start = comment.len() - content.len() + offset;
+                content = &content[offset + IGNORE.len()..];
+
+                if before.is_some_and(|byte| byte.is_ascii_alphanumeric() || *byte == b'-') {
+                    continue;
+                }
+
+                let next_line = content.starts_with(NEXT_LINE);
+                if next_line {
+                    content = &content[NEXT_LINE.len()..];
+                }
+
+                if content
+                    .first()
+                    .is_some_and(|byte| !byte.is_ascii_whitespace() && *byte != b'*')
+                {
+                    continue;
+                }
+
+                let scope = if next_line {
+                    let lines = token.value.iter().filter(|byte| **byte == b'\n').count();
+
+                    SuppressionScope::Line(token.span.line + lines + 1)
+                } else {
+                    SuppressionScope::Range(attached_range(&tokens[index + 1..]))
+                };
+
+                let codes = codes(content);
+                let directive = if next_line {
+                    "@parser-ignore-next-line"
+                } else {
+                    "@parser-ignore"
+                };
+
+                suppressions.push(Suppression {
+                    span: offset_span(token.span, &comment[..start]),
+                    directive: directive.to_string(),
+                    codes,
+                    scope,
+                });
+            }
+        }
+
+        suppressions
+    }
+
+    /// Whether the given error is suppressed.
+    pub fn suppresses(&self, error: &ParseError) -> bool {
+        self.matching_code(error).is_some()
+    }
+
+    /// The index of the code matching the given error, which is `0` for a suppression
+    /// without codes.
+    fn matching_code(&self, error: &ParseError) -> Option<usize> {
+        let within = match &self.scope {
+            SuppressionScope::Line(line) => error.span.line == *line,
+            SuppressionScope::Range(range) => range.contains(&error.span.position),
+        };
+
+        if !within {
+            return None;
+        }
+
+        if self.codes.is_empty() {
+            return Some(0);
+        }
+
+        self.codes.iter().position(|code| *code == error.id)
+    }
+}
+
+/// The suppressions of an input being parsed, along with whether each of their codes
+/// matched an error.
+#[derive(Debug)]
+pub(crate) struct Suppressions {
+    suppressions: Vec<(Suppression, Vec<bool>)>,
+    report_unused: bool,
+}
+
+impl Suppressions {
+    pub fn new(tokens: &[Token], mode: SuppressionMode) -> Option<Self> {
+        if mode == SuppressionMode::Off {
+            return None;
+        }
+
+        let suppressions = Suppression::collect(tokens)
+            .into_iter()
+            .map(|suppression| {
+                let used = vec![false; suppression.codes.len().max(1)];
+
+                (suppression, used)
+            })
+            .collect();
+
+        Some(Self {
+            suppressions,
+            report_unused: mode == SuppressionMode::ReportUnused,
+        })
+    }
+
+    /// Whether the given error is suppressed, marking the matching suppressions as used.
+    pub fn suppress(&mut self, error: &ParseError) -> bool {
+        let mut suppressed = false;
+        for (suppression, used) in &mut self.suppressions {
+            if let Some(code) = suppression.matching_code(error) {
+                used[code] = true;
+                suppressed = true;
+            }
+        }
+
+        suppressed
+    }
+
+    /// An unused suppression error for every suppressed code which matched no error, when
+    /// they are reported.
+    pub fn unused(&self) -> Vec<ParseError> {
+        if !self.report_unused {
+            return Vec::new();
+        }
+
+        let mut errors = Vec::new();
+        for (suppression, used) in &self.suppressions {
+            for (index, used) in used.iter().enumerate() {
+                if *used {
+                    continue;
+                }
+
+                let directive = match suppression.codes.get(index) {
+                    Some(code) => format!("{} {}", suppression.directive, code),
+                    None => suppression.directive.clone(),
+                };
+
+                errors.push(error::unused_suppression(
+                    &directive,
+                    suppression.span,
+                    suppression.directive.len(),
+                ));
+            }
+        }
+
+        errors
+    }
+}
+
+fn is_comment(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::SingleLineComment
+            | TokenKind::MultiLineComment
+            | TokenKind::HashMarkComment
+            | TokenKind::DocumentComment
+    )
+}
+
+/// The span of the text following the given prefix of a comment.
+fn offset_span(comment: Span, prefix: &[u8]) -> Span {
+    let lines = prefix.iter().filter(|byte| **byte == b'\n').count();
+    let column = match prefix.iter().rposition(|byte| *byte == b'\n') {
+        Some(newline) => prefix.len() - newline,
+        None => comment.column + prefix.len(),
+    };
+
+    Span::new(
+        comment.line + lines,
+        column,
+        comment.position + prefix.len(),
+    )
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// The leading error codes of the given text, separated by spaces or commas.
+fn codes(text: &[u8]) -> Vec<String> {
+    text.split(|byte| byte.is_ascii_whitespace() || *byte == b',')
+        .filter(|word| !word.is_empty())
+        .take_while(|word| {
+            word.len() > 1 && word[0] == b'E' && word[1..].iter().all(u8::is_ascii_digit)
+        })
+        .map(|word| String::from_utf8_lossy(word).into_owned())
+        .collect()
+}
+
+/// The byte range of the statement or member starting with the given tokens, which ends
+/// with its `;` or the `}` closing its body, or the end of the enclosing block.
+fn attached_range(tokens: &[Token]) -> Range<usize> {
+    let tokens = tokens.iter().filter(|token| !is_comment(&token.kind));
+    let start = match tokens.clone().next() {
+        Some(token) => token.span.position,
+        None => return 0..0,
+    };
+
+    // The number of brackets opened by the statement which are still open.
+    let mut depth = 0usize;
+    // Whether the token is within an interpolated string, where `{` and `}` delimit
+    // expressions rather than blocks.
+    let mut string = false;
+    for token in tokens {
+        let position = token.span.position;
+
+        match token.kind {
+            // An interpolated string starts with a part holding its opening quote.
+            TokenKind::StringPart | TokenKind::StartDocString(_) => string = true,
+            TokenKind::DoubleQuote | TokenKind::EndDocString(..) => string = false,
+            TokenKind::Backtick => string = !string,
+            TokenKind::LeftBrace
+            | TokenKind::DollarLeftBrace
+            | TokenKind::LeftParen
+            | TokenKind::LeftBracket
+            | TokenKind::Attribute => depth += 1,
+            TokenKind::RightParen | TokenKind::RightBracket => depth = depth.saturating_sub(1),
+            TokenKind::RightBrace if depth == 0 => return start..position,
+            TokenKind::RightBrace => {
+                depth -= 1;
+
+                if depth == 0 && !string {
+                    return start..position + 1;
+                }
+            }
+            TokenKind::SemiColon | TokenKind::CloseTag if depth == 0 => return start..position + 1,
+            TokenKind::Eof => return start..position,
+            _ => {}
+        }
+    }
+
+    start..start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SuppressionMode;
+    use crate::parser::error::ParseErrorStack;
+    use crate::ParserOptions;
+    use crate::PhpVersion;
+
+    fn errors(code: &str, mode: SuppressionMode) -> Vec<String> {
+        let options = ParserOptions::new()
+            .with_php_version(PhpVersion::Php74)
+            .with_suppressions(mode);
+
+        let stack = match crate::parse_with_options(code, &options) {
+            Ok(_) => return vec![],
+            Err(stack) => stack,
+        };
+
+        errors_of(&stack)
+    }
+
+    fn errors_of(stack: &ParseErrorStack) -> Vec<String> {
+        stack
+            .errors
+            .iter()
+            .map(|error| format!("{} {} {}", error.id, error.span.line, error.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_next_line() {
+        let code = "<?php
+// @parser-ignore-next-line
+enum A {}
+/* @parser-ignore-next-line E071, E072 */
+enum B {}
+// @parser-ignore-next-line E072 enums are fine here
+enum C {}
+
+enum D {}
+";
+
+        assert_eq!(
+            errors(code, SuppressionMode::On),
+            vec![
+                "E071 7 using enums requires PHP 8.1 or later",
+                "E071 9 using enums requires PHP 8.1 or later",
+            ]
+        );
+        assert_eq!(errors(code, SuppressionMode::Off).len(), 4);
+    }
+
+    #[test]
+    fn test_attached() {
+        let code = "<?php
+/**
+ * @parser-ignore E071
+ */
+final class A {
+    public function a(): mixed { return \"{$this?->b}\"; }
+
+    public function b(): mixed {}
+}
+
+enum B {}
+
+class C {
+    /** @parser-ignore */
+    public function c(): mixed {}
+
+    public function d(): mixed {}
+}
+";
+
+        assert_eq!(
+            errors(code, SuppressionMode::On),
+            vec![
+                "E071 11 using enums requires PHP 8.1 or later",
+                "E071 17 using the `mixed` type requires PHP 8.0 or later",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unused() {
+        let code = "<?php
+// @parser-ignore-next-line E071 E072
+enum A {}
+// @parser-ignore-next-line
+echo 1;
+// @parser-ignore-next-lines
+// not @parser-ignored
+";
+
+        assert_eq!(
+            errors(code, SuppressionMode::ReportUnused),
+            vec![
+                "E082 2 unused suppression `@parser-ignore-next-line E072`",
+                "E082 4 unused suppression `@parser-ignore-next-line`",
+            ]
+        );
+        assert_eq!(errors(code, SuppressionMode::On), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_errors_stopping_the_parse() {
+        let options = ParserOptions::new().with_suppressions(SuppressionMode::On);
+        let stack =
+            crate::parse_with_options("<?php\n// @parser-ignore-next-line\n$a = ;", &options)
+                .unwrap_err();
+
+        assert_eq!(errors_of(&stack), vec!["E003 3 unexpected token `;`"]);
+
+        // Errors recovered from don't stop the parse.
+        let parse = crate::parse_all_with_options(
+            "<?php\n// @parser-ignore-next-line\n$a = ;\n$b = ;",
+            &options,
+        );
+
+        assert_eq!(parse.errors.len(), 1);
+        assert_eq!(parse.errors[0].span.line, 4);
+    }
+}
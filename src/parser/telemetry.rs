@@ -0,0 +1,167 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::lexer::token::Token;
+use crate::parser::error::ErrorScope;
+use crate::parser::error::ParseError;
+
+/// The number of tokens on each side of the unexpected token within the window of an
+/// [`UnknownConstruct`].
+pub const WINDOW: usize = 8;
+
+/// An unexpected token error of a parse, reported to the telemetry hook of the parser
+/// options, see [`crate::ParserOptions::with_telemetry`].
+///
+/// ```
+/// use std::sync::mpsc;
+///
+/// use php_parser_rs::ParserOptions;
+///
+/// let (sender, receiver) = mpsc::channel();
+/// let options = ParserOptions::new().with_telemetry(move |construct| {
+///     sender.send(construct.shape()).unwrap();
+/// });
+///
+/// assert!(php_parser_rs::parse_with_options("<?php $a = ?;", &options).is_err());
+/// assert_eq!(receiver.try_recv().unwrap(), "<?php Variable = >?< ; [end of file]");
+/// ```
+#[derive(Debug)]
+pub struct UnknownConstruct<'a> {
+    /// The unexpected token error, see [`ParseError::is_unexpected_token`].
+    pub error: &'a ParseError,
+    /// The tokens surrounding the unexpected token, up to [`WINDOW`] on each side.
+    pub window: &'a [Token<'a>],
+    /// The index of the unexpected token within the window.
+    pub index: usize,
+}
+
+impl UnknownConstruct<'_> {
+    /// The unexpected token.
+    pub fn token(&self) -> &Token<'_> {
+        &self.window[self.index]
+    }
+
+    /// The declarations enclosing the unexpected token, outermost first.
+    pub fn scopes(&self) -> &[ErrorScope] {
+        &self.error.scopes
+    }
+
+    /// The kinds of the tokens of the window, with the unexpected one marked, e.g.
+    /// `Variable = >?< ;`.
+    ///
+    /// Unlike the tokens, it holds no names, literals or comments of the source, so it can be
+    /// aggregated across inputs and reported without leaking them.
+    pub fn shape(&self) -> String {
+        self.window
+            .iter()
+            .enumerate()
+            .map(|(index, token)| {
+                if index == self.index {
+                    format!(">{}<", token.kind)
+                } else {
+                    token.kind.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// The telemetry hook of the parser options, see [`crate::ParserOptions::with_telemetry`].
+///
+/// Clones share the hook, and are equal to each other only.
+#[derive(Clone)]
+pub struct Telemetry(Arc<dyn for<'a> Fn(&UnknownConstruct<'a>) + Send + Sync>);
+
+impl Telemetry {
+    pub fn new(hook: impl for<'a> Fn(&UnknownConstruct<'a>) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(hook))
+    }
+
+    /// Report the given error of a parse of the given tokens, if it is an unexpected token
+    /// error.
+    pub(crate) fn report(&self, tokens: &[Token], error: &ParseError) {
+        if !error.is_unexpected_token() {
+            return;
+        }
+
+        // The error may point past the last token, e.g. at a synthetic one.
+        let found = tokens
+            .partition_point(|token| token.span.position < error.span.position)
+            .min(tokens.len().saturating_sub(1));
+        let start = found.saturating_sub(WINDOW);
+        let end = (found + WINDOW + 1).min(tokens.len());
+
+        if let Some(window) = tokens.get(start..end).filter(|window| !window.is_empty()) {
+            (self.0)(&UnknownConstruct {
+                error,
+                window,
+                index: found - start,
+            });
+        }
+    }
+}
+
+impl PartialEq for Telemetry {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Telemetry {}
+
+impl Debug for Telemetry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Telemetry").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use crate::ParserOptions;
+
+    #[test]
+    fn test_telemetry() {
+        let (sender, receiver) = mpsc::channel();
+        let options = ParserOptions::new().with_telemetry(move |construct| {
+            sender
+                .send((construct.shape(), construct.token().span.line))
+                .unwrap();
+        });
+        let code = "<?php\n$a = ?;\nfunction b() { $c = ]; }\n";
+
+        assert!(crate::parse_with_options(code, &options).is_err());
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            vec![(
+                "<?php Variable = >?< ; function Identifier ( ) { Variable =".to_string(),
+                2
+            )]
+        );
+
+        // Recovered errors are reported as well.
+        let parse = crate::parse_all_with_options(code, &options);
+        assert_eq!(parse.errors.len(), 2);
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    "<?php Variable = >?< ; function Identifier ( ) { Variable =".to_string(),
+                    2
+                ),
+                (
+                    "; function Identifier ( ) { Variable = >]< ; } [end of file]".to_string(),
+                    3
+                ),
+            ]
+        );
+
+        assert!(
+            crate::parse_tolerant_with_options(code, &options.clone().with_telemetry(|_| {}))
+                .is_err()
+        );
+        assert_eq!(receiver.try_iter().count(), 0);
+    }
+}
@@ -0,0 +1,40 @@
+//! The stable entry points of the crate, for glob importing.
+//!
+//! ```
+//! use php_parser_rs::prelude::*;
+//!
+//! let program: Program = parse("<?php echo 1;").unwrap();
+//!
+//! assert!(matches!(program[1], Statement::Echo(_)));
+//! ```
+
+pub use crate::downcast::downcast;
+pub use crate::downcast::downcast_mut;
+pub use crate::lexer::byte_string::ByteString;
+pub use crate::lexer::error::SyntaxError;
+pub use crate::lexer::stream::TokenStream;
+pub use crate::lexer::token::Span;
+pub use crate::lexer::token::Token;
+pub use crate::lexer::token::TokenKind;
+pub use crate::lexer::Lexer;
+pub use crate::node::Node;
+pub use crate::parser::ast;
+pub use crate::parser::ast::Expression;
+pub use crate::parser::ast::Program;
+pub use crate::parser::ast::Statement;
+pub use crate::parser::cancellation::CancellationToken;
+pub use crate::parser::catalog::MessageCatalog;
+pub use crate::parser::error::ErrorScope;
+pub use crate::parser::error::Parse;
+pub use crate::parser::error::ParseError;
+pub use crate::parser::error::ParseErrorStack;
+pub use crate::parser::error::ParseResult;
+pub use crate::parser::limits::ResourceLimit;
+pub use crate::parser::limits::ResourceLimits;
+pub use crate::parser::options::ParserOptions;
+pub use crate::parser::{
+    construct, construct_with_options, parse, parse_all, parse_all_with_options,
+    parse_class_member, parse_method_body, parse_template, parse_tolerant,
+    parse_tolerant_with_options, parse_untrusted, parse_with_options,
+};
+pub use crate::traverser::Visitor;
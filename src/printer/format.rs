@@ -0,0 +1,303 @@
+use std::fmt::Display;
+
+use crate::analysis::structural::structural_hash;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::TokenKind;
+use crate::lexer::Lexer;
+use crate::parser::ast::visitor_mut::walk_expression_mut;
+use crate::parser::ast::visitor_mut::VisitorMut;
+use crate::parser::ast::Expression;
+use crate::parser::error::ParseErrorStack;
+use crate::printer::pretty::print_program;
+use crate::printer::pretty::PrinterOptions;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FormatError {
+    /// The code does not parse.
+    Parse(ParseErrorStack),
+    /// Formatting would drop the given comments, which the pretty printer only keeps when they
+    /// are attached to a declaration or a statement.
+    Comments(Vec<ByteString>),
+    /// The formatted code does not parse back to the same program.
+    Changed,
+}
+
+impl Display for FormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatError::Parse(stack) => write!(f, "{}", stack),
+            FormatError::Comments(comments) => {
+                write!(f, "formatting would drop {} comment(s)", comments.len())?;
+                for comment in comments.iter() {
+                    write!(f, "\n  {}", comment)?;
+                }
+
+                Ok(())
+            }
+            FormatError::Changed => write!(f, "formatting would change the program"),
+        }
+    }
+}
+
+/// Format PHP code with the pretty printer.
+///
+/// Unlike [`print_program`], the code is only formatted when nothing but its layout changes:
+/// the formatted code must parse back to the same program, and keep all of its comments.
+///
+/// ```
+/// use php_parser_rs::printer::format;
+/// use php_parser_rs::printer::PrinterOptions;
+///
+/// let formatted = format("<?php if($a){echo 1+2;}", &PrinterOptions::new()).unwrap();
+/// assert_eq!(formatted, "<?php\n\nif ($a) {\n    echo 1 + 2;\n}\n");
+/// ```
+pub fn format(code: &str, options: &PrinterOptions) -> Result<String, FormatError> {
+    let program = crate::parse(code).map_err(FormatError::Parse)?;
+    let formatted = print_program(&program, options);
+
+    let lost = lost_comments(code, &formatted);
+    if !lost.is_empty() {
+        return Err(FormatError::Comments(lost));
+    }
+
+    match crate::parse(&formatted) {
+        Ok(mut reparsed) => {
+            let mut program = program;
+            UnparenthesizeNew.visit_program_mut(&mut program);
+            UnparenthesizeNew.visit_program_mut(&mut reparsed);
+
+            if structural_hash(&reparsed) == structural_hash(&program) {
+                Ok(formatted)
+            } else {
+                Err(FormatError::Changed)
+            }
+        }
+        _ => Err(FormatError::Changed),
+    }
+}
+
+/// Removes the parentheses around `new` expressions, which never change the meaning of the
+/// code, and which the printer adds around the target of a member access, e.g. for
+/// `new Foo()->bar()`, which can only be written without them since PHP 8.4.
+struct UnparenthesizeNew;
+
+impl VisitorMut for UnparenthesizeNew {
+    fn visit_expression_mut(&mut self, expression: &mut Expression) {
+        while let Expression::Parenthesized { start, expr, .. } = expression {
+            if !matches!(expr.as_ref(), Expression::New { .. }) {
+                break;
+            }
+
+            let noop = Expression::Noop { span: *start };
+            *expression = std::mem::replace(expr.as_mut(), noop);
+        }
+
+        walk_expression_mut(self, expression);
+    }
+}
+
+// The comments of the code missing from the formatted code, compared without indentation as
+// multi-line comments are re-indented.
+fn lost_comments(code: &str, formatted: &str) -> Vec<ByteString> {
+    let comments = |code: &str| -> Vec<ByteString> {
+        Lexer::new()
+            .tokenize(code.as_bytes())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|token| {
+                matches!(
+                    token.kind,
+                    TokenKind::SingleLineComment
+                        | TokenKind::HashMarkComment
+                        | TokenKind::MultiLineComment
+                        | TokenKind::DocumentComment
+                )
+            })
+            .map(|token| {
+                token
+                    .value
+                    .split(|byte| *byte == b'\n')
+                    .map(|line| line.trim_ascii())
+                    .collect::<Vec<&[u8]>>()
+                    .join(&b'\n')
+                    .into()
+            })
+            .collect()
+    };
+
+    let mut remaining = comments(formatted);
+
+    comments(code)
+        .into_iter()
+        .filter(
+            |comment| match remaining.iter().position(|other| other == comment) {
+                Some(position) => {
+                    remaining.swap_remove(position);
+
+                    false
+                }
+                None => true,
+            },
+        )
+        .collect()
+}
+
+/// A unified diff of two versions of a file, with three lines of context around each change,
+/// or an empty string when they are the same.
+pub fn unified_diff(path: &str, before: &str, after: &str) -> String {
+    const CONTEXT: usize = 3;
+
+    let old = before.lines().collect::<Vec<&str>>();
+    let new = after.lines().collect::<Vec<&str>>();
+
+    // lengths[i][j] is the length of the longest common subsequence of old[i..] and new[j..].
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    // (prefix, line, old line number, new line number)
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push((' ', old[i], i, j));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lengths[i + 1][j] >= lengths[i][j + 1]) {
+            lines.push(('-', old[i], i, j));
+            i += 1;
+        } else {
+            lines.push(('+', new[j], i, j));
+            j += 1;
+        }
+    }
+
+    let changed = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.0 != ' ')
+        .map(|(index, _)| index)
+        .collect::<Vec<usize>>();
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    let mut output = format!("--- {}\n+++ {}\n", path, path);
+
+    // group changes whose context overlaps into a single hunk.
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for index in changed {
+        let start = index.saturating_sub(CONTEXT);
+        let end = (index + CONTEXT + 1).min(lines.len());
+
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.1 => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    for (start, end) in hunks {
+        let hunk = &lines[start..end];
+        let count = |prefix: char| hunk.iter().filter(|line| line.0 != prefix).count();
+        let (old_count, new_count) = (count('+'), count('-'));
+
+        output.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            hunk[0].2 + usize::from(old_count > 0),
+            old_count,
+            hunk[0].3 + usize::from(new_count > 0),
+            new_count
+        ));
+
+        for (prefix, line, _, _) in hunk.iter() {
+            output.push_str(&format!("{}{}\n", prefix, line));
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format;
+    use super::unified_diff;
+    use super::FormatError;
+    use crate::printer::PrinterOptions;
+
+    #[test]
+    fn test_format() {
+        let options = PrinterOptions::new();
+
+        let code = "<?php\n\n/** A function. */\nfunction f($a) { return $a; }\n";
+        let formatted = format(code, &options).unwrap();
+        assert_eq!(
+            formatted,
+            "<?php\n\n/** A function. */\nfunction f($a)\n{\n    return $a;\n}\n"
+        );
+        assert_eq!(format(&formatted, &options).unwrap(), formatted);
+
+        assert!(matches!(
+            format("<?php function", &options),
+            Err(FormatError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_format_long_concatenation() {
+        let operands = (0..1100).map(|i| format!("$a{}", i)).collect::<Vec<_>>();
+        let code = format!("<?php\n\necho {};\n", operands.join(" . "));
+
+        // The printer recurses into each operand, which debug builds can't do on the stack of
+        // a test thread.
+        let formatted = std::thread::Builder::new()
+            .stack_size(64 * 1024 * 1024)
+            .spawn(move || format(&code, &PrinterOptions::new()).map(|formatted| formatted == code))
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(formatted, Ok(true));
+    }
+
+    #[test]
+    fn test_format_new_without_parentheses() {
+        assert_eq!(
+            format("<?php new Foo()->bar();", &PrinterOptions::new()).unwrap(),
+            "<?php\n\n(new Foo())->bar();\n"
+        );
+    }
+
+    #[test]
+    fn test_format_keeps_comments() {
+        let error = format(
+            "<?php\nfunction f() {\n    return 1; // one\n}\n",
+            &PrinterOptions::new(),
+        )
+        .unwrap_err();
+
+        assert_eq!(error, FormatError::Comments(vec!["// one".into()]));
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        assert_eq!(unified_diff("a.php", "a\nb\n", "a\nb\n"), "");
+
+        let before = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n";
+        let after = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n10\n11\n12\n13\n";
+        assert_eq!(
+            unified_diff("a.php", before, after),
+            "--- a.php\n+++ a.php\n@@ -2,7 +2,7 @@\n 2\n 3\n 4\n-5\n+five\n 6\n 7\n 8\n@@ -10,3 +10,4 @@\n 10\n 11\n 12\n+13\n"
+        );
+
+        assert_eq!(
+            unified_diff("a.php", "", "a\n"),
+            "--- a.php\n+++ a.php\n@@ -0,0 +1,1 @@\n+a\n"
+        );
+    }
+}
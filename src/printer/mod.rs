@@ -4,6 +4,26 @@ use std::collections::HashMap;
 use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
 
+pub use self::format::format;
+pub use self::format::unified_diff;
+pub use self::format::FormatError;
+pub use self::preserving::print_preserving;
+pub use self::pretty::print_expression;
+pub use self::pretty::print_program;
+pub use self::pretty::print_statement;
+pub use self::pretty::BraceStyle;
+pub use self::pretty::Indentation;
+pub use self::pretty::PrinterOptions;
+pub use self::stubs::print_stubs;
+pub use self::stubs::stubs;
+
+pub(crate) use self::pretty::print_expression_within;
+
+mod format;
+mod preserving;
+mod pretty;
+mod stubs;
+
 /// Prints the tokens as a string
 ///
 /// # Example
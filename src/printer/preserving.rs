@@ -0,0 +1,1038 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::lexer::token::Span;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+use crate::lexer::Lexer;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::Statement;
+use crate::printer::pretty::print_member_within;
+use crate::printer::pretty::print_program;
+use crate::printer::pretty::print_statement_within;
+use crate::printer::pretty::Member;
+use crate::printer::pretty::PrinterOptions;
+
+/// Print a modified program, reusing the original source for the parts that were not modified.
+///
+/// `original` must be the program parsed from `source`, and `modified` a modified copy of it.
+/// Statements and class members equal to their original are copied from the source, along
+/// with the whitespace and comments around them, so only the lines of the modified nodes
+/// change. A modified function, method, class-like, namespace or block whose body is the
+/// only change keeps its original header and braces, and is compared member by member.
+///
+/// Other modified nodes are printed with the given options, see [`print_program`], and
+/// indented like the node they replace.
+///
+/// ```
+/// use php_parser_rs::parser::ast::Statement;
+/// use php_parser_rs::printer::print_preserving;
+/// use php_parser_rs::printer::PrinterOptions;
+///
+/// let source = "<?php\n\nfunction foo() {\n  $a   = 1;\n  return $a;\n}\n";
+/// let original = php_parser_rs::parse(source).unwrap();
+///
+/// let mut modified = original.clone();
+/// if let Statement::Function(function) = &mut modified[1] {
+///     function.body.statements.remove(0);
+/// }
+///
+/// assert_eq!(
+///     print_preserving(source, &original, &modified, &PrinterOptions::new()),
+///     "<?php\n\nfunction foo() {\n  return $a;\n}\n"
+/// );
+/// ```
+pub fn print_preserving<B: ?Sized + AsRef<[u8]>>(
+    source: &B,
+    original: &[Statement],
+    modified: &[Statement],
+    options: &PrinterOptions,
+) -> String {
+    let source = source.as_ref();
+    let tokens = match Lexer::new().tokenize(source) {
+        Ok(tokens) => tokens,
+        Err(_) => return print_program(modified, options),
+    };
+
+    let mut printer = Preserving {
+        source,
+        tokens,
+        options,
+        output: Vec::with_capacity(source.len()),
+    };
+
+    printer.list(original, modified, 0, source.len(), None);
+
+    String::from_utf8_lossy(&printer.output).into_owned()
+}
+
+/// A node of a statement or member list.
+trait Item: PartialEq + Serialize {
+    /// Print the node, indenting its lines but the first one with the given margin.
+    fn print(&self, options: &PrinterOptions, margin: &str) -> String;
+
+    /// Print the modified node, keeping the source of the original one but for its body.
+    ///
+    /// Returns `false`, without printing anything, when the node was modified outside of
+    /// its body.
+    fn splice(&self, _modified: &Self, _printer: &mut Preserving, _extent: Extent) -> bool {
+        false
+    }
+
+    /// The positions of the spans of the node, which all precede the end of the node.
+    fn positions(&self) -> Vec<usize> {
+        positions(self)
+    }
+
+    /// Whether the node extends to the end of the source, e.g. `__halt_compiler();` and the
+    /// data following it.
+    fn extends_to_end(&self) -> bool {
+        false
+    }
+}
+
+/// The byte range of a node in the source, including its attached comments.
+#[derive(Clone, Copy)]
+struct Extent {
+    start: usize,
+    end: usize,
+    /// The start of the comments following the node on its last line, kept when the node
+    /// is replaced.
+    trailing: usize,
+    /// The byte range of the body of the node, set before splicing it.
+    body: Option<Body>,
+}
+
+/// The byte range of the list of statements or members of a node.
+#[derive(Clone, Copy)]
+struct Body {
+    start: usize,
+    end: usize,
+    /// Whether the list is followed by a closing brace.
+    closed: bool,
+}
+
+impl Extent {
+    fn with(self, body: Body) -> Self {
+        Self {
+            body: Some(body),
+            ..self
+        }
+    }
+
+    /// The extent of a node whose body is delimited by the given braces.
+    fn braced(self, (left, right): (Span, Span)) -> Self {
+        self.with(Body {
+            start: left.position + 1,
+            end: right.position,
+            closed: true,
+        })
+    }
+}
+
+/// The edit turning the original list into the modified one.
+enum Edit {
+    Keep(usize),
+    Replace(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+struct Preserving<'a> {
+    source: &'a [u8],
+    tokens: Vec<Token<'a>>,
+    options: &'a PrinterOptions,
+    output: Vec<u8>,
+}
+
+impl<'a> Preserving<'a> {
+    /// Print the modified list in place of the original one, which spans from `start` to `end`
+    /// in the source.
+    ///
+    /// `closing` is the indentation of the closing brace of the list, if any.
+    fn list<T: Item>(
+        &mut self,
+        original: &[T],
+        modified: &[T],
+        start: usize,
+        end: usize,
+        closing: Option<&[u8]>,
+    ) {
+        let spans = original
+            .iter()
+            .map(Item::positions)
+            .collect::<Vec<Vec<usize>>>();
+
+        let mut cursor = start;
+        let extents = original
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                // The spans of an item stop before the first span of the next one, apart from
+                // the spans of comments, which may be attached to a node following them.
+                let bound = spans[index + 1..]
+                    .iter()
+                    .find_map(|positions| {
+                        positions
+                            .iter()
+                            .copied()
+                            .filter(|position| !self.is_comment_at(*position))
+                            .min()
+                    })
+                    .unwrap_or(end)
+                    .min(end);
+
+                let extent = self.extent(item, &spans[index], cursor, bound, end);
+                cursor = extent.end;
+
+                extent
+            })
+            .collect::<Vec<Extent>>();
+
+        let indentation = match extents
+            .first()
+            .and_then(|extent| self.indentation(extent.start))
+        {
+            Some(indentation) => indentation.to_vec(),
+            None => {
+                let mut indentation = closing.unwrap_or_default().to_vec();
+                if closing.is_some() {
+                    indentation.extend_from_slice(self.options.indentation.unit().as_bytes());
+                }
+
+                indentation
+            }
+        };
+        let separator = [b"\n", &indentation[..]].concat();
+
+        let edits = diff(original, modified);
+        let mut cursor = start;
+        let mut emitted = false;
+        // The whitespace preceding a deleted first item, or the comments preceding a deleted
+        // item that are not attached to it, which precede the next one instead.
+        let mut carry: Option<Vec<u8>> = None;
+        // Whether the whitespace preceding the next original item was taken by an insertion.
+        let mut taken = false;
+
+        for (index, edit) in edits.iter().enumerate() {
+            match *edit {
+                Edit::Keep(i) | Edit::Replace(i, _) => {
+                    let extent = extents[i];
+                    let gap = if std::mem::take(&mut taken) {
+                        separator.clone()
+                    } else {
+                        carry
+                            .take()
+                            .unwrap_or_else(|| self.source[cursor..extent.start].to_vec())
+                    };
+
+                    self.output.extend_from_slice(&gap);
+                    match *edit {
+                        Edit::Replace(i, j) => self.replace(&original[i], &modified[j], extent),
+                        _ => self
+                            .output
+                            .extend_from_slice(&self.source[extent.start..extent.end]),
+                    }
+
+                    cursor = extent.end;
+                    emitted = true;
+                }
+                Edit::Delete(i) => {
+                    let extent = extents[i];
+                    let gap = detach(&self.source[cursor..extent.start]);
+                    let comments = gap.iter().any(|byte| !byte.is_ascii_whitespace());
+
+                    if (!emitted || comments) && carry.is_none() && !taken {
+                        carry = Some(gap.to_vec());
+                    }
+
+                    cursor = extent.end;
+                }
+                Edit::Insert(j) => {
+                    let next = edits[index..].iter().find_map(|edit| match edit {
+                        Edit::Keep(i) | Edit::Replace(i, _) => Some(extents[*i]),
+                        _ => None,
+                    });
+
+                    let gap = match (carry.take(), next) {
+                        (Some(gap), _) => gap,
+                        (None, Some(next)) if !emitted && !taken => {
+                            taken = true;
+
+                            self.source[cursor..next.start].to_vec()
+                        }
+                        _ => separator.clone(),
+                    };
+
+                    self.output.extend_from_slice(&gap);
+                    self.print(&modified[j], &indentation);
+                    emitted = true;
+                }
+            }
+        }
+
+        let trailing = &self.source[cursor.min(end)..end];
+        match closing {
+            Some(closing) if emitted && !trailing.contains(&b'\n') => {
+                self.output.push(b'\n');
+                self.output.extend_from_slice(closing);
+            }
+            _ => self.output.extend_from_slice(trailing),
+        }
+    }
+
+    /// Print a modified item in place of the original one.
+    fn replace<T: Item>(&mut self, original: &T, modified: &T, extent: Extent) {
+        if !original.splice(modified, self, extent) {
+            let indentation = self.indentation(extent.start).unwrap_or_default().to_vec();
+
+            self.print(modified, &indentation);
+            self.output
+                .extend_from_slice(&self.source[extent.trailing..extent.end]);
+        }
+    }
+
+    fn print<T: Item>(&mut self, item: &T, indentation: &[u8]) {
+        let margin = String::from_utf8_lossy(indentation);
+
+        self.output
+            .extend_from_slice(item.print(self.options, &margin).as_bytes());
+    }
+
+    /// Print a modified item whose body is the only change, see [`Extent::with`].
+    fn list_within<T: Item>(&mut self, extent: Extent, original: &[T], modified: &[T]) {
+        let body = match extent.body {
+            Some(body) => body,
+            None => unreachable!(),
+        };
+        let closing = self.indentation(extent.start).unwrap_or_default().to_vec();
+
+        self.output
+            .extend_from_slice(&self.source[extent.start..body.start]);
+        self.list(
+            original,
+            modified,
+            body.start,
+            body.end,
+            body.closed.then_some(&closing[..]),
+        );
+        self.output
+            .extend_from_slice(&self.source[body.end..extent.end]);
+    }
+
+    /// The extent of an original item, which starts at or after the given position, and
+    /// ends before the given bound.
+    ///
+    /// Not all tokens have a span in the tree, e.g. the closing brace of a `switch` or the
+    /// semicolon of a `use`, so the item extends past its last span to the end of the
+    /// statement it belongs to.
+    fn extent<T: Item>(
+        &self,
+        item: &T,
+        spans: &[usize],
+        cursor: usize,
+        bound: usize,
+        end: usize,
+    ) -> Extent {
+        // Comments between items are not part of the next item, unless they are attached to it.
+        let first = self
+            .tokens
+            .partition_point(|token| token.span.position < cursor);
+        let first = (first..self.tokens.len())
+            .find(|index| !is_comment(&self.tokens[*index].kind))
+            .unwrap_or(self.tokens.len() - 1);
+
+        let start = spans
+            .iter()
+            .copied()
+            .filter(|position| *position >= cursor)
+            .chain([self.tokens[first].span.position])
+            .min()
+            .unwrap_or(cursor);
+
+        let (trailing, last) = if item.extends_to_end() {
+            (end, end)
+        } else {
+            // Items without any span are a single token, e.g. inline HTML.
+            let mut last = match spans
+                .iter()
+                .copied()
+                .filter(|position| *position >= cursor && *position < bound)
+                .max()
+            {
+                Some(position) => self
+                    .tokens
+                    .partition_point(|token| token.span.position <= position)
+                    .saturating_sub(1),
+                None => first,
+            };
+
+            let mut nesting = Nesting::default();
+            let from = self
+                .tokens
+                .partition_point(|token| token.span.position < start);
+            for token in self.tokens.get(from..=last).unwrap_or_default() {
+                nesting.step(&token.kind);
+            }
+
+            while let Some(token) = self.tokens.get(last + 1) {
+                if token.span.position >= bound || !nesting.continues(&token.kind) {
+                    break;
+                }
+
+                nesting.step(&token.kind);
+                last += 1;
+            }
+
+            let trailing = self.end(last).min(end);
+            while let Some(token) = self.tokens.get(last + 1) {
+                if token.span.position >= end
+                    || !is_comment(&token.kind)
+                    || self.source[self.end(last)..token.span.position].contains(&b'\n')
+                {
+                    break;
+                }
+
+                last += 1;
+            }
+
+            (trailing, self.end(last).min(end))
+        };
+
+        Extent {
+            start,
+            end: last.max(start),
+            trailing: trailing.max(start),
+            body: None,
+        }
+    }
+
+    fn is_comment_at(&self, position: usize) -> bool {
+        let index = self
+            .tokens
+            .partition_point(|token| token.span.position < position);
+
+        self.tokens
+            .get(index)
+            .is_some_and(|token| is_comment(&token.kind))
+    }
+
+    /// The position following the token at the given index.
+    fn end(&self, index: usize) -> usize {
+        let token = &self.tokens[index];
+        let next = self
+            .tokens
+            .get(index + 1)
+            .map_or(self.source.len(), |next| next.span.position);
+
+        if token.kind == TokenKind::InlineHtml {
+            return next;
+        }
+
+        let mut end = next;
+        while end > token.span.position + 1 && self.source[end - 1].is_ascii_whitespace() {
+            end -= 1;
+        }
+
+        end
+    }
+
+    /// The indentation of the line starting with the given position, if nothing else precedes
+    /// it on the line.
+    fn indentation(&self, position: usize) -> Option<&'a [u8]> {
+        let source = self.source;
+        let line = source[..position]
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |index| index + 1);
+        let indentation = &source[line..position];
+
+        indentation
+            .iter()
+            .all(|byte| *byte == b' ' || *byte == b'\t')
+            .then_some(indentation)
+    }
+}
+
+/// The edits turning the original list into the modified one, keeping the longest common
+/// subsequence of equal items.
+fn diff<T: PartialEq>(original: &[T], modified: &[T]) -> Vec<Edit> {
+    let (n, m) = (original.len(), modified.len());
+    let mut lengths = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if original[i] == modified[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    let (mut deleted, mut inserted) = (Vec::new(), Vec::new());
+    loop {
+        let done = i == n || j == m;
+        if done || original[i] == modified[j] {
+            deleted.extend(i..if done { n } else { i });
+            inserted.extend(j..if done { m } else { j });
+
+            // Unmatched items between two kept ones are replaced pairwise.
+            let pairs = deleted.len().min(inserted.len());
+            for (i, j) in deleted.iter().zip(&inserted) {
+                edits.push(Edit::Replace(*i, *j));
+            }
+            edits.extend(deleted.drain(..).skip(pairs).map(Edit::Delete));
+            edits.extend(inserted.drain(..).skip(pairs).map(Edit::Insert));
+
+            if done {
+                break;
+            }
+
+            edits.push(Edit::Keep(i));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            deleted.push(i);
+            i += 1;
+        } else {
+            inserted.push(j);
+            j += 1;
+        }
+    }
+
+    edits
+}
+
+/// The part of the whitespace and comments preceding a node that is not attached to it.
+///
+/// Comments are attached to the node following them, unless a blank line separates them.
+fn detach(gap: &[u8]) -> &[u8] {
+    if gap.iter().all(u8::is_ascii_whitespace) {
+        return gap;
+    }
+
+    // The rest of the line of the previous node is never attached to the next one.
+    let first = gap
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .map_or(0, |index| index + 1);
+    let (mut end, mut line) = (first, first);
+    for (index, byte) in gap.iter().enumerate().skip(first) {
+        if *byte == b'\n' {
+            if gap[line..index].iter().all(u8::is_ascii_whitespace) {
+                end = index + 1;
+            }
+
+            line = index + 1;
+        }
+    }
+
+    &gap[..end]
+}
+
+/// The nesting of brackets and alternative syntax blocks, e.g. `if (...): ... endif;`, at
+/// a token of a statement.
+#[derive(Default)]
+struct Nesting {
+    brackets: usize,
+    blocks: usize,
+    /// Whether each open parenthesis follows a control structure keyword.
+    parentheses: Vec<bool>,
+    previous: Option<TokenKind>,
+    /// Whether the previous token closes the condition of a control structure.
+    condition: bool,
+}
+
+impl Nesting {
+    fn step(&mut self, kind: &TokenKind) {
+        if is_comment(kind) {
+            return;
+        }
+
+        let mut condition = false;
+        match kind {
+            TokenKind::LeftParen => {
+                self.brackets += 1;
+                self.parentheses.push(matches!(
+                    self.previous,
+                    Some(
+                        TokenKind::If
+                            | TokenKind::While
+                            | TokenKind::For
+                            | TokenKind::Foreach
+                            | TokenKind::Switch
+                            | TokenKind::Declare
+                    )
+                ));
+            }
+            TokenKind::RightParen => {
+                self.brackets = self.brackets.saturating_sub(1);
+                condition = self.parentheses.pop().unwrap_or(false);
+            }
+            TokenKind::LeftBrace
+            | TokenKind::LeftBracket
+            | TokenKind::CurlyOpen
+            | TokenKind::DollarLeftBrace
+            | TokenKind::Attribute => self.brackets += 1,
+            TokenKind::RightBrace | TokenKind::RightBracket => {
+                self.brackets = self.brackets.saturating_sub(1)
+            }
+            TokenKind::Colon if self.condition && self.brackets == 0 => self.blocks += 1,
+            TokenKind::EndDeclare
+            | TokenKind::EndFor
+            | TokenKind::EndForeach
+            | TokenKind::EndIf
+            | TokenKind::EndSwitch
+            | TokenKind::EndWhile => self.blocks = self.blocks.saturating_sub(1),
+            _ => {}
+        }
+
+        self.condition = condition;
+        self.previous = Some(kind.clone());
+    }
+
+    /// Whether the given token, following the previous one, belongs to the same statement.
+    fn continues(&self, kind: &TokenKind) -> bool {
+        if self.brackets > 0 || self.blocks > 0 {
+            return true;
+        }
+
+        match (&self.previous, kind) {
+            (Some(TokenKind::SemiColon), _) => false,
+            (_, TokenKind::SemiColon) => true,
+            (
+                Some(
+                    TokenKind::RightParen
+                    | TokenKind::Try
+                    | TokenKind::Finally
+                    | TokenKind::Else
+                    | TokenKind::Do,
+                ),
+                TokenKind::LeftBrace,
+            ) => true,
+            (
+                Some(TokenKind::RightBrace),
+                TokenKind::Catch | TokenKind::Finally | TokenKind::Else | TokenKind::ElseIf,
+            ) => true,
+            (Some(TokenKind::Catch), TokenKind::LeftParen) => true,
+            _ => false,
+        }
+    }
+}
+
+/// The positions of the spans of a node.
+fn positions<T: ?Sized + Serialize>(item: &T) -> Vec<usize> {
+    let mut spans = Vec::new();
+    if let Ok(value) = serde_json::to_value(item) {
+        collect(&value, &mut spans);
+    }
+
+    spans.iter().map(|span| span.position).collect()
+}
+
+/// Collect the spans of a serialized node.
+fn collect(value: &Value, spans: &mut Vec<Span>) {
+    match value {
+        Value::Object(object) => {
+            let position = object.get("position").and_then(Value::as_u64);
+            let line = object.get("line").and_then(Value::as_u64);
+            let column = object.get("column").and_then(Value::as_u64);
+            let synthetic = object.get("synthetic").and_then(Value::as_bool);
+
+            match (position, line, column) {
+                (Some(position), Some(line), Some(column)) => {
+                    if synthetic != Some(true) {
+                        spans.push(Span::new(line as usize, column as usize, position as usize));
+                    }
+                }
+                _ => {
+                    for value in object.values() {
+                        collect(value, spans);
+                    }
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                collect(value, spans);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_comment(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::SingleLineComment
+            | TokenKind::HashMarkComment
+            | TokenKind::MultiLineComment
+            | TokenKind::DocumentComment
+    )
+}
+
+/// Splice the body of a modified node when it is the only change, by comparing the
+/// original node, given the modified body, to the modified node.
+macro_rules! splice_body {
+    ($printer:expr, $extent:expr, $original:expr, $modified:expr, $($list:ident).+) => {{
+        let mut header = $original.clone();
+        header.$($list).+ = $modified.$($list).+.clone();
+
+        if header != *$modified {
+            return false;
+        }
+
+        $printer.list_within(
+            $extent,
+            &$original.$($list).+,
+            &$modified.$($list).+,
+        );
+
+        true
+    }};
+}
+
+impl Item for Statement {
+    fn print(&self, options: &PrinterOptions, margin: &str) -> String {
+        print_statement_within(self, options, margin)
+    }
+
+    fn splice(&self, modified: &Self, printer: &mut Preserving, extent: Extent) -> bool {
+        match (self, modified) {
+            (Statement::Function(original), Statement::Function(modified)) => {
+                let braces = (original.body.left_brace, original.body.right_brace);
+                splice_body!(
+                    printer,
+                    extent.braced(braces),
+                    original,
+                    modified,
+                    body.statements
+                )
+            }
+            (Statement::Class(original), Statement::Class(modified)) => {
+                let braces = (original.body.left_brace, original.body.right_brace);
+                splice_body!(
+                    printer,
+                    extent.braced(braces),
+                    original,
+                    modified,
+                    body.members
+                )
+            }
+            (Statement::Trait(original), Statement::Trait(modified)) => {
+                let braces = (original.body.left_brace, original.body.right_brace);
+                splice_body!(
+                    printer,
+                    extent.braced(braces),
+                    original,
+                    modified,
+                    body.members
+                )
+            }
+            (Statement::Interface(original), Statement::Interface(modified)) => {
+                let braces = (original.body.left_brace, original.body.right_brace);
+                splice_body!(
+                    printer,
+                    extent.braced(braces),
+                    original,
+                    modified,
+                    body.members
+                )
+            }
+            (Statement::UnitEnum(original), Statement::UnitEnum(modified)) => {
+                let braces = (original.body.left_brace, original.body.right_brace);
+                splice_body!(
+                    printer,
+                    extent.braced(braces),
+                    original,
+                    modified,
+                    body.members
+                )
+            }
+            (Statement::BackedEnum(original), Statement::BackedEnum(modified)) => {
+                let braces = (original.body.left_brace, original.body.right_brace);
+                splice_body!(
+                    printer,
+                    extent.braced(braces),
+                    original,
+                    modified,
+                    body.members
+                )
+            }
+            (
+                Statement::Namespace(NamespaceStatement::Braced(original)),
+                Statement::Namespace(NamespaceStatement::Braced(modified)),
+            ) => {
+                let braces = (original.body.start, original.body.end);
+                splice_body!(
+                    printer,
+                    extent.braced(braces),
+                    original,
+                    modified,
+                    body.statements
+                )
+            }
+            (
+                Statement::Namespace(NamespaceStatement::Unbraced(original)),
+                Statement::Namespace(NamespaceStatement::Unbraced(modified)),
+            ) => {
+                // The statements of an unbraced namespace extend to the next namespace.
+                let body = Body {
+                    start: original.end.position + 1,
+                    end: extent.end,
+                    closed: false,
+                };
+                splice_body!(printer, extent.with(body), original, modified, statements)
+            }
+            (Statement::Block(original), Statement::Block(modified)) => {
+                let braces = (original.left_brace, original.right_brace);
+                splice_body!(
+                    printer,
+                    extent.braced(braces),
+                    original,
+                    modified,
+                    statements
+                )
+            }
+            _ => false,
+        }
+    }
+
+    fn positions(&self) -> Vec<usize> {
+        let mut positions = positions(self);
+
+        // The end of a `try` statement and of its blocks is the token following them.
+        if let Statement::Try(statement) = self {
+            let ends = statement
+                .catches
+                .iter()
+                .map(|catch| catch.end)
+                .chain(statement.finally.iter().map(|finally| finally.end))
+                .chain([statement.end])
+                .map(|span| span.position)
+                .collect::<Vec<usize>>();
+
+            positions.retain(|position| !ends.contains(position));
+        }
+
+        positions
+    }
+
+    fn extends_to_end(&self) -> bool {
+        match self {
+            Statement::HaltCompiler(_) => true,
+            Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => namespace
+                .statements
+                .last()
+                .is_some_and(|statement| statement.extends_to_end()),
+            _ => false,
+        }
+    }
+}
+
+/// Implement [`Item`] for the members of a class-like, splicing the bodies of the given
+/// method variants.
+macro_rules! member_item {
+    ($member:ident) => {
+        impl Item for $member {
+            fn print(&self, options: &PrinterOptions, margin: &str) -> String {
+                print_member_within(Member::from(self), options, margin)
+            }
+        }
+    };
+    ($member:ident, $($method:ident),+) => {
+        impl Item for $member {
+            fn print(&self, options: &PrinterOptions, margin: &str) -> String {
+                print_member_within(Member::from(self), options, margin)
+            }
+
+            fn splice(&self, modified: &Self, printer: &mut Preserving, extent: Extent) -> bool {
+                match (self, modified) {
+                    $(($member::$method(original), $member::$method(modified)) => {
+                        let braces = (original.body.left_brace, original.body.right_brace);
+                        splice_body!(printer, extent.braced(braces), original, modified, body.statements)
+                    })+
+                    _ => false,
+                }
+            }
+        }
+    };
+}
+
+member_item!(ClassMember, ConcreteMethod, ConcreteConstructor);
+member_item!(TraitMember, ConcreteMethod, ConcreteConstructor);
+member_item!(InterfaceMember);
+member_item!(UnitEnumMember, Method);
+member_item!(BackedEnumMember, Method);
+
+#[cfg(test)]
+mod tests {
+    use super::print_preserving;
+    use crate::parser::ast::classes::ClassMember;
+    use crate::parser::ast::Statement;
+    use crate::printer::PrinterOptions;
+
+    fn preserve(source: &str, edit: impl FnOnce(&mut Vec<Statement>)) -> String {
+        let original = crate::parse(source).unwrap();
+        let mut modified = original.clone();
+        edit(&mut modified);
+
+        print_preserving(source, &original, &modified, &PrinterOptions::new())
+    }
+
+    #[test]
+    fn test_statements() {
+        let source = "<?php\n\n// first\n$a   =   1;\n\n$b=2; // second\n\nfoo( $a,$b );\n";
+
+        assert_eq!(
+            preserve(source, |program| {
+                program.remove(2);
+            }),
+            "<?php\n\n// first\n$a   =   1;\n\nfoo( $a,$b );\n"
+        );
+
+        assert_eq!(
+            preserve(source, |program| {
+                program.remove(1);
+            }),
+            "<?php\n\n$b=2; // second\n\nfoo( $a,$b );\n"
+        );
+
+        let statement = crate::parse("<?php bar( 1 );").unwrap().remove(1);
+        assert_eq!(
+            preserve(source, |program| program[2] = statement.clone()),
+            "<?php\n\n// first\n$a   =   1;\n\nbar(1); // second\n\nfoo( $a,$b );\n"
+        );
+
+        // Comments separated from a removed statement by a blank line are kept.
+        assert_eq!(
+            preserve(
+                "<?php\n\n// License.\n\ndeclare(strict_types=1);\n\n$a = 1;\n",
+                |program| {
+                    program.remove(1);
+                }
+            ),
+            "<?php\n\n// License.\n\n$a = 1;\n"
+        );
+
+        let source = "<?php\n\n$a   =   1;\n$b=2;\n";
+
+        assert_eq!(
+            preserve(source, |program| program.insert(2, statement.clone())),
+            "<?php\n\n$a   =   1;\nbar(1);\n$b=2;\n"
+        );
+        assert_eq!(
+            preserve(source, |program| program[2] = statement.clone()),
+            "<?php\n\n$a   =   1;\nbar(1);\n"
+        );
+        assert_eq!(
+            preserve(source, |program| program.push(statement.clone())),
+            "<?php\n\n$a   =   1;\n$b=2;\nbar(1);\n"
+        );
+    }
+
+    #[test]
+    fn test_members() {
+        let source = "<?php
+
+class A   {
+    const B=1;
+
+    public function foo( $a ) {
+        if($a){ return 1; }
+        return   2;
+    }
+}
+";
+
+        assert_eq!(
+            preserve(source, |program| {
+                if let Statement::Class(class) = &mut program[1] {
+                    if let ClassMember::ConcreteMethod(method) = &mut class.body.members[1] {
+                        method.body.statements.pop();
+                    }
+                }
+            }),
+            "<?php
+
+class A   {
+    const B=1;
+
+    public function foo( $a ) {
+        if($a){ return 1; }
+    }
+}
+"
+        );
+
+        assert_eq!(
+            preserve(source, |program| {
+                if let Statement::Class(class) = &mut program[1] {
+                    class.body.members.remove(0);
+                    class.body.members.push(class.body.members[0].clone());
+                }
+            }),
+            "<?php
+
+class A   {
+    public function foo( $a ) {
+        if($a){ return 1; }
+        return   2;
+    }
+    public function foo($a)
+    {
+        if ($a) {
+            return 1;
+        }
+        return 2;
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_extents() {
+        let source = "<?php
+use A\\{B, C};
+static $a;
+switch ($a) {
+    case 1;
+    default:
+}
+try {} catch (E $e) {}
+if ($a): echo 1; else: echo 2; endif;
+do { } while ($a);
+global $b;
+";
+
+        for index in 1..8 {
+            let expected = source
+                .split_inclusive('\n')
+                .enumerate()
+                .filter(|(line, _)| match index {
+                    3 => !(3..=6).contains(line),
+                    index if index > 3 => *line != index + 3,
+                    index => *line != index,
+                })
+                .map(|(_, line)| line)
+                .collect::<String>();
+
+            assert_eq!(
+                preserve(source, |program| {
+                    program.remove(index);
+                }),
+                expected,
+                "removing statement {}",
+                index
+            );
+        }
+    }
+}
@@ -0,0 +1,2863 @@
+use crate::lexer::token::Span;
+use crate::parser::ast::arguments::Argument;
+use crate::parser::ast::arguments::ArgumentList;
+use crate::parser::ast::arguments::SingleArgument;
+use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::classes::AnonymousClass;
+use crate::parser::ast::classes::AnonymousClassMember;
+use crate::parser::ast::classes::ClassExtends;
+use crate::parser::ast::classes::ClassImplements;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::comments::Comment;
+use crate::parser::ast::comments::CommentFormat;
+use crate::parser::ast::comments::CommentGroup;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::constant::ConstantEntry;
+use crate::parser::ast::control_flow::IfStatement;
+use crate::parser::ast::control_flow::IfStatementBody;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::declares::DeclareBody;
+use crate::parser::ast::enums::BackedEnumCase;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::BackedEnumType;
+use crate::parser::ast::enums::UnitEnumCase;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::functions::AbstractConstructor;
+use crate::parser::ast::functions::AbstractMethod;
+use crate::parser::ast::functions::ArrowFunction;
+use crate::parser::ast::functions::Closure;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::ConstructorParameterList;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::functions::ReturnType;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::loops::ForStatementBody;
+use crate::parser::ast::loops::ForeachStatementBody;
+use crate::parser::ast::loops::ForeachStatementIterator;
+use crate::parser::ast::loops::Level;
+use crate::parser::ast::loops::WhileStatementBody;
+use crate::parser::ast::modifiers::ClassModifier;
+use crate::parser::ast::modifiers::ConstantModifier;
+use crate::parser::ast::modifiers::MethodModifier;
+use crate::parser::ast::modifiers::PropertyModifier;
+use crate::parser::ast::modifiers::VisibilityModifier;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::operators::ArithmeticOperation;
+use crate::parser::ast::operators::AssignmentOperation;
+use crate::parser::ast::operators::BitwiseOperation;
+use crate::parser::ast::operators::ComparisonOperation;
+use crate::parser::ast::operators::LogicalOperation;
+use crate::parser::ast::properties::Property;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::properties::PropertyHookBody;
+use crate::parser::ast::properties::PropertyHookList;
+use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::traits::TraitUsageAdaptation;
+use crate::parser::ast::try_block::CatchType;
+use crate::parser::ast::try_block::TryStatement;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::CastKind;
+use crate::parser::ast::Ending;
+use crate::parser::ast::ExitKind;
+use crate::parser::ast::Expression;
+use crate::parser::ast::InterpolationSyntax;
+use crate::parser::ast::ListEntry;
+use crate::parser::ast::MagicConstant;
+use crate::parser::ast::MatchExpression;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StringPart;
+use crate::parser::ast::Use;
+use crate::parser::ast::UseKind;
+
+/// The indentation of the printed code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indentation {
+    /// The given number of spaces per level.
+    Spaces(usize),
+    /// A tab per level.
+    Tabs,
+}
+
+impl Indentation {
+    /// The indentation of a single level.
+    pub(super) fn unit(&self) -> String {
+        match self {
+            Indentation::Spaces(width) => " ".repeat(*width),
+            Indentation::Tabs => "\t".to_string(),
+        }
+    }
+}
+
+/// The placement of opening braces.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BraceStyle {
+    /// On their own line for classes, functions and methods, and on the same line for
+    /// control structures, as in PSR-12.
+    #[default]
+    Psr12,
+    /// Always on the same line.
+    SameLine,
+    /// Always on their own line.
+    NextLine,
+}
+
+impl std::str::FromStr for BraceStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "psr12" => Ok(Self::Psr12),
+            "same-line" => Ok(Self::SameLine),
+            "next-line" => Ok(Self::NextLine),
+            _ => Err(format!(
+                "unknown brace style `{}`, expecting `psr12`, `same-line` or `next-line`",
+                s
+            )),
+        }
+    }
+}
+
+/// The options of the pretty printer, see [`print_program`].
+///
+/// Closures, anonymous classes and `match` arms are always opened on the same line,
+/// whatever the brace style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrinterOptions {
+    pub indentation: Indentation,
+    pub brace_style: BraceStyle,
+}
+
+impl Default for PrinterOptions {
+    fn default() -> Self {
+        Self {
+            indentation: Indentation::Spaces(4),
+            brace_style: BraceStyle::default(),
+        }
+    }
+}
+
+impl PrinterOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_indentation(mut self, indentation: Indentation) -> Self {
+        self.indentation = indentation;
+
+        self
+    }
+
+    pub fn with_brace_style(mut self, brace_style: BraceStyle) -> Self {
+        self.brace_style = brace_style;
+
+        self
+    }
+}
+
+/// Print a program back to PHP code.
+///
+/// The layout of the original code is not preserved, only its syntax: comments attached to
+/// declarations, parentheses, and the alternative syntax of control structures are kept, while
+/// whitespace is normalized.
+///
+/// ```
+/// use php_parser_rs::printer::print_program;
+/// use php_parser_rs::printer::PrinterOptions;
+///
+/// let program = php_parser_rs::parse("<?php if($a){echo 1+2;}").unwrap();
+///
+/// assert_eq!(
+///     print_program(&program, &PrinterOptions::new()),
+///     "<?php\n\nif ($a) {\n    echo 1 + 2;\n}\n"
+/// );
+/// ```
+pub fn print_program(program: &[Statement], options: &PrinterOptions) -> String {
+    let mut printer = Printer::new(options);
+
+    printer.statements(program);
+    if !ends_raw(program) {
+        printer.output.push('\n');
+    }
+
+    printer.output
+}
+
+/// Whether the program ends with content printed as is, which must not be followed by
+/// a new line, e.g. inline HTML or the data after `__halt_compiler();`.
+fn ends_raw(statements: &[Statement]) -> bool {
+    match statements.last() {
+        None | Some(Statement::InlineHtml(_) | Statement::HaltCompiler(_)) => true,
+        Some(Statement::Namespace(NamespaceStatement::Unbraced(namespace))) => {
+            !namespace.statements.is_empty() && ends_raw(&namespace.statements)
+        }
+        _ => false,
+    }
+}
+
+/// Print a single statement, without a trailing new line.
+pub fn print_statement(statement: &Statement, options: &PrinterOptions) -> String {
+    let mut printer = Printer::new(options);
+
+    printer.statement(statement);
+
+    printer.output
+}
+
+/// Print a single statement, indenting its lines but the first one with the given margin.
+pub(super) fn print_statement_within(
+    statement: &Statement,
+    options: &PrinterOptions,
+    margin: &str,
+) -> String {
+    let mut printer = Printer::new(options);
+
+    printer.margin = margin;
+    printer.statement(statement);
+
+    printer.output
+}
+
+/// Print a single member of a class-like, see [`print_statement_within`].
+pub(super) fn print_member_within(
+    member: Member,
+    options: &PrinterOptions,
+    margin: &str,
+) -> String {
+    let mut printer = Printer::new(options);
+
+    printer.margin = margin;
+    printer.member(member);
+
+    printer.output
+}
+
+/// Print a single expression, see [`print_statement_within`].
+pub(crate) fn print_expression_within(
+    expression: &Expression,
+    options: &PrinterOptions,
+    margin: &str,
+) -> String {
+    let mut printer = Printer::new(options);
+
+    printer.margin = margin;
+    printer.expression(expression);
+
+    printer.output
+}
+
+/// Print a single expression.
+///
+/// Parentheses are added where the precedence of the operators requires them, e.g. for an
+/// addition nested in a multiplication that was built without a parenthesized node.
+pub fn print_expression(expression: &Expression, options: &PrinterOptions) -> String {
+    let mut printer = Printer::new(options);
+
+    printer.expression(expression);
+
+    printer.output
+}
+
+// The binding power of expressions, from the loosest to the tightest.
+const OR: u8 = 1;
+const XOR: u8 = 2;
+const AND: u8 = 3;
+const PRINT: u8 = 4;
+const YIELD: u8 = 5;
+const YIELD_FROM: u8 = 6;
+const ASSIGNMENT: u8 = 7;
+const TERNARY: u8 = 8;
+const COALESCE: u8 = 9;
+const BOOLEAN_OR: u8 = 10;
+const BOOLEAN_AND: u8 = 11;
+const BITWISE_OR: u8 = 12;
+const BITWISE_XOR: u8 = 13;
+const BITWISE_AND: u8 = 14;
+const EQUALITY: u8 = 15;
+const COMPARISON: u8 = 16;
+const PIPE: u8 = 17;
+const CONCAT: u8 = 18;
+const SHIFT: u8 = 19;
+const ADDITIVE: u8 = 20;
+const MULTIPLICATIVE: u8 = 21;
+const NOT: u8 = 22;
+const INSTANCEOF: u8 = 23;
+const UNARY: u8 = 24;
+const POW: u8 = 25;
+const CLONE_OR_NEW: u8 = 26;
+const PRIMARY: u8 = 27;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Associativity {
+    Left,
+    Right,
+    None,
+}
+
+fn precedence(expression: &Expression) -> u8 {
+    match expression {
+        Expression::LogicalOperation(operation) => match operation {
+            LogicalOperation::LogicalOr { .. } => OR,
+            LogicalOperation::LogicalXor { .. } => XOR,
+            LogicalOperation::LogicalAnd { .. } => AND,
+            LogicalOperation::Or { .. } => BOOLEAN_OR,
+            LogicalOperation::And { .. } => BOOLEAN_AND,
+            LogicalOperation::Not { .. } => NOT,
+        },
+        Expression::Print { .. }
+        | Expression::Throw { .. }
+        | Expression::Include { .. }
+        | Expression::IncludeOnce { .. }
+        | Expression::Require { .. }
+        | Expression::RequireOnce { .. }
+        | Expression::ArrowFunction(_) => PRINT,
+        Expression::Yield { .. } => YIELD,
+        Expression::YieldFrom { .. } => YIELD_FROM,
+        Expression::AssignmentOperation(_) => ASSIGNMENT,
+        Expression::Ternary { .. } | Expression::ShortTernary { .. } => TERNARY,
+        Expression::Coalesce { .. } => COALESCE,
+        Expression::BitwiseOperation(operation) => match operation {
+            BitwiseOperation::Or { .. } => BITWISE_OR,
+            BitwiseOperation::Xor { .. } => BITWISE_XOR,
+            BitwiseOperation::And { .. } => BITWISE_AND,
+            BitwiseOperation::LeftShift { .. } | BitwiseOperation::RightShift { .. } => SHIFT,
+            BitwiseOperation::Not { .. } => UNARY,
+        },
+        Expression::ComparisonOperation(operation) => match operation {
+            ComparisonOperation::LessThan { .. }
+            | ComparisonOperation::GreaterThan { .. }
+            | ComparisonOperation::LessThanOrEqual { .. }
+            | ComparisonOperation::GreaterThanOrEqual { .. } => COMPARISON,
+            _ => EQUALITY,
+        },
+        Expression::Pipe { .. } => PIPE,
+        Expression::Concat { .. } => CONCAT,
+        Expression::ArithmeticOperation(operation) => match operation {
+            ArithmeticOperation::Addition { .. } | ArithmeticOperation::Subtraction { .. } => {
+                ADDITIVE
+            }
+            ArithmeticOperation::Multiplication { .. }
+            | ArithmeticOperation::Division { .. }
+            | ArithmeticOperation::Modulo { .. } => MULTIPLICATIVE,
+            ArithmeticOperation::Exponentiation { .. } => POW,
+            _ => UNARY,
+        },
+        Expression::Instanceof { .. } => INSTANCEOF,
+        Expression::Cast { .. } | Expression::ErrorSuppress { .. } => UNARY,
+        Expression::Clone { .. } | Expression::New { .. } | Expression::Closure(_) => CLONE_OR_NEW,
+        _ => PRIMARY,
+    }
+}
+
+/// The operator, binding power and associativity of a binary operation.
+fn binary(expression: &Expression) -> Option<(&Expression, &'static str, &Expression)> {
+    Some(match expression {
+        Expression::ArithmeticOperation(operation) => match operation {
+            ArithmeticOperation::Addition { left, right, .. } => (left, "+", right),
+            ArithmeticOperation::Subtraction { left, right, .. } => (left, "-", right),
+            ArithmeticOperation::Multiplication { left, right, .. } => (left, "*", right),
+            ArithmeticOperation::Division { left, right, .. } => (left, "/", right),
+            ArithmeticOperation::Modulo { left, right, .. } => (left, "%", right),
+            ArithmeticOperation::Exponentiation { left, right, .. } => (left, "**", right),
+            _ => return None,
+        },
+        Expression::AssignmentOperation(operation) => match operation {
+            AssignmentOperation::Assign { left, right, .. } => (left, "=", right),
+            AssignmentOperation::AssignByReference { left, right, .. } => (left, "= &", right),
+            AssignmentOperation::Addition { left, right, .. } => (left, "+=", right),
+            AssignmentOperation::Subtraction { left, right, .. } => (left, "-=", right),
+            AssignmentOperation::Multiplication { left, right, .. } => (left, "*=", right),
+            AssignmentOperation::Division { left, right, .. } => (left, "/=", right),
+            AssignmentOperation::Modulo { left, right, .. } => (left, "%=", right),
+            AssignmentOperation::Exponentiation { left, right, .. } => (left, "**=", right),
+            AssignmentOperation::Concat { left, right, .. } => (left, ".=", right),
+            AssignmentOperation::BitwiseAnd { left, right, .. } => (left, "&=", right),
+            AssignmentOperation::BitwiseOr { left, right, .. } => (left, "|=", right),
+            AssignmentOperation::BitwiseXor { left, right, .. } => (left, "^=", right),
+            AssignmentOperation::LeftShift { left, right, .. } => (left, "<<=", right),
+            AssignmentOperation::RightShift { left, right, .. } => (left, ">>=", right),
+            AssignmentOperation::Coalesce { left, right, .. } => (left, "??=", right),
+        },
+        Expression::BitwiseOperation(operation) => match operation {
+            BitwiseOperation::And { left, right, .. } => (left, "&", right),
+            BitwiseOperation::Or { left, right, .. } => (left, "|", right),
+            BitwiseOperation::Xor { left, right, .. } => (left, "^", right),
+            BitwiseOperation::LeftShift { left, right, .. } => (left, "<<", right),
+            BitwiseOperation::RightShift { left, right, .. } => (left, ">>", right),
+            BitwiseOperation::Not { .. } => return None,
+        },
+        Expression::ComparisonOperation(operation) => match operation {
+            ComparisonOperation::Equal { left, right, .. } => (left, "==", right),
+            ComparisonOperation::Identical { left, right, .. } => (left, "===", right),
+            ComparisonOperation::NotEqual { left, right, .. } => (left, "!=", right),
+            ComparisonOperation::AngledNotEqual { left, right, .. } => (left, "<>", right),
+            ComparisonOperation::NotIdentical { left, right, .. } => (left, "!==", right),
+            ComparisonOperation::LessThan { left, right, .. } => (left, "<", right),
+            ComparisonOperation::GreaterThan { left, right, .. } => (left, ">", right),
+            ComparisonOperation::LessThanOrEqual { left, right, .. } => (left, "<=", right),
+            ComparisonOperation::GreaterThanOrEqual { left, right, .. } => (left, ">=", right),
+            ComparisonOperation::Spaceship { left, right, .. } => (left, "<=>", right),
+        },
+        Expression::LogicalOperation(operation) => match operation {
+            LogicalOperation::And { left, right, .. } => (left, "&&", right),
+            LogicalOperation::Or { left, right, .. } => (left, "||", right),
+            LogicalOperation::LogicalAnd { left, right, .. } => (left, "and", right),
+            LogicalOperation::LogicalOr { left, right, .. } => (left, "or", right),
+            LogicalOperation::LogicalXor { left, right, .. } => (left, "xor", right),
+            LogicalOperation::Not { .. } => return None,
+        },
+        Expression::Concat { left, right, .. } => (left, ".", right),
+        Expression::Coalesce { lhs, rhs, .. } => (lhs, "??", rhs),
+        Expression::Instanceof { left, right, .. } => (left, "instanceof", right),
+        Expression::Pipe { left, right, .. } => (left, "|>", right),
+        _ => return None,
+    })
+}
+
+fn associativity(expression: &Expression) -> Associativity {
+    match expression {
+        Expression::AssignmentOperation(_)
+        | Expression::Coalesce { .. }
+        | Expression::ArithmeticOperation(ArithmeticOperation::Exponentiation { .. }) => {
+            Associativity::Right
+        }
+        Expression::ComparisonOperation(_) | Expression::Instanceof { .. } => Associativity::None,
+        _ => Associativity::Left,
+    }
+}
+
+/// Whether the expression starts with a keyword and extends as far right as possible,
+/// e.g. `throw $e` or `fn () => $a`, so it doesn't need parentheses as a right operand.
+fn is_prefix(expression: &Expression) -> bool {
+    matches!(
+        expression,
+        Expression::ArrowFunction(_)
+            | Expression::Throw { .. }
+            | Expression::Include { .. }
+            | Expression::IncludeOnce { .. }
+            | Expression::Require { .. }
+            | Expression::RequireOnce { .. }
+            | Expression::Print { value: Some(_), .. }
+            | Expression::Yield { .. }
+            | Expression::YieldFrom { .. }
+    )
+}
+
+/// Whether the rightmost operand of the expression is a prefix expression, which would
+/// absorb anything printed after it.
+fn open_ended(expression: &Expression) -> bool {
+    if is_prefix(expression) {
+        return true;
+    }
+
+    if let Some((_, _, right)) = binary(expression) {
+        return open_ended(right);
+    }
+
+    match expression {
+        Expression::ArithmeticOperation(
+            ArithmeticOperation::Negative { right, .. }
+            | ArithmeticOperation::Positive { right, .. }
+            | ArithmeticOperation::PreIncrement { right, .. }
+            | ArithmeticOperation::PreDecrement { right, .. },
+        )
+        | Expression::BitwiseOperation(BitwiseOperation::Not { right, .. })
+        | Expression::LogicalOperation(LogicalOperation::Not { right, .. }) => open_ended(right),
+        Expression::ErrorSuppress { expr: right, .. }
+        | Expression::Cast { value: right, .. }
+        | Expression::Clone { target: right, .. }
+        | Expression::Ternary { r#else: right, .. }
+        | Expression::ShortTernary { r#else: right, .. } => open_ended(right),
+        _ => false,
+    }
+}
+
+/// The closing label of a heredoc or nowdoc, which must not start any line of its body.
+fn label(parts: &[StringPart]) -> String {
+    let mut content = Vec::new();
+    for part in parts {
+        if let StringPart::Literal(literal) = part {
+            content.extend_from_slice(&literal.value);
+        }
+    }
+
+    let mut label = String::from("EOT");
+    while content
+        .split(|byte| *byte == b'\n')
+        .any(|line| line.trim_ascii_start().starts_with(label.as_bytes()))
+    {
+        label.push('_');
+    }
+
+    label
+}
+
+/// A member of a class-like body.
+#[derive(Clone, Copy)]
+pub(super) enum Member<'a> {
+    Constant(&'a ClassishConstant),
+    TraitUsage(&'a TraitUsage),
+    Property(&'a Property),
+    VariableProperty(&'a VariableProperty),
+    AbstractMethod(&'a AbstractMethod),
+    AbstractConstructor(&'a AbstractConstructor),
+    ConcreteMethod(&'a ConcreteMethod),
+    ConcreteConstructor(&'a ConcreteConstructor),
+    UnitCase(&'a UnitEnumCase),
+    BackedCase(&'a BackedEnumCase),
+}
+
+impl<'a> Member<'a> {
+    fn is_method(&self) -> bool {
+        matches!(
+            self,
+            Member::AbstractMethod(_)
+                | Member::AbstractConstructor(_)
+                | Member::ConcreteMethod(_)
+                | Member::ConcreteConstructor(_)
+        )
+    }
+
+    // Whether comments precede the member, separating it from the members of its group.
+    fn has_comments(&self) -> bool {
+        let comments = match self {
+            Member::Constant(constant) => &constant.comments,
+            Member::TraitUsage(_) => return false,
+            Member::Property(property) => &property.comments,
+            Member::VariableProperty(property) => &property.comments,
+            Member::AbstractMethod(method) => &method.comments,
+            Member::AbstractConstructor(constructor) => &constructor.comments,
+            Member::ConcreteMethod(method) => &method.comments,
+            Member::ConcreteConstructor(constructor) => &constructor.comments,
+            Member::UnitCase(case) => &case.comments,
+            Member::BackedCase(case) => &case.comments,
+        };
+
+        !comments.comments.is_empty()
+    }
+
+    fn group(&self) -> u8 {
+        match self {
+            Member::TraitUsage(_) => 0,
+            Member::UnitCase(_) | Member::BackedCase(_) => 1,
+            Member::Constant(_) => 2,
+            Member::Property(_) | Member::VariableProperty(_) => 3,
+            _ => 4,
+        }
+    }
+}
+
+impl<'a> From<&'a ClassMember> for Member<'a> {
+    fn from(member: &'a ClassMember) -> Self {
+        match member {
+            ClassMember::Constant(constant) => Member::Constant(constant),
+            ClassMember::TraitUsage(usage) => Member::TraitUsage(usage),
+            ClassMember::Property(property) => Member::Property(property),
+            ClassMember::VariableProperty(property) => Member::VariableProperty(property),
+            ClassMember::AbstractMethod(method) => Member::AbstractMethod(method),
+            ClassMember::AbstractConstructor(constructor) => {
+                Member::AbstractConstructor(constructor)
+            }
+            ClassMember::ConcreteMethod(method) => Member::ConcreteMethod(method),
+            ClassMember::ConcreteConstructor(constructor) => {
+                Member::ConcreteConstructor(constructor)
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a AnonymousClassMember> for Member<'a> {
+    fn from(member: &'a AnonymousClassMember) -> Self {
+        match member {
+            AnonymousClassMember::Constant(constant) => Member::Constant(constant),
+            AnonymousClassMember::TraitUsage(usage) => Member::TraitUsage(usage),
+            AnonymousClassMember::Property(property) => Member::Property(property),
+            AnonymousClassMember::VariableProperty(property) => Member::VariableProperty(property),
+            AnonymousClassMember::ConcreteMethod(method) => Member::ConcreteMethod(method),
+            AnonymousClassMember::ConcreteConstructor(constructor) => {
+                Member::ConcreteConstructor(constructor)
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a TraitMember> for Member<'a> {
+    fn from(member: &'a TraitMember) -> Self {
+        match member {
+            TraitMember::Constant(constant) => Member::Constant(constant),
+            TraitMember::TraitUsage(usage) => Member::TraitUsage(usage),
+            TraitMember::Property(property) => Member::Property(property),
+            TraitMember::VariableProperty(property) => Member::VariableProperty(property),
+            TraitMember::AbstractMethod(method) => Member::AbstractMethod(method),
+            TraitMember::AbstractConstructor(constructor) => {
+                Member::AbstractConstructor(constructor)
+            }
+            TraitMember::ConcreteMethod(method) => Member::ConcreteMethod(method),
+            TraitMember::ConcreteConstructor(constructor) => {
+                Member::ConcreteConstructor(constructor)
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a InterfaceMember> for Member<'a> {
+    fn from(member: &'a InterfaceMember) -> Self {
+        match member {
+            InterfaceMember::Constant(constant) => Member::Constant(constant),
+            InterfaceMember::Constructor(constructor) => Member::AbstractConstructor(constructor),
+            InterfaceMember::Method(method) => Member::AbstractMethod(method),
+            InterfaceMember::Property(property) => Member::Property(property),
+        }
+    }
+}
+
+impl<'a> From<&'a UnitEnumMember> for Member<'a> {
+    fn from(member: &'a UnitEnumMember) -> Self {
+        match member {
+            UnitEnumMember::Case(case) => Member::UnitCase(case),
+            UnitEnumMember::Method(method) => Member::ConcreteMethod(method),
+            UnitEnumMember::Constant(constant) => Member::Constant(constant),
+        }
+    }
+}
+
+impl<'a> From<&'a BackedEnumMember> for Member<'a> {
+    fn from(member: &'a BackedEnumMember) -> Self {
+        match member {
+            BackedEnumMember::Case(case) => Member::BackedCase(case),
+            BackedEnumMember::Method(method) => Member::ConcreteMethod(method),
+            BackedEnumMember::Constant(constant) => Member::Constant(constant),
+        }
+    }
+}
+
+/// The delimiters of an interpolated string, which decide the characters to escape.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Quote {
+    Double,
+    Backtick,
+    Heredoc,
+}
+
+struct Printer<'a> {
+    options: &'a PrinterOptions,
+    output: String,
+    level: usize,
+    /// The indentation of the printed code within its surroundings, added to every line
+    /// but the first one.
+    margin: &'a str,
+}
+
+impl<'a> Printer<'a> {
+    fn new(options: &'a PrinterOptions) -> Self {
+        Self {
+            options,
+            output: String::new(),
+            level: 0,
+            margin: "",
+        }
+    }
+
+    fn write(&mut self, value: &str) {
+        self.output.push_str(value);
+    }
+
+    fn bytes(&mut self, value: &[u8]) {
+        self.output.push_str(&String::from_utf8_lossy(value));
+    }
+
+    fn newline(&mut self) {
+        self.output.push('\n');
+        self.output.push_str(self.margin);
+        for _ in 0..self.level {
+            self.output.push_str(&self.options.indentation.unit());
+        }
+    }
+
+    fn blank_line(&mut self) {
+        self.output.push('\n');
+        self.newline();
+    }
+
+    fn open_brace(&mut self, declaration: bool) {
+        let next_line = match self.options.brace_style {
+            BraceStyle::Psr12 => declaration,
+            BraceStyle::SameLine => false,
+            BraceStyle::NextLine => true,
+        };
+
+        if next_line {
+            self.newline();
+            self.write("{");
+        } else {
+            self.write(" {");
+        }
+    }
+
+    /// Print the keyword following the closing brace of a block, e.g. `else` or `catch`.
+    fn continuation(&mut self, keyword: &str) {
+        if self.output.ends_with('}') && self.options.brace_style != BraceStyle::NextLine {
+            self.write(" ");
+        } else {
+            self.newline();
+        }
+
+        self.write(keyword);
+    }
+
+    /// Print the statements of a block, and its closing brace.
+    fn block(&mut self, statements: &[Statement]) {
+        self.indented(statements);
+        self.newline();
+        self.write("}");
+    }
+
+    fn braced(&mut self, statements: &[Statement], declaration: bool) {
+        self.open_brace(declaration);
+        self.block(statements);
+    }
+
+    fn indented(&mut self, statements: &[Statement]) {
+        self.level += 1;
+        match statements.first() {
+            Some(Statement::ClosingTag(_)) => self.write(" "),
+            Some(_) => self.newline(),
+            None => {}
+        }
+        self.statements(statements);
+        // The opening tag following inline HTML in a block is not part of the AST.
+        if let Some(Statement::InlineHtml(_)) = statements.last() {
+            self.write("<?php");
+        }
+        self.level -= 1;
+    }
+
+    fn statements(&mut self, statements: &[Statement]) {
+        let mut previous: Option<&Statement> = None;
+
+        for statement in statements {
+            if let Some(previous) = previous {
+                self.separate(previous, statement);
+            }
+
+            self.statement(statement);
+            previous = Some(statement);
+        }
+    }
+
+    fn separate(&mut self, previous: &Statement, next: &Statement) {
+        let declaration = |statement: &Statement| {
+            matches!(
+                statement,
+                Statement::Function(_)
+                    | Statement::Class(_)
+                    | Statement::Interface(_)
+                    | Statement::Trait(_)
+                    | Statement::UnitEnum(_)
+                    | Statement::BackedEnum(_)
+                    | Statement::Namespace(_)
+            )
+        };
+        let import =
+            |statement: &Statement| matches!(statement, Statement::Use(_) | Statement::GroupUse(_));
+
+        // Code embedded in a line of HTML, e.g. `<p><?= $a ?></p>`, is kept on that line.
+        let line = &self.output[self.output.rfind('\n').map_or(0, |i| i + 1)..];
+        let embedded = line.contains("<?") && line != "<?php";
+
+        match previous {
+            // Inline HTML is followed by an opening tag, and the text following a closing tag
+            // is part of the output of the script.
+            Statement::InlineHtml(_) => match next {
+                Statement::FullOpeningTag(_)
+                | Statement::ShortOpeningTag(_)
+                | Statement::EchoOpeningTag(_)
+                | Statement::InlineHtml(_) => {}
+                _ => self.write("<?php "),
+            },
+            _ if self.output.ends_with("?>") => {}
+            Statement::EchoOpeningTag(_) | Statement::ShortOpeningTag(_) => self.write(" "),
+            _ if embedded && matches!(next, Statement::ClosingTag(_)) => self.write(" "),
+            Statement::FullOpeningTag(_) if embedded => self.write(" "),
+            Statement::FullOpeningTag(_) if self.level == 0 => self.blank_line(),
+            Statement::Declare(declare) if matches!(declare.body, DeclareBody::Noop { .. }) => {
+                self.blank_line()
+            }
+            _ if declaration(previous) || declaration(next) => self.blank_line(),
+            _ if import(previous) && !import(next) => self.blank_line(),
+            _ => self.newline(),
+        }
+    }
+
+    fn comments(&mut self, comments: &CommentGroup) {
+        for comment in &comments.comments {
+            self.comment(comment);
+            self.newline();
+        }
+    }
+
+    fn comment(&mut self, comment: &Comment) {
+        let content = String::from_utf8_lossy(&comment.content).to_string();
+
+        match comment.format {
+            CommentFormat::SingleLine | CommentFormat::HashMark => self.write(content.trim_end()),
+            CommentFormat::MultiLine | CommentFormat::Document => {
+                for (i, line) in content.lines().enumerate() {
+                    if i == 0 {
+                        self.write(line.trim_end());
+                        continue;
+                    }
+
+                    // Re-indent the continuation lines of docblocks, which are aligned on
+                    // the `*` of their first line.
+                    let trimmed = line.trim();
+                    if trimmed.starts_with('*') {
+                        self.newline();
+                        self.write(" ");
+                        self.write(trimmed);
+                    } else {
+                        self.output.push('\n');
+                        self.write(line.trim_end());
+                    }
+                }
+            }
+        }
+    }
+
+    fn attributes(&mut self, groups: &[AttributeGroup], inline: bool) {
+        for group in groups {
+            self.write("#[");
+            for (i, attribute) in group.members.iter().enumerate() {
+                if i > 0 {
+                    self.write(", ");
+                }
+
+                self.identifier(&attribute.name);
+                if let Some(arguments) = &attribute.arguments {
+                    self.arguments(arguments);
+                }
+            }
+            self.write("]");
+
+            if inline {
+                self.write(" ");
+            } else {
+                self.newline();
+            }
+        }
+    }
+
+    fn identifier(&mut self, identifier: &SimpleIdentifier) {
+        self.bytes(&identifier.value);
+    }
+
+    fn identifiers(&mut self, identifiers: &[SimpleIdentifier]) {
+        for (i, identifier) in identifiers.iter().enumerate() {
+            if i > 0 {
+                self.write(", ");
+            }
+
+            self.identifier(identifier);
+        }
+    }
+
+    fn ending(&mut self, ending: &Ending) {
+        match ending {
+            Ending::Semicolon(_) => self.write(";"),
+            Ending::CloseTag(_) => self.write(" ?>"),
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::FullOpeningTag(_) => self.write("<?php"),
+            Statement::ShortOpeningTag(_) => self.write("<?"),
+            Statement::EchoOpeningTag(_) => self.write("<?="),
+            Statement::ClosingTag(_) => self.write("?>"),
+            Statement::InlineHtml(html) => self.bytes(html),
+            Statement::ForeignChunk(chunk) => self.bytes(&chunk.value),
+            Statement::Label(label) => {
+                self.comments(&label.comments);
+                self.identifier(&label.label);
+                self.write(":");
+            }
+            Statement::Goto(goto) => {
+                self.comments(&goto.comments);
+                self.write("goto ");
+                self.identifier(&goto.label);
+                self.write(";");
+            }
+            Statement::HaltCompiler(halt) => {
+                self.write("__halt_compiler();");
+                if let Some(content) = &halt.content {
+                    self.bytes(content);
+                }
+            }
+            Statement::Static(statement) => {
+                self.write("static ");
+                for (i, var) in statement.vars.iter().enumerate() {
+                    if i > 0 {
+                        self.write(", ");
+                    }
+
+                    self.variable(&var.var);
+                    if let Some(default) = &var.default {
+                        self.write(" = ");
+                        self.expression(default);
+                    }
+                }
+                self.write(";");
+            }
+            Statement::DoWhile(statement) => {
+                self.write("do");
+                self.body(&statement.body);
+                self.continuation("while (");
+                self.expression(&statement.condition);
+                self.write(");");
+            }
+            Statement::While(statement) => {
+                self.write("while (");
+                self.expression(&statement.condition);
+                self.write(")");
+                match &statement.body {
+                    WhileStatementBody::Statement(body) => self.body(body),
+                    WhileStatementBody::Block {
+                        statements, ending, ..
+                    } => self.alternative(statements, "endwhile", ending),
+                }
+            }
+            Statement::For(statement) => {
+                self.write("for (");
+                let iterator = &statement.iterator;
+                for (i, expressions) in [
+                    &iterator.initializations.inner,
+                    &iterator.conditions.inner,
+                    &iterator.r#loop.inner,
+                ]
+                .into_iter()
+                .enumerate()
+                {
+                    if i > 0 {
+                        self.write(";");
+                        if !expressions.is_empty() {
+                            self.write(" ");
+                        }
+                    }
+
+                    self.expressions(expressions);
+                }
+                self.write(")");
+                match &statement.body {
+                    ForStatementBody::Statement(body) => self.body(body),
+                    ForStatementBody::Block {
+                        statements, ending, ..
+                    } => self.alternative(statements, "endfor", ending),
+                }
+            }
+            Statement::Foreach(statement) => {
+                self.write("foreach (");
+                match &statement.iterator {
+                    ForeachStatementIterator::Value {
+                        expression,
+                        ampersand,
+                        value,
+                        ..
+                    } => {
+                        self.expression(expression);
+                        self.write(" as ");
+                        if ampersand.is_some() {
+                            self.write("&");
+                        }
+                        self.expression(value);
+                    }
+                    ForeachStatementIterator::KeyAndValue {
+                        expression,
+                        ampersand,
+                        key,
+                        value,
+                        ..
+                    } => {
+                        self.expression(expression);
+                        self.write(" as ");
+                        self.expression(key);
+                        self.write(" => ");
+                        if ampersand.is_some() {
+                            self.write("&");
+                        }
+                        self.expression(value);
+                    }
+                }
+                self.write(")");
+                match &statement.body {
+                    ForeachStatementBody::Statement(body) => self.body(body),
+                    ForeachStatementBody::Block {
+                        statements, ending, ..
+                    } => self.alternative(statements, "endforeach", ending),
+                }
+            }
+            Statement::Break(statement) => {
+                self.write("break");
+                if let Some(level) = &statement.level {
+                    self.write(" ");
+                    self.level(level);
+                }
+                self.ending(&statement.ending);
+            }
+            Statement::Continue(statement) => {
+                self.write("continue");
+                if let Some(level) = &statement.level {
+                    self.write(" ");
+                    self.level(level);
+                }
+                self.ending(&statement.ending);
+            }
+            Statement::Constant(constant) => {
+                self.comments(&constant.comments);
+                self.write("const ");
+                self.constant_entries(&constant.entries);
+                self.write(";");
+            }
+            Statement::Function(function) => {
+                self.comments(&function.comments);
+                self.attributes(&function.attributes, false);
+                self.write("function ");
+                if function.ampersand.is_some() {
+                    self.write("&");
+                }
+                self.identifier(&function.name);
+                self.parameters(&function.parameters);
+                self.return_type(&function.return_type);
+                self.braced(&function.body.statements, true);
+            }
+            Statement::Class(class) => {
+                self.comments(&class.comments);
+                self.attributes(&class.attributes, false);
+                for modifier in &class.modifiers.modifiers {
+                    self.write(match modifier {
+                        ClassModifier::Final(_) => "final ",
+                        ClassModifier::Abstract(_) => "abstract ",
+                        ClassModifier::Readonly(_) => "readonly ",
+                    });
+                }
+                self.write("class ");
+                self.identifier(&class.name);
+                self.extends(&class.extends);
+                self.implements(&class.implements);
+                self.open_brace(true);
+                self.members(class.body.members.iter().map(Member::from));
+            }
+            Statement::Trait(r#trait) => {
+                self.comments(&r#trait.comments);
+                self.attributes(&r#trait.attributes, false);
+                self.write("trait ");
+                self.identifier(&r#trait.name);
+                self.open_brace(true);
+                self.members(r#trait.body.members.iter().map(Member::from));
+            }
+            Statement::Interface(interface) => {
+                self.comments(&interface.comments);
+                self.attributes(&interface.attributes, false);
+                self.write("interface ");
+                self.identifier(&interface.name);
+                if let Some(extends) = &interface.extends {
+                    self.write(" extends ");
+                    self.identifiers(&extends.parents.inner);
+                }
+                self.open_brace(true);
+                self.members(interface.body.members.iter().map(Member::from));
+            }
+            Statement::UnitEnum(r#enum) => {
+                self.comments(&r#enum.comments);
+                self.attributes(&r#enum.attributes, false);
+                self.write("enum ");
+                self.identifier(&r#enum.name);
+                if !r#enum.implements.is_empty() {
+                    self.write(" implements ");
+                    self.identifiers(&r#enum.implements);
+                }
+                self.open_brace(true);
+                self.members(r#enum.body.members.iter().map(Member::from));
+            }
+            Statement::BackedEnum(r#enum) => {
+                self.comments(&r#enum.comments);
+                self.attributes(&r#enum.attributes, false);
+                self.write("enum ");
+                self.identifier(&r#enum.name);
+                self.write(match r#enum.backed_type {
+                    BackedEnumType::String(..) => ": string",
+                    BackedEnumType::Int(..) => ": int",
+                });
+                if !r#enum.implements.is_empty() {
+                    self.write(" implements ");
+                    self.identifiers(&r#enum.implements);
+                }
+                self.open_brace(true);
+                self.members(r#enum.body.members.iter().map(Member::from));
+            }
+            Statement::If(statement) => self.r#if(statement),
+            Statement::Switch(statement) => {
+                self.write("switch (");
+                self.expression(&statement.condition);
+                self.write(")");
+                self.open_brace(false);
+                self.level += 1;
+                for case in &statement.cases {
+                    self.newline();
+                    match &case.condition {
+                        Some(condition) => {
+                            self.write("case ");
+                            self.expression(condition);
+                            self.write(":");
+                        }
+                        None => self.write("default:"),
+                    }
+                    self.indented(&case.body);
+                }
+                self.level -= 1;
+                self.newline();
+                self.write("}");
+            }
+            Statement::Echo(statement) => {
+                self.write("echo ");
+                self.expressions(&statement.values);
+                self.ending(&statement.ending);
+            }
+            Statement::Expression(statement) => {
+                self.expression(&statement.expression);
+                self.ending(&statement.ending);
+            }
+            Statement::Return(statement) => {
+                self.write("return");
+                if let Some(value) = &statement.value {
+                    self.write(" ");
+                    self.expression(value);
+                }
+                self.ending(&statement.ending);
+            }
+            Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                self.write("namespace ");
+                self.identifier(&namespace.name);
+                self.write(";");
+                if !namespace.statements.is_empty() {
+                    self.blank_line();
+                    self.statements(&namespace.statements);
+                }
+            }
+            Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                self.write("namespace");
+                if let Some(name) = &namespace.name {
+                    self.write(" ");
+                    self.identifier(name);
+                }
+                self.braced(&namespace.body.statements, false);
+            }
+            Statement::Use(statement) => {
+                self.write("use ");
+                self.use_kind(&statement.kind);
+                for (i, r#use) in statement.uses.iter().enumerate() {
+                    if i > 0 {
+                        self.write(", ");
+                    }
+
+                    self.r#use(r#use);
+                }
+                self.write(";");
+            }
+            Statement::GroupUse(statement) => {
+                self.write("use ");
+                self.use_kind(&statement.kind);
+                self.bytes(&statement.prefix.value);
+                if !statement.prefix.value.ends_with(b"\\") {
+                    self.write("\\");
+                }
+                self.write("{");
+                for (i, r#use) in statement.uses.iter().enumerate() {
+                    if i > 0 {
+                        self.write(", ");
+                    }
+
+                    if let Some(kind) = &r#use.kind {
+                        self.use_kind(kind);
+                    }
+                    self.r#use(r#use);
+                }
+                self.write("};");
+            }
+            Statement::Comment(comment) => self.comment(comment),
+            Statement::Try(statement) => self.r#try(statement),
+            Statement::Block(block) => {
+                self.write("{");
+                self.block(&block.statements);
+            }
+            Statement::Global(statement) => {
+                self.write("global ");
+                for (i, variable) in statement.variables.iter().enumerate() {
+                    if i > 0 {
+                        self.write(", ");
+                    }
+
+                    self.variable(variable);
+                }
+                self.write(";");
+            }
+            Statement::Declare(statement) => {
+                self.write("declare(");
+                for (i, entry) in statement.entries.entries.iter().enumerate() {
+                    if i > 0 {
+                        self.write(", ");
+                    }
+
+                    self.identifier(&entry.key);
+                    self.write("=");
+                    self.literal(&entry.value);
+                }
+                self.write(")");
+                match &statement.body {
+                    DeclareBody::Noop { .. } => self.write(";"),
+                    DeclareBody::Braced { statements, .. } => self.braced(statements, false),
+                    DeclareBody::Expression { expression, .. } => {
+                        self.write(" ");
+                        self.expression(expression);
+                        self.write(";");
+                    }
+                    DeclareBody::Block { statements, .. } => {
+                        self.write(":");
+                        self.indented(statements);
+                        self.newline();
+                        self.write("enddeclare;");
+                    }
+                }
+            }
+            Statement::Noop(_) => self.write(";"),
+            Statement::Error(error) => {
+                for (i, token) in error.tokens.iter().enumerate() {
+                    if i > 0 {
+                        self.write(" ");
+                    }
+
+                    self.bytes(&token.value);
+                }
+            }
+        }
+    }
+
+    /// Print the body of a control structure, which is either a block or a single statement.
+    fn body(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Block(block) => self.braced(&block.statements, false),
+            Statement::Noop(_) => self.write(";"),
+            _ => {
+                self.level += 1;
+                self.newline();
+                self.statement(statement);
+                self.level -= 1;
+            }
+        }
+    }
+
+    /// Print the body of a control structure using the alternative syntax, e.g. `while (): endwhile;`.
+    fn alternative(&mut self, statements: &[Statement], end: &str, ending: &Ending) {
+        self.write(":");
+        self.indented(statements);
+        self.newline();
+        self.write(end);
+        self.ending(ending);
+    }
+
+    fn r#if(&mut self, statement: &IfStatement) {
+        self.write("if (");
+        self.expression(&statement.condition);
+        self.write(")");
+
+        match &statement.body {
+            IfStatementBody::Statement {
+                statement,
+                elseifs,
+                r#else,
+            } => {
+                self.body(statement);
+                for elseif in elseifs {
+                    self.continuation("elseif (");
+                    self.expression(&elseif.condition);
+                    self.write(")");
+                    self.body(&elseif.statement);
+                }
+                if let Some(r#else) = r#else {
+                    self.continuation("else");
+                    match r#else.statement.as_ref() {
+                        Statement::If(statement) => {
+                            self.write(" ");
+                            self.r#if(statement);
+                        }
+                        statement => self.body(statement),
+                    }
+                }
+            }
+            IfStatementBody::Block {
+                statements,
+                elseifs,
+                r#else,
+                ending,
+                ..
+            } => {
+                self.write(":");
+                self.indented(statements);
+                for elseif in elseifs {
+                    self.newline();
+                    self.write("elseif (");
+                    self.expression(&elseif.condition);
+                    self.write("):");
+                    self.indented(&elseif.statements);
+                }
+                if let Some(r#else) = r#else {
+                    self.newline();
+                    self.write("else:");
+                    self.indented(&r#else.statements);
+                }
+                self.newline();
+                self.write("endif");
+                self.ending(ending);
+            }
+        }
+    }
+
+    fn r#try(&mut self, statement: &TryStatement) {
+        self.write("try");
+        self.braced(&statement.body, false);
+        for catch in &statement.catches {
+            self.continuation("catch (");
+            match &catch.types {
+                CatchType::Identifier(identifier) => self.identifier(identifier),
+                CatchType::Union(identifiers) => {
+                    for (i, identifier) in identifiers.iter().enumerate() {
+                        if i > 0 {
+                            self.write(" | ");
+                        }
+
+                        self.identifier(identifier);
+                    }
+                }
+            }
+            if let Some(var) = &catch.var {
+                self.write(" ");
+                self.bytes(&var.name);
+            }
+            self.write(")");
+            self.braced(&catch.body, false);
+        }
+        if let Some(finally) = &statement.finally {
+            self.continuation("finally");
+            self.braced(&finally.body, false);
+        }
+    }
+
+    fn level(&mut self, level: &Level) {
+        match level {
+            Level::Literal(literal) => self.bytes(&literal.value),
+            Level::Parenthesized { level, .. } => {
+                self.write("(");
+                self.level(level);
+                self.write(")");
+            }
+        }
+    }
+
+    fn use_kind(&mut self, kind: &UseKind) {
+        match kind {
+            UseKind::Normal => {}
+            UseKind::Function => self.write("function "),
+            UseKind::Const => self.write("const "),
+        }
+    }
+
+    fn r#use(&mut self, r#use: &Use) {
+        self.identifier(&r#use.name);
+        if let Some(alias) = &r#use.alias {
+            self.write(" as ");
+            self.identifier(alias);
+        }
+    }
+
+    fn constant_entries(&mut self, entries: &[ConstantEntry]) {
+        for (i, entry) in entries.iter().enumerate() {
+            if i > 0 {
+                self.write(", ");
+            }
+
+            self.identifier(&entry.name);
+            self.write(" = ");
+            self.expression(&entry.value);
+        }
+    }
+
+    fn extends(&mut self, extends: &Option<ClassExtends>) {
+        if let Some(extends) = extends {
+            self.write(" extends ");
+            self.identifier(&extends.parent);
+        }
+    }
+
+    fn implements(&mut self, implements: &Option<ClassImplements>) {
+        if let Some(implements) = implements {
+            self.write(" implements ");
+            self.identifiers(&implements.interfaces.inner);
+        }
+    }
+
+    /// Print the members of a class-like body, after its opening brace.
+    fn members<'m>(&mut self, members: impl Iterator<Item = Member<'m>>) {
+        self.level += 1;
+        let mut previous: Option<Member> = None;
+        for member in members {
+            match previous {
+                Some(previous)
+                    if previous.is_method()
+                        || member.is_method()
+                        || member.has_comments()
+                        || previous.group() != member.group() =>
+                {
+                    self.blank_line()
+                }
+                _ => self.newline(),
+            }
+
+            self.member(member);
+            previous = Some(member);
+        }
+        self.level -= 1;
+        self.newline();
+        self.write("}");
+    }
+
+    fn member(&mut self, member: Member) {
+        match member {
+            Member::Constant(constant) => {
+                self.comments(&constant.comments);
+                self.attributes(&constant.attributes, false);
+                for modifier in &constant.modifiers.modifiers {
+                    self.write(match modifier {
+                        ConstantModifier::Final(_) => "final ",
+                        ConstantModifier::Public(_) => "public ",
+                        ConstantModifier::Protected(_) => "protected ",
+                        ConstantModifier::Private(_) => "private ",
+                    });
+                }
+                self.write("const ");
+                if let Some(data_type) = &constant.data_type {
+                    self.data_type(data_type);
+                    self.write(" ");
+                }
+                self.constant_entries(&constant.entries);
+                self.write(";");
+            }
+            Member::TraitUsage(usage) => {
+                self.write("use ");
+                self.identifiers(&usage.traits);
+                if usage.adaptations.is_empty() {
+                    self.write(";");
+                    return;
+                }
+
+                self.write(" {");
+                self.level += 1;
+                for adaptation in &usage.adaptations {
+                    self.newline();
+                    self.adaptation(adaptation);
+                    self.write(";");
+                }
+                self.level -= 1;
+                self.newline();
+                self.write("}");
+            }
+            Member::Property(property) => {
+                self.comments(&property.comments);
+                self.attributes(&property.attributes, false);
+                for modifier in &property.modifiers.modifiers {
+                    self.write(match modifier {
+                        PropertyModifier::Public(_) => "public ",
+                        PropertyModifier::Protected(_) => "protected ",
+                        PropertyModifier::Private(_) => "private ",
+                        PropertyModifier::Static(_) => "static ",
+                        PropertyModifier::Readonly(_) => "readonly ",
+                        PropertyModifier::Abstract(_) => "abstract ",
+                        PropertyModifier::Final(_) => "final ",
+                        PropertyModifier::PublicSet(_) => "public(set) ",
+                        PropertyModifier::ProtectedSet(_) => "protected(set) ",
+                        PropertyModifier::PrivateSet(_) => "private(set) ",
+                    });
+                }
+                self.property(&property.r#type, &property.entries, property.hooks.as_ref());
+            }
+            Member::VariableProperty(property) => {
+                self.comments(&property.comments);
+                self.attributes(&property.attributes, false);
+                self.write("var ");
+                self.property(&property.r#type, &property.entries, None);
+            }
+            Member::AbstractMethod(method) => {
+                self.comments(&method.comments);
+                self.attributes(&method.attributes, false);
+                self.method_modifiers(&method.modifiers.modifiers);
+                self.function_name(&method.ampersand, &method.name);
+                self.parameters(&method.parameters);
+                self.return_type(&method.return_type);
+                self.write(";");
+            }
+            Member::AbstractConstructor(constructor) => {
+                self.comments(&constructor.comments);
+                self.attributes(&constructor.attributes, false);
+                self.method_modifiers(&constructor.modifiers.modifiers);
+                self.function_name(&constructor.ampersand, &constructor.name);
+                self.parameters(&constructor.parameters);
+                self.write(";");
+            }
+            Member::ConcreteMethod(method) => {
+                self.comments(&method.comments);
+                self.attributes(&method.attributes, false);
+                self.method_modifiers(&method.modifiers.modifiers);
+                self.function_name(&method.ampersand, &method.name);
+                self.parameters(&method.parameters);
+                self.return_type(&method.return_type);
+                self.braced(&method.body.statements, true);
+            }
+            Member::ConcreteConstructor(constructor) => {
+                self.comments(&constructor.comments);
+                self.attributes(&constructor.attributes, false);
+                self.method_modifiers(&constructor.modifiers.modifiers);
+                self.function_name(&constructor.ampersand, &constructor.name);
+                self.constructor_parameters(&constructor.parameters);
+                self.braced(&constructor.body.statements, true);
+            }
+            Member::UnitCase(case) => {
+                self.comments(&case.comments);
+                self.attributes(&case.attributes, false);
+                self.write("case ");
+                self.identifier(&case.name);
+                self.write(";");
+            }
+            Member::BackedCase(case) => {
+                self.comments(&case.comments);
+                self.attributes(&case.attributes, false);
+                self.write("case ");
+                self.identifier(&case.name);
+                self.write(" = ");
+                self.expression(&case.value);
+                self.write(";");
+            }
+        }
+    }
+
+    fn adaptation(&mut self, adaptation: &TraitUsageAdaptation) {
+        let (r#trait, method) = match adaptation {
+            TraitUsageAdaptation::Alias {
+                r#trait, method, ..
+            }
+            | TraitUsageAdaptation::Visibility {
+                r#trait, method, ..
+            }
+            | TraitUsageAdaptation::Precedence {
+                r#trait, method, ..
+            } => (r#trait, method),
+        };
+
+        if let Some(r#trait) = r#trait {
+            self.identifier(r#trait);
+            self.write("::");
+        }
+        self.identifier(method);
+
+        match adaptation {
+            TraitUsageAdaptation::Alias {
+                alias, visibility, ..
+            } => {
+                self.write(" as ");
+                if let Some(visibility) = visibility {
+                    self.visibility(visibility);
+                    self.write(" ");
+                }
+                self.identifier(alias);
+            }
+            TraitUsageAdaptation::Visibility { visibility, .. } => {
+                self.write(" as ");
+                self.visibility(visibility);
+            }
+            TraitUsageAdaptation::Precedence { insteadof, .. } => {
+                self.write(" insteadof ");
+                self.identifiers(insteadof);
+            }
+        }
+    }
+
+    fn visibility(&mut self, visibility: &VisibilityModifier) {
+        self.write(match visibility {
+            VisibilityModifier::Public(_) => "public",
+            VisibilityModifier::Protected(_) => "protected",
+            VisibilityModifier::Private(_) => "private",
+        });
+    }
+
+    fn method_modifiers(&mut self, modifiers: &[MethodModifier]) {
+        for modifier in modifiers {
+            self.write(match modifier {
+                MethodModifier::Final(_) => "final ",
+                MethodModifier::Static(_) => "static ",
+                MethodModifier::Abstract(_) => "abstract ",
+                MethodModifier::Public(_) => "public ",
+                MethodModifier::Protected(_) => "protected ",
+                MethodModifier::Private(_) => "private ",
+            });
+        }
+    }
+
+    fn function_name(&mut self, ampersand: &Option<Span>, name: &SimpleIdentifier) {
+        self.write("function ");
+        if ampersand.is_some() {
+            self.write("&");
+        }
+        self.identifier(name);
+    }
+
+    fn property(
+        &mut self,
+        r#type: &Option<Type>,
+        entries: &[PropertyEntry],
+        hooks: Option<&PropertyHookList>,
+    ) {
+        if let Some(r#type) = r#type {
+            self.data_type(r#type);
+            self.write(" ");
+        }
+
+        for (i, entry) in entries.iter().enumerate() {
+            if i > 0 {
+                self.write(", ");
+            }
+
+            match entry {
+                PropertyEntry::Uninitialized { variable } => self.bytes(&variable.name),
+                PropertyEntry::Initialized {
+                    variable, value, ..
+                } => {
+                    self.bytes(&variable.name);
+                    self.write(" = ");
+                    self.expression(value);
+                }
+            }
+        }
+
+        match hooks {
+            Some(hooks) => self.hooks(hooks),
+            None => self.write(";"),
+        }
+    }
+
+    /// Print the hooks of a property, on a single line when none of them has a body.
+    fn hooks(&mut self, hooks: &PropertyHookList) {
+        let inline = hooks.hooks.iter().all(|hook| {
+            hook.is_abstract() && hook.attributes.is_empty() && hook.parameters.is_none()
+        });
+
+        self.write(" {");
+        self.level += 1;
+        for hook in &hooks.hooks {
+            if inline {
+                self.write(" ");
+            } else {
+                self.newline();
+                self.comments(&hook.comments);
+                self.attributes(&hook.attributes, false);
+            }
+
+            if hook.r#final.is_some() {
+                self.write("final ");
+            }
+            if hook.ampersand.is_some() {
+                self.write("&");
+            }
+            self.identifier(&hook.name);
+            if let Some(parameters) = &hook.parameters {
+                self.parameters(parameters);
+            }
+
+            match &hook.body {
+                PropertyHookBody::Abstract { .. } => self.write(";"),
+                PropertyHookBody::Expression { expression, .. } => {
+                    self.write(" => ");
+                    self.expression(expression);
+                    self.write(";");
+                }
+                PropertyHookBody::Block(body) => {
+                    self.write(" {");
+                    self.block(&body.statements);
+                }
+            }
+        }
+        self.level -= 1;
+
+        if inline {
+            self.write(" }");
+        } else {
+            self.newline();
+            self.write("}");
+        }
+    }
+
+    fn parameters(&mut self, parameters: &FunctionParameterList) {
+        self.write("(");
+        for (i, parameter) in parameters.parameters.inner.iter().enumerate() {
+            if i > 0 {
+                self.write(", ");
+            }
+
+            self.attributes(&parameter.attributes, true);
+            self.parameter(
+                &parameter.data_type,
+                parameter.ampersand.is_some(),
+                parameter.ellipsis.is_some(),
+                &parameter.name.name,
+                &parameter.default,
+            );
+        }
+        self.write(")");
+    }
+
+    fn constructor_parameters(&mut self, parameters: &ConstructorParameterList) {
+        self.write("(");
+        for (i, parameter) in parameters.parameters.inner.iter().enumerate() {
+            if i > 0 {
+                self.write(", ");
+            }
+
+            self.attributes(&parameter.attributes, true);
+            for modifier in &parameter.modifiers.modifiers {
+                self.write(&modifier.to_string());
+                self.write(" ");
+            }
+            self.parameter(
+                &parameter.data_type,
+                parameter.ampersand.is_some(),
+                parameter.ellipsis.is_some(),
+                &parameter.name.name,
+                &parameter.default,
+            );
+        }
+        self.write(")");
+    }
+
+    fn parameter(
+        &mut self,
+        r#type: &Option<Type>,
+        ampersand: bool,
+        ellipsis: bool,
+        name: &[u8],
+        default: &Option<Expression>,
+    ) {
+        if let Some(r#type) = r#type {
+            self.data_type(r#type);
+            self.write(" ");
+        }
+        if ampersand {
+            self.write("&");
+        }
+        if ellipsis {
+            self.write("...");
+        }
+        self.bytes(name);
+        if let Some(default) = default {
+            self.write(" = ");
+            self.expression(default);
+        }
+    }
+
+    fn return_type(&mut self, return_type: &Option<ReturnType>) {
+        if let Some(return_type) = return_type {
+            self.write(": ");
+            self.data_type(&return_type.data_type);
+        }
+    }
+
+    fn data_type(&mut self, r#type: &Type) {
+        match r#type {
+            Type::Nullable(_, inner) => {
+                self.write("?");
+                self.data_type(inner);
+            }
+            Type::Union(types) => {
+                for (i, inner) in types.iter().enumerate() {
+                    if i > 0 {
+                        self.write("|");
+                    }
+
+                    if let Type::Intersection(_) = inner {
+                        self.write("(");
+                        self.data_type(inner);
+                        self.write(")");
+                    } else {
+                        self.data_type(inner);
+                    }
+                }
+            }
+            Type::Intersection(types) => {
+                for (i, inner) in types.iter().enumerate() {
+                    if i > 0 {
+                        self.write("&");
+                    }
+
+                    if let Type::Union(_) = inner {
+                        self.write("(");
+                        self.data_type(inner);
+                        self.write(")");
+                    } else {
+                        self.data_type(inner);
+                    }
+                }
+            }
+            Type::Named(_, name) => self.bytes(name),
+            _ => self.write(&r#type.to_string()),
+        }
+    }
+
+    fn expressions(&mut self, expressions: &[Expression]) {
+        for (i, expression) in expressions.iter().enumerate() {
+            if i > 0 {
+                self.write(", ");
+            }
+
+            self.expression(expression);
+        }
+    }
+
+    /// Print an operand, within parentheses when it binds looser than the given power.
+    fn operand(&mut self, expression: &Expression, parenthesize: bool) {
+        if parenthesize {
+            self.write("(");
+            self.expression(expression);
+            self.write(")");
+        } else {
+            self.expression(expression);
+        }
+    }
+
+    /// Print the target of a member access, call or array index.
+    fn target(&mut self, expression: &Expression) {
+        self.operand(expression, precedence(expression) < PRIMARY);
+    }
+
+    fn prefix(&mut self, operator: &str, operand: &Expression, power: u8) {
+        self.write(operator);
+        // `- -$a` must not be printed as `--$a`.
+        let repeated = matches!(
+            operand,
+            Expression::ArithmeticOperation(
+                ArithmeticOperation::Negative { .. }
+                    | ArithmeticOperation::Positive { .. }
+                    | ArithmeticOperation::PreIncrement { .. }
+                    | ArithmeticOperation::PreDecrement { .. }
+            )
+        ) && (operator == "-" || operator == "+");
+
+        self.operand(operand, repeated || precedence(operand) < power);
+    }
+
+    fn expression(&mut self, expression: &Expression) {
+        if let Some((left, operator, right)) = binary(expression) {
+            let power = precedence(expression);
+            let associativity = associativity(expression);
+            let left_power = precedence(left);
+            let right_power = precedence(right);
+
+            self.operand(
+                left,
+                left_power < power
+                    || (left_power == power && associativity != Associativity::Left)
+                    || open_ended(left),
+            );
+            self.write(" ");
+            self.write(operator);
+            self.write(" ");
+            self.operand(
+                right,
+                !is_prefix(right)
+                    && (right_power < power
+                        || (right_power == power && associativity != Associativity::Right)),
+            );
+
+            return;
+        }
+
+        match expression {
+            Expression::Eval { argument, .. } => {
+                self.write("eval");
+                self.single_argument(argument);
+            }
+            Expression::Empty { argument, .. } => {
+                self.write("empty");
+                self.single_argument(argument);
+            }
+            Expression::Exit(exit) => {
+                self.write(match exit.kind {
+                    ExitKind::Exit => "exit",
+                    ExitKind::Die => "die",
+                });
+                if let Some(arguments) = &exit.arguments {
+                    self.arguments(arguments);
+                }
+            }
+            Expression::Isset { arguments, .. } => {
+                self.write("isset");
+                self.arguments(arguments);
+            }
+            Expression::Unset { arguments, .. } => {
+                self.write("unset");
+                self.arguments(arguments);
+            }
+            Expression::Print {
+                value, argument, ..
+            } => {
+                if let Some(argument) = argument {
+                    self.write("print");
+                    self.single_argument(argument);
+                } else if let Some(value) = value {
+                    self.prefix("print ", value, PRINT);
+                }
+            }
+            Expression::Literal(literal) => self.literal(literal),
+            Expression::ArithmeticOperation(operation) => match operation {
+                ArithmeticOperation::Negative { right, .. } => self.prefix("-", right, UNARY),
+                ArithmeticOperation::Positive { right, .. } => self.prefix("+", right, UNARY),
+                ArithmeticOperation::PreIncrement { right, .. } => self.prefix("++", right, UNARY),
+                ArithmeticOperation::PreDecrement { right, .. } => self.prefix("--", right, UNARY),
+                ArithmeticOperation::PostIncrement { left, .. } => {
+                    self.target(left);
+                    self.write("++");
+                }
+                ArithmeticOperation::PostDecrement { left, .. } => {
+                    self.target(left);
+                    self.write("--");
+                }
+                _ => unreachable!(),
+            },
+            Expression::BitwiseOperation(BitwiseOperation::Not { right, .. }) => {
+                self.prefix("~", right, UNARY)
+            }
+            Expression::LogicalOperation(LogicalOperation::Not { right, .. }) => {
+                self.prefix("!", right, NOT)
+            }
+            Expression::Parenthesized { expr, .. } => {
+                self.write("(");
+                self.expression(expr);
+                self.write(")");
+            }
+            Expression::ErrorSuppress { expr, .. } => self.prefix("@", expr, UNARY),
+            Expression::Identifier(identifier) => self.member_name(identifier),
+            Expression::Variable(variable) => self.variable(variable),
+            Expression::Include { path, .. } => self.prefix("include ", path, PRINT),
+            Expression::IncludeOnce { path, .. } => self.prefix("include_once ", path, PRINT),
+            Expression::Require { path, .. } => self.prefix("require ", path, PRINT),
+            Expression::RequireOnce { path, .. } => self.prefix("require_once ", path, PRINT),
+            Expression::FunctionCall { target, arguments } => {
+                self.target(target);
+                self.arguments(arguments);
+            }
+            Expression::FunctionClosureCreation { target, .. } => {
+                self.target(target);
+                self.write("(...)");
+            }
+            Expression::MethodCall {
+                target,
+                method,
+                arguments,
+                ..
+            } => {
+                self.target(target);
+                self.write("->");
+                self.member_expression(method);
+                self.arguments(arguments);
+            }
+            Expression::MethodClosureCreation { target, method, .. } => {
+                self.target(target);
+                self.write("->");
+                self.member_expression(method);
+                self.write("(...)");
+            }
+            Expression::NullsafeMethodCall {
+                target,
+                method,
+                arguments,
+                ..
+            } => {
+                self.target(target);
+                self.write("?->");
+                self.member_expression(method);
+                self.arguments(arguments);
+            }
+            Expression::StaticMethodCall {
+                target,
+                method,
+                arguments,
+                ..
+            } => {
+                self.target(target);
+                self.write("::");
+                self.member_name(method);
+                self.arguments(arguments);
+            }
+            Expression::StaticVariableMethodCall {
+                target,
+                method,
+                arguments,
+                ..
+            } => {
+                self.target(target);
+                self.write("::");
+                self.variable(method);
+                self.arguments(arguments);
+            }
+            Expression::StaticMethodClosureCreation { target, method, .. } => {
+                self.target(target);
+                self.write("::");
+                self.member_name(method);
+                self.write("(...)");
+            }
+            Expression::StaticVariableMethodClosureCreation { target, method, .. } => {
+                self.target(target);
+                self.write("::");
+                self.variable(method);
+                self.write("(...)");
+            }
+            Expression::PropertyFetch {
+                target, property, ..
+            } => {
+                self.target(target);
+                self.write("->");
+                self.member_expression(property);
+            }
+            Expression::NullsafePropertyFetch {
+                target, property, ..
+            } => {
+                self.target(target);
+                self.write("?->");
+                self.member_expression(property);
+            }
+            Expression::StaticPropertyFetch {
+                target, property, ..
+            } => {
+                self.target(target);
+                self.write("::");
+                self.variable(property);
+            }
+            Expression::ConstantFetch {
+                target, constant, ..
+            } => {
+                self.target(target);
+                self.write("::");
+                self.member_name(constant);
+            }
+            Expression::Static { .. } => self.write("static"),
+            Expression::Self_ { .. } => self.write("self"),
+            Expression::Parent { .. } => self.write("parent"),
+            Expression::ShortArray { items, .. } => {
+                self.write("[");
+                self.array_items(&items.inner);
+                self.write("]");
+            }
+            Expression::Array(array) => {
+                self.write("array(");
+                self.array_items(&array.items.inner);
+                self.write(")");
+            }
+            Expression::List(list) => {
+                self.write("list(");
+                for (i, item) in list.items.iter().enumerate() {
+                    if i > 0 {
+                        self.write(", ");
+                    }
+
+                    match item {
+                        ListEntry::Skipped => {}
+                        ListEntry::Value { value } => self.expression(value),
+                        ListEntry::KeyValue { key, value, .. } => {
+                            self.expression(key);
+                            self.write(" => ");
+                            self.expression(value);
+                        }
+                    }
+                }
+                self.write(")");
+            }
+            Expression::Closure(closure) => self.closure(closure),
+            Expression::ArrowFunction(function) => self.arrow_function(function),
+            Expression::New {
+                target, arguments, ..
+            } => {
+                self.write("new ");
+                match target.as_ref() {
+                    Expression::AnonymousClass(class) => {
+                        self.anonymous_class(class, arguments.as_deref())
+                    }
+                    target => {
+                        // `new` accepts names, variables and property fetches, anything else
+                        // must be parenthesized.
+                        let name = matches!(
+                            target,
+                            Expression::Identifier(_)
+                                | Expression::Variable(_)
+                                | Expression::Static { .. }
+                                | Expression::Self_ { .. }
+                                | Expression::Parent { .. }
+                                | Expression::PropertyFetch { .. }
+                                | Expression::NullsafePropertyFetch { .. }
+                                | Expression::StaticPropertyFetch { .. }
+                                | Expression::ArrayIndex { .. }
+                                | Expression::Parenthesized { .. }
+                                | Expression::Bool { .. }
+                                | Expression::Null { .. }
+                        );
+                        self.operand(target, !name);
+                        if let Some(arguments) = arguments {
+                            self.arguments(arguments);
+                        }
+                    }
+                }
+            }
+            Expression::AnonymousClass(class) => {
+                self.write("new ");
+                self.anonymous_class(class, None);
+            }
+            Expression::InterpolatedString { parts, .. } => {
+                // The first literal of a parsed string includes its opening quote.
+                let opening = match parts.first() {
+                    Some(StringPart::Literal(literal)) => ["b\"", "B\"", "\""]
+                        .into_iter()
+                        .find(|opening| literal.value.starts_with(opening.as_bytes())),
+                    _ => None,
+                };
+
+                match opening {
+                    Some(opening) => {
+                        self.write(opening);
+                        if let Some(StringPart::Literal(literal)) = parts.first() {
+                            self.string_literal(
+                                &literal.value[opening.len()..],
+                                Quote::Double,
+                                parts.get(1),
+                            );
+                        }
+                        self.string_parts_from(parts, 1, Quote::Double);
+                    }
+                    None => {
+                        self.write("\"");
+                        self.string_parts(parts, Quote::Double);
+                    }
+                }
+                self.write("\"");
+            }
+            Expression::ShellExec { parts, .. } => {
+                self.write("`");
+                self.string_parts(parts, Quote::Backtick);
+                self.write("`");
+            }
+            Expression::Heredoc { parts, .. } => {
+                let label = label(parts);
+                self.write("<<<");
+                self.write(&label);
+                self.output.push('\n');
+                self.string_parts(parts, Quote::Heredoc);
+                self.output.push('\n');
+                self.write(&label);
+            }
+            Expression::Nowdoc { parts, .. } => {
+                let label = label(parts);
+                self.write("<<<'");
+                self.write(&label);
+                self.write("'\n");
+                for part in parts {
+                    if let StringPart::Literal(literal) = part {
+                        self.bytes(&literal.value);
+                    }
+                }
+                self.output.push('\n');
+                self.write(&label);
+            }
+            Expression::Bool { value, .. } => self.write(if *value { "true" } else { "false" }),
+            Expression::ArrayIndex { array, index, .. } => {
+                self.target(array);
+                self.write("[");
+                if let Some(index) = index {
+                    self.expression(index);
+                }
+                self.write("]");
+            }
+            Expression::Null { .. } => self.write("null"),
+            Expression::MagicConstant(constant) => self.write(match constant {
+                MagicConstant::Directory(_) => "__DIR__",
+                MagicConstant::File(_) => "__FILE__",
+                MagicConstant::Line(_) => "__LINE__",
+                MagicConstant::Class(_) => "__CLASS__",
+                MagicConstant::Function(_) => "__FUNCTION__",
+                MagicConstant::Method(_) => "__METHOD__",
+                MagicConstant::Namespace(_) => "__NAMESPACE__",
+                MagicConstant::Trait(_) => "__TRAIT__",
+                MagicConstant::CompilerHaltOffset(_) => "__COMPILER_HALT_OFFSET__",
+            }),
+            Expression::ShortTernary {
+                condition, r#else, ..
+            } => {
+                self.operand(
+                    condition,
+                    precedence(condition) <= TERNARY || open_ended(condition),
+                );
+                self.write(" ?: ");
+                // Short ternaries can be chained without parentheses, unlike full ones.
+                self.operand(
+                    r#else,
+                    !is_prefix(r#else)
+                        && !matches!(r#else.as_ref(), Expression::ShortTernary { .. })
+                        && precedence(r#else) <= TERNARY,
+                );
+            }
+            Expression::Ternary {
+                condition,
+                then,
+                r#else,
+                ..
+            } => {
+                self.operand(
+                    condition,
+                    precedence(condition) <= TERNARY || open_ended(condition),
+                );
+                self.write(" ? ");
+                self.expression(then);
+                self.write(" : ");
+                self.operand(r#else, !is_prefix(r#else) && precedence(r#else) <= TERNARY);
+            }
+            Expression::Clone { target, .. } => self.prefix("clone ", target, CLONE_OR_NEW),
+            Expression::Match(r#match) => {
+                let MatchExpression {
+                    condition,
+                    default,
+                    arms,
+                    ..
+                } = r#match.as_ref();
+
+                self.write("match (");
+                self.expression(condition);
+                self.write(") {");
+                self.level += 1;
+                for arm in arms {
+                    self.newline();
+                    self.expressions(&arm.conditions);
+                    self.write(" => ");
+                    self.expression(&arm.body);
+                    self.write(",");
+                }
+                if let Some(default) = default {
+                    self.newline();
+                    self.write("default => ");
+                    self.expression(&default.body);
+                    self.write(",");
+                }
+                self.level -= 1;
+                if !arms.is_empty() || default.is_some() {
+                    self.newline();
+                }
+                self.write("}");
+            }
+            Expression::Throw { value, .. } => self.prefix("throw ", value, PRINT),
+            Expression::Yield { key, value, .. } => {
+                self.write("yield");
+                if let Some(key) = key {
+                    self.write(" ");
+                    self.operand(key, precedence(key) <= YIELD);
+                    self.write(" =>");
+                }
+                if let Some(value) = value {
+                    self.write(" ");
+                    self.operand(value, precedence(value) < YIELD);
+                }
+            }
+            Expression::YieldFrom { value, .. } => self.prefix("yield from ", value, YIELD_FROM),
+            Expression::Cast { kind, value, .. } => {
+                let cast = match kind {
+                    CastKind::Int => "(int) ",
+                    CastKind::Bool => "(bool) ",
+                    CastKind::Float => "(float) ",
+                    CastKind::String => "(string) ",
+                    CastKind::Array => "(array) ",
+                    CastKind::Object => "(object) ",
+                    CastKind::Unset => "(unset) ",
+                };
+
+                self.prefix(cast, value, UNARY);
+            }
+            Expression::Noop { .. } | Expression::Missing { .. } => {}
+            // Binary operations are printed above.
+            Expression::AssignmentOperation(_)
+            | Expression::BitwiseOperation(_)
+            | Expression::ComparisonOperation(_)
+            | Expression::LogicalOperation(_)
+            | Expression::Concat { .. }
+            | Expression::Instanceof { .. }
+            | Expression::Pipe { .. }
+            | Expression::Coalesce { .. } => unreachable!(),
+        }
+    }
+
+    fn literal(&mut self, literal: &Literal) {
+        match literal {
+            Literal::String(string) => {
+                let value = &string.value[..];
+                let (prefix, value) = match value {
+                    [b'b' | b'B', rest @ ..] if rest.len() >= 2 => (&value[..1], rest),
+                    _ => (&value[..0], value),
+                };
+
+                self.bytes(prefix);
+                match value {
+                    [b'\'', content @ .., b'\''] if std::str::from_utf8(content).is_ok() => {
+                        // A backslash only needs escaping before another backslash, a quote,
+                        // or the closing quote.
+                        let mut escaped = Vec::with_capacity(content.len() + 2);
+                        escaped.push(b'\'');
+                        for (i, byte) in content.iter().enumerate() {
+                            match (byte, content.get(i + 1)) {
+                                (b'\'', _) => escaped.extend_from_slice(b"\\'"),
+                                (b'\\', None | Some(b'\\' | b'\'')) => {
+                                    escaped.extend_from_slice(b"\\\\")
+                                }
+                                _ => escaped.push(*byte),
+                            }
+                        }
+                        escaped.push(b'\'');
+
+                        self.bytes(&escaped);
+                    }
+                    [b'\'', content @ .., b'\''] | [b'"', content @ .., b'"'] => {
+                        self.write("\"");
+                        self.string_literal(content, Quote::Double, None);
+                        self.write("\"");
+                    }
+                    // A literal without quotes, e.g. the key of `"$a[b]"`.
+                    _ => self.bytes(value),
+                }
+            }
+            Literal::Integer(integer) => self.bytes(&integer.value),
+            Literal::Float(float) => self.bytes(&float.value),
+        }
+    }
+
+    fn string_parts(&mut self, parts: &[StringPart], quote: Quote) {
+        self.string_parts_from(parts, 0, quote);
+    }
+
+    /// Print the parts of a string starting at the given index, the previous and next parts
+    /// decide how an interpolation is delimited.
+    fn string_parts_from(&mut self, parts: &[StringPart], start: usize, quote: Quote) {
+        for (i, part) in parts.iter().enumerate().skip(start) {
+            match part {
+                StringPart::Literal(literal) => {
+                    self.string_literal(&literal.value, quote, parts.get(i + 1))
+                }
+                StringPart::Expression(part) => {
+                    let previous = i.checked_sub(1).and_then(|i| parts.get(i));
+                    let next = parts.get(i + 1);
+                    // A simple interpolation must not be followed by characters that would
+                    // extend it, or preceded by `{`, which would turn it into a complex one.
+                    let simple = part.syntax == InterpolationSyntax::Simple
+                        && !matches!(previous, Some(StringPart::Literal(literal)) if literal.value.ends_with(b"{"))
+                        && !matches!(next, Some(StringPart::Literal(literal)) if matches!(&literal.value[..], [b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'[' | 0x80..=0xff, ..] | [b'-', b'>', ..] | [b'?', b'-', b'>', ..]));
+
+                    match part.syntax {
+                        InterpolationSyntax::Simple if simple => {
+                            self.simple_interpolation(&part.expression)
+                        }
+                        InterpolationSyntax::DollarBrace => self.expression(&part.expression),
+                        _ => {
+                            self.write("{");
+                            self.expression(&part.expression);
+                            self.write("}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn simple_interpolation(&mut self, expression: &Expression) {
+        match expression {
+            Expression::ArrayIndex {
+                array,
+                index: Some(index),
+                ..
+            } => {
+                self.expression(array);
+                self.write("[");
+                match index.as_ref() {
+                    Expression::Literal(Literal::String(key)) => self.bytes(&key.value),
+                    index => self.expression(index),
+                }
+                self.write("]");
+            }
+            expression => self.expression(expression),
+        }
+    }
+
+    /// Print the literal part of an interpolated string, escaping the characters that
+    /// would otherwise be interpreted.
+    fn string_literal(&mut self, value: &[u8], quote: Quote, next: Option<&StringPart>) {
+        let mut escaped = Vec::with_capacity(value.len());
+        let utf8 = std::str::from_utf8(value).is_ok();
+
+        for (i, byte) in value.iter().enumerate() {
+            let following = value.get(i + 1).copied();
+            match byte {
+                b'\\' => escaped.extend_from_slice(b"\\\\"),
+                b'"' if quote == Quote::Double => escaped.extend_from_slice(b"\\\""),
+                b'`' if quote == Quote::Backtick => escaped.extend_from_slice(b"\\`"),
+                b'$' => {
+                    let interpolated = match following {
+                        Some(b'a'..=b'z' | b'A'..=b'Z' | b'_' | b'{' | 0x80..=0xff) => true,
+                        Some(_) => false,
+                        None => matches!(next, Some(StringPart::Expression(_))),
+                    };
+                    if interpolated || escaped.ends_with(b"{") {
+                        escaped.extend_from_slice(b"\\$");
+                    } else {
+                        escaped.push(b'$');
+                    }
+                }
+                b'\n' if quote == Quote::Heredoc => escaped.push(b'\n'),
+                b'\n' => escaped.extend_from_slice(b"\\n"),
+                b'\t' => escaped.extend_from_slice(b"\\t"),
+                b'\r' => escaped.extend_from_slice(b"\\r"),
+                0x0b => escaped.extend_from_slice(b"\\v"),
+                0x0c => escaped.extend_from_slice(b"\\f"),
+                0x1b => escaped.extend_from_slice(b"\\e"),
+                0x00..=0x1f | 0x7f => {
+                    escaped.extend_from_slice(format!("\\x{:02x}", byte).as_bytes())
+                }
+                0x80..=0xff if !utf8 => {
+                    escaped.extend_from_slice(format!("\\x{:02x}", byte).as_bytes())
+                }
+                _ => escaped.push(*byte),
+            }
+        }
+
+        self.bytes(&escaped);
+    }
+
+    fn variable(&mut self, variable: &Variable) {
+        match variable {
+            Variable::SimpleVariable(variable) => self.bytes(&variable.name),
+            Variable::VariableVariable(variable) => {
+                self.write("$");
+                self.variable(&variable.variable);
+            }
+            Variable::BracedVariableVariable(variable) => {
+                self.write("${");
+                self.expression(&variable.variable);
+                self.write("}");
+            }
+        }
+    }
+
+    /// Print the name of a member, or of a class constant, e.g. `b` or `{$b}` in `$a->b`.
+    fn member_name(&mut self, identifier: &Identifier) {
+        match identifier {
+            Identifier::SimpleIdentifier(identifier) => self.identifier(identifier),
+            Identifier::DynamicIdentifier(identifier) => {
+                self.write("{");
+                self.expression(&identifier.expr);
+                self.write("}");
+            }
+        }
+    }
+
+    fn member_expression(&mut self, member: &Expression) {
+        match member {
+            Expression::Identifier(identifier) => self.member_name(identifier),
+            Expression::Variable(variable) => self.variable(variable),
+            member => {
+                self.write("{");
+                self.expression(member);
+                self.write("}");
+            }
+        }
+    }
+
+    fn arguments(&mut self, arguments: &ArgumentList) {
+        self.write("(");
+        for (i, argument) in arguments.arguments.iter().enumerate() {
+            if i > 0 {
+                self.write(", ");
+            }
+
+            self.argument(argument);
+        }
+        self.write(")");
+    }
+
+    fn single_argument(&mut self, argument: &SingleArgument) {
+        self.write("(");
+        self.argument(&argument.argument);
+        self.write(")");
+    }
+
+    fn argument(&mut self, argument: &Argument) {
+        if let Some(name) = argument.name() {
+            self.identifier(name);
+            self.write(": ");
+        }
+        if argument.is_spread() {
+            self.write("...");
+        }
+        self.expression(argument.value());
+    }
+
+    fn array_items(&mut self, items: &[ArrayItem]) {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.write(", ");
+            }
+
+            match item {
+                ArrayItem::Skipped => {}
+                ArrayItem::Value { value } => self.expression(value),
+                ArrayItem::ReferencedValue { value, .. } => {
+                    self.write("&");
+                    self.expression(value);
+                }
+                ArrayItem::SpreadValue { value, .. } => {
+                    self.write("...");
+                    self.expression(value);
+                }
+                ArrayItem::KeyValue { key, value, .. } => {
+                    self.expression(key);
+                    self.write(" => ");
+                    self.expression(value);
+                }
+                ArrayItem::ReferencedKeyValue { key, value, .. } => {
+                    self.expression(key);
+                    self.write(" => &");
+                    self.expression(value);
+                }
+            }
+        }
+    }
+
+    fn closure(&mut self, closure: &Closure) {
+        self.attributes(&closure.attributes, true);
+        if closure.r#static.is_some() {
+            self.write("static ");
+        }
+        self.write("function ");
+        if closure.ampersand.is_some() {
+            self.write("&");
+        }
+        self.parameters(&closure.parameters);
+        if let Some(uses) = &closure.uses {
+            self.write(" use (");
+            for (i, variable) in uses.variables.inner.iter().enumerate() {
+                if i > 0 {
+                    self.write(", ");
+                }
+
+                if variable.ampersand.is_some() {
+                    self.write("&");
+                }
+                self.bytes(&variable.variable.name);
+            }
+            self.write(")");
+        }
+        self.return_type(&closure.return_type);
+        self.write(" {");
+        self.block(&closure.body.statements);
+    }
+
+    fn arrow_function(&mut self, function: &ArrowFunction) {
+        self.attributes(&function.attributes, true);
+        if function.r#static.is_some() {
+            self.write("static ");
+        }
+        self.write("fn ");
+        if function.ampersand.is_some() {
+            self.write("&");
+        }
+        self.parameters(&function.parameters);
+        self.return_type(&function.return_type);
+        self.write(" => ");
+        self.expression(&function.body);
+    }
+
+    fn anonymous_class(&mut self, class: &AnonymousClass, arguments: Option<&ArgumentList>) {
+        self.attributes(&class.attributes, true);
+        if class.readonly.is_some() {
+            self.write("readonly ");
+        }
+        self.write("class");
+        if let Some(arguments) = arguments {
+            self.arguments(arguments);
+        }
+        self.extends(&class.extends);
+        self.implements(&class.implements);
+        self.write(" {");
+        self.members(class.body.members.iter().map(Member::from));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::print_expression;
+    use super::print_program;
+    use super::BraceStyle;
+    use super::Indentation;
+    use super::PrinterOptions;
+    use crate::parser::ast::visitor_mut::walk_expression_mut;
+    use crate::parser::ast::visitor_mut::VisitorMut;
+    use crate::parser::ast::Expression;
+    use crate::parser::ast::Statement;
+    use crate::ParserOptions;
+
+    struct Unparenthesize;
+
+    impl VisitorMut for Unparenthesize {
+        fn visit_expression_mut(&mut self, expression: &mut Expression) {
+            while let Expression::Parenthesized { start, expr, .. } = expression {
+                let noop = Expression::Noop { span: *start };
+                *expression = std::mem::replace(expr.as_mut(), noop);
+            }
+
+            walk_expression_mut(self, expression);
+        }
+    }
+
+    /// Print the given expression after removing its parentheses, which must be restored
+    /// from the precedence of the operators.
+    fn reparenthesize(code: &str) -> String {
+        let options = ParserOptions::new().with_experimental(true);
+        let mut program = crate::parse_with_options(&format!("<?php {};", code), &options).unwrap();
+
+        match &mut program[1] {
+            Statement::Expression(statement) => {
+                Unparenthesize.visit_expression_mut(&mut statement.expression);
+
+                print_expression(&statement.expression, &PrinterOptions::new())
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn print(code: &str, options: PrinterOptions) -> String {
+        print_program(&crate::parse(code).unwrap(), &options)
+    }
+
+    #[test]
+    fn test_parentheses() {
+        for code in [
+            "(1 + 2) * 3",
+            "1 - (2 - 3)",
+            "(2 ** 3) ** 4",
+            "-(-$a)",
+            "!($a && $b)",
+            "!$a instanceof B",
+            "($a = $b) + 1",
+            "$a ?? throw $e",
+            "($a ? $b : $c) ? $d : $e",
+            "$a ?: $b ?: $c",
+            "(fn () => $a) || $b",
+            "(clone $a)->b()",
+            "(new A())->b",
+            "new (foo())()",
+            "($a . 'b')::c()",
+            "$a |> foo(...) |> bar(...)",
+            "$a . 'b' |> strlen(...) == 3",
+            "($a |> foo(...)) . 'b'",
+            "$a |> ($b |> c(...))",
+        ] {
+            assert_eq!(reparenthesize(code), code);
+        }
+    }
+
+    #[test]
+    fn test_redundant_parentheses() {
+        let code = "<?php\n\n$a = ((1)) + (2 * 3);\nif (($a)) {\n    echo ($a);\n}\n";
+
+        assert_eq!(print(code, PrinterOptions::new()), code);
+    }
+
+    #[test]
+    fn test_typed_constants() {
+        let code = "<?php\n\nclass A\n{\n    final public const ?int B = null, C = 1;\n}\n";
+
+        assert_eq!(print(code, PrinterOptions::new()), code);
+    }
+
+    #[test]
+    fn test_property_hooks() {
+        let code = "\
+<?php
+
+interface A
+{
+    public string $b { get; set; }
+}
+
+class C implements A
+{
+    public string $b = '' {
+        #[D]
+        final get => $this->b;
+        set(string $value) {
+            $this->b = $value;
+        }
+    }
+}
+";
+
+        assert_eq!(print(code, PrinterOptions::new()), code);
+    }
+
+    #[test]
+    fn test_asymmetric_visibility() {
+        let code = "\
+<?php
+
+class A
+{
+    public private(set) string $b;
+    protected(set) readonly int $c;
+
+    public function __construct(public protected(set) array $d = [])
+    {
+    }
+}
+";
+
+        assert_eq!(print(code, PrinterOptions::new()), code);
+    }
+
+    #[test]
+    fn test_new_without_parentheses() {
+        let code = "\
+<?php
+
+(new Foo())->bar();
+(new Foo)->bar();
+new $a->b['c']();
+";
+
+        assert_eq!(print(code, PrinterOptions::new()), code);
+        assert_eq!(
+            print("<?php new Foo()::BAR;", PrinterOptions::new()),
+            "<?php\n\n(new Foo())::BAR;\n"
+        );
+    }
+
+    #[test]
+    fn test_exit() {
+        let code = "<?php\n\nexit;\nexit();\nexit(1);\ndie('message');\n";
+
+        assert_eq!(print(code, PrinterOptions::new()), code);
+    }
+
+    #[test]
+    fn test_strings() {
+        let code = r#"<?php
+
+'a\nb\c\'\\';
+"a\n\$b {$c} $d->e {$f}g \"";
+"#;
+
+        assert_eq!(print(code, PrinterOptions::new()), code);
+    }
+
+    #[test]
+    fn test_brace_styles() {
+        let code = "<?php class A { function b() { if ($c) {} else {} } }";
+
+        assert_eq!(
+            print(code, PrinterOptions::new()),
+            "\
+<?php
+
+class A
+{
+    function b()
+    {
+        if ($c) {
+        } else {
+        }
+    }
+}
+"
+        );
+
+        assert_eq!(
+            print(
+                code,
+                PrinterOptions::new()
+                    .with_brace_style(BraceStyle::SameLine)
+                    .with_indentation(Indentation::Tabs)
+            ),
+            "<?php\n\nclass A {\n\tfunction b() {\n\t\tif ($c) {\n\t\t} else {\n\t\t}\n\t}\n}\n"
+        );
+
+        assert_eq!(
+            print(
+                code,
+                PrinterOptions::new()
+                    .with_brace_style(BraceStyle::NextLine)
+                    .with_indentation(Indentation::Spaces(2))
+            ),
+            "\
+<?php
+
+class A
+{
+  function b()
+  {
+    if ($c)
+    {
+    }
+    else
+    {
+    }
+  }
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_inline_html() {
+        let code = "<p><?= $a ?></p>\n<?php if ($b): ?>\n<i></i>\n<?php endif ?>\n";
+
+        assert_eq!(
+            print(code, PrinterOptions::new()),
+            "<p><?= $a ?></p>\n<?php\n\nif ($b): ?>\n<i></i>\n<?php\nendif ?>\n"
+        );
+    }
+
+    #[test]
+    fn test_deserialized_program() {
+        let program = crate::parse("<?php\n\nfoo($a);\n").unwrap();
+
+        // a tree exported as json, edited externally, and read back.
+        let mut json = serde_json::to_value(&program).unwrap();
+        *json
+            .pointer_mut("/1/value/expression/value/target/value/value/value")
+            .unwrap() = "bar".into();
+        let program: crate::parser::ast::Program = serde_json::from_value(json).unwrap();
+
+        assert_eq!(
+            print_program(&program, &PrinterOptions::new()),
+            "<?php\n\nbar($a);\n"
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::modifiers::Visibility;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::Statement;
+use crate::printer::pretty::print_program;
+use crate::printer::pretty::PrinterOptions;
+
+/// Print the signatures of the declarations of a program, e.g. to publish IDE stubs of a
+/// package without its implementation.
+///
+/// See [`stubs`] for what is kept, an empty string is returned when nothing is.
+///
+/// ```
+/// use php_parser_rs::printer::print_stubs;
+/// use php_parser_rs::printer::PrinterOptions;
+///
+/// let program = php_parser_rs::parse("<?php function foo(int $a): int { return $a; } foo(1);").unwrap();
+///
+/// assert_eq!(
+///     print_stubs(&program, &PrinterOptions::new()),
+///     "<?php\n\nfunction foo(int $a): int\n{\n}\n"
+/// );
+/// ```
+pub fn print_stubs(program: &[Statement], options: &PrinterOptions) -> String {
+    let stubs = stubs(program);
+
+    if stubs
+        .iter()
+        .all(|statement| matches!(statement, Statement::FullOpeningTag(_)))
+    {
+        return String::new();
+    }
+
+    print_program(&stubs, options)
+}
+
+/// The declarations of a program without their implementation.
+///
+/// Functions, class-likes, constants, and the imports and namespaces they are declared in are
+/// kept, along with their docblocks and attributes. Bodies of functions and methods are
+/// emptied, and private members are removed, except for constructors. Any other statement,
+/// including declarations nested in a conditional, is removed.
+pub fn stubs(program: &[Statement]) -> Vec<Statement> {
+    let mut stubs = declarations(program);
+
+    if let Some(Statement::FullOpeningTag(span)) = program.first() {
+        stubs.insert(0, Statement::FullOpeningTag(*span));
+    }
+
+    stubs
+}
+
+fn declarations(statements: &[Statement]) -> Vec<Statement> {
+    let mut stubs = Vec::new();
+
+    for statement in statements.iter() {
+        let mut statement = statement.clone();
+
+        match &mut statement {
+            Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                namespace.statements = declarations(&namespace.statements);
+            }
+            Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                namespace.body.statements = declarations(&namespace.body.statements);
+            }
+            Statement::Use(_) | Statement::GroupUse(_) | Statement::Constant(_) => {}
+            Statement::Function(function) => function.body.statements.clear(),
+            Statement::Class(class) => {
+                class.body.members.retain_mut(|member| match member {
+                    ClassMember::Constant(constant) => {
+                        constant.modifiers.visibility() != Visibility::Private
+                    }
+                    ClassMember::Property(property) => {
+                        property.modifiers.visibility() != Visibility::Private
+                    }
+                    ClassMember::AbstractMethod(_)
+                    | ClassMember::AbstractConstructor(_)
+                    | ClassMember::VariableProperty(_)
+                    | ClassMember::TraitUsage(_) => true,
+                    ClassMember::ConcreteMethod(method) => {
+                        method.body.statements.clear();
+
+                        method.modifiers.visibility() != Visibility::Private
+                    }
+                    ClassMember::ConcreteConstructor(constructor) => {
+                        constructor.body.statements.clear();
+
+                        true
+                    }
+                });
+            }
+            Statement::Trait(r#trait) => {
+                r#trait.body.members.retain_mut(|member| match member {
+                    TraitMember::Constant(constant) => {
+                        constant.modifiers.visibility() != Visibility::Private
+                    }
+                    TraitMember::Property(property) => {
+                        property.modifiers.visibility() != Visibility::Private
+                    }
+                    TraitMember::AbstractMethod(method) => {
+                        method.modifiers.visibility() != Visibility::Private
+                    }
+                    TraitMember::AbstractConstructor(_)
+                    | TraitMember::VariableProperty(_)
+                    | TraitMember::TraitUsage(_) => true,
+                    TraitMember::ConcreteMethod(method) => {
+                        method.body.statements.clear();
+
+                        method.modifiers.visibility() != Visibility::Private
+                    }
+                    TraitMember::ConcreteConstructor(constructor) => {
+                        constructor.body.statements.clear();
+
+                        true
+                    }
+                });
+            }
+            Statement::Interface(_) => {}
+            Statement::UnitEnum(r#enum) => {
+                r#enum.body.members.retain_mut(|member| match member {
+                    UnitEnumMember::Case(_) => true,
+                    UnitEnumMember::Constant(constant) => {
+                        constant.modifiers.visibility() != Visibility::Private
+                    }
+                    UnitEnumMember::Method(method) => {
+                        method.body.statements.clear();
+
+                        method.modifiers.visibility() != Visibility::Private
+                    }
+                });
+            }
+            Statement::BackedEnum(r#enum) => {
+                r#enum.body.members.retain_mut(|member| match member {
+                    BackedEnumMember::Case(_) => true,
+                    BackedEnumMember::Constant(constant) => {
+                        constant.modifiers.visibility() != Visibility::Private
+                    }
+                    BackedEnumMember::Method(method) => {
+                        method.body.statements.clear();
+
+                        method.modifiers.visibility() != Visibility::Private
+                    }
+                });
+            }
+            _ => continue,
+        }
+
+        stubs.push(statement);
+    }
+
+    // drop namespaces, and the imports of namespaces, without any declaration.
+    let declares = |statements: &[Statement]| {
+        statements.iter().any(|statement| {
+            !matches!(
+                statement,
+                Statement::Use(_) | Statement::GroupUse(_) | Statement::Namespace(_)
+            )
+        })
+    };
+
+    stubs.retain(|statement| match statement {
+        Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+            declares(&namespace.statements)
+        }
+        Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+            declares(&namespace.body.statements)
+        }
+        _ => true,
+    });
+
+    if !declares(&stubs) {
+        stubs.retain(|statement| matches!(statement, Statement::Namespace(_)));
+    }
+
+    stubs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::print_stubs;
+    use crate::printer::PrinterOptions;
+
+    fn stubs(code: &str) -> String {
+        print_stubs(&crate::parse(code).unwrap(), &PrinterOptions::new())
+    }
+
+    #[test]
+    fn test_stubs() {
+        let code = r#"<?php
+
+declare(strict_types=1);
+
+namespace App;
+
+use Foo\Bar;
+
+const VERSION = '1.0';
+
+/**
+ * A user.
+ */
+final class User extends Bar
+{
+    private const SECRET = 'a';
+
+    public const ADMIN = 'admin';
+
+    private int $id;
+
+    protected ?string $name = null;
+
+    private function __construct(int $id)
+    {
+        $this->id = $id;
+    }
+
+    /**
+     * The name of the user.
+     */
+    public function name(): ?string
+    {
+        return $this->name;
+    }
+
+    private function secret(): string
+    {
+        return self::SECRET;
+    }
+}
+
+if (!function_exists('App\helper')) {
+    function helper() {}
+}
+
+echo new User(1);
+"#;
+
+        assert_eq!(
+            stubs(code),
+            r#"<?php
+
+namespace App;
+
+use Foo\Bar;
+
+const VERSION = '1.0';
+
+/**
+ * A user.
+ */
+final class User extends Bar
+{
+    public const ADMIN = 'admin';
+
+    protected ?string $name = null;
+
+    private function __construct(int $id)
+    {
+    }
+
+    /**
+     * The name of the user.
+     */
+    public function name(): ?string
+    {
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_stubs_without_declarations() {
+        assert_eq!(
+            stubs("<?php\n\nnamespace App;\n\nuse Foo\\Bar;\n\necho 1;\n"),
+            ""
+        );
+        assert_eq!(stubs("<html><?php echo 1; ?></html>"), "");
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use serde::de::Error;
+use serde::Deserialize;
+use serde::Deserializer;
+
+use super::ScanOptions;
+use crate::analysis::pedantic::PedanticRule;
+use crate::analysis::pedantic::Severity;
+use crate::parser::options::ParserOptions;
+use crate::parser::options::PhpVersion;
+use crate::parser::suppressions::SuppressionMode;
+use crate::printer::BraceStyle;
+use crate::printer::Indentation;
+use crate::printer::PrinterOptions;
+
+/// The name of the configuration file of a project.
+pub const CONFIG_FILE: &str = "php-parser.toml";
+
+/// The configuration of a project, read from the `php-parser.toml` file at its root.
+///
+/// ```toml
+/// php-version = "8.1"
+/// experimental = false
+/// suppressions = "report-unused"
+/// pedantic = ["assignment-in-condition", "suppressed-include"]
+///
+/// [severities]
+/// assignment-in-condition = "error"
+///
+/// [files]
+/// extensions = ["php"]
+/// include = ["src/**"]
+/// exclude = ["src/legacy/**"]
+/// skip-generated = true
+/// max-file-size = 1048576
+///
+/// [printer]
+/// indent = 4
+/// tabs = false
+/// braces = "psr12"
+/// ```
+///
+/// Every key is optional, and unknown keys are rejected.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// The version of PHP the code targets, see [`ParserOptions::php_version`].
+    #[serde(deserialize_with = "parse_optional")]
+    pub php_version: Option<PhpVersion>,
+    /// Whether to accept experimental syntax, see [`ParserOptions::experimental`].
+    pub experimental: bool,
+    /// How suppression comments are handled, see [`ParserOptions::suppressions`].
+    #[serde(deserialize_with = "parse_optional")]
+    pub suppressions: Option<SuppressionMode>,
+    /// The pedantic rules to check, see [`PedanticRule`].
+    #[serde(deserialize_with = "parse_list")]
+    pub pedantic: Vec<PedanticRule>,
+    /// The severity of the warnings of each pedantic rule, see [`ProjectConfig::severity`].
+    #[serde(deserialize_with = "parse_map")]
+    pub severities: BTreeMap<PedanticRule, Severity>,
+    pub files: FilesConfig,
+    pub printer: PrinterConfig,
+}
+
+/// The files of a project, see [`ScanOptions`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct FilesConfig {
+    pub extensions: Vec<String>,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub skip_generated: bool,
+    pub max_file_size: Option<u64>,
+}
+
+impl Default for FilesConfig {
+    fn default() -> Self {
+        let options = ScanOptions::default();
+
+        Self {
+            extensions: options.extensions,
+            include: options.include,
+            exclude: options.exclude,
+            skip_generated: options.skip_generated,
+            max_file_size: options.max_file_size,
+        }
+    }
+}
+
+/// The style of the pretty printer, see [`PrinterOptions`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct PrinterConfig {
+    /// The number of spaces per indentation level.
+    pub indent: usize,
+    /// Whether to indent with tabs rather than spaces.
+    pub tabs: bool,
+    /// The placement of opening braces: `psr12`, `same-line` or `next-line`.
+    #[serde(deserialize_with = "parse")]
+    pub braces: BraceStyle,
+}
+
+impl Default for PrinterConfig {
+    fn default() -> Self {
+        Self {
+            indent: 4,
+            tabs: false,
+            braces: BraceStyle::default(),
+        }
+    }
+}
+
+impl ProjectConfig {
+    /// Read the configuration file at the given path.
+    ///
+    /// An invalid file is reported as an [`std::io::ErrorKind::InvalidData`] error.
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        std::fs::read_to_string(path)?.parse().map_err(|error| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), error),
+            )
+        })
+    }
+
+    /// Find the configuration file of the project containing the given directory, searching
+    /// the directory and then its ancestors.
+    pub fn find(directory: &Path) -> Option<PathBuf> {
+        directory
+            .ancestors()
+            .map(|ancestor| ancestor.join(CONFIG_FILE))
+            .find(|path| path.is_file())
+    }
+
+    /// The options to parse the files of the project with, if any differ from the defaults.
+    pub fn parser_options(&self) -> Option<ParserOptions> {
+        if self.php_version.is_none() && !self.experimental && self.suppressions.is_none() {
+            return None;
+        }
+
+        Some(ParserOptions {
+            php_version: self.php_version,
+            ..ParserOptions::new()
+                .with_experimental(self.experimental)
+                .with_suppressions(self.suppressions.unwrap_or_default())
+        })
+    }
+
+    /// The severity of the warnings of the given pedantic rule, a warning unless configured
+    /// otherwise.
+    pub fn severity(&self, rule: PedanticRule) -> Severity {
+        self.severities.get(&rule).copied().unwrap_or_default()
+    }
+
+    pub fn scan_options(&self) -> ScanOptions {
+        ScanOptions {
+            extensions: self.files.extensions.clone(),
+            include: self.files.include.clone(),
+            exclude: self.files.exclude.clone(),
+            skip_generated: self.files.skip_generated,
+            max_file_size: self.files.max_file_size,
+            parser: self.parser_options(),
+            ..ScanOptions::default()
+        }
+    }
+
+    pub fn printer_options(&self) -> PrinterOptions {
+        PrinterOptions::new()
+            .with_indentation(if self.printer.tabs {
+                Indentation::Tabs
+            } else {
+                Indentation::Spaces(self.printer.indent)
+            })
+            .with_brace_style(self.printer.braces)
+    }
+}
+
+impl FromStr for ProjectConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s).map_err(|error| error.message().to_string())
+    }
+}
+
+fn parse<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(D::Error::custom)
+}
+
+fn parse_optional<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    parse(deserializer).map(Some)
+}
+
+fn parse_list<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|value| value.parse().map_err(D::Error::custom))
+        .collect()
+}
+
+fn parse_map<'de, D, K, V>(deserializer: D) -> Result<BTreeMap<K, V>, D::Error>
+where
+    D: Deserializer<'de>,
+    K: FromStr + Ord,
+    K::Err: Display,
+    V: FromStr,
+    V::Err: Display,
+{
+    BTreeMap::<String, String>::deserialize(deserializer)?
+        .iter()
+        .map(|(key, value)| {
+            Ok((
+                key.parse().map_err(D::Error::custom)?,
+                value.parse().map_err(D::Error::custom)?,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProjectConfig;
+    use super::CONFIG_FILE;
+    use crate::analysis::pedantic::PedanticRule;
+    use crate::analysis::pedantic::Severity;
+    use crate::parser::options::PhpVersion;
+    use crate::parser::suppressions::SuppressionMode;
+    use crate::printer::BraceStyle;
+    use crate::printer::Indentation;
+    use crate::project::ScanOptions;
+
+    #[test]
+    fn test_defaults() {
+        let config: ProjectConfig = "".parse().unwrap();
+
+        assert_eq!(config, ProjectConfig::default());
+        assert_eq!(config.parser_options(), None);
+        assert_eq!(config.scan_options(), ScanOptions::default());
+        assert_eq!(config.printer_options(), Default::default());
+    }
+
+    #[test]
+    fn test_config() {
+        let config: ProjectConfig = r#"
+            php-version = "7.4"
+            suppressions = "report-unused"
+            pedantic = ["bitwise-comparison"]
+
+            [severities]
+            bitwise-comparison = "error"
+            negated-comparison = "warning"
+
+            [files]
+            include = ["src/**"]
+            exclude = ["src/legacy/**"]
+            max-file-size = 1024
+
+            [printer]
+            tabs = true
+            braces = "next-line"
+        "#
+        .parse()
+        .unwrap();
+
+        let parser = config.parser_options().unwrap();
+        assert_eq!(parser.php_version, Some(PhpVersion::Php74));
+        assert!(!parser.experimental);
+        assert_eq!(parser.suppressions, SuppressionMode::ReportUnused);
+        assert_eq!(config.pedantic, vec![PedanticRule::BitwiseComparison]);
+        assert_eq!(
+            config.severity(PedanticRule::BitwiseComparison),
+            Severity::Error
+        );
+        assert_eq!(
+            config.severity(PedanticRule::AssignmentInCondition),
+            Severity::Warning
+        );
+
+        let scan = config.scan_options();
+        assert_eq!(scan.extensions, vec!["php".to_string()]);
+        assert_eq!(scan.include, vec!["src/**".to_string()]);
+        assert_eq!(scan.exclude, vec!["src/legacy/**".to_string()]);
+        assert!(scan.skip_generated);
+        assert_eq!(scan.max_file_size, Some(1024));
+        assert_eq!(scan.parser, Some(parser));
+
+        let printer = config.printer_options();
+        assert_eq!(printer.indentation, Indentation::Tabs);
+        assert_eq!(printer.brace_style, BraceStyle::NextLine);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert_eq!(
+            "php-version = \"5.6\"".parse::<ProjectConfig>(),
+            Err("unknown PHP version `5.6`".to_string())
+        );
+        assert_eq!(
+            "[printer]\nbraces = \"k&r\"".parse::<ProjectConfig>(),
+            Err(
+                "unknown brace style `k&r`, expecting `psr12`, `same-line` or `next-line`"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            "pedantic = [\"yoda\"]".parse::<ProjectConfig>(),
+            Err("unknown pedantic rule `yoda`".to_string())
+        );
+        assert_eq!(
+            "[severities]\nbitwise-comparison = \"fatal\"".parse::<ProjectConfig>(),
+            Err("unknown severity `fatal`, expecting `warning` or `error`".to_string())
+        );
+        assert!("[files]\nexcludes = []"
+            .parse::<ProjectConfig>()
+            .unwrap_err()
+            .starts_with("unknown field `excludes`"));
+    }
+
+    #[test]
+    fn test_find() {
+        let root = std::env::temp_dir().join(format!("php-parser-config-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src/nested")).unwrap();
+        std::fs::write(root.join(CONFIG_FILE), "php-version = \"8.0\"").unwrap();
+
+        let path = ProjectConfig::find(&root.join("src/nested")).unwrap();
+        assert_eq!(path, root.join(CONFIG_FILE));
+        assert_eq!(
+            ProjectConfig::load(&path).unwrap().php_version,
+            Some(PhpVersion::Php80)
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
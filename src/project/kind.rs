@@ -0,0 +1,176 @@
+use crate::lexer::error::SyntaxResult;
+use crate::lexer::token::OpenTagKind;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+use crate::lexer::Lexer;
+
+/// The kind of a PHP file, as guessed from its tokens.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FileKind {
+    /// A file declaring classes, interfaces, traits, or enums, and nothing else
+    /// besides namespaces, imports, and `declare` statements.
+    ClassFile,
+    /// A file executing code when included, e.g. a script or a file of functions.
+    Script,
+    /// A file mixing HTML and PHP.
+    Template,
+}
+
+/// Guess the kind of the given source code without parsing it.
+pub fn detect_kind<B: ?Sized + AsRef<[u8]>>(input: &B) -> SyntaxResult<FileKind> {
+    let tokens = Lexer::new().tokenize(input)?;
+
+    Ok(detect_kind_from_tokens(&tokens))
+}
+
+/// Guess the kind of a file from its tokens.
+pub fn detect_kind_from_tokens(tokens: &[Token]) -> FileKind {
+    let is_template = tokens
+        .iter()
+        .enumerate()
+        .any(|(index, token)| match &token.kind {
+            TokenKind::OpenTag(OpenTagKind::Echo) => true,
+            // Whitespace following a closing tag, and a leading shebang line, are ignored.
+            TokenKind::InlineHtml => {
+                let whitespace = token.value.iter().all(u8::is_ascii_whitespace);
+                let shebang = index == 0 && token.value.starts_with(b"#!");
+
+                !whitespace && !shebang
+            }
+            _ => false,
+        });
+
+    if is_template {
+        return FileKind::Template;
+    }
+
+    let mut depth = 0;
+    // The depth of the statements of a bracketed namespace.
+    let mut namespace_depth = None;
+    let mut namespace = false;
+    let mut statement_start = true;
+    let mut attribute_depth = 0;
+    let mut declarations = 0;
+
+    for token in tokens {
+        let top_level = depth == 0 || Some(depth) == namespace_depth;
+
+        match &token.kind {
+            TokenKind::SingleLineComment
+            | TokenKind::HashMarkComment
+            | TokenKind::MultiLineComment
+            | TokenKind::DocumentComment
+            | TokenKind::OpenTag(_)
+            | TokenKind::CloseTag
+            | TokenKind::InlineHtml
+            | TokenKind::Eof => continue,
+            TokenKind::Attribute => {
+                attribute_depth += 1;
+                continue;
+            }
+            TokenKind::LeftBracket if attribute_depth > 0 => {
+                attribute_depth += 1;
+                continue;
+            }
+            TokenKind::RightBracket if attribute_depth > 0 => {
+                attribute_depth -= 1;
+                continue;
+            }
+            _ if attribute_depth > 0 => continue,
+            _ => {}
+        }
+
+        if top_level && statement_start {
+            statement_start = false;
+
+            match &token.kind {
+                TokenKind::Class
+                | TokenKind::Interface
+                | TokenKind::Trait
+                | TokenKind::Enum
+                | TokenKind::Abstract
+                | TokenKind::Final
+                | TokenKind::Readonly => declarations += 1,
+                TokenKind::Namespace => namespace = true,
+                TokenKind::Use | TokenKind::Declare => {}
+                // The end of a bracketed namespace.
+                TokenKind::RightBrace => {}
+                TokenKind::SemiColon => statement_start = true,
+                _ => return FileKind::Script,
+            }
+        }
+
+        match &token.kind {
+            TokenKind::LeftBrace | TokenKind::DollarLeftBrace => {
+                depth += 1;
+
+                if namespace && top_level {
+                    namespace_depth = Some(depth);
+                    statement_start = true;
+                }
+
+                namespace = false;
+            }
+            TokenKind::RightBrace => {
+                if Some(depth) == namespace_depth {
+                    namespace_depth = None;
+                }
+
+                depth -= 1;
+                statement_start = depth == 0 || Some(depth) == namespace_depth;
+            }
+            TokenKind::SemiColon if top_level => {
+                namespace = false;
+                statement_start = true;
+            }
+            _ => {}
+        }
+    }
+
+    if declarations > 0 {
+        FileKind::ClassFile
+    } else {
+        FileKind::Script
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect_kind;
+    use super::FileKind;
+
+    #[test]
+    fn test_detect_kind() {
+        let class_files = [
+            "<?php declare(strict_types=1);\n\nnamespace App;\n\nuse Foo\\Bar;\n\n#[Attribute([1, 2])]\nfinal class A extends Bar { public function a() { return \"{$this->b}\"; } }\n",
+            "<?php namespace A { interface B {} } namespace C { enum D: string { case E = 'e'; } }",
+            "#!/usr/bin/env php\n<?php abstract class A {}\n?>\n",
+        ];
+
+        for code in class_files {
+            assert_eq!(detect_kind(code), Ok(FileKind::ClassFile), "{}", code);
+        }
+
+        let scripts = [
+            "<?php echo 1;",
+            "<?php namespace A; function a() {}",
+            "<?php class A {} A::boot();",
+            "<?php namespace A { class B {} } namespace { (new A\\B())->run(); }",
+            "<?php use A\\B;",
+            "",
+        ];
+
+        for code in scripts {
+            assert_eq!(detect_kind(code), Ok(FileKind::Script), "{}", code);
+        }
+
+        let templates = [
+            "<h1><?php echo $title; ?></h1>",
+            "<?php $a = 1; ?>\n<p><?= $a ?></p>",
+        ];
+
+        for code in templates {
+            assert_eq!(detect_kind(code), Ok(FileKind::Template), "{}", code);
+        }
+    }
+}
@@ -0,0 +1,793 @@
+use std::fmt::Display;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::node::Node;
+use crate::parser::ast::Program;
+use crate::parser::cancellation::CancellationToken;
+use crate::parser::error::ParseErrorStack;
+use crate::parser::options::ParserOptions;
+use crate::parser::reusable::Parser;
+
+mod config;
+mod kind;
+#[cfg(feature = "async")]
+mod stream;
+
+pub use config::FilesConfig;
+pub use config::PrinterConfig;
+pub use config::ProjectConfig;
+pub use config::CONFIG_FILE;
+pub use kind::detect_kind;
+pub use kind::detect_kind_from_tokens;
+pub use kind::FileKind;
+
+#[cfg(feature = "async")]
+pub use stream::parse_dir_stream;
+
+/// The number of leading bytes searched for a generated code marker.
+const GENERATED_MARKER_WINDOW: usize = 1024;
+
+const GENERATED_MARKERS: [&[u8]; 2] = [b"@generated", b"@auto-generated"];
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ScanOptions {
+    /// The extensions of the files to parse, without a leading `.`.
+    pub extensions: Vec<String>,
+    /// Patterns of the paths to parse, relative to the scanned directory, e.g. `src/**`.
+    ///
+    /// Files matching none of the patterns are left out of the scan, unless there are none.
+    pub include: Vec<String>,
+    /// Patterns of the paths to skip, relative to the scanned directory, e.g. `vendor/**`.
+    ///
+    /// `*` matches any characters except `/`, `**` matches any number of directories,
+    /// and `?` matches a single character except `/`.
+    pub exclude: Vec<String>,
+    /// Whether to skip files marked with `@generated` near the top of the file.
+    pub skip_generated: bool,
+    /// The maximum size of a file, in bytes.
+    pub max_file_size: Option<u64>,
+    /// The maximum time spent parsing a single file.
+    ///
+    /// Parsing is cancelled between the top-level statements of a file once the limit is
+    /// exceeded, files exceeding it are reported as skipped, and their AST is discarded.
+    pub max_parse_time: Option<Duration>,
+    /// Whether to record a [`FileProfile`] for every parsed file.
+    pub profile: bool,
+    /// A token cancelling the scan, checked between files and between the top-level
+    /// statements of a file.
+    pub cancellation: Option<CancellationToken>,
+    /// The options every file is parsed with, see [`crate::parse_with_options`].
+    pub parser: Option<ParserOptions>,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            extensions: vec!["php".to_string()],
+            include: vec![],
+            exclude: vec![],
+            skip_generated: true,
+            max_file_size: None,
+            max_parse_time: None,
+            profile: false,
+            cancellation: None,
+            parser: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SkipReason {
+    Excluded { pattern: String },
+    Generated,
+    TooLarge { size: u64, limit: u64 },
+    TooSlow { elapsed: Duration, limit: Duration },
+    Unreadable { message: String },
+}
+
+#[derive(Debug)]
+pub enum FileOutcome {
+    Parsed(Program),
+    Failed(ParseErrorStack),
+    Skipped(SkipReason),
+}
+
+/// The cost of parsing a single file.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct FileProfile {
+    pub bytes: usize,
+    pub tokens: usize,
+    pub nodes: usize,
+    /// The time spent lexing and parsing the file.
+    pub duration: Duration,
+}
+
+/// The error of a project scan.
+#[derive(Debug)]
+pub enum ScanError {
+    /// The scan was cancelled, see [`ScanOptions::cancellation`], with the results of the
+    /// files scanned so far which were not returned yet, including the partial program of a
+    /// file whose parse was cancelled.
+    Cancelled { results: Vec<FileResult> },
+    /// The scanned directory or file could not be read.
+    Io(std::io::Error),
+}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScanError::Cancelled { .. } => write!(f, "scan was cancelled"),
+            ScanError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ScanError {}
+
+impl From<std::io::Error> for ScanError {
+    fn from(error: std::io::Error) -> Self {
+        ScanError::Io(error)
+    }
+}
+
+#[derive(Debug)]
+pub struct FileResult {
+    /// The path of the file, relative to the scanned directory.
+    pub path: PathBuf,
+    pub outcome: FileOutcome,
+    /// The profile of the file, when profiling is enabled and the file was parsed.
+    pub profile: Option<FileProfile>,
+}
+
+/// The profiles of the parsed files of a scan, ordered from the slowest file to the fastest.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct ProfileReport {
+    pub files: Vec<(PathBuf, FileProfile)>,
+}
+
+impl ProfileReport {
+    pub fn new(results: &[FileResult]) -> Self {
+        let mut files: Vec<(PathBuf, FileProfile)> = results
+            .iter()
+            .filter_map(|result| Some((result.path.clone(), result.profile?)))
+            .collect();
+        files.sort_by(|(a, a_profile), (b, b_profile)| {
+            b_profile.duration.cmp(&a_profile.duration).then(a.cmp(b))
+        });
+
+        Self { files }
+    }
+
+    pub fn slowest(&self, count: usize) -> &[(PathBuf, FileProfile)] {
+        &self.files[..count.min(self.files.len())]
+    }
+
+    pub fn total(&self) -> FileProfile {
+        self.files
+            .iter()
+            .fold(FileProfile::default(), |total, (_, profile)| FileProfile {
+                bytes: total.bytes + profile.bytes,
+                tokens: total.tokens + profile.tokens,
+                nodes: total.nodes + profile.nodes,
+                duration: total.duration + profile.duration,
+            })
+    }
+}
+
+impl Display for ProfileReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line = |f: &mut std::fmt::Formatter<'_>, profile: &FileProfile, path: &str| {
+            writeln!(
+                f,
+                "{:>12.3?} {:>10} bytes {:>8} tokens {:>8} nodes  {}",
+                profile.duration, profile.bytes, profile.tokens, profile.nodes, path
+            )
+        };
+
+        for (path, profile) in self.files.iter() {
+            line(f, profile, &path.to_string_lossy())?;
+        }
+
+        line(
+            f,
+            &self.total(),
+            &format!("total ({} files)", self.files.len()),
+        )
+    }
+}
+
+/// Parse every matching file within the given directory, recursively, in path order.
+///
+/// Symbolic links to directories are not followed. Directories matching an exclusion
+/// pattern ending with `/**`, and directories which cannot be read, are reported as
+/// skipped rather than walked.
+///
+/// When the scan is cancelled, a [`ScanError::Cancelled`] error holding the results of the
+/// files scanned so far is returned.
+pub fn scan(root: &Path, options: &ScanOptions) -> Result<Vec<FileResult>, ScanError> {
+    let mut found = Vec::new();
+    collect(root, root, options, &mut found)?;
+
+    let mut parser = parser(options);
+    let mut results = Vec::new();
+    for entry in found {
+        if is_cancelled(options) {
+            return Err(ScanError::Cancelled { results });
+        }
+
+        let (path, relative) = match entry {
+            Entry::File(path, relative) => (path, relative),
+            Entry::Skipped(relative, reason) => {
+                results.push(FileResult {
+                    path: relative,
+                    outcome: FileOutcome::Skipped(reason),
+                    profile: None,
+                });
+
+                continue;
+            }
+        };
+
+        let (outcome, profile) = parse_file(&mut parser, &path, &relative, options);
+        let cancelled = is_parse_cancelled(&outcome, options);
+
+        results.push(FileResult {
+            path: relative,
+            outcome,
+            profile,
+        });
+
+        if cancelled {
+            return Err(ScanError::Cancelled { results });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Parse a single file within the given directory, as if it was found by [`scan`].
+pub fn scan_file(
+    root: &Path,
+    file: &str,
+    options: &ScanOptions,
+) -> Result<Vec<FileResult>, ScanError> {
+    let path = root.join(file);
+    if !path.is_file() {
+        return Err(ScanError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} is not a file", path.display()),
+        )));
+    }
+
+    if is_cancelled(options) {
+        return Err(ScanError::Cancelled { results: vec![] });
+    }
+
+    let mut parser = parser(options);
+    let (outcome, profile) = parse_file(&mut parser, &path, Path::new(file), options);
+    let cancelled = is_parse_cancelled(&outcome, options);
+    let results = vec![FileResult {
+        path: file.into(),
+        outcome,
+        profile,
+    }];
+
+    if cancelled {
+        return Err(ScanError::Cancelled { results });
+    }
+
+    Ok(results)
+}
+
+/// Whether the given source is marked as generated, i.e. contains an `@generated`
+/// or `@auto-generated` marker near the top of the file.
+pub fn is_generated(source: &[u8]) -> bool {
+    let header = &source[..source.len().min(GENERATED_MARKER_WINDOW)];
+
+    GENERATED_MARKERS.iter().any(|marker| {
+        header
+            .windows(marker.len())
+            .any(|window| window.eq_ignore_ascii_case(marker))
+    })
+}
+
+/// Whether the given `/` separated path matches the given inclusion or exclusion pattern.
+pub fn matches_pattern(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    let path: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    matches_segments(&pattern, &path)
+}
+
+fn matches_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match (pattern.first(), path.first()) {
+        (None, None) => true,
+        (Some(&"**"), _) => {
+            matches_segments(&pattern[1..], path)
+                || (!path.is_empty() && matches_segments(pattern, &path[1..]))
+        }
+        (Some(segment), Some(name)) => {
+            matches_segment(segment.as_bytes(), name.as_bytes())
+                && matches_segments(&pattern[1..], &path[1..])
+        }
+        _ => false,
+    }
+}
+
+fn matches_segment(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => {
+            matches_segment(&pattern[1..], name)
+                || (!name.is_empty() && matches_segment(pattern, &name[1..]))
+        }
+        (Some(b'?'), Some(_)) => matches_segment(&pattern[1..], &name[1..]),
+        (Some(a), Some(b)) => a == b && matches_segment(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+fn parser(options: &ScanOptions) -> Parser {
+    let mut parser_options = options.parser.clone().unwrap_or_default();
+    if let Some(cancellation) = &options.cancellation {
+        parser_options = parser_options.with_cancellation(cancellation.clone());
+    }
+
+    Parser::new().with_options(parser_options)
+}
+
+fn is_cancelled(options: &ScanOptions) -> bool {
+    options
+        .cancellation
+        .as_ref()
+        .is_some_and(|cancellation| cancellation.is_cancelled())
+}
+
+/// Whether the parse of a file was stopped as the scan was cancelled.
+fn is_parse_cancelled(outcome: &FileOutcome, options: &ScanOptions) -> bool {
+    matches!(outcome, FileOutcome::Failed(stack) if stack.is_cancelled()) && is_cancelled(options)
+}
+
+/// An entry found while walking the scanned directory.
+enum Entry {
+    /// A file to parse, and its path relative to the scanned directory.
+    File(PathBuf, PathBuf),
+    /// A file or directory left out of the scan, by its path relative to the scanned directory.
+    Skipped(PathBuf, SkipReason),
+}
+
+fn collect(
+    root: &Path,
+    directory: &Path,
+    options: &ScanOptions,
+    found: &mut Vec<Entry>,
+) -> std::io::Result<()> {
+    let mut entries = std::fs::read_dir(directory)?
+        .map(|entry| entry.and_then(|entry| Ok((entry.path(), entry.file_type()?))))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    for (path, file_type) in entries {
+        let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+
+        // Symbolic links to directories are not followed, as they may point out of the
+        // scanned directory, or back into it.
+        if file_type.is_dir() {
+            if let Some(reason) = pruned(&relative, options) {
+                found.push(Entry::Skipped(relative, reason));
+            } else if let Err(error) = collect(root, &path, options, found) {
+                found.push(Entry::Skipped(relative, unreadable(error)));
+            }
+        } else if (file_type.is_file() || (file_type.is_symlink() && path.is_file()))
+            && has_extension(&path, options)
+            && included(&relative, options)
+        {
+            found.push(Entry::File(path, relative));
+        }
+    }
+
+    Ok(())
+}
+
+fn has_extension(path: &Path, options: &ScanOptions) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            options
+                .extensions
+                .iter()
+                .any(|candidate| candidate.eq_ignore_ascii_case(extension))
+        })
+}
+
+fn parse_file(
+    parser: &mut Parser,
+    path: &Path,
+    relative: &Path,
+    options: &ScanOptions,
+) -> (FileOutcome, Option<FileProfile>) {
+    if let Some(reason) = excluded(relative, options) {
+        return skipped(reason);
+    }
+
+    if let Some(limit) = options.max_file_size {
+        match std::fs::metadata(path) {
+            Ok(metadata) => {
+                if let Some(reason) = too_large(metadata.len(), limit) {
+                    return skipped(reason);
+                }
+            }
+            Err(error) => return skipped(unreadable(error)),
+        }
+    }
+
+    match std::fs::read(path) {
+        Ok(source) => parse_source(parser, &source, options),
+        Err(error) => skipped(unreadable(error)),
+    }
+}
+
+fn included(relative: &Path, options: &ScanOptions) -> bool {
+    let normalized = normalize(relative);
+
+    options.include.is_empty()
+        || options
+            .include
+            .iter()
+            .any(|pattern| matches_pattern(pattern, &normalized))
+}
+
+fn excluded(relative: &Path, options: &ScanOptions) -> Option<SkipReason> {
+    let normalized = normalize(relative);
+
+    options
+        .exclude
+        .iter()
+        .find(|pattern| matches_pattern(pattern, &normalized))
+        .map(|pattern| SkipReason::Excluded {
+            pattern: pattern.clone(),
+        })
+}
+
+/// The exclusion pattern skipping every file within the given directory, if any, so the
+/// directory does not need to be walked.
+fn pruned(relative: &Path, options: &ScanOptions) -> Option<SkipReason> {
+    let normalized = normalize(relative);
+
+    options
+        .exclude
+        .iter()
+        .find(|pattern| {
+            pattern == &"**"
+                || pattern
+                    .strip_suffix("/**")
+                    .is_some_and(|prefix| matches_pattern(prefix, &normalized))
+        })
+        .map(|pattern| SkipReason::Excluded {
+            pattern: pattern.clone(),
+        })
+}
+
+fn normalize(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn too_large(size: u64, limit: u64) -> Option<SkipReason> {
+    (size > limit).then_some(SkipReason::TooLarge { size, limit })
+}
+
+fn unreadable(error: std::io::Error) -> SkipReason {
+    SkipReason::Unreadable {
+        message: error.to_string(),
+    }
+}
+
+fn parse_source(
+    parser: &mut Parser,
+    source: &[u8],
+    options: &ScanOptions,
+) -> (FileOutcome, Option<FileProfile>) {
+    if options.skip_generated && is_generated(source) {
+        return skipped(SkipReason::Generated);
+    }
+
+    let start = Instant::now();
+    if let Some(limit) = options.max_parse_time {
+        let cancellation = options.cancellation.clone().unwrap_or_default();
+        parser.options_mut().cancellation = Some(cancellation.with_deadline(start + limit));
+    }
+
+    let result = parser.parse(source);
+    let tokens = parser.token_count();
+    let elapsed = start.elapsed();
+
+    if let Some(limit) = options.max_parse_time {
+        // A parse cancelled by the deadline took at least as long as the limit.
+        if elapsed >= limit {
+            return skipped(SkipReason::TooSlow { elapsed, limit });
+        }
+    }
+
+    let mut outcome = match result {
+        Ok(program) => FileOutcome::Parsed(program),
+        Err(stack) => FileOutcome::Failed(stack),
+    };
+
+    let profile = options.profile.then(|| {
+        let program = match &mut outcome {
+            FileOutcome::Parsed(program) => program,
+            FileOutcome::Failed(stack) => &mut stack.partial,
+            FileOutcome::Skipped(_) => unreachable!(),
+        };
+
+        FileProfile {
+            bytes: source.len(),
+            tokens,
+            nodes: program
+                .iter_mut()
+                .map(|statement| count_nodes(statement))
+                .sum(),
+            duration: elapsed,
+        }
+    });
+
+    (outcome, profile)
+}
+
+fn skipped(reason: SkipReason) -> (FileOutcome, Option<FileProfile>) {
+    (FileOutcome::Skipped(reason), None)
+}
+
+fn count_nodes(node: &mut dyn Node) -> usize {
+    1 + node.children().into_iter().map(count_nodes).sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::is_generated;
+    use super::matches_pattern;
+    use super::scan;
+    use super::FileOutcome;
+    use super::ProfileReport;
+    use super::ScanError;
+    use super::ScanOptions;
+    use super::SkipReason;
+    use crate::parser::cancellation::CancellationToken;
+    use crate::parser::options::ParserOptions;
+    use crate::parser::options::PhpVersion;
+
+    #[test]
+    fn test_exclusion_patterns() {
+        assert!(matches_pattern("vendor/**", "vendor/foo/bar.php"));
+        assert!(matches_pattern(
+            "**/*.blade.php",
+            "resources/views/app.blade.php"
+        ));
+        assert!(matches_pattern("**/fixtures/*", "fixtures/min.php"));
+        assert!(matches_pattern("src/?oo.php", "src/foo.php"));
+        assert!(!matches_pattern("vendor/*", "vendor/foo/bar.php"));
+        assert!(!matches_pattern("*.php", "src/foo.php"));
+    }
+
+    #[test]
+    fn test_generated_markers() {
+        assert!(is_generated(b"<?php\n/**\n * @generated by protoc\n */"));
+        assert!(is_generated(b"<?php // @Auto-Generated"));
+        assert!(!is_generated(b"<?php echo 'hello';"));
+
+        let mut late = b"<?php ".to_vec();
+        late.extend(vec![b' '; 2048]);
+        late.extend(b"// @generated");
+        assert!(!is_generated(&late));
+    }
+
+    #[test]
+    fn test_scan_skips_files() {
+        let root = std::env::temp_dir().join(format!("php-parser-scan-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("vendor/acme")).unwrap();
+        std::fs::write(root.join("src/a.php"), "<?php echo 1;").unwrap();
+        std::fs::write(root.join("src/b.php"), "<?php echo").unwrap();
+        std::fs::write(root.join("src/c.php"), "<?php // @generated\necho 1;").unwrap();
+        std::fs::write(root.join("src/d.php"), format!("<?php {}", "1;".repeat(64))).unwrap();
+        std::fs::write(root.join("src/notes.txt"), "<?php").unwrap();
+        std::fs::write(root.join("vendor/acme/e.php"), "<?php").unwrap();
+
+        let options = ScanOptions {
+            exclude: vec!["vendor/**".to_string()],
+            max_file_size: Some(64),
+            ..ScanOptions::default()
+        };
+        let results = scan(&root, &options).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let paths = results
+            .iter()
+            .map(|result| result.path.to_string_lossy().replace('\\', "/"))
+            .collect::<Vec<String>>();
+        assert_eq!(
+            paths,
+            vec!["src/a.php", "src/b.php", "src/c.php", "src/d.php", "vendor"]
+        );
+
+        assert!(matches!(results[0].outcome, FileOutcome::Parsed(_)));
+        assert!(matches!(results[1].outcome, FileOutcome::Failed(_)));
+        assert!(matches!(
+            results[2].outcome,
+            FileOutcome::Skipped(SkipReason::Generated)
+        ));
+        assert!(matches!(
+            results[3].outcome,
+            FileOutcome::Skipped(SkipReason::TooLarge { limit: 64, .. })
+        ));
+        assert!(matches!(
+            &results[4].outcome,
+            FileOutcome::Skipped(SkipReason::Excluded { pattern }) if pattern == "vendor/**"
+        ));
+    }
+
+    #[test]
+    fn test_scan_skips_slow_files() {
+        let root = std::env::temp_dir().join(format!("php-parser-slow-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("a.php"), "<?php echo 1;").unwrap();
+
+        let options = ScanOptions {
+            max_parse_time: Some(Duration::ZERO),
+            ..ScanOptions::default()
+        };
+        let results = scan(&root, &options).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        // The deadline is reached before the first statement is parsed.
+        assert!(matches!(
+            results[0].outcome,
+            FileOutcome::Skipped(SkipReason::TooSlow {
+                limit: Duration::ZERO,
+                ..
+            })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_skips_symlinked_directories() {
+        let root = std::env::temp_dir().join(format!("php-parser-symlink-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("vendor")).unwrap();
+        std::fs::create_dir_all(root.join("locked")).unwrap();
+        std::fs::write(root.join("a.php"), "<?php echo 1;").unwrap();
+        std::fs::write(root.join("locked/b.php"), "<?php echo 1;").unwrap();
+        std::os::unix::fs::symlink("../", root.join("vendor/self")).unwrap();
+        std::os::unix::fs::symlink("a.php", root.join("c.php")).unwrap();
+
+        let locked = root.join("locked");
+        let mut permissions = std::fs::metadata(&locked).unwrap().permissions();
+        std::os::unix::fs::PermissionsExt::set_mode(&mut permissions, 0o000);
+        std::fs::set_permissions(&locked, permissions.clone()).unwrap();
+        let readable = std::fs::read_dir(&locked).is_ok();
+
+        let results = scan(&root, &ScanOptions::default()).unwrap();
+
+        std::os::unix::fs::PermissionsExt::set_mode(&mut permissions, 0o755);
+        std::fs::set_permissions(&locked, permissions).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let paths = results
+            .iter()
+            .map(|result| result.path.to_string_lossy().replace('\\', "/"))
+            .collect::<Vec<String>>();
+
+        // Permissions are not enforced when running as root.
+        if readable {
+            assert_eq!(paths, vec!["a.php", "c.php", "locked/b.php"]);
+        } else {
+            assert_eq!(paths, vec!["a.php", "c.php", "locked"]);
+            assert!(matches!(
+                results[2].outcome,
+                FileOutcome::Skipped(SkipReason::Unreadable { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_scan_options() {
+        let root = std::env::temp_dir().join(format!("php-parser-include-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("tests")).unwrap();
+        std::fs::write(root.join("src/a.php"), "<?php $a = [1, 2];").unwrap();
+        std::fs::write(root.join("src/b.php"), "<?php $b = array(1) |> f(...);").unwrap();
+        std::fs::write(root.join("tests/c.php"), "<?php echo 1;").unwrap();
+
+        let options = ScanOptions {
+            include: vec!["src/**".to_string()],
+            parser: Some(ParserOptions::new().with_php_version(PhpVersion::Php84)),
+            ..ScanOptions::default()
+        };
+        let results = scan(&root, &options).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[0].outcome, FileOutcome::Parsed(_)));
+        assert!(matches!(
+            &results[1].outcome,
+            FileOutcome::Failed(stack) if stack.errors[0].id == "E081"
+        ));
+    }
+
+    #[test]
+    fn test_profile_report() {
+        let root = std::env::temp_dir().join(format!("php-parser-profile-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("a.php"), "<?php echo 1;").unwrap();
+        std::fs::write(root.join("b.php"), "<?php // @generated").unwrap();
+
+        let options = ScanOptions {
+            profile: true,
+            ..ScanOptions::default()
+        };
+        let results = scan(&root, &options).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let profile = results[0].profile.unwrap();
+        assert_eq!(profile.bytes, 13);
+        assert_eq!(profile.tokens, 5);
+        assert!(profile.nodes >= 3);
+        assert!(results[1].profile.is_none());
+
+        let report = ProfileReport::new(&results);
+        assert_eq!(report.files.len(), 1);
+        assert_eq!(report.slowest(5).len(), 1);
+        assert_eq!(report.total().tokens, 5);
+        assert!(report.to_string().ends_with("total (1 files)\n"));
+    }
+
+    #[test]
+    fn test_cancelled_scan() {
+        let root = std::env::temp_dir().join(format!("php-parser-cancel-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("a.php"), "<?php echo 1;").unwrap();
+        std::fs::write(root.join("b.php"), "<?php $b = ?;").unwrap();
+        std::fs::write(root.join("c.php"), "<?php echo 3;").unwrap();
+
+        let cancellation = CancellationToken::new();
+        let options = ScanOptions {
+            cancellation: Some(cancellation.clone()),
+            ..ScanOptions::default()
+        };
+        assert_eq!(scan(&root, &options).unwrap().len(), 3);
+
+        // Cancel the scan while parsing the second file.
+        let hook = cancellation.clone();
+        let options = ScanOptions {
+            parser: Some(ParserOptions::new().with_telemetry(move |_| hook.cancel())),
+            ..options
+        };
+        let error = scan(&root, &options).unwrap_err();
+        let results = match error {
+            ScanError::Cancelled { results } => results,
+            error => panic!("unexpected error: {error}"),
+        };
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[1].outcome, FileOutcome::Failed(_)));
+
+        let error = scan(&root, &options).unwrap_err();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert!(matches!(error, ScanError::Cancelled { results } if results.is_empty()));
+    }
+}
@@ -0,0 +1,262 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use futures_util::stream;
+use futures_util::Stream;
+
+use super::excluded;
+use super::has_extension;
+use super::included;
+use super::is_cancelled;
+use super::is_parse_cancelled;
+use super::parse_source;
+use super::parser;
+use super::pruned;
+use super::skipped;
+use super::too_large;
+use super::unreadable;
+use super::FileOutcome;
+use super::FileProfile;
+use super::FileResult;
+use super::ScanError;
+use super::ScanOptions;
+use crate::parser::reusable::Parser;
+
+struct Walk {
+    root: PathBuf,
+    options: ScanOptions,
+    // Paths left to visit, the next one last, and whether they are directories.
+    pending: Vec<(PathBuf, bool)>,
+    parser: Parser,
+    done: bool,
+}
+
+/// Parse every matching file within the given directory, recursively, in path order,
+/// yielding each result as soon as the file is parsed.
+///
+/// File IO is performed using tokio's file system API, and files are parsed one at a time
+/// on tokio's blocking thread pool, so a large file does not block the executor, and callers
+/// don't need to manage a thread pool or a channel. Directories are walked as by
+/// [`super::scan`].
+///
+/// The stream ends after yielding an error, e.g. a [`ScanError::Cancelled`] error when the
+/// scan is cancelled, holding the result of the file whose parse was cancelled, if any.
+pub fn parse_dir_stream(
+    root: impl AsRef<Path>,
+    options: ScanOptions,
+) -> impl Stream<Item = Result<FileResult, ScanError>> {
+    let root = root.as_ref().to_path_buf();
+    let walk = Walk {
+        pending: vec![(root.clone(), true)],
+        root,
+        parser: parser(&options),
+        options,
+        done: false,
+    };
+
+    stream::unfold(walk, |mut walk| async move {
+        if walk.done {
+            return None;
+        }
+
+        match next(&mut walk).await {
+            Ok(Some(result)) => Some((Ok(result), walk)),
+            Ok(None) => None,
+            Err(error) => {
+                walk.done = true;
+
+                Some((Err(error), walk))
+            }
+        }
+    })
+}
+
+async fn next(walk: &mut Walk) -> Result<Option<FileResult>, ScanError> {
+    while let Some((path, directory)) = walk.pending.pop() {
+        if is_cancelled(&walk.options) {
+            return Err(ScanError::Cancelled { results: vec![] });
+        }
+
+        let relative = path.strip_prefix(&walk.root).unwrap_or(&path).to_path_buf();
+
+        if directory {
+            let reason = match pruned(&relative, &walk.options) {
+                Some(reason) if path != walk.root => reason,
+                _ => match entries(&path).await {
+                    Ok(entries) => {
+                        walk.pending.extend(entries.into_iter().rev());
+
+                        continue;
+                    }
+                    Err(error) if path == walk.root => return Err(ScanError::Io(error)),
+                    Err(error) => unreadable(error),
+                },
+            };
+
+            return Ok(Some(FileResult {
+                path: relative,
+                outcome: FileOutcome::Skipped(reason),
+                profile: None,
+            }));
+        }
+
+        if !has_extension(&path, &walk.options) || !included(&relative, &walk.options) {
+            continue;
+        }
+
+        let (outcome, profile) = parse_file(walk, &path, &relative).await?;
+        let cancelled = is_parse_cancelled(&outcome, &walk.options);
+        let result = FileResult {
+            path: relative,
+            outcome,
+            profile,
+        };
+
+        if cancelled {
+            return Err(ScanError::Cancelled {
+                results: vec![result],
+            });
+        }
+
+        return Ok(Some(result));
+    }
+
+    Ok(None)
+}
+
+/// The files and directories within the given directory, in path order, leaving out
+/// symbolic links to directories.
+async fn entries(directory: &Path) -> std::io::Result<Vec<(PathBuf, bool)>> {
+    let mut entries = Vec::new();
+    let mut reader = tokio::fs::read_dir(directory).await?;
+    while let Some(entry) = reader.next_entry().await? {
+        let path = entry.path();
+        let file_type = entry.file_type().await?;
+
+        if file_type.is_dir() {
+            entries.push((path, true));
+        } else if file_type.is_file()
+            || (file_type.is_symlink()
+                && tokio::fs::metadata(&path)
+                    .await
+                    .is_ok_and(|metadata| metadata.is_file()))
+        {
+            entries.push((path, false));
+        }
+    }
+
+    entries.sort();
+
+    Ok(entries)
+}
+
+async fn parse_file(
+    walk: &mut Walk,
+    path: &Path,
+    relative: &Path,
+) -> std::io::Result<(FileOutcome, Option<FileProfile>)> {
+    if let Some(reason) = excluded(relative, &walk.options) {
+        return Ok(skipped(reason));
+    }
+
+    if let Some(limit) = walk.options.max_file_size {
+        match tokio::fs::metadata(path).await {
+            Ok(metadata) => {
+                if let Some(reason) = too_large(metadata.len(), limit) {
+                    return Ok(skipped(reason));
+                }
+            }
+            Err(error) => return Ok(skipped(unreadable(error))),
+        }
+    }
+
+    let source = match tokio::fs::read(path).await {
+        Ok(source) => source,
+        Err(error) => return Ok(skipped(unreadable(error))),
+    };
+
+    let mut parser = std::mem::take(&mut walk.parser);
+    let options = walk.options.clone();
+    let (parser, result) = tokio::task::spawn_blocking(move || {
+        let result = parse_source(&mut parser, &source, &options);
+
+        (parser, result)
+    })
+    .await
+    .map_err(|error| match error.try_into_panic() {
+        Ok(panic) => std::panic::resume_unwind(panic),
+        Err(error) => std::io::Error::other(error),
+    })?;
+    walk.parser = parser;
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+
+    use super::parse_dir_stream;
+    use crate::parser::cancellation::CancellationToken;
+    use crate::project::FileOutcome;
+    use crate::project::ScanError;
+    use crate::project::ScanOptions;
+    use crate::project::SkipReason;
+
+    #[tokio::test]
+    async fn test_parse_dir_stream() {
+        let root = std::env::temp_dir().join(format!("php-parser-stream-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src/nested")).unwrap();
+        std::fs::write(root.join("a.php"), "<?php echo 1;").unwrap();
+        std::fs::write(root.join("src/b.php"), "<?php // @generated").unwrap();
+        std::fs::write(root.join("src/nested/c.php"), "<?php echo").unwrap();
+        std::fs::write(root.join("src/d.txt"), "<?php").unwrap();
+        std::fs::create_dir_all(root.join("vendor")).unwrap();
+        std::fs::write(root.join("vendor/e.php"), "<?php").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink("..", root.join("src/self")).unwrap();
+
+        let cancellation = CancellationToken::new();
+        let options = ScanOptions {
+            exclude: vec!["vendor/**".to_string()],
+            cancellation: Some(cancellation.clone()),
+            ..ScanOptions::default()
+        };
+
+        let results = parse_dir_stream(&root, options.clone())
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let paths = results
+            .iter()
+            .map(|result| result.path.to_string_lossy().replace('\\', "/"))
+            .collect::<Vec<String>>();
+        assert_eq!(
+            paths,
+            vec!["a.php", "src/b.php", "src/nested/c.php", "vendor"]
+        );
+        assert!(matches!(results[0].outcome, FileOutcome::Parsed(_)));
+        assert!(matches!(
+            results[1].outcome,
+            FileOutcome::Skipped(SkipReason::Generated)
+        ));
+        assert!(matches!(results[2].outcome, FileOutcome::Failed(_)));
+        assert!(matches!(
+            &results[3].outcome,
+            FileOutcome::Skipped(SkipReason::Excluded { pattern }) if pattern == "vendor/**"
+        ));
+
+        let mut stream = Box::pin(parse_dir_stream(&root, options));
+        assert!(stream.next().await.unwrap().is_ok());
+        cancellation.cancel();
+        let error = stream.next().await.unwrap().unwrap_err();
+        assert!(matches!(error, ScanError::Cancelled { results } if results.is_empty()));
+        assert!(stream.next().await.is_none());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
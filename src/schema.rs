@@ -0,0 +1,521 @@
+//! The shape of the serialized AST, for tools consuming it in other languages.
+//!
+//! [`json_schema`] describes the json produced by [`to_json`], a [`Program`] along with the
+//! [`SCHEMA_VERSION`] of its shape, and [`typescript`] renders the same description as
+//! TypeScript type declarations.
+//!
+//! With the `binary` feature, [`to_bytes`] serializes the same shape in a compact binary
+//! format, e.g. to cache parse results between runs.
+
+use std::fmt::Display;
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::InstanceType;
+use schemars::schema::RootSchema;
+use schemars::schema::Schema;
+use schemars::schema::SchemaObject;
+use schemars::schema::SingleOrVec;
+use schemars::schema_for;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+
+/// The version of the shape of the serialized AST.
+///
+/// It is bumped whenever a change to the AST could break a consumer of the serialized AST,
+/// such as renaming or removing a node or a field, or adding a required field.
+pub const SCHEMA_VERSION: u32 = 3;
+
+/// A serialized program, along with the version of its shape.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SerializedProgram {
+    /// The version of the shape of the program.
+    #[schemars(schema_with = "schema_version")]
+    pub schema_version: u32,
+    pub program: Program,
+}
+
+impl SerializedProgram {
+    pub fn new(program: Program) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            program,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SchemaError {
+    /// The json was serialized with another version of the AST, or without a version.
+    Version { found: Option<u64> },
+    /// The json does not describe a serialized program.
+    Json(serde_json::Error),
+    /// The program could not be encoded by [`to_bytes`].
+    #[cfg(feature = "binary")]
+    Encode(rmp_serde::encode::Error),
+    /// The bytes do not describe a serialized program.
+    #[cfg(feature = "binary")]
+    Decode(rmp_serde::decode::Error),
+}
+
+impl Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaError::Version {
+                found: Some(version),
+            } => write!(
+                f,
+                "unsupported schema version {}, expected {}",
+                version, SCHEMA_VERSION
+            ),
+            SchemaError::Version { found: None } => {
+                write!(f, "missing schema version, expected {}", SCHEMA_VERSION)
+            }
+            SchemaError::Json(error) => write!(f, "{}", error),
+            #[cfg(feature = "binary")]
+            SchemaError::Encode(error) => write!(f, "{}", error),
+            #[cfg(feature = "binary")]
+            SchemaError::Decode(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+// A borrowed [`SerializedProgram`], to serialize a program without cloning it.
+#[derive(Serialize)]
+struct Versioned<'a> {
+    schema_version: u32,
+    program: &'a [Statement],
+}
+
+/// Serialize a program, along with the [`SCHEMA_VERSION`] of its shape.
+///
+/// ```
+/// let program = php_parser_rs::parse("<?php echo 1;").unwrap();
+/// let json = php_parser_rs::schema::to_json(&program).unwrap();
+///
+/// assert!(json.starts_with(r#"{"schema_version":3,"program":[{"type":"full_opening_tag""#));
+/// assert_eq!(php_parser_rs::schema::from_json(&json).unwrap(), program);
+/// ```
+pub fn to_json(program: &[Statement]) -> serde_json::Result<String> {
+    serde_json::to_string(&Versioned {
+        schema_version: SCHEMA_VERSION,
+        program,
+    })
+}
+
+/// Like [`to_json`], with indentation.
+pub fn to_json_pretty(program: &[Statement]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&Versioned {
+        schema_version: SCHEMA_VERSION,
+        program,
+    })
+}
+
+/// Deserialize a program serialized by [`to_json`], refusing programs serialized with
+/// another [`SCHEMA_VERSION`] rather than failing on the first node which changed.
+pub fn from_json(json: &str) -> Result<Program, SchemaError> {
+    let value: serde_json::Value = serde_json::from_str(json).map_err(SchemaError::Json)?;
+
+    let version = value
+        .get("schema_version")
+        .and_then(|version| version.as_u64());
+    if version != Some(u64::from(SCHEMA_VERSION)) {
+        return Err(SchemaError::Version { found: version });
+    }
+
+    serde_json::from_value::<SerializedProgram>(value)
+        .map(|serialized| serialized.program)
+        .map_err(SchemaError::Json)
+}
+
+/// The bytes starting the output of [`to_bytes`], followed by the [`SCHEMA_VERSION`].
+#[cfg(feature = "binary")]
+const MAGIC: &[u8; 4] = b"PHPA";
+
+/// Serialize a program in a compact binary format, along with the [`SCHEMA_VERSION`] of its
+/// shape.
+///
+/// The format is MessagePack, holding the same shape as the json of [`to_json`]. Formats
+/// without field and variant names, such as bincode, can't represent the tagged enums of
+/// the AST.
+///
+/// ```
+/// let program = php_parser_rs::parse("<?php echo 1;").unwrap();
+/// let bytes = php_parser_rs::schema::to_bytes(&program).unwrap();
+///
+/// assert_eq!(php_parser_rs::schema::from_bytes(&bytes).unwrap(), program);
+/// ```
+#[cfg(feature = "binary")]
+pub fn to_bytes(program: &[Statement]) -> Result<Vec<u8>, SchemaError> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(&SCHEMA_VERSION.to_le_bytes());
+
+    rmp_serde::encode::write_named(&mut bytes, program).map_err(SchemaError::Encode)?;
+
+    Ok(bytes)
+}
+
+/// Deserialize a program serialized by [`to_bytes`], refusing programs serialized with
+/// another [`SCHEMA_VERSION`].
+#[cfg(feature = "binary")]
+pub fn from_bytes(bytes: &[u8]) -> Result<Program, SchemaError> {
+    let version = bytes
+        .strip_prefix(MAGIC)
+        .and_then(|bytes| bytes.get(..4))
+        .and_then(|version| version.try_into().ok())
+        .map(u32::from_le_bytes);
+    if version != Some(SCHEMA_VERSION) {
+        return Err(SchemaError::Version {
+            found: version.map(u64::from),
+        });
+    }
+
+    rmp_serde::from_slice(&bytes[MAGIC.len() + 4..]).map_err(SchemaError::Decode)
+}
+
+/// The JSON Schema of a [`SerializedProgram`], i.e. of the json produced by [`to_json`].
+pub fn json_schema() -> RootSchema {
+    schema_for!(SerializedProgram)
+}
+
+/// TypeScript declarations of a [`SerializedProgram`], with one exported type per
+/// definition of the [`json_schema`], `SerializedProgram` as the root type, and `Program`
+/// as the type of its program.
+pub fn typescript() -> String {
+    let schema = json_schema();
+
+    let mut output = String::from("// This file is generated, do not edit.\n\n");
+    output.push_str(&declaration("SerializedProgram", &schema.schema));
+    output.push('\n');
+    output.push_str(&declaration("Program", &schema_for!(Program).schema));
+
+    for (name, definition) in schema.definitions.iter() {
+        output.push('\n');
+        output.push_str(&match definition {
+            Schema::Object(object) => declaration(name, object),
+            Schema::Bool(_) => format!("export type {} = {};\n", name, render(definition, 0)),
+        });
+    }
+
+    output
+}
+
+// The schema of the version of a serialized program, only accepting the current version.
+fn schema_version(_: &mut SchemaGenerator) -> Schema {
+    Schema::Object(SchemaObject {
+        instance_type: Some(SingleOrVec::Single(Box::new(InstanceType::Integer))),
+        const_value: Some(serde_json::json!(SCHEMA_VERSION)),
+        ..SchemaObject::default()
+    })
+}
+
+fn declaration(name: &str, object: &SchemaObject) -> String {
+    format!(
+        "{}export type {} = {};\n",
+        comment(object, 0),
+        name,
+        render_object(object, 0)
+    )
+}
+
+fn comment(object: &SchemaObject, depth: usize) -> String {
+    match object
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.description.as_ref())
+    {
+        Some(description) => {
+            let indentation = indent(depth);
+            let mut comment = format!("{}/**\n", indentation);
+            for line in description.lines() {
+                comment.push_str(&format!("{} * {}\n", indentation, line).replace(" \n", "\n"));
+            }
+            comment.push_str(&format!("{} */\n", indentation));
+
+            comment
+        }
+        None => String::new(),
+    }
+}
+
+fn render(schema: &Schema, depth: usize) -> String {
+    match schema {
+        Schema::Bool(true) => "unknown".to_string(),
+        Schema::Bool(false) => "never".to_string(),
+        Schema::Object(object) => render_object(object, depth),
+    }
+}
+
+fn render_object(object: &SchemaObject, depth: usize) -> String {
+    if let Some(reference) = &object.reference {
+        return reference
+            .rsplit('/')
+            .next()
+            .unwrap_or(reference)
+            .to_string();
+    }
+
+    if let Some(value) = &object.const_value {
+        return value.to_string();
+    }
+
+    if let Some(values) = &object.enum_values {
+        return union(values.iter().map(|value| value.to_string()).collect());
+    }
+
+    if let Some(subschemas) = &object.subschemas {
+        if let Some(schemas) = subschemas.any_of.as_ref().or(subschemas.one_of.as_ref()) {
+            return union(schemas.iter().map(|schema| render(schema, depth)).collect());
+        }
+
+        if let Some(schemas) = &subschemas.all_of {
+            return schemas
+                .iter()
+                .map(|schema| parenthesize(render(schema, depth)))
+                .collect::<Vec<String>>()
+                .join(" & ");
+        }
+    }
+
+    let types = match &object.instance_type {
+        Some(SingleOrVec::Single(instance_type)) => vec![**instance_type],
+        Some(SingleOrVec::Vec(instance_types)) => instance_types.clone(),
+        None if object.object.is_some() => vec![InstanceType::Object],
+        None => return "unknown".to_string(),
+    };
+
+    union(
+        types
+            .iter()
+            .map(|instance_type| match instance_type {
+                InstanceType::Null => "null".to_string(),
+                InstanceType::Boolean => "boolean".to_string(),
+                InstanceType::Integer | InstanceType::Number => "number".to_string(),
+                InstanceType::String => "string".to_string(),
+                InstanceType::Array => render_array(object, depth),
+                InstanceType::Object => render_properties(object, depth),
+            })
+            .collect(),
+    )
+}
+
+fn render_array(object: &SchemaObject, depth: usize) -> String {
+    match object.array.as_ref().and_then(|array| array.items.as_ref()) {
+        Some(SingleOrVec::Single(item)) => format!("{}[]", parenthesize(render(item, depth))),
+        Some(SingleOrVec::Vec(items)) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(|item| render(item, depth))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        None => "unknown[]".to_string(),
+    }
+}
+
+fn render_properties(object: &SchemaObject, depth: usize) -> String {
+    let validation = match &object.object {
+        Some(validation) => validation,
+        None => return "Record<string, unknown>".to_string(),
+    };
+
+    let mut members = Vec::new();
+    for (name, property) in validation.properties.iter() {
+        let documentation = match property {
+            Schema::Object(object) => comment(object, depth + 1),
+            Schema::Bool(_) => String::new(),
+        };
+        let optional = if validation.required.contains(name) {
+            ""
+        } else {
+            "?"
+        };
+
+        members.push(format!(
+            "{}{}{}{}: {};\n",
+            documentation,
+            indent(depth + 1),
+            property_name(name),
+            optional,
+            render(property, depth + 1)
+        ));
+    }
+
+    if let Some(additional) = &validation.additional_properties {
+        if !matches!(additional.as_ref(), Schema::Bool(false)) {
+            members.push(format!(
+                "{}[key: string]: {};\n",
+                indent(depth + 1),
+                render(additional, depth + 1)
+            ));
+        }
+    }
+
+    if members.is_empty() {
+        return "{}".to_string();
+    }
+
+    format!("{{\n{}{}}}", members.concat(), indent(depth))
+}
+
+fn union(mut types: Vec<String>) -> String {
+    types.dedup();
+
+    match types.len() {
+        0 => "never".to_string(),
+        1 => types.remove(0),
+        _ => types.join(" | "),
+    }
+}
+
+// Wrap unions and intersections, so they can be used as an operand.
+fn parenthesize(rendered: String) -> String {
+    let mut depth = 0;
+    for byte in rendered.bytes() {
+        match byte {
+            b'{' | b'[' | b'(' => depth += 1,
+            b'}' | b']' | b')' => depth -= 1,
+            b'|' | b'&' if depth == 0 => return format!("({})", rendered),
+            _ => {}
+        }
+    }
+
+    rendered
+}
+
+fn property_name(name: &str) -> String {
+    let identifier = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+    if identifier {
+        name.to_string()
+    } else {
+        format!("{:?}", name)
+    }
+}
+
+fn indent(depth: usize) -> String {
+    "  ".repeat(depth)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_json;
+    use super::json_schema;
+    use super::to_json;
+    use super::typescript;
+    use super::SchemaError;
+    use super::SCHEMA_VERSION;
+
+    #[test]
+    fn test_json_schema_definitions() {
+        let schema = json_schema();
+
+        for name in ["Statement", "Expression", "Span", "ByteString"] {
+            assert!(schema.definitions.contains_key(name), "missing {}", name);
+        }
+    }
+
+    #[test]
+    fn test_typescript_declarations() {
+        let declarations = typescript();
+
+        assert!(declarations.contains("export type Program = Statement[];\n"));
+        assert!(
+            declarations.contains("export type OpenTagKind = \"full\" | \"short\" | \"echo\";\n")
+        );
+        assert!(declarations.contains("export type ByteString = string | number[];\n"));
+
+        // every referenced type is declared.
+        let declared = declarations
+            .lines()
+            .filter_map(|line| line.strip_prefix("export type "))
+            .filter_map(|line| line.split(' ').next())
+            .collect::<Vec<&str>>();
+        for name in json_schema().definitions.keys() {
+            assert!(declared.contains(&name.as_str()), "missing {}", name);
+        }
+    }
+
+    #[test]
+    fn test_generated_schemas_are_up_to_date() {
+        // When this fails, regenerate the schemas with `cargo run --bin php-parser-schema`,
+        // and bump `SCHEMA_VERSION` if the change could break consumers of the serialized AST.
+        let schema = serde_json::to_string_pretty(&json_schema()).unwrap() + "\n";
+
+        assert!(
+            schema == include_str!("../schema.json"),
+            "schema.json is outdated"
+        );
+        assert!(
+            typescript() == include_str!("../schema.d.ts"),
+            "schema.d.ts is outdated"
+        );
+    }
+
+    #[test]
+    fn test_versioned_json() {
+        let program = crate::parse("<?php function foo() { return 1; }").unwrap();
+
+        let json = to_json(&program).unwrap();
+        assert_eq!(from_json(&json).unwrap(), program);
+
+        let outdated = json.replace(
+            &format!("\"schema_version\":{}", SCHEMA_VERSION),
+            "\"schema_version\":0",
+        );
+        assert!(matches!(
+            from_json(&outdated),
+            Err(SchemaError::Version { found: Some(0) })
+        ));
+
+        let unversioned = serde_json::to_string(&program).unwrap();
+        assert!(matches!(
+            from_json(&unversioned),
+            Err(SchemaError::Version { found: None })
+        ));
+
+        assert!(matches!(
+            from_json(&json.replace(r#""type":"function""#, r#""type":"fun""#)),
+            Err(SchemaError::Json(_))
+        ));
+    }
+    #[cfg(feature = "binary")]
+    #[test]
+    fn test_versioned_bytes() {
+        use super::from_bytes;
+        use super::to_bytes;
+
+        let program = crate::parse("<?php function foo() { return \"\\xff\" . 1; }").unwrap();
+
+        let bytes = to_bytes(&program).unwrap();
+        assert_eq!(from_bytes(&bytes).unwrap(), program);
+        assert!(bytes.len() < to_json(&program).unwrap().len());
+
+        let mut outdated = bytes.clone();
+        outdated[4..8].copy_from_slice(&0u32.to_le_bytes());
+        assert!(matches!(
+            from_bytes(&outdated),
+            Err(SchemaError::Version { found: Some(0) })
+        ));
+
+        assert!(matches!(
+            from_bytes(&bytes[8..]),
+            Err(SchemaError::Version { found: None })
+        ));
+        assert!(matches!(
+            from_bytes(&bytes[..bytes.len() - 1]),
+            Err(SchemaError::Decode(_))
+        ));
+    }
+}
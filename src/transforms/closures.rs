@@ -0,0 +1,425 @@
+//! Conversions between closures returning a single expression and arrow functions.
+//!
+//! A closure captures the variables listed in its `use` clause, while an arrow function
+//! captures by value every variable of the parent scope it uses, see
+//! [`crate::analysis::captures`]. The conversions are only done when the captured variables
+//! are the same:
+//!
+//! - The body of the closure is a single `return` statement.
+//! - The closure doesn't capture variables by reference, which an arrow function can't do.
+//! - Every variable of the parent scope used by the closure is listed in its `use` clause,
+//!   otherwise the variable is undefined in the closure but captured by the arrow function.
+//! - Variables are not accessed dynamically, e.g. with `$$name` or `compact()`.
+//!
+//! An arrow function is converted into a closure using the variables it captures. Unlike an
+//! arrow function, the closure warns when one of them is undefined in the parent scope.
+//!
+//! ```
+//! use php_parser_rs::printer::PrinterOptions;
+//! use php_parser_rs::transforms::apply;
+//! use php_parser_rs::transforms::closures::closures_to_arrow_functions;
+//!
+//! let source = "<?php\n\n$double = function ($a) use ($b) {\n    return $a * $b;\n};\n";
+//! let program = php_parser_rs::parse(source).unwrap();
+//!
+//! let edits = closures_to_arrow_functions(source, &program, &PrinterOptions::new());
+//!
+//! assert_eq!(
+//!     apply(source, &edits),
+//!     b"<?php\n\n$double = fn ($a) => $a * $b;\n",
+//! );
+//! ```
+
+use std::fmt::Display;
+
+use crate::analysis::captures;
+use crate::analysis::captures::CaptureKind;
+use crate::downcast::downcast_mut;
+use crate::lexer::Lexer;
+use crate::node::Node;
+use crate::parser::ast::comments::CommentGroup;
+use crate::parser::ast::functions::ArrowFunction;
+use crate::parser::ast::functions::Closure;
+use crate::parser::ast::functions::ClosureUse;
+use crate::parser::ast::functions::ClosureUseVariable;
+use crate::parser::ast::functions::FunctionBody;
+use crate::parser::ast::utils::CommaSeparated;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::Ending;
+use crate::parser::ast::Expression;
+use crate::parser::ast::ReturnStatement;
+use crate::parser::ast::Statement;
+use crate::printer::PrinterOptions;
+use crate::transforms;
+use crate::transforms::TextEdit;
+use crate::traverser::Visitor;
+
+/// The reason a closure or an arrow function can't be converted.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum NotConvertible {
+    // The body of the closure isn't a single `return` statement with a value.
+    NotSingleExpression,
+    // `use (&$a)`
+    CaptureByReference(String),
+    // The variable is used by the closure without being listed in its `use` clause.
+    UncapturedVariable(String),
+    // `$$name`, `compact()`, `extract()` or `get_defined_vars()`
+    DynamicVariables,
+}
+
+impl Display for NotConvertible {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "not convertible because ")?;
+
+        match self {
+            Self::NotSingleExpression => {
+                write!(f, "the body is not a single `return` statement")
+            }
+            Self::CaptureByReference(variable) => {
+                write!(f, "`{}` is captured by reference", variable)
+            }
+            Self::UncapturedVariable(variable) => {
+                write!(f, "`{}` is used without being captured", variable)
+            }
+            Self::DynamicVariables => write!(f, "variables are accessed dynamically"),
+        }
+    }
+}
+
+/// Convert a closure returning a single expression into an arrow function.
+pub fn closure_to_arrow_function(closure: &Closure) -> Result<ArrowFunction, NotConvertible> {
+    let value = match &closure.body.statements[..] {
+        [Statement::Return(statement)] => statement.value.as_ref(),
+        _ => None,
+    }
+    .ok_or(NotConvertible::NotSingleExpression)?;
+
+    let uses = closure
+        .uses
+        .as_ref()
+        .map(|uses| &uses.variables.inner[..])
+        .unwrap_or_default();
+    if let Some(variable) = uses.iter().find(|variable| variable.ampersand.is_some()) {
+        return Err(NotConvertible::CaptureByReference(
+            variable.variable.name.to_string(),
+        ));
+    }
+
+    let function = ArrowFunction {
+        comments: closure.comments.clone(),
+        r#static: closure.r#static,
+        ampersand: closure.ampersand,
+        r#fn: closure.function,
+        attributes: closure.attributes.clone(),
+        parameters: closure.parameters.clone(),
+        return_type: closure.return_type.clone(),
+        double_arrow: closure.body.left_brace,
+        body: Box::new(value.clone()),
+    };
+
+    let captures = captures::arrow_function(&mut function.clone());
+    if captures.dynamic {
+        return Err(NotConvertible::DynamicVariables);
+    }
+
+    for capture in captures.captures {
+        if capture.kind == CaptureKind::Implicit
+            && !uses
+                .iter()
+                .any(|variable| variable.variable.name == capture.variable)
+        {
+            return Err(NotConvertible::UncapturedVariable(
+                capture.variable.to_string(),
+            ));
+        }
+    }
+
+    Ok(function)
+}
+
+/// Convert an arrow function into a closure capturing the same variables.
+pub fn arrow_function_to_closure(function: &ArrowFunction) -> Result<Closure, NotConvertible> {
+    let captures = captures::arrow_function(&mut function.clone());
+    if captures.dynamic {
+        return Err(NotConvertible::DynamicVariables);
+    }
+
+    let span = function.double_arrow;
+    let variables = captures
+        .captures
+        .into_iter()
+        .filter(|capture| capture.kind == CaptureKind::Implicit)
+        .map(|capture| ClosureUseVariable {
+            comments: CommentGroup { comments: vec![] },
+            ampersand: None,
+            variable: SimpleVariable {
+                span,
+                name: capture.variable,
+            },
+        })
+        .collect::<Vec<ClosureUseVariable>>();
+
+    let uses = if variables.is_empty() {
+        None
+    } else {
+        Some(ClosureUse {
+            comments: CommentGroup { comments: vec![] },
+            r#use: span,
+            left_parenthesis: span,
+            variables: CommaSeparated {
+                commas: vec![span; variables.len() - 1],
+                inner: variables,
+            },
+            right_parenthesis: span,
+        })
+    };
+
+    Ok(Closure {
+        comments: function.comments.clone(),
+        attributes: function.attributes.clone(),
+        r#static: function.r#static,
+        function: function.r#fn,
+        ampersand: function.ampersand,
+        parameters: function.parameters.clone(),
+        uses,
+        return_type: function.return_type.clone(),
+        body: FunctionBody {
+            comments: CommentGroup { comments: vec![] },
+            left_brace: span,
+            statements: vec![Statement::Return(Box::new(ReturnStatement {
+                r#return: span,
+                value: Some(function.body.as_ref().clone()),
+                ending: Ending::Semicolon(span),
+            }))],
+            right_brace: function.body.end(),
+        },
+    })
+}
+
+/// The edits converting every convertible closure of the program into an arrow function.
+///
+/// `program` must be parsed from `source`. Only the converted functions are printed, with
+/// the given options and indented like the line they start on, and functions containing
+/// comments are skipped. The edits don't overlap: a closure nested within a converted one
+/// is left for another pass.
+pub fn closures_to_arrow_functions<B: ?Sized + AsRef<[u8]>>(
+    source: &B,
+    program: &[Statement],
+    options: &PrinterOptions,
+) -> Vec<TextEdit> {
+    edits(
+        source.as_ref(),
+        program,
+        options,
+        |expression| match expression {
+            Expression::Closure(closure) => closure_to_arrow_function(closure)
+                .ok()
+                .map(|function| Expression::ArrowFunction(Box::new(function))),
+            _ => None,
+        },
+    )
+}
+
+/// The edits converting every arrow function of the program into a closure, see
+/// [`closures_to_arrow_functions`].
+pub fn arrow_functions_to_closures<B: ?Sized + AsRef<[u8]>>(
+    source: &B,
+    program: &[Statement],
+    options: &PrinterOptions,
+) -> Vec<TextEdit> {
+    edits(
+        source.as_ref(),
+        program,
+        options,
+        |expression| match expression {
+            Expression::ArrowFunction(function) => arrow_function_to_closure(function)
+                .ok()
+                .map(|closure| Expression::Closure(Box::new(closure))),
+            _ => None,
+        },
+    )
+}
+
+fn edits(
+    source: &[u8],
+    program: &[Statement],
+    options: &PrinterOptions,
+    convert: fn(&Expression) -> Option<Expression>,
+) -> Vec<TextEdit> {
+    let tokens = match Lexer::new().tokenize(source) {
+        Ok(tokens) => tokens,
+        Err(_) => return vec![],
+    };
+
+    let mut finder = Finder {
+        convert,
+        replacements: vec![],
+    };
+    for statement in program.to_vec().iter_mut() {
+        let _ = finder.visit_node(statement);
+    }
+
+    let mut edits: Vec<TextEdit> = vec![];
+    for (original, modified) in finder.replacements {
+        let edit = match transforms::replace(source, &tokens, &original, &modified, options) {
+            Some(edit) => edit,
+            None => continue,
+        };
+
+        // functions are found before the functions nested within them.
+        if edits
+            .last()
+            .is_none_or(|last| last.range.end <= edit.range.start)
+        {
+            edits.push(edit);
+        }
+    }
+
+    edits
+}
+
+/// Find the functions to convert, with their conversion.
+struct Finder {
+    convert: fn(&Expression) -> Option<Expression>,
+    replacements: Vec<(Expression, Expression)>,
+}
+
+impl Visitor<()> for Finder {
+    fn visit(&mut self, node: &mut dyn Node) -> Result<(), ()> {
+        if let Some(expression) = downcast_mut::<Expression>(node) {
+            if let Some(modified) = (self.convert)(expression) {
+                self.replacements.push((expression.clone(), modified));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::arrow_function_to_closure;
+    use super::arrow_functions_to_closures;
+    use super::closure_to_arrow_function;
+    use super::closures_to_arrow_functions;
+    use super::NotConvertible;
+    use crate::parser::ast::Expression;
+    use crate::parser::ast::Statement;
+    use crate::printer::PrinterOptions;
+    use crate::transforms::apply;
+
+    fn expression(code: &str) -> Expression {
+        match crate::parse(&format!("<?php {};", code)).unwrap().remove(1) {
+            Statement::Expression(statement) => statement.expression,
+            statement => panic!("expected an expression, got {:?}", statement),
+        }
+    }
+
+    fn to_arrow_function(code: &str) -> Result<(), NotConvertible> {
+        match expression(code) {
+            Expression::Closure(closure) => closure_to_arrow_function(&closure).map(|_| ()),
+            expression => panic!("expected a closure, got {:?}", expression),
+        }
+    }
+
+    fn to_closure(code: &str) -> Result<(), NotConvertible> {
+        match expression(code) {
+            Expression::ArrowFunction(function) => arrow_function_to_closure(&function).map(|_| ()),
+            expression => panic!("expected an arrow function, got {:?}", expression),
+        }
+    }
+
+    fn rewrite(source: &str, to_arrow_functions: bool) -> String {
+        let program = crate::parse(source).unwrap();
+        let options = PrinterOptions::new();
+        let edits = if to_arrow_functions {
+            closures_to_arrow_functions(source, &program, &options)
+        } else {
+            arrow_functions_to_closures(source, &program, &options)
+        };
+
+        String::from_utf8(apply(source, &edits)).unwrap()
+    }
+
+    #[test]
+    fn test_closures_to_arrow_functions() {
+        assert_eq!(
+            rewrite(
+                "<?php\n\nclass A {\n    public function b() {\n        // keep me\n        return array_map(static function (int $x) use ($y): int {\n            return $x + $y;\n        }, $this->items);\n    }\n}\n",
+                true
+            ),
+            "<?php\n\nclass A {\n    public function b() {\n        // keep me\n        return array_map(static fn (int $x): int => $x + $y, $this->items);\n    }\n}\n"
+        );
+
+        // the nested closure is converted by another pass.
+        let source = "<?php $a = function () { return function () { return 1; }; };";
+        let once = rewrite(source, true);
+        assert_eq!(once, "<?php $a = fn () => function () {\n    return 1;\n};");
+        assert_eq!(rewrite(&once, true), "<?php $a = fn () => fn () => 1;");
+
+        // comments are not part of the AST.
+        for source in [
+            "<?php $a = function () { foo(); };",
+            "<?php $a = function () { // why\n return 1; };",
+        ] {
+            assert_eq!(rewrite(source, true), source);
+        }
+    }
+
+    #[test]
+    fn test_arrow_functions_to_closures() {
+        assert_eq!(
+            rewrite("<?php\n\n$total = fn ($x) => $x * $rate + $tax;\n", false),
+            "<?php\n\n$total = function ($x) use ($rate, $tax) {\n    return $x * $rate + $tax;\n};\n"
+        );
+
+        assert_eq!(
+            rewrite("<?php $a = fn () => $this->b;", false),
+            "<?php $a = function () {\n    return $this->b;\n};"
+        );
+    }
+
+    #[test]
+    fn test_not_convertible() {
+        assert_eq!(
+            to_arrow_function("function () use ($a) { return $a; }"),
+            Ok(())
+        );
+        assert_eq!(
+            to_arrow_function("function () use ($a, $b) { return $a; }"),
+            Ok(())
+        );
+
+        assert_eq!(
+            to_arrow_function("function () { $a = 1; return $a; }"),
+            Err(NotConvertible::NotSingleExpression)
+        );
+        assert_eq!(
+            to_arrow_function("function () { return; }"),
+            Err(NotConvertible::NotSingleExpression)
+        );
+        assert_eq!(
+            to_arrow_function("function () use (&$a) { return $a++; }"),
+            Err(NotConvertible::CaptureByReference("$a".to_string()))
+        );
+        assert_eq!(
+            to_arrow_function("function ($a) { return $a + $b; }"),
+            Err(NotConvertible::UncapturedVariable("$b".to_string()))
+        );
+        assert_eq!(
+            to_arrow_function("function () use ($a) { return compact('a'); }"),
+            Err(NotConvertible::DynamicVariables)
+        );
+
+        assert_eq!(to_closure("fn () => $a"), Ok(()));
+        assert_eq!(
+            to_closure("fn () => $$a"),
+            Err(NotConvertible::DynamicVariables)
+        );
+
+        assert_eq!(
+            NotConvertible::UncapturedVariable("$b".to_string()).to_string(),
+            "not convertible because `$b` is used without being captured"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! Transformations of the AST, to rewrite code with the printers of [`crate::printer`].
+//!
+//! A transformation either returns the transformed node, or the reason the node can't be
+//! transformed without changing the behaviour of the code.
+
+use std::ops::Range;
+
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+use crate::parser::ast::Expression;
+use crate::printer::print_expression_within;
+use crate::printer::PrinterOptions;
+
+pub mod closures;
+pub mod switch_match;
+
+/// The replacement of a byte range of the source, e.g. to be applied by an editor.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TextEdit {
+    pub range: Range<usize>,
+    pub replacement: String,
+}
+
+/// The edit replacing an expression of the source with a modified one, printed with the
+/// given options and indented like the line the original expression starts on.
+///
+/// Returns `None` when the original expression contains comments, which are not part of
+/// the AST and would be lost.
+pub(crate) fn replace(
+    source: &[u8],
+    tokens: &[Token],
+    original: &Expression,
+    modified: &Expression,
+    options: &PrinterOptions,
+) -> Option<TextEdit> {
+    let start = original.start().position;
+    let first = tokens.partition_point(|token| token.span.position < start);
+    let last = tokens.partition_point(|token| token.span.position < original.end().position);
+    let inner = tokens.get(first..=last)?;
+
+    if inner.iter().any(|token| {
+        matches!(
+            token.kind,
+            TokenKind::SingleLineComment
+                | TokenKind::HashMarkComment
+                | TokenKind::MultiLineComment
+                | TokenKind::DocumentComment
+        )
+    }) {
+        return None;
+    }
+
+    let line = source[..start]
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |index| index + 1);
+    let indentation = source[line..start]
+        .iter()
+        .take_while(|byte| **byte == b' ' || **byte == b'\t')
+        .count();
+    let margin = String::from_utf8_lossy(&source[line..line + indentation]);
+
+    let end = &inner[inner.len() - 1];
+    Some(TextEdit {
+        range: start..end.span.position + end.raw().len(),
+        replacement: print_expression_within(modified, options, &margin),
+    })
+}
+
+/// Apply non-overlapping edits to the source.
+pub fn apply<B: ?Sized + AsRef<[u8]>>(source: &B, edits: &[TextEdit]) -> Vec<u8> {
+    let source = source.as_ref();
+    let mut edits = edits.iter().collect::<Vec<_>>();
+    edits.sort_by_key(|edit| edit.range.start);
+
+    let mut output = Vec::with_capacity(source.len());
+    let mut position = 0;
+    for edit in edits {
+        output.extend_from_slice(&source[position..edit.range.start]);
+        output.extend_from_slice(edit.replacement.as_bytes());
+        position = edit.range.end;
+    }
+
+    output.extend_from_slice(&source[position..]);
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::apply;
+    use super::TextEdit;
+
+    #[test]
+    fn test_apply() {
+        let source = "<?php $a = 'é'; $b = 2;";
+        let edits = [
+            TextEdit {
+                range: 22..23,
+                replacement: "3".to_string(),
+            },
+            TextEdit {
+                range: 6..8,
+                replacement: "$c".to_string(),
+            },
+        ];
+
+        assert_eq!(apply(source, &edits), "<?php $c = 'é'; $b = 3;".as_bytes());
+    }
+}
@@ -0,0 +1,631 @@
+//! Conversions between `switch` statements and `match` expressions.
+//!
+//! A `switch` compares its subject with `==` and runs into the next case without a `break`,
+//! while a `match` compares with `===`, evaluates a single expression per arm, and throws an
+//! `UnhandledMatchError` when no arm matches. The conversions are only done when they keep
+//! the behaviour of the code:
+//!
+//! - A `default` case or arm is required.
+//! - Every case ends with a `break`, a `return` or a `throw`.
+//! - Every case returns a value, assigns the same variable, or evaluates a single expression.
+//! - The subject is an integer, float or string literal, or a cast to one of these types, and
+//!   the conditions are literals of the same type. Otherwise `==` and `===` may compare
+//!   differently, e.g. `'1'` matches `case 1:` but not `1 =>`, and constants may hold `null`
+//!   or booleans. Numeric and empty strings are rejected, as they compare loosely with numbers.
+//!
+//! ```
+//! use php_parser_rs::parser::ast::Statement;
+//! use php_parser_rs::printer::print_statement;
+//! use php_parser_rs::printer::PrinterOptions;
+//! use php_parser_rs::transforms::switch_match::switch_to_match;
+//!
+//! let program = php_parser_rs::parse(
+//!     "<?php switch ((int) $a) { case 1: case 2: return 'low'; default: return 'high'; }",
+//! )
+//! .unwrap();
+//!
+//! let statement = match &program[1] {
+//!     Statement::Switch(switch) => switch_to_match(switch).unwrap(),
+//!     _ => unreachable!(),
+//! };
+//!
+//! assert_eq!(
+//!     print_statement(&statement, &PrinterOptions::new()),
+//!     "return match ((int) $a) {\n    1, 2 => 'low',\n    default => 'high',\n};",
+//! );
+//! ```
+
+use std::fmt::Display;
+
+use crate::lexer::token::Span;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::literals::LiteralString;
+use crate::parser::ast::loops::BreakStatement;
+use crate::parser::ast::operators::ArithmeticOperation;
+use crate::parser::ast::operators::AssignmentOperation;
+use crate::parser::ast::Case;
+use crate::parser::ast::CastKind;
+use crate::parser::ast::DefaultMatchArm;
+use crate::parser::ast::Ending;
+use crate::parser::ast::Expression;
+use crate::parser::ast::ExpressionStatement;
+use crate::parser::ast::MatchArm;
+use crate::parser::ast::MatchExpression;
+use crate::parser::ast::ReturnStatement;
+use crate::parser::ast::Statement;
+use crate::parser::ast::SwitchStatement;
+use crate::printer::print_expression;
+use crate::printer::PrinterOptions;
+
+/// The reason a `switch` statement or a `match` expression can't be converted.
+///
+/// Cases and arms are referred to by their index in the `switch` or the `match`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum NotConvertible {
+    // The statement isn't a `match` returned, assigned to a variable, or used as a statement.
+    UnsupportedStatement,
+    // Without a `default`, an unhandled value throws with a `match` but not with a `switch`.
+    MissingDefault,
+    // The case runs into the next one, e.g. `case 1: foo(); case 2: bar(); break;`.
+    Fallthrough(usize),
+    // The case isn't a single `return`, `throw`, assignment or expression.
+    UnsupportedBody(usize),
+    // The case doesn't return, or doesn't assign the same variable, like the previous cases.
+    InconsistentBody(usize),
+    // The condition may compare differently with `==` and `===` to the subject, e.g. `null`,
+    // `'1'`, or any condition when the type of the subject isn't known.
+    LooseComparison(usize),
+}
+
+impl Display for NotConvertible {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "not convertible because ")?;
+
+        match self {
+            Self::UnsupportedStatement => write!(
+                f,
+                "the statement is not a returned, assigned or standalone `match` expression"
+            ),
+            Self::MissingDefault => write!(f, "there is no `default`"),
+            Self::Fallthrough(index) => write!(f, "case {} falls through", index),
+            Self::UnsupportedBody(index) => write!(
+                f,
+                "case {} is not a single `return`, `throw`, assignment or expression",
+                index
+            ),
+            Self::InconsistentBody(index) => write!(
+                f,
+                "case {} does not return or assign the same variable as the previous cases",
+                index
+            ),
+            Self::LooseComparison(index) => write!(
+                f,
+                "the condition of case {} may compare differently with `==` and `===`",
+                index
+            ),
+        }
+    }
+}
+
+/// What a case does with the value of its single expression.
+enum Body {
+    Return(Span, Expression),
+    Assign(Expression, Span, Expression),
+    Throw(Expression),
+    Expression(Expression),
+}
+
+/// How the value of a `match` expression is used.
+enum Form {
+    Return(Span),
+    Assign(Expression, Span),
+    Expression,
+}
+
+/// The types of subjects and conditions compared the same with `==` and `===`.
+#[derive(PartialEq, Clone, Copy)]
+enum Kind {
+    Integer,
+    Float,
+    String,
+}
+
+/// Convert a `switch` statement into a `match` expression, which is returned, assigned or
+/// evaluated like the bodies of the cases.
+pub fn switch_to_match(switch: &SwitchStatement) -> Result<Statement, NotConvertible> {
+    if switch.cases.iter().all(|case| case.condition.is_some()) {
+        return Err(NotConvertible::MissingDefault);
+    }
+
+    comparisons(
+        &switch.condition,
+        switch
+            .cases
+            .iter()
+            .enumerate()
+            .filter_map(|(index, case)| case.condition.as_ref().map(|c| (index, c))),
+    )?;
+
+    // consecutive cases without a body share the body of the next case.
+    let last = switch.cases.len() - 1;
+    let mut conditions = vec![];
+    let mut default = false;
+    let mut groups = vec![];
+    for (index, case) in switch.cases.iter().enumerate() {
+        match &case.condition {
+            Some(condition) => conditions.push(condition.clone()),
+            None => default = true,
+        }
+
+        if case.body.is_empty() {
+            if index == last {
+                return Err(NotConvertible::UnsupportedBody(index));
+            }
+
+            continue;
+        }
+
+        let body = body(&case.body, index, index == last)?;
+        groups.push((
+            std::mem::take(&mut conditions),
+            std::mem::replace(&mut default, false),
+            body,
+            index,
+        ));
+    }
+
+    let mut form = None;
+    for (_, _, body, index) in &groups {
+        match (&form, body) {
+            (_, Body::Throw(_)) => {}
+            (None, Body::Return(r#return, _)) => form = Some(Form::Return(*r#return)),
+            (None, Body::Assign(left, equals, _)) => {
+                form = Some(Form::Assign(left.clone(), *equals))
+            }
+            (None, Body::Expression(_)) => form = Some(Form::Expression),
+            (Some(Form::Return(_)), Body::Return(..)) => {}
+            (Some(Form::Expression), Body::Expression(_)) => {}
+            (Some(Form::Assign(variable, _)), Body::Assign(left, _, _)) if same(variable, left) => {
+            }
+            _ => return Err(NotConvertible::InconsistentBody(*index)),
+        }
+    }
+
+    let mut arms = vec![];
+    let mut default = None;
+    for (conditions, is_default, body, _) in groups {
+        let body = match body {
+            Body::Return(_, value) | Body::Assign(_, _, value) => value,
+            Body::Throw(value) | Body::Expression(value) => value,
+        };
+
+        if is_default {
+            default = Some(Box::new(DefaultMatchArm {
+                keyword: switch.switch,
+                double_arrow: switch.switch,
+                body,
+            }));
+        } else {
+            arms.push(MatchArm {
+                conditions,
+                arrow: switch.switch,
+                body,
+            });
+        }
+    }
+
+    let r#match = Expression::Match(Box::new(MatchExpression {
+        keyword: switch.switch,
+        left_parenthesis: switch.left_parenthesis,
+        condition: switch.condition.clone(),
+        right_parenthesis: switch.right_parenthesis,
+        left_brace: switch.right_parenthesis,
+        default,
+        arms,
+        right_brace: switch.right_parenthesis,
+    }));
+    let ending = Ending::Semicolon(switch.right_parenthesis);
+
+    Ok(match form.unwrap_or(Form::Expression) {
+        Form::Return(r#return) => Statement::Return(Box::new(ReturnStatement {
+            r#return,
+            value: Some(r#match),
+            ending,
+        })),
+        Form::Assign(left, equals) => Statement::Expression(ExpressionStatement {
+            expression: Expression::AssignmentOperation(AssignmentOperation::Assign {
+                left: Box::new(left),
+                equals,
+                right: Box::new(r#match),
+            }),
+            ending,
+        }),
+        Form::Expression => Statement::Expression(ExpressionStatement {
+            expression: r#match,
+            ending,
+        }),
+    })
+}
+
+/// Convert a statement returning, assigning or evaluating a `match` expression into a
+/// `switch` statement.
+pub fn match_to_switch(statement: &Statement) -> Result<Statement, NotConvertible> {
+    let (form, r#match) = match statement {
+        Statement::Return(statement) => match &statement.value {
+            Some(value @ Expression::Match { .. }) => (Form::Return(statement.r#return), value),
+            _ => return Err(NotConvertible::UnsupportedStatement),
+        },
+        Statement::Expression(ExpressionStatement {
+            expression:
+                Expression::AssignmentOperation(AssignmentOperation::Assign {
+                    left,
+                    equals,
+                    right,
+                }),
+            ..
+        }) if matches!(right.as_ref(), Expression::Match { .. }) => {
+            (Form::Assign(left.as_ref().clone(), *equals), right.as_ref())
+        }
+        Statement::Expression(ExpressionStatement {
+            expression: value @ Expression::Match { .. },
+            ..
+        }) => (Form::Expression, value),
+        _ => return Err(NotConvertible::UnsupportedStatement),
+    };
+
+    let (keyword, left_parenthesis, condition, right_parenthesis, default, arms) = match r#match {
+        Expression::Match(r#match) => (
+            r#match.keyword,
+            r#match.left_parenthesis,
+            &r#match.condition,
+            r#match.right_parenthesis,
+            &r#match.default,
+            &r#match.arms,
+        ),
+        _ => unreachable!(),
+    };
+
+    let default = match default {
+        Some(default) => default,
+        None => return Err(NotConvertible::MissingDefault),
+    };
+
+    comparisons(
+        condition,
+        arms.iter().enumerate().flat_map(|(index, arm)| {
+            arm.conditions
+                .iter()
+                .map(move |condition| (index, condition))
+        }),
+    )?;
+
+    let mut cases = vec![];
+    for arm in arms {
+        let (last, conditions) = match arm.conditions.split_last() {
+            Some(split) => split,
+            None => continue,
+        };
+
+        for condition in conditions {
+            cases.push(Case {
+                condition: Some(condition.clone()),
+                body: vec![],
+            });
+        }
+
+        cases.push(Case {
+            condition: Some(last.clone()),
+            body: block(&form, &arm.body, keyword),
+        });
+    }
+
+    cases.push(Case {
+        condition: None,
+        body: block(&form, &default.body, default.keyword),
+    });
+
+    Ok(Statement::Switch(Box::new(SwitchStatement {
+        switch: keyword,
+        left_parenthesis,
+        condition: condition.clone(),
+        right_parenthesis,
+        cases,
+    })))
+}
+
+/// Classify the body of a case, ignoring its trailing `break`.
+fn body(block: &[Statement], index: usize, last: bool) -> Result<Body, NotConvertible> {
+    let (statement, terminated) = match block {
+        [statement] => (statement, false),
+        [statement, Statement::Break(BreakStatement { level: None, .. })] => (statement, true),
+        _ => {
+            return Err(match block.last() {
+                Some(Statement::Break(_) | Statement::Return(_)) => {
+                    NotConvertible::UnsupportedBody(index)
+                }
+                _ if !last => NotConvertible::Fallthrough(index),
+                _ => NotConvertible::UnsupportedBody(index),
+            })
+        }
+    };
+
+    if let Statement::Return(statement) = statement {
+        if let Some(value) = &statement.value {
+            return Ok(Body::Return(statement.r#return, value.clone()));
+        }
+    }
+
+    let expression = match statement {
+        Statement::Expression(ExpressionStatement {
+            expression: expression @ Expression::Throw { .. },
+            ..
+        }) => return Ok(Body::Throw(expression.clone())),
+        Statement::Expression(ExpressionStatement { expression, .. }) => expression,
+        _ if !terminated && !last => return Err(NotConvertible::Fallthrough(index)),
+        _ => return Err(NotConvertible::UnsupportedBody(index)),
+    };
+
+    if !terminated && !last {
+        return Err(NotConvertible::Fallthrough(index));
+    }
+
+    Ok(match expression {
+        Expression::AssignmentOperation(AssignmentOperation::Assign {
+            left,
+            equals,
+            right,
+        }) => Body::Assign(left.as_ref().clone(), *equals, right.as_ref().clone()),
+        expression => Body::Expression(expression.clone()),
+    })
+}
+
+/// Build the body of a case from the body of an arm.
+fn block(form: &Form, value: &Expression, span: Span) -> Vec<Statement> {
+    let ending = Ending::Semicolon(span);
+    let r#break = Statement::Break(BreakStatement {
+        r#break: span,
+        level: None,
+        ending: ending.clone(),
+    });
+
+    if matches!(value, Expression::Throw { .. }) {
+        return vec![Statement::Expression(ExpressionStatement {
+            expression: value.clone(),
+            ending,
+        })];
+    }
+
+    match form {
+        Form::Return(r#return) => vec![Statement::Return(Box::new(ReturnStatement {
+            r#return: *r#return,
+            value: Some(value.clone()),
+            ending,
+        }))],
+        Form::Assign(left, equals) => vec![
+            Statement::Expression(ExpressionStatement {
+                expression: Expression::AssignmentOperation(AssignmentOperation::Assign {
+                    left: Box::new(left.clone()),
+                    equals: *equals,
+                    right: Box::new(value.clone()),
+                }),
+                ending,
+            }),
+            r#break,
+        ],
+        Form::Expression => vec![
+            Statement::Expression(ExpressionStatement {
+                expression: value.clone(),
+                ending,
+            }),
+            r#break,
+        ],
+    }
+}
+
+/// Check that the conditions compare the same with `==` and `===` to the subject, i.e. that
+/// they are literals of the type the subject is known to have.
+fn comparisons<'a>(
+    subject: &Expression,
+    conditions: impl Iterator<Item = (usize, &'a Expression)>,
+) -> Result<(), NotConvertible> {
+    let subject = match subject {
+        Expression::Cast { kind: cast, .. } => match cast {
+            CastKind::Int => Some(Kind::Integer),
+            CastKind::Float => Some(Kind::Float),
+            CastKind::String => Some(Kind::String),
+            _ => None,
+        },
+        subject => kind(subject),
+    };
+
+    for (index, condition) in conditions {
+        if subject.is_none() || kind(condition) != subject {
+            return Err(NotConvertible::LooseComparison(index));
+        }
+    }
+
+    Ok(())
+}
+
+fn kind(condition: &Expression) -> Option<Kind> {
+    match condition {
+        Expression::Literal(Literal::Integer(_)) => Some(Kind::Integer),
+        Expression::Literal(Literal::Float(_)) => Some(Kind::Float),
+        Expression::Literal(Literal::String(string)) if !is_numeric(string) => Some(Kind::String),
+        Expression::ArithmeticOperation(ArithmeticOperation::Negative { right, .. }) => {
+            match kind(right) {
+                Some(kind @ (Kind::Integer | Kind::Float)) => Some(kind),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Whether a string literal is empty or numeric, which compare loosely with numbers.
+fn is_numeric(string: &LiteralString) -> bool {
+    if string.is_deferred() {
+        return true;
+    }
+
+    let bytes = &string.value.bytes;
+    let value = match bytes.len() {
+        0..=1 => &bytes[..],
+        length => &bytes[1..length - 1],
+    };
+
+    let value = String::from_utf8_lossy(value);
+    let value = value.trim();
+
+    value.is_empty() || value.parse::<f64>().is_ok()
+}
+
+fn same(left: &Expression, right: &Expression) -> bool {
+    let options = PrinterOptions::new();
+
+    print_expression(left, &options) == print_expression(right, &options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::match_to_switch;
+    use super::switch_to_match;
+    use super::NotConvertible;
+    use crate::parser::ast::Statement;
+    use crate::printer::print_statement;
+    use crate::printer::PrinterOptions;
+
+    fn statement(code: &str) -> Statement {
+        let mut program = crate::parse(&format!("<?php {}", code)).unwrap();
+
+        program.remove(1)
+    }
+
+    fn to_match(code: &str) -> Result<String, NotConvertible> {
+        match statement(code) {
+            Statement::Switch(switch) => switch_to_match(&switch)
+                .map(|statement| print_statement(&statement, &PrinterOptions::new())),
+            statement => panic!("expected a switch statement, got {:?}", statement),
+        }
+    }
+
+    fn to_switch(code: &str) -> Result<String, NotConvertible> {
+        match_to_switch(&statement(code))
+            .map(|statement| print_statement(&statement, &PrinterOptions::new()))
+    }
+
+    #[test]
+    fn test_switch_to_match() {
+        assert_eq!(
+            to_match(
+                "switch ((int) $a) { case 1: $b = 'one'; break; case 2: case 3: $b = 'more'; break; default: throw new Exception(); }"
+            ),
+            Ok("$b = match ((int) $a) {\n    1 => 'one',\n    2, 3 => 'more',\n    default => throw new Exception(),\n};".to_string())
+        );
+
+        assert_eq!(
+            to_match("switch (-1) { case -1: foo(); break; default: bar(); }"),
+            Ok("match (-1) {\n    -1 => foo(),\n    default => bar(),\n};".to_string())
+        );
+
+        assert_eq!(
+            to_match("switch ((string) $a) { case 'a': return -1; default: return 1; }"),
+            Ok("return match ((string) $a) {\n    'a' => -1,\n    default => 1,\n};".to_string())
+        );
+    }
+
+    #[test]
+    fn test_match_to_switch() {
+        assert_eq!(
+            to_switch("$b = match ((int) $a) { 1, 2 => 'low', default => throw new Exception() };"),
+            Ok("switch ((int) $a) {\n    case 1:\n    case 2:\n        $b = 'low';\n        break;\n    default:\n        throw new Exception();\n}".to_string())
+        );
+
+        assert_eq!(
+            to_switch("return match ((string) $a) { 'a' => 1, default => 2 };"),
+            Ok("switch ((string) $a) {\n    case 'a':\n        return 1;\n    default:\n        return 2;\n}".to_string())
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let code = "switch ((int) $a) {\n    case 1:\n        foo();\n        break;\n    default:\n        bar();\n        break;\n}";
+
+        let r#match = match statement(code) {
+            Statement::Switch(switch) => switch_to_match(&switch).unwrap(),
+            _ => unreachable!(),
+        };
+
+        let switch = match_to_switch(&r#match).unwrap();
+
+        assert_eq!(print_statement(&switch, &PrinterOptions::new()), code);
+    }
+
+    #[test]
+    fn test_not_convertible() {
+        assert_eq!(
+            to_match("switch ($a) { case 1: return 1; }"),
+            Err(NotConvertible::MissingDefault)
+        );
+        assert_eq!(
+            to_match("switch ((int) $a) { case 1: foo(); case 2: bar(); break; default: baz(); }"),
+            Err(NotConvertible::Fallthrough(0))
+        );
+        assert_eq!(
+            to_match("switch ((int) $a) { case 1: foo(); bar(); break; default: baz(); }"),
+            Err(NotConvertible::UnsupportedBody(0))
+        );
+        assert_eq!(
+            to_match("switch ((int) $a) { case 1: $b = 1; break; default: $c = 2; }"),
+            Err(NotConvertible::InconsistentBody(1))
+        );
+        assert_eq!(
+            to_match("switch ((int) $a) { case 1: return 1; default: foo(); }"),
+            Err(NotConvertible::InconsistentBody(1))
+        );
+        assert_eq!(
+            to_match(
+                "switch ((int) $a) { case 1: return 1; case '1': return 2; default: return 3; }"
+            ),
+            Err(NotConvertible::LooseComparison(1))
+        );
+        assert_eq!(
+            to_match("switch ((string) $a) { case 'a': return 1; case 'b': return 2; case null: return 3; default: return 4; }"),
+            Err(NotConvertible::LooseComparison(2))
+        );
+        assert_eq!(
+            to_match("switch (true) { case $a > 1: return 1; default: return 2; }"),
+            Err(NotConvertible::LooseComparison(0))
+        );
+        // The type of the subject isn't known.
+        assert_eq!(
+            to_match("switch ($a) { case 1: return 1; default: return 2; }"),
+            Err(NotConvertible::LooseComparison(0))
+        );
+        // Constants may hold `null` or booleans.
+        assert_eq!(
+            to_match(
+                "switch ((int) $a) { case 1: return 1; case Foo::A: return 2; default: return 3; }"
+            ),
+            Err(NotConvertible::LooseComparison(1))
+        );
+
+        assert_eq!(
+            to_switch("return match ($a) { 1 => 2 };"),
+            Err(NotConvertible::MissingDefault)
+        );
+        assert_eq!(
+            to_switch("foo(match ($a) { default => 2 });"),
+            Err(NotConvertible::UnsupportedStatement)
+        );
+        assert_eq!(
+            to_switch("return match ((int) $a) { 1 => 1, '2' => 2, default => 3 };"),
+            Err(NotConvertible::LooseComparison(1))
+        );
+
+        assert_eq!(
+            NotConvertible::Fallthrough(2).to_string(),
+            "not convertible because case 2 falls through"
+        );
+    }
+}
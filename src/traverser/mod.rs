@@ -1,16 +1,19 @@
 use std::fmt::Debug;
 
 use crate::node::Node;
+use crate::parser::limits::grow_stack;
 
 pub trait Visitor<E: Debug> {
     fn visit_node(&mut self, node: &mut dyn Node) -> Result<(), E> {
         self.visit(node)?;
 
-        for child in node.children() {
-            self.visit_node(child)?;
-        }
+        grow_stack(|| {
+            for child in node.children() {
+                self.visit_node(child)?;
+            }
 
-        Ok(())
+            Ok(())
+        })
     }
 
     fn visit(&mut self, node: &mut dyn Node) -> Result<(), E>;
@@ -1 +1 @@
-<?php continue;
\ No newline at end of file
+<?php while (true) { continue; }
\ No newline at end of file
@@ -1 +1 @@
-<?php continue 2;
\ No newline at end of file
+<?php while (true) { while (true) { continue 2; } }
\ No newline at end of file
@@ -0,0 +1,9 @@
+<?php
+
+class a {
+    public function b() {
+        return static function () {
+            return $this->c;
+        };
+    }
+}
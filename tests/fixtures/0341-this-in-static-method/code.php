@@ -0,0 +1,7 @@
+<?php
+
+class a {
+    public static function b() {
+        return $this->c;
+    }
+}
@@ -0,0 +1,5 @@
+<?php
+
+function foo() {
+    return self::BAR;
+}
@@ -0,0 +1,7 @@
+<?php
+
+class a {
+    public function b() {
+        return parent::b();
+    }
+}
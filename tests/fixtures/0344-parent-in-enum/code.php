@@ -0,0 +1,7 @@
+<?php
+
+enum a {
+    public function b() {
+        return new parent();
+    }
+}
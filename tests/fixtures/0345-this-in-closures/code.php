@@ -0,0 +1,19 @@
+<?php
+
+$a = function () {
+    return $this->foo;
+};
+
+$b = fn () => $this->bar;
+
+class c {
+    public function d() {
+        return fn () => static::e($this, self::F, parent::class);
+    }
+}
+
+trait g {
+    public function h() {
+        return parent::h();
+    }
+}
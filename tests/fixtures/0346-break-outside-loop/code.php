@@ -0,0 +1,5 @@
+<?php
+
+if ($a) {
+    break;
+}
@@ -0,0 +1,5 @@
+<?php
+
+for ($i = 0; $i < 10; $i++) {
+    break $i;
+}
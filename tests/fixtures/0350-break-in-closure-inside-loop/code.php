@@ -0,0 +1,7 @@
+<?php
+
+while (true) {
+    $a = function () {
+        break;
+    };
+}
@@ -0,0 +1,14 @@
+<?php
+
+do {
+    foreach ($a as $b):
+        for (;;) {
+            switch ($b) {
+                case 1:
+                    break 4;
+                default:
+                    continue (3);
+            }
+        }
+    endforeach;
+} while (true);
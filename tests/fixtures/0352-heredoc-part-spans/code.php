@@ -0,0 +1,13 @@
+<?php
+
+$a = <<<SQL
+    SELECT *
+      FROM {$table}
+
+    WHERE id = $id AND x = "\n"
+    SQL;
+$b = <<<'EOT'
+  foo
+
+    bar
+  EOT;
@@ -0,0 +1,11 @@
+<?php
+
+$fn();
+($fn)();
+'strlen'($a);
+('str' . $x)();
+['Foo', 'bar']();
+$obj->$method();
+$obj->{$method}();
+Foo::$method();
+Foo::{$method}();
@@ -0,0 +1,5 @@
+<?php
+
+$a = &$b;
+$a = &$obj->prop;
+$a =& $b['c'];
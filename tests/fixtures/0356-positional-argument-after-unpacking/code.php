@@ -0,0 +1,3 @@
+<?php
+
+foo(...$args, $bar);
@@ -0,0 +1,8 @@
+<?php
+
+foo(
+    // the first argument
+    $a->b,
+    ...$rest,
+    name: bar(1, 2),
+);
@@ -0,0 +1,5 @@
+<?php
+
+namespace Foo;
+
+declare(strict_types=1);
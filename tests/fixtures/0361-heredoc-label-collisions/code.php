@@ -0,0 +1,17 @@
+<?php
+
+$a = <<<EOT
+EOTX
+  EOT_1 EOT
+foo EOT;
+"quoted" \" \$b
+EOT;
+
+$b = <<<EOT
+EOT;
+
+foo(<<<EOT
+    a
+    EOT, <<<'B'
+    BB
+    B);
@@ -0,0 +1,6 @@
+<?php
+
+"$a $a[0] $a[-1] $a[b] $a[$b] $a->b $a?->b";
+"$a->b->c $a[0][1] $a->b()";
+"${a} ${a[0]} ${$a}";
+"{$a} {$a->b->c} {$a->b()} {$a?->b()[0]} {$a['b']} {$a::$b} {$a::b()} {$a()}";
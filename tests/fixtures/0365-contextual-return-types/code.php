@@ -0,0 +1,20 @@
+<?php
+
+$a = fn(): ?static => null;
+$b = function (): never {
+    throw new Exception();
+};
+$c = static function (): self {
+    return new self();
+};
+$d = static fn(): parent|null => null;
+
+class A extends B {
+    public function a(): ?static {
+        return fn(): static => $this;
+    }
+
+    public static function b(self $b): self|parent {
+        return $b;
+    }
+}
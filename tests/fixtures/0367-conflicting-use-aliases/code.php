@@ -0,0 +1,4 @@
+<?php
+
+use A\Foo;
+use B\Foo;
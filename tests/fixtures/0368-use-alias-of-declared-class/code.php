@@ -0,0 +1,7 @@
+<?php
+
+namespace App;
+
+class Foo {}
+
+use Vendor\Foo;
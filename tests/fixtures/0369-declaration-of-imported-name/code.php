@@ -0,0 +1,7 @@
+<?php
+
+namespace App;
+
+use function Vendor\helper;
+
+function helper() {}
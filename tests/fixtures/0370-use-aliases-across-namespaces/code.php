@@ -0,0 +1,16 @@
+<?php
+
+namespace A {
+    class Foo {}
+
+    use A\Foo;
+    use B\Foo as Bar;
+    use function B\foo;
+    use const B\FOO;
+    use const B\foo;
+}
+
+namespace B {
+    use A\Bar;
+    use C\{Foo, D\Foo as Baz};
+}
@@ -0,0 +1,6 @@
+<?php
+
+class A {
+    private const B = 1;
+    final protected const C = 2;
+}
@@ -0,0 +1,34 @@
+<?php
+
+/**
+ * A user.
+ */
+#[Entity]
+final class User
+{
+    /** The id. */
+    public int $id;
+
+    // legacy
+    var $name;
+}
+
+/** A contract. */
+interface Contract {}
+
+# A trait.
+trait Greets {}
+
+/** A status. */
+enum Status
+{
+    /** Active. */
+    case Active;
+}
+
+/* A suit. */
+enum Suit: string
+{
+    // Hearts.
+    case Hearts = 'H';
+}
@@ -0,0 +1,5 @@
+<?php
+
+$foo = new readonly class(1) {
+    public function __construct(public int $bar) {}
+};
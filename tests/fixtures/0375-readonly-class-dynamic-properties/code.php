@@ -0,0 +1,8 @@
+<?php
+
+namespace App;
+
+use AllowDynamicProperties as Dynamic;
+
+#[Dynamic]
+readonly class Foo {}
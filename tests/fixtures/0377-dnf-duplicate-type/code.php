@@ -0,0 +1,3 @@
+<?php
+
+function foo((A&B)|C|(B&A) $a) {}
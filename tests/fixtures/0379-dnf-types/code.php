@@ -0,0 +1,15 @@
+<?php
+
+class Foo
+{
+    public (A&B)|null $a = null;
+
+    public function __construct(private (A&B)|(C&D) $b)
+    {
+    }
+
+    public function bar(): (A&B)|static
+    {
+        return $this;
+    }
+}
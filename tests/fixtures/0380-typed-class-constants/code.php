@@ -0,0 +1,22 @@
+<?php
+
+class Foo
+{
+    const int A = 1;
+
+    final public const ?string B = null, C = 'c';
+
+    const E = 1;
+}
+
+interface Bar
+{
+    const array A = [];
+}
+
+enum Baz: int
+{
+    const self D = self::E;
+
+    case E = 1;
+}
@@ -0,0 +1,6 @@
+<?php
+
+class Foo
+{
+    const never A = 1;
+}
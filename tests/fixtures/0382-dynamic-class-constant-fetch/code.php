@@ -0,0 +1,4 @@
+<?php
+
+$value = Foo::{$name};
+$value = Bar::{strtoupper($name) . '_SUFFIX'}::{$other};
@@ -0,0 +1,28 @@
+<?php
+
+class User
+{
+    public string $fullName {
+        get => $this->first . ' ' . $this->last;
+    }
+
+    public string $email = '' {
+        #[Pure]
+        final get {
+            return strtolower($this->email);
+        }
+        set(string $value) {
+            $this->email = trim($value);
+        }
+    }
+
+    private array $items = [] {
+        &get => $this->items;
+    }
+
+    final public int $count = 0;
+
+    public function __construct(public string $first, public string $last)
+    {
+    }
+}
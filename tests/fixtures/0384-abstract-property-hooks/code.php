@@ -0,0 +1,17 @@
+<?php
+
+interface HasName
+{
+    public string $name { get; }
+
+    public string $label { get; set; }
+}
+
+abstract class Named implements HasName
+{
+    abstract public string $name { get; }
+
+    public string $label {
+        set => ucfirst($value);
+    }
+}
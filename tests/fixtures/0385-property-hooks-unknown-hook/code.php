@@ -0,0 +1,10 @@
+<?php
+
+class Foo
+{
+    public int $bar {
+        get => 1;
+        get => 2;
+        unset => 3;
+    }
+}
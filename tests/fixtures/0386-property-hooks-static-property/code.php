@@ -0,0 +1,8 @@
+<?php
+
+class Foo
+{
+    public static int $bar {
+        get => 1;
+    }
+}
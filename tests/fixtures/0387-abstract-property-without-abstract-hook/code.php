@@ -0,0 +1,6 @@
+<?php
+
+abstract class Foo
+{
+    abstract public int $bar;
+}
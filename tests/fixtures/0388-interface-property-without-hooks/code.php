@@ -0,0 +1,6 @@
+<?php
+
+interface Foo
+{
+    public int $bar;
+}
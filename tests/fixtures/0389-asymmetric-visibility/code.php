@@ -0,0 +1,18 @@
+<?php
+
+class Book
+{
+    public private(set) string $title;
+
+    protected(set) int $pages = 0;
+
+    public readonly PRIVATE(SET) ?string $isbn;
+
+    public function __construct(
+        public protected(set) string $author,
+        private(set) array $tags = [],
+    ) {
+    }
+
+    public function private() {}
+}
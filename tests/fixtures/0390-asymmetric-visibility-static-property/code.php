@@ -0,0 +1,6 @@
+<?php
+
+class Book
+{
+    public static private(set) int $count = 0;
+}
@@ -0,0 +1,8 @@
+<?php
+
+class Book
+{
+    public function __construct(protected(set) $title)
+    {
+    }
+}
@@ -0,0 +1,10 @@
+<?php
+
+new Foo()->bar();
+new Foo()?->baz;
+new Foo()::BAR;
+new Foo()['qux'];
+new Foo()();
+new class {}->bar();
+new $factory->class['name']();
+new Foo::$class;
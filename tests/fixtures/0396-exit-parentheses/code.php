@@ -0,0 +1,8 @@
+<?php
+
+exit;
+exit();
+exit(1);
+exit (1 + 2);
+die();
+die("message");
@@ -0,0 +1,7 @@
+<?php
+
+function create() {
+    parent::__construct();
+
+    return static::create();
+}
@@ -0,0 +1,3 @@
+<?php
+
+$result = $input |> trim(...) |> strtoupper(...);
@@ -0,0 +1,2 @@
+<h1><?= $this->title ?></h1>
+<?php echo $this->e(self::NAME); ?>
@@ -1,8 +1,15 @@
 use std::env;
 use std::fs::read_dir;
 use std::io;
+use std::io::Write;
 use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
 
+use php_parser_rs::parser::ast::Program;
+use php_parser_rs::printer::print_preserving;
+use php_parser_rs::printer::print_program;
+use php_parser_rs::printer::PrinterOptions;
 use pretty_assertions::assert_str_eq;
 
 struct TestFixture {
@@ -99,6 +106,97 @@ fn run_test(test_fixture: &TestFixture) -> io::Result<()> {
             "ast mismatch for fixture `{}`",
             test_fixture.fixture
         );
+
+        // The whole tree must serialize, e.g. to be consumed as JSON, and read back as is.
+        let json = serde_json::to_string(&ast).unwrap_or_else(|error| {
+            panic!(
+                "ast of fixture `{}` does not serialize: {}",
+                test_fixture.fixture, error
+            )
+        });
+        let deserialized: Program = serde_json::from_str(&json).unwrap_or_else(|error| {
+            panic!(
+                "ast of fixture `{}` does not deserialize: {}",
+                test_fixture.fixture, error
+            )
+        });
+        assert_eq!(
+            ast, deserialized,
+            "serde round-trip mismatch for fixture `{}`",
+            test_fixture.fixture
+        );
+
+        // The whole tree must also survive the binary format, e.g. to be cached.
+        #[cfg(feature = "binary")]
+        {
+            let bytes = php_parser_rs::schema::to_bytes(&ast).unwrap();
+            assert_eq!(
+                php_parser_rs::schema::from_bytes(&bytes).unwrap(),
+                ast,
+                "binary round-trip mismatch for fixture `{}`",
+                test_fixture.fixture
+            );
+        }
+
+        // The printed code must parse, and print back to itself.
+        let options = PrinterOptions::new();
+        let printed = print_program(&ast, &options);
+        let reparsed = php_parser_rs::parse(&printed).unwrap_or_else(|error| {
+            panic!(
+                "printed code of fixture `{}` does not parse: {}\n{}",
+                test_fixture.fixture, error, printed
+            )
+        });
+        assert_str_eq!(
+            printed,
+            print_program(&reparsed, &options),
+            "printer round-trip mismatch for fixture `{}`",
+            test_fixture.fixture
+        );
+
+        // Every statement of a modified program is printed within the original layout.
+        let preserved = print_preserving(&code, &ast, &reparsed, &options);
+        php_parser_rs::parse(&preserved).unwrap_or_else(|error| {
+            panic!(
+                "preserved code of fixture `{}` does not parse: {}\n{}",
+                test_fixture.fixture, error, preserved
+            )
+        });
+
+        // Opt-in, the printed code must also be valid PHP to the local interpreter, provided
+        // the fixture itself is.
+        if let Some(php) = php_lint() {
+            if lint(&php, &code).is_ok() {
+                for (printer, output) in [("pretty", &printed), ("preserving", &preserved)] {
+                    if let Err(message) = lint(&php, output) {
+                        panic!(
+                            "{} printer output of fixture `{}` is not valid PHP: {}\n{}",
+                            printer,
+                            test_fixture.fixture,
+                            message.trim(),
+                            output
+                        );
+                    }
+                }
+            }
+        }
+
+        // An unmodified program is printed as is.
+        assert_str_eq!(
+            code,
+            print_preserving(&code, &ast, &ast, &options),
+            "format-preserving printer mismatch for fixture `{}`",
+            test_fixture.fixture
+        );
+
+        // The lossless syntax tree reproduces the code byte for byte.
+        let tree = php_parser_rs::parser::cst::parse(&code).unwrap();
+        assert_str_eq!(
+            code,
+            tree.to_string(),
+            "lossless syntax tree mismatch for fixture `{}`",
+            test_fixture.fixture
+        );
     }
 
     if !expected.error.is_empty() {
@@ -116,3 +214,39 @@ fn run_test(test_fixture: &TestFixture) -> io::Result<()> {
 
     Ok(())
 }
+
+/// The PHP binary to lint printed code with, e.g. `PHP_PARSER_LINT=php cargo test`.
+fn php_lint() -> Option<String> {
+    env::var("PHP_PARSER_LINT")
+        .ok()
+        .filter(|php| !php.is_empty())
+}
+
+/// Check the syntax of the given code with `php -l`, returning its output on failure.
+fn lint(php: &str, code: &str) -> Result<(), String> {
+    let mut child = Command::new(php)
+        .arg("-l")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|error| panic!("failed to run `{} -l`: {}", php, error));
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(code.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    }
+}